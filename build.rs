@@ -0,0 +1,23 @@
+use std::{env, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(diagnostic_namespace, diagnostic_do_not_recommend)");
+
+    // `#[diagnostic::on_unimplemented]` is stable since Rust 1.78, and
+    // `#[diagnostic::do_not_recommend]` since Rust 1.85.
+    let minor = rustc_minor_version().unwrap_or_default();
+    if minor >= 78 {
+        println!("cargo:rustc-cfg=diagnostic_namespace");
+    }
+    if minor >= 85 {
+        println!("cargo:rustc-cfg=diagnostic_do_not_recommend");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    version.split('.').nth(1)?.parse().ok()
+}
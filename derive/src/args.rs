@@ -56,6 +56,7 @@ pub enum Visible {
     None,
     HiddenAlways,
     FnName(Path),
+    Profiles(Vec<String>),
 }
 
 impl FromMeta for Visible {
@@ -67,6 +68,71 @@ impl FromMeta for Visible {
             _ => Err(darling::Error::unexpected_lit_type(value)),
         }
     }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut profiles = Vec::new();
+        for item in items {
+            if let NestedMeta::Lit(Lit::Str(profile)) = item {
+                profiles.push(profile.value());
+            } else {
+                return Err(darling::Error::custom("Invalid visibility profile list"));
+            }
+        }
+        Ok(Visible::Profiles(profiles))
+    }
+}
+
+pub struct DurationLit(pub std::time::Duration);
+
+impl FromMeta for DurationLit {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        let digits = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(digits);
+        let number = number.parse::<u64>().ok();
+        let duration = match (number, unit.trim()) {
+            (Some(n), "ms") => Some(std::time::Duration::from_millis(n)),
+            (Some(n), "s") => Some(std::time::Duration::from_secs(n)),
+            (Some(n), "m") => Some(std::time::Duration::from_secs(n * 60)),
+            (Some(n), "h") => Some(std::time::Duration::from_secs(n * 3600)),
+            _ => None,
+        };
+        duration.map(DurationLit).ok_or_else(|| {
+            darling::Error::custom(format!(
+                "Invalid duration \"{}\", expected a duration such as \"500ms\" or \"2s\"",
+                value
+            ))
+        })
+    }
+}
+
+#[derive(FromMeta)]
+pub struct Retry {
+    pub times: usize,
+    #[darling(default)]
+    pub backoff: Option<DurationLit>,
+    #[darling(default)]
+    pub jitter: bool,
+    #[darling(default)]
+    pub retry_if: Option<Path>,
+}
+
+#[derive(Debug, Copy, Clone, Default, FromMeta, PartialEq, Eq)]
+#[darling(rename_all = "lowercase")]
+pub enum CacheKey {
+    #[default]
+    Args,
+    None,
+}
+
+#[derive(FromMeta)]
+pub struct Cache {
+    pub ttl: DurationLit,
+    #[darling(default)]
+    pub swr: Option<DurationLit>,
+    #[darling(default)]
+    pub key: CacheKey,
 }
 
 pub struct PathList(pub Vec<Path>);
@@ -85,6 +151,40 @@ impl FromMeta for PathList {
     }
 }
 
+#[derive(Default)]
+pub struct InputObjectValidator {
+    pub custom: Option<Expr>,
+    pub validate_input: bool,
+}
+
+#[derive(FromMeta)]
+struct InputObjectValidatorList {
+    #[darling(default)]
+    custom: Option<Expr>,
+    #[darling(default)]
+    validate_input: bool,
+}
+
+impl FromMeta for InputObjectValidator {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(InputObjectValidator {
+            custom: Some(syn::parse_str::<Expr>(value)?),
+            validate_input: false,
+        })
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let InputObjectValidatorList {
+            custom,
+            validate_input,
+        } = InputObjectValidatorList::from_list(items)?;
+        Ok(InputObjectValidator {
+            custom,
+            validate_input,
+        })
+    }
+}
+
 #[derive(FromMeta)]
 pub struct ConcreteType {
     pub name: String,
@@ -291,6 +391,9 @@ pub struct ObjectField {
     pub flatten: bool,
     #[darling(default, multiple, rename = "directive")]
     pub directives: Vec<Expr>,
+    pub timeout: Option<DurationLit>,
+    pub retry: Option<Retry>,
+    pub cache: Option<Cache>,
 }
 
 #[derive(FromMeta, Default, Clone)]
@@ -328,6 +431,8 @@ pub struct Enum {
     pub inaccessible: bool,
     #[darling(default, multiple, rename = "tag")]
     pub tags: Vec<String>,
+    #[darling(default)]
+    pub int_values: bool,
 }
 
 #[derive(FromVariant)]
@@ -347,6 +452,10 @@ pub struct EnumItem {
     pub inaccessible: bool,
     #[darling(default, multiple, rename = "tag")]
     pub tags: Vec<String>,
+    #[darling(default)]
+    pub fallback: bool,
+    #[darling(default)]
+    pub external: Option<String>,
 }
 
 #[derive(FromDeriveInput)]
@@ -372,6 +481,8 @@ pub struct Union {
     // for OneofObject
     #[darling(default)]
     pub input_name: Option<String>,
+    #[darling(default)]
+    pub remote: Option<Type>,
 }
 
 #[derive(FromVariant)]
@@ -448,12 +559,42 @@ pub struct InputObject {
     #[darling(default, multiple, rename = "concrete")]
     pub concretes: Vec<ConcreteType>,
     #[darling(default)]
-    pub validator: Option<Expr>,
+    pub validator: Option<InputObjectValidator>,
     // for SimpleObject
     #[darling(default)]
     pub complex: bool,
 }
 
+#[derive(FromField)]
+#[darling(attributes(patch), forward_attrs(doc))]
+pub struct PatchField {
+    pub ident: Option<Ident>,
+    pub ty: Type,
+    pub vis: Visibility,
+    pub attrs: Vec<Attribute>,
+
+    #[darling(default)]
+    pub name: Option<String>,
+    #[darling(default)]
+    pub skip: bool,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(patch))]
+pub struct Patch {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub vis: Visibility,
+    pub data: Data<Ignored, PatchField>,
+
+    #[darling(default)]
+    pub internal: bool,
+    #[darling(default)]
+    pub name: Option<String>,
+    #[darling(default)]
+    pub rename_fields: Option<RenameRule>,
+}
+
 #[derive(FromVariant)]
 #[darling(attributes(graphql), forward_attrs(doc))]
 pub struct OneofObjectField {
@@ -466,6 +607,8 @@ pub struct OneofObjectField {
     #[darling(default)]
     pub validator: Option<Validators>,
     #[darling(default)]
+    pub process_with: Option<Expr>,
+    #[darling(default)]
     pub visible: Option<Visible>,
     #[darling(default)]
     pub inaccessible: bool,
@@ -587,6 +730,8 @@ pub struct Interface {
     pub inaccessible: bool,
     #[darling(default, multiple, rename = "tag")]
     pub tags: Vec<String>,
+    #[darling(default)]
+    pub remote: Option<Type>,
 }
 
 #[derive(FromMeta, Default)]
@@ -643,11 +788,33 @@ pub struct SubscriptionField {
     pub guard: Option<Expr>,
     pub visible: Option<Visible>,
     pub complexity: Option<Expr>,
+    pub terminate_on_error: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Namespace {
+    Default,
+    Name(String),
+}
+
+impl FromMeta for Namespace {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Namespace::Default)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Namespace::Name(value.to_string()))
+    }
 }
 
 #[derive(FromField)]
+#[darling(attributes(graphql), forward_attrs(doc))]
 pub struct MergedObjectField {
     pub ty: Type,
+    pub attrs: Vec<Attribute>,
+
+    #[darling(default)]
+    pub namespace: Option<Namespace>,
 }
 
 #[derive(FromDeriveInput)]
@@ -833,6 +1000,30 @@ pub struct NewType {
     pub specified_by_url: Option<String>,
 }
 
+#[derive(FromDeriveInput)]
+#[darling(attributes(graphql))]
+pub struct NewTypeId {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub data: Data<Ignored, syn::Type>,
+
+    #[darling(default)]
+    pub internal: bool,
+    #[darling(default)]
+    pub prefix: Option<String>,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(graphql))]
+pub struct ExtendObject {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub data: Data<Ignored, syn::Type>,
+
+    #[darling(default)]
+    pub internal: bool,
+}
+
 #[derive(FromMeta, Default)]
 #[darling(default)]
 pub struct ComplexObject {
@@ -863,6 +1054,9 @@ pub struct ComplexObjectField {
     #[darling(multiple)]
     pub derived: Vec<DerivedField>,
     pub flatten: bool,
+    pub timeout: Option<DurationLit>,
+    pub retry: Option<Retry>,
+    pub cache: Option<Cache>,
 }
 
 #[derive(FromMeta, Default)]
@@ -879,6 +1073,13 @@ pub struct Directive {
     pub locations: Vec<DirectiveLocation>,
 }
 
+#[derive(FromMeta, Default)]
+#[darling(default)]
+pub struct Loader {
+    pub internal: bool,
+    pub name: Option<String>,
+}
+
 #[derive(Debug, Copy, Clone, FromMeta, strum::Display)]
 #[darling(rename_all = "PascalCase")]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
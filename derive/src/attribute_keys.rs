@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use darling::ast::NestedMeta;
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Attribute, Data, DeriveInput, Expr, ExprLit, Fields,
+    Item, Lit, Meta, Token,
+};
+
+/// The source of the darling structs, which is parsed to know the keys they
+/// accept.
+const ARGS_SOURCE: &str = include_str!("args.rs");
+
+thread_local! {
+    static ATTRIBUTE_KEYS: HashMap<String, Vec<String>> = parse_attribute_keys(ARGS_SOURCE);
+}
+
+/// Returns the keys accepted by each darling struct of `source`, by the name
+/// of the struct.
+fn parse_attribute_keys(source: &str) -> HashMap<String, Vec<String>> {
+    let file = match syn::parse_file(source) {
+        Ok(file) => file,
+        Err(_) => return HashMap::new(),
+    };
+
+    let mut keys = HashMap::new();
+    for item in file.items {
+        let item = match item {
+            Item::Struct(item) => item,
+            _ => continue,
+        };
+        let fields = match &item.fields {
+            Fields::Named(fields) => fields,
+            _ => continue,
+        };
+
+        // the fields filled by darling with the input instead of the attribute
+        let derives = derived_traits(&item.attrs);
+        let input_fields: &[&str] = if derives.iter().any(|name| name == "FromDeriveInput") {
+            &["ident", "vis", "generics", "data", "attrs"]
+        } else if derives.iter().any(|name| name == "FromField") {
+            &["ident", "vis", "ty", "attrs"]
+        } else if derives.iter().any(|name| name == "FromVariant") {
+            &["ident", "fields", "discriminant", "attrs"]
+        } else if derives.iter().any(|name| name == "FromMeta") {
+            &[]
+        } else {
+            continue;
+        };
+
+        let struct_keys = fields
+            .named
+            .iter()
+            .filter_map(|field| {
+                let name = field.ident.as_ref()?.to_string();
+                let mut key = Some(name.clone()).filter(|name| !input_fields.contains(&&**name));
+                for meta in darling_metas(&field.attrs) {
+                    match meta {
+                        Meta::Path(path) if path.is_ident("skip") => return None,
+                        Meta::NameValue(meta) if meta.path.is_ident("rename") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(rename),
+                                ..
+                            }) = meta.value
+                            {
+                                key = Some(rename.value());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                key
+            })
+            .collect();
+        keys.insert(item.ident.to_string(), struct_keys);
+    }
+    keys
+}
+
+fn derived_traits(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter_map(|path| {
+            path.segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+        })
+        .collect()
+}
+
+fn darling_metas(attrs: &[Attribute]) -> Vec<Meta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("darling"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect()
+}
+
+/// Returns the keys accepted by the darling struct `T`, or nothing if `T` is
+/// not one, such as `()`.
+fn attribute_keys<T>() -> Vec<String> {
+    let name = std::any::type_name::<T>()
+        .rsplit("::")
+        .next()
+        .unwrap_or_default();
+    ATTRIBUTE_KEYS.with(|keys| keys.get(name).cloned().unwrap_or_default())
+}
+
+/// Checks that the keys of an attribute are accepted by the darling struct
+/// `T`, to list the valid keys in the error of an unknown key.
+pub fn check_keys<T>(nested: &[NestedMeta]) -> darling::Result<()> {
+    let keys = attribute_keys::<T>();
+    if keys.is_empty() {
+        return Ok(());
+    }
+
+    let mut errors = darling::Error::accumulator();
+    for meta in nested {
+        let path = match meta {
+            NestedMeta::Meta(meta) => meta.path(),
+            NestedMeta::Lit(_) => continue,
+        };
+        let key = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        if !keys.contains(&key) {
+            let valid_keys = keys
+                .iter()
+                .map(|key| format!("`{}`", key))
+                .collect::<Vec<_>>()
+                .join(", ");
+            errors.push(
+                darling::Error::custom(format!(
+                    "Unknown key `{}`. The valid keys are {}.",
+                    key, valid_keys
+                ))
+                .with_span(&path.span()),
+            );
+        }
+    }
+    errors.finish()
+}
+
+/// Checks the keys of the `#[graphql(...)]` attributes with the darling
+/// struct `T`.
+pub fn check_graphql_attrs<T>(attrs: &[Attribute]) -> darling::Result<()> {
+    let mut errors = darling::Error::accumulator();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("graphql")) {
+        if let Meta::List(list) = &attr.meta {
+            // the syntax errors are reported by darling
+            if let Ok(nested) = NestedMeta::parse_meta_list(list.tokens.clone()) {
+                errors.handle(check_keys::<T>(&nested));
+            }
+        }
+    }
+    errors.finish()
+}
+
+/// Checks the keys of the `#[graphql(...)]` attributes of a derive input with
+/// the darling struct of the type (`T`) and of its fields or variants (`M`).
+pub fn check_derive_input<T, M>(input: &DeriveInput) -> darling::Result<()> {
+    let mut errors = darling::Error::accumulator();
+    errors.handle(check_graphql_attrs::<T>(&input.attrs));
+    match &input.data {
+        Data::Struct(data) => {
+            for field in &data.fields {
+                errors.handle(check_graphql_attrs::<M>(&field.attrs));
+            }
+        }
+        Data::Enum(data) => {
+            for variant in &data.variants {
+                errors.handle(check_graphql_attrs::<M>(&variant.attrs));
+            }
+        }
+        Data::Union(_) => {}
+    }
+    errors.finish()
+}
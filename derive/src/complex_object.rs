@@ -12,8 +12,9 @@ use crate::{
     args::{self, RenameRuleExt, RenameTarget},
     output_type::OutputType,
     utils::{
-        extract_input_args, gen_deprecation, generate_default, generate_guards, get_cfg_attrs,
-        get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
+        extract_input_args, gen_deprecation, gen_type_info, generate_await_obj,
+        generate_cached_field, generate_default, generate_guards, generate_resolver_future,
+        get_cfg_attrs, get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
         parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
     },
 };
@@ -256,16 +257,17 @@ pub fn generate(
                     })
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
 
-                let visible = visible_fn(visible);
+                let visible = visible_fn(&crate_name, visible);
                 let tags = tags
                     .iter()
                     .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
                     .collect::<Vec<_>>();
+                let type_info = gen_type_info(&crate_name, ty, quote!(InputType));
                 schema_args.push(quote! {
                         args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                             name: ::std::string::ToString::to_string(#name),
                             description: #desc,
-                            ty: <#ty as #crate_name::InputType>::create_type_info(registry),
+                            ty: #type_info,
                             default_value: #schema_default,
                             visible: #visible,
                             inaccessible: #inaccessible,
@@ -290,17 +292,27 @@ pub fn generate(
                     None => Default::default(),
                 };
 
+                let (param_value, redact) = if *secret {
+                    (
+                        quote!(secret_param_value),
+                        Some(quote!(.redact(&#crate_name::InputType::to_value(&#param_ident)))),
+                    )
+                } else {
+                    (quote!(param_value), None)
+                };
                 let validators = validator.clone().unwrap_or_default().create_validators(
                     &crate_name,
+                    &name,
                     quote!(&#ident),
-                    Some(quote!(.map_err(|err| err.into_server_error(__pos)))),
+                    Some(quote!(.map_err(|err| err #redact .into_server_error(__pos)))),
+                    Some(quote!(ctx)),
                 )?;
 
                 let mut non_mut_ident = ident.clone();
                 non_mut_ident.mutability = None;
                 get_params.push(quote! {
                     #[allow(non_snake_case, unused_mut)]
-                    let (__pos, mut #non_mut_ident) = ctx.param_value::<#ty>(#name, #default)?;
+                    let (__pos, mut #non_mut_ident) = ctx.#param_value::<#ty>(#name, #default)?;
                     #process_with
                     #validators
                     #[allow(non_snake_case)]
@@ -319,7 +331,7 @@ pub fn generate(
                 }
             };
             let schema_ty = ty.value_type();
-            let visible = visible_fn(&method_args.visible);
+            let visible = visible_fn(&crate_name, &method_args.visible);
 
             let complexity = if let Some(complexity) = &method_args.complexity {
                 let (variables, expr) = parse_complexity_expr(complexity.clone())?;
@@ -364,6 +376,7 @@ pub fn generate(
                 quote! { ::std::option::Option::None }
             };
 
+            let type_info = gen_type_info(&crate_name, &schema_ty, quote!(OutputType));
             schema_fields.push(quote! {
                 #(#cfg_attrs)*
                 fields.push((#field_name.to_string(), #crate_name::registry::MetaField {
@@ -374,7 +387,7 @@ pub fn generate(
                         #(#schema_args)*
                         args
                     },
-                    ty: <#schema_ty as #crate_name::OutputType>::create_type_info(registry),
+                    ty: #type_info,
                     deprecation: #field_deprecation,
                     cache_control: #cache_control,
                     external: #external,
@@ -422,17 +435,30 @@ pub fn generate(
                 None => None,
             };
 
+            let await_obj = generate_await_obj(&crate_name, &method_args.timeout);
+            let resolver_future = generate_resolver_future(
+                &crate_name,
+                &method_args.retry,
+                quote! {{
+                    #(#get_params)*
+                    #guard
+                    #resolve_obj
+                }},
+            );
+            let resolve_field = generate_cached_field(
+                &crate_name,
+                &method_args.cache,
+                quote! {{
+                    #resolver_future
+                    #await_obj
+                    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                    #crate_name::OutputType::resolve(&obj, &ctx_obj, ctx.item).await.map(::std::option::Option::Some)
+                }},
+            );
             resolvers.push(quote! {
                 #(#cfg_attrs)*
                 if ctx.item.node.name.node == #field_name {
-                    let f = async move {
-                        #(#get_params)*
-                        #guard
-                        #resolve_obj
-                    };
-                    let obj = f.await.map_err(|err| ctx.set_error_path(err))?;
-                    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                    return #crate_name::OutputType::resolve(&obj, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                    #resolve_field
                 }
             });
 
@@ -30,7 +30,7 @@ pub fn generate(
     let desc = get_rustdoc(&item_fn.attrs)?
         .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
         .unwrap_or_else(|| quote!(::std::option::Option::None));
-    let visible = visible_fn(&directive_args.visible);
+    let visible = visible_fn(&crate_name, &directive_args.visible);
     let repeatable = directive_args.repeatable;
 
     let mut get_params = Vec::new();
@@ -85,7 +85,7 @@ pub fn generate(
                 }
             })
             .unwrap_or_else(|| quote! {::std::option::Option::None});
-        let visible = visible_fn(&visible);
+        let visible = visible_fn(&crate_name, &visible);
 
         schema_args.push(quote! {
             args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
@@ -100,10 +100,20 @@ pub fn generate(
             });
         });
 
+        let (param_value, redact) = if secret {
+            (
+                quote!(secret_param_value),
+                Some(quote!(.redact(&#crate_name::InputType::to_value(&#arg_ident)))),
+            )
+        } else {
+            (quote!(param_value), None)
+        };
         let validators = validator.clone().unwrap_or_default().create_validators(
             &crate_name,
+            &name,
             quote!(&#arg_ident),
-            Some(quote!(.map_err(|err| err.into_server_error(__pos)))),
+            Some(quote!(.map_err(|err| err #redact .into_server_error(__pos)))),
+            None,
         )?;
 
         let default = match default {
@@ -113,7 +123,7 @@ pub fn generate(
             None => quote! { ::std::option::Option::None },
         };
         get_params.push(quote! {
-            let (__pos, #arg_ident) = ctx.param_value::<#arg_ty>(#name, #default)?;
+            let (__pos, #arg_ident) = ctx.#param_value::<#arg_ty>(#name, #default)?;
             #validators
         });
 
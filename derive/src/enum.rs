@@ -38,7 +38,9 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
 
     let mut enum_items = Vec::new();
     let mut items = Vec::new();
+    let mut external_items = Vec::new();
     let mut schema_enum_items = Vec::new();
+    let mut fallback = None;
 
     for variant in e {
         if !variant.fields.is_empty() {
@@ -69,6 +71,17 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
             .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
             .unwrap_or_else(|| quote! {::std::option::Option::None});
 
+        if variant.fallback {
+            if fallback.is_some() {
+                return Err(Error::new_spanned(
+                    &variant.ident,
+                    "Only one variant can be the fallback.",
+                )
+                .into());
+            }
+            fallback = Some(item_ident);
+        }
+
         enum_items.push(item_ident);
         items.push(quote! {
             #crate_name::resolver_utils::EnumItem {
@@ -76,16 +89,30 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
                 value: #ident::#item_ident,
             }
         });
+        let external_name = variant.external.as_ref().unwrap_or(&gql_item_name);
+        external_items.push(quote! {
+            #crate_name::resolver_utils::EnumItem {
+                name: #external_name,
+                value: #ident::#item_ident,
+            }
+        });
 
-        let visible = visible_fn(&variant.visible);
+        let int_value = if enum_args.int_values {
+            quote!(::std::option::Option::Some(#ident::#item_ident as ::std::primitive::i64))
+        } else {
+            quote!(::std::option::Option::None)
+        };
+        let visible = visible_fn(&crate_name, &variant.visible);
         schema_enum_items.push(quote! {
-            enum_items.insert(::std::string::ToString::to_string(#gql_item_name), #crate_name::registry::MetaEnumValue {
-                name: ::std::string::ToString::to_string(#gql_item_name),
-                description: #item_desc,
-                deprecation: #item_deprecation,
-                visible: #visible,
-                inaccessible: #inaccessible,
-                tags: ::std::vec![ #(#tags),* ],
+            enum_items.insert(::std::string::ToString::to_string(#gql_item_name), {
+                let mut value = #crate_name::registry::MetaEnumValue::new(#gql_item_name);
+                value.description = #item_desc;
+                value.deprecation = #item_deprecation;
+                value.visible = #visible;
+                value.inaccessible = #inaccessible;
+                value.tags = ::std::vec![ #(#tags),* ];
+                value.int_value = #int_value;
+                value
             });
         });
     }
@@ -130,13 +157,47 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
         .into());
     }
 
-    let visible = visible_fn(&enum_args.visible);
+    let fallback_value = match &fallback {
+        Some(item_ident) => quote!(::std::option::Option::Some(#ident::#item_ident)),
+        None => quote!(::std::option::Option::None),
+    };
+
+    let int_conversion = if enum_args.int_values {
+        Some(quote! {
+            fn as_int(self) -> ::std::option::Option<::std::primitive::i64> {
+                ::std::option::Option::Some(self as ::std::primitive::i64)
+            }
+
+            fn from_int(value: ::std::primitive::i64) -> ::std::option::Option<Self> {
+                #(
+                    if value == #ident::#enum_items as ::std::primitive::i64 {
+                        return ::std::option::Option::Some(#ident::#enum_items);
+                    }
+                )*
+                ::std::option::Option::None
+            }
+        })
+    } else {
+        None
+    };
+
+    let visible = visible_fn(&crate_name, &enum_args.visible);
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::resolver_utils::EnumType for #ident {
             fn items() -> &'static [#crate_name::resolver_utils::EnumItem<#ident>] {
                 &[#(#items),*]
             }
+
+            fn fallback() -> ::std::option::Option<#ident> {
+                #fallback_value
+            }
+
+            fn external_items() -> &'static [#crate_name::resolver_utils::EnumItem<#ident>] {
+                &[#(#external_items),*]
+            }
+
+            #int_conversion
         }
 
         #[allow(clippy::all, clippy::pedantic)]
@@ -0,0 +1,48 @@
+use darling::ast::{Data, Style};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::Error;
+
+use crate::{
+    args,
+    utils::{get_crate_name, GeneratorResult},
+};
+
+pub fn generate(extend_object_args: &args::ExtendObject) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(extend_object_args.internal);
+    let ident = &extend_object_args.ident;
+
+    if !extend_object_args.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &extend_object_args.generics,
+            "ExtendObject cannot be applied to a generic struct.",
+        )
+        .into());
+    }
+
+    let fields = match &extend_object_args.data {
+        Data::Struct(e) => e,
+        _ => {
+            return Err(
+                Error::new_spanned(ident, "ExtendObject can only be applied to an struct.").into(),
+            )
+        }
+    };
+
+    if fields.style != Style::Tuple || fields.fields.len() != 1 {
+        return Err(Error::new_spanned(ident, "Invalid type.").into());
+    }
+    let target_ty = &fields.fields[0];
+
+    let expanded = quote! {
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #crate_name::ObjectExtension for #ident {
+            type Target = #target_ty;
+
+            fn extend(target: &Self::Target) -> Self {
+                #ident(::std::clone::Clone::clone(target))
+            }
+        }
+    };
+    Ok(expanded.into())
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use async_graphql_parser::{
+    parse_query, parse_schema,
+    types::{
+        BaseType, DocumentOperations, ExecutableDocument, FieldDefinition, OperationType,
+        Selection, SelectionSet, ServiceDocument, Type as GqlType, TypeKind, TypeSystemDefinition,
+    },
+    Error as ParseError, Pos, Positioned,
+};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Ident, LitStr, Token, Type,
+};
+
+use crate::utils::{get_crate_name, GeneratorResult};
+
+pub struct GraphQLQuery {
+    schema: Type,
+    query: LitStr,
+    sdl: Option<LitStr>,
+}
+
+impl Parse for GraphQLQuery {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let schema = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let query = input.parse()?;
+        let mut sdl = None;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            if !input.is_empty() {
+                let key = input.parse::<Ident>()?;
+                if key != "sdl" {
+                    return Err(Error::new(
+                        key.span(),
+                        format!("Unknown key `{}`. The valid keys are `sdl`.", key),
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                sdl = Some(input.parse()?);
+                if input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+        }
+        Ok(Self { schema, query, sdl })
+    }
+}
+
+pub fn generate(input: &GraphQLQuery) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(false);
+    let schema = &input.schema;
+    let query = &input.query;
+    let sdl = input.sdl.as_ref().ok_or_else(|| {
+        Error::new(
+            query.span(),
+            "The SDL of the schema is required to check the query, such as `sdl = \"schema.graphql\"`.",
+        )
+    })?;
+
+    let document = match parse_query(query.value()) {
+        Ok(document) => document,
+        Err(err) => {
+            let message = match err.positions().next() {
+                // the syntax errors already show their position
+                Some(_) if matches!(err, ParseError::Syntax { .. }) => {
+                    format!("Invalid GraphQL query:\n{}", err)
+                }
+                Some(pos) => format!("Invalid GraphQL query: {} at {}", err, pos),
+                None => format!("Invalid GraphQL query: {}", err),
+            };
+            return Err(Error::new(query.span(), message).into());
+        }
+    };
+
+    let path = sdl_path(&sdl.value()).map_err(|message| Error::new(sdl.span(), message))?;
+    let service = std::fs::read_to_string(&path)
+        .map_err(|err| {
+            format!(
+                "Failed to read the SDL of the schema from `{}`: {}",
+                path.display(),
+                err
+            )
+        })
+        .and_then(|source| {
+            parse_schema(source).map_err(|err| {
+                format!("Invalid SDL of the schema in `{}`: {}", path.display(), err)
+            })
+        })
+        .map_err(|message| Error::new(sdl.span(), message))?;
+
+    // the errors are reported at once, since the expression can only expand to
+    // one `compile_error!`
+    let errors = SchemaTypes::new(&service).check(&document);
+    if !errors.is_empty() {
+        let message = errors
+            .into_iter()
+            .map(|(pos, message)| format!("Invalid GraphQL query at {}: {}", pos, message))
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Err(Error::new(query.span(), message).into());
+    }
+
+    // the SDL is included to rebuild the query when it changes
+    let path = path.display().to_string();
+    let expanded = quote! {{
+        const _: &[u8] = ::std::include_bytes!(#path);
+        #crate_name::StaticQuery::<#schema>::new(#query)
+    }};
+    Ok(expanded.into())
+}
+
+/// Returns the path of the SDL file, which is relative to the directory of
+/// the manifest unless it starts with an environment variable such as
+/// `$OUT_DIR`.
+fn sdl_path(sdl: &str) -> Result<PathBuf, String> {
+    if let Some(sdl) = sdl.strip_prefix('$') {
+        let (name, rest) = sdl.split_once('/').unwrap_or((sdl, ""));
+        let dir = std::env::var_os(name)
+            .ok_or_else(|| format!("The environment variable `{}` is not set.", name))?;
+        return Ok(PathBuf::from(dir).join(rest));
+    }
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    Ok(PathBuf::from(manifest_dir).join(sdl))
+}
+
+enum Kind {
+    Scalar,
+    Composite,
+    Input,
+}
+
+struct SchemaType<'a> {
+    kind: Kind,
+    fields: Vec<&'a FieldDefinition>,
+}
+
+/// The types of a schema, parsed from its SDL.
+struct SchemaTypes<'a> {
+    types: HashMap<&'a str, SchemaType<'a>>,
+    query: &'a str,
+    mutation: Option<&'a str>,
+    subscription: Option<&'a str>,
+}
+
+impl<'a> SchemaTypes<'a> {
+    fn new(service: &'a ServiceDocument) -> Self {
+        let mut types = ["Int", "Float", "String", "Boolean", "ID"]
+            .into_iter()
+            .map(|name| {
+                let ty = SchemaType {
+                    kind: Kind::Scalar,
+                    fields: Vec::new(),
+                };
+                (name, ty)
+            })
+            .collect::<HashMap<_, _>>();
+        let (mut query, mut mutation, mut subscription) = (None, None, None);
+
+        for definition in &service.definitions {
+            match definition {
+                TypeSystemDefinition::Schema(schema) => {
+                    let schema = &schema.node;
+                    query = query.or_else(|| schema.query.as_ref().map(|name| &*name.node));
+                    mutation =
+                        mutation.or_else(|| schema.mutation.as_ref().map(|name| &*name.node));
+                    subscription = subscription
+                        .or_else(|| schema.subscription.as_ref().map(|name| &*name.node));
+                }
+                TypeSystemDefinition::Type(ty) => {
+                    let (kind, fields) = match &ty.node.kind {
+                        TypeKind::Scalar | TypeKind::Enum(_) => (Kind::Scalar, &[][..]),
+                        TypeKind::Object(object) => (Kind::Composite, &object.fields[..]),
+                        TypeKind::Interface(interface) => (Kind::Composite, &interface.fields[..]),
+                        TypeKind::Union(_) => (Kind::Composite, &[][..]),
+                        TypeKind::InputObject(_) => (Kind::Input, &[][..]),
+                    };
+                    // the fields of the type extensions are merged
+                    types
+                        .entry(&*ty.node.name.node)
+                        .or_insert(SchemaType {
+                            kind,
+                            fields: Vec::new(),
+                        })
+                        .fields
+                        .extend(fields.iter().map(|field| &field.node));
+                }
+                TypeSystemDefinition::Directive(_) => {}
+            }
+        }
+
+        let default_root = |name: &'static str| Some(name).filter(|name| types.contains_key(name));
+        Self {
+            query: query.or_else(|| default_root("Query")).unwrap_or("Query"),
+            mutation: mutation.or_else(|| default_root("Mutation")),
+            subscription: subscription.or_else(|| default_root("Subscription")),
+            types,
+        }
+    }
+
+    /// Checks the fields, arguments and types referenced by the document, and
+    /// returns the errors with their positions in the document.
+    fn check(&self, document: &ExecutableDocument) -> Vec<(Pos, String)> {
+        let mut checker = Checker {
+            schema: self,
+            document,
+            checked_fragments: HashSet::new(),
+            errors: Vec::new(),
+        };
+
+        let operations: Vec<_> = match &document.operations {
+            DocumentOperations::Single(operation) => vec![operation],
+            DocumentOperations::Multiple(operations) => operations.values().collect(),
+        };
+        for operation in operations {
+            for variable in &operation.node.variable_definitions {
+                checker.check_type(variable.pos, &variable.node.var_type.node);
+            }
+            let root = match operation.node.ty {
+                OperationType::Query => Some(self.query),
+                OperationType::Mutation => self.mutation,
+                OperationType::Subscription => self.subscription,
+            };
+            match root {
+                Some(root) => checker.check_selection_set(root, &operation.node.selection_set),
+                None => checker.errors.push((
+                    operation.pos,
+                    format!("Schema is not configured for {}s.", operation.node.ty),
+                )),
+            }
+        }
+
+        let mut errors = checker.errors;
+        errors.sort_by_key(|(pos, _)| (pos.line, pos.column));
+        errors
+    }
+}
+
+struct Checker<'a> {
+    schema: &'a SchemaTypes<'a>,
+    document: &'a ExecutableDocument,
+    checked_fragments: HashSet<&'a str>,
+    errors: Vec<(Pos, String)>,
+}
+
+impl<'a> Checker<'a> {
+    fn check_type(&mut self, pos: Pos, ty: &GqlType) {
+        let name = base_type_name(ty);
+        if !self.schema.types.contains_key(name) {
+            self.errors
+                .push((pos, format!(r#"Unknown type "{}""#, name)));
+        }
+    }
+
+    fn check_type_condition(&mut self, pos: Pos, name: &'a str) -> bool {
+        let exists = self.schema.types.contains_key(name);
+        if !exists {
+            self.errors
+                .push((pos, format!(r#"Unknown type "{}""#, name)));
+        }
+        exists
+    }
+
+    fn check_selection_set(
+        &mut self,
+        type_name: &'a str,
+        selection_set: &'a Positioned<SelectionSet>,
+    ) {
+        for selection in &selection_set.node.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let name = &*field.node.name.node;
+                    let is_introspection = name == "__typename"
+                        || (type_name == self.schema.query
+                            && matches!(name, "__schema" | "__type"));
+                    if is_introspection {
+                        continue;
+                    }
+
+                    let definition = self.schema.types.get(type_name).and_then(|ty| {
+                        ty.fields
+                            .iter()
+                            .find(|definition| definition.name.node == name)
+                    });
+                    let definition = match definition {
+                        Some(definition) => *definition,
+                        None => {
+                            self.errors.push((
+                                field.pos,
+                                format!(r#"Unknown field "{}" on type "{}"."#, name, type_name),
+                            ));
+                            continue;
+                        }
+                    };
+
+                    for (arg, _) in &field.node.arguments {
+                        if !definition
+                            .arguments
+                            .iter()
+                            .any(|definition| definition.node.name.node == arg.node)
+                        {
+                            self.errors.push((
+                                arg.pos,
+                                format!(
+                                    r#"Unknown argument "{}" on field "{}" of type "{}"."#,
+                                    arg.node, name, type_name
+                                ),
+                            ));
+                        }
+                    }
+                    for arg in &definition.arguments {
+                        let arg = &arg.node;
+                        if !arg.ty.node.nullable
+                            && arg.default_value.is_none()
+                            && !field
+                                .node
+                                .arguments
+                                .iter()
+                                .any(|(name, _)| name.node == arg.name.node)
+                        {
+                            self.errors.push((
+                                field.pos,
+                                format!(
+                                    r#"Field "{}" argument "{}" of type "{}" is required but not provided"#,
+                                    name, arg.name.node, type_name
+                                ),
+                            ));
+                        }
+                    }
+
+                    let field_type = base_type_name(&definition.ty.node);
+                    let has_selection = !field.node.selection_set.node.items.is_empty();
+                    match self.schema.types.get(field_type).map(|ty| &ty.kind) {
+                        Some(Kind::Composite) if has_selection => {
+                            self.check_selection_set(field_type, &field.node.selection_set)
+                        }
+                        Some(Kind::Composite) => self.errors.push((
+                            field.pos,
+                            format!(
+                                r#"Field "{}" of type "{}" must have a selection of subfields"#,
+                                name, definition.ty.node
+                            ),
+                        )),
+                        Some(Kind::Scalar) if has_selection => self.errors.push((
+                            field.pos,
+                            format!(
+                                r#"Field "{}" must not have a selection since type "{}" has no subfields"#,
+                                name, definition.ty.node
+                            ),
+                        )),
+                        _ => {}
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = &*spread.node.fragment_name.node;
+                    match self.document.fragments.get(name) {
+                        Some(fragment) => {
+                            // the fragments are checked once
+                            if self.checked_fragments.insert(name) {
+                                let on = &fragment.node.type_condition.node.on;
+                                if self.check_type_condition(on.pos, &on.node) {
+                                    self.check_selection_set(
+                                        &on.node,
+                                        &fragment.node.selection_set,
+                                    );
+                                }
+                            }
+                        }
+                        None => self
+                            .errors
+                            .push((spread.pos, format!(r#"Unknown fragment: "{}""#, name))),
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let type_name = match &fragment.node.type_condition {
+                        Some(condition) => {
+                            let on = &condition.node.on;
+                            if !self.check_type_condition(on.pos, &on.node) {
+                                continue;
+                            }
+                            &*on.node
+                        }
+                        None => type_name,
+                    };
+                    self.check_selection_set(type_name, &fragment.node.selection_set);
+                }
+            }
+        }
+    }
+}
+
+fn base_type_name(ty: &GqlType) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name,
+        BaseType::List(ty) => base_type_name(ty),
+    }
+}
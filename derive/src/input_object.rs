@@ -5,7 +5,9 @@ use syn::{ext::IdentExt, Error};
 
 use crate::{
     args::{self, RenameRuleExt, RenameTarget},
-    utils::{generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{
+        gen_type_info, generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+    },
 };
 
 pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream> {
@@ -93,14 +95,30 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
             None => Default::default(),
         };
 
+        let redact = if field.secret {
+            Some(quote!(.map_err(|err| err.redact(&#crate_name::InputType::to_value(&#ident)))))
+        } else {
+            None
+        };
+        let in_field = if field.flatten {
+            None
+        } else {
+            Some(quote! {
+                .map_err(|err| err.with_path_segment(
+                    #crate_name::PathSegment::Field(::std::string::ToString::to_string(#name))
+                ))
+            })
+        };
         let validators = field
             .validator
             .clone()
             .unwrap_or_default()
             .create_validators(
                 &crate_name,
+                &name,
                 quote!(&#ident),
-                Some(quote!(.map_err(#crate_name::InputValueError::propagate))),
+                Some(quote!(#redact #in_field .map_err(#crate_name::InputValueError::propagate))),
+                None,
             )?;
 
         if field.flatten {
@@ -149,6 +167,16 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
             })
             .unwrap_or_else(|| quote!(::std::option::Option::None));
         let secret = field.secret;
+        let redact_parse = if secret {
+            Some(quote! {
+                .map_err(|err| match obj.get(#name) {
+                    ::std::option::Option::Some(value) => err.redact(value),
+                    ::std::option::Option::None => err,
+                })
+            })
+        } else {
+            None
+        };
 
         if let Some(default) = default {
             get_fields.push(quote! {
@@ -158,6 +186,8 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                         ::std::option::Option::Some(value) => {
                             #[allow(unused_mut)]
                             let mut #ident = #crate_name::InputType::parse(::std::option::Option::Some(::std::clone::Clone::clone(&value)))
+                                #redact_parse
+                                #in_field
                                 .map_err(#crate_name::InputValueError::propagate)?;
                             #process_with
                             #ident
@@ -172,6 +202,8 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
             get_fields.push(quote! {
                 #[allow(non_snake_case, unused_mut)]
                 let mut #ident: #ty = #crate_name::InputType::parse(obj.get(#name).cloned())
+                    #redact_parse
+                    #in_field
                     .map_err(#crate_name::InputValueError::propagate)?;
                 #process_with
                 #validators
@@ -186,12 +218,13 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         });
 
         fields.push(ident);
-        let visible = visible_fn(&field.visible);
+        let visible = visible_fn(&crate_name, &field.visible);
+        let type_info = gen_type_info(&crate_name, ty, quote!(InputType));
         schema_fields.push(quote! {
             fields.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                 name: ::std::string::ToString::to_string(#name),
                 description: #desc,
-                ty: <#ty as #crate_name::InputType>::create_type_info(registry),
+                ty: #type_info,
                 default_value: #schema_default,
                 visible: #visible,
                 inaccessible: #inaccessible,
@@ -209,7 +242,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         .into());
     }
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
 
     let get_federation_fields = {
         let fields = federation_fields.into_iter().map(|(ty, name)| {
@@ -228,10 +261,22 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         }
     };
 
-    let obj_validator = object_args
-        .validator
-        .as_ref()
-        .map(|expr| quote! { #crate_name::CustomValidator::check(&#expr, &obj)?; });
+    let validator = object_args.validator.as_ref();
+    let validate_input = matches!(validator, Some(validator) if validator.validate_input);
+    let obj_validator = validator.map(|validator| {
+        let validate_input = validator
+            .validate_input
+            .then(|| quote! { #crate_name::ValidateInput::validate(&obj)?; });
+        let custom = validator
+            .custom
+            .as_ref()
+            .map(|expr| quote! { #crate_name::CustomValidator::check(&#expr, &obj)?; });
+        quote! {
+            #validate_input
+            #custom
+        }
+    });
+    let validate_input_bound = validate_input.then(|| quote!(+ #crate_name::ValidateInput));
 
     let expanded = if object_args.concretes.is_empty() {
         quote! {
@@ -311,7 +356,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                     })
                 }
 
-                fn __internal_parse(value: ::std::option::Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> where Self: #crate_name::InputType {
+                fn __internal_parse(value: ::std::option::Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> where Self: #crate_name::InputType #validate_input_bound {
                     if let ::std::option::Option::Some(#crate_name::Value::Object(obj)) = value {
                         #(#get_fields)*
                         let obj = Self { #(#fields),* };
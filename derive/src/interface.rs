@@ -10,8 +10,8 @@ use crate::{
     args::{self, InterfaceField, InterfaceFieldArgument, RenameRuleExt, RenameTarget},
     output_type::OutputType,
     utils::{
-        gen_deprecation, generate_default, get_crate_name, get_rustdoc, visible_fn,
-        GeneratorResult, RemoveLifetime,
+        gen_deprecation, gen_remote_conversion, generate_default, get_crate_name, get_rustdoc,
+        visible_fn, GeneratorResult, RemoveLifetime,
     },
 };
 
@@ -234,7 +234,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                     }
                 })
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
-            let visible = visible_fn(visible);
+            let visible = visible_fn(&crate_name, visible);
             let tags = tags
                 .iter()
                 .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
@@ -284,7 +284,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             }
         });
 
-        let visible = visible_fn(visible);
+        let visible = visible_fn(&crate_name, visible);
         let tags = tags
             .iter()
             .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
@@ -340,7 +340,11 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         }
     };
 
-    let visible = visible_fn(&interface_args.visible);
+    let visible = visible_fn(&crate_name, &interface_args.visible);
+    let remote_conversion = interface_args.remote.as_ref().map(|remote_ty| {
+        gen_remote_conversion(ident, &interface_args.generics, remote_ty, &enum_names)
+    });
+
     let expanded = quote! {
         #(#type_into_impls)*
 
@@ -412,6 +416,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         }
 
         impl #impl_generics #crate_name::InterfaceType for #ident #ty_generics #where_clause {}
+
+        #remote_conversion
     };
     Ok(expanded.into())
 }
@@ -6,18 +6,24 @@
 extern crate proc_macro;
 
 mod args;
+mod attribute_keys;
 mod complex_object;
 mod description;
 mod directive;
 mod r#enum;
+mod extend_object;
+mod graphql_query;
 mod input_object;
 mod interface;
+mod loader;
 mod merged_object;
 mod merged_subscription;
 mod newtype;
+mod newtype_id;
 mod object;
 mod oneof_object;
 mod output_type;
+mod patch;
 mod scalar;
 mod simple_object;
 mod subscription;
@@ -41,6 +47,9 @@ macro_rules! parse_nested_meta {
             }
         };
 
+        if let Err(err) = attribute_keys::check_keys::<$ty>(&meta) {
+            return TokenStream::from(err.write_errors());
+        }
         match <$ty>::from_list(&meta) {
             Ok(object_args) => object_args,
             Err(err) => return TokenStream::from(err.write_errors()),
@@ -61,11 +70,12 @@ pub fn Object(args: TokenStream, input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(SimpleObject, attributes(graphql))]
 pub fn derive_simple_object(input: TokenStream) -> TokenStream {
-    let object_args =
-        match args::SimpleObject::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
-            Ok(object_args) => object_args,
-            Err(err) => return TokenStream::from(err.write_errors()),
-        };
+    let object_args = match utils::parse_derive_input::<args::SimpleObject, args::SimpleObjectField>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
+        Ok(object_args) => object_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
     match simple_object::generate(&object_args) {
         Ok(expanded) => expanded,
         Err(err) => err.write_errors().into(),
@@ -85,7 +95,9 @@ pub fn ComplexObject(args: TokenStream, input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(Enum, attributes(graphql))]
 pub fn derive_enum(input: TokenStream) -> TokenStream {
-    let enum_args = match args::Enum::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
+    let enum_args = match utils::parse_derive_input::<args::Enum, args::EnumItem>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
         Ok(enum_args) => enum_args,
         Err(err) => return TokenStream::from(err.write_errors()),
     };
@@ -97,24 +109,39 @@ pub fn derive_enum(input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(InputObject, attributes(graphql))]
 pub fn derive_input_object(input: TokenStream) -> TokenStream {
-    let object_args =
-        match args::InputObject::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
-            Ok(object_args) => object_args,
-            Err(err) => return TokenStream::from(err.write_errors()),
-        };
+    let object_args = match utils::parse_derive_input::<args::InputObject, args::InputObjectField>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
+        Ok(object_args) => object_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
     match input_object::generate(&object_args) {
         Ok(expanded) => expanded,
         Err(err) => err.write_errors().into(),
     }
 }
 
+#[proc_macro_derive(Patch, attributes(patch))]
+pub fn derive_patch(input: TokenStream) -> TokenStream {
+    let patch_args = match args::Patch::from_derive_input(&parse_macro_input!(input as DeriveInput))
+    {
+        Ok(patch_args) => patch_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
+    match patch::generate(&patch_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
 #[proc_macro_derive(Interface, attributes(graphql))]
 pub fn derive_interface(input: TokenStream) -> TokenStream {
-    let interface_args =
-        match args::Interface::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
-            Ok(interface_args) => interface_args,
-            Err(err) => return TokenStream::from(err.write_errors()),
-        };
+    let interface_args = match utils::parse_derive_input::<args::Interface, ()>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
+        Ok(interface_args) => interface_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
     match interface::generate(&interface_args) {
         Ok(expanded) => expanded,
         Err(err) => err.write_errors().into(),
@@ -123,8 +150,9 @@ pub fn derive_interface(input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(Union, attributes(graphql))]
 pub fn derive_union(input: TokenStream) -> TokenStream {
-    let union_args = match args::Union::from_derive_input(&parse_macro_input!(input as DeriveInput))
-    {
+    let union_args = match utils::parse_derive_input::<args::Union, args::UnionItem>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
         Ok(union_args) => union_args,
         Err(err) => return TokenStream::from(err.write_errors()),
     };
@@ -158,11 +186,12 @@ pub fn Scalar(args: TokenStream, input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(MergedObject, attributes(graphql))]
 pub fn derive_merged_object(input: TokenStream) -> TokenStream {
-    let object_args =
-        match args::MergedObject::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
-            Ok(object_args) => object_args,
-            Err(err) => return TokenStream::from(err.write_errors()),
-        };
+    let object_args = match utils::parse_derive_input::<args::MergedObject, args::MergedObjectField>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
+        Ok(object_args) => object_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
     match merged_object::generate(&object_args) {
         Ok(expanded) => expanded,
         Err(err) => err.write_errors().into(),
@@ -171,9 +200,9 @@ pub fn derive_merged_object(input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(MergedSubscription, attributes(graphql))]
 pub fn derive_merged_subscription(input: TokenStream) -> TokenStream {
-    let object_args = match args::MergedSubscription::from_derive_input(&parse_macro_input!(
-        input as DeriveInput
-    )) {
+    let object_args = match utils::parse_derive_input::<args::MergedSubscription, ()>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
         Ok(object_args) => object_args,
         Err(err) => return TokenStream::from(err.write_errors()),
     };
@@ -198,17 +227,46 @@ pub fn derive_description(input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(NewType, attributes(graphql))]
 pub fn derive_newtype(input: TokenStream) -> TokenStream {
-    let newtype_args =
-        match args::NewType::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
-            Ok(newtype_args) => newtype_args,
-            Err(err) => return TokenStream::from(err.write_errors()),
-        };
+    let newtype_args = match utils::parse_derive_input::<args::NewType, ()>(&parse_macro_input!(
+        input as DeriveInput
+    )) {
+        Ok(newtype_args) => newtype_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
     match newtype::generate(&newtype_args) {
         Ok(expanded) => expanded,
         Err(err) => err.write_errors().into(),
     }
 }
 
+#[proc_macro_derive(NewTypeId, attributes(graphql))]
+pub fn derive_newtype_id(input: TokenStream) -> TokenStream {
+    let newtype_id_args = match utils::parse_derive_input::<args::NewTypeId, ()>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
+        Ok(newtype_id_args) => newtype_id_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
+    match newtype_id::generate(&newtype_id_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
+#[proc_macro_derive(ExtendObject, attributes(graphql))]
+pub fn derive_extend_object(input: TokenStream) -> TokenStream {
+    let extend_object_args = match utils::parse_derive_input::<args::ExtendObject, ()>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
+        Ok(extend_object_args) => extend_object_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
+    match extend_object::generate(&extend_object_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
 #[proc_macro_attribute]
 #[allow(non_snake_case)]
 pub fn Directive(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -231,15 +289,36 @@ pub fn TypeDirective(args: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_attribute]
+#[allow(non_snake_case)]
+pub fn Loader(args: TokenStream, input: TokenStream) -> TokenStream {
+    let loader_args = parse_nested_meta!(args::Loader, args);
+    let mut item_fn = parse_macro_input!(input as ItemFn);
+    match loader::generate(&loader_args, &mut item_fn) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
 #[proc_macro_derive(OneofObject, attributes(graphql))]
 pub fn derive_oneof_object(input: TokenStream) -> TokenStream {
-    let object_args =
-        match args::OneofObject::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
-            Ok(object_args) => object_args,
-            Err(err) => return TokenStream::from(err.write_errors()),
-        };
+    let object_args = match utils::parse_derive_input::<args::OneofObject, args::OneofObjectField>(
+        &parse_macro_input!(input as DeriveInput),
+    ) {
+        Ok(object_args) => object_args,
+        Err(err) => return TokenStream::from(err.write_errors()),
+    };
     match oneof_object::generate(&object_args) {
         Ok(expanded) => expanded,
         Err(err) => err.write_errors().into(),
     }
 }
+
+#[proc_macro]
+pub fn graphql_query(input: TokenStream) -> TokenStream {
+    let query = parse_macro_input!(input as graphql_query::GraphQLQuery);
+    match graphql_query::generate(&query) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
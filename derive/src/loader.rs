@@ -0,0 +1,169 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    ext::IdentExt, Error, FnArg, GenericArgument, Ident, ItemFn, PathArguments, ReturnType, Type,
+    Visibility,
+};
+
+use crate::{
+    args,
+    args::RenameTarget,
+    utils::{get_crate_name, GeneratorResult},
+};
+
+pub fn generate(loader_args: &args::Loader, item_fn: &mut ItemFn) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(loader_args.internal);
+    let ident = item_fn.sig.ident.clone();
+    let vis = std::mem::replace(&mut item_fn.vis, Visibility::Inherited);
+    let loader_ident = match &loader_args.name {
+        Some(name) => Ident::new(name, Span::call_site()),
+        None => {
+            let fn_name = ident.unraw().to_string();
+            let name = fn_name.strip_prefix("load_").unwrap_or(&fn_name);
+            Ident::new(
+                &format!("{}Loader", RenameTarget::Type.rename(name)),
+                Span::call_site(),
+            )
+        }
+    };
+
+    if item_fn.sig.asyncness.is_none() {
+        return Err(Error::new_spanned(&item_fn.sig, "The loader function must be async.").into());
+    }
+
+    let mut arg_types = Vec::new();
+    for arg in &item_fn.sig.inputs {
+        match arg {
+            FnArg::Typed(pat) => match &*pat.ty {
+                Type::Reference(r) if r.mutability.is_none() => arg_types.push(&*r.elem),
+                _ => {
+                    return Err(Error::new_spanned(
+                        &pat.ty,
+                        "The arguments of the loader function must be shared references.",
+                    )
+                    .into())
+                }
+            },
+            FnArg::Receiver(_) => {
+                return Err(
+                    Error::new_spanned(arg, "The loader function cannot have a receiver.").into(),
+                )
+            }
+        }
+    }
+
+    let (state_ty, keys_ty) = match arg_types.as_slice() {
+        [keys_ty] => (None, *keys_ty),
+        [state_ty, keys_ty] => (Some(*state_ty), *keys_ty),
+        _ => {
+            return Err(Error::new_spanned(
+                &item_fn.sig.inputs,
+                "The loader function must have the keys argument, optionally preceded by a state argument.",
+            )
+            .into())
+        }
+    };
+    let key_ty = match keys_ty {
+        Type::Slice(slice) => &*slice.elem,
+        _ => {
+            return Err(
+                Error::new_spanned(keys_ty, "The keys argument must be a slice of keys.").into(),
+            )
+        }
+    };
+
+    let return_ty = match &item_fn.sig.output {
+        ReturnType::Type(_, ty) => &**ty,
+        ReturnType::Default => {
+            return Err(Error::new_spanned(
+                &item_fn.sig,
+                "The loader function must return a `HashMap` or a `Result` of a `HashMap`.",
+            )
+            .into())
+        }
+    };
+    let (map_ty, error_ty) = match type_args(return_ty, "Result").as_deref() {
+        Some([map_ty, error_ty]) => (*map_ty, quote!(#error_ty)),
+        Some([map_ty]) => (*map_ty, quote!(#crate_name::Error)),
+        _ => (return_ty, quote!(::std::convert::Infallible)),
+    };
+    let value_ty = match type_args(map_ty, "HashMap").as_deref() {
+        Some([_, value_ty, ..]) => *value_ty,
+        _ => {
+            return Err(Error::new_spanned(
+                return_ty,
+                "The loader function must return a `HashMap` or a `Result` of a `HashMap`.",
+            )
+            .into())
+        }
+    };
+    let is_result = !std::ptr::eq(map_ty, return_ty);
+
+    let (state_field, call) = match state_ty {
+        Some(state_ty) => (quote!((pub #state_ty)), quote!(#ident(&self.0, keys).await)),
+        None => (quote!(), quote!(#ident(keys).await)),
+    };
+    let call = if is_result {
+        call
+    } else {
+        quote!(::std::result::Result::Ok(#call))
+    };
+
+    let expanded = quote! {
+        #vis struct #loader_ident #state_field;
+
+        #[#crate_name::async_trait::async_trait]
+        impl #crate_name::dataloader::Loader<#key_ty> for #loader_ident {
+            type Value = #value_ty;
+            type Error = #error_ty;
+
+            async fn load(
+                &self,
+                keys: &[#key_ty],
+            ) -> ::std::result::Result<::std::collections::HashMap<#key_ty, Self::Value>, Self::Error> {
+                #item_fn
+                #call
+            }
+        }
+
+        impl #crate_name::dataloader::SingleKeyLoader for #loader_ident {
+            type Key = #key_ty;
+        }
+
+        impl #loader_ident {
+            /// Creates a `DataLoader` that uses this loader, it can be added
+            /// to the data of the schema or the request.
+            #vis fn data_loader(
+                self,
+                runtime: impl #crate_name::dataloader::Runtime,
+            ) -> #crate_name::dataloader::DataLoader<Self> {
+                #crate_name::dataloader::DataLoader::new(self, runtime)
+            }
+        }
+    };
+    Ok(expanded.into())
+}
+
+fn type_args<'a>(ty: &'a Type, name: &str) -> Option<Vec<&'a Type>> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != name {
+        return None;
+    }
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args,
+        _ => return None,
+    };
+    Some(
+        args.args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+    )
+}
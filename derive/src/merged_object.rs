@@ -2,10 +2,10 @@ use darling::ast::Data;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
-use syn::{Error, LitInt};
+use syn::{Error, LitInt, Type};
 
 use crate::{
-    args::{self, RenameTarget, TypeDirectiveLocation},
+    args::{self, Namespace, RenameTarget, TypeDirectiveLocation},
     utils::{gen_directive_calls, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
 };
 
@@ -46,31 +46,124 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
         }
     };
 
-    let mut types = Vec::new();
-    for field in &s.fields {
-        types.push(&field.ty);
-    }
-
-    let create_merged_obj = {
-        let mut obj = quote! { #crate_name::MergedObjectTail };
-        for i in 0..types.len() {
-            let n = LitInt::new(&format!("{}", i), Span::call_site());
-            obj = quote! { #crate_name::MergedObject(&self.#n, #obj) };
-        }
-        quote! {
-            #obj
+    let mut merge_fields = Vec::new();
+    let mut owner_arms = Vec::new();
+    let mut fallbacks = Vec::new();
+    let mut find_entities = Vec::new();
+    let serial = object_args.serial;
+
+    for (idx, field) in s.fields.iter().enumerate() {
+        let ty = &field.ty;
+        let n = LitInt::new(&format!("{}", idx), Span::call_site());
+
+        let namespace = match &field.namespace {
+            Some(Namespace::Name(name)) => Some(name.clone()),
+            Some(Namespace::Default) => {
+                let segment = match ty {
+                    Type::Path(path) => path.path.segments.last(),
+                    _ => None,
+                };
+                match segment {
+                    Some(segment) => Some(RenameTarget::Field.rename(segment.ident.to_string())),
+                    None => {
+                        return Err(Error::new_spanned(
+                            ty,
+                            "A namespace name must be specified for this type.",
+                        )
+                        .into())
+                    }
+                }
+            }
+            None => None,
+        };
+
+        match namespace {
+            Some(namespace) => {
+                let field_desc = get_rustdoc(&field.attrs)?
+                    .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
+                    .unwrap_or_else(|| quote! {::std::option::Option::None});
+                // The fields of a namespace of the mutation root are mutations, which
+                // are executed serially.
+                let resolve_namespace = quote! {
+                    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                    let is_mutation = #serial
+                        || (ctx.query_env.operation.node.ty == #crate_name::parser::types::OperationType::Mutation
+                            && ctx.path_node.map_or(false, |node| node.parent.is_none()));
+                    if is_mutation {
+                        return #crate_name::resolver_utils::resolve_container_serial(&ctx_obj, &self.#n).await.map(::std::option::Option::Some);
+                    }
+                    return #crate_name::OutputType::resolve(&self.#n, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                };
+
+                merge_fields.push(quote! {
+                    fields.insert(::std::borrow::ToOwned::to_owned(#namespace), #crate_name::registry::MetaField {
+                        name: ::std::borrow::ToOwned::to_owned(#namespace),
+                        description: #field_desc,
+                        args: ::std::default::Default::default(),
+                        ty: <#ty as #crate_name::OutputType>::create_type_info(registry),
+                        deprecation: #crate_name::registry::Deprecation::NoDeprecated,
+                        cache_control: ::std::default::Default::default(),
+                        external: false,
+                        provides: ::std::option::Option::None,
+                        requires: ::std::option::Option::None,
+                        shareable: false,
+                        inaccessible: false,
+                        tags: ::std::default::Default::default(),
+                        override_from: ::std::option::Option::None,
+                        visible: ::std::option::Option::None,
+                        compute_complexity: ::std::option::Option::None,
+                        directive_invocations: ::std::default::Default::default(),
+                    });
+                    owners.insert(::std::borrow::ToOwned::to_owned(#namespace), #idx);
+                });
+                owner_arms.push(quote! {
+                    ::std::option::Option::Some(#n) => {
+                        #resolve_namespace
+                    }
+                });
+                fallbacks.push(quote! {
+                    if ctx.item.node.name.node == #namespace {
+                        #resolve_namespace
+                    }
+                });
+            }
+            None => {
+                merge_fields.push(quote! {
+                    if let #crate_name::registry::MetaType::Object {
+                        fields: obj_fields,
+                        cache_control: obj_cache_control,
+                        ..
+                    } = registry.create_fake_output_type::<#ty>() {
+                        for name in obj_fields.keys() {
+                            owners.insert(::std::clone::Clone::clone(name), #idx);
+                        }
+                        ::std::iter::Extend::extend(&mut fields, obj_fields);
+                        cache_control = cache_control.merge(&obj_cache_control);
+                    }
+                });
+                owner_arms.push(quote! {
+                    ::std::option::Option::Some(#n) => return #crate_name::resolver_utils::ContainerType::resolve_field(&self.#n, ctx).await,
+                });
+                fallbacks.push(quote! {
+                    if let ::std::option::Option::Some(value) = #crate_name::resolver_utils::ContainerType::resolve_field(&self.#n, ctx).await? {
+                        return ::std::result::Result::Ok(::std::option::Option::Some(value));
+                    }
+                });
+            }
         }
-    };
 
-    let merged_type = {
-        let mut obj = quote! { #crate_name::MergedObjectTail };
-        for ty in &types {
-            obj = quote! { #crate_name::MergedObject::<#ty, #obj> };
-        }
-        obj
-    };
+        find_entities.push(quote! {
+            if let ::std::option::Option::Some(value) = #crate_name::resolver_utils::ContainerType::find_entity(&self.#n, ctx, params).await? {
+                return ::std::result::Result::Ok(::std::option::Option::Some(value));
+            }
+        });
+    }
 
-    let visible = visible_fn(&object_args.visible);
+    // Later parts take precedence over earlier ones when field names overlap.
+    fallbacks.reverse();
+    find_entities.reverse();
+
+    let visible = visible_fn(&crate_name, &object_args.visible);
     let resolve_container = if object_args.serial {
         quote! { #crate_name::resolver_utils::resolve_container_serial(ctx, self).await }
     } else {
@@ -82,11 +175,24 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
         #[#crate_name::async_trait::async_trait]
         impl #impl_generics #crate_name::resolver_utils::ContainerType for #ident #ty_generics #where_clause {
             async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<::std::option::Option<#crate_name::Value>> {
-                #create_merged_obj.resolve_field(ctx).await
+                let owner = ctx
+                    .schema_env
+                    .registry
+                    .merged_fields
+                    .get(::std::convert::AsRef::<::std::primitive::str>::as_ref(&<Self as #crate_name::OutputType>::type_name()))
+                    .and_then(|owners| owners.get(ctx.item.node.name.node.as_str()))
+                    .copied();
+                match owner {
+                    #(#owner_arms)*
+                    _ => {}
+                }
+                #(#fallbacks)*
+                ::std::result::Result::Ok(::std::option::Option::None)
             }
 
             async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) ->  #crate_name::ServerResult<::std::option::Option<#crate_name::Value>> {
-               #create_merged_obj.find_entity(ctx, params).await
+                #(#find_entities)*
+                ::std::result::Result::Ok(::std::option::Option::None)
             }
         }
 
@@ -99,17 +205,13 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
 
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
                 registry.create_output_type::<Self, _>(#crate_name::registry::MetaTypeId::Object, |registry| {
-                    let mut fields = ::std::default::Default::default();
-                    let mut cache_control = ::std::default::Default::default();
+                    let mut fields = #crate_name::indexmap::IndexMap::new();
+                    let mut cache_control = <#crate_name::CacheControl as ::std::default::Default>::default();
+                    let mut owners = ::std::collections::HashMap::new();
 
-                    if let #crate_name::registry::MetaType::Object {
-                        fields: obj_fields,
-                        cache_control: obj_cache_control,
-                        ..
-                    } = registry.create_fake_output_type::<#merged_type>() {
-                        fields = obj_fields;
-                        cache_control = obj_cache_control;
-                    }
+                    #(#merge_fields)*
+
+                    registry.merged_fields.insert(::std::borrow::Cow::into_owned(#gql_typename), owners);
 
                     #crate_name::registry::MetaType::Object {
                         name: ::std::borrow::Cow::into_owned(#gql_typename),
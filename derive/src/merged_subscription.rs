@@ -53,7 +53,7 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
         |obj, ty| quote!(#crate_name::MergedObject::<#ty, #obj>),
     );
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #impl_generics #crate_name::SubscriptionType for #ident #ty_generics #where_clause {
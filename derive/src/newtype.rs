@@ -26,7 +26,7 @@ pub fn generate(newtype_args: &args::NewType) -> GeneratorResult<TokenStream> {
     let desc = get_rustdoc(&newtype_args.attrs)?
         .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
-    let visible = visible_fn(&newtype_args.visible);
+    let visible = visible_fn(&crate_name, &newtype_args.visible);
 
     let fields = match &newtype_args.data {
         Data::Struct(e) => e,
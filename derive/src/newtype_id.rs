@@ -0,0 +1,133 @@
+use darling::ast::{Data, Style};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::Error;
+
+use crate::{
+    args,
+    utils::{get_crate_name, GeneratorResult},
+};
+
+pub fn generate(newtype_id_args: &args::NewTypeId) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(newtype_id_args.internal);
+    let ident = &newtype_id_args.ident;
+    let (impl_generics, ty_generics, where_clause) = newtype_id_args.generics.split_for_impl();
+
+    let fields = match &newtype_id_args.data {
+        Data::Struct(e) => e,
+        _ => {
+            return Err(
+                Error::new_spanned(ident, "NewTypeId can only be applied to an struct.").into(),
+            )
+        }
+    };
+
+    if fields.style != Style::Tuple || fields.fields.len() != 1 {
+        return Err(Error::new_spanned(ident, "Invalid type.").into());
+    }
+    let inner_ty = &fields.fields[0];
+
+    let (strip_prefix, to_value) = match &newtype_id_args.prefix {
+        Some(prefix) => {
+            let prefix = format!("{}_", prefix);
+            (
+                quote! {
+                    let id = id.strip_prefix(#prefix).ok_or_else(|| {
+                        #crate_name::InputValueError::custom(::std::format!(
+                            "the ID must start with \"{}\"",
+                            #prefix
+                        ))
+                    })?;
+                },
+                quote!(::std::format!("{}{}", #prefix, self.0)),
+            )
+        }
+        None => (
+            quote!(),
+            quote!(::std::string::ToString::to_string(&self.0)),
+        ),
+    };
+
+    let expanded = quote! {
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #impl_generics #crate_name::ScalarType for #ident #ty_generics #where_clause {
+            fn parse(value: #crate_name::Value) -> #crate_name::InputValueResult<Self> {
+                let id = <#crate_name::ID as #crate_name::ScalarType>::parse(value)
+                    .map_err(#crate_name::InputValueError::propagate)?;
+                let id: &::std::primitive::str = &id;
+                #strip_prefix
+                ::std::str::FromStr::from_str(id)
+                    .map(#ident)
+                    .map_err(|err: <#inner_ty as ::std::str::FromStr>::Err| #crate_name::InputValueError::custom(err))
+            }
+
+            fn is_valid(value: &#crate_name::Value) -> bool {
+                <Self as #crate_name::ScalarType>::parse(::std::clone::Clone::clone(value)).is_ok()
+            }
+
+            fn to_value(&self) -> #crate_name::Value {
+                #crate_name::Value::String(#to_value)
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#inner_ty> for #ident #ty_generics #where_clause {
+            fn from(value: #inner_ty) -> Self {
+                Self(value)
+            }
+        }
+
+        #[allow(clippy::from_over_into)]
+        impl #impl_generics ::std::convert::Into<#inner_ty> for #ident #ty_generics #where_clause {
+            fn into(self) -> #inner_ty {
+                self.0
+            }
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #impl_generics #crate_name::InputType for #ident #ty_generics #where_clause {
+            type RawValueType = Self;
+
+            fn type_name() -> ::std::borrow::Cow<'static, ::std::primitive::str> {
+                <#crate_name::ID as #crate_name::InputType>::type_name()
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
+                <#crate_name::ID as #crate_name::InputType>::create_type_info(registry)
+            }
+
+            fn parse(value: ::std::option::Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
+                <#ident as #crate_name::ScalarType>::parse(value.unwrap_or_default())
+            }
+
+            fn to_value(&self) -> #crate_name::Value {
+                <#ident as #crate_name::ScalarType>::to_value(self)
+            }
+
+            fn as_raw_value(&self) -> ::std::option::Option<&Self::RawValueType> {
+                ::std::option::Option::Some(self)
+            }
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        #[#crate_name::async_trait::async_trait]
+        impl #impl_generics #crate_name::OutputType for #ident #ty_generics #where_clause {
+            fn type_name() -> ::std::borrow::Cow<'static, ::std::primitive::str> {
+                <#crate_name::ID as #crate_name::OutputType>::type_name()
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
+                <#crate_name::ID as #crate_name::OutputType>::create_type_info(registry)
+            }
+
+            async fn resolve(
+                &self,
+                _: &#crate_name::ContextSelectionSet<'_>,
+                _field: &#crate_name::Positioned<#crate_name::parser::types::Field>
+            ) -> #crate_name::ServerResult<#crate_name::Value> {
+                ::std::result::Result::Ok(#crate_name::ScalarType::to_value(self))
+            }
+        }
+    };
+
+    Ok(expanded.into())
+}
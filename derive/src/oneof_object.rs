@@ -89,7 +89,7 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
             enum_names.push(enum_name);
 
             let secret = variant.secret;
-            let visible = visible_fn(&variant.visible);
+            let visible = visible_fn(&crate_name, &variant.visible);
 
             schema_fields.push(quote! {
                 fields.insert(::std::borrow::ToOwned::to_owned(#field_name), #crate_name::registry::MetaInputValue {
@@ -104,19 +104,51 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
                 });
             });
 
+            let (clone_value, redact_parse, redact) = if secret {
+                (
+                    quote!(::std::clone::Clone::clone(&value)),
+                    Some(quote! {
+                        .map_err(|err| match &value {
+                            ::std::option::Option::Some(value) => err.redact(value),
+                            ::std::option::Option::None => err,
+                        })
+                    }),
+                    Some(
+                        quote!(.map_err(|err| err.redact(&#crate_name::InputType::to_value(&value)))),
+                    ),
+                )
+            } else {
+                (quote!(value), None, None)
+            };
+            let in_field = quote! {
+                .map_err(|err| err.with_path_segment(
+                    #crate_name::PathSegment::Field(::std::string::ToString::to_string(#field_name))
+                ))
+            };
+            let process_with = match variant.process_with.as_ref() {
+                Some(fn_path) => quote! { #fn_path(&mut value); },
+                None => Default::default(),
+            };
             let validators = variant
                 .validator
                 .clone()
                 .unwrap_or_default()
                 .create_validators(
                     &crate_name,
+                    &field_name,
                     quote!(&value),
-                    Some(quote!(.map_err(#crate_name::InputValueError::propagate))),
+                    Some(
+                        quote!(#redact #in_field .map_err(#crate_name::InputValueError::propagate)),
+                    ),
+                    None,
                 )?;
 
             parse_item.push(quote! {
                 if obj.contains_key(#field_name) && obj.len() == 1 {
-                    let value = #crate_name::InputType::parse(obj.remove(#field_name)).map_err(#crate_name::InputValueError::propagate)?;
+                    let value = obj.remove(#field_name);
+                    #[allow(unused_mut)]
+                    let mut value = #crate_name::InputType::parse(#clone_value)#redact_parse #in_field .map_err(#crate_name::InputValueError::propagate)?;
+                    #process_with
                     #validators
                     return ::std::result::Result::Ok(Self::#enum_name(value));
                 }
@@ -132,7 +164,7 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
         }
     }
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
     let expanded = if object_args.concretes.is_empty() {
         quote! {
             impl #crate_name::InputType for #ident {
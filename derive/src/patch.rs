@@ -0,0 +1,126 @@
+use darling::ast::Data;
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, Error, GenericArgument, PathArguments, Type};
+
+use crate::{
+    args::{self, RenameRuleExt, RenameTarget},
+    utils::{get_crate_name, GeneratorResult},
+};
+
+pub fn generate(patch_args: &args::Patch) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(patch_args.internal);
+    let ident = &patch_args.ident;
+    let vis = &patch_args.vis;
+    let patch_ident = format_ident!("{}Patch", ident);
+
+    if !patch_args.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &patch_args.generics,
+            "Patch does not support generic types.",
+        )
+        .into());
+    }
+
+    let s = match &patch_args.data {
+        Data::Struct(s) => s,
+        _ => {
+            return Err(Error::new_spanned(ident, "Patch can only be applied to an struct.").into())
+        }
+    };
+
+    let mut fields = Vec::new();
+    let mut apply_fields = Vec::new();
+
+    for field in &s.fields {
+        if field.skip {
+            continue;
+        }
+
+        let field_ident = match &field.ident {
+            Some(ident) => ident,
+            None => return Err(Error::new_spanned(ident, "All fields must be named.").into()),
+        };
+        let field_vis = &field.vis;
+        let attrs = &field.attrs;
+        let name = field.name.clone().unwrap_or_else(|| {
+            patch_args
+                .rename_fields
+                .rename(field_ident.unraw().to_string(), RenameTarget::Field)
+        });
+
+        // Nullable fields can be cleared with `null`, other fields can only be
+        // replaced.
+        let (patch_ty, apply) = match option_inner_type(&field.ty) {
+            Some(inner_ty) => (
+                quote!(#crate_name::MaybeUndefined<#inner_ty>),
+                quote!(self.#field_ident.update_to(&mut target.#field_ident);),
+            ),
+            None => {
+                let ty = &field.ty;
+                (
+                    quote!(::std::option::Option<#ty>),
+                    quote! {
+                        if let ::std::option::Option::Some(value) = self.#field_ident {
+                            target.#field_ident = value;
+                        }
+                    },
+                )
+            }
+        };
+
+        fields.push(quote! {
+            #(#attrs)*
+            #[graphql(name = #name)]
+            #field_vis #field_ident: #patch_ty
+        });
+        apply_fields.push(apply);
+    }
+
+    let name = patch_args
+        .name
+        .as_ref()
+        .map(|name| quote!(#[graphql(name = #name)]));
+    let internal = patch_args.internal.then(|| quote!(#[graphql(internal)]));
+    let apply_doc = format!(
+        "Applies the provided fields to the `{}`, leaving the other fields unchanged.",
+        ident
+    );
+
+    let expanded = quote! {
+        #[allow(missing_docs)]
+        #[derive(#crate_name::InputObject)]
+        #internal
+        #name
+        #vis struct #patch_ident {
+            #(#fields),*
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #patch_ident {
+            #[doc = #apply_doc]
+            #vis fn apply_to(self, target: &mut #ident) {
+                #(#apply_fields)*
+            }
+        }
+    };
+    Ok(expanded.into())
+}
+
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(ty) if ty.qself.is_none() => &ty.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -2,14 +2,14 @@ use std::str::FromStr;
 
 use darling::ast::Data;
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::{ext::IdentExt, visit::Visit, Error, Ident, LifetimeParam, Path, Type};
 
 use crate::{
     args::{self, RenameRuleExt, RenameTarget, SimpleObjectField, TypeDirectiveLocation},
     utils::{
-        gen_deprecation, gen_directive_calls, generate_guards, get_crate_name, get_rustdoc,
-        visible_fn, GeneratorResult,
+        gen_deprecation, gen_directive_calls, gen_type_info, generate_as_any, generate_guards,
+        get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
     },
 };
 
@@ -189,16 +189,17 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             }
         };
 
-        let visible = visible_fn(&field.visible);
+        let visible = visible_fn(&crate_name, &field.visible);
         let directives =
             gen_directive_calls(&field.directives, TypeDirectiveLocation::FieldDefinition);
         if !field.flatten {
+            let type_info = gen_type_info(&crate_name, ty, quote!(OutputType));
             schema_fields.push(quote! {
                 fields.insert(::std::borrow::ToOwned::to_owned(#field_name), #crate_name::registry::MetaField {
                     name: ::std::borrow::ToOwned::to_owned(#field_name),
                     description: #field_desc,
                     args: ::std::default::Default::default(),
-                    ty: <#ty as #crate_name::OutputType>::create_type_info(registry),
+                    ty: #type_info,
                     deprecation: #field_deprecation,
                     cache_control: #cache_control,
                     external: #external,
@@ -214,8 +215,9 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 });
             });
         } else {
+            let type_info = gen_type_info(&crate_name, ty, quote!(OutputType));
             schema_fields.push(quote! {
-                <#ty as #crate_name::OutputType>::create_type_info(registry);
+                #type_info;
                 if let #crate_name::registry::MetaType::Object { fields: obj_fields, .. } =
                     registry.create_fake_output_type::<#ty>() {
                     fields.extend(obj_fields);
@@ -234,10 +236,10 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         let with_function = derived.as_ref().and_then(|x| x.with.as_ref());
 
         let mut block = match !owned {
-            true => quote! {
+            true => quote_spanned! {base_ident.span()=>
                 &self.#base_ident
             },
-            false => quote! {
+            false => quote_spanned! {base_ident.span()=>
                 ::std::clone::Clone::clone(&self.#base_ident)
             },
         };
@@ -246,8 +248,8 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             (Some(_), Some(with)) => quote! {
                 #with(#block)
             },
-            (Some(_), None) => quote! {
-                ::std::convert::Into::into(#block)
+            (Some(_), None) => quote_spanned! {base_ident.span()=>
+                #crate_name::resolver_utils::IntoDerived::into_derived(#block)
             },
             (_, _) => block,
         };
@@ -309,7 +311,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         }
     };
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
 
     let mut concat_complex_fields = quote!();
     let mut complex_resolver = quote!();
@@ -331,6 +333,11 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         quote! { #crate_name::resolver_utils::resolve_container(ctx, self).await }
     };
 
+    let as_any = generate_as_any(
+        &object_args.generics,
+        &syn::parse_quote!(#ident #ty_generics),
+    );
+
     let expanded = if object_args.concretes.is_empty() {
         quote! {
             #[allow(clippy::all, clippy::pedantic)]
@@ -347,6 +354,8 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     #complex_resolver
                     ::std::result::Result::Ok(::std::option::Option::None)
                 }
+
+                #as_any
             }
 
             #[allow(clippy::all, clippy::pedantic)]
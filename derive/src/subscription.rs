@@ -1,8 +1,8 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{
-    ext::IdentExt, Block, Error, ImplItem, ItemImpl, ReturnType, Type, TypeImplTrait,
-    TypeParamBound,
+    ext::IdentExt, Block, Error, GenericArgument, ImplItem, ItemImpl, PathArguments, ReturnType,
+    Type, TypeImplTrait, TypeParamBound,
 };
 
 use crate::{
@@ -116,7 +116,7 @@ pub fn generate(
                     })
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
 
-                let visible = visible_fn(arg_visible);
+                let visible = visible_fn(&crate_name, arg_visible);
                 schema_args.push(quote! {
                     args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                             name: ::std::string::ToString::to_string(#name),
@@ -145,17 +145,27 @@ pub fn generate(
                     None => Default::default(),
                 };
 
+                let (param_value, redact) = if *secret {
+                    (
+                        quote!(secret_param_value),
+                        Some(quote!(.redact(&#crate_name::InputType::to_value(&#param_ident)))),
+                    )
+                } else {
+                    (quote!(param_value), None)
+                };
                 let validators = validator.clone().unwrap_or_default().create_validators(
                     &crate_name,
+                    &name,
                     quote!(&#ident),
-                    Some(quote!(.map_err(|err| err.into_server_error(__pos)))),
+                    Some(quote!(.map_err(|err| err #redact .into_server_error(__pos)))),
+                    Some(quote!(ctx)),
                 )?;
 
                 let mut non_mut_ident = ident.clone();
                 non_mut_ident.mutability = None;
                 get_params.push(quote! {
                     #[allow(non_snake_case, unused_mut)]
-                    let (__pos, mut #non_mut_ident) = ctx.param_value::<#ty>(#name, #default)?;
+                    let (__pos, mut #non_mut_ident) = ctx.#param_value::<#ty>(#name, #default)?;
                     #process_with
                     #validators
                     #[allow(non_snake_case)]
@@ -186,6 +196,20 @@ pub fn generate(
                 quote! { #res_ty }
             };
 
+            let item_is_result = stream_item_is_result(&res_ty);
+            if field.terminate_on_error && !item_is_result {
+                return Err(Error::new_spanned(
+                    &method.sig.output,
+                    "The `terminate_on_error` attribute requires a stream of `Result`",
+                )
+                .into());
+            }
+            let item_ty = if item_is_result {
+                quote! { <<#stream_ty as #crate_name::futures_util::stream::Stream>::Item as #crate_name::SubscriptionItemResult>::Item }
+            } else {
+                quote! { <#stream_ty as #crate_name::futures_util::stream::Stream>::Item }
+            };
+
             if let OutputType::Value(inner_ty) = &ty {
                 let block = &method.block;
                 let new_block = quote!({
@@ -200,7 +224,7 @@ pub fn generate(
                         .expect("invalid result type");
             }
 
-            let visible = visible_fn(&field.visible);
+            let visible = visible_fn(&crate_name, &field.visible);
             let complexity = if let Some(complexity) = &field.complexity {
                 let (variables, expr) = parse_complexity_expr(complexity.clone())?;
                 let mut parse_args = Vec::new();
@@ -254,7 +278,7 @@ pub fn generate(
                         #(#schema_args)*
                         args
                     },
-                    ty: <<#stream_ty as #crate_name::futures_util::stream::Stream>::Item as #crate_name::OutputType>::create_type_info(registry),
+                    ty: <#item_ty as #crate_name::OutputType>::create_type_info(registry),
                     deprecation: #field_deprecation,
                     cache_control: ::std::default::Default::default(),
                     external: false,
@@ -289,6 +313,48 @@ pub fn generate(
                 Some(code) => Some(generate_guards(&crate_name, code, guard_map_err)?),
                 None => None,
             };
+            let map_items = if item_is_result {
+                let terminate = if field.terminate_on_error {
+                    Some(quote! {
+                        let stream = #crate_name::futures_util::stream::StreamExt::scan(stream, false, |terminated, item| {
+                            if *terminated {
+                                return #crate_name::futures_util::future::ready(::std::option::Option::None);
+                            }
+                            *terminated = ::std::result::Result::is_err(&item);
+                            #crate_name::futures_util::future::ready(::std::option::Option::Some(item))
+                        });
+                    })
+                } else {
+                    None
+                };
+                Some(quote! {
+                    let stream = #crate_name::futures_util::stream::StreamExt::map(stream, #crate_name::SubscriptionItemResult::into_result);
+                    #terminate
+                })
+            } else {
+                None
+            };
+            let resolve_item = if item_is_result {
+                quote! {
+                    match msg {
+                        ::std::result::Result::Ok(value) => {
+                            #crate_name::OutputType::resolve(&value, &ctx_selection_set, &*field)
+                                .await
+                                .map(::std::option::Option::Some)
+                        }
+                        ::std::result::Result::Err(err) => {
+                            ::std::result::Result::Err(ctx_selection_set.set_error_path(err.into_server_error(field.pos)))
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    #crate_name::OutputType::resolve(&msg, &ctx_selection_set, &*field)
+                        .await
+                        .map(::std::option::Option::Some)
+                }
+            };
+
             let stream_fn = quote! {
                 let field_name = ::std::clone::Clone::clone(&ctx.item.node.response_key().node);
                 let field = ::std::sync::Arc::new(::std::clone::Clone::clone(&ctx.item));
@@ -299,6 +365,7 @@ pub fn generate(
                     #create_field_stream
                 };
                 let stream = f.await.map_err(|err| ctx.set_error_path(err))?;
+                #map_items
 
                 let pos = ctx.item.pos;
                 let schema_env = ::std::clone::Clone::clone(&ctx.schema_env);
@@ -326,15 +393,13 @@ pub fn generate(
                                 let ri = #crate_name::extensions::ResolveInfo {
                                     path_node: ctx_selection_set.path_node.as_ref().unwrap(),
                                     parent_type: &parent_type,
-                                    return_type: &<<#stream_ty as #crate_name::futures_util::stream::Stream>::Item as #crate_name::OutputType>::qualified_type_name(),
+                                    return_type: &<#item_ty as #crate_name::OutputType>::qualified_type_name(),
                                     name: field.node.name.node.as_str(),
                                     alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
                                     is_for_introspection: false,
                                 };
                                 let resolve_fut = async {
-                                    #crate_name::OutputType::resolve(&msg, &ctx_selection_set, &*field)
-                                        .await
-                                        .map(::std::option::Option::Some)
+                                    #resolve_item
                                 };
                                 #crate_name::futures_util::pin_mut!(resolve_fut);
                                 let mut resp = query_env.extensions.resolve(ri, &mut resolve_fut).await.map(|value| {
@@ -346,6 +411,7 @@ pub fn generate(
 
                                 use ::std::iter::Extend;
                                 resp.errors.extend(::std::mem::take(&mut *query_env.errors.lock().unwrap()));
+                                resp.extensions.extend(::std::mem::take(&mut *query_env.response_extensions.lock().unwrap()));
                                 resp
                             };
                             #crate_name::futures_util::pin_mut!(execute_fut);
@@ -382,7 +448,7 @@ pub fn generate(
         .into());
     }
 
-    let visible = visible_fn(&subscription_args.visible);
+    let visible = visible_fn(&crate_name, &subscription_args.visible);
 
     let expanded = quote! {
         #item_impl
@@ -429,3 +495,28 @@ pub fn generate(
 
     Ok(expanded.into())
 }
+
+/// Returns `true` if the type is `impl Stream<Item = Result<..>>`.
+fn stream_item_is_result(ty: &Type) -> bool {
+    let bounds = match ty {
+        Type::ImplTrait(TypeImplTrait { bounds, .. }) => bounds,
+        _ => return false,
+    };
+    for bound in bounds {
+        if let TypeParamBound::Trait(bound) = bound {
+            if let Some(PathArguments::AngleBracketed(args)) =
+                bound.path.segments.last().map(|segment| &segment.arguments)
+            {
+                for arg in &args.args {
+                    if let GenericArgument::AssocType(assoc) = arg {
+                        if assoc.ident == "Item" {
+                            return matches!(&assoc.ty, Type::Path(path)
+                                if path.path.segments.last().map(|segment| segment.ident == "Result").unwrap_or_default());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
@@ -30,7 +30,7 @@ pub fn generate(
     let desc = get_rustdoc(&item_fn.attrs)?
         .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
         .unwrap_or_else(|| quote!(::std::option::Option::None));
-    let visible = visible_fn(&directive_args.visible);
+    let visible = visible_fn(&crate_name, &directive_args.visible);
     let repeatable = directive_args.repeatable;
 
     let composable = match directive_args.composable.as_ref() {
@@ -89,7 +89,7 @@ pub fn generate(
                 }
             })
             .unwrap_or_else(|| quote! {::std::option::Option::None});
-        let visible = visible_fn(&visible);
+        let visible = visible_fn(&crate_name, &visible);
 
         schema_args.push(quote! {
             args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
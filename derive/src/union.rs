@@ -7,7 +7,10 @@ use syn::{visit_mut::VisitMut, Error, Type};
 
 use crate::{
     args::{self, RenameTarget},
-    utils::{get_crate_name, get_rustdoc, visible_fn, GeneratorResult, RemoveLifetime},
+    utils::{
+        gen_remote_conversion, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+        RemoveLifetime,
+    },
 };
 
 pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
@@ -155,7 +158,11 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         .into());
     }
 
-    let visible = visible_fn(&union_args.visible);
+    let visible = visible_fn(&crate_name, &union_args.visible);
+    let remote_conversion = union_args.remote.as_ref().map(|remote_ty| {
+        gen_remote_conversion(ident, &union_args.generics, remote_ty, &enum_names)
+    });
+
     let expanded = quote! {
         #(#type_into_impls)*
 
@@ -213,6 +220,8 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         }
 
         impl #impl_generics #crate_name::UnionType for #ident #ty_generics #where_clause {}
+
+        #remote_conversion
     };
 
     Ok(expanded.into())
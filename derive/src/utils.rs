@@ -1,17 +1,22 @@
 use std::collections::HashSet;
 
-use darling::FromMeta;
+use darling::{FromDeriveInput, FromMeta};
 use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_crate::{crate_name, FoundCrate};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::{
-    visit::Visit, visit_mut, visit_mut::VisitMut, Attribute, Error, Expr, ExprLit, ExprPath, FnArg,
-    Ident, ImplItemFn, Lifetime, Lit, LitStr, Meta, Pat, PatIdent, Type, TypeGroup, TypeParamBound,
-    TypeReference,
+    spanned::Spanned, visit::Visit, visit_mut, visit_mut::VisitMut, Attribute, DeriveInput, Error,
+    Expr, ExprLit, ExprPath, FnArg, Generics, Ident, ImplItemFn, Lifetime, Lit, LitStr, Meta, Pat,
+    PatIdent, PathArguments, Type, TypeGroup, TypeParamBound, TypeReference,
 };
 use thiserror::Error;
 
-use crate::args::{self, Deprecation, TypeDirectiveLocation, Visible};
+use crate::{
+    args::{
+        self, Cache, CacheKey, Deprecation, DurationLit, Retry, TypeDirectiveLocation, Visible,
+    },
+    attribute_keys,
+};
 
 #[derive(Error, Debug)]
 pub enum GeneratorError {
@@ -45,6 +50,15 @@ pub fn get_crate_name(internal: bool) -> TokenStream {
     }
 }
 
+/// Generates the registration of the type `ty` in the registry with
+/// `type_trait`, `OutputType` or `InputType`, spanned at the type so that the
+/// error of a type that is not a GraphQL type points at it.
+pub fn gen_type_info(crate_name: &TokenStream, ty: &Type, type_trait: TokenStream) -> TokenStream {
+    quote_spanned! {ty.span()=>
+        <#ty as #crate_name::#type_trait>::create_type_info(registry)
+    }
+}
+
 pub fn generate_guards(
     crate_name: &TokenStream,
     expr: &Expr,
@@ -55,7 +69,8 @@ pub fn generate_guards(
         #expr
     }};
     Ok(quote! {
-        #crate_name::Guard::check(&#code, &ctx).await #map_err ?;
+        #crate_name::Guard::check(&#code, &ctx).await
+            .map_err(|err| ctx.render_guard_error(err)) #map_err ?;
     })
 }
 
@@ -145,12 +160,20 @@ pub fn parse_graphql_attrs<T: FromMeta + Default>(
 ) -> GeneratorResult<Option<T>> {
     for attr in attrs {
         if attr.path().is_ident("graphql") {
+            attribute_keys::check_graphql_attrs::<T>(std::slice::from_ref(attr))?;
             return Ok(Some(T::from_meta(&attr.meta)?));
         }
     }
     Ok(None)
 }
 
+/// Parses the arguments of a derive macro, checking the keys of the
+/// attributes of the type with `T` and of its fields or variants with `M`.
+pub fn parse_derive_input<T: FromDeriveInput, M>(input: &DeriveInput) -> darling::Result<T> {
+    attribute_keys::check_derive_input::<T, M>(input)?;
+    T::from_derive_input(input)
+}
+
 pub fn remove_graphql_attrs(attrs: &mut Vec<Attribute>) {
     if let Some((idx, _)) = attrs
         .iter()
@@ -189,12 +212,150 @@ pub fn get_type_path_and_name(ty: &Type) -> GeneratorResult<(&Type, String)> {
     }
 }
 
-pub fn visible_fn(visible: &Option<Visible>) -> TokenStream {
+pub fn visible_fn(crate_name: &TokenStream, visible: &Option<Visible>) -> TokenStream {
     match visible {
         None | Some(Visible::None) => quote! { ::std::option::Option::None },
-        Some(Visible::HiddenAlways) => quote! { ::std::option::Option::Some(|_| false) },
-        Some(Visible::FnName(name)) => {
-            quote! { ::std::option::Option::Some(#name) }
+        Some(Visible::HiddenAlways) => quote! {
+            ::std::option::Option::Some(#crate_name::registry::MetaVisible::Hidden)
+        },
+        Some(Visible::FnName(name)) => quote! {
+            ::std::option::Option::Some(#crate_name::registry::MetaVisible::Fn(#name))
+        },
+        Some(Visible::Profiles(profiles)) => quote! {
+            ::std::option::Option::Some(#crate_name::registry::MetaVisible::Profiles(&[#(#profiles),*]))
+        },
+    }
+}
+
+/// Generates the statement that returns the resolved value of a field, which
+/// is cached if a cache policy is specified.
+pub fn generate_cached_field(
+    crate_name: &TokenStream,
+    cache: &Option<Cache>,
+    body: TokenStream,
+) -> TokenStream {
+    match cache {
+        Some(cache) => {
+            let DurationLit(ttl) = cache.ttl;
+            let ttl = ttl.as_millis() as u64;
+            let swr = cache
+                .swr
+                .as_ref()
+                .map(|DurationLit(swr)| swr.as_millis() as u64)
+                .unwrap_or_default();
+            let key_args = cache.key == CacheKey::Args;
+            quote! {
+                return #crate_name::resolver_utils::resolve_with_cache(
+                    ctx,
+                    &<Self as #crate_name::OutputType>::type_name(),
+                    #crate_name::resolver_utils::FieldCachePolicy {
+                        ttl: ::std::time::Duration::from_millis(#ttl),
+                        swr: ::std::time::Duration::from_millis(#swr),
+                        key_args: #key_args,
+                    },
+                    async move #body,
+                )
+                .await;
+            }
+        }
+        None => quote! {
+            return #body;
+        },
+    }
+}
+
+/// Generates the statement that binds the resolver future of a field to `f`,
+/// which retries the resolver if a retry policy is specified.
+pub fn generate_resolver_future(
+    crate_name: &TokenStream,
+    retry: &Option<Retry>,
+    body: TokenStream,
+) -> TokenStream {
+    match retry {
+        Some(retry) => {
+            let times = retry.times;
+            let backoff = retry
+                .backoff
+                .as_ref()
+                .map(|DurationLit(backoff)| backoff.as_millis() as u64)
+                .unwrap_or_default();
+            let jitter = retry.jitter;
+            let retry_if = match &retry.retry_if {
+                Some(retry_if) => quote! { ::std::option::Option::Some(#retry_if) },
+                None => quote! { ::std::option::Option::None },
+            };
+            quote! {
+                let f = #crate_name::resolver_utils::resolve_with_retry(
+                    ctx,
+                    #crate_name::resolver_utils::RetryPolicy {
+                        times: #times,
+                        backoff: ::std::time::Duration::from_millis(#backoff),
+                        jitter: #jitter,
+                        retry_if: #retry_if,
+                    },
+                    move || async move #body,
+                );
+            }
+        }
+        None => quote! {
+            let f = async move #body;
+        },
+    }
+}
+
+/// Generates the statement that awaits the resolver future `f` of a field
+/// and binds the result to `obj`, with the timeout of the field if specified.
+pub fn generate_await_obj(crate_name: &TokenStream, timeout: &Option<DurationLit>) -> TokenStream {
+    match timeout {
+        Some(DurationLit(timeout)) => {
+            let millis = timeout.as_millis() as u64;
+            quote! {
+                let obj = match #crate_name::resolver_utils::resolve_with_timeout(
+                    ctx,
+                    ::std::time::Duration::from_millis(#millis),
+                    f,
+                )
+                .await
+                .map_err(|err| ctx.set_error_path(err))?
+                {
+                    ::std::option::Option::Some(obj) => obj,
+                    ::std::option::Option::None => {
+                        return ::std::result::Result::Ok(::std::option::Option::Some(#crate_name::Value::Null));
+                    }
+                };
+            }
+        }
+        None => quote! {
+            let obj = f.await.map_err(|err| ctx.set_error_path(err))?;
+        },
+    }
+}
+
+/// Generates the `ContainerType::as_any` method of a container, which
+/// returns `None` unless the type is sized and `'static`, so that it can be
+/// extended with an `ObjectExtension`.
+pub fn generate_as_any(generics: &Generics, ty: &Type) -> TokenStream {
+    struct NotExtensible(bool);
+
+    impl<'a> Visit<'a> for NotExtensible {
+        fn visit_lifetime(&mut self, i: &'a Lifetime) {
+            self.0 |= i.ident != "static";
+        }
+
+        fn visit_type_trait_object(&mut self, _: &'a syn::TypeTraitObject) {
+            self.0 = true;
+        }
+    }
+
+    let mut not_extensible = NotExtensible(false);
+    not_extensible.visit_type(ty);
+    if !generics.params.is_empty() || not_extensible.0 {
+        return quote!();
+    }
+
+    quote! {
+        fn as_any(&self) -> ::std::option::Option<&(dyn ::std::any::Any + ::std::marker::Send + ::std::marker::Sync)> {
+            ::std::option::Option::Some(self)
         }
     }
 }
@@ -236,6 +397,52 @@ pub fn gen_deprecation(deprecation: &Deprecation, crate_name: &TokenStream) -> T
     }
 }
 
+/// Generates the conversions in both directions between the enum `ident`,
+/// whose variants wrap a single value, and the remote enum `remote_ty` with
+/// the same variants, converting the wrapped values with `Into`.
+pub fn gen_remote_conversion(
+    ident: &Ident,
+    generics: &Generics,
+    remote_ty: &Type,
+    variants: &[&Ident],
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let remote_path = turbofish(remote_ty);
+    quote! {
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #impl_generics ::std::convert::From<#ident #ty_generics> for #remote_ty #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                match value {
+                    #(#ident::#variants(value) => #remote_path::#variants(::std::convert::Into::into(value)),)*
+                }
+            }
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #impl_generics ::std::convert::From<#remote_ty> for #ident #ty_generics #where_clause {
+            fn from(value: #remote_ty) -> Self {
+                match value {
+                    #(#remote_path::#variants(value) => #ident::#variants(::std::convert::Into::into(value)),)*
+                }
+            }
+        }
+    }
+}
+
+/// Returns the type `ty` with its generic arguments in turbofish form, such as
+/// `Remote::<T>`, so that it can be followed by a variant in a path.
+fn turbofish(ty: &Type) -> Type {
+    let mut ty = ty.clone();
+    if let Type::Path(type_path) = &mut ty {
+        for segment in &mut type_path.path.segments {
+            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                args.colon2_token = Some(Default::default());
+            }
+        }
+    }
+    ty
+}
+
 pub fn extract_input_args<T: FromMeta + Default>(
     crate_name: &proc_macro2::TokenStream,
     method: &mut ImplItemFn,
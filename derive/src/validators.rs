@@ -1,7 +1,7 @@
 use darling::FromMeta;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Expr, Lit, Result};
+use syn::{Error, Expr, Lit, LitStr, Result};
 
 #[derive(Clone)]
 pub enum Number {
@@ -39,6 +39,10 @@ pub struct Validators {
     #[darling(default)]
     minimum: Option<Number>,
     #[darling(default)]
+    exclusive_maximum: Option<Number>,
+    #[darling(default)]
+    exclusive_minimum: Option<Number>,
+    #[darling(default)]
     max_length: Option<usize>,
     #[darling(default)]
     min_length: Option<usize>,
@@ -57,19 +61,44 @@ pub struct Validators {
     #[darling(default)]
     ip: bool,
     #[darling(default)]
+    uuid: bool,
+    #[darling(default)]
+    not_blank: bool,
+    #[darling(default)]
     regex: Option<String>,
+    #[darling(default)]
+    date_format: Option<String>,
+    #[darling(default)]
+    one_of: Option<Vec<LitStr>>,
     #[darling(default, multiple)]
     custom: Vec<Expr>,
+    #[darling(default, multiple)]
+    async_custom: Vec<Expr>,
     #[darling(default)]
     list: bool,
+    #[darling(default)]
+    each: Option<Box<Validators>>,
 }
 
 impl Validators {
     pub fn create_validators(
         &self,
         crate_name: &TokenStream,
+        name: &str,
         value: TokenStream,
         map_err: Option<TokenStream>,
+        ctx: Option<TokenStream>,
+    ) -> Result<TokenStream> {
+        self.create_validators_with_path(crate_name, quote!(#name), value, map_err, ctx)
+    }
+
+    fn create_validators_with_path(
+        &self,
+        crate_name: &TokenStream,
+        path: TokenStream,
+        value: TokenStream,
+        map_err: Option<TokenStream>,
+        ctx: Option<TokenStream>,
     ) -> Result<TokenStream> {
         let mut list_validators = Vec::new();
         let mut elem_validators = Vec::new();
@@ -111,6 +140,18 @@ impl Validators {
             });
         }
 
+        if let Some(n) = &self.exclusive_maximum {
+            elem_validators.push(quote! {
+                #crate_name::validators::exclusive_maximum(__raw_value, #n)
+            });
+        }
+
+        if let Some(n) = &self.exclusive_minimum {
+            elem_validators.push(quote! {
+                #crate_name::validators::exclusive_minimum(__raw_value, #n)
+            });
+        }
+
         if let Some(n) = &self.max_length {
             elem_validators.push(quote! {
                 #crate_name::validators::max_length(__raw_value, #n)
@@ -153,12 +194,36 @@ impl Validators {
             });
         }
 
+        if self.uuid {
+            elem_validators.push(quote! {
+                #crate_name::validators::uuid(__raw_value)
+            });
+        }
+
+        if self.not_blank {
+            elem_validators.push(quote! {
+                #crate_name::validators::not_blank(__raw_value)
+            });
+        }
+
         if let Some(re) = &self.regex {
             elem_validators.push(quote! {
                 #crate_name::validators::regex(__raw_value, #re)
             });
         }
 
+        if let Some(format) = &self.date_format {
+            elem_validators.push(quote! {
+                #crate_name::validators::date_format(__raw_value, #format)
+            });
+        }
+
+        if let Some(values) = &self.one_of {
+            elem_validators.push(quote! {
+                #crate_name::validators::one_of(__raw_value, &[#(#values),*])
+            });
+        }
+
         if !list_validators.is_empty() {
             codes.push(quote! {
                 if let ::std::option::Option::Some(__raw_value) = #crate_name::InputType::as_raw_value(#value) {
@@ -187,13 +252,29 @@ impl Validators {
             }
         }
 
-        for expr in &self.custom {
+        let mut checks = self
+            .custom
+            .iter()
+            .map(|expr| quote!(#crate_name::CustomValidator::check(&(#expr), __raw_value)))
+            .collect::<Vec<_>>();
+        for expr in &self.async_custom {
+            let ctx = ctx.as_ref().ok_or_else(|| {
+                Error::new_spanned(
+                    expr,
+                    "`async_custom` validators are only supported on the arguments of objects, complex objects and subscriptions",
+                )
+            })?;
+            checks.push(quote! {
+                #crate_name::AsyncCustomValidator::check(&(#expr), #ctx, __raw_value).await
+            });
+        }
+        for check in checks {
             if self.list {
                 codes.push(quote! {
                     if let ::std::option::Option::Some(value) = #crate_name::InputType::as_raw_value(#value) {
                         for __item in value {
                             if let ::std::option::Option::Some(__raw_value) = #crate_name::InputType::as_raw_value(__item) {
-                                #crate_name::CustomValidator::check(&(#expr), __raw_value) #map_err ?;
+                                #check #map_err ?;
                             }
                         }
                     }
@@ -201,7 +282,27 @@ impl Validators {
             } else {
                 codes.push(quote! {
                     if let ::std::option::Option::Some(__raw_value) = #crate_name::InputType::as_raw_value(#value) {
-                        #crate_name::CustomValidator::check(&(#expr), __raw_value) #map_err ?;
+                        #check #map_err ?;
+                    }
+                });
+            }
+        }
+
+        if let Some(each) = &self.each {
+            let item_validators = each.create_validators_with_path(
+                crate_name,
+                quote!(__path()),
+                quote!(__item),
+                Some(quote!(.map_err(|err| err.with_fields([__path()])) #map_err)),
+                ctx,
+            )?;
+            if !item_validators.is_empty() {
+                codes.push(quote! {
+                    if let ::std::option::Option::Some(value) = #crate_name::InputType::as_raw_value(#value) {
+                        for (__index, __item) in ::std::iter::Iterator::enumerate(::std::iter::IntoIterator::into_iter(value)) {
+                            let __path = || ::std::format!("{}.{}", #path, __index);
+                            #item_validators
+                        }
                     }
                 });
             }
@@ -12,7 +12,7 @@ use actix_web::{
     http::{Method, StatusCode},
     Error, FromRequest, HttpRequest, HttpResponse, Responder, Result,
 };
-use async_graphql::{http::MultipartOptions, ParseRequestError};
+use async_graphql::{http::MultipartOptions, ClientInfo, ParseRequestError};
 use futures_util::{
     future::{self, FutureExt},
     StreamExt, TryStreamExt,
@@ -73,11 +73,25 @@ impl FromRequest for GraphQLBatchRequest {
             .app_data::<MultipartOptions>()
             .cloned()
             .unwrap_or_default();
+        let header = |name| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let client_info = ClientInfo::from_headers(
+            header(ClientInfo::NAME_HEADER),
+            header(ClientInfo::VERSION_HEADER),
+        );
 
         if req.method() == Method::GET {
             let res = async_graphql::http::parse_query_string(req.query_string())
                 .map_err(|err| io::Error::new(ErrorKind::Other, err));
-            Box::pin(async move { Ok(Self(async_graphql::BatchRequest::Single(res?))) })
+            Box::pin(async move {
+                Ok(Self(with_client_info(
+                    async_graphql::BatchRequest::Single(res?),
+                    client_info,
+                )))
+            })
         } else if req.method() == Method::POST {
             let content_type = req
                 .headers()
@@ -98,7 +112,7 @@ impl FromRequest for GraphQLBatchRequest {
             });
 
             Box::pin(async move {
-                Ok(GraphQLBatchRequest(
+                Ok(GraphQLBatchRequest(with_client_info(
                     async_graphql::http::receive_batch_body(
                         content_type,
                         rx.map_err(|e| match e {
@@ -131,7 +145,8 @@ impl FromRequest for GraphQLBatchRequest {
                         }
                         _ => actix_web::error::ErrorBadRequest(err),
                     })?,
-                ))
+                    client_info,
+                )))
             })
         } else {
             Box::pin(async move {
@@ -143,6 +158,16 @@ impl FromRequest for GraphQLBatchRequest {
     }
 }
 
+fn with_client_info(
+    request: async_graphql::BatchRequest,
+    client_info: Option<ClientInfo>,
+) -> async_graphql::BatchRequest {
+    match client_info {
+        Some(client_info) => request.data(client_info),
+        None => request,
+    }
+}
+
 /// Responder for a GraphQL response.
 ///
 /// This contains a batch response, but since regular responses are a type of
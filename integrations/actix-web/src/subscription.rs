@@ -1,6 +1,6 @@
 use std::{
     future::Future,
-    str::FromStr,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -12,10 +12,13 @@ use actix_http::{error::PayloadError, ws};
 use actix_web::{web::Bytes, Error, HttpRequest, HttpResponse};
 use actix_web_actors::ws::{CloseReason, Message, ProtocolError, WebsocketContext};
 use async_graphql::{
-    http::{WebSocket, WebSocketProtocols, WsMessage, ALL_WEBSOCKET_PROTOCOLS},
+    http::{WebSocket, WebSocketProtocol, WebSocketProtocolSet, WsMessage},
     Data, Executor, Result,
 };
-use futures_util::{future::Ready, stream::Stream};
+use futures_util::{
+    future::{BoxFuture, Ready},
+    stream::Stream,
+};
 
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
@@ -24,6 +27,9 @@ const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
 #[error("failed to parse graphql protocol")]
 pub struct ParseGraphQLProtocolError;
 
+type OnConnRefreshFn =
+    Box<dyn FnMut(serde_json::Value) -> BoxFuture<'static, async_graphql::Result<Data>> + Send>;
+
 type DefaultOnConnInitType = fn(serde_json::Value) -> Ready<async_graphql::Result<Data>>;
 
 fn default_on_connection_init(_: serde_json::Value) -> Ready<async_graphql::Result<Data>> {
@@ -35,6 +41,8 @@ pub struct GraphQLSubscription<E, OnInit> {
     executor: E,
     data: Data,
     on_connection_init: OnInit,
+    on_connection_refresh: Option<OnConnRefreshFn>,
+    protocols: WebSocketProtocolSet,
 }
 
 impl<E> GraphQLSubscription<E, DefaultOnConnInitType> {
@@ -44,6 +52,8 @@ impl<E> GraphQLSubscription<E, DefaultOnConnInitType> {
             executor,
             data: Default::default(),
             on_connection_init: default_on_connection_init,
+            on_connection_refresh: None,
+            protocols: WebSocketProtocolSet::default(),
         }
     }
 }
@@ -79,6 +89,31 @@ where
             executor: self.executor,
             data: self.data,
             on_connection_init: callback,
+            on_connection_refresh: self.on_connection_refresh,
+            protocols: self.protocols,
+        }
+    }
+
+    /// Specify the protocols accepted by the subscription actor, the built-in
+    /// protocols by default.
+    #[must_use]
+    pub fn with_protocols(self, protocols: WebSocketProtocolSet) -> Self {
+        Self { protocols, ..self }
+    }
+
+    /// Specify a callback function to be called when the client refreshes the
+    /// connection, usually to re-validate its credentials.
+    ///
+    /// See [`async_graphql::http::WebSocket::on_connection_refresh`].
+    #[must_use]
+    pub fn on_connection_refresh<F, Fut>(self, mut callback: F) -> Self
+    where
+        F: FnMut(serde_json::Value) -> Fut + Send + 'static,
+        Fut: Future<Output = async_graphql::Result<Data>> + Send + 'static,
+    {
+        Self {
+            on_connection_refresh: Some(Box::new(move |payload| Box::pin(callback(payload)))),
+            ..self
         }
     }
 
@@ -91,12 +126,9 @@ where
             .headers()
             .get("sec-websocket-protocol")
             .and_then(|value| value.to_str().ok())
-            .and_then(|protocols| {
-                protocols
-                    .split(',')
-                    .find_map(|p| WebSocketProtocols::from_str(p.trim()).ok())
-            })
+            .and_then(|value| self.protocols.negotiate(value))
             .ok_or_else(|| actix_web::error::ErrorBadRequest(ParseGraphQLProtocolError))?;
+        let sec_websocket_protocols = self.protocols.sec_websocket_protocols().collect::<Vec<_>>();
 
         let actor = GraphQLSubscriptionActor {
             executor: self.executor,
@@ -105,11 +137,12 @@ where
             last_heartbeat: Instant::now(),
             messages: None,
             on_connection_init: Some(self.on_connection_init),
+            on_connection_refresh: self.on_connection_refresh,
             continuation: Vec::new(),
         };
 
         actix_web_actors::ws::WsResponseBuilder::new(actor, request, stream)
-            .protocols(&ALL_WEBSOCKET_PROTOCOLS)
+            .protocols(&sec_websocket_protocols)
             .start()
     }
 }
@@ -117,10 +150,11 @@ where
 struct GraphQLSubscriptionActor<E, OnInit> {
     executor: E,
     data: Option<Data>,
-    protocol: WebSocketProtocols,
+    protocol: Arc<dyn WebSocketProtocol>,
     last_heartbeat: Instant,
     messages: Option<async_channel::Sender<Vec<u8>>>,
     on_connection_init: Option<OnInit>,
+    on_connection_refresh: Option<OnConnRefreshFn>,
     continuation: Vec<u8>,
 }
 
@@ -153,9 +187,13 @@ where
 
         let (tx, rx) = async_channel::unbounded();
 
-        WebSocket::new(self.executor.clone(), rx, self.protocol)
+        let mut stream = WebSocket::new(self.executor.clone(), rx, self.protocol.clone())
             .connection_data(self.data.take().unwrap())
-            .on_connection_init(self.on_connection_init.take().unwrap())
+            .on_connection_init(self.on_connection_init.take().unwrap());
+        if let Some(on_connection_refresh) = self.on_connection_refresh.take() {
+            stream = stream.on_connection_refresh(on_connection_refresh);
+        }
+        stream
             .into_actor(self)
             .map(|response, _act, ctx| match response {
                 WsMessage::Text(text) => ctx.text(text),
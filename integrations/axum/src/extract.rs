@@ -1,6 +1,8 @@
 use std::{io::ErrorKind, marker::PhantomData};
 
-use async_graphql::{futures_util::TryStreamExt, http::MultipartOptions, ParseRequestError};
+use async_graphql::{
+    futures_util::TryStreamExt, http::MultipartOptions, ClientInfo, ParseRequestError,
+};
 use axum::{
     extract::{BodyStream, FromRequest},
     http::{self, Method, Request},
@@ -107,7 +109,8 @@ where
     type Rejection = R;
 
     async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
-        if let (&Method::GET, uri) = (req.method(), req.uri()) {
+        let client_info = ClientInfo::from_header_map(req.headers());
+        let request = if let (&Method::GET, uri) = (req.method(), req.uri()) {
             let res = async_graphql::http::parse_query_string(uri.query().unwrap_or_default())
                 .map_err(|err| {
                     ParseRequestError::Io(std::io::Error::new(
@@ -115,7 +118,7 @@ where
                         format!("failed to parse graphql request from uri query: {}", err),
                     ))
                 });
-            Ok(Self(async_graphql::BatchRequest::Single(res?), PhantomData))
+            async_graphql::BatchRequest::Single(res?)
         } else {
             let content_type = req
                 .headers()
@@ -132,15 +135,19 @@ where
                 })?
                 .map_err(|err| std::io::Error::new(ErrorKind::Other, err.to_string()));
             let body_reader = tokio_util::io::StreamReader::new(body_stream).compat();
-            Ok(Self(
-                async_graphql::http::receive_batch_body(
-                    content_type,
-                    body_reader,
-                    MultipartOptions::default(),
-                )
-                .await?,
-                PhantomData,
-            ))
-        }
+            async_graphql::http::receive_batch_body(
+                content_type,
+                body_reader,
+                MultipartOptions::default(),
+            )
+            .await?
+        };
+        Ok(Self(
+            match client_info {
+                Some(client_info) => request.data(client_info),
+                None => request,
+            },
+            PhantomData,
+        ))
     }
 }
@@ -1,8 +1,8 @@
-use std::{borrow::Cow, convert::Infallible, future::Future, str::FromStr};
+use std::{borrow::Cow, convert::Infallible, fmt, future::Future, sync::Arc};
 
 use async_graphql::{
     futures_util::task::{Context, Poll},
-    http::{WebSocketProtocols, WsMessage, ALL_WEBSOCKET_PROTOCOLS},
+    http::{WebSocketProtocol, WebSocketProtocolSet, WsMessage},
     Data, Executor, Result,
 };
 use axum::{
@@ -25,9 +25,30 @@ use tower_service::Service;
 
 /// A GraphQL protocol extractor.
 ///
-/// It extract GraphQL protocol from `SEC_WEBSOCKET_PROTOCOL` header.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct GraphQLProtocol(WebSocketProtocols);
+/// It extract GraphQL protocol from `SEC_WEBSOCKET_PROTOCOL` header, which is
+/// negotiated with the [`WebSocketProtocolSet`] of the request extensions, or
+/// the built-in protocols if there is none.
+#[derive(Clone)]
+pub struct GraphQLProtocol(Arc<dyn WebSocketProtocol>);
+
+impl GraphQLProtocol {
+    fn negotiate(parts: &Parts, protocols: &WebSocketProtocolSet) -> Option<Self> {
+        parts
+            .headers
+            .get(http::header::SEC_WEBSOCKET_PROTOCOL)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| protocols.negotiate(value))
+            .map(Self)
+    }
+}
+
+impl fmt::Debug for GraphQLProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("GraphQLProtocol")
+            .field(&self.0.sec_websocket_protocol())
+            .finish()
+    }
+}
 
 #[async_trait::async_trait]
 impl<S> FromRequestParts<S> for GraphQLProtocol
@@ -37,23 +58,19 @@ where
     type Rejection = StatusCode;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        parts
-            .headers
-            .get(http::header::SEC_WEBSOCKET_PROTOCOL)
-            .and_then(|value| value.to_str().ok())
-            .and_then(|protocols| {
-                protocols
-                    .split(',')
-                    .find_map(|p| WebSocketProtocols::from_str(p.trim()).ok())
-            })
-            .map(Self)
-            .ok_or(StatusCode::BAD_REQUEST)
+        let protocols = parts
+            .extensions
+            .get::<WebSocketProtocolSet>()
+            .cloned()
+            .unwrap_or_default();
+        Self::negotiate(parts, &protocols).ok_or(StatusCode::BAD_REQUEST)
     }
 }
 
 /// A GraphQL subscription service.
 pub struct GraphQLSubscription<E> {
     executor: E,
+    protocols: WebSocketProtocolSet,
 }
 
 impl<E> Clone for GraphQLSubscription<E>
@@ -63,6 +80,7 @@ where
     fn clone(&self) -> Self {
         Self {
             executor: self.executor.clone(),
+            protocols: self.protocols.clone(),
         }
     }
 }
@@ -73,7 +91,17 @@ where
 {
     /// Create a GraphQL subscription service.
     pub fn new(executor: E) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            protocols: WebSocketProtocolSet::default(),
+        }
+    }
+
+    /// Specify the protocols accepted by the service, the built-in protocols
+    /// by default.
+    #[must_use]
+    pub fn with_protocols(self, protocols: WebSocketProtocolSet) -> Self {
+        Self { protocols, ..self }
     }
 }
 
@@ -92,13 +120,14 @@ where
 
     fn call(&mut self, req: Request<B>) -> Self::Future {
         let executor = self.executor.clone();
+        let protocols = self.protocols.clone();
 
         Box::pin(async move {
             let (mut parts, _body) = req.into_parts();
 
-            let protocol = match GraphQLProtocol::from_request_parts(&mut parts, &()).await {
-                Ok(protocol) => protocol,
-                Err(err) => return Ok(err.into_response().map(boxed)),
+            let protocol = match GraphQLProtocol::negotiate(&parts, &protocols) {
+                Some(protocol) => protocol,
+                None => return Ok(StatusCode::BAD_REQUEST.into_response().map(boxed)),
             };
             let upgrade = match WebSocketUpgrade::from_request_parts(&mut parts, &()).await {
                 Ok(protocol) => protocol,
@@ -108,7 +137,12 @@ where
             let executor = executor.clone();
 
             let resp = upgrade
-                .protocols(ALL_WEBSOCKET_PROTOCOLS)
+                .protocols(
+                    protocols
+                        .sec_websocket_protocols()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>(),
+                )
                 .on_upgrade(move |stream| {
                     GraphQLWebSocket::new(stream, executor, protocol).serve()
                 });
@@ -117,6 +151,9 @@ where
     }
 }
 
+type OnConnRefreshFn =
+    Box<dyn FnMut(serde_json::Value) -> BoxFuture<'static, async_graphql::Result<Data>> + Send>;
+
 type DefaultOnConnInitType = fn(serde_json::Value) -> Ready<async_graphql::Result<Data>>;
 
 fn default_on_connection_init(_: serde_json::Value) -> Ready<async_graphql::Result<Data>> {
@@ -130,6 +167,7 @@ pub struct GraphQLWebSocket<Sink, Stream, E, OnConnInit> {
     executor: E,
     data: Data,
     on_connection_init: OnConnInit,
+    on_connection_refresh: Option<OnConnRefreshFn>,
     protocol: GraphQLProtocol,
 }
 
@@ -164,6 +202,7 @@ where
             executor,
             data: Data::default(),
             on_connection_init: default_on_connection_init,
+            on_connection_refresh: None,
             protocol,
         }
     }
@@ -204,10 +243,27 @@ where
             executor: self.executor,
             data: self.data,
             on_connection_init: callback,
+            on_connection_refresh: self.on_connection_refresh,
             protocol: self.protocol,
         }
     }
 
+    /// Specify a callback function to be called when the client refreshes the
+    /// connection, usually to re-validate its credentials.
+    ///
+    /// See [`async_graphql::http::WebSocket::on_connection_refresh`].
+    #[must_use]
+    pub fn on_connection_refresh<F, Fut>(self, mut callback: F) -> Self
+    where
+        F: FnMut(serde_json::Value) -> Fut + Send + 'static,
+        Fut: Future<Output = async_graphql::Result<Data>> + Send + 'static,
+    {
+        Self {
+            on_connection_refresh: Some(Box::new(move |payload| Box::pin(callback(payload)))),
+            ..self
+        }
+    }
+
     /// Processing subscription requests.
     pub async fn serve(self) {
         let input = self
@@ -223,17 +279,20 @@ where
             })
             .map(Message::into_data);
 
-        let stream =
+        let mut stream =
             async_graphql::http::WebSocket::new(self.executor.clone(), input, self.protocol.0)
                 .connection_data(self.data)
-                .on_connection_init(self.on_connection_init)
-                .map(|msg| match msg {
-                    WsMessage::Text(text) => Message::Text(text),
-                    WsMessage::Close(code, status) => Message::Close(Some(CloseFrame {
-                        code,
-                        reason: Cow::from(status),
-                    })),
-                });
+                .on_connection_init(self.on_connection_init);
+        if let Some(on_connection_refresh) = self.on_connection_refresh {
+            stream = stream.on_connection_refresh(on_connection_refresh);
+        }
+        let stream = stream.map(|msg| match msg {
+            WsMessage::Text(text) => Message::Text(text),
+            WsMessage::Close(code, status) => Message::Close(Some(CloseFrame {
+                code,
+                reason: Cow::from(status),
+            })),
+        });
 
         let sink = self.sink;
         futures_util::pin_mut!(stream, sink);
@@ -1,4 +1,4 @@
-use async_graphql::http::MultipartOptions;
+use async_graphql::{http::MultipartOptions, ClientInfo};
 use poem::{
     async_trait,
     error::BadRequest,
@@ -66,26 +66,29 @@ pub struct GraphQLBatchRequest(pub async_graphql::BatchRequest);
 #[async_trait]
 impl<'a> FromRequest<'a> for GraphQLBatchRequest {
     async fn from_request(req: &'a Request, body: &mut RequestBody) -> Result<Self> {
-        if req.method() == Method::GET {
+        let client_info = ClientInfo::from_header_map(req.headers());
+        let request = if req.method() == Method::GET {
             let req =
                 async_graphql::http::parse_query_string(req.uri().query().unwrap_or_default())
                     .map_err(BadRequest)?;
-            Ok(Self(async_graphql::BatchRequest::Single(req)))
+            async_graphql::BatchRequest::Single(req)
         } else {
             let content_type = req
                 .headers()
                 .get(header::CONTENT_TYPE)
                 .and_then(|value| value.to_str().ok())
                 .map(ToString::to_string);
-            Ok(Self(
-                async_graphql::http::receive_batch_body(
-                    content_type,
-                    body.take()?.into_async_read().compat(),
-                    MultipartOptions::default(),
-                )
-                .await
-                .map_err(BadRequest)?,
-            ))
-        }
+            async_graphql::http::receive_batch_body(
+                content_type,
+                body.take()?.into_async_read().compat(),
+                MultipartOptions::default(),
+            )
+            .await
+            .map_err(BadRequest)?
+        };
+        Ok(Self(match client_info {
+            Some(client_info) => request.data(client_info),
+            None => request,
+        }))
     }
 }
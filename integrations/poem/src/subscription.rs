@@ -1,11 +1,11 @@
-use std::{io::Error as IoError, str::FromStr};
+use std::{fmt, io::Error as IoError, sync::Arc};
 
 use async_graphql::{
-    http::{WebSocketProtocols, WsMessage, ALL_WEBSOCKET_PROTOCOLS},
+    http::{WebSocketProtocol, WebSocketProtocolSet, WsMessage},
     Data, Executor,
 };
 use futures_util::{
-    future::{self, Ready},
+    future::{self, BoxFuture, Ready},
     stream::{SplitSink, SplitStream},
     Future, Sink, SinkExt, Stream, StreamExt,
 };
@@ -18,26 +18,42 @@ use poem::{
 
 /// A GraphQL protocol extractor.
 ///
-/// It extract GraphQL protocol from `SEC_WEBSOCKET_PROTOCOL` header.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct GraphQLProtocol(pub WebSocketProtocols);
+/// It extract GraphQL protocol from `SEC_WEBSOCKET_PROTOCOL` header, which is
+/// negotiated with the [`WebSocketProtocolSet`] of the request data, or the
+/// built-in protocols if there is none.
+#[derive(Clone)]
+pub struct GraphQLProtocol(pub Arc<dyn WebSocketProtocol>);
 
-#[poem::async_trait]
-impl<'a> FromRequest<'a> for GraphQLProtocol {
-    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+impl GraphQLProtocol {
+    fn negotiate(req: &Request, protocols: &WebSocketProtocolSet) -> Result<Self> {
         req.headers()
             .get(http::header::SEC_WEBSOCKET_PROTOCOL)
             .and_then(|value| value.to_str().ok())
-            .and_then(|protocols| {
-                protocols
-                    .split(',')
-                    .find_map(|p| WebSocketProtocols::from_str(p.trim()).ok())
-            })
+            .and_then(|value| protocols.negotiate(value))
             .map(Self)
             .ok_or_else(|| Error::from_status(StatusCode::BAD_REQUEST))
     }
 }
 
+impl fmt::Debug for GraphQLProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("GraphQLProtocol")
+            .field(&self.0.sec_websocket_protocol())
+            .finish()
+    }
+}
+
+#[poem::async_trait]
+impl<'a> FromRequest<'a> for GraphQLProtocol {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        let protocols = req
+            .data::<WebSocketProtocolSet>()
+            .cloned()
+            .unwrap_or_default();
+        Self::negotiate(req, &protocols)
+    }
+}
+
 /// A GraphQL subscription endpoint.
 ///
 /// # Example
@@ -73,12 +89,23 @@ impl<'a> FromRequest<'a> for GraphQLProtocol {
 /// ```
 pub struct GraphQLSubscription<E> {
     executor: E,
+    protocols: WebSocketProtocolSet,
 }
 
 impl<E> GraphQLSubscription<E> {
     /// Create a GraphQL subscription endpoint.
     pub fn new(executor: E) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            protocols: WebSocketProtocolSet::default(),
+        }
+    }
+
+    /// Specify the protocols accepted by the endpoint, the built-in protocols
+    /// by default.
+    #[must_use]
+    pub fn with_protocols(self, protocols: WebSocketProtocolSet) -> Self {
+        Self { protocols, ..self }
     }
 }
 
@@ -92,17 +119,25 @@ where
     async fn call(&self, req: Request) -> Result<Self::Output> {
         let (req, mut body) = req.split();
         let websocket = WebSocket::from_request(&req, &mut body).await?;
-        let protocol = GraphQLProtocol::from_request(&req, &mut body).await?;
+        let protocol = GraphQLProtocol::negotiate(&req, &self.protocols)?;
         let executor = self.executor.clone();
 
         let resp = websocket
-            .protocols(ALL_WEBSOCKET_PROTOCOLS)
+            .protocols(
+                self.protocols
+                    .sec_websocket_protocols()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+            )
             .on_upgrade(move |stream| GraphQLWebSocket::new(stream, executor, protocol).serve())
             .into_response();
         Ok(resp)
     }
 }
 
+type OnConnRefreshFn =
+    Box<dyn FnMut(serde_json::Value) -> BoxFuture<'static, async_graphql::Result<Data>> + Send>;
+
 type DefaultOnConnInitType = fn(serde_json::Value) -> Ready<async_graphql::Result<Data>>;
 
 fn default_on_connection_init(_: serde_json::Value) -> Ready<async_graphql::Result<Data>> {
@@ -116,6 +151,7 @@ pub struct GraphQLWebSocket<Sink, Stream, E, OnConnInit> {
     executor: E,
     data: Data,
     on_connection_init: OnConnInit,
+    on_connection_refresh: Option<OnConnRefreshFn>,
     protocol: GraphQLProtocol,
 }
 
@@ -150,6 +186,7 @@ where
             executor,
             data: Data::default(),
             on_connection_init: default_on_connection_init,
+            on_connection_refresh: None,
             protocol,
         }
     }
@@ -190,10 +227,27 @@ where
             executor: self.executor,
             data: self.data,
             on_connection_init: callback,
+            on_connection_refresh: self.on_connection_refresh,
             protocol: self.protocol,
         }
     }
 
+    /// Specify a callback function to be called when the client refreshes the
+    /// connection, usually to re-validate its credentials.
+    ///
+    /// See [`async_graphql::http::WebSocket::on_connection_refresh`].
+    #[must_use]
+    pub fn on_connection_refresh<F, Fut>(self, mut callback: F) -> Self
+    where
+        F: FnMut(serde_json::Value) -> Fut + Send + 'static,
+        Fut: Future<Output = async_graphql::Result<Data>> + Send + 'static,
+    {
+        Self {
+            on_connection_refresh: Some(Box::new(move |payload| Box::pin(callback(payload)))),
+            ..self
+        }
+    }
+
     /// Processing subscription requests.
     pub async fn serve(self) {
         let stream = self
@@ -209,14 +263,17 @@ where
             })
             .map(Message::into_bytes);
 
-        let stream =
+        let mut stream =
             async_graphql::http::WebSocket::new(self.executor.clone(), stream, self.protocol.0)
                 .connection_data(self.data)
-                .on_connection_init(self.on_connection_init)
-                .map(|msg| match msg {
-                    WsMessage::Text(text) => Message::text(text),
-                    WsMessage::Close(code, status) => Message::close_with(code, status),
-                });
+                .on_connection_init(self.on_connection_init);
+        if let Some(on_connection_refresh) = self.on_connection_refresh {
+            stream = stream.on_connection_refresh(on_connection_refresh);
+        }
+        let stream = stream.map(|msg| match msg {
+            WsMessage::Text(text) => Message::text(text),
+            WsMessage::Close(code, status) => Message::close_with(code, status),
+        });
 
         let sink = self.sink;
         futures_util::pin_mut!(stream, sink);
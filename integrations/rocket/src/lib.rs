@@ -15,7 +15,7 @@
 use core::any::Any;
 use std::io::Cursor;
 
-use async_graphql::{http::MultipartOptions, Executor, ParseRequestError};
+use async_graphql::{http::MultipartOptions, ClientInfo, Executor, ParseRequestError};
 use rocket::{
     data::{self, Data, FromData, ToByteUnit},
     form::FromForm,
@@ -66,8 +66,15 @@ impl<'r> FromData<'r> for GraphQLBatchRequest {
         )
         .await;
 
+        let client_info = ClientInfo::from_headers(
+            req.headers().get_one(ClientInfo::NAME_HEADER),
+            req.headers().get_one(ClientInfo::VERSION_HEADER),
+        );
         match request {
-            Ok(request) => data::Outcome::Success(Self(request)),
+            Ok(request) => data::Outcome::Success(Self(match client_info {
+                Some(client_info) => request.data(client_info),
+                None => request,
+            })),
             Err(e) => data::Outcome::Failure((
                 match e {
                     ParseRequestError::PayloadTooLarge => Status::PayloadTooLarge,
@@ -11,7 +11,7 @@
 #[cfg(feature = "websocket")]
 mod subscription;
 
-use async_graphql::{http::MultipartOptions, Executor, ParseRequestError};
+use async_graphql::{http::MultipartOptions, ClientInfo, Executor, ParseRequestError};
 #[cfg(feature = "websocket")]
 pub use subscription::GraphQLSubscription;
 use tide::{
@@ -124,7 +124,17 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
     mut request: Request<State>,
     opts: MultipartOptions,
 ) -> tide::Result<async_graphql::BatchRequest> {
-    if request.method() == Method::Get {
+    let header = |name| {
+        request
+            .header(name)
+            .and_then(|values| values.get(0))
+            .map(HeaderValue::as_str)
+    };
+    let client_info = ClientInfo::from_headers(
+        header(ClientInfo::NAME_HEADER),
+        header(ClientInfo::VERSION_HEADER),
+    );
+    let batch_request = if request.method() == Method::Get {
         async_graphql::http::parse_query_string(request.url().query().unwrap_or_default())
             .map(Into::into)
             .map_err(|err| tide::Error::new(StatusCode::BadRequest, err))
@@ -151,7 +161,11 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
             StatusCode::MethodNotAllowed,
             "GraphQL only supports GET and POST requests",
         ))
-    }
+    }?;
+    Ok(match client_info {
+        Some(client_info) => batch_request.data(client_info),
+        None => batch_request,
+    })
 }
 
 /// Convert a GraphQL response to a Tide response.
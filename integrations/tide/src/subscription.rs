@@ -1,10 +1,17 @@
-use std::{future::Future, str::FromStr};
+use std::{future::Future, sync::Arc};
 
 use async_graphql::{
-    http::{WebSocket as AGWebSocket, WebSocketProtocols, WsMessage, ALL_WEBSOCKET_PROTOCOLS},
+    http::{
+        WebSocket as AGWebSocket, WebSocketProtocol, WebSocketProtocolSet, WebSocketProtocols,
+        WsMessage,
+    },
     Data, Executor, Result,
 };
-use futures_util::{future, future::Ready, StreamExt};
+use futures_util::{
+    future,
+    future::{BoxFuture, Ready},
+    StreamExt,
+};
 use tide::Endpoint;
 use tide_websockets::{tungstenite::protocol::CloseFrame, Message};
 
@@ -13,8 +20,13 @@ use tide_websockets::{tungstenite::protocol::CloseFrame, Message};
 pub struct GraphQLSubscription<E, OnConnInit> {
     executor: E,
     on_connection_init: OnConnInit,
+    on_connection_refresh: Option<OnConnRefreshFn>,
+    protocols: WebSocketProtocolSet,
 }
 
+type OnConnRefreshFn =
+    Arc<dyn Fn(serde_json::Value) -> BoxFuture<'static, async_graphql::Result<Data>> + Send + Sync>;
+
 type DefaultOnConnInitType = fn(serde_json::Value) -> Ready<async_graphql::Result<Data>>;
 
 fn default_on_connection_init(_: serde_json::Value) -> Ready<async_graphql::Result<Data>> {
@@ -30,6 +42,8 @@ where
         GraphQLSubscription {
             executor,
             on_connection_init: default_on_connection_init,
+            on_connection_refresh: None,
+            protocols: WebSocketProtocolSet::default(),
         }
     }
 }
@@ -57,27 +71,56 @@ where
         GraphQLSubscription {
             executor: self.executor,
             on_connection_init: callback,
+            on_connection_refresh: self.on_connection_refresh,
+            protocols: self.protocols,
+        }
+    }
+
+    /// Specify the protocols accepted by the endpoint, the built-in protocols
+    /// by default.
+    #[must_use]
+    pub fn with_protocols(self, protocols: WebSocketProtocolSet) -> Self {
+        Self { protocols, ..self }
+    }
+
+    /// Specify a callback function to be called when the client refreshes the
+    /// connection, usually to re-validate its credentials.
+    ///
+    /// See [`async_graphql::http::WebSocket::on_connection_refresh`].
+    #[must_use]
+    pub fn on_connection_refresh<F, Fut>(self, callback: F) -> Self
+    where
+        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = async_graphql::Result<Data>> + Send + 'static,
+    {
+        Self {
+            on_connection_refresh: Some(Arc::new(move |payload| Box::pin(callback(payload)))),
+            ..self
         }
     }
 
     /// Consumes this builder to create a tide endpoint.
     pub fn build<S: Send + Sync + Clone + 'static>(self) -> impl Endpoint<S> {
+        let sec_websocket_protocols = self
+            .protocols
+            .sec_websocket_protocols()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
         tide_websockets::WebSocket::<S, _>::new(move |request, connection| {
             let executor = self.executor.clone();
             let on_connection_init = self.on_connection_init.clone();
+            let on_connection_refresh = self.on_connection_refresh.clone();
+            let protocols = self.protocols.clone();
             async move {
                 let protocol = match request
                     .header("sec-websocket-protocol")
-                    .map(|value| value.as_str())
-                    .and_then(|protocols| {
-                        protocols
-                            .split(',')
-                            .find_map(|p| WebSocketProtocols::from_str(p.trim()).ok())
-                    }) {
+                    .and_then(|value| protocols.negotiate(value.as_str()))
+                {
                     Some(protocol) => protocol,
                     None => {
                         // default to the prior standard
-                        WebSocketProtocols::SubscriptionsTransportWS
+                        Arc::new(WebSocketProtocols::SubscriptionsTransportWS)
+                            as Arc<dyn WebSocketProtocol>
                     }
                 };
 
@@ -91,6 +134,10 @@ where
                     protocol,
                 )
                 .on_connection_init(on_connection_init);
+                if let Some(on_connection_refresh) = on_connection_refresh {
+                    stream =
+                        stream.on_connection_refresh(move |payload| on_connection_refresh(payload));
+                }
 
                 while let Some(data) = stream.next().await {
                     match data {
@@ -114,6 +161,11 @@ where
                 Ok(())
             }
         })
-        .with_protocols(&ALL_WEBSOCKET_PROTOCOLS)
+        .with_protocols(
+            &sec_websocket_protocols
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+        )
     }
 }
@@ -217,3 +217,54 @@ async fn upload() -> Result<()> {
 
     Ok(())
 }
+
+#[async_std::test]
+async fn client_info() -> Result<()> {
+    let listen_addr = "127.0.0.1:8084";
+
+    async_std::task::spawn(async move {
+        struct QueryRoot;
+        #[Object]
+        impl QueryRoot {
+            async fn client(&self, ctx: &Context<'_>) -> Option<String> {
+                ctx.data_opt::<ClientInfo>().map(|info| {
+                    format!("{} {}", info.name, info.version.as_deref().unwrap_or("-"))
+                })
+            }
+        }
+
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish();
+
+        let mut app = tide::new();
+        app.at("/").post(async_graphql_tide::graphql(schema));
+        app.listen(listen_addr).await
+    });
+
+    test_utils::wait_server_ready().await;
+
+    let client = test_utils::client();
+
+    let resp = client
+        .post(&format!("http://{}", listen_addr))
+        .json(&json!({"query":"{ client }"}))
+        .header(ClientInfo::NAME_HEADER, "web")
+        .header(ClientInfo::VERSION_HEADER, "1.2.0")
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let string = resp.text().await?;
+    assert_eq!(string, json!({"data": {"client": "web 1.2.0"}}).to_string());
+
+    let resp = client
+        .post(&format!("http://{}", listen_addr))
+        .json(&json!({"query":"{ client }"}))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let string = resp.text().await?;
+    assert_eq!(string, json!({"data": {"client": null}}).to_string());
+
+    Ok(())
+}
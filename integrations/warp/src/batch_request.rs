@@ -1,6 +1,6 @@
 use std::{io, io::ErrorKind};
 
-use async_graphql::{http::MultipartOptions, BatchRequest, Executor};
+use async_graphql::{http::MultipartOptions, BatchRequest, ClientInfo, Executor};
 use futures_util::TryStreamExt;
 use warp::{reply::Response as WarpResponse, Buf, Filter, Rejection, Reply};
 
@@ -54,7 +54,17 @@ where
                 .map_err(|e| warp::reject::custom(GraphQLBadRequest(e)))
             }))
         .unify()
-        .map(move |res| (executor.clone(), res))
+        .and(warp::header::optional::<String>(ClientInfo::NAME_HEADER))
+        .and(warp::header::optional::<String>(ClientInfo::VERSION_HEADER))
+        .map(
+            move |res: BatchRequest, name: Option<String>, version: Option<String>| {
+                let res = match ClientInfo::from_headers(name.as_deref(), version.as_deref()) {
+                    Some(client_info) => res.data(client_info),
+                    None => res,
+                };
+                (executor.clone(), res)
+            },
+        )
 }
 
 /// Reply for `async_graphql::BatchRequest`.
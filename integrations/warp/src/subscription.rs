@@ -6,7 +6,7 @@ use async_graphql::{
 };
 use futures_util::{
     future,
-    future::Ready,
+    future::{BoxFuture, Ready},
     stream::{SplitSink, SplitStream},
     Sink, Stream, StreamExt,
 };
@@ -96,6 +96,8 @@ pub fn graphql_protocol() -> impl Filter<Extract = (WebSocketProtocols,), Error
     })
 }
 
+type OnConnRefreshFn = Box<dyn FnMut(serde_json::Value) -> BoxFuture<'static, Result<Data>> + Send>;
+
 type DefaultOnConnInitType = fn(serde_json::Value) -> Ready<async_graphql::Result<Data>>;
 
 fn default_on_connection_init(_: serde_json::Value) -> Ready<async_graphql::Result<Data>> {
@@ -167,6 +169,7 @@ pub struct GraphQLWebSocket<Sink, Stream, E, OnInit> {
     executor: E,
     data: Data,
     on_init: OnInit,
+    on_connection_refresh: Option<OnConnRefreshFn>,
 }
 
 impl<S, E> GraphQLWebSocket<SplitSink<S, Message>, SplitStream<S>, E, DefaultOnConnInitType>
@@ -201,6 +204,7 @@ where
             executor,
             data: Data::default(),
             on_init: default_on_connection_init,
+            on_connection_refresh: None,
         }
     }
 }
@@ -240,10 +244,27 @@ where
             executor: self.executor,
             data: self.data,
             on_init: callback,
+            on_connection_refresh: self.on_connection_refresh,
             protocol: self.protocol,
         }
     }
 
+    /// Specify a callback function to be called when the client refreshes the
+    /// connection, usually to re-validate its credentials.
+    ///
+    /// See [`async_graphql::http::WebSocket::on_connection_refresh`].
+    #[must_use]
+    pub fn on_connection_refresh<F, Fut>(self, mut callback: F) -> Self
+    where
+        F: FnMut(serde_json::Value) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Data>> + Send + 'static,
+    {
+        Self {
+            on_connection_refresh: Some(Box::new(move |payload| Box::pin(callback(payload)))),
+            ..self
+        }
+    }
+
     /// Processing subscription requests.
     pub async fn serve(self) {
         let stream = self
@@ -253,9 +274,14 @@ where
             .filter(|msg| future::ready(msg.is_text() || msg.is_binary()))
             .map(ws::Message::into_bytes);
 
-        let _ = async_graphql::http::WebSocket::new(self.executor.clone(), stream, self.protocol)
-            .connection_data(self.data)
-            .on_connection_init(self.on_init)
+        let mut stream =
+            async_graphql::http::WebSocket::new(self.executor.clone(), stream, self.protocol)
+                .connection_data(self.data)
+                .on_connection_init(self.on_init);
+        if let Some(on_connection_refresh) = self.on_connection_refresh {
+            stream = stream.on_connection_refresh(on_connection_refresh);
+        }
+        let _ = stream
             .map(|msg| match msg {
                 WsMessage::Text(text) => ws::Message::text(text),
                 WsMessage::Close(code, status) => ws::Message::close_with(code, status),
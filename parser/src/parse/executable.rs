@@ -292,6 +292,9 @@ fn parse_field(
     let arguments = parse_if_rule(&mut pairs, Rule::arguments, |pair| {
         parse_arguments(pair, pc)
     })?;
+    let nullability = parse_if_rule(&mut pairs, Rule::nullability_designator, |pair| {
+        parse_nullability_designator(pair, pc)
+    })?;
     let directives = parse_opt_directives(&mut pairs, pc)?;
     let selection_set = parse_if_rule(&mut pairs, Rule::selection_set, |pair| {
         parse_selection_set(pair, pc, recursion_depth!(remaining_depth))
@@ -304,6 +307,7 @@ fn parse_field(
             alias,
             name,
             arguments: arguments.unwrap_or_default(),
+            nullability,
             directives,
             selection_set: selection_set.unwrap_or_default(),
         },
@@ -311,6 +315,24 @@ fn parse_field(
     ))
 }
 
+fn parse_nullability_designator(
+    pair: Pair<Rule>,
+    pc: &mut PositionCalculator,
+) -> Result<Positioned<NullabilityDesignator>> {
+    debug_assert_eq!(pair.as_rule(), Rule::nullability_designator);
+
+    let pos = pc.step(&pair);
+
+    Ok(Positioned::new(
+        match pair.as_str() {
+            "!" => NullabilityDesignator::Required,
+            "?" => NullabilityDesignator::Optional,
+            _ => unreachable!(),
+        },
+        pos,
+    ))
+}
+
 fn parse_alias(pair: Pair<Rule>, pc: &mut PositionCalculator) -> Result<Positioned<Name>> {
     debug_assert_eq!(pair.as_rule(), Rule::alias);
     parse_name(exactly_one(pair.into_inner()), pc)
@@ -326,6 +348,10 @@ fn parse_fragment_spread(
     let mut pairs = pair.into_inner();
 
     let fragment_name = parse_name(pairs.next().unwrap(), pc)?;
+    let arguments = parse_if_rule(&mut pairs, Rule::arguments, |pair| {
+        check_fragment_arguments(&pair, pc)?;
+        parse_arguments(pair, pc)
+    })?;
     let directives = parse_opt_directives(&mut pairs, pc)?;
 
     debug_assert_eq!(pairs.next(), None);
@@ -333,12 +359,28 @@ fn parse_fragment_spread(
     Ok(Positioned::new(
         FragmentSpread {
             fragment_name,
+            arguments: arguments.unwrap_or_default(),
             directives,
         },
         pos,
     ))
 }
 
+#[cfg(feature = "fragment-arguments")]
+fn check_fragment_arguments(_pair: &Pair<Rule>, _pc: &mut PositionCalculator) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(not(feature = "fragment-arguments"))]
+fn check_fragment_arguments(pair: &Pair<Rule>, pc: &mut PositionCalculator) -> Result<()> {
+    Err(Error::Syntax {
+        message: "Fragment arguments are only supported with the `fragment-arguments` feature."
+            .to_string(),
+        start: pc.step(pair),
+        end: None,
+    })
+}
+
 fn parse_inline_fragment(
     pair: Pair<Rule>,
     pc: &mut PositionCalculator,
@@ -383,6 +425,10 @@ fn parse_fragment_definition_item(
     let mut pairs = pair.into_inner();
 
     let name = parse_name(pairs.next().unwrap(), pc)?;
+    let variable_definitions = parse_if_rule(&mut pairs, Rule::variable_definitions, |pair| {
+        check_fragment_arguments(&pair, pc)?;
+        parse_variable_definitions(pair, pc)
+    })?;
     let type_condition = parse_type_condition(pairs.next().unwrap(), pc)?;
     let directives = parse_opt_directives(&mut pairs, pc)?;
     let selection_set = parse_selection_set(pairs.next().unwrap(), pc, MAX_RECURSION_DEPTH)?;
@@ -393,6 +439,7 @@ fn parse_fragment_definition_item(
         FragmentDefinitionItem {
             name,
             definition: FragmentDefinition {
+                variable_definitions: variable_definitions.unwrap_or_default(),
                 type_condition,
                 directives,
                 selection_set,
@@ -446,6 +493,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fragment_arguments() {
+        let query = r#"
+            query { ...A(x: 1) }
+            fragment A($x: Int = 2) on Query { value(x: $x) }
+        "#;
+
+        #[cfg(feature = "fragment-arguments")]
+        {
+            let doc = parse_query(query).unwrap();
+            let fragment = &doc.fragments["A"].node;
+            assert_eq!(fragment.variable_definitions.len(), 1);
+            assert_eq!(fragment.variable_definitions[0].node.name.node, "x");
+
+            let operation = doc.operations.iter().next().unwrap().1;
+            match &operation.node.selection_set.node.items[0].node {
+                Selection::FragmentSpread(spread) => {
+                    assert_eq!(spread.node.arguments.len(), 1);
+                    assert_eq!(spread.node.arguments[0].0.node, "x");
+                }
+                _ => panic!("expected a fragment spread"),
+            }
+        }
+
+        #[cfg(not(feature = "fragment-arguments"))]
+        assert!(matches!(parse_query(query), Err(Error::Syntax { .. })));
+    }
+
+    #[test]
+    fn test_nullability_designators() {
+        let doc = parse_query("{ a! b? @skip(if: false) c(x: 1)! { d? } e }").unwrap();
+        let operation = doc.operations.iter().next().unwrap().1;
+        let nullability = |selection_set: &SelectionSet| {
+            selection_set
+                .items
+                .iter()
+                .map(|selection| match &selection.node {
+                    Selection::Field(field) => field.node.nullability.map(|n| n.node),
+                    _ => panic!("expected a field"),
+                })
+                .collect::<Vec<_>>()
+        };
+        let items = &operation.node.selection_set.node;
+        assert_eq!(
+            nullability(items),
+            vec![
+                Some(NullabilityDesignator::Required),
+                Some(NullabilityDesignator::Optional),
+                Some(NullabilityDesignator::Required),
+                None,
+            ]
+        );
+        match &items.items[2].node {
+            Selection::Field(field) => assert_eq!(
+                nullability(&field.node.selection_set.node),
+                vec![Some(NullabilityDesignator::Optional)]
+            ),
+            _ => panic!("expected a field"),
+        }
+
+        assert!(parse_query("{ a!! }").is_err());
+    }
+
     #[test]
     fn test_parse_overflowing_int() {
         let query_ok = format!("mutation {{ add(big: {}) }} ", std::i32::MAX);
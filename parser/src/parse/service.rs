@@ -57,6 +57,7 @@ fn parse_schema_definition(
     let pos = pc.step(&pair);
     let mut pairs = pair.into_inner();
 
+    let description = parse_if_rule(&mut pairs, Rule::string, |pair| parse_string(pair, pc))?;
     let extend = next_if_rule(&mut pairs, Rule::extend).is_some();
     let directives = parse_opt_const_directives(&mut pairs, pc)?;
 
@@ -95,6 +96,7 @@ fn parse_schema_definition(
     Ok(Positioned::new(
         SchemaDefinition {
             extend,
+            description,
             directives,
             query,
             mutation,
@@ -307,6 +309,7 @@ fn parse_directive_definition(
             .collect()
     })?
     .unwrap_or_default();
+    let is_repeatable = next_if_rule(&mut pairs, Rule::repeatable).is_some();
     let locations = {
         let pair = pairs.next().unwrap();
         debug_assert_eq!(pair.as_rule(), Rule::directive_locations);
@@ -350,6 +353,7 @@ fn parse_directive_definition(
             description,
             name,
             arguments,
+            is_repeatable,
             locations,
         },
         pos,
@@ -191,6 +191,11 @@ pub struct Field {
     pub name: Positioned<Name>,
     /// The arguments to the field, empty if no arguments are provided.
     pub arguments: Vec<(Positioned<Name>, Positioned<Value>)>,
+    /// The client-controlled nullability designator of the field, if any.
+    ///
+    /// This is an experimental feature that is rejected by the validation
+    /// unless it is enabled in the schema.
+    pub nullability: Option<Positioned<NullabilityDesignator>>,
     /// The directives in the field selector.
     pub directives: Vec<Positioned<Directive>>,
     /// The subfields being selected in this field, if it is an object. Empty if
@@ -198,6 +203,29 @@ pub struct Field {
     pub selection_set: Positioned<SelectionSet>,
 }
 
+/// A client-controlled nullability designator, `!` or `?`, overriding the
+/// nullability of a field in the response.
+///
+/// [Reference](https://github.com/graphql/graphql-wg/blob/main/rfcs/ClientControlledNullability.md).
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub enum NullabilityDesignator {
+    /// `!`, the field is non-null: a null value or an error is propagated to
+    /// the parent field.
+    Required,
+    /// `?`, the field is nullable: an error is caught and the field is set to
+    /// null.
+    Optional,
+}
+
+impl Display for NullabilityDesignator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Required => "!",
+            Self::Optional => "?",
+        })
+    }
+}
+
 impl Field {
     /// Get the response key of the field. This is the alias if present and the
     /// name otherwise.
@@ -223,6 +251,12 @@ impl Field {
 pub struct FragmentSpread {
     /// The name of the fragment being selected.
     pub fragment_name: Positioned<Name>,
+    /// The arguments passed to the fragment, empty if no arguments are
+    /// provided.
+    ///
+    /// Fragment arguments are only parsed with the `fragment-arguments`
+    /// feature.
+    pub arguments: Vec<(Positioned<Name>, Positioned<Value>)>,
     /// The directives in the fragment selector.
     pub directives: Vec<Positioned<Directive>>,
 }
@@ -246,6 +280,12 @@ pub struct InlineFragment {
 /// [Reference](https://spec.graphql.org/October2021/#FragmentDefinition).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FragmentDefinition {
+    /// The variables defined by the fragment, empty if the fragment has no
+    /// arguments.
+    ///
+    /// Fragment arguments are only parsed with the `fragment-arguments`
+    /// feature.
+    pub variable_definitions: Vec<Positioned<VariableDefinition>>,
     /// The type this fragment operates on.
     pub type_condition: Positioned<TypeCondition>,
     /// Directives in the fragment.
@@ -35,6 +35,8 @@ pub enum TypeSystemDefinition {
 pub struct SchemaDefinition {
     /// Whether the schema is an extension of another schema.
     pub extend: bool,
+    /// The description of the schema.
+    pub description: Option<Positioned<String>>,
     /// The directives of the schema definition.
     pub directives: Vec<Positioned<ConstDirective>>,
     /// The query root. This is always `Some` when the schema is not extended.
@@ -188,6 +190,8 @@ pub struct DirectiveDefinition {
     pub name: Positioned<Name>,
     /// The arguments of the directive.
     pub arguments: Vec<Positioned<InputValueDefinition>>,
+    /// Whether the directive can be used multiple times on the same location.
+    pub is_repeatable: bool,
     /// The locations the directive applies to.
     pub locations: Vec<Positioned<DirectiveLocation>>,
 }
@@ -8,7 +8,7 @@ use std::fs;
 const PREAMBLE: &str = r#"
 //! This is @generated code, do not edit by hand.
 //! See `graphql.pest` and `tests/codegen.rs`.
-#![allow(unused_attributes)]
+#![allow(unused_attributes, dead_code)]
 use super::GraphQLParser;
 "#;
 
@@ -21,6 +21,14 @@ pub trait TypeName: Send + Sync {
 }
 
 /// Represents a GraphQL input type.
+#[cfg_attr(
+    diagnostic_namespace,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not a GraphQL input type",
+        label = "not a GraphQL input type",
+        note = "derive `InputObject`, `OneofObject` or `Enum` for `{Self}`, or implement `ScalarType` and register it with `scalar!` or `#[Scalar]`"
+    )
+)]
 pub trait InputType: Send + Sync + Sized {
     /// The raw type used for validator.
     ///
@@ -60,6 +68,14 @@ pub trait InputType: Send + Sync + Sized {
 }
 
 /// Represents a GraphQL output type.
+#[cfg_attr(
+    diagnostic_namespace,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not a GraphQL output type",
+        label = "not a GraphQL output type",
+        note = "derive `SimpleObject`, `Enum`, `Union`, `Interface` or `MergedObject` for `{Self}`, use `#[Object]`, or implement `ScalarType` and register it with `scalar!` or `#[Scalar]`"
+    )
+)]
 #[async_trait::async_trait]
 pub trait OutputType: Send + Sync {
     /// Type the name.
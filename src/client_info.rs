@@ -0,0 +1,89 @@
+/// The client application that sent a request, identified by the
+/// `apollographql-client-name` and `apollographql-client-version` headers
+/// sent by Apollo Client and other clients.
+///
+/// The HTTP integrations parse these headers and add a `ClientInfo` to the
+/// data of the request, so the resolvers can read it with
+/// [`Context::data_opt`](crate::Context::data_opt). The `Logger`, `Analyzer`
+/// and `OpenTelemetryMetrics` extensions record it with the requests, so
+/// their output can be segmented per client.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn client(&self, ctx: &Context<'_>) -> Option<String> {
+///         ctx.data_opt::<ClientInfo>().map(|info| info.name.clone())
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let client_info = ClientInfo::from_headers(Some("web"), Some("1.2.0")).unwrap();
+/// let request = Request::new("{ client }").data(client_info);
+/// assert_eq!(
+///     schema.execute(request).await.into_result().unwrap().data,
+///     value!({ "client": "web" })
+/// );
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientInfo {
+    /// The name of the client.
+    pub name: String,
+    /// The version of the client.
+    pub version: Option<String>,
+}
+
+impl ClientInfo {
+    /// The header containing the name of the client.
+    pub const NAME_HEADER: &'static str = "apollographql-client-name";
+
+    /// The header containing the version of the client.
+    pub const VERSION_HEADER: &'static str = "apollographql-client-version";
+
+    /// Create the information of a client with its name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+        }
+    }
+
+    /// Set the version of the client.
+    #[must_use]
+    pub fn version(self, version: impl Into<String>) -> Self {
+        Self {
+            version: Some(version.into()),
+            ..self
+        }
+    }
+
+    /// Create the information of a client from the values of the
+    /// [`NAME_HEADER`](Self::NAME_HEADER) and
+    /// [`VERSION_HEADER`](Self::VERSION_HEADER) headers.
+    ///
+    /// Returns `None` if the name is missing or blank.
+    pub fn from_headers(name: Option<&str>, version: Option<&str>) -> Option<Self> {
+        let name = name.map(str::trim).filter(|name| !name.is_empty())?;
+        let version = version.map(str::trim).filter(|version| !version.is_empty());
+        Some(Self {
+            name: name.to_string(),
+            version: version.map(ToString::to_string),
+        })
+    }
+
+    /// Create the information of a client from the headers of an HTTP
+    /// request.
+    ///
+    /// Returns `None` if the name is missing or blank.
+    pub fn from_header_map(headers: &http::HeaderMap) -> Option<Self> {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        Self::from_headers(header(Self::NAME_HEADER), header(Self::VERSION_HEADER))
+    }
+}
@@ -2,10 +2,13 @@
 
 use std::{
     any::{Any, TypeId},
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     ops::Deref,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use async_graphql_value::{Value as InputValue, Variables};
@@ -14,21 +17,37 @@ use http::{
     header::{AsHeaderName, HeaderMap, IntoHeaderName},
     HeaderValue,
 };
+use once_cell::sync::OnceCell;
 use serde::{
     ser::{SerializeSeq, Serializer},
     Serialize,
 };
 
+#[cfg(feature = "dataloader")]
+use crate::{
+    dataloader::{DataLoader, Loader, SingleKeyLoader},
+    extensions::ExplainLoads,
+};
 use crate::{
+    execution_stats::ExecutionStatsCollector,
     extensions::Extensions,
+    meta::SchemaMeta,
+    operation_signature::LazySignature,
     parser::types::{
         Directive, Field, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
     },
+    provider::ProvidedData,
     schema::{IntrospectionMode, SchemaEnv},
-    Error, InputType, Lookahead, Name, OneofObjectType, PathSegment, Pos, Positioned, Result,
-    ServerError, ServerResult, UploadValue, Value,
+    Error, GuardDenial, InputType, Lookahead, Name, OneofObjectType, OperationMetadata,
+    PathSegment, Pos, Positioned, RelayConnection, Result, ServerError, ServerResult, UploadValue,
+    Value,
 };
 
+const ERROR_LIMIT_MESSAGE: &str = "Too many errors, error limit reached. Execution aborted.";
+
+#[cfg(feature = "dataloader")]
+type LoaderValue<L> = <L as Loader<<L as SingleKeyLoader>::Key>>::Value;
+
 /// Data related functions of the context.
 pub trait DataContext<'a> {
     /// Gets the global data defined in the `Context` or `Schema`.
@@ -57,7 +76,10 @@ pub trait DataContext<'a> {
 ///
 /// This is a type map, allowing you to store anything inside it.
 #[derive(Default)]
-pub struct Data(FnvHashMap<TypeId, Box<dyn Any + Sync + Send>>);
+pub struct Data(
+    FnvHashMap<TypeId, Box<dyn Any + Sync + Send>>,
+    Option<Arc<Data>>,
+);
 
 impl Deref for Data {
     type Target = FnvHashMap<TypeId, Box<dyn Any + Sync + Send>>;
@@ -73,9 +95,54 @@ impl Data {
         self.0.insert(TypeId::of::<D>(), Box::new(data));
     }
 
-    pub(crate) fn merge(&mut self, other: Data) {
+    /// Insert data, returning `self` so that several values can be chained.
+    ///
+    /// ```
+    /// use async_graphql::Data;
+    ///
+    /// struct Token(String);
+    /// struct UserId(u64);
+    ///
+    /// let data = Data::default()
+    ///     .data(Token("123456".to_string()))
+    ///     .data(UserId(1));
+    /// assert_eq!(data.data_opt::<Token>().unwrap().0, "123456");
+    /// assert_eq!(data.data_opt::<UserId>().unwrap().0, 1);
+    /// ```
+    #[must_use]
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.insert(data);
+        self
+    }
+
+    /// Gets the data of the specified type or `None` if it does not exist.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&D> {
+        self.get_value(&TypeId::of::<D>())
+            .and_then(|data| data.downcast_ref::<D>())
+    }
+
+    /// Merge `other` into this data, the values of `other` replacing the
+    /// values of the same types.
+    pub fn merge(&mut self, other: Data) {
         self.0.extend(other.0);
     }
+
+    /// Creates an empty data whose missing values are looked up in `parent`.
+    pub(crate) fn with_parent(parent: Arc<Data>) -> Self {
+        Self(Default::default(), Some(parent))
+    }
+
+    pub(crate) fn parent(&self) -> Option<&Arc<Data>> {
+        self.1.as_ref()
+    }
+
+    /// Gets the value of the type, or the value of the parent data if it
+    /// does not exist.
+    pub(crate) fn get_value(&self, type_id: &TypeId) -> Option<&Box<dyn Any + Sync + Send>> {
+        self.0
+            .get(type_id)
+            .or_else(|| self.1.as_ref()?.get_value(type_id))
+    }
 }
 
 impl Debug for Data {
@@ -84,6 +151,98 @@ impl Debug for Data {
     }
 }
 
+/// Data that overrides the global data for the descendants of a field, see
+/// [`Context::scoped_data`].
+#[derive(Default)]
+pub struct ScopedData {
+    pending: Mutex<Data>,
+    data: OnceCell<Data>,
+}
+
+impl ScopedData {
+    /// Insert data.
+    ///
+    /// The data is visible to the descendants of the field, so it must be
+    /// inserted before the resolver of the field returns.
+    pub fn insert<D: Any + Send + Sync>(&self, data: D) {
+        self.pending.lock().unwrap().insert(data);
+    }
+
+    fn data(&self) -> &Data {
+        self.data
+            .get_or_init(|| std::mem::take(&mut *self.pending.lock().unwrap()))
+    }
+
+    /// Removes the data of type `D`.
+    pub(crate) fn take<D: Any + Send + Sync>(self) -> Option<D> {
+        let mut data = match self.data.into_inner() {
+            Some(data) => data,
+            None => self.pending.into_inner().unwrap(),
+        };
+        let data = data.0.remove(&TypeId::of::<D>())?;
+        data.downcast::<D>().ok().map(|data| *data)
+    }
+}
+
+impl Debug for ScopedData {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("ScopedData").finish()
+    }
+}
+
+/// The scoped data visible to a context, a reverse linked list like
+/// [`QueryPathNode`].
+#[derive(Clone, Default)]
+pub(crate) struct DataScope<'a> {
+    parent: Option<&'a DataScope<'a>>,
+    /// The scoped data of the field of a selection set.
+    inherited: Option<Arc<ScopedData>>,
+    /// The scoped data inserted by the resolver of the field.
+    own: OnceCell<Arc<ScopedData>>,
+}
+
+impl<'a> DataScope<'a> {
+    /// Returns the scope of a root context, which sees `data`.
+    pub(crate) fn root(data: Arc<ScopedData>) -> DataScope<'a> {
+        DataScope {
+            inherited: Some(data),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the scope of a context created from the context of this
+    /// scope, which sees its scoped data.
+    fn child(&'a self) -> DataScope<'a> {
+        let is_empty = self.inherited.is_none() && self.own.get().is_none();
+        DataScope {
+            parent: if is_empty { self.parent } else { Some(self) },
+            ..Default::default()
+        }
+    }
+
+    /// Same as [`DataScope::child`], but does not borrow this scope.
+    fn selection_set(&self) -> DataScope<'a> {
+        DataScope {
+            parent: self.parent,
+            inherited: self.own.get().or(self.inherited.as_ref()).cloned(),
+            ..Default::default()
+        }
+    }
+
+    fn get(&self, type_id: &TypeId) -> Option<&'a (dyn Any + Send + Sync)> {
+        let mut scope = self.parent;
+        while let Some(current) = scope {
+            for scoped_data in current.own.get().into_iter().chain(&current.inherited) {
+                if let Some(data) = scoped_data.data().get(type_id) {
+                    return Some(data.as_ref());
+                }
+            }
+            scope = current.parent;
+        }
+        None
+    }
+}
+
 /// Context for `SelectionSet`
 pub type ContextSelectionSet<'a> = ContextBase<'a, &'a Positioned<SelectionSet>>;
 
@@ -171,6 +330,18 @@ impl<'a> QueryPathNode<'a> {
         res
     }
 
+    /// Get the path represented by the segments of the path of an error.
+    pub(crate) fn to_path(self) -> Vec<PathSegment> {
+        let mut res = Vec::new();
+        self.for_each(|s| {
+            res.push(match s {
+                QueryPathSegment::Name(name) => PathSegment::Field((*name).to_string()),
+                QueryPathSegment::Index(idx) => PathSegment::Index(*idx),
+            });
+        });
+        res
+    }
+
     /// Iterate over the parents of the node.
     pub fn parents(&self) -> Parents<'_> {
         Parents(self)
@@ -244,6 +415,7 @@ pub struct ContextBase<'a, T> {
     pub schema_env: &'a SchemaEnv,
     #[doc(hidden)]
     pub query_env: &'a QueryEnv,
+    pub(crate) scope: DataScope<'a>,
 }
 
 #[doc(hidden)]
@@ -251,8 +423,8 @@ pub struct QueryEnvInner {
     pub extensions: Extensions,
     pub variables: Variables,
     pub operation_name: Option<String>,
-    pub operation: Positioned<OperationDefinition>,
-    pub fragments: HashMap<Name, Positioned<FragmentDefinition>>,
+    pub operation: Arc<Positioned<OperationDefinition>>,
+    pub fragments: Arc<HashMap<Name, Positioned<FragmentDefinition>>>,
     pub uploads: Vec<UploadValue>,
     pub session_data: Arc<Data>,
     pub ctx_data: Arc<Data>,
@@ -260,6 +432,17 @@ pub struct QueryEnvInner {
     pub http_headers: Mutex<HeaderMap>,
     pub introspection_mode: IntrospectionMode,
     pub errors: Mutex<Vec<ServerError>>,
+    pub max_errors: Option<usize>,
+    pub errors_exceeded: AtomicBool,
+    pub max_response_size: Option<usize>,
+    pub response_size: AtomicUsize,
+    pub response_extensions: Mutex<BTreeMap<String, Value>>,
+    pub live_query_tokens: Mutex<HashSet<String>>,
+    pub(crate) operation_signature: LazySignature,
+    pub(crate) provided_data: HashMap<TypeId, ProvidedData>,
+    pub(crate) execution_stats: Option<ExecutionStatsCollector>,
+    /// The key of the cached field whose value is refreshed by this query.
+    pub(crate) field_cache_refresh: Option<String>,
 }
 
 #[doc(hidden)]
@@ -280,6 +463,111 @@ impl QueryEnv {
         QueryEnv(Arc::new(inner))
     }
 
+    /// Returns whether the errors of the query exceeded the limit, in which
+    /// case the fields are no longer resolved.
+    pub(crate) fn is_errors_exceeded(&self) -> bool {
+        self.errors_exceeded.load(Ordering::Relaxed)
+    }
+
+    /// Returns whether the approximate size of the response exceeded the
+    /// limit, in which case the fields are no longer resolved.
+    pub(crate) fn is_response_size_exceeded(&self) -> bool {
+        matches!(self.max_response_size, Some(max_response_size)
+            if self.response_size.load(Ordering::Relaxed) > max_response_size)
+    }
+
+    /// Adds the approximate size of a resolved field to the size of the
+    /// response.
+    ///
+    /// The values of the fields with a selection set are accounted by the
+    /// fields of their selection set, so only their key is added.
+    pub(crate) fn add_response_size(&self, field: &Field, value: &Value) {
+        if self.max_response_size.is_none() {
+            return;
+        }
+        let mut size = field.response_key().node.len() + 3;
+        if field.selection_set.node.items.is_empty() {
+            size += approximate_size(value);
+        }
+        self.response_size.fetch_add(size, Ordering::Relaxed);
+    }
+
+    /// Checks the size of the response while a list is built, before the
+    /// field returning the list is accounted by
+    /// [`add_response_size`](Self::add_response_size).
+    ///
+    /// `list_size` is the size of the items of the list resolved so far, to
+    /// which the item is added if the items are leaf values, so that a huge
+    /// list is not converted in full before the limit is checked.
+    pub(crate) fn check_list_item_size(
+        &self,
+        list_size: &AtomicUsize,
+        field: &Positioned<Field>,
+        item: &Value,
+    ) -> ServerResult<()> {
+        let max_response_size = match self.max_response_size {
+            Some(max_response_size) => max_response_size,
+            None => return Ok(()),
+        };
+        let mut size = self.response_size.load(Ordering::Relaxed);
+        if field.node.selection_set.node.items.is_empty() {
+            let item_size = approximate_size(item) + 1;
+            size += list_size.fetch_add(item_size, Ordering::Relaxed) + item_size;
+        }
+        if size > max_response_size {
+            return Err(response_size_error(max_response_size, Some(field.pos)));
+        }
+        Ok(())
+    }
+
+    /// Returns the error aborting the execution if the approximate size of
+    /// the response exceeded the limit.
+    pub(crate) fn response_size_error(&self) -> Option<ServerError> {
+        match self.max_response_size {
+            Some(max_response_size) if self.is_response_size_exceeded() => {
+                Some(response_size_error(max_response_size, None))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether errors were added during the execution.
+    pub(crate) fn has_errors(&self) -> bool {
+        self.is_errors_exceeded() || !self.errors.lock().unwrap().is_empty()
+    }
+
+    /// Returns the error of a field that is not resolved because the errors
+    /// exceeded the limit.
+    pub(crate) fn errors_exceeded_error(&self, pos: Pos) -> ServerError {
+        ServerError::new(ERROR_LIMIT_MESSAGE, Some(pos))
+    }
+
+    /// Adds the errors added during the execution to the errors of the
+    /// response, and keeps the first ones up to the limit followed by an error
+    /// reporting that the limit is exceeded if it is.
+    pub(crate) fn take_errors(&self, errors: &mut Vec<ServerError>) {
+        errors.extend(std::mem::take(&mut *self.errors.lock().unwrap()));
+        let max_errors = match self.max_errors {
+            Some(max_errors) if self.is_errors_exceeded() || errors.len() > max_errors => {
+                max_errors
+            }
+            _ => return,
+        };
+        // the fields that are not resolved once the limit is exceeded fail with the
+        // error reporting it, which is only added once
+        errors.retain(|err| err.message != ERROR_LIMIT_MESSAGE);
+        errors.truncate(max_errors);
+        errors.push(ServerError::new(ERROR_LIMIT_MESSAGE, None));
+    }
+
+    pub(crate) fn operation_metadata(&self) -> OperationMetadata {
+        OperationMetadata::with_lazy_signature(
+            self.operation_name.clone(),
+            self.operation.node.ty,
+            self.operation_signature.clone(),
+        )
+    }
+
     #[doc(hidden)]
     pub fn create_context<'a, T>(
         &'a self,
@@ -293,6 +581,43 @@ impl QueryEnv {
             item,
             schema_env,
             query_env: self,
+            scope: Default::default(),
+        }
+    }
+}
+
+/// Returns the approximate length of the JSON serialization of a value.
+fn response_size_error(max_response_size: usize, pos: Option<Pos>) -> ServerError {
+    ServerError::new(
+        format!(
+            "Response too large, the size limit of {} bytes is exceeded. Execution aborted.",
+            max_response_size
+        ),
+        pos,
+    )
+}
+
+fn approximate_size(value: &Value) -> usize {
+    match value {
+        Value::Null => 4,
+        Value::Boolean(true) => 4,
+        Value::Boolean(false) => 5,
+        Value::Number(n) => n.to_string().len(),
+        Value::String(s) => s.len() + 2,
+        Value::Binary(bytes) => bytes.len(),
+        Value::Enum(name) => name.len() + 2,
+        Value::List(items) => {
+            items
+                .iter()
+                .map(|item| approximate_size(item) + 1)
+                .sum::<usize>()
+                + 2
+        }
+        Value::Object(map) => {
+            map.iter()
+                .map(|(name, value)| name.len() + 4 + approximate_size(value))
+                .sum::<usize>()
+                + 2
         }
     }
 }
@@ -326,6 +651,7 @@ impl<'a, T> ContextBase<'a, T> {
             item: field,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            scope: self.scope.child(),
         }
     }
 
@@ -340,31 +666,90 @@ impl<'a, T> ContextBase<'a, T> {
             item: selection_set,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            scope: self.scope.selection_set(),
         }
     }
 
     #[doc(hidden)]
     pub fn set_error_path(&self, error: ServerError) -> ServerError {
         if let Some(node) = self.path_node {
-            let mut path = Vec::new();
-            node.for_each(|current_node| {
-                path.push(match current_node {
-                    QueryPathSegment::Name(name) => PathSegment::Field((*name).to_string()),
-                    QueryPathSegment::Index(idx) => PathSegment::Index(*idx),
-                })
-            });
-            ServerError { path, ..error }
+            ServerError {
+                path: node.to_path(),
+                ..error
+            }
         } else {
             error
         }
     }
 
+    /// Returns a read-only view over the metadata of the schema.
+    pub fn schema_meta(&self) -> SchemaMeta<'a> {
+        SchemaMeta::new(&self.schema_env.registry)
+    }
+
     /// Report a resolver error.
     ///
     /// When implementing `OutputType`, if an error occurs, call this function
     /// to report this error and return `Value::Null`.
     pub fn add_error(&self, error: ServerError) {
-        self.query_env.errors.lock().unwrap().push(error);
+        let mut errors = self.query_env.errors.lock().unwrap();
+        if matches!(self.query_env.max_errors, Some(max_errors) if errors.len() >= max_errors) {
+            self.query_env
+                .errors_exceeded
+                .store(true, Ordering::Relaxed);
+            return;
+        }
+        errors.push(error);
+    }
+
+    /// Inserts a value into the `extensions` of the response.
+    ///
+    /// Allows a resolver to return metadata to the client, such as the
+    /// remaining rate limit, without writing an extension. If the key is
+    /// already present, the previous value is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self, ctx: &Context<'_>) -> i32 {
+    ///         ctx.insert_response_extension("rateLimitRemaining", 99);
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    /// let resp = schema.execute("{ value }").await;
+    /// assert_eq!(resp.extensions["rateLimitRemaining"], value!(99));
+    /// # });
+    /// ```
+    pub fn insert_response_extension(&self, key: impl Into<String>, value: impl Into<Value>) {
+        self.query_env
+            .response_extensions
+            .lock()
+            .unwrap()
+            .insert(key.into(), value.into());
+    }
+
+    /// Adds an invalidation token to the current live query.
+    ///
+    /// When the query is marked with the `@live` directive, it is executed
+    /// again each time this token is invalidated in the
+    /// [`LiveQueryStore`](crate::LiveQueryStore), for example with
+    /// [`MemoryLiveQueryStore::invalidate`](crate::MemoryLiveQueryStore::invalidate).
+    /// Otherwise, this does nothing.
+    pub fn add_live_query_token(&self, token: impl Into<String>) {
+        self.query_env
+            .live_query_tokens
+            .lock()
+            .unwrap()
+            .insert(token.into());
     }
 
     /// Gets the global data defined in the `Context` or `Schema`.
@@ -397,16 +782,115 @@ impl<'a, T> ContextBase<'a, T> {
     /// Gets the global data defined in the `Context` or `Schema` or `None` if
     /// the specified type data does not exist.
     pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        if let Some(data) = self.scope.get(&TypeId::of::<D>()) {
+            return data.downcast_ref::<D>();
+        }
         self.query_env
             .extension_data
             .0
             .get(&TypeId::of::<D>())
             .or_else(|| self.query_env.ctx_data.0.get(&TypeId::of::<D>()))
-            .or_else(|| self.query_env.session_data.0.get(&TypeId::of::<D>()))
-            .or_else(|| self.schema_env.data.0.get(&TypeId::of::<D>()))
+            .or_else(|| self.query_env.session_data.get_value(&TypeId::of::<D>()))
+            .map(AsRef::as_ref)
+            .or_else(|| self.schema_env.get_data(&TypeId::of::<D>()))
             .and_then(|d| d.downcast_ref::<D>())
     }
 
+    /// Gets the data of the `Schema`, ignoring the data of the request and the
+    /// scoped data, or `None` if the specified type data does not exist.
+    pub fn schema_data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        self.schema_env
+            .get_data(&TypeId::of::<D>())
+            .and_then(|d| d.downcast_ref::<D>())
+    }
+
+    /// Gets the data of the `Request`, ignoring the scoped data, or `None` if
+    /// the specified type data does not exist.
+    pub fn request_data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        self.query_env
+            .ctx_data
+            .0
+            .get(&TypeId::of::<D>())
+            .and_then(|d| d.downcast_ref::<D>())
+    }
+
+    /// Returns the name of the active visibility profile, `None` if no
+    /// [`VisibilityProfile`](crate::VisibilityProfile) is selected and no
+    /// profile is declared.
+    pub fn visibility_profile(&self) -> Option<&'a str> {
+        crate::visibility::active_visibility_profile(
+            &self.schema_env,
+            &[
+                &self.query_env.extension_data,
+                &self.query_env.ctx_data,
+                &self.query_env.session_data,
+            ],
+        )
+    }
+
+    /// Loads a value with the [`DataLoader`](crate::dataloader::DataLoader)
+    /// of the loader `L`, which must be added to the data of the `Schema` or
+    /// the `Request`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Error` if the `DataLoader` does not exist or the loader
+    /// fails.
+    #[cfg(feature = "dataloader")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dataloader")))]
+    pub async fn load_one<L>(&self, key: L::Key) -> Result<Option<LoaderValue<L>>>
+    where
+        L: SingleKeyLoader + Loader<<L as SingleKeyLoader>::Key>,
+        <L as Loader<L::Key>>::Error: Into<Error>,
+    {
+        let mut values = self.load_many::<L>(std::iter::once(key.clone())).await?;
+        Ok(values.remove(&key))
+    }
+
+    /// Loads many values with the
+    /// [`DataLoader`](crate::dataloader::DataLoader) of the loader `L`, which
+    /// must be added to the data of the `Schema` or the `Request`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Error` if the `DataLoader` does not exist or the loader
+    /// fails.
+    #[cfg(feature = "dataloader")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dataloader")))]
+    pub async fn load_many<L>(
+        &self,
+        keys: impl IntoIterator<Item = L::Key>,
+    ) -> Result<HashMap<L::Key, LoaderValue<L>>>
+    where
+        L: SingleKeyLoader + Loader<<L as SingleKeyLoader>::Key>,
+        <L as Loader<L::Key>>::Error: Into<Error>,
+    {
+        let loader = self.data::<DataLoader<L>>()?;
+        let loads = self.data_opt::<ExplainLoads>();
+        if self.query_env.execution_stats.is_none() && loads.is_none() {
+            return loader.load_many(keys).await.map_err(Into::into);
+        }
+
+        let batches = AtomicUsize::new(0);
+        let mut keys_count = 0;
+        let values = loader
+            .load_many_counted(
+                keys.into_iter().inspect(|_| keys_count += 1),
+                Some(&batches),
+            )
+            .await;
+        let batches = batches.into_inner();
+        if let Some(stats) = &self.query_env.execution_stats {
+            stats
+                .dataloader_batches
+                .fetch_add(batches, Ordering::Relaxed);
+        }
+        if let Some(loads) = loads {
+            loads.record::<L>(self.path_node.as_ref(), keys_count, batches > 0);
+        }
+        values.map_err(Into::into)
+    }
+
     /// Returns whether the HTTP header `key` is currently set on the response
     ///
     /// # Examples
@@ -580,6 +1064,7 @@ impl<'a, T> ContextBase<'a, T> {
         arguments: &[(Positioned<Name>, Positioned<InputValue>)],
         name: &str,
         default: Option<fn() -> Q>,
+        is_secret: bool,
     ) -> ServerResult<(Pos, Q)> {
         let value = arguments
             .iter()
@@ -595,9 +1080,16 @@ impl<'a, T> ContextBase<'a, T> {
             Some(value) => (value.pos, Some(self.resolve_input_value(value)?)),
             None => (Pos::default(), None),
         };
+        let secret = match (is_secret, &value) {
+            (true, Some(value)) => Some(value.clone()),
+            _ => None,
+        };
         InputType::parse(value)
             .map(|value| (pos, value))
-            .map_err(|e| e.into_server_error(pos))
+            .map_err(|e| match &secret {
+                Some(secret) => e.redact(secret).in_argument(name).into_server_error(pos),
+                None => e.in_argument(name).into_server_error(pos),
+            })
     }
 
     #[doc(hidden)]
@@ -615,18 +1107,40 @@ impl<'a, T> ContextBase<'a, T> {
             item: self.item,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            scope: self.scope.child(),
         }
     }
 }
 
 impl<'a> ContextBase<'a, &'a Positioned<Field>> {
+    #[doc(hidden)]
+    pub fn render_guard_error(&self, err: Error) -> Error {
+        let denial = err
+            .source
+            .as_ref()
+            .and_then(|source| source.downcast_ref::<GuardDenial>());
+        match (&self.schema_env.guard_denial_hook, denial) {
+            (Some(hook), Some(denial)) => hook(self, denial),
+            _ => err,
+        }
+    }
+
     #[doc(hidden)]
     pub fn param_value<T: InputType>(
         &self,
         name: &str,
         default: Option<fn() -> T>,
     ) -> ServerResult<(Pos, T)> {
-        self.get_param_value(&self.item.node.arguments, name, default)
+        self.get_param_value(&self.item.node.arguments, name, default, false)
+    }
+
+    #[doc(hidden)]
+    pub fn secret_param_value<T: InputType>(
+        &self,
+        name: &str,
+        default: Option<fn() -> T>,
+    ) -> ServerResult<(Pos, T)> {
+        self.get_param_value(&self.item.node.arguments, name, default, true)
     }
 
     #[doc(hidden)]
@@ -731,6 +1245,93 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
             context: self,
         }
     }
+
+    /// Returns the data scoped to the descendants of the current field.
+    ///
+    /// The data inserted into it override the data of the same type for the
+    /// fields of the value returned by the resolver, and their descendants,
+    /// but not for the current field and its siblings. For example, it allows
+    /// a field to select another database replica or locale for its subtree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::*;
+    ///
+    /// struct Locale(&'static str);
+    ///
+    /// struct Greeting;
+    ///
+    /// #[Object]
+    /// impl Greeting {
+    ///     async fn hello(&self, ctx: &Context<'_>) -> &'static str {
+    ///         match ctx.data_unchecked::<Locale>().0 {
+    ///             "fr" => "Bonjour",
+    ///             _ => "Hello",
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn greeting(&self) -> Greeting {
+    ///         Greeting
+    ///     }
+    ///
+    ///     async fn french(&self, ctx: &Context<'_>) -> Greeting {
+    ///         ctx.scoped_data().insert(Locale("fr"));
+    ///         Greeting
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .data(Locale("en"))
+    ///     .finish();
+    /// let res = schema
+    ///     .execute("{ greeting { hello } french { hello } }")
+    ///     .await
+    ///     .into_result()
+    ///     .unwrap()
+    ///     .data;
+    /// assert_eq!(
+    ///     res,
+    ///     value!({
+    ///         "greeting": { "hello": "Hello" },
+    ///         "french": { "hello": "Bonjour" },
+    ///     })
+    /// );
+    /// # });
+    /// ```
+    pub fn scoped_data(&self) -> &ScopedData {
+        self.scope.own.get_or_init(Default::default)
+    }
+
+    /// Gets the data created by the [`Provider`](crate::Provider) of type
+    /// `D`, calling the provider the first time it is needed in the request.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Error` if there is no provider of type `D`, or if the
+    /// provider fails.
+    pub async fn provide<D: Any + Send + Sync>(&self) -> Result<&'a D> {
+        let provided_data = self
+            .query_env
+            .provided_data
+            .get(&TypeId::of::<D>())
+            .ok_or_else(|| {
+                Error::new(format!(
+                    "Provider of `{}` does not exist.",
+                    std::any::type_name::<D>()
+                ))
+            })?;
+        let data = provided_data.get_or_provide(self).await?;
+        Ok(data
+            .downcast_ref::<D>()
+            .expect("You definitely encountered a bug!"))
+    }
 }
 
 impl<'a> ContextBase<'a, &'a Positioned<Directive>> {
@@ -740,7 +1341,16 @@ impl<'a> ContextBase<'a, &'a Positioned<Directive>> {
         name: &str,
         default: Option<fn() -> T>,
     ) -> ServerResult<(Pos, T)> {
-        self.get_param_value(&self.item.node.arguments, name, default)
+        self.get_param_value(&self.item.node.arguments, name, default, false)
+    }
+
+    #[doc(hidden)]
+    pub fn secret_param_value<T: InputType>(
+        &self,
+        name: &str,
+        default: Option<fn() -> T>,
+    ) -> ServerResult<(Pos, T)> {
+        self.get_param_value(&self.item.node.arguments, name, default, true)
     }
 }
 
@@ -781,6 +1391,63 @@ impl<'a> SelectionField<'a> {
         Ok(arguments)
     }
 
+    /// Get the arguments of the directive `name` applied to this field, or
+    /// `None` if the field has no such directive.
+    pub fn directive_arguments(&self, name: &str) -> ServerResult<Option<Vec<(Name, Value)>>> {
+        let directive = match self
+            .field
+            .directives
+            .iter()
+            .find(|directive| directive.node.name.node == name)
+        {
+            Some(directive) => directive,
+            None => return Ok(None),
+        };
+        let mut arguments = Vec::with_capacity(directive.node.arguments.len());
+        for (name, value) in &directive.node.arguments {
+            let pos = name.pos;
+            arguments.push((
+                name.node.clone(),
+                value
+                    .clone()
+                    .node
+                    .into_const_with(|name| self.context.var_value(&name, pos))?,
+            ));
+        }
+        Ok(Some(arguments))
+    }
+
+    /// Get the arguments of the Relay `@connection` directive applied to this
+    /// field.
+    ///
+    /// See [`SchemaBuilder::enable_relay_connection_directive`](crate::SchemaBuilder::enable_relay_connection_directive).
+    pub fn relay_connection(&self) -> ServerResult<Option<RelayConnection>> {
+        let arguments = match self.directive_arguments("connection")? {
+            Some(arguments) => arguments,
+            None => return Ok(None),
+        };
+        let mut key = None;
+        let mut filter = None;
+        for (name, value) in arguments {
+            match (name.as_str(), value) {
+                ("key", Value::String(value)) => key = Some(value),
+                ("filter", Value::List(values)) => {
+                    filter = Some(
+                        values
+                            .into_iter()
+                            .filter_map(|value| match value {
+                                Value::String(value) => Some(value),
+                                _ => None,
+                            })
+                            .collect(),
+                    )
+                }
+                _ => {}
+            }
+        }
+        Ok(key.map(|key| RelayConnection { key, filter }))
+    }
+
     /// Get all subfields of the current selection set.
     pub fn selection_set(&self) -> impl Iterator<Item = SelectionField<'a>> {
         SelectionFieldsIter {
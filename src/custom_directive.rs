@@ -1,8 +1,10 @@
 use std::borrow::Cow;
 
+use indexmap::IndexMap;
+
 use crate::{
     extensions::ResolveFut, parser::types::Directive, registry::Registry, Context,
-    ContextDirective, ServerResult, Value,
+    ContextDirective, Name, ServerResult, Value,
 };
 
 #[doc(hidden)]
@@ -27,10 +29,34 @@ pub trait TypeDirective {
 }
 
 /// Represents a custom directive.
+///
+/// The hooks are called in this order for each field the directive is
+/// applied to: [`skip_field`](Self::skip_field), then
+/// [`transform_arguments`](Self::transform_arguments), then
+/// [`resolve_field`](Self::resolve_field).
 #[async_trait::async_trait]
 #[allow(unused_variables)]
 pub trait CustomDirective: Sync + Send + 'static {
-    /// Called at resolve field.
+    /// Called before the field is resolved. If it returns `true`, the field is
+    /// not resolved and is omitted from the response, like with `@skip`.
+    async fn skip_field(&self, ctx: &Context<'_>) -> ServerResult<bool> {
+        Ok(false)
+    }
+
+    /// Called before the field is resolved, to transform its arguments, for
+    /// example to fill in a `locale` argument.
+    ///
+    /// The variables are already substituted in the arguments.
+    async fn transform_arguments(
+        &self,
+        ctx: &Context<'_>,
+        arguments: &mut IndexMap<Name, Value>,
+    ) -> ServerResult<()> {
+        Ok(())
+    }
+
+    /// Called at resolve field, it can post-process the value returned by the
+    /// resolver.
     async fn resolve_field(
         &self,
         ctx: &Context<'_>,
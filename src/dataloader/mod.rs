@@ -57,6 +57,7 @@
 //! ```
 
 mod cache;
+mod runtime;
 
 use std::{
     any::{Any, TypeId},
@@ -64,17 +65,24 @@ use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex,
     },
     time::Duration,
 };
 
+#[doc = include_str!("../docs/loader.md")]
+pub use async_graphql_derive::Loader;
 pub use cache::{CacheFactory, CacheStorage, HashMapCache, LruCache, NoCache};
 use fnv::FnvHashMap;
 use futures_channel::oneshot;
-use futures_timer::Delay;
-use futures_util::future::BoxFuture;
+#[cfg(feature = "dataloader-async-std")]
+pub use runtime::AsyncStdRuntime;
+pub use runtime::Runtime;
+#[cfg(feature = "dataloader-tokio")]
+pub use runtime::TokioRuntime;
+#[cfg(all(feature = "dataloader-wasm", target_arch = "wasm32"))]
+pub use runtime::WasmRuntime;
 #[cfg(feature = "tracing")]
 use tracing::{info_span, instrument, Instrument};
 #[cfg(feature = "tracing")]
@@ -126,6 +134,16 @@ pub trait Loader<K: Send + Sync + Hash + Eq + Clone + 'static>: Send + Sync + 's
     async fn load(&self, keys: &[K]) -> Result<HashMap<K, Self::Value>, Self::Error>;
 }
 
+/// A [`Loader`] with a single key type.
+///
+/// It is implemented by the [`Loader`](macro@Loader) macro, and allows
+/// loading the values with [`Context::load_one`](crate::Context::load_one)
+/// and [`Context::load_many`](crate::Context::load_many).
+pub trait SingleKeyLoader: Send + Sync + 'static {
+    /// Type of key.
+    type Key: Send + Sync + Hash + Eq + Clone + 'static;
+}
+
 struct DataLoaderInner<T> {
     requests: Mutex<FnvHashMap<TypeId, Box<dyn Any + Sync + Send>>>,
     loader: T,
@@ -187,15 +205,15 @@ pub struct DataLoader<T, C = NoCache> {
     delay: Duration,
     max_batch_size: usize,
     disable_cache: AtomicBool,
-    spawner: Box<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>,
+    runtime: Box<dyn Runtime>,
 }
 
 impl<T> DataLoader<T, NoCache> {
     /// Use `Loader` to create a [DataLoader] that does not cache records.
-    pub fn new<S, R>(loader: T, spawner: S) -> Self
-    where
-        S: Fn(BoxFuture<'static, ()>) -> R + Send + Sync + 'static,
-    {
+    ///
+    /// The `runtime` can be a spawner function such as `tokio::spawn`, see
+    /// [`Runtime`].
+    pub fn new(loader: T, runtime: impl Runtime) -> Self {
         Self {
             inner: Arc::new(DataLoaderInner {
                 requests: Mutex::new(Default::default()),
@@ -205,19 +223,14 @@ impl<T> DataLoader<T, NoCache> {
             delay: Duration::from_millis(1),
             max_batch_size: 1000,
             disable_cache: false.into(),
-            spawner: Box::new(move |fut| {
-                spawner(fut);
-            }),
+            runtime: Box::new(runtime),
         }
     }
 }
 
 impl<T, C: CacheFactory> DataLoader<T, C> {
     /// Use `Loader` to create a [DataLoader] with a cache factory.
-    pub fn with_cache<S, R>(loader: T, spawner: S, cache_factory: C) -> Self
-    where
-        S: Fn(BoxFuture<'static, ()>) -> R + Send + Sync + 'static,
-    {
+    pub fn with_cache(loader: T, runtime: impl Runtime, cache_factory: C) -> Self {
         Self {
             inner: Arc::new(DataLoaderInner {
                 requests: Mutex::new(Default::default()),
@@ -227,9 +240,7 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
             delay: Duration::from_millis(1),
             max_batch_size: 1000,
             disable_cache: false.into(),
-            spawner: Box::new(move |fut| {
-                spawner(fut);
-            }),
+            runtime: Box::new(runtime),
         }
     }
 
@@ -293,6 +304,21 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
     /// Use this `DataLoader` to load some data.
     #[cfg_attr(feature = "tracing", instrument(skip_all))]
     pub async fn load_many<K, I>(&self, keys: I) -> Result<HashMap<K, T::Value>, T::Error>
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        I: IntoIterator<Item = K>,
+        T: Loader<K>,
+    {
+        self.load_many_counted(keys, None).await
+    }
+
+    /// Loads some data like `load_many`, and increments `batches` if it
+    /// starts a new batch.
+    pub(crate) async fn load_many_counted<K, I>(
+        &self,
+        keys: I,
+        batches: Option<&AtomicUsize>,
+    ) -> Result<HashMap<K, T::Value>, T::Error>
     where
         K: Send + Sync + Hash + Eq + Clone + 'static,
         I: IntoIterator<Item = K>,
@@ -306,7 +332,7 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
 
         let tid = TypeId::of::<K>();
 
-        let (action, rx) = {
+        let (action, prev_count, rx) = {
             let mut requests = self.inner.requests.lock().unwrap();
             let typed_requests = requests
                 .entry(tid)
@@ -347,7 +373,7 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
             ));
 
             if typed_requests.keys.len() >= self.max_batch_size {
-                (Action::ImmediateLoad(typed_requests.take()), rx)
+                (Action::ImmediateLoad(typed_requests.take()), prev_count, rx)
             } else {
                 (
                     if !typed_requests.keys.is_empty() && prev_count == 0 {
@@ -355,11 +381,23 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
                     } else {
                         Action::Delay
                     },
+                    prev_count,
                     rx,
                 )
             }
         };
 
+        // A batch is started by the first keys added after the previous batch
+        // is taken, including when they are loaded immediately.
+        let starts_batch = match &action {
+            Action::ImmediateLoad(_) => prev_count == 0,
+            Action::StartFetch => true,
+            Action::Delay => false,
+        };
+        if let (true, Some(batches)) = (starts_batch, batches) {
+            batches.fetch_add(1, Ordering::Relaxed);
+        }
+
         match action {
             Action::ImmediateLoad(keys) => {
                 let inner = self.inner.clone();
@@ -370,15 +408,15 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
                     .instrument(info_span!("immediate_load"))
                     .in_current_span();
 
-                (self.spawner)(Box::pin(task));
+                self.runtime.spawn(Box::pin(task));
             }
             Action::StartFetch => {
                 let inner = self.inner.clone();
                 let disable_cache = self.disable_cache.load(Ordering::SeqCst);
-                let delay = self.delay;
+                let delay = self.runtime.delay(self.delay);
 
                 let task = async move {
-                    Delay::new(delay).await;
+                    delay.await;
 
                     let keys = {
                         let mut request = inner.requests.lock().unwrap();
@@ -396,7 +434,7 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
                 };
                 #[cfg(feature = "tracing")]
                 let task = task.instrument(info_span!("start_fetch")).in_current_span();
-                (self.spawner)(Box::pin(task))
+                self.runtime.spawn(Box::pin(task))
             }
             Action::Delay => {}
         }
@@ -468,6 +506,7 @@ mod tests {
     use std::sync::Arc;
 
     use fnv::FnvBuildHasher;
+    use futures_util::future::BoxFuture;
 
     use super::*;
 
@@ -688,4 +727,35 @@ mod tests {
         handle.abort();
         loader.load_many(vec![4, 5, 6]).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_dataloader_runtime() {
+        struct MyRuntime(Arc<AtomicUsize>);
+
+        impl Runtime for MyRuntime {
+            fn spawn(&self, fut: BoxFuture<'static, ()>) {
+                tokio::spawn(fut);
+            }
+
+            fn delay(&self, duration: Duration) -> BoxFuture<'static, ()> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Box::pin(tokio::time::sleep(duration))
+            }
+        }
+
+        let delays = Arc::new(AtomicUsize::new(0));
+        let loader = DataLoader::new(MyLoader, MyRuntime(delays.clone()));
+        assert_eq!(
+            loader.load_many(vec![1, 2, 3]).await.unwrap(),
+            vec![(1, 1), (2, 2), (3, 3)].into_iter().collect()
+        );
+        assert_eq!(delays.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "dataloader-tokio")]
+    #[tokio::test]
+    async fn test_dataloader_tokio_runtime() {
+        let loader = DataLoader::new(MyLoader, TokioRuntime);
+        assert_eq!(loader.load_one(1).await.unwrap(), Some(1));
+    }
 }
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+
+/// The runtime used by a [`DataLoader`](super::DataLoader) to spawn the
+/// loading tasks and to wait for the batching delay.
+///
+/// A spawner function, such as `tokio::spawn`, is also a runtime. Its delay
+/// uses the timer of the `futures-timer` crate, which runs in a separate
+/// thread and is not available on WebAssembly.
+pub trait Runtime: Send + Sync + 'static {
+    /// Spawns a future in the background.
+    fn spawn(&self, fut: BoxFuture<'static, ()>);
+
+    /// Returns a future that completes after `duration`.
+    fn delay(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(futures_timer::Delay::new(duration))
+    }
+}
+
+impl<S, R> Runtime for S
+where
+    S: Fn(BoxFuture<'static, ()>) -> R + Send + Sync + 'static,
+{
+    fn spawn(&self, fut: BoxFuture<'static, ()>) {
+        self(fut);
+    }
+}
+
+/// The Tokio runtime, it uses the timer of Tokio.
+#[cfg(feature = "dataloader-tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dataloader-tokio")))]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TokioRuntime;
+
+#[cfg(feature = "dataloader-tokio")]
+impl Runtime for TokioRuntime {
+    fn spawn(&self, fut: BoxFuture<'static, ()>) {
+        tokio::spawn(fut);
+    }
+
+    fn delay(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// The async-std runtime, it uses the timer of async-std.
+#[cfg(feature = "dataloader-async-std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dataloader-async-std")))]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct AsyncStdRuntime;
+
+#[cfg(feature = "dataloader-async-std")]
+impl Runtime for AsyncStdRuntime {
+    fn spawn(&self, fut: BoxFuture<'static, ()>) {
+        async_std::task::spawn(fut);
+    }
+
+    fn delay(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(async_std::task::sleep(duration))
+    }
+}
+
+/// The WebAssembly runtime, it spawns the tasks on the current thread with
+/// `wasm-bindgen-futures` and uses the timer of the browser.
+#[cfg(all(feature = "dataloader-wasm", target_arch = "wasm32"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "dataloader-wasm")))]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct WasmRuntime;
+
+#[cfg(all(feature = "dataloader-wasm", target_arch = "wasm32"))]
+impl Runtime for WasmRuntime {
+    fn spawn(&self, fut: BoxFuture<'static, ()>) {
+        wasm_bindgen_futures::spawn_local(fut);
+    }
+}
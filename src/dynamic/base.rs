@@ -1,3 +1,5 @@
+use indexmap::IndexSet;
+
 use crate::dynamic::{Field, InputValue, Interface, InterfaceField, Object, TypeRef};
 
 pub(crate) trait BaseField {
@@ -14,6 +16,8 @@ pub(crate) trait BaseContainer {
     fn graphql_type(&self) -> &str;
 
     fn field(&self, name: &str) -> Option<&Self::FieldType>;
+
+    fn implements(&self) -> &IndexSet<String>;
 }
 
 impl BaseField for Field {
@@ -44,6 +48,11 @@ impl BaseContainer for Object {
     fn field(&self, name: &str) -> Option<&Self::FieldType> {
         self.fields.get(name)
     }
+
+    #[inline]
+    fn implements(&self) -> &IndexSet<String> {
+        &self.implements
+    }
 }
 
 impl BaseField for InterfaceField {
@@ -74,4 +83,9 @@ impl BaseContainer for Interface {
     fn field(&self, name: &str) -> Option<&Self::FieldType> {
         self.fields.get(name)
     }
+
+    #[inline]
+    fn implements(&self) -> &IndexSet<String> {
+        &self.implements
+    }
 }
@@ -17,6 +17,7 @@ impl SchemaInner {
         self.check_input_objects()?;
         self.check_interfaces()?;
         self.check_unions()?;
+        self.check_entity_reference_resolvers()?;
         Ok(())
     }
 
@@ -341,6 +342,23 @@ impl SchemaInner {
         Ok(())
     }
 
+    fn check_entity_reference_resolvers(&self) -> Result<(), SchemaError> {
+        for type_name in self.entity_reference_resolvers.keys() {
+            match self.types.get(type_name) {
+                Some(Type::Object(_)) => {}
+                Some(_) => {
+                    return Err(format!(
+                        "The reference resolver of \"{}\" must be registered for an object",
+                        type_name
+                    )
+                    .into())
+                }
+                None => return Err(format!("Type \"{}\" not found", type_name).into()),
+            }
+        }
+        Ok(())
+    }
+
     fn check_unions(&self) -> Result<(), SchemaError> {
         // https://spec.graphql.org/October2021/#sec-Unions.Type-Validation
         for ty in self.types.values() {
@@ -427,6 +445,23 @@ fn check_is_valid_implementation(
         }
     }
 
+    // If implementedType declares it implements any interfaces, implementingType
+    // must also declare it implements those interfaces.
+    for interface_name in &implemented_type.implements {
+        if interface_name != implementing_type.name()
+            && !implementing_type.implements().contains(interface_name)
+        {
+            return Err(format!(
+                "{} \"{}\" must implement interface \"{}\" because it is implemented by interface \"{}\"",
+                implementing_type.graphql_type(),
+                implementing_type.name(),
+                interface_name,
+                implemented_type.name
+            )
+            .into());
+        }
+    }
+
     Ok(())
 }
 
@@ -96,6 +96,7 @@ impl Enum {
                     visible: None,
                     inaccessible: item.inaccessible,
                     tags: item.tags.clone(),
+                    int_value: None,
                 },
             );
         }
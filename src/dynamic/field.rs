@@ -10,7 +10,7 @@ use indexmap::IndexMap;
 
 use crate::{
     dynamic::{InputValue, ObjectAccessor, TypeRef},
-    registry::Deprecation,
+    registry::{Deprecation, MetaField},
     Context, Error, Result, Value,
 };
 
@@ -283,7 +283,7 @@ pub struct Field {
     pub(crate) description: Option<String>,
     pub(crate) arguments: IndexMap<String, InputValue>,
     pub(crate) ty: TypeRef,
-    pub(crate) resolver_fn: BoxResolverFn,
+    pub(crate) resolver_fn: Option<BoxResolverFn>,
     pub(crate) deprecation: Deprecation,
     pub(crate) external: bool,
     pub(crate) requires: Option<String>,
@@ -319,7 +319,33 @@ impl Field {
             description: None,
             arguments: Default::default(),
             ty: ty.into(),
-            resolver_fn: Box::new(resolver_fn),
+            resolver_fn: Some(Box::new(resolver_fn)),
+            deprecation: Deprecation::NoDeprecated,
+            external: false,
+            requires: None,
+            provides: None,
+            shareable: false,
+            inaccessible: false,
+            tags: Vec::new(),
+            override_from: None,
+        }
+    }
+
+    /// Create a GraphQL field without a resolver
+    ///
+    /// The field returns an error unless the schema has
+    /// [`Mocks`](crate::dynamic::Mocks), then it returns a mock value.
+    pub fn unresolved<N, T>(name: N, ty: T) -> Self
+    where
+        N: Into<String>,
+        T: Into<TypeRef>,
+    {
+        Self {
+            name: name.into(),
+            description: None,
+            arguments: Default::default(),
+            ty: ty.into(),
+            resolver_fn: None,
             deprecation: Deprecation::NoDeprecated,
             external: false,
             requires: None,
@@ -347,4 +373,31 @@ impl Field {
         self.arguments.insert(input_value.name.clone(), input_value);
         self
     }
+
+    pub(crate) fn to_meta_field(&self) -> MetaField {
+        let mut args = IndexMap::new();
+
+        for argument in self.arguments.values() {
+            args.insert(argument.name.clone(), argument.to_meta_input_value());
+        }
+
+        MetaField {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            args,
+            ty: self.ty.to_string(),
+            deprecation: self.deprecation.clone(),
+            cache_control: Default::default(),
+            external: self.external,
+            requires: self.requires.clone(),
+            provides: self.provides.clone(),
+            visible: None,
+            shareable: self.shareable,
+            inaccessible: self.inaccessible,
+            tags: self.tags.clone(),
+            override_from: self.override_from.clone(),
+            compute_complexity: None,
+            directive_invocations: vec![],
+        }
+    }
 }
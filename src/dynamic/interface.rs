@@ -260,6 +260,10 @@ impl Interface {
             },
         );
 
+        for interface in &self.implements {
+            registry.add_implements(&self.name, interface);
+        }
+
         Ok(())
     }
 }
@@ -433,4 +437,110 @@ mod tests {
             })
         );
     }
+
+    #[tokio::test]
+    async fn interface_implements_interface() {
+        let node =
+            Interface::new("Node").field(InterfaceField::new("id", TypeRef::named_nn(TypeRef::ID)));
+        let resource = Interface::new("Resource")
+            .implement("Node")
+            .field(InterfaceField::new("id", TypeRef::named_nn(TypeRef::ID)))
+            .field(InterfaceField::new(
+                "url",
+                TypeRef::named_nn(TypeRef::STRING),
+            ));
+        let file = Object::new("File")
+            .implement("Resource")
+            .implement("Node")
+            .field(Field::new("id", TypeRef::named_nn(TypeRef::ID), |_| {
+                FieldFuture::new(async { Ok(Some(Value::from("1"))) })
+            }))
+            .field(Field::new(
+                "url",
+                TypeRef::named_nn(TypeRef::STRING),
+                |_| FieldFuture::new(async { Ok(Some(Value::from("/a.txt"))) }),
+            ))
+            .field(Field::new(
+                "name",
+                TypeRef::named_nn(TypeRef::STRING),
+                |_| FieldFuture::new(async { Ok(Some(Value::from("a.txt"))) }),
+            ));
+        let query = Object::new("Query").field(Field::new(
+            "resource",
+            TypeRef::named_nn("Resource"),
+            |_| {
+                FieldFuture::new(async {
+                    Ok(Some(FieldValue::with_type(FieldValue::NULL, "File")))
+                })
+            },
+        ));
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(node)
+            .register(resource)
+            .register(file)
+            .register(query)
+            .finish()
+            .unwrap();
+
+        assert!(schema
+            .sdl()
+            .contains("interface Resource implements Node {"));
+
+        let query = r#"
+        {
+            resource {
+                ... on Node { id }
+                ... on Resource { url }
+                ... on File { name }
+            }
+            __type(name: "Resource") {
+                interfaces { name }
+                possibleTypes { name }
+            }
+        }
+        "#;
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap().data,
+            value!({
+                "resource": {
+                    "id": "1",
+                    "url": "/a.txt",
+                    "name": "a.txt",
+                },
+                "__type": {
+                    "interfaces": [{ "name": "Node" }],
+                    "possibleTypes": [{ "name": "File" }],
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn missing_transitive_interface() {
+        let node =
+            Interface::new("Node").field(InterfaceField::new("id", TypeRef::named_nn(TypeRef::ID)));
+        let resource = Interface::new("Resource")
+            .implement("Node")
+            .field(InterfaceField::new("id", TypeRef::named_nn(TypeRef::ID)));
+        let file = Object::new("File").implement("Resource").field(Field::new(
+            "id",
+            TypeRef::named_nn(TypeRef::ID),
+            |_| FieldFuture::new(async { Ok(Some(Value::from("1"))) }),
+        ));
+        let query =
+            Object::new("Query").field(Field::new("file", TypeRef::named_nn("File"), |_| {
+                FieldFuture::new(async { Ok(Some(FieldValue::NULL)) })
+            }));
+        let err = Schema::build(query.type_name(), None, None)
+            .register(node)
+            .register(resource)
+            .register(file)
+            .register(query)
+            .finish()
+            .unwrap_err();
+        assert_eq!(
+            err.0,
+            "Object \"File\" must implement interface \"Node\" because it is implemented by interface \"Resource\""
+        );
+    }
 }
@@ -0,0 +1,320 @@
+use std::{collections::HashMap, fmt::Debug};
+
+use crate::{
+    dynamic::{r#type::Type, type_ref::TypeRefInner, FieldValue, Schema},
+    Name, Value,
+};
+
+type MockFn = Box<dyn Fn() -> Value + Send + Sync>;
+
+/// Mock values of a dynamic schema.
+///
+/// With mocks, the fields without resolvers, created with
+/// [`Field::unresolved`](crate::dynamic::Field::unresolved), return plausible
+/// values generated from their types instead of errors. This allows running
+/// the clients against a mocked version of the real schema before the
+/// resolvers exist.
+///
+/// The default mock values are:
+///
+/// | Type      | Mock value                               |
+/// |-----------|------------------------------------------|
+/// | Int       | `42`                                     |
+/// | Float     | `4.2`                                    |
+/// | String    | `"Hello World"`                          |
+/// | Boolean   | `true`                                   |
+/// | ID        | `"1"`                                    |
+/// | Scalar    | `"Hello World"`                          |
+/// | Enum      | The first item                           |
+/// | List      | Two mock values                          |
+/// | Interface | A mock object of the first possible type |
+/// | Union     | A mock object of the first possible type |
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql::{dynamic::*, value, Value};
+///
+/// let user = Object::new("User")
+///     .field(Field::unresolved("id", TypeRef::named_nn(TypeRef::ID)))
+///     .field(Field::unresolved("name", TypeRef::named_nn(TypeRef::STRING)))
+///     .field(Field::unresolved("createdAt", TypeRef::named_nn("DateTime")));
+/// let query = Object::new("Query").field(Field::unresolved(
+///     "users",
+///     TypeRef::named_nn_list_nn(user.type_name()),
+/// ));
+///
+/// let schema = Schema::build(query.type_name(), None, None)
+///     .register(Scalar::new("DateTime"))
+///     .register(user)
+///     .register(query)
+///     .mocks(
+///         Mocks::new()
+///             .mock("DateTime", || Value::from("2023-01-01T00:00:00Z"))
+///             .list_length(1),
+///     )
+///     .finish()
+///     .unwrap();
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// assert_eq!(
+///     schema
+///         .execute("{ users { id name createdAt } }")
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .data,
+///     value!({
+///         "users": [{ "id": "1", "name": "Hello World", "createdAt": "2023-01-01T00:00:00Z" }]
+///     })
+/// );
+/// # });
+/// ```
+pub struct Mocks {
+    mocks: HashMap<String, MockFn>,
+    list_length: usize,
+    all_fields: bool,
+}
+
+impl Debug for Mocks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mocks")
+            .field("types", &self.mocks.keys())
+            .field("list_length", &self.list_length)
+            .field("all_fields", &self.all_fields)
+            .finish()
+    }
+}
+
+impl Default for Mocks {
+    fn default() -> Self {
+        Self {
+            mocks: Default::default(),
+            list_length: 2,
+            all_fields: false,
+        }
+    }
+}
+
+impl Mocks {
+    /// Create the default mocks.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the function that returns the mock values of a type.
+    ///
+    /// For an object type, the function returns an object whose fields
+    /// replace the mock values of the unresolved fields.
+    #[must_use]
+    pub fn mock(
+        mut self,
+        type_name: impl Into<String>,
+        f: impl Fn() -> Value + Send + Sync + 'static,
+    ) -> Self {
+        self.mocks.insert(type_name.into(), Box::new(f));
+        self
+    }
+
+    /// Set the number of the items of the mock lists, the default is `2`.
+    #[must_use]
+    pub fn list_length(self, list_length: usize) -> Self {
+        Self {
+            list_length,
+            ..self
+        }
+    }
+
+    /// Mock all fields, including the fields that have resolvers.
+    #[must_use]
+    pub fn all_fields(self) -> Self {
+        Self {
+            all_fields: true,
+            ..self
+        }
+    }
+
+    pub(crate) fn is_mock_field(&self, unresolved: bool) -> bool {
+        unresolved || self.all_fields
+    }
+
+    pub(crate) fn mock_field<'a>(
+        &self,
+        schema: &Schema,
+        field_name: &str,
+        type_ref: &TypeRefInner,
+        parent_value: &FieldValue<'_>,
+    ) -> FieldValue<'a> {
+        if let Some(Value::Object(fields)) = parent_value.as_value() {
+            if let Some(value) = fields.get(field_name) {
+                return FieldValue::value(value.clone());
+            }
+        }
+        self.mock_type_ref(schema, type_ref)
+    }
+
+    fn mock_type_ref<'a>(&self, schema: &Schema, type_ref: &TypeRefInner) -> FieldValue<'a> {
+        match type_ref {
+            TypeRefInner::NonNull(type_ref) => self.mock_type_ref(schema, type_ref),
+            TypeRefInner::List(type_ref) => FieldValue::list(
+                (0..self.list_length).map(|_| self.mock_type_ref(schema, type_ref)),
+            ),
+            TypeRefInner::Named(type_name) => self.mock_named_type(schema, type_name),
+        }
+    }
+
+    fn mock_named_type<'a>(&self, schema: &Schema, type_name: &str) -> FieldValue<'a> {
+        if let Some(f) = self.mocks.get(type_name) {
+            return FieldValue::value(f());
+        }
+
+        match schema.0.types.get(type_name) {
+            Some(Type::Scalar(_)) => FieldValue::value(match type_name {
+                "Int" => Value::from(42),
+                "Float" => Value::from(4.2),
+                "Boolean" => Value::from(true),
+                "ID" => Value::from("1"),
+                _ => Value::from("Hello World"),
+            }),
+            Some(Type::Enum(e)) => match e.enum_values.keys().next() {
+                Some(name) => FieldValue::value(Value::Enum(Name::new(name))),
+                None => FieldValue::NULL,
+            },
+            Some(Type::Object(_)) => FieldValue::value(Value::Object(Default::default())),
+            Some(Type::Interface(_)) | Some(Type::Union(_)) => {
+                let object_type = schema
+                    .0
+                    .env
+                    .registry
+                    .types
+                    .get(type_name)
+                    .and_then(|meta_type| meta_type.possible_types())
+                    .and_then(|possible_types| possible_types.iter().next());
+                match object_type {
+                    Some(object_type) => self
+                        .mock_named_type(schema, object_type)
+                        .with_type(object_type.clone()),
+                    None => FieldValue::NULL,
+                }
+            }
+            _ => FieldValue::NULL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dynamic::*, value, Value};
+
+    #[tokio::test]
+    async fn mock_types() {
+        let status = Enum::new("Status").item("ACTIVE").item("DISABLED");
+        let user = Object::new("User")
+            .implement("Node")
+            .field(Field::unresolved("id", TypeRef::named_nn(TypeRef::ID)))
+            .field(Field::unresolved("age", TypeRef::named(TypeRef::INT)))
+            .field(Field::unresolved("score", TypeRef::named(TypeRef::FLOAT)))
+            .field(Field::unresolved("admin", TypeRef::named(TypeRef::BOOLEAN)))
+            .field(Field::unresolved(
+                "status",
+                TypeRef::named_nn(status.type_name()),
+            ))
+            .field(Field::new(
+                "name",
+                TypeRef::named_nn(TypeRef::STRING),
+                |_| FieldFuture::new(async { Ok(Some(Value::from("abc"))) }),
+            ));
+        let node =
+            Interface::new("Node").field(InterfaceField::new("id", TypeRef::named_nn(TypeRef::ID)));
+        let query = Object::new("Query")
+            .field(Field::unresolved(
+                "node",
+                TypeRef::named_nn(node.type_name()),
+            ))
+            .field(Field::unresolved(
+                "users",
+                TypeRef::named_nn_list_nn(user.type_name()),
+            ))
+            .field(Field::unresolved(
+                "admin",
+                TypeRef::named_nn(user.type_name()),
+            ));
+
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(status)
+            .register(user)
+            .register(node)
+            .register(query)
+            .mocks(Mocks::new().mock("User", || value!({ "age": 30, "admin": true })))
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .execute(
+                    "{ node { id ... on User { status } } users { age } admin { id age score admin status name } }"
+                )
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({
+                "node": { "id": "1", "status": "ACTIVE" },
+                "users": [{ "age": 30 }, { "age": 30 }],
+                "admin": {
+                    "id": "1",
+                    "age": 30,
+                    "score": 4.2,
+                    "admin": true,
+                    "status": "ACTIVE",
+                    "name": "abc",
+                },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_all_fields() {
+        let query = Object::new("Query")
+            .field(Field::new("value", TypeRef::named_nn(TypeRef::INT), |_| {
+                FieldFuture::new(async { Ok(Some(Value::from(10))) })
+            }))
+            .field(Field::new(
+                "values",
+                TypeRef::named_list(TypeRef::STRING),
+                |_| FieldFuture::new(async { Ok(FieldValue::NONE) }),
+            ));
+
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(query)
+            .mocks(Mocks::new().all_fields().list_length(3))
+            .finish()
+            .unwrap();
+        assert_eq!(
+            schema
+                .execute("{ value values }")
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({
+                "value": 42,
+                "values": ["Hello World", "Hello World", "Hello World"],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn unresolved_without_mocks() {
+        let query =
+            Object::new("Query").field(Field::unresolved("value", TypeRef::named(TypeRef::INT)));
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(query)
+            .finish()
+            .unwrap();
+        assert_eq!(
+            schema.execute("{ value }").await.into_result().unwrap_err()[0].message,
+            "field \"value\" has no resolver"
+        );
+    }
+}
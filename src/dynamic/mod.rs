@@ -40,6 +40,7 @@ mod field;
 mod input_object;
 mod input_value;
 mod interface;
+mod mock;
 mod object;
 mod request;
 mod resolve;
@@ -56,10 +57,12 @@ pub use field::{Field, FieldFuture, FieldValue, ResolverContext};
 pub use input_object::InputObject;
 pub use input_value::InputValue;
 pub use interface::{Interface, InterfaceField};
+pub use mock::Mocks;
 pub use object::Object;
 pub use r#enum::{Enum, EnumItem};
 pub use r#type::Type;
 pub use request::{DynamicRequest, DynamicRequestExt};
+pub(crate) use resolve::resolve_dynamic_field;
 pub use scalar::Scalar;
 pub use schema::{Schema, SchemaBuilder};
 pub use subscription::{Subscription, SubscriptionField, SubscriptionFieldFuture};
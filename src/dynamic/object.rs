@@ -2,7 +2,7 @@ use indexmap::{IndexMap, IndexSet};
 
 use crate::{
     dynamic::{Field, SchemaError},
-    registry::{MetaField, MetaType, Registry},
+    registry::{MetaType, Registry},
 };
 
 /// A GraphQL object type
@@ -131,33 +131,7 @@ impl Object {
         let mut fields = IndexMap::new();
 
         for field in self.fields.values() {
-            let mut args = IndexMap::new();
-
-            for argument in field.arguments.values() {
-                args.insert(argument.name.clone(), argument.to_meta_input_value());
-            }
-
-            fields.insert(
-                field.name.clone(),
-                MetaField {
-                    name: field.name.clone(),
-                    description: field.description.clone(),
-                    args,
-                    ty: field.ty.to_string(),
-                    deprecation: field.deprecation.clone(),
-                    cache_control: Default::default(),
-                    external: field.external,
-                    requires: field.requires.clone(),
-                    provides: field.provides.clone(),
-                    visible: None,
-                    shareable: field.shareable,
-                    inaccessible: field.inaccessible,
-                    tags: field.tags.clone(),
-                    override_from: field.override_from.clone(),
-                    compute_complexity: None,
-                    directive_invocations: vec![],
-                },
-            );
+            fields.insert(field.name.clone(), field.to_meta_field());
         }
 
         registry.types.insert(
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, pin::Pin};
+use std::{any::Any, borrow::Cow, pin::Pin, sync::atomic::AtomicUsize};
 
 use async_graphql_derive::SimpleObject;
 use futures_util::{future::BoxFuture, Future, FutureExt};
@@ -6,9 +6,11 @@ use indexmap::IndexMap;
 
 use crate::{
     dynamic::{
-        field::FieldValueInner, type_ref::TypeRefInner, FieldValue, Object, ObjectAccessor,
+        field::FieldValueInner, type_ref::TypeRefInner, Field, FieldValue, Object, ObjectAccessor,
         ResolverContext, Schema, Type, TypeRef,
     },
+    entity_cache::add_entity_cache_tags,
+    export_directive::export_field_value,
     extensions::ResolveInfo,
     parser::types::Selection,
     resolver_utils::create_value_object,
@@ -188,13 +190,14 @@ fn collect_fields<'a>(
                         fields.push(
                             async move {
                                 let ctx_field = ctx.with_field(field);
-                                let entity_resolver =
-                                    schema.0.entity_resolver.as_ref().ok_or_else(|| {
-                                        ctx_field.set_error_path(
-                                            Error::new("internal: missing entity resolver")
-                                                .into_server_error(ctx_field.item.pos),
-                                        )
-                                    })?;
+                                if schema.0.entity_resolver.is_none()
+                                    && schema.0.entity_reference_resolvers.is_empty()
+                                {
+                                    return Err(ctx_field.set_error_path(
+                                        Error::new("internal: missing entity resolver")
+                                            .into_server_error(ctx_field.item.pos),
+                                    ));
+                                }
                                 let entity_type = TypeRef::named_list_nn("_Entity");
 
                                 let arguments = ObjectAccessor(Cow::Owned(
@@ -210,14 +213,35 @@ fn collect_fields<'a>(
                                         .collect::<ServerResult<IndexMap<Name, Value>>>()?,
                                 ));
 
-                                let field_value = (entity_resolver)(ResolverContext {
-                                    ctx: &ctx_field,
-                                    args: arguments,
-                                    parent_value,
-                                })
-                                .0
-                                .await
-                                .map_err(|err| err.into_server_error(field.pos))?;
+                                if let Some(Value::List(representations)) =
+                                    arguments.0.get("representations")
+                                {
+                                    add_entity_cache_tags(&ctx_field, representations);
+                                }
+
+                                let field_value = match &schema.0.entity_resolver {
+                                    Some(entity_resolver) => {
+                                        (entity_resolver)(ResolverContext {
+                                            ctx: &ctx_field,
+                                            args: arguments,
+                                            parent_value,
+                                        })
+                                        .0
+                                        .await
+                                    }
+                                    None => {
+                                        resolve_entity_references(
+                                            schema,
+                                            &ctx_field,
+                                            arguments,
+                                            parent_value,
+                                        )
+                                        .await
+                                    }
+                                }
+                                .map_err(|err| {
+                                    ctx_field.set_error_path(err.into_server_error(field.pos))
+                                })?;
                                 let value = resolve(
                                     schema,
                                     &ctx_field,
@@ -249,7 +273,16 @@ fn collect_fields<'a>(
                     let ctx = ctx.clone();
                     fields.push(
                         async move {
+                            if ctx.query_env.is_response_size_exceeded() {
+                                return Ok((field.node.response_key().node.clone(), Value::Null));
+                            }
+
                             let ctx_field = ctx.with_field(field);
+                            if ctx.query_env.is_errors_exceeded() {
+                                return Err(ctx_field.set_error_path(
+                                    ctx.query_env.errors_exceeded_error(field.pos),
+                                ));
+                            }
                             let arguments = ObjectAccessor(Cow::Owned(
                                 field
                                     .node
@@ -273,14 +306,30 @@ fn collect_fields<'a>(
                             };
 
                             let resolve_fut = async {
-                                let field_value = (field_def.resolver_fn)(ResolverContext {
-                                    ctx: &ctx_field,
-                                    args: arguments,
-                                    parent_value,
-                                })
-                                .0
-                                .await
-                                .map_err(|err| err.into_server_error(field.pos))?;
+                                let field_value = match (&field_def.resolver_fn, &schema.0.mocks) {
+                                    (resolver_fn, Some(mocks))
+                                        if mocks.is_mock_field(resolver_fn.is_none()) =>
+                                    {
+                                        Some(mocks.mock_field(
+                                            schema,
+                                            &field_def.name,
+                                            &field_def.ty.0,
+                                            parent_value,
+                                        ))
+                                    }
+                                    (Some(resolver_fn), _) => (resolver_fn)(ResolverContext {
+                                        ctx: &ctx_field,
+                                        args: arguments,
+                                        parent_value,
+                                    })
+                                    .0
+                                    .await
+                                    .map_err(|err| err.into_server_error(field.pos))?,
+                                    (None, _) => {
+                                        return Err(unresolved_field_error(field_def)
+                                            .into_server_error(field.pos))
+                                    }
+                                };
                                 let value = resolve(
                                     schema,
                                     &ctx_field,
@@ -288,6 +337,12 @@ fn collect_fields<'a>(
                                     field_value.as_ref(),
                                 )
                                 .await?;
+                                match &value {
+                                    Some(value) if schema.0.export_directive => {
+                                        export_field_value(&ctx_field, &field.node, value)?;
+                                    }
+                                    _ => {}
+                                }
                                 Ok(value)
                             };
                             futures_util::pin_mut!(resolve_fut);
@@ -298,6 +353,7 @@ fn collect_fields<'a>(
                                 .resolve(resolve_info, &mut resolve_fut)
                                 .await?
                                 .unwrap_or_default();
+                            ctx.query_env.add_response_size(&field.node, &res_value);
                             Ok((field.node.response_key().node.clone(), res_value))
                         }
                         .boxed(),
@@ -358,6 +414,176 @@ fn collect_fields<'a>(
     Ok(())
 }
 
+async fn resolve_entity_references<'a>(
+    schema: &'a Schema,
+    ctx: &'a Context<'a>,
+    arguments: ObjectAccessor<'_>,
+    parent_value: &'a FieldValue<'a>,
+) -> Result<Option<FieldValue<'a>>, Error> {
+    let representations = match arguments.0.into_owned().remove("representations") {
+        Some(Value::List(representations)) => representations,
+        _ => return Err(Error::new("internal: invalid representations")),
+    };
+
+    let mut futures = Vec::with_capacity(representations.len());
+    for (idx, representation) in representations.into_iter().enumerate() {
+        let representation = match representation {
+            Value::Object(representation) => representation,
+            _ => return Err(Error::new("internal: invalid representation")),
+        };
+        let type_name = match representation.get("__typename") {
+            Some(Value::String(type_name)) => type_name.clone(),
+            _ => {
+                return Err(Error::new(
+                    "internal: representation missing \"__typename\"",
+                ))
+            }
+        };
+        if ctx.schema_env.unknown_entity_hook.is_none()
+            && !schema.0.entity_reference_resolvers.contains_key(&type_name)
+        {
+            return Err(missing_reference_resolver(&type_name));
+        }
+        futures.push(async move {
+            match resolve_entity_reference(schema, ctx, type_name, representation, parent_value)
+                .await
+            {
+                Ok(Some(value)) => value,
+                Ok(None) => FieldValue::NULL,
+                Err(err) => {
+                    // The other entities of the batch are still returned.
+                    ctx.add_error(
+                        ctx.with_index(idx)
+                            .set_error_path(err.into_server_error(ctx.item.pos)),
+                    );
+                    FieldValue::NULL
+                }
+            }
+        });
+    }
+
+    let values = futures_util::future::join_all(futures).await;
+    Ok(Some(FieldValue::list(values)))
+}
+
+async fn resolve_entity_reference<'a>(
+    schema: &'a Schema,
+    ctx: &'a Context<'a>,
+    type_name: String,
+    representation: IndexMap<Name, Value>,
+    parent_value: &'a FieldValue<'a>,
+) -> Result<Option<FieldValue<'a>>, Error> {
+    let (type_name, representation) = match schema.0.entity_reference_resolvers.get(&type_name) {
+        Some(_) => (type_name, representation),
+        None => {
+            let hook = match &ctx.schema_env.unknown_entity_hook {
+                Some(hook) => hook,
+                None => return Err(missing_reference_resolver(&type_name)),
+            };
+            match hook(ctx, &Value::Object(representation)).await? {
+                Some(Value::Object(representation)) => match representation.get("__typename") {
+                    Some(Value::String(type_name)) => (type_name.clone(), representation),
+                    _ => {
+                        return Err(Error::new(
+                            "internal: representation missing \"__typename\"",
+                        ))
+                    }
+                },
+                Some(_) => return Err(Error::new("internal: invalid representation")),
+                None => return Ok(None),
+            }
+        }
+    };
+    let resolver_fn = schema
+        .0
+        .entity_reference_resolvers
+        .get(&type_name)
+        .ok_or_else(|| missing_reference_resolver(&type_name))?;
+    let value = (resolver_fn)(ResolverContext {
+        ctx,
+        args: ObjectAccessor(Cow::Owned(representation)),
+        parent_value,
+    })
+    .0
+    .await?;
+    Ok(value.map(|value| match value {
+        value @ FieldValue(FieldValueInner::WithType { .. }) => value,
+        value => value.with_type(type_name),
+    }))
+}
+
+fn missing_reference_resolver(type_name: &str) -> Error {
+    Error::new(format!(
+        "internal: missing reference resolver for entity \"{}\"",
+        type_name
+    ))
+}
+
+/// Resolves a dynamic field attached to a code-first object.
+///
+/// The parent value of the resolver borrows the object, if it can be
+/// downcast.
+pub(crate) async fn resolve_dynamic_field(
+    field_def: &Field,
+    parent: Option<&(dyn Any + Send + Sync)>,
+    ctx: &Context<'_>,
+) -> ServerResult<Option<Value>> {
+    fn to_value(ctx: &Context<'_>, value: &FieldValue<'_>) -> ServerResult<Value> {
+        match &value.0 {
+            FieldValueInner::Value(value) => Ok(value.clone()),
+            FieldValueInner::List(values) => values
+                .iter()
+                .map(|value| to_value(ctx, value))
+                .collect::<ServerResult<Vec<_>>>()
+                .map(Value::List),
+            FieldValueInner::WithType { value, .. } => to_value(ctx, value),
+            FieldValueInner::BorrowedAny(_) | FieldValueInner::OwnedAny(_) => Err(ctx
+                .set_error_path(
+                    Error::new("internal: invalid value, expected \"FieldValue::Value\"")
+                        .into_server_error(ctx.item.pos),
+                )),
+        }
+    }
+
+    let arguments = ObjectAccessor(Cow::Owned(
+        ctx.item
+            .node
+            .arguments
+            .iter()
+            .map(|(name, value)| {
+                ctx.resolve_input_value(value.clone())
+                    .map(|value| (name.node.clone(), value))
+            })
+            .collect::<ServerResult<IndexMap<Name, Value>>>()?,
+    ));
+
+    let resolver_fn = field_def.resolver_fn.as_ref().ok_or_else(|| {
+        ctx.set_error_path(unresolved_field_error(field_def).into_server_error(ctx.item.pos))
+    })?;
+    let parent_value = parent.map_or(FieldValue::NULL, FieldValue::borrowed_any);
+    let field_value = (resolver_fn)(ResolverContext {
+        ctx,
+        args: arguments,
+        parent_value: &parent_value,
+    })
+    .0
+    .await
+    .map_err(|err| ctx.set_error_path(err.into_server_error(ctx.item.pos)))?;
+
+    match field_value {
+        Some(value) => to_value(ctx, &value).map(Some),
+        None if field_def.ty.is_nullable() => Ok(None),
+        None => Err(ctx.set_error_path(
+            Error::new("internal: non-null types require a return value")
+                .into_server_error(ctx.item.pos),
+        )),
+    }
+}
+
+fn unresolved_field_error(field_def: &Field) -> Error {
+    Error::new(format!("field \"{}\" has no resolver", field_def.name))
+}
+
 pub(crate) fn resolve<'a>(
     schema: &'a Schema,
     ctx: &'a Context<'a>,
@@ -408,6 +634,7 @@ async fn resolve_list<'a>(
     type_ref: &'a TypeRefInner,
     values: &[FieldValue<'_>],
 ) -> ServerResult<Option<Value>> {
+    let list_size = &AtomicUsize::new(0);
     let mut futures = Vec::with_capacity(values.len());
     for (idx, value) in values.iter().enumerate() {
         let ctx_item = ctx.with_index(idx);
@@ -436,8 +663,12 @@ async fn resolve_list<'a>(
                 .query_env
                 .extensions
                 .resolve(resolve_info, &mut resolve_fut)
-                .await?;
-            Ok::<_, ServerError>(res_value.unwrap_or_default())
+                .await?
+                .unwrap_or_default();
+            ctx.query_env
+                .check_list_item_size(list_size, ctx.item, &res_value)
+                .map_err(|err| ctx_item.set_error_path(err))?;
+            Ok::<_, ServerError>(res_value)
         });
     }
     let values = futures_util::future::try_join_all(futures).await?;
@@ -451,16 +682,10 @@ async fn resolve_value(
     value: &FieldValue<'_>,
 ) -> ServerResult<Option<Value>> {
     match (field_type, &value.0) {
-        (Type::Scalar(scalar), FieldValueInner::Value(value)) if scalar.validate(value) => {
-            Ok(Some(value.clone()))
-        }
-        (Type::Scalar(scalar), _) => Err(ctx.set_error_path(
-            Error::new(format!(
-                "internal: invalid value for scalar \"{}\", expected \"FieldValue::Value\"",
-                scalar.name
-            ))
-            .into_server_error(ctx.item.pos),
-        )),
+        (Type::Scalar(scalar), _) => scalar
+            .coerce_output(value)
+            .map(Some)
+            .map_err(|err| ctx.set_error_path(err.into_server_error(ctx.item.pos))),
 
         (Type::Object(object), _) => {
             resolve_container(
@@ -600,6 +825,9 @@ async fn resolve_value(
             )
             .await
         }
+        // The null items of a list, such as the unresolved entities of the
+        // `_entities` field.
+        (Type::Union(_), FieldValueInner::Value(Value::Null)) => Ok(None),
         (Type::Union(union), _) => Err(ctx.set_error_path(
             Error::new(format!(
                 "internal: invalid value for union \"{}\", expected \"FieldValue::WithType\"",
@@ -4,11 +4,13 @@ use std::{
 };
 
 use crate::{
-    dynamic::SchemaError,
+    dynamic::{field::FieldValueInner, FieldValue, SchemaError},
     registry::{MetaType, Registry, ScalarValidatorFn},
-    Value,
+    Error, ScalarType, Value,
 };
 
+type ScalarCoercionFn = Arc<dyn Fn(&FieldValue<'_>) -> Option<Value> + Send + Sync>;
+
 /// A GraphQL scalar type
 ///
 /// # Examples
@@ -47,6 +49,7 @@ pub struct Scalar {
     pub(crate) description: Option<String>,
     pub(crate) specified_by_url: Option<String>,
     pub(crate) validator: Option<ScalarValidatorFn>,
+    coercion: Option<ScalarCoercionFn>,
     inaccessible: bool,
     tags: Vec<String>,
 }
@@ -72,6 +75,7 @@ impl Scalar {
             description: None,
             specified_by_url: None,
             validator: None,
+            coercion: None,
             inaccessible: false,
             tags: Vec::new(),
         }
@@ -90,6 +94,58 @@ impl Scalar {
         }
     }
 
+    /// Use the parsing and serialization of a code-first scalar
+    ///
+    /// Input values are validated with [`ScalarType::is_valid`] and
+    /// [`ScalarType::parse`]. Resolvers can return either a `T` with
+    /// [`FieldValue::owned_any`] or a [`Value`] that `T` can parse, and the
+    /// result is serialized with [`ScalarType::to_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::{dynamic::*, value, Value, ID};
+    ///
+    /// let id = Scalar::new("MyID").with_coercion::<ID>();
+    ///
+    /// let query = Object::new("Query").field(Field::new("value", TypeRef::named_nn(id.type_name()), |_| {
+    ///     FieldFuture::new(async move { Ok(Some(FieldValue::owned_any(ID::from("abc")))) })
+    /// }));
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    ///
+    /// let schema = Schema::build(query.type_name(), None, None)
+    ///     .register(id)
+    ///     .register(query)
+    ///     .finish()?;
+    ///
+    /// assert_eq!(
+    ///    schema
+    ///        .execute("{ value }")
+    ///        .await
+    ///        .into_result()
+    ///        .unwrap()
+    ///        .data,
+    ///    value!({ "value": "abc" })
+    /// );
+    ///
+    /// # Ok::<_, SchemaError>(())
+    /// # }).unwrap();
+    /// ```
+    #[inline]
+    pub fn with_coercion<T: ScalarType + Send + Sync + 'static>(self) -> Self {
+        Self {
+            validator: Some(Arc::new(|value| {
+                T::is_valid(value) && T::parse(value.clone()).is_ok()
+            })),
+            coercion: Some(Arc::new(|value| match &value.0 {
+                FieldValueInner::Value(value) => T::parse(value.clone()).ok().map(|v| v.to_value()),
+                _ => value.downcast_ref::<T>().map(ScalarType::to_value),
+            })),
+            ..self
+        }
+    }
+
     #[inline]
     pub(crate) fn validate(&self, value: &Value) -> bool {
         match &self.validator {
@@ -98,6 +154,22 @@ impl Scalar {
         }
     }
 
+    pub(crate) fn coerce_output(&self, value: &FieldValue<'_>) -> Result<Value, Error> {
+        match (&self.coercion, &value.0) {
+            (Some(coercion), _) => (coercion)(value).ok_or_else(|| {
+                Error::new(format!(
+                    "internal: invalid value for scalar \"{}\"",
+                    self.name
+                ))
+            }),
+            (None, FieldValueInner::Value(value)) if self.validate(value) => Ok(value.clone()),
+            (None, _) => Err(Error::new(format!(
+                "internal: invalid value for scalar \"{}\", expected \"FieldValue::Value\"",
+                self.name
+            ))),
+        }
+    }
+
     /// Set the specified by url
     #[inline]
     pub fn specified_by_url(self, specified_by_url: impl Into<String>) -> Self {
@@ -134,7 +206,10 @@ impl Scalar {
 mod tests {
     use async_graphql_parser::Pos;
 
-    use crate::{dynamic::*, value, PathSegment, ServerError};
+    use crate::{
+        dynamic::*, value, InputValueError, InputValueResult, PathSegment, Scalar, ScalarType,
+        ServerError, Value,
+    };
 
     #[tokio::test]
     async fn custom_scalar() {
@@ -201,4 +276,76 @@ mod tests {
             }]
         );
     }
+
+    #[tokio::test]
+    async fn scalar_with_coercion() {
+        struct Even(i32);
+
+        #[Scalar(internal)]
+        impl ScalarType for Even {
+            fn parse(value: Value) -> InputValueResult<Self> {
+                match &value {
+                    Value::Number(n) if matches!(n.as_i64(), Some(n) if n % 2 == 0) => {
+                        Ok(Even(n.as_i64().unwrap() as i32))
+                    }
+                    _ => Err(InputValueError::expected_type(value)),
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::from(self.0)
+            }
+        }
+
+        let scalar = Scalar::new("Even").with_coercion::<Even>();
+        let query = Object::new("Query")
+            .field(
+                Field::new("double", TypeRef::named_nn(scalar.type_name()), |ctx| {
+                    FieldFuture::new(async move {
+                        let n = ctx.args.try_get("n")?.i64()? as i32;
+                        Ok(Some(FieldValue::owned_any(Even(n * 2))))
+                    })
+                })
+                .argument(InputValue::new("n", TypeRef::named_nn(TypeRef::INT))),
+            )
+            .field(
+                Field::new("echo", TypeRef::named_nn(scalar.type_name()), |ctx| {
+                    FieldFuture::new(async move {
+                        Ok(Some(FieldValue::value(ctx.args.try_get("value")?.i64()?)))
+                    })
+                })
+                .argument(InputValue::new("value", TypeRef::named_nn(TypeRef::INT))),
+            );
+
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(query)
+            .register(scalar)
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .execute("{ double(n: 3) echo(value: 4) }")
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "double": 6, "echo": 4 })
+        );
+
+        assert_eq!(
+            schema
+                .execute("{ echo(value: 3) }")
+                .await
+                .into_result()
+                .unwrap_err(),
+            vec![ServerError {
+                message: "internal: invalid value for scalar \"Even\"".to_owned(),
+                source: None,
+                locations: vec![Pos { column: 3, line: 1 }],
+                path: vec![PathSegment::Field("echo".to_owned())],
+                extensions: None,
+            }]
+        );
+    }
 }
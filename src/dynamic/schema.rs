@@ -1,23 +1,43 @@
-use std::{any::Any, collections::HashMap, fmt::Debug, sync::Arc};
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use async_graphql_parser::types::OperationType;
-use futures_util::{stream::BoxStream, Stream, StreamExt, TryFutureExt};
+use async_graphql_parser::types::{OperationDefinition, OperationType};
+use futures_util::{
+    future::BoxFuture,
+    stream::{BoxStream, FuturesOrdered},
+    Stream, StreamExt, TryFutureExt,
+};
 use indexmap::IndexMap;
 
 use crate::{
     dynamic::{
         field::BoxResolverFn, r#type::Type, resolve::resolve_container, DynamicRequest,
-        FieldFuture, FieldValue, Object, ResolverContext, Scalar, SchemaError, Subscription,
+        FieldFuture, FieldValue, Mocks, Object, ResolverContext, Scalar, SchemaError, Subscription,
+        Union,
+    },
+    export_directive::{ExportDirectiveFactory, ExportedValues},
+    extensions::{create_extensions, ConditionalExtension, ExtensionFactory, Extensions},
+    locale::DescriptionResolver,
+    meta::SchemaMeta,
+    registry::{MetaType, Registry, RegistryStats},
+    schema::{
+        check_batch_operation_names, prepare_request, with_timeout, IntrospectionHook,
+        PrepareRequestConfig, RegistryHook, SchemaEnvInner, UnknownEntityHook, VariablesHook,
     },
-    extensions::{ExtensionFactory, Extensions},
-    registry::{MetaType, Registry},
-    schema::{prepare_request, SchemaEnvInner},
-    Data, Executor, IntrospectionMode, QueryEnv, Request, Response, SDLExportOptions, SchemaEnv,
-    ServerError, ServerResult, ValidationMode,
+    timer::{default_timer, Timer},
+    BatchRequest, BatchResponse, Context, CustomDirectiveFactory, Data, Error, Executor,
+    IntrospectionMode, QueryEnv, Request, Response, SDLExportAudience, SDLExportOptions, SchemaEnv,
+    ServerError, ServerResult, ValidationMode, ValidationResult, Value, Variables,
 };
 
 /// Dynamic schema builder
 pub struct SchemaBuilder {
+    description: Option<String>,
     query_type: String,
     mutation_type: Option<String>,
     subscription_type: Option<String>,
@@ -28,10 +48,26 @@ pub struct SchemaBuilder {
     recursive_depth: usize,
     complexity: Option<usize>,
     depth: Option<usize>,
+    skip_introspection_limits: bool,
+    input_depth: Option<usize>,
+    input_nodes: Option<usize>,
+    max_errors: Option<usize>,
+    max_response_size: Option<usize>,
+    disable_anonymous_operations: bool,
+    unique_batch_operation_names: bool,
+    export_directive: bool,
     enable_suggestions: bool,
     introspection_mode: IntrospectionMode,
     enable_federation: bool,
     entity_resolver: Option<BoxResolverFn>,
+    entity_reference_resolvers: HashMap<String, BoxResolverFn>,
+    unknown_entity_hook: Option<UnknownEntityHook>,
+    registry_hooks: Vec<RegistryHook>,
+    variables_hooks: Vec<VariablesHook>,
+    introspection_hooks: Vec<IntrospectionHook>,
+    description_resolver: Option<DescriptionResolver>,
+    timer: Option<Arc<dyn Timer>>,
+    mocks: Option<Mocks>,
 }
 
 impl SchemaBuilder {
@@ -58,6 +94,23 @@ impl SchemaBuilder {
         self
     }
 
+    /// Add an extension to the schema, which is only enabled for the requests
+    /// matching `predicate`.
+    ///
+    /// See [`SchemaBuilder::extension_if`](crate::SchemaBuilder::extension_if).
+    #[must_use]
+    pub fn extension_if(
+        mut self,
+        extension: impl ExtensionFactory,
+        predicate: impl Fn(&Request) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.extensions.push(Box::new(ConditionalExtension {
+            extension,
+            predicate,
+        }));
+        self
+    }
+
     /// Set the maximum complexity a query can have. By default, there is no
     /// limit.
     #[must_use]
@@ -73,6 +126,15 @@ impl SchemaBuilder {
         self
     }
 
+    /// Do not apply the depth and complexity limits to operations that only
+    /// select introspection fields (`__schema`, `__type` and `__typename`),
+    /// such as the queries sent by GraphiQL.
+    #[must_use]
+    pub fn skip_introspection_limits(mut self) -> Self {
+        self.skip_introspection_limits = true;
+        self
+    }
+
     /// Set the maximum recursive depth a query can have. (default: 32)
     ///
     /// If the value is too large, stack overflow may occur, usually `32` is
@@ -83,6 +145,85 @@ impl SchemaBuilder {
         self
     }
 
+    /// Set the maximum nesting depth of the input values in the variables of a
+    /// request. By default, there is no limit.
+    ///
+    /// Each list or input object counts as one level, so `{ a: [1] }` has a
+    /// depth of `2`. This protects recursive input objects, such as filter
+    /// trees, from overflowing the stack during coercion.
+    #[must_use]
+    pub fn limit_input_depth(mut self, depth: usize) -> Self {
+        self.input_depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum total number of input values in the variables of a
+    /// request. By default, there is no limit.
+    ///
+    /// Every scalar, list and input object counts as one node.
+    #[must_use]
+    pub fn limit_input_nodes(mut self, nodes: usize) -> Self {
+        self.input_nodes = Some(nodes);
+        self
+    }
+
+    /// Set the maximum number of errors of a request. By default, there is
+    /// no limit.
+    ///
+    /// The validation is aborted once the limit is exceeded, and the execution
+    /// stops resolving the fields, so that the documents that trigger a lot of
+    /// errors do not consume a lot of resources. The fields that are no longer
+    /// resolved fail like the fields returning an error, and only the first
+    /// errors are kept, followed by an error reporting that the limit is
+    /// exceeded.
+    #[must_use]
+    pub fn limit_errors(mut self, errors: usize) -> Self {
+        self.max_errors = Some(errors);
+        self
+    }
+
+    /// Set the maximum approximate size, in bytes, of the response of a
+    /// request. By default, there is no limit.
+    ///
+    /// The execution is aborted with an error once the limit is exceeded.
+    /// Subscriptions are not limited.
+    #[must_use]
+    pub fn limit_response_size(mut self, size: usize) -> Self {
+        self.max_response_size = Some(size);
+        self
+    }
+
+    /// Reject the operations that have no name, such as `{ value }` or
+    /// `query { value }`.
+    #[must_use]
+    pub fn disable_anonymous_operations(mut self) -> Self {
+        self.disable_anonymous_operations = true;
+        self
+    }
+
+    /// Require that the requests of a batch have operation names that are
+    /// unique in the batch.
+    ///
+    /// The requests whose operation is anonymous or whose name is used by
+    /// another request of the batch are not executed, and an error is
+    /// returned for them instead.
+    #[must_use]
+    pub fn require_unique_batch_operation_names(mut self) -> Self {
+        self.unique_batch_operation_names = true;
+        self
+    }
+
+    /// Enable the `@export(as: "name")` directive, which exports the value
+    /// of a field as a variable of the following requests of a batch.
+    ///
+    /// See
+    /// [`SchemaBuilder::enable_export_directive`](crate::SchemaBuilder::enable_export_directive).
+    #[must_use]
+    pub fn enable_export_directive(mut self) -> Self {
+        self.export_directive = true;
+        self
+    }
+
     /// Set the validation mode, default is `ValidationMode::Strict`.
     #[must_use]
     pub fn validation_mode(mut self, validation_mode: ValidationMode) -> Self {
@@ -97,6 +238,13 @@ impl SchemaBuilder {
         self
     }
 
+    /// Set the description of the schema.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
     /// Disable introspection queries.
     #[must_use]
     pub fn disable_introspection(mut self) -> Self {
@@ -131,9 +279,160 @@ impl SchemaBuilder {
         }
     }
 
+    /// Set the reference resolver of an entity type for federation
+    ///
+    /// When no [`entity_resolver`](Self::entity_resolver) is set, the
+    /// `_entities` field calls the reference resolver of the `__typename` of
+    /// each representation, with the fields of the representation as
+    /// arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::{dynamic::*, Value};
+    ///
+    /// let product = Object::new("Product")
+    ///     .key("upc")
+    ///     .field(Field::new("upc", TypeRef::named_nn(TypeRef::STRING), |ctx| {
+    ///         FieldFuture::new(async move {
+    ///             Ok(Some(Value::from(ctx.parent_value.try_downcast_ref::<String>()?.clone())))
+    ///         })
+    ///     }));
+    ///
+    /// let query = Object::new("Query").field(Field::new("value", TypeRef::named(TypeRef::INT), |_| {
+    ///     FieldFuture::new(async move { Ok(Some(Value::from(10))) })
+    /// }));
+    ///
+    /// let schema = Schema::build(query.type_name(), None, None)
+    ///     .register(product)
+    ///     .register(query)
+    ///     .entity_reference_resolver("Product", |ctx| {
+    ///         FieldFuture::new(async move {
+    ///             let upc = ctx.args.try_get("upc")?.string()?.to_string();
+    ///             Ok(Some(FieldValue::owned_any(upc)))
+    ///         })
+    ///     })
+    ///     .finish()
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn entity_reference_resolver<F>(
+        mut self,
+        type_name: impl Into<String>,
+        resolver_fn: F,
+    ) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static,
+    {
+        self.entity_reference_resolvers
+            .insert(type_name.into(), Box::new(resolver_fn));
+        self
+    }
+
+    /// Set the resolver of the descriptions, which are used as message keys
+    /// resolved for the locale of the request.
+    ///
+    /// See
+    /// [`SchemaBuilder::description_resolver`](crate::SchemaBuilder::description_resolver).
+    #[must_use]
+    pub fn description_resolver(
+        mut self,
+        f: impl Fn(&str, &str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.description_resolver = Some(Arc::new(f));
+        self
+    }
+
+    /// Set a hook called when no reference resolver is registered for the
+    /// `__typename` of a representation of the `_entities` field.
+    ///
+    /// It is only called when no
+    /// [`entity_resolver`](Self::entity_resolver) is set, and works like
+    /// [`crate::SchemaBuilder::on_unknown_entity`]:
+    ///
+    /// - `Ok(Some(representation))` resolves the returned representation
+    ///   instead.
+    /// - `Ok(None)` returns a null entity without error.
+    /// - `Err(err)` returns a null entity with the error.
+    ///
+    /// Without a hook, the `_entities` field fails with a `missing reference
+    /// resolver` error.
+    #[must_use]
+    pub fn on_unknown_entity<F>(mut self, f: F) -> Self
+    where
+        F: for<'a> Fn(&'a Context<'_>, &'a Value) -> BoxFuture<'a, Result<Option<Value>, Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.unknown_entity_hook = Some(Arc::new(f));
+        self
+    }
+
+    /// Add a hook that can modify the registry before the schema is
+    /// finished.
+    ///
+    /// Hooks are called in the order they are added, after all types have
+    /// been registered. The resolvers are not changed, so renaming a type or
+    /// field will break the execution.
+    #[must_use]
+    pub fn on_registry(mut self, f: impl FnOnce(&mut Registry) + 'static) -> Self {
+        self.registry_hooks.push(Box::new(f));
+        self
+    }
+
+    /// Add a hook that can rewrite or reject the variables of a request.
+    ///
+    /// Hooks are called in the order they are added, after the operation to
+    /// execute has been selected and validated, and before it is executed.
+    /// Returning an error rejects the request. The rewritten variables are not
+    /// validated again.
+    #[must_use]
+    pub fn on_variables(
+        mut self,
+        f: impl Fn(&mut Variables, &OperationDefinition) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        self.variables_hooks.push(Box::new(f));
+        self
+    }
+
+    /// Add a hook that can post-process the result of the `__schema` and
+    /// `__type` introspection fields of a request.
+    ///
+    /// See [`SchemaBuilder::on_introspection`](crate::SchemaBuilder::on_introspection).
+    #[must_use]
+    pub fn on_introspection(
+        mut self,
+        f: impl Fn(&Context<'_>, &mut Value) + Send + Sync + 'static,
+    ) -> Self {
+        self.introspection_hooks.push(Box::new(f));
+        self
+    }
+
+    /// Set the timer used for the timeouts of the requests.
+    ///
+    /// See [`SchemaBuilder::timer`](crate::SchemaBuilder::timer).
+    #[must_use]
+    pub fn timer(mut self, timer: impl Timer) -> Self {
+        self.timer = Some(Arc::new(timer));
+        self
+    }
+
+    /// Enable the mock mode, the fields without resolvers return mock values.
+    ///
+    /// See [`Mocks`] for the generated values.
+    #[must_use]
+    pub fn mocks(self, mocks: Mocks) -> Self {
+        Self {
+            mocks: Some(mocks),
+            ..self
+        }
+    }
+
     /// Consumes this builder and returns a schema.
     pub fn finish(mut self) -> Result<Schema, SchemaError> {
         let mut registry = Registry {
+            description: self.description,
             types: Default::default(),
             directives: Default::default(),
             implements: Default::default(),
@@ -145,6 +444,10 @@ impl SchemaBuilder {
             federation_subscription: false,
             ignore_name_conflicts: Default::default(),
             enable_suggestions: self.enable_suggestions,
+            enable_experimental_ccn: false,
+            merged_fields: Default::default(),
+            visibility_profiles: Default::default(),
+            registered_names: Default::default(),
         };
         registry.add_system_types();
 
@@ -171,21 +474,65 @@ impl SchemaBuilder {
         if self.enable_federation || registry.has_entities() {
             registry.enable_federation = true;
             registry.create_federation_types();
+
+            // the `_Entity` union is resolved like the other dynamic types
+            if let Some(MetaType::Union { possible_types, .. }) = registry.types.get("_Entity") {
+                let entity = possible_types
+                    .iter()
+                    .fold(Union::new("_Entity"), Union::possible_type);
+                self.types
+                    .insert("_Entity".to_string(), Type::Union(entity));
+            }
+        }
+
+        if self.export_directive {
+            ExportDirectiveFactory.register(&mut registry);
         }
 
+        for hook in self.registry_hooks {
+            hook(&mut registry);
+        }
+        if registry.enable_federation {
+            registry.check_federation_field_sets()?;
+        }
+        registry.shrink_to_fit();
+
         let inner = SchemaInner {
             env: SchemaEnv(Arc::new(SchemaEnvInner {
-                registry,
+                registry: Arc::new(registry),
                 data: self.data,
                 custom_directives: Default::default(),
+                entity_resolvers: Default::default(),
+                unknown_entity_hook: self.unknown_entity_hook,
+                guard_denial_hook: None,
+                introspection_hooks: Arc::new(self.introspection_hooks),
+                description_resolver: self.description_resolver,
+                timer: self.timer,
+                field_cache: None,
+                object_extensions: Default::default(),
+                providers: Default::default(),
+                execution_stats: false,
+                dynamic_fields: Default::default(),
+                base: None,
             })),
             extensions: self.extensions,
             types: self.types,
             recursive_depth: self.recursive_depth,
             complexity: self.complexity,
             depth: self.depth,
+            skip_introspection_limits: self.skip_introspection_limits,
+            input_depth: self.input_depth,
+            input_nodes: self.input_nodes,
+            max_errors: self.max_errors,
+            max_response_size: self.max_response_size,
+            disable_anonymous_operations: self.disable_anonymous_operations,
+            unique_batch_operation_names: self.unique_batch_operation_names,
+            export_directive: self.export_directive,
+            variables_hooks: self.variables_hooks,
             validation_mode: self.validation_mode,
             entity_resolver: self.entity_resolver,
+            entity_reference_resolvers: self.entity_reference_resolvers,
+            mocks: self.mocks,
         };
         inner.check()?;
         Ok(Schema(Arc::new(inner)))
@@ -211,14 +558,44 @@ pub struct SchemaInner {
     recursive_depth: usize,
     complexity: Option<usize>,
     depth: Option<usize>,
+    skip_introspection_limits: bool,
+    input_depth: Option<usize>,
+    input_nodes: Option<usize>,
+    max_errors: Option<usize>,
+    max_response_size: Option<usize>,
+    disable_anonymous_operations: bool,
+    unique_batch_operation_names: bool,
+    pub(crate) export_directive: bool,
+    variables_hooks: Vec<VariablesHook>,
     validation_mode: ValidationMode,
     pub(crate) entity_resolver: Option<BoxResolverFn>,
+    pub(crate) entity_reference_resolvers: HashMap<String, BoxResolverFn>,
+    pub(crate) mocks: Option<Mocks>,
+}
+
+impl SchemaInner {
+    fn prepare_request_config(&self) -> PrepareRequestConfig<'_> {
+        PrepareRequestConfig {
+            validation_mode: self.validation_mode,
+            recursive_depth: self.recursive_depth,
+            complexity: self.complexity,
+            depth: self.depth,
+            skip_introspection_limits: self.skip_introspection_limits,
+            input_depth: self.input_depth,
+            input_nodes: self.input_nodes,
+            max_errors: self.max_errors,
+            max_response_size: self.max_response_size,
+            disable_anonymous_operations: self.disable_anonymous_operations,
+            variables_hooks: &self.variables_hooks,
+        }
+    }
 }
 
 impl Schema {
     /// Create a schema builder
     pub fn build(query: &str, mutation: Option<&str>, subscription: Option<&str>) -> SchemaBuilder {
         SchemaBuilder {
+            description: None,
             query_type: query.to_string(),
             mutation_type: mutation.map(ToString::to_string),
             subscription_type: subscription.map(ToString::to_string),
@@ -229,16 +606,32 @@ impl Schema {
             recursive_depth: 32,
             complexity: None,
             depth: None,
+            skip_introspection_limits: false,
+            input_depth: None,
+            input_nodes: None,
+            max_errors: None,
+            max_response_size: None,
+            disable_anonymous_operations: false,
+            unique_batch_operation_names: false,
+            export_directive: false,
             enable_suggestions: true,
             introspection_mode: IntrospectionMode::Enabled,
             entity_resolver: None,
+            entity_reference_resolvers: Default::default(),
+            unknown_entity_hook: None,
             enable_federation: false,
+            registry_hooks: Default::default(),
+            variables_hooks: Default::default(),
+            introspection_hooks: Default::default(),
+            description_resolver: None,
+            timer: default_timer(),
+            mocks: None,
         }
     }
 
-    fn create_extensions(&self, session_data: Arc<Data>) -> Extensions {
+    fn create_extensions(&self, session_data: Arc<Data>, request: &Request) -> Extensions {
         Extensions::new(
-            self.0.extensions.iter().map(|f| f.create()),
+            create_extensions(&self.0.extensions, request),
             self.0.env.clone(),
             session_data,
         )
@@ -284,6 +677,91 @@ impl Schema {
         self.0.env.registry.export_sdl(options)
     }
 
+    /// Returns SDL(Schema Definition Language) of this schema with options,
+    /// with only the elements seen by `audience`.
+    pub fn sdl_for_audience(
+        &self,
+        options: SDLExportOptions,
+        audience: &SDLExportAudience,
+    ) -> String {
+        self.0.env.export_sdl_for_audience(options, audience)
+    }
+
+    /// Writes the SDL(Schema Definition Language) of this schema with options
+    /// to the file `file_name` in the `OUT_DIR` of the build script, and
+    /// returns the path of the file.
+    ///
+    /// See [`Schema::write_sdl_to_out_dir`](crate::Schema::write_sdl_to_out_dir).
+    pub fn write_sdl_to_out_dir(
+        &self,
+        file_name: impl AsRef<Path>,
+        options: SDLExportOptions,
+    ) -> std::io::Result<PathBuf> {
+        self.0.env.write_sdl_to_out_dir(file_name.as_ref(), options)
+    }
+
+    /// Writes the SDL(Schema Definition Language) of this schema with options
+    /// to the file `path`.
+    ///
+    /// See [`Schema::write_sdl`](crate::Schema::write_sdl).
+    pub fn write_sdl(
+        &self,
+        path: impl AsRef<Path>,
+        options: SDLExportOptions,
+    ) -> std::io::Result<()> {
+        self.0.env.write_sdl(path.as_ref(), options)
+    }
+
+    /// Returns a read-only view over the metadata of this schema.
+    pub fn meta(&self) -> SchemaMeta<'_> {
+        SchemaMeta::new(&self.0.env.registry)
+    }
+
+    /// Returns the result of the standard introspection query of this schema,
+    /// without executing it.
+    ///
+    /// The returned value contains the `__schema` field, and can be written to
+    /// a `schema.json` file used by code generators. Like
+    /// [`sdl`](Self::sdl), all types and fields are included regardless of
+    /// their visibility.
+    pub fn introspection(&self) -> serde_json::Value {
+        self.0.env.registry.introspection()
+    }
+
+    /// Returns the JSON Schema of the input object `type_name`, or `None` if
+    /// it is not an input object of this schema.
+    ///
+    /// See [`Schema::input_json_schema`](crate::Schema::input_json_schema).
+    pub fn input_json_schema(&self, type_name: &str) -> Option<serde_json::Value> {
+        self.0.env.input_json_schema(type_name)
+    }
+
+    /// Returns the JSON Schema of the arguments of the field `field_name` of
+    /// the object or interface `type_name`, or `None` if the field does not
+    /// exist.
+    ///
+    /// See [`Schema::arguments_json_schema`](crate::Schema::arguments_json_schema).
+    pub fn arguments_json_schema(
+        &self,
+        type_name: &str,
+        field_name: &str,
+    ) -> Option<serde_json::Value> {
+        self.0.env.arguments_json_schema(type_name, field_name)
+    }
+
+    /// Returns the number of types, fields and other elements of this schema,
+    /// and the estimated memory usage of its registry.
+    pub fn registry_stats(&self) -> RegistryStats {
+        self.0.env.registry.stats()
+    }
+
+    /// Returns a stable fingerprint of this schema.
+    ///
+    /// See [`Schema::fingerprint`](crate::Schema::fingerprint).
+    pub fn fingerprint(&self) -> String {
+        self.0.env.registry.fingerprint()
+    }
+
     async fn execute_once(&self, env: QueryEnv, root_value: &FieldValue<'static>) -> Response {
         // execute
         let ctx = env.create_context(&self.0.env, None, &env.operation.node.selection_set);
@@ -308,21 +786,29 @@ impl Schema {
             )),
         };
 
+        let res = match env.response_size_error() {
+            Some(err) => Err(err),
+            None => res,
+        };
         let mut resp = match res {
             Ok(value) => Response::new(value.unwrap_or_default()),
             Err(err) => Response::from_errors(vec![err]),
         }
-        .http_headers(std::mem::take(&mut *env.http_headers.lock().unwrap()));
+        .http_headers(std::mem::take(&mut *env.http_headers.lock().unwrap()))
+        .operation(env.operation_metadata());
 
-        resp.errors
-            .extend(std::mem::take(&mut *env.errors.lock().unwrap()));
+        env.take_errors(&mut resp.errors);
+        resp.extensions.extend(std::mem::take(
+            &mut *env.response_extensions.lock().unwrap(),
+        ));
         resp
     }
 
     /// Execute a GraphQL query.
     pub async fn execute(&self, request: impl Into<DynamicRequest>) -> Response {
         let request = request.into();
-        let extensions = self.create_extensions(Default::default());
+        let timeout = request.inner.timeout;
+        let extensions = self.create_extensions(Default::default(), &request.inner);
         let request_fut = {
             let extensions = extensions.clone();
             async move {
@@ -330,19 +816,20 @@ impl Schema {
                     extensions,
                     request.inner,
                     Default::default(),
-                    &self.0.env.registry,
-                    self.0.validation_mode,
-                    self.0.recursive_depth,
-                    self.0.complexity,
-                    self.0.depth,
+                    &self.0.env,
+                    self.0.prepare_request_config(),
                 )
                 .await
                 {
-                    Ok((env, cache_control)) => {
+                    Ok((env, validation_result)) => {
                         let fut = async {
-                            self.execute_once(env.clone(), &request.root_value)
-                                .await
-                                .cache_control(cache_control)
+                            with_timeout(
+                                self.execute_once(env.clone(), &request.root_value),
+                                timeout,
+                                self.0.env.timer.as_deref(),
+                            )
+                            .await
+                            .cache_control(validation_result.cache_control)
                         };
                         futures_util::pin_mut!(fut);
                         env.extensions
@@ -357,6 +844,85 @@ impl Schema {
         extensions.request(&mut request_fut).await
     }
 
+    /// Validate a GraphQL request without executing it.
+    ///
+    /// The request is parsed, validated and checked against the limits of the
+    /// schema exactly as [`Schema::execute`] would do, but no resolvers are
+    /// called.
+    pub async fn validate(
+        &self,
+        request: impl Into<DynamicRequest>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let request = request.into().inner;
+        let extensions = self.create_extensions(Default::default(), &request);
+        prepare_request(
+            extensions,
+            request,
+            Default::default(),
+            &self.0.env,
+            self.0.prepare_request_config(),
+        )
+        .await
+        .map(|(_, validation_result)| validation_result)
+    }
+
+    /// Execute a GraphQL batch query.
+    pub async fn execute_batch(&self, batch_request: BatchRequest) -> BatchResponse {
+        match batch_request {
+            BatchRequest::Single(request) => BatchResponse::Single(self.execute(request).await),
+            BatchRequest::Batch(mut requests) => {
+                let errors = if self.0.unique_batch_operation_names {
+                    check_batch_operation_names(&mut requests)
+                } else {
+                    vec![None; requests.len()]
+                };
+                if self.0.export_directive {
+                    return BatchResponse::Batch(
+                        self.execute_batch_with_exports(requests, errors).await,
+                    );
+                }
+                BatchResponse::Batch(
+                    FuturesOrdered::from_iter(requests.into_iter().zip(errors).map(
+                        |(request, error)| async move {
+                            match error {
+                                Some(error) => Response::from_errors(vec![error]),
+                                None => self.execute(request).await,
+                            }
+                        },
+                    ))
+                    .collect()
+                    .await,
+                )
+            }
+        }
+    }
+
+    async fn execute_batch_with_exports(
+        &self,
+        requests: Vec<Request>,
+        errors: Vec<Option<ServerError>>,
+    ) -> Vec<Response> {
+        let mut exported = Variables::default();
+        let mut responses = Vec::with_capacity(requests.len());
+        for (mut request, error) in requests.into_iter().zip(errors) {
+            if let Some(error) = error {
+                responses.push(Response::from_errors(vec![error]));
+                continue;
+            }
+
+            request.variables.extend(
+                exported
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+            let exports = ExportedValues::default();
+            request.data.insert(exports.clone());
+            responses.push(self.execute(request).await);
+            exports.export_to(&mut exported);
+        }
+        responses
+    }
+
     /// Execute a GraphQL subscription with session data.
     pub fn execute_stream_with_session_data(
         &self,
@@ -365,7 +931,7 @@ impl Schema {
     ) -> impl Stream<Item = Response> + Send + Unpin {
         let schema = self.clone();
         let request = request.into();
-        let extensions = self.create_extensions(session_data.clone());
+        let extensions = self.create_extensions(session_data.clone(), &request.inner);
 
         let stream = {
             let extensions = extensions.clone();
@@ -383,11 +949,8 @@ impl Schema {
                     extensions,
                     request.inner,
                     session_data,
-                    &schema.0.env.registry,
-                    schema.0.validation_mode,
-                    schema.0.recursive_depth,
-                    schema.0.complexity,
-                    schema.0.depth,
+                    &schema.0.env,
+                    schema.0.prepare_request_config(),
                 )
                 .await {
                     Ok(res) => res,
@@ -410,9 +973,10 @@ impl Schema {
                 let mut streams = Vec::new();
                 subscription.collect_streams(&schema, &ctx, &mut streams, &request.root_value);
 
+                let operation = env.operation_metadata();
                 let mut stream = futures_util::stream::select_all(streams);
                 while let Some(resp) = stream.next().await {
-                    yield resp;
+                    yield resp.operation(operation.clone());
                 }
             }
         };
@@ -434,6 +998,10 @@ impl Executor for Schema {
         Schema::execute(self, request).await
     }
 
+    async fn execute_batch(&self, batch_request: BatchRequest) -> BatchResponse {
+        Schema::execute_batch(self, batch_request).await
+    }
+
     fn execute_stream(
         &self,
         request: Request,
@@ -484,7 +1052,8 @@ mod tests {
     use crate::{
         dynamic::{DynamicRequestExt, *},
         extensions::*,
-        value, PathSegment, Request, Response, ServerError, ServerResult, ValidationResult, Value,
+        value, Locale, Name, PathSegment, Request, Response, SDLExportAudience, SDLExportOptions,
+        ServerError, ServerResult, ValidationResult, Value,
     };
 
     #[tokio::test]
@@ -995,4 +1564,375 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn entity_reference_resolvers() {
+        struct Product {
+            upc: String,
+        }
+
+        let product = Object::new("Product")
+            .key("upc")
+            .field(Field::new(
+                "upc",
+                TypeRef::named_nn(TypeRef::STRING),
+                |ctx| {
+                    FieldFuture::new(async move {
+                        Ok(Some(Value::from(
+                            ctx.parent_value.try_downcast_ref::<Product>()?.upc.clone(),
+                        )))
+                    })
+                },
+            ))
+            .field(
+                Field::new("price", TypeRef::named_nn(TypeRef::INT), |_| {
+                    FieldFuture::new(async move { Ok(Some(Value::from(100))) })
+                })
+                .shareable(),
+            );
+        let user = Object::new("User").key("id").extends().field(
+            Field::new("id", TypeRef::named_nn(TypeRef::ID), |ctx| {
+                FieldFuture::new(async move {
+                    Ok(Some(
+                        ctx.parent_value.as_value().cloned().unwrap_or_default(),
+                    ))
+                })
+            })
+            .external(),
+        );
+        let query =
+            Object::new("Query").field(Field::new("value", TypeRef::named(TypeRef::INT), |_| {
+                FieldFuture::new(async move { Ok(Some(Value::from(10))) })
+            }));
+
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(product)
+            .register(user)
+            .register(query)
+            .entity_reference_resolver("Product", |ctx| {
+                FieldFuture::new(async move {
+                    let upc = ctx.args.try_get("upc")?.string()?.to_string();
+                    Ok(Some(FieldValue::owned_any(Product { upc })))
+                })
+            })
+            .entity_reference_resolver("User", |ctx| {
+                FieldFuture::new(async move {
+                    let id = ctx.args.try_get("id")?.string()?.to_string();
+                    Ok(Some(FieldValue::value(id)))
+                })
+            })
+            .finish()
+            .unwrap();
+
+        let sdl = schema
+            .execute("{ _service { sdl } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data
+            .into_json()
+            .unwrap()["_service"]["sdl"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(sdl.contains("type Product @key(fields: \"upc\") {"));
+        assert!(sdl.contains("price: Int! @shareable"));
+        assert!(sdl.contains("extend type User @key(fields: \"id\") {"));
+        assert!(sdl.contains("id: ID! @external"));
+
+        let query = r#"{
+            _entities(representations: [
+                { __typename: "Product", upc: "a" },
+                { __typename: "User", id: "1" },
+            ]) {
+                __typename
+                ... on Product { upc price }
+                ... on User { id }
+            }
+        }"#;
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap().data,
+            value!({
+                "_entities": [
+                    { "__typename": "Product", "upc": "a", "price": 100 },
+                    { "__typename": "User", "id": "1" },
+                ]
+            })
+        );
+
+        let query = r#"{
+            _entities(representations: [{ __typename: "Review", id: "1" }]) {
+                __typename
+            }
+        }"#;
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap_err(),
+            vec![ServerError {
+                message: "internal: missing reference resolver for entity \"Review\"".to_owned(),
+                source: None,
+                locations: vec![Pos {
+                    line: 2,
+                    column: 13
+                }],
+                path: vec![PathSegment::Field("_entities".to_owned())],
+                extensions: None,
+            }]
+        );
+
+        let err = Schema::build("Query", None, None)
+            .register(Object::new("Query").field(Field::new(
+                "value",
+                TypeRef::named(TypeRef::INT),
+                |_| FieldFuture::new(async move { Ok(Some(Value::from(10))) }),
+            )))
+            .entity_reference_resolver("Product", |_| {
+                FieldFuture::new(async move { Ok(None::<FieldValue>) })
+            })
+            .finish()
+            .unwrap_err();
+        assert_eq!(err.0, "Type \"Product\" not found");
+    }
+
+    #[tokio::test]
+    async fn export_directive() {
+        let query = Object::new("Query")
+            .field(Field::new(
+                "userId",
+                TypeRef::named_nn(TypeRef::INT),
+                |_| FieldFuture::new(async move { Ok(Some(Value::from(1))) }),
+            ))
+            .field(Field::new(
+                "friendIds",
+                TypeRef::named_nn_list_nn(TypeRef::INT),
+                |_| {
+                    FieldFuture::new(async move {
+                        Ok(Some(FieldValue::list((2..5).map(FieldValue::value))))
+                    })
+                },
+            ))
+            .field(
+                Field::new("count", TypeRef::named_nn(TypeRef::INT), |ctx| {
+                    FieldFuture::new(async move {
+                        let user_id = ctx.args.try_get("userId")?.i64()?;
+                        let ids = ctx.args.try_get("ids")?.list()?.len() as i64;
+                        Ok(Some(Value::from(user_id * 10 + ids)))
+                    })
+                })
+                .argument(InputValue::new("userId", TypeRef::named_nn(TypeRef::INT)))
+                .argument(InputValue::new(
+                    "ids",
+                    TypeRef::named_nn_list_nn(TypeRef::INT),
+                )),
+            );
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(query)
+            .enable_export_directive()
+            .finish()
+            .unwrap();
+
+        let batch: crate::BatchRequest = vec![
+            Request::new(r#"{ userId @export(as: "userId") }"#),
+            Request::new(r#"{ friendIds @export(as: "ids") }"#),
+            Request::new(
+                "query($userId: Int!, $ids: [Int!]!) { count(userId: $userId, ids: $ids) }",
+            ),
+        ]
+        .into();
+        let resp = schema.execute_batch(batch).await;
+        assert_eq!(
+            serde_json::to_value(&resp).unwrap(),
+            serde_json::json!([
+                {"data": { "userId": 1 }},
+                {"data": { "friendIds": [2, 3, 4] }},
+                {"data": { "count": 13 }},
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn description_resolver() {
+        let query = Object::new("Query").description("query.description").field(
+            Field::new("value", TypeRef::named(TypeRef::INT), |_| {
+                FieldFuture::new(async move { Ok(Some(Value::from(10))) })
+            })
+            .description("query.value"),
+        );
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(query)
+            .description_resolver(|key, locale| match (key, locale) {
+                ("query.description", "fr") => "La requête".to_string(),
+                ("query.description", _) => "The query".to_string(),
+                ("query.value", "fr") => "La valeur".to_string(),
+                ("query.value", _) => "The value".to_string(),
+                _ => key.to_string(),
+            })
+            .data(Locale::new("en"))
+            .finish()
+            .unwrap();
+
+        let query = r#"{
+            __type(name: "Query") { description fields { description } }
+        }"#;
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap().data,
+            value!({
+                "__type": {
+                    "description": "The query",
+                    "fields": [{ "description": "The value" }],
+                }
+            })
+        );
+        assert_eq!(
+            schema
+                .execute(Request::new(query).data(Locale::new("fr")))
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({
+                "__type": {
+                    "description": "La requête",
+                    "fields": [{ "description": "La valeur" }],
+                }
+            })
+        );
+        assert!(schema
+            .sdl_for_audience(
+                SDLExportOptions::new().prefer_single_line_descriptions(),
+                &SDLExportAudience::new().locale("fr"),
+            )
+            .contains("\"La requête\"\ntype Query {\n\t\"La valeur\"\n\tvalue: Int\n}"));
+    }
+
+    #[tokio::test]
+    async fn unknown_entity_hook() {
+        let user = Object::new("User").key("id").field(Field::new(
+            "id",
+            TypeRef::named_nn(TypeRef::ID),
+            |ctx| {
+                FieldFuture::new(async move {
+                    Ok(Some(
+                        ctx.parent_value.as_value().cloned().unwrap_or_default(),
+                    ))
+                })
+            },
+        ));
+        let query =
+            Object::new("Query").field(Field::new("value", TypeRef::named(TypeRef::INT), |_| {
+                FieldFuture::new(async move { Ok(Some(Value::from(10))) })
+            }));
+
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(user)
+            .register(query)
+            .entity_reference_resolver("User", |ctx| {
+                FieldFuture::new(async move {
+                    let id = ctx.args.try_get("id")?.string()?.to_string();
+                    Ok(Some(FieldValue::value(id)))
+                })
+            })
+            .on_unknown_entity(|_ctx, representation| {
+                Box::pin(async move {
+                    let mut representation = match representation {
+                        Value::Object(representation) => representation.clone(),
+                        _ => return Ok(None),
+                    };
+                    match representation.get("__typename") {
+                        Some(Value::String(type_name)) if type_name == "Account" => {
+                            representation.insert(Name::new("__typename"), value!("User"));
+                            Ok(Some(Value::Object(representation)))
+                        }
+                        Some(Value::String(type_name)) if type_name == "Stale" => Ok(None),
+                        _ => Err("Unknown entity".into()),
+                    }
+                })
+            })
+            .finish()
+            .unwrap();
+
+        let query = r#"{
+            _entities(representations: [
+                { __typename: "Account", id: "1" },
+                { __typename: "User", id: "2" },
+                { __typename: "Stale", id: "3" },
+                { __typename: "Review", id: "4" },
+            ]) {
+                ... on User { id }
+            }
+        }"#;
+        let resp = schema.execute(query).await;
+        assert_eq!(
+            resp.data,
+            value!({ "_entities": [{ "id": "1" }, { "id": "2" }, null, null] })
+        );
+        assert_eq!(
+            resp.errors,
+            vec![ServerError {
+                message: "Unknown entity".to_owned(),
+                source: None,
+                locations: vec![Pos {
+                    line: 2,
+                    column: 13
+                }],
+                path: vec![
+                    PathSegment::Field("_entities".to_owned()),
+                    PathSegment::Index(3)
+                ],
+                extensions: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_federation_key() {
+        let product = Object::new("Product").key("sku").field(Field::new(
+            "upc",
+            TypeRef::named_nn(TypeRef::STRING),
+            |_| FieldFuture::new(async move { Ok(Some(Value::from("1"))) }),
+        ));
+        let query =
+            Object::new("Query").field(Field::new("value", TypeRef::named(TypeRef::INT), |_| {
+                FieldFuture::new(async move { Ok(Some(Value::from(10))) })
+            }));
+
+        let err = Schema::build(query.type_name(), None, None)
+            .register(product)
+            .register(query)
+            .enable_federation()
+            .finish()
+            .unwrap_err();
+        assert_eq!(
+            err.0,
+            r#"Invalid @key(fields: "sku") on type "Product": field "sku" does not exist on type "Product"."#
+        );
+    }
+
+    #[tokio::test]
+    async fn introspection_hook() {
+        let query = Object::new("Query").field(
+            Field::new("value", TypeRef::named(TypeRef::INT), |_| {
+                FieldFuture::new(async move { Ok(Some(Value::from(10))) })
+            })
+            .description("The value"),
+        );
+        let schema = Schema::build(query.type_name(), None, None)
+            .register(query)
+            .on_introspection(|ctx, value| {
+                if ctx.field().name() == "description" {
+                    *value = Value::from("La valeur");
+                }
+            })
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .execute(r#"{ __type(name: "Query") { fields { text: description } } }"#)
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "__type": { "fields": [{ "text": "La valeur" }] } })
+        );
+    }
 }
@@ -247,14 +247,16 @@ impl Subscription {
                                     futures_util::pin_mut!(resolve_fut);
                                     let value = ctx_field.query_env.extensions.resolve(ri, &mut resolve_fut).await;
 
-                                    match value {
+                                    let mut resp = match value {
                                         Ok(value) => {
                                             let mut map = IndexMap::new();
                                             map.insert(field_name.clone(), value.unwrap_or_default());
                                             Response::new(Value::Object(map))
                                         },
                                         Err(err) => Response::from_errors(vec![err]),
-                                    }
+                                    };
+                                    resp.extensions.extend(std::mem::take(&mut *ctx_field.query_env.response_extensions.lock().unwrap()));
+                                    resp
                                 };
                                 futures_util::pin_mut!(execute_fut);
                                 let resp = ctx_field.query_env.extensions.execute(ctx_field.query_env.operation_name.as_deref(), &mut execute_fut).await;
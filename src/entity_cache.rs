@@ -0,0 +1,203 @@
+use indexmap::IndexMap;
+
+use crate::{
+    parser::types::{Selection, SelectionSet},
+    registry::{MetaType, MetaTypeName, Registry},
+    CacheControl, Context, Name, Positioned, Value,
+};
+
+/// The response extension read by the Apollo Router to tag the cached
+/// entities, with one list of tags per representation.
+const ENTITY_CACHE_TAGS: &str = "apolloEntityCacheTags";
+
+/// Adds the cache tags of the entities resolved by the `_entities` field of
+/// `ctx` to the response extensions.
+///
+/// An entity is tagged with its surrogate key, made of its `__typename` and
+/// of its key fields, if the cache hint of its type and of the fields
+/// selected on it allows it to be cached. The extension is not added if no
+/// entity can be cached.
+pub(crate) fn add_entity_cache_tags<'a>(
+    ctx: &Context<'_>,
+    representations: impl IntoIterator<Item = &'a Value>,
+) {
+    let registry = &ctx.schema_env.registry;
+    let mut cacheable = false;
+    let tags = representations
+        .into_iter()
+        .map(|representation| {
+            let key = match representation {
+                Value::Object(representation) => entity_surrogate_key(registry, representation),
+                _ => None,
+            };
+            match key {
+                Some((type_name, key)) if entity_cache_control(ctx, type_name).max_age > 0 => {
+                    cacheable = true;
+                    Value::List(vec![Value::String(key)])
+                }
+                _ => Value::List(Vec::new()),
+            }
+        })
+        .collect();
+
+    if cacheable {
+        ctx.insert_response_extension(ENTITY_CACHE_TAGS, Value::List(tags));
+    }
+}
+
+/// Returns the type name and the surrogate key of an entity representation,
+/// such as `User:{"id":"1"}`.
+///
+/// The key contains the fields of the first `@key` of the type found in the
+/// representation, or all the fields of the representation if the type has
+/// no such key.
+fn entity_surrogate_key<'a>(
+    registry: &Registry,
+    representation: &'a IndexMap<Name, Value>,
+) -> Option<(&'a str, String)> {
+    let type_name = match representation.get("__typename") {
+        Some(Value::String(type_name)) => type_name.as_str(),
+        _ => return None,
+    };
+    let keys = match registry.types.get(type_name) {
+        Some(MetaType::Object { keys, .. }) | Some(MetaType::Interface { keys, .. }) => {
+            keys.as_deref().unwrap_or_default()
+        }
+        _ => &[],
+    };
+
+    let key_fields = keys.iter().map(|key| key_field_names(key)).find(|names| {
+        names
+            .iter()
+            .all(|name| representation.contains_key(name.as_str()))
+    });
+    let key_values = match key_fields {
+        Some(names) => names
+            .into_iter()
+            .filter_map(|name| {
+                let value = representation.get(name.as_str())?.clone();
+                Some((Name::new(name), value))
+            })
+            .collect(),
+        None => representation
+            .iter()
+            .filter(|(name, _)| name.as_str() != "__typename")
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect(),
+    };
+
+    Some((
+        type_name,
+        format!(
+            "{}:{}",
+            type_name,
+            Value::Object(key_values).into_json().ok()?
+        ),
+    ))
+}
+
+/// Returns the names of the top level fields of a `@key` field set, such as
+/// `id` and `organization` for `id organization { id }`.
+fn key_field_names(key: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut depth = 0;
+    for token in key
+        .replace('{', " { ")
+        .replace('}', " } ")
+        .split_whitespace()
+    {
+        match token {
+            "{" => depth += 1,
+            "}" => depth -= 1,
+            name if depth == 0 => names.push(name.to_string()),
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Returns the cache control of an entity of `type_name`, merged from the
+/// cache control of its type and of the fields selected on it.
+fn entity_cache_control(ctx: &Context<'_>, type_name: &str) -> CacheControl {
+    let mut cache_control = CacheControl::default();
+    merge_cache_control(
+        ctx,
+        type_name,
+        &ctx.item.node.selection_set,
+        &mut cache_control,
+    );
+    cache_control
+}
+
+fn merge_cache_control(
+    ctx: &Context<'_>,
+    type_name: &str,
+    selection_set: &Positioned<SelectionSet>,
+    cache_control: &mut CacheControl,
+) {
+    let registry = &ctx.schema_env.registry;
+    let ty = registry.types.get(type_name);
+    if let Some(MetaType::Object {
+        cache_control: type_cache_control,
+        ..
+    }) = ty
+    {
+        *cache_control = cache_control.merge(type_cache_control);
+    }
+
+    let applies = |type_condition: &str| {
+        type_condition == type_name
+            || ty.map_or(false, |ty| ty.is_possible_type(type_condition))
+            || registry
+                .types
+                .get(type_condition)
+                .map_or(false, |ty| ty.is_possible_type(type_name))
+    };
+
+    for selection in &selection_set.node.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                if let Some(meta_field) = ty.and_then(|ty| ty.field_by_name(&field.node.name.node))
+                {
+                    *cache_control = cache_control.merge(&meta_field.cache_control);
+                    if !field.node.selection_set.node.items.is_empty() {
+                        merge_cache_control(
+                            ctx,
+                            MetaTypeName::concrete_typename(&meta_field.ty),
+                            &field.node.selection_set,
+                            cache_control,
+                        );
+                    }
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                if let Some(fragment) = ctx.query_env.fragments.get(&spread.node.fragment_name.node)
+                {
+                    let type_condition = fragment.node.type_condition.node.on.node.as_str();
+                    if applies(type_condition) {
+                        merge_cache_control(
+                            ctx,
+                            type_condition,
+                            &fragment.node.selection_set,
+                            cache_control,
+                        );
+                    }
+                }
+            }
+            Selection::InlineFragment(fragment) => match &fragment.node.type_condition {
+                Some(type_condition) if applies(&type_condition.node.on.node) => {
+                    merge_cache_control(
+                        ctx,
+                        &type_condition.node.on.node,
+                        &fragment.node.selection_set,
+                        cache_control,
+                    )
+                }
+                Some(_) => {}
+                None => {
+                    merge_cache_control(ctx, type_name, &fragment.node.selection_set, cache_control)
+                }
+            },
+        }
+    }
+}
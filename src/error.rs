@@ -160,7 +160,7 @@ impl From<parser::Error> for ServerError {
 ///
 /// This is like [`QueryPathSegment`](enum.QueryPathSegment.html), but owned and
 /// used as a part of errors instead of during execution.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum PathSegment {
     /// A field in an object.
@@ -180,6 +180,7 @@ pub type ServerResult<T> = std::result::Result<T, ServerError>;
 pub struct InputValueError<T> {
     message: String,
     extensions: Option<ErrorExtensionValues>,
+    path: Vec<PathSegment>,
     phantom: PhantomData<T>,
 }
 
@@ -188,6 +189,7 @@ impl<T: InputType> InputValueError<T> {
         Self {
             message,
             extensions,
+            path: Vec::new(),
             phantom: PhantomData,
         }
     }
@@ -219,17 +221,49 @@ impl<T: InputType> InputValueError<T> {
 
     /// Propagate the error message to a different type.
     pub fn propagate<U: InputType>(self) -> InputValueError<U> {
-        if T::type_name() != U::type_name() {
-            InputValueError::new(
-                format!(
-                    r#"{} (occurred while parsing "{}")"#,
-                    self.message,
-                    U::type_name()
-                ),
-                self.extensions,
+        let message = if T::type_name() != U::type_name() {
+            format!(
+                r#"{} (occurred while parsing "{}")"#,
+                self.message,
+                U::type_name()
             )
         } else {
-            InputValueError::new(self.message, self.extensions)
+            self.message
+        };
+        InputValueError {
+            message,
+            extensions: self.extensions,
+            path: self.path,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Prepend a segment to the path of the nested input value that caused
+    /// this error.
+    ///
+    /// Input objects and lists call this with the name of the field or the
+    /// index of the item that failed to parse, so the error reports the full
+    /// path, such as `input.filters[2].range.start`.
+    #[must_use]
+    pub fn with_path_segment(mut self, segment: PathSegment) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+
+    /// Returns the path of the nested input value that caused this error, or
+    /// an empty slice if the error occurred at the top-level value.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// Prepend the argument name to the path of a nested input value.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn in_argument(self, name: &str) -> Self {
+        if self.path.is_empty() {
+            self
+        } else {
+            self.with_path_segment(PathSegment::Field(name.to_string()))
         }
     }
 
@@ -241,20 +275,153 @@ impl<T: InputType> InputValueError<T> {
         self
     }
 
+    /// Set the names of the input fields that caused this error, they are
+    /// reported in the `fields` extension.
+    #[must_use]
+    pub fn with_fields<I>(self, fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let fields = fields
+            .into_iter()
+            .map(|field| Value::String(field.into()))
+            .collect::<Vec<_>>();
+        self.with_extension("fields", Value::List(fields))
+    }
+
+    /// Replace the occurrences of a secret value in the error message with
+    /// `<secret>`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn redact(mut self, value: &Value) -> Self {
+        self.message = redact_secret(&self.message, value);
+        self
+    }
+
     /// Convert the error into a server error.
+    ///
+    /// If the error occurred in a nested input value, the path of the value is
+    /// rendered in the message and reported in the `inputPath` extension.
     pub fn into_server_error(self, pos: Pos) -> ServerError {
-        let mut err = ServerError::new(self.message, Some(pos));
-        err.extensions = self.extensions;
+        if self.path.is_empty() {
+            let mut err = ServerError::new(self.message, Some(pos));
+            err.extensions = self.extensions;
+            return err;
+        }
+
+        let mut err = ServerError::new(
+            format!(
+                r#"Invalid value for argument "{}", {}"#,
+                render_input_path(&self.path),
+                self.message
+            ),
+            Some(pos),
+        );
+        let path = self
+            .path
+            .into_iter()
+            .map(|segment| match segment {
+                PathSegment::Field(name) => Value::String(name),
+                PathSegment::Index(idx) => Value::from(idx as u64),
+            })
+            .collect();
+        let mut extensions = self.extensions.unwrap_or_default();
+        extensions.set("inputPath", Value::List(path));
+        err.extensions = Some(extensions);
         err
     }
 }
 
+/// Renders the path of a nested input value, such as
+/// `input.filters[2].range.start`.
+fn render_input_path(path: &[PathSegment]) -> String {
+    let mut output = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Field(name) => {
+                if !output.is_empty() {
+                    output.push('.');
+                }
+                output.push_str(name);
+            }
+            PathSegment::Index(idx) => {
+                output.push_str(&format!("[{}]", idx));
+            }
+        }
+    }
+    output
+}
+
 impl<T: InputType, E: Display> From<E> for InputValueError<T> {
     fn from(error: E) -> Self {
         Self::custom(error)
     }
 }
 
+/// Replaces the occurrences of the (possibly nested) secret `value` in
+/// `message` with `<secret>`.
+///
+/// Only whole words are replaced, so a short secret does not mangle the rest of
+/// the message.
+pub(crate) fn redact_secret(message: &str, value: &Value) -> String {
+    fn collect(value: &Value, secrets: &mut Vec<String>) {
+        match value {
+            Value::Null | Value::Binary(_) => {}
+            Value::String(s) => secrets.push(s.clone()),
+            Value::Number(n) => secrets.push(n.to_string()),
+            Value::Boolean(b) => secrets.push(b.to_string()),
+            Value::Enum(name) => secrets.push(name.to_string()),
+            Value::List(items) => {
+                secrets.push(value.to_string());
+                items.iter().for_each(|item| collect(item, secrets));
+            }
+            Value::Object(obj) => {
+                secrets.push(value.to_string());
+                obj.values().for_each(|item| collect(item, secrets));
+            }
+        }
+    }
+
+    let mut secrets = Vec::new();
+    collect(value, &mut secrets);
+    secrets.retain(|secret| !secret.is_empty());
+    secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut output = String::with_capacity(message.len());
+    let mut prev = None;
+    let mut rest = message;
+    while let Some(c) = rest.chars().next() {
+        let secret = secrets
+            .iter()
+            .find(|secret| match rest.strip_prefix(secret.as_str()) {
+                Some(after) => {
+                    let starts_inside_word =
+                        is_word_char(c) && matches!(prev, Some(prev) if is_word_char(prev));
+                    let ends_inside_word =
+                        matches!(secret.chars().last(), Some(last) if is_word_char(last))
+                            && matches!(after.chars().next(), Some(next) if is_word_char(next));
+                    !(starts_inside_word || ends_inside_word)
+                }
+                None => false,
+            });
+        match secret {
+            Some(secret) => {
+                output.push_str("<secret>");
+                prev = secret.chars().last();
+                rest = &rest[secret.len()..];
+            }
+            None => {
+                output.push(c);
+                prev = Some(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    output
+}
+
 /// An error parsing a value of type `T`.
 pub type InputValueResult<T> = Result<T, InputValueError<T>>;
 
@@ -0,0 +1,96 @@
+use std::{
+    future::Future,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Statistics of the execution of a request.
+///
+/// They are collected when enabled with
+/// [`SchemaBuilder::enable_execution_stats`](crate::SchemaBuilder::enable_execution_stats),
+/// and attached to [`Response::execution_stats`](crate::Response::execution_stats).
+/// They allow to monitor the health of the executor without a tracing stack.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionStats {
+    /// The number of fields resolved, excluding `__typename`.
+    pub resolver_count: usize,
+
+    /// The maximum number of fields being resolved at the same time. A field
+    /// is being resolved until its sub-fields are resolved.
+    pub max_concurrency: usize,
+
+    /// The sum of the times taken to resolve each field. The time of a field
+    /// includes the time of its sub-fields, so it is larger than
+    /// `wall_time` when the fields are resolved concurrently or nested.
+    pub total_await_time: Duration,
+
+    /// The time taken to execute the operation.
+    pub wall_time: Duration,
+
+    /// The number of fields returned from the field cache.
+    pub cache_hits: usize,
+
+    /// The number of batches started by the dataloaders used with
+    /// [`Context::load_one`](crate::Context::load_one) and
+    /// [`Context::load_many`](crate::Context::load_many).
+    pub dataloader_batches: usize,
+}
+
+/// Collects the statistics during the execution of a request.
+#[derive(Default)]
+pub(crate) struct ExecutionStatsCollector {
+    resolver_count: AtomicUsize,
+    concurrency: AtomicUsize,
+    max_concurrency: AtomicUsize,
+    total_await_time: AtomicU64,
+    cache_hits: AtomicUsize,
+    pub(crate) dataloader_batches: AtomicUsize,
+}
+
+impl ExecutionStatsCollector {
+    /// Resolves a field, recording its time and the concurrency.
+    pub(crate) async fn resolve_field<F: Future>(&self, fut: F) -> F::Output {
+        self.resolver_count.fetch_add(1, Ordering::Relaxed);
+        let concurrency = self.concurrency.fetch_add(1, Ordering::Relaxed) + 1;
+        self.max_concurrency
+            .fetch_max(concurrency, Ordering::Relaxed);
+
+        // The field is also finished when it is cancelled by the error of a
+        // sibling.
+        let _guard = FieldGuard {
+            collector: self,
+            start_time: Instant::now(),
+        };
+        fut.await
+    }
+
+    pub(crate) fn add_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn finish(&self, wall_time: Duration) -> ExecutionStats {
+        ExecutionStats {
+            resolver_count: self.resolver_count.load(Ordering::Relaxed),
+            max_concurrency: self.max_concurrency.load(Ordering::Relaxed),
+            total_await_time: Duration::from_nanos(self.total_await_time.load(Ordering::Relaxed)),
+            wall_time,
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            dataloader_batches: self.dataloader_batches.load(Ordering::Relaxed),
+        }
+    }
+}
+
+struct FieldGuard<'a> {
+    collector: &'a ExecutionStatsCollector,
+    start_time: Instant,
+}
+
+impl Drop for FieldGuard<'_> {
+    fn drop(&mut self) {
+        self.collector.total_await_time.fetch_add(
+            self.start_time.elapsed().as_nanos() as u64,
+            Ordering::Relaxed,
+        );
+        self.collector.concurrency.fetch_sub(1, Ordering::Relaxed);
+    }
+}
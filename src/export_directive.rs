@@ -0,0 +1,175 @@
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
+
+use indexmap::IndexMap;
+
+use crate::{
+    context::QueryPathSegment,
+    extensions::ResolveFut,
+    parser::types::Directive,
+    registry::{__DirectiveLocation, MetaDirective, MetaInputValue, Registry},
+    Context, ContextDirective, CustomDirective, CustomDirectiveFactory, Name, ServerResult, Value,
+    Variables,
+};
+
+pub(crate) struct ExportDirectiveFactory;
+
+impl CustomDirectiveFactory for ExportDirectiveFactory {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("export")
+    }
+
+    fn register(&self, registry: &mut Registry) {
+        let arg = MetaInputValue {
+            name: "as".to_string(),
+            description: Some(
+                "The name of the variable of the following operations of the batch.".to_string(),
+            ),
+            ty: "String!".to_string(),
+            default_value: None,
+            visible: None,
+            inaccessible: false,
+            tags: Default::default(),
+            is_secret: false,
+        };
+        registry.add_directive(MetaDirective {
+            name: "export".into(),
+            description: Some(
+                "Exports the value of the field as a variable of the following operations of the batch."
+                    .to_string(),
+            ),
+            locations: vec![__DirectiveLocation::FIELD],
+            args: [(arg.name.clone(), arg)].into_iter().collect(),
+            is_repeatable: false,
+            visible: None,
+            composable: None,
+        });
+    }
+
+    fn create(
+        &self,
+        ctx: &ContextDirective<'_>,
+        _directive: &Directive,
+    ) -> ServerResult<Box<dyn CustomDirective>> {
+        let (_, name) = ctx.param_value::<String>("as", None)?;
+        Ok(Box::new(ExportDirective { name }))
+    }
+}
+
+struct ExportDirective {
+    name: String,
+}
+
+#[async_trait::async_trait]
+impl CustomDirective for ExportDirective {
+    async fn resolve_field(
+        &self,
+        ctx: &Context<'_>,
+        resolve: ResolveFut<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let value = resolve.await?;
+        if let Some(value) = &value {
+            export_value(ctx, self.name.clone(), value);
+        }
+        Ok(value)
+    }
+}
+
+/// Exports the value of the field of `ctx` if it has an `@export` directive,
+/// for the dynamic schema which does not support the custom directives.
+#[cfg(feature = "dynamic-schema")]
+pub(crate) fn export_field_value(
+    ctx: &Context<'_>,
+    field: &crate::parser::types::Field,
+    value: &Value,
+) -> ServerResult<()> {
+    let directive = match field
+        .directives
+        .iter()
+        .find(|directive| directive.node.name.node == "export")
+    {
+        Some(directive) => directive,
+        None => return Ok(()),
+    };
+    let name = match directive
+        .node
+        .get_argument("as")
+        .map(|name| ctx.resolve_input_value(name.clone()))
+        .transpose()?
+    {
+        Some(Value::String(name)) => name,
+        _ => {
+            return Err(ctx.set_error_path(crate::ServerError::new(
+                r#"Invalid value for argument "as", expected type "String!""#,
+                Some(directive.pos),
+            )))
+        }
+    };
+    export_value(ctx, name, value);
+    Ok(())
+}
+
+fn export_value(ctx: &Context<'_>, name: String, value: &Value) {
+    if let Some(exports) = ctx.data_opt::<ExportedValues>() {
+        let mut indexes = Vec::new();
+        if let Some(path_node) = ctx.path_node {
+            path_node.for_each(|segment| {
+                if let QueryPathSegment::Index(idx) = segment {
+                    indexes.push(*idx);
+                }
+            });
+        }
+        exports.0.lock().unwrap().push(ExportedValue {
+            name,
+            indexes,
+            value: value.clone(),
+        });
+    }
+}
+
+struct ExportedValue {
+    name: String,
+    /// The list indexes in the path of the field.
+    indexes: Vec<usize>,
+    value: Value,
+}
+
+/// The values exported by the fields of an operation with `@export`.
+#[derive(Clone, Default)]
+pub(crate) struct ExportedValues(Arc<Mutex<Vec<ExportedValue>>>);
+
+impl ExportedValues {
+    /// Adds the exported values to `variables`.
+    ///
+    /// The value of a field inside of a list is exported as the list of the
+    /// values, in the order of the response.
+    pub(crate) fn export_to(&self, variables: &mut Variables) {
+        let mut values = std::mem::take(&mut *self.0.lock().unwrap());
+        values.sort_by(|a, b| a.indexes.cmp(&b.indexes));
+
+        let mut exports = IndexMap::<String, Value>::new();
+        for ExportedValue {
+            name,
+            indexes,
+            value,
+        } in values
+        {
+            if indexes.is_empty() {
+                exports.insert(name, value);
+            } else {
+                match exports
+                    .entry(name)
+                    .or_insert_with(|| Value::List(Vec::new()))
+                {
+                    Value::List(items) => items.push(value),
+                    export => *export = Value::List(vec![value]),
+                }
+            }
+        }
+        for (name, value) in exports {
+            variables.insert(Name::new(name), value);
+        }
+    }
+}
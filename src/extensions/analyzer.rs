@@ -3,14 +3,17 @@ use std::sync::Arc;
 use futures_util::lock::Mutex;
 
 use crate::{
-    extensions::{Extension, ExtensionContext, ExtensionFactory, NextRequest, NextValidation},
-    value, Response, ServerError, ValidationResult,
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextRequest, NextValidation, RequestCapture,
+    },
+    value, ClientInfo, Name, Response, ServerError, ValidationResult, Value,
 };
 
 /// Analyzer extension
 ///
 /// This extension will output the `analyzer` field containing `complexity` and
-/// `depth` in the response extension of each query.
+/// `depth` in the response extension of each query, and the `clientName` and
+/// `clientVersion` of the [`ClientInfo`] of the request, if any.
 pub struct Analyzer;
 
 impl ExtensionFactory for Analyzer {
@@ -22,6 +25,7 @@ impl ExtensionFactory for Analyzer {
 #[derive(Default)]
 struct AnalyzerExtension {
     validation_result: Mutex<Option<ValidationResult>>,
+    client_info: RequestCapture<ClientInfo>,
 }
 
 #[async_trait::async_trait]
@@ -30,13 +34,19 @@ impl Extension for AnalyzerExtension {
         let mut resp = next.run(ctx).await;
         let validation_result = self.validation_result.lock().await.take();
         if let Some(validation_result) = validation_result {
-            resp = resp.extension(
-                "analyzer",
-                value! ({
-                    "complexity": validation_result.complexity,
-                    "depth": validation_result.depth,
-                }),
-            );
+            let mut analyzer = value! ({
+                "complexity": validation_result.complexity,
+                "depth": validation_result.depth,
+            });
+            if let (Value::Object(analyzer), Some(client_info)) =
+                (&mut analyzer, self.client_info.take())
+            {
+                analyzer.insert(Name::new("clientName"), Value::String(client_info.name));
+                if let Some(version) = client_info.version {
+                    analyzer.insert(Name::new("clientVersion"), Value::String(version));
+                }
+            }
+            resp = resp.extension("analyzer", analyzer);
         }
         resp
     }
@@ -48,6 +58,7 @@ impl Extension for AnalyzerExtension {
     ) -> Result<ValidationResult, Vec<ServerError>> {
         let res = next.run(ctx).await?;
         *self.validation_result.lock().await = Some(res);
+        self.client_info.capture(ctx);
         Ok(res)
     }
 }
@@ -117,5 +128,22 @@ mod tests {
                 "depth": 3,
             }))
         );
+
+        let res = schema
+            .execute(Request::new("{ value }").data(ClientInfo::new("web").version("1.2.0")))
+            .await
+            .into_result()
+            .unwrap()
+            .extensions
+            .remove("analyzer");
+        assert_eq!(
+            res,
+            Some(value!({
+                "complexity": 1,
+                "depth": 1,
+                "clientName": "web",
+                "clientVersion": "1.2.0",
+            }))
+        );
     }
 }
@@ -1,12 +1,18 @@
 //! Apollo persisted queries extension.
 
-use std::sync::Arc;
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_graphql_parser::types::ExecutableDocument;
+use fnv::FnvHasher;
 use futures_util::lock::Mutex;
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
 
+pub use crate::extensions::CacheStorage;
 use crate::{
     extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
     from_value, Request, ServerError, ServerResult,
@@ -19,16 +25,6 @@ struct PersistedQuery {
     sha256_hash: String,
 }
 
-/// Cache storage for persisted queries.
-#[async_trait::async_trait]
-pub trait CacheStorage: Send + Sync + Clone + 'static {
-    /// Load the query by `key`.
-    async fn get(&self, key: String) -> Option<ExecutableDocument>;
-
-    /// Save the query by `key`.
-    async fn set(&self, key: String, query: ExecutableDocument);
-}
-
 /// Memory-based LRU cache.
 #[derive(Clone)]
 pub struct LruCacheStorage(Arc<Mutex<lru::LruCache<String, ExecutableDocument>>>);
@@ -53,29 +49,283 @@ impl CacheStorage for LruCacheStorage {
     }
 }
 
+type Shard = lru::LruCache<String, (ExecutableDocument, Instant)>;
+
+/// Memory-based LRU cache split into several shards, each protected by its
+/// own lock, which reduces the contention between concurrent requests.
+///
+/// Entries can optionally expire after a time to live.
+#[derive(Clone)]
+pub struct ShardedLruCacheStorage {
+    shards: Arc<[Mutex<Shard>]>,
+    ttl: Option<Duration>,
+}
+
+impl ShardedLruCacheStorage {
+    /// Creates a new LRU cache with `shards` shards that each hold at most
+    /// `cap` items.
+    pub fn new(shards: usize, cap: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| Mutex::new(lru::LruCache::new(cap)))
+                .collect(),
+            ttl: None,
+        }
+    }
+
+    /// Expire the entries `ttl` after they have been saved.
+    #[must_use]
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..self
+        }
+    }
+
+    fn shard(&self, key: &str) -> &Mutex<Shard> {
+        let mut hasher = FnvHasher::default();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheStorage for ShardedLruCacheStorage {
+    async fn get(&self, key: String) -> Option<ExecutableDocument> {
+        let mut cache = self.shard(&key).lock().await;
+        match cache.get(&key) {
+            Some((_, saved_at)) if matches!(self.ttl, Some(ttl) if saved_at.elapsed() > ttl) => {
+                cache.pop(&key);
+                None
+            }
+            Some((doc, _)) => Some(doc.clone()),
+            None => None,
+        }
+    }
+
+    async fn set(&self, key: String, query: ExecutableDocument) {
+        let mut cache = self.shard(&key).lock().await;
+        cache.put(key, (query, Instant::now()));
+    }
+}
+
+/// A key-value store shared by several instances of the server, such as
+/// Redis, that can hold persisted queries.
+///
+/// Use it with [`KeyValueCacheStorage`]. The values are the persisted
+/// queries serialized as JSON.
+#[async_trait::async_trait]
+pub trait KeyValueStore: Send + Sync + 'static {
+    /// Load the value of `key`.
+    async fn get(&self, key: &str) -> Option<String>;
+
+    /// Save the value of `key`, which should expire after `ttl` if it is set.
+    async fn set(&self, key: &str, value: String, ttl: Option<Duration>);
+}
+
+/// Cache storage backed by a [`KeyValueStore`].
+///
+/// The queries are saved as serialized parsed documents, whose format can
+/// change between the versions of this crate, so the keys are prefixed with
+/// the version that saved them, and the queries saved by another version are
+/// not found.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_graphql::extensions::apollo_persisted_queries::{
+///     ApolloPersistedQueries, KeyValueCacheStorage, KeyValueStore,
+/// };
+///
+/// struct RedisStore {
+///     // The Redis client
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl KeyValueStore for RedisStore {
+///     async fn get(&self, key: &str) -> Option<String> {
+///         // GET apq:<key>
+///         None
+///     }
+///
+///     async fn set(&self, key: &str, value: String, ttl: Option<Duration>) {
+///         // SET apq:<key> <value> EX <ttl>
+///     }
+/// }
+///
+/// let extension = ApolloPersistedQueries::new(
+///     KeyValueCacheStorage::new(RedisStore {}).ttl(Duration::from_secs(86400)),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct KeyValueCacheStorage {
+    store: Arc<dyn KeyValueStore>,
+    ttl: Option<Duration>,
+}
+
+impl KeyValueCacheStorage {
+    /// Creates a cache storage saving the queries into `store`.
+    pub fn new(store: impl KeyValueStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            ttl: None,
+        }
+    }
+
+    /// Expire the entries `ttl` after they have been saved.
+    #[must_use]
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..self
+        }
+    }
+}
+
+fn versioned_key(key: &str) -> String {
+    format!("{}:{}", env!("CARGO_PKG_VERSION"), key)
+}
+
+#[async_trait::async_trait]
+impl CacheStorage for KeyValueCacheStorage {
+    async fn get(&self, key: String) -> Option<ExecutableDocument> {
+        let value = self.store.get(&versioned_key(&key)).await?;
+        serde_json::from_str(&value).ok()
+    }
+
+    async fn set(&self, key: String, query: ExecutableDocument) {
+        if let Ok(value) = serde_json::to_string(&query) {
+            self.store.set(&versioned_key(&key), value, self.ttl).await;
+        }
+    }
+}
+
+/// The result of looking up a persisted query, reported to the function
+/// passed to [`ApolloPersistedQueries::on_lookup`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CacheLookup {
+    /// The query was found in the cache storage.
+    Hit,
+    /// The query was not found in the cache storage.
+    Miss,
+    /// The query was recently not found, and the cache storage was not
+    /// queried again.
+    NegativeHit,
+}
+
+struct NegativeCache {
+    cache: Mutex<lru::LruCache<String, Instant>>,
+    ttl: Duration,
+}
+
 /// Apollo persisted queries extension.
 ///
 /// [Reference](https://www.apollographql.com/docs/react/api/link/persisted-queries/)
 #[cfg_attr(docsrs, doc(cfg(feature = "apollo_persisted_queries")))]
-pub struct ApolloPersistedQueries<T>(T);
+pub struct ApolloPersistedQueries<T> {
+    storage: T,
+    negative_cache: Option<Arc<NegativeCache>>,
+    on_lookup: Option<Arc<dyn Fn(CacheLookup) + Send + Sync>>,
+}
 
 impl<T: CacheStorage> ApolloPersistedQueries<T> {
     /// Creates an apollo persisted queries extension.
     pub fn new(cache_storage: T) -> ApolloPersistedQueries<T> {
-        Self(cache_storage)
+        Self {
+            storage: cache_storage,
+            negative_cache: None,
+            on_lookup: None,
+        }
+    }
+
+    /// Remember up to `cap` hashes that were not found in the cache storage
+    /// for `ttl`, and answer `PersistedQueryNotFound` for them without
+    /// querying the storage again.
+    ///
+    /// The hash is forgotten as soon as the query is saved.
+    #[must_use]
+    pub fn negative_cache(self, cap: usize, ttl: Duration) -> Self {
+        Self {
+            negative_cache: Some(Arc::new(NegativeCache {
+                cache: Mutex::new(lru::LruCache::new(cap)),
+                ttl,
+            })),
+            ..self
+        }
+    }
+
+    /// Call `f` with the result of every persisted query lookup, for example
+    /// to record the hit rate of the cache.
+    #[must_use]
+    pub fn on_lookup(self, f: impl Fn(CacheLookup) + Send + Sync + 'static) -> Self {
+        Self {
+            on_lookup: Some(Arc::new(f)),
+            ..self
+        }
     }
 }
 
 impl<T: CacheStorage> ExtensionFactory for ApolloPersistedQueries<T> {
     fn create(&self) -> Arc<dyn Extension> {
         Arc::new(ApolloPersistedQueriesExtension {
-            storage: self.0.clone(),
+            storage: self.storage.clone(),
+            negative_cache: self.negative_cache.clone(),
+            on_lookup: self.on_lookup.clone(),
         })
     }
 }
 
 struct ApolloPersistedQueriesExtension<T> {
     storage: T,
+    negative_cache: Option<Arc<NegativeCache>>,
+    on_lookup: Option<Arc<dyn Fn(CacheLookup) + Send + Sync>>,
+}
+
+impl<T: CacheStorage> ApolloPersistedQueriesExtension<T> {
+    async fn lookup(&self, key: String) -> Option<ExecutableDocument> {
+        if let Some(negative_cache) = &self.negative_cache {
+            let mut cache = negative_cache.cache.lock().await;
+            match cache.get(&key) {
+                Some(missed_at) if missed_at.elapsed() <= negative_cache.ttl => {
+                    self.report(CacheLookup::NegativeHit);
+                    return None;
+                }
+                Some(_) => {
+                    cache.pop(&key);
+                }
+                None => {}
+            }
+        }
+
+        match self.storage.get(key.clone()).await {
+            Some(doc) => {
+                self.report(CacheLookup::Hit);
+                Some(doc)
+            }
+            None => {
+                self.report(CacheLookup::Miss);
+                if let Some(negative_cache) = &self.negative_cache {
+                    negative_cache.cache.lock().await.put(key, Instant::now());
+                }
+                None
+            }
+        }
+    }
+
+    async fn save(&self, key: String, doc: ExecutableDocument) {
+        if let Some(negative_cache) = &self.negative_cache {
+            negative_cache.cache.lock().await.pop(&key);
+        }
+        self.storage.set(key, doc).await;
+    }
+
+    fn report(&self, lookup: CacheLookup) {
+        if let Some(on_lookup) = &self.on_lookup {
+            on_lookup(lookup);
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -97,7 +347,7 @@ impl<T: CacheStorage> Extension for ApolloPersistedQueriesExtension<T> {
             }
 
             if request.query.is_empty() {
-                if let Some(doc) = self.storage.get(persisted_query.sha256_hash).await {
+                if let Some(doc) = self.lookup(persisted_query.sha256_hash).await {
                     Ok(Request {
                         parsed_query: Some(doc),
                         ..request
@@ -112,7 +362,12 @@ impl<T: CacheStorage> Extension for ApolloPersistedQueriesExtension<T> {
                     Err(ServerError::new("provided sha does not match query", None))
                 } else {
                     let doc = async_graphql_parser::parse_query(&request.query)?;
-                    self.storage.set(sha256_hash, doc.clone()).await;
+                    // The queries with secret values written inline are not saved into the
+                    // storage, so these values are not stored, and the clients keep sending
+                    // them in full.
+                    if !ctx.schema_env.registry.has_secret_literals(&doc) {
+                        self.save(sha256_hash, doc.clone()).await;
+                    }
                     Ok(Request {
                         query: String::new(),
                         parsed_query: Some(doc),
@@ -193,4 +448,120 @@ mod tests {
             vec![ServerError::new("PersistedQueryNotFound", None)]
         );
     }
+
+    #[tokio::test]
+    async fn test_storages() {
+        use std::{collections::HashMap, sync::Mutex as StdMutex};
+
+        use super::*;
+        use crate::*;
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn value(&self) -> i32 {
+                100
+            }
+        }
+
+        #[derive(Default)]
+        struct MemoryStore(StdMutex<HashMap<String, (String, Option<Duration>)>>);
+
+        #[async_trait::async_trait]
+        impl KeyValueStore for Arc<MemoryStore> {
+            async fn get(&self, key: &str) -> Option<String> {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .get(key)
+                    .map(|(value, _)| value.clone())
+            }
+
+            async fn set(&self, key: &str, value: String, ttl: Option<Duration>) {
+                self.0.lock().unwrap().insert(key.to_string(), (value, ttl));
+            }
+        }
+
+        fn persisted_request(query: &str) -> Request {
+            let mut request = Request::new(query);
+            request.extensions.insert(
+                "persistedQuery".to_string(),
+                value!({
+                    "version": 1,
+                    "sha256Hash": "854174ebed716fe24fd6659c30290aecd9bc1d17dc4f47939a1848a1b8ed3c6b",
+                }),
+            );
+            request
+        }
+
+        let store = Arc::new(MemoryStore::default());
+        let lookups = Arc::new(StdMutex::new(Vec::new()));
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(
+                ApolloPersistedQueries::new(
+                    KeyValueCacheStorage::new(store.clone()).ttl(Duration::from_secs(60)),
+                )
+                .negative_cache(16, Duration::from_secs(60))
+                .on_lookup({
+                    let lookups = lookups.clone();
+                    move |lookup| lookups.lock().unwrap().push(lookup)
+                }),
+            )
+            .finish();
+
+        for _ in 0..2 {
+            assert_eq!(
+                schema
+                    .execute(persisted_request(""))
+                    .await
+                    .into_result()
+                    .unwrap_err(),
+                vec![ServerError::new("PersistedQueryNotFound", None)]
+            );
+        }
+        schema
+            .execute(persisted_request("{ value }"))
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(
+            store.0.lock().unwrap().keys().collect::<Vec<_>>(),
+            vec![&format!(
+                "{}:854174ebed716fe24fd6659c30290aecd9bc1d17dc4f47939a1848a1b8ed3c6b",
+                env!("CARGO_PKG_VERSION")
+            )]
+        );
+        assert!(store
+            .0
+            .lock()
+            .unwrap()
+            .values()
+            .all(|(_, ttl)| *ttl == Some(Duration::from_secs(60))));
+        assert_eq!(
+            schema
+                .execute(persisted_request(""))
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "value": 100 })
+        );
+        assert_eq!(
+            *lookups.lock().unwrap(),
+            vec![
+                CacheLookup::Miss,
+                CacheLookup::NegativeHit,
+                CacheLookup::Hit
+            ]
+        );
+
+        let storage = ShardedLruCacheStorage::new(4, 16).ttl(Duration::from_millis(50));
+        let doc = parser::parse_query("{ value }").unwrap();
+        storage.set("a".to_string(), doc).await;
+        assert!(storage.get("a".to_string()).await.is_some());
+        assert!(storage.get("b".to_string()).await.is_none());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(storage.get("a".to_string()).await.is_none());
+    }
 }
@@ -0,0 +1,481 @@
+use std::{
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant, SystemTime},
+};
+
+use futures_util::{
+    future::BoxFuture,
+    stream::{BoxStream, StreamExt},
+};
+use serde::Serialize;
+
+use crate::{
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextParseQuery, NextPrepareRequest,
+        NextRequest, NextSubscribe, RequestId,
+    },
+    operation_signature,
+    parser::types::{DocumentOperations, ExecutableDocument, OperationType},
+    OperationSignatureOptions, Request, Response, ServerResult, Timer, Value, Variables,
+};
+
+/// The identity of the client of a request, recorded by the [`AuditLog`]
+/// extension and used as the key of the budgets of the
+/// [`ComplexityBudget`](super::ComplexityBudget) extension.
+///
+/// Insert it into the data of the request or of the session, usually from the
+/// authentication of the HTTP layer:
+///
+/// ```
+/// use async_graphql::{extensions::ClientIdentity, Request};
+///
+/// let request = Request::new("{ value }").data(ClientIdentity("billing-service".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientIdentity(pub String);
+
+/// The outcome of an audited request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The operation was executed without errors.
+    Success,
+    /// The operation was executed with errors.
+    Error,
+    /// The request was rejected before the execution, for example because
+    /// the query is invalid.
+    Rejected,
+}
+
+/// The record of a request, passed to the [`AuditSink`] of the [`AuditLog`]
+/// extension.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditRecord {
+    /// The time when the request started.
+    pub timestamp: SystemTime,
+    /// The normalized text of the operation, see
+    /// [`operation_signature`](crate::operation_signature), or `None` if
+    /// the query cannot be parsed.
+    pub document: Option<String>,
+    /// The operation name.
+    pub operation_name: Option<String>,
+    /// The operation type.
+    pub operation_type: Option<OperationType>,
+    /// The shape of the variables, where the values are replaced with the
+    /// names of their types, such as `"string"` or `"number"`.
+    pub variables: Value,
+    /// The [`ClientIdentity`] of the request, if any.
+    pub client: Option<String>,
+    /// The [`RequestId`] of the request, if any.
+    pub request_id: Option<String>,
+    /// The outcome of the request.
+    pub outcome: AuditOutcome,
+    /// The number of errors in the response.
+    pub errors: usize,
+    /// The duration of the request.
+    pub duration: Duration,
+}
+
+/// The destination of the records of the [`AuditLog`] extension, such as a
+/// Kafka topic or an S3 bucket.
+#[async_trait::async_trait]
+pub trait AuditSink: Send + Sync + 'static {
+    /// Write a batch of records.
+    async fn write(&self, records: Vec<AuditRecord>);
+}
+
+/// Audit sink that writes the records to the standard output, one JSON object
+/// per line.
+pub struct StdoutAuditSink;
+
+#[async_trait::async_trait]
+impl AuditSink for StdoutAuditSink {
+    async fn write(&self, records: Vec<AuditRecord>) {
+        for record in records {
+            if let Ok(line) = serde_json::to_string(&record) {
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+/// Audit log extension
+///
+/// Records every executed query, mutation and subscription, with its
+/// normalized text, operation name, the shape of its variables, the
+/// [`ClientIdentity`] of the request and its outcome, and writes the records
+/// to an [`AuditSink`] by batches. A subscription is recorded when its stream
+/// ends or is dropped, with the errors of all its responses.
+///
+/// The batches are written in tasks created with the spawner, such as
+/// `tokio::spawn`, so the requests do not wait for the sink. The pending
+/// records are written when the last clone of the extension is dropped, but
+/// the runtime may stop before the task completes, so call
+/// [`AuditLog::flush`] when the server shuts down.
+///
+/// The values of the variables are not recorded, and the literals are
+/// removed from the normalized text.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// use async_graphql::{
+///     extensions::{AuditLog, AuditRecord, AuditSink, ClientIdentity},
+///     *,
+/// };
+///
+/// #[derive(Clone, Default)]
+/// struct MemorySink(Arc<Mutex<Vec<AuditRecord>>>);
+///
+/// #[async_trait::async_trait]
+/// impl AuditSink for MemorySink {
+///     async fn write(&self, records: Vec<AuditRecord>) {
+///         self.0.lock().unwrap().extend(records);
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn add(&self, a: i32, b: i32) -> i32 {
+///         a + b
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let sink = MemorySink::default();
+/// let audit_log = AuditLog::new(sink.clone(), tokio::spawn).batch_size(10);
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(audit_log.clone())
+///     .finish();
+///
+/// let request = Request::new("query Add($b: Int!) { add(a: 1, b: $b) }")
+///     .variables(Variables::from_value(value!({ "b": 2 })))
+///     .data(ClientIdentity("billing-service".to_string()));
+/// schema.execute(request).await;
+///
+/// // Write the records that do not fill a batch yet, such as when the server
+/// // shuts down.
+/// audit_log.flush().await;
+///
+/// let records = sink.0.lock().unwrap();
+/// assert_eq!(
+///     records[0].document.as_deref(),
+///     Some("query Add($b:Int!){add(a:0,b:$b)}")
+/// );
+/// assert_eq!(records[0].variables, value!({ "b": "number" }));
+/// assert_eq!(records[0].client.as_deref(), Some("billing-service"));
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct AuditLog {
+    buffer: Arc<AuditBuffer>,
+    batch_size: usize,
+    flush_interval: Option<Duration>,
+}
+
+type AuditSpawner = Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>;
+
+/// The pending records, which are written when the buffer is dropped.
+struct AuditBuffer {
+    sink: Arc<dyn AuditSink>,
+    spawner: AuditSpawner,
+    records: Mutex<Vec<AuditRecord>>,
+}
+
+impl AuditBuffer {
+    fn write(&self, records: Vec<AuditRecord>) {
+        if !records.is_empty() {
+            let sink = self.sink.clone();
+            (self.spawner)(Box::pin(async move { sink.write(records).await }));
+        }
+    }
+}
+
+impl Drop for AuditBuffer {
+    fn drop(&mut self) {
+        let records = std::mem::take(&mut *self.records.lock().unwrap());
+        self.write(records);
+    }
+}
+
+impl AuditLog {
+    /// Create an audit log extension that writes the records to `sink`, in
+    /// the tasks created with `spawner`, such as `tokio::spawn`.
+    pub fn new<S, R>(sink: impl AuditSink, spawner: S) -> Self
+    where
+        S: Fn(BoxFuture<'static, ()>) -> R + Send + Sync + 'static,
+    {
+        Self {
+            buffer: Arc::new(AuditBuffer {
+                sink: Arc::new(sink),
+                spawner: Arc::new(move |fut| {
+                    spawner(fut);
+                }),
+                records: Default::default(),
+            }),
+            batch_size: 1,
+            flush_interval: None,
+        }
+    }
+
+    /// Set the number of records written to the sink at once, default is
+    /// `1`.
+    #[must_use]
+    pub fn batch_size(self, batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Write the records which do not fill a batch at most `interval` after
+    /// they are recorded, with the [`Timer`] of the schema.
+    ///
+    /// By default, they are only written when a batch is full, when
+    /// [`AuditLog::flush`] is called and when the extension is dropped.
+    #[must_use]
+    pub fn flush_interval(self, interval: Duration) -> Self {
+        Self {
+            flush_interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Write the pending records to the sink, and wait for it.
+    pub async fn flush(&self) {
+        let records = std::mem::take(&mut *self.buffer.records.lock().unwrap());
+        if !records.is_empty() {
+            self.buffer.sink.write(records).await;
+        }
+    }
+
+    fn push(&self, record: AuditRecord, timer: Option<&dyn Timer>) {
+        let (batch, is_first) = {
+            let mut records = self.buffer.records.lock().unwrap();
+            records.push(record);
+            if records.len() >= self.batch_size {
+                (std::mem::take(&mut *records), false)
+            } else {
+                (Vec::new(), records.len() == 1)
+            }
+        };
+        self.buffer.write(batch);
+
+        // the first pending record schedules the flush of the batch
+        if !is_first {
+            return;
+        }
+        if let (Some(interval), Some(timer)) = (self.flush_interval, timer) {
+            let delay = timer.delay(interval);
+            let buffer = Arc::downgrade(&self.buffer);
+            (self.buffer.spawner)(Box::pin(flush_after(delay, buffer)));
+        }
+    }
+}
+
+async fn flush_after(delay: BoxFuture<'static, ()>, buffer: Weak<AuditBuffer>) {
+    delay.await;
+    if let Some(buffer) = buffer.upgrade() {
+        let records = std::mem::take(&mut *buffer.records.lock().unwrap());
+        if !records.is_empty() {
+            buffer.sink.write(records).await;
+        }
+    }
+}
+
+impl ExtensionFactory for AuditLog {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(AuditLogExtension {
+            audit_log: self.clone(),
+            state: Default::default(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct RequestState {
+    operation_name: Option<String>,
+    document: Option<String>,
+    operation_type: Option<OperationType>,
+    variables: Option<Value>,
+    client: Option<String>,
+    request_id: Option<String>,
+}
+
+impl RequestState {
+    fn into_record(
+        self,
+        timestamp: SystemTime,
+        outcome: AuditOutcome,
+        errors: usize,
+        duration: Duration,
+    ) -> AuditRecord {
+        AuditRecord {
+            timestamp,
+            document: self.document,
+            operation_name: self.operation_name,
+            operation_type: self.operation_type,
+            variables: self.variables.unwrap_or_default(),
+            client: self.client,
+            request_id: self.request_id,
+            outcome,
+            errors,
+            duration,
+        }
+    }
+}
+
+struct AuditLogExtension {
+    audit_log: AuditLog,
+    state: Arc<Mutex<RequestState>>,
+}
+
+/// Records a subscription when its stream is dropped.
+struct AuditedSubscription {
+    audit_log: AuditLog,
+    state: Arc<Mutex<RequestState>>,
+    timer: Option<Arc<dyn Timer>>,
+    timestamp: SystemTime,
+    start_time: Instant,
+    rejected: bool,
+    errors: usize,
+}
+
+impl AuditedSubscription {
+    fn add_response(&mut self, resp: &Response) {
+        // the requests rejected before the execution have no operation
+        self.rejected |= resp.operation.is_none();
+        self.errors += resp.errors.len();
+    }
+}
+
+impl Drop for AuditedSubscription {
+    fn drop(&mut self) {
+        let state = std::mem::take(&mut *self.state.lock().unwrap());
+        let outcome = if self.rejected {
+            AuditOutcome::Rejected
+        } else if self.errors == 0 {
+            AuditOutcome::Success
+        } else {
+            AuditOutcome::Error
+        };
+        let record = state.into_record(
+            self.timestamp,
+            outcome,
+            self.errors,
+            self.start_time.elapsed(),
+        );
+        self.audit_log.push(record, self.timer.as_deref());
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for AuditLogExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let timestamp = SystemTime::now();
+        let start_time = Instant::now();
+        let resp = next.run(ctx).await;
+
+        let state = std::mem::take(&mut *self.state.lock().unwrap());
+        let outcome = if resp.operation.is_none() {
+            AuditOutcome::Rejected
+        } else if resp.errors.is_empty() {
+            AuditOutcome::Success
+        } else {
+            AuditOutcome::Error
+        };
+        let record = state.into_record(timestamp, outcome, resp.errors.len(), start_time.elapsed());
+        self.audit_log.push(record, ctx.schema_env.timer.as_deref());
+        resp
+    }
+
+    fn subscribe<'s>(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        stream: BoxStream<'s, Response>,
+        next: NextSubscribe<'_>,
+    ) -> BoxStream<'s, Response> {
+        let mut subscription = AuditedSubscription {
+            audit_log: self.audit_log.clone(),
+            state: self.state.clone(),
+            timer: ctx.schema_env.timer.clone(),
+            timestamp: SystemTime::now(),
+            start_time: Instant::now(),
+            rejected: false,
+            errors: 0,
+        };
+        Box::pin(next.run(ctx, stream).map(move |resp| {
+            subscription.add_response(&resp);
+            resp
+        }))
+    }
+
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let request = next.run(ctx, request).await?;
+        self.state.lock().unwrap().operation_name = request.operation_name.clone();
+        Ok(request)
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.client = ctx.data_opt::<ClientIdentity>().map(|id| id.0.clone());
+            state.request_id = ctx.data_opt::<RequestId>().map(|id| id.0.clone());
+            state.variables = Some(variables_shape(&variables.clone().into_value()));
+        }
+
+        let document = next.run(ctx, query, variables).await?;
+        let mut state = self.state.lock().unwrap();
+        let operation_name = state.operation_name.clone();
+        state.document = operation_signature(
+            &document,
+            operation_name.as_deref(),
+            &OperationSignatureOptions::new().sorted_fields(),
+        );
+        state.operation_type = match (&document.operations, operation_name.as_deref()) {
+            (DocumentOperations::Single(operation), None) => Some(operation.node.ty),
+            (DocumentOperations::Multiple(operations), Some(name)) => {
+                operations.get(name).map(|operation| operation.node.ty)
+            }
+            (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => operations
+                .iter()
+                .next()
+                .map(|(_, operation)| operation.node.ty),
+            _ => None,
+        };
+        Ok(document)
+    }
+}
+
+fn variables_shape(value: &Value) -> Value {
+    match value {
+        Value::Null => Value::Null,
+        Value::Number(_) => Value::from("number"),
+        Value::String(_) => Value::from("string"),
+        Value::Boolean(_) => Value::from("boolean"),
+        Value::Binary(_) => Value::from("binary"),
+        Value::Enum(_) => Value::from("enum"),
+        Value::List(items) => Value::List(items.iter().take(1).map(variables_shape).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), variables_shape(value)))
+                .collect(),
+        ),
+    }
+}
@@ -0,0 +1,17 @@
+use async_graphql_parser::types::ExecutableDocument;
+
+/// Cache storage, for the persisted queries of
+/// [`ApolloPersistedQueries`](crate::extensions::apollo_persisted_queries::ApolloPersistedQueries)
+/// and for the values of the fields cached with
+/// `#[graphql(cache(ttl = "30s"))]`, which are
+/// [`FieldCacheEntry`](crate::FieldCacheEntry).
+#[async_trait::async_trait]
+pub trait CacheStorage<V: Send + 'static = ExecutableDocument>:
+    Send + Sync + Clone + 'static
+{
+    /// Load the value by `key`.
+    async fn get(&self, key: String) -> Option<V>;
+
+    /// Save the value by `key`.
+    async fn set(&self, key: String, value: V);
+}
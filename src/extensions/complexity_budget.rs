@@ -0,0 +1,373 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    extensions::{
+        ClientIdentity, Extension, ExtensionContext, ExtensionFactory, NextRequest, NextValidation,
+    },
+    value, Response, ServerError, ValidationResult,
+};
+
+/// The state of a complexity budget after a request tried to consume it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BudgetUsage {
+    /// Whether the cost of the request was consumed from the budget, `false`
+    /// if the remaining budget was not enough.
+    pub consumed: bool,
+    /// The remaining budget.
+    pub remaining: usize,
+    /// The time until the budget is reset.
+    pub reset_in: Duration,
+}
+
+/// Storage for the complexity budgets, such as a key-value store shared by
+/// several instances of the server.
+#[async_trait::async_trait]
+pub trait BudgetStorage: Send + Sync + 'static {
+    /// Consume `cost` from the budget of `key` if the remaining budget is
+    /// enough, the budget being reset to `limit` every `window`.
+    async fn consume(&self, key: &str, cost: usize, limit: usize, window: Duration) -> BudgetUsage;
+}
+
+#[derive(Copy, Clone)]
+struct BudgetWindow {
+    started_at: Instant,
+    window: Duration,
+    consumed: usize,
+}
+
+impl BudgetWindow {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.started_at) >= self.window
+    }
+}
+
+#[derive(Default)]
+struct Budgets {
+    windows: HashMap<String, BudgetWindow>,
+    cleaned_at: Option<Instant>,
+}
+
+/// Memory-based storage for the complexity budgets.
+///
+/// The window of a budget starts with the first request consuming it. The
+/// expired windows are removed at most once per window, so the budgets of
+/// the clients that stopped sending requests don't accumulate.
+#[derive(Clone, Default)]
+pub struct MemoryBudgetStorage(Arc<Mutex<Budgets>>);
+
+impl MemoryBudgetStorage {
+    /// Creates a memory-based storage for the complexity budgets.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl BudgetStorage for MemoryBudgetStorage {
+    async fn consume(&self, key: &str, cost: usize, limit: usize, window: Duration) -> BudgetUsage {
+        let mut budgets = self.0.lock().unwrap();
+        let now = Instant::now();
+        if budgets
+            .cleaned_at
+            .map_or(true, |cleaned_at| now.duration_since(cleaned_at) >= window)
+        {
+            budgets.windows.retain(|_, budget| !budget.is_expired(now));
+            budgets.cleaned_at = Some(now);
+        }
+
+        let new_window = BudgetWindow {
+            started_at: now,
+            window,
+            consumed: 0,
+        };
+        let budget = budgets.windows.entry(key.to_string()).or_insert(new_window);
+        if budget.is_expired(now) {
+            *budget = new_window;
+        }
+
+        let remaining = limit.saturating_sub(budget.consumed);
+        let consumed = cost <= remaining;
+        if consumed {
+            budget.consumed += cost;
+        }
+        BudgetUsage {
+            consumed,
+            remaining: limit.saturating_sub(budget.consumed),
+            reset_in: window.saturating_sub(now.duration_since(budget.started_at)),
+        }
+    }
+}
+
+/// Complexity budget extension
+///
+/// Each request consumes its complexity from the budget of its
+/// [`ClientIdentity`], such as the API key of the client, which is reset
+/// every time window, and is rejected if the remaining budget is not enough.
+/// This limits the clients by the cost of their queries rather than by their
+/// number of requests. The requests without a [`ClientIdentity`] are not
+/// limited.
+///
+/// The `complexityBudget` field of the response extensions contains the
+/// `limit`, the `cost` of the request, the `remaining` budget and the number
+/// of seconds until it is reset in `resetIn`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_graphql::{
+///     extensions::{ClientIdentity, ComplexityBudget},
+///     *,
+/// };
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(ComplexityBudget::new(5000, Duration::from_secs(3600)))
+///     .finish();
+/// let resp = schema
+///     .execute(Request::new("{ value }").data(ClientIdentity("api-key-1".to_string())))
+///     .await;
+/// let budget = resp.extensions["complexityBudget"].clone().into_json().unwrap();
+/// assert_eq!(budget["remaining"], 4999);
+/// # });
+/// ```
+pub struct ComplexityBudget {
+    storage: Arc<dyn BudgetStorage>,
+    limit: usize,
+    window: Duration,
+}
+
+impl ComplexityBudget {
+    /// Creates a complexity budget extension allowing the complexity `limit`
+    /// to be consumed every `window` by each client, with a
+    /// [`MemoryBudgetStorage`].
+    pub fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            storage: Arc::new(MemoryBudgetStorage::new()),
+            limit,
+            window,
+        }
+    }
+
+    /// Store the budgets in `storage`.
+    #[must_use]
+    pub fn storage(self, storage: impl BudgetStorage) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            ..self
+        }
+    }
+}
+
+impl ExtensionFactory for ComplexityBudget {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ComplexityBudgetExtension {
+            storage: self.storage.clone(),
+            limit: self.limit,
+            window: self.window,
+            usage: Mutex::new(None),
+        })
+    }
+}
+
+struct ComplexityBudgetExtension {
+    storage: Arc<dyn BudgetStorage>,
+    limit: usize,
+    window: Duration,
+    usage: Mutex<Option<(usize, BudgetUsage)>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for ComplexityBudgetExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let resp = next.run(ctx).await;
+        let usage = self.usage.lock().unwrap().take();
+        match usage {
+            Some((cost, usage)) => resp.extension(
+                "complexityBudget",
+                value!({
+                    "limit": self.limit,
+                    "cost": cost,
+                    "remaining": usage.remaining,
+                    "resetIn": reset_in_secs(usage.reset_in),
+                }),
+            ),
+            None => resp,
+        }
+    }
+
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let res = next.run(ctx).await?;
+        let client = match ctx.data_opt::<ClientIdentity>() {
+            Some(client) => client,
+            None => return Ok(res),
+        };
+
+        let usage = self
+            .storage
+            .consume(&client.0, res.complexity, self.limit, self.window)
+            .await;
+        *self.usage.lock().unwrap() = Some((res.complexity, usage));
+        if !usage.consumed {
+            return Err(vec![ServerError::new(
+                format!(
+                    "Query complexity budget exceeded, the cost of the query is {} but the remaining budget is {}, which is reset in {} seconds.",
+                    res.complexity,
+                    usage.remaining,
+                    reset_in_secs(usage.reset_in)
+                ),
+                None,
+            )]);
+        }
+        Ok(res)
+    }
+}
+
+fn reset_in_secs(reset_in: Duration) -> u64 {
+    // rounded up, so that the budget is reset when the client retries
+    reset_in.as_secs() + u64::from(reset_in.subsec_nanos() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        extensions::{BudgetStorage, ClientIdentity, ComplexityBudget, MemoryBudgetStorage},
+        *,
+    };
+
+    struct Query;
+
+    #[derive(SimpleObject, Copy, Clone)]
+    #[graphql(internal)]
+    struct MyObj {
+        value: i32,
+    }
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+
+        #[graphql(complexity = "count * child_complexity")]
+        async fn objs(&self, count: usize) -> Vec<MyObj> {
+            vec![MyObj { value: 1 }; count]
+        }
+    }
+
+    fn remaining(resp: &Response) -> u64 {
+        let budget = resp.extensions["complexityBudget"]
+            .clone()
+            .into_json()
+            .unwrap();
+        budget["remaining"].as_u64().unwrap()
+    }
+
+    #[tokio::test]
+    async fn complexity_budget() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(ComplexityBudget::new(10, Duration::from_secs(60)))
+            .finish();
+        let request = |query: &str, client: &str| {
+            Request::new(query).data(ClientIdentity(client.to_string()))
+        };
+
+        let resp = schema
+            .execute(request("{ objs(count: 8) { value } }", "a"))
+            .await;
+        assert!(resp.errors.is_empty());
+        assert_eq!(
+            resp.extensions["complexityBudget"],
+            value!({
+                "limit": 10,
+                "cost": 8,
+                "remaining": 2,
+                "resetIn": 60,
+            })
+        );
+
+        let resp = schema
+            .execute(request("{ objs(count: 3) { value } }", "a"))
+            .await;
+        assert_eq!(resp.data, Value::Null);
+        assert_eq!(
+            resp.errors[0].message,
+            "Query complexity budget exceeded, the cost of the query is 3 but the remaining budget is 2, which is reset in 60 seconds."
+        );
+        assert_eq!(remaining(&resp), 2);
+
+        let resp = schema.execute(request("{ value }", "a")).await;
+        assert!(resp.errors.is_empty());
+        assert_eq!(remaining(&resp), 1);
+
+        // each client has its own budget
+        let resp = schema
+            .execute(request("{ objs(count: 8) { value } }", "b"))
+            .await;
+        assert!(resp.errors.is_empty());
+
+        // the requests without a client identity are not limited
+        let resp = schema.execute("{ objs(count: 20) { value } }").await;
+        assert!(resp.errors.is_empty());
+        assert!(!resp.extensions.contains_key("complexityBudget"));
+    }
+
+    #[tokio::test]
+    async fn memory_budget_storage_reset() {
+        let storage = MemoryBudgetStorage::new();
+        let window = Duration::from_millis(50);
+
+        let usage = storage.consume("a", 8, 10, window).await;
+        assert!(usage.consumed);
+        assert_eq!(usage.remaining, 2);
+        assert!(!storage.consume("a", 3, 10, window).await.consumed);
+
+        tokio::time::sleep(window).await;
+        let usage = storage.consume("a", 3, 10, window).await;
+        assert!(usage.consumed);
+        assert_eq!(usage.remaining, 7);
+    }
+
+    #[tokio::test]
+    async fn memory_budget_storage_remove_expired() {
+        let storage = MemoryBudgetStorage::new();
+        let window = Duration::from_millis(50);
+
+        storage.consume("a", 1, 10, window).await;
+        storage.consume("b", 1, 10, window).await;
+        assert_eq!(storage.0.lock().unwrap().windows.len(), 2);
+
+        // the windows of the clients which stopped sending requests are removed
+        tokio::time::sleep(window).await;
+        storage.consume("c", 1, 10, window).await;
+        let budgets = storage.0.lock().unwrap();
+        assert_eq!(
+            budgets
+                .windows
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+            vec!["c"]
+        );
+    }
+}
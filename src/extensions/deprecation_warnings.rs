@@ -0,0 +1,226 @@
+use std::sync::{Arc, Mutex};
+
+use indexmap::IndexMap;
+
+use crate::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextRequest, SchemaUsage},
+    parser::types::{ExecutableDocument, OperationDefinition},
+    registry::{Deprecation, MetaType, Registry},
+    validation::collect_schema_usage,
+    Name, Positioned, Response, ServerResult, Value, Variables,
+};
+
+/// Deprecation warnings extension
+///
+/// This extension appends a warning to the `warnings` field of the response
+/// extensions for each deprecated field and enum value selected by the
+/// request, including those selected through fragments or passed in the
+/// variables, so that the client teams notice them before they are removed.
+/// The warnings do not fail the request.
+///
+/// Each warning contains a `message`, the schema `coordinate` of the deprecated
+/// field or enum value, such as `User.login`, and the deprecation `reason`, if
+/// any.
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql::{extensions::DeprecationWarnings, *};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     #[graphql(deprecation = "Use `value`.")]
+///     async fn old_value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(DeprecationWarnings)
+///     .finish();
+/// let resp = schema.execute("{ oldValue }").await;
+/// assert_eq!(
+///     resp.extensions["warnings"],
+///     value!([{
+///         "message": "The field \"Query.oldValue\" is deprecated: Use `value`.",
+///         "coordinate": "Query.oldValue",
+///         "reason": "Use `value`.",
+///     }])
+/// );
+/// # });
+/// ```
+pub struct DeprecationWarnings;
+
+impl ExtensionFactory for DeprecationWarnings {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(DeprecationWarningsExtension::default())
+    }
+}
+
+#[derive(Default)]
+struct DeprecationWarningsExtension {
+    warnings: Mutex<Vec<Value>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for DeprecationWarningsExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let mut resp = next.run(ctx).await;
+        let warnings = std::mem::take(&mut *self.warnings.lock().unwrap());
+        if !warnings.is_empty() {
+            match resp.extensions.get_mut("warnings") {
+                // keep the warnings of the other extensions
+                Some(Value::List(items)) => items.extend(warnings),
+                _ => resp = resp.extension("warnings", Value::List(warnings)),
+            }
+        }
+        resp
+    }
+
+    async fn validation_did_finish(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        document: &ExecutableDocument,
+        _operation_name: Option<&str>,
+        operation: &Positioned<OperationDefinition>,
+        variables: &Variables,
+    ) -> ServerResult<()> {
+        let registry = &ctx.schema_env.registry;
+        let mut usage = SchemaUsage::default();
+        collect_schema_usage(registry, document, Some(variables), operation, &mut usage);
+
+        let mut warnings = self.warnings.lock().unwrap();
+        for coordinate in &usage.fields {
+            if let Some(deprecation) = field_deprecation(registry, coordinate) {
+                warnings.push(warning("field", coordinate, deprecation));
+            }
+        }
+        for coordinate in &usage.enum_values {
+            if let Some(deprecation) = enum_value_deprecation(registry, coordinate) {
+                warnings.push(warning("enum value", coordinate, deprecation));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the deprecation of the field `Type.field`, `None` if it is not
+/// deprecated.
+fn field_deprecation<'a>(registry: &'a Registry, coordinate: &str) -> Option<&'a Deprecation> {
+    let (type_name, field_name) = coordinate.split_once('.')?;
+    let field = registry.types.get(type_name)?.field_by_name(field_name)?;
+    Some(&field.deprecation).filter(|deprecation| deprecation.is_deprecated())
+}
+
+/// Returns the deprecation of the enum value `Enum.VALUE`, `None` if it is not
+/// deprecated.
+fn enum_value_deprecation<'a>(registry: &'a Registry, coordinate: &str) -> Option<&'a Deprecation> {
+    let (type_name, value_name) = coordinate.split_once('.')?;
+    match registry.types.get(type_name)? {
+        MetaType::Enum { enum_values, .. } => Some(&enum_values.get(value_name)?.deprecation)
+            .filter(|deprecation| deprecation.is_deprecated()),
+        _ => None,
+    }
+}
+
+fn warning(kind: &str, coordinate: &str, deprecation: &Deprecation) -> Value {
+    let message = match deprecation.reason() {
+        Some(reason) => format!("The {} \"{}\" is deprecated: {}", kind, coordinate, reason),
+        None => format!("The {} \"{}\" is deprecated.", kind, coordinate),
+    };
+    let mut warning = IndexMap::new();
+    warning.insert(Name::new("message"), Value::String(message));
+    warning.insert(
+        Name::new("coordinate"),
+        Value::String(coordinate.to_string()),
+    );
+    if let Some(reason) = deprecation.reason() {
+        warning.insert(Name::new("reason"), Value::String(reason.to_string()));
+    }
+    Value::Object(warning)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{extensions::DeprecationWarnings, *};
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(internal)]
+    enum Role {
+        Admin,
+        #[graphql(deprecation)]
+        Guest,
+        User,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(internal)]
+    struct User {
+        name: String,
+        #[graphql(deprecation = "Use `name`.")]
+        login: String,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn user(&self, role: Option<Role>) -> User {
+            let _ = role;
+            User {
+                name: "a".to_string(),
+                login: "a".to_string(),
+            }
+        }
+
+        async fn users(&self, roles: Vec<Role>) -> Vec<User> {
+            let _ = roles;
+            Vec::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn deprecation_warnings() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(DeprecationWarnings)
+            .finish();
+
+        let query = r#"
+            query($roles: [Role!]!) {
+                user(role: ADMIN) { ...UserFields }
+                users(roles: $roles) { name }
+            }
+
+            fragment UserFields on User { name login }
+        "#;
+        let resp = schema
+            .execute(
+                Request::new(query).variables(Variables::from_json(serde_json::json!({
+                    "roles": ["USER", "GUEST"],
+                }))),
+            )
+            .await;
+        assert!(resp.errors.is_empty());
+        assert_eq!(
+            resp.extensions["warnings"],
+            value!([
+                {
+                    "message": "The field \"User.login\" is deprecated: Use `name`.",
+                    "coordinate": "User.login",
+                    "reason": "Use `name`.",
+                },
+                {
+                    "message": "The enum value \"Role.GUEST\" is deprecated.",
+                    "coordinate": "Role.GUEST",
+                },
+            ])
+        );
+
+        let resp = schema.execute("{ user(role: USER) { name } }").await;
+        assert!(resp.errors.is_empty());
+        assert!(!resp.extensions.contains_key("warnings"));
+    }
+}
@@ -0,0 +1,448 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use futures_util::lock::Mutex;
+
+#[cfg(feature = "dataloader")]
+use crate::QueryPathNode;
+use crate::{
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextPrepareRequest,
+        NextResolve, ResolveInfo,
+    },
+    value, Name, PathSegment, Request, Response, ServerResult, Value,
+};
+
+/// Enables the [`Explain`] extension for a request.
+///
+/// Insert it into the data of requests that should be explained, for example
+/// when a debug header is present:
+///
+/// ```
+/// use async_graphql::{extensions::ExplainEnabled, Request};
+///
+/// let debug_header_present = true;
+/// let mut request = Request::new("{ value }");
+/// if debug_header_present {
+///     request = request.data(ExplainEnabled);
+/// }
+/// ```
+pub struct ExplainEnabled;
+
+/// Explain extension
+///
+/// This extension records how a request was actually resolved, like
+/// `EXPLAIN ANALYZE` for GraphQL, and outputs it in the `explain` field of
+/// the response extensions. Only requests whose data contains
+/// [`ExplainEnabled`] are recorded.
+///
+/// The output contains the total `duration` of the execution, the maximum
+/// number of resolvers that ran concurrently, the number of
+/// `dataloaderBatches` and the tree of the resolved fields. Each node of the
+/// tree contains the `path`, `parentType`, `fieldName` and `returnType` of the
+/// field, the `order` in which it started, its `startOffset` and `duration` in
+/// nanoseconds, the number of resolvers running when it started
+/// (`concurrency`), whether it returned an `error`, its `children` and its
+/// `dataloaderLoads`.
+///
+/// The dataloader loads are recorded for the loaders called with
+/// [`Context::load_one`](crate::Context::load_one) and
+/// [`Context::load_many`](crate::Context::load_many). Each load contains the
+/// type of the `loader`, the number of `keys` and whether it started a new
+/// batch (`newBatch`).
+pub struct Explain;
+
+impl ExtensionFactory for Explain {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ExplainExtension {
+            start_time: Mutex::new(Instant::now()),
+            started: AtomicUsize::new(0),
+            running: AtomicUsize::new(0),
+            nodes: Default::default(),
+            loads: Default::default(),
+        })
+    }
+}
+
+struct ResolveNode {
+    path: Vec<PathSegment>,
+    field_name: String,
+    parent_type: String,
+    return_type: String,
+    order: usize,
+    start_offset: Duration,
+    duration: Duration,
+    concurrency: usize,
+    error: bool,
+}
+
+struct ExplainExtension {
+    start_time: Mutex<Instant>,
+    started: AtomicUsize,
+    running: AtomicUsize,
+    nodes: Mutex<Vec<ResolveNode>>,
+    loads: ExplainLoads,
+}
+
+#[cfg_attr(not(feature = "dataloader"), allow(dead_code))]
+struct LoadNode {
+    path: Vec<PathSegment>,
+    loader: &'static str,
+    keys: usize,
+    new_batch: bool,
+}
+
+/// The dataloader loads of a request recorded by the [`Explain`] extension,
+/// which is added to the data of the explained requests.
+#[derive(Clone, Default)]
+pub(crate) struct ExplainLoads(Arc<std::sync::Mutex<Vec<LoadNode>>>);
+
+impl ExplainLoads {
+    #[cfg(feature = "dataloader")]
+    pub(crate) fn record<L>(&self, path: Option<&QueryPathNode>, keys: usize, new_batch: bool) {
+        self.0.lock().unwrap().push(LoadNode {
+            path: path.map(|path| path.to_path()).unwrap_or_default(),
+            loader: std::any::type_name::<L>(),
+            keys,
+            new_batch,
+        });
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for ExplainExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let mut request = next.run(ctx, request).await?;
+        if ctx.data_opt::<ExplainEnabled>().is_some() {
+            request.data.insert(self.loads.clone());
+        }
+        Ok(request)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        if ctx.data_opt::<ExplainEnabled>().is_none() {
+            return next.run(ctx, operation_name).await;
+        }
+
+        let start_time = Instant::now();
+        *self.start_time.lock().await = start_time;
+        let resp = next.run(ctx, operation_name).await;
+        let duration = start_time.elapsed();
+
+        let mut nodes = std::mem::take(&mut *self.nodes.lock().await);
+        nodes.sort_by_key(|node| node.order);
+        let loads = std::mem::take(&mut *self.loads.0.lock().unwrap());
+        let dataloader_batches = loads.iter().filter(|load| load.new_batch).count();
+        let max_concurrency = nodes
+            .iter()
+            .map(|node| node.concurrency)
+            .max()
+            .unwrap_or_default();
+        resp.extension(
+            "explain",
+            value!({
+                "duration": duration.as_nanos() as u64,
+                "maxConcurrency": max_concurrency,
+                "dataloaderBatches": dataloader_batches,
+                "resolvers": build_tree(nodes, loads),
+            }),
+        )
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if info.is_for_introspection || ctx.data_opt::<ExplainEnabled>().is_none() {
+            return next.run(ctx, info).await;
+        }
+
+        let path = info.path_node.to_path();
+        let field_name = info.path_node.field_name().to_string();
+        let parent_type = info.parent_type.to_string();
+        let return_type = info.return_type.to_string();
+
+        let start_time = Instant::now();
+        let start_offset = start_time.saturating_duration_since(*self.start_time.lock().await);
+        let order = self.started.fetch_add(1, Ordering::SeqCst);
+        let concurrency = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        let res = next.run(ctx, info).await;
+        self.running.fetch_sub(1, Ordering::SeqCst);
+        let duration = start_time.elapsed();
+
+        self.nodes.lock().await.push(ResolveNode {
+            path,
+            field_name,
+            parent_type,
+            return_type,
+            order,
+            start_offset,
+            duration,
+            concurrency,
+            error: res.is_err(),
+        });
+        res
+    }
+}
+
+/// Nests each node under the closest node whose path is a prefix of its own,
+/// and adds the loads to the node of their field.
+fn build_tree(nodes: Vec<ResolveNode>, loads: Vec<LoadNode>) -> Value {
+    let index = nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| (node.path.clone(), idx))
+        .collect::<HashMap<_, _>>();
+    let mut children = vec![Vec::new(); nodes.len()];
+    let mut node_loads = (0..nodes.len()).map(|_| Vec::new()).collect::<Vec<_>>();
+    let mut roots = Vec::new();
+
+    for load in loads {
+        if let Some(idx) = index.get(&load.path) {
+            node_loads[*idx].push(value!({
+                "loader": load.loader,
+                "keys": load.keys,
+                "newBatch": load.new_batch,
+            }));
+        }
+    }
+
+    for (idx, node) in nodes.iter().enumerate() {
+        let parent = (1..node.path.len())
+            .rev()
+            .find_map(|len| index.get(&node.path[..len]));
+        match parent {
+            Some(parent) => children[*parent].push(idx),
+            None => roots.push(idx),
+        }
+    }
+
+    fn to_value(
+        nodes: &[ResolveNode],
+        children: &[Vec<usize>],
+        loads: &mut [Vec<Value>],
+        idx: usize,
+    ) -> Value {
+        let node = &nodes[idx];
+        let path = node
+            .path
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Field(name) => Value::from(name.as_str()),
+                PathSegment::Index(idx) => Value::from(*idx),
+            })
+            .collect::<Vec<_>>();
+        let mut map = value!({
+            "path": path,
+            "parentType": node.parent_type.clone(),
+            "fieldName": node.field_name.clone(),
+            "returnType": node.return_type.clone(),
+            "order": node.order,
+            "startOffset": node.start_offset.as_nanos() as u64,
+            "duration": node.duration.as_nanos() as u64,
+            "concurrency": node.concurrency,
+            "error": node.error,
+        });
+        if let Value::Object(map) = &mut map {
+            map.insert(
+                Name::new("children"),
+                Value::List(
+                    children[idx]
+                        .iter()
+                        .map(|child| to_value(nodes, children, loads, *child))
+                        .collect(),
+                ),
+            );
+            map.insert(
+                Name::new("dataloaderLoads"),
+                Value::List(std::mem::take(&mut loads[idx])),
+            );
+        }
+        map
+    }
+
+    Value::List(
+        roots
+            .into_iter()
+            .map(|idx| to_value(&nodes, &children, &mut node_loads, idx))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    struct Query;
+
+    #[derive(SimpleObject)]
+    #[graphql(internal)]
+    struct MyObj {
+        value: i32,
+    }
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+
+        async fn objs(&self) -> Vec<MyObj> {
+            vec![MyObj { value: 1 }, MyObj { value: 2 }]
+        }
+    }
+
+    #[tokio::test]
+    async fn explain() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(extensions::Explain)
+            .finish();
+
+        let resp = schema.execute("{ value }").await.into_result().unwrap();
+        assert!(resp.extensions.get("explain").is_none());
+
+        let mut resp = schema
+            .execute(Request::new("{ value objs { value } }").data(extensions::ExplainEnabled))
+            .await
+            .into_result()
+            .unwrap();
+        let explain = resp
+            .extensions
+            .remove("explain")
+            .unwrap()
+            .into_json()
+            .unwrap();
+
+        let resolvers = explain["resolvers"].as_array().unwrap();
+        let summary = resolvers
+            .iter()
+            .map(|node| {
+                (
+                    node["fieldName"].as_str().unwrap(),
+                    node["children"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|child| child["path"].clone())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("value", vec![]),
+                (
+                    "objs",
+                    vec![
+                        serde_json::json!(["objs", 0]),
+                        serde_json::json!(["objs", 1]),
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(
+            resolvers[1]["children"][0]["children"][0]["path"],
+            serde_json::json!(["objs", 0, "value"])
+        );
+        assert_eq!(resolvers[1]["returnType"], "[MyObj!]!");
+        assert_eq!(resolvers[1]["error"], false);
+        assert!(explain["maxConcurrency"].as_u64().unwrap() >= 1);
+        assert_eq!(explain["dataloaderBatches"], 0);
+    }
+
+    #[cfg(feature = "dataloader")]
+    #[tokio::test]
+    async fn explain_dataloader() {
+        use std::collections::HashMap;
+
+        use crate::dataloader::{DataLoader, Loader, SingleKeyLoader};
+
+        struct MyLoader;
+
+        impl SingleKeyLoader for MyLoader {
+            type Key = i32;
+        }
+
+        #[async_trait::async_trait]
+        impl Loader<i32> for MyLoader {
+            type Value = i32;
+            type Error = Error;
+
+            async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, i32>, Self::Error> {
+                Ok(keys.iter().map(|key| (*key, *key * 10)).collect())
+            }
+        }
+
+        struct Query;
+
+        #[derive(SimpleObject)]
+        #[graphql(internal, complex)]
+        struct Item {
+            id: i32,
+        }
+
+        #[ComplexObject(internal)]
+        impl Item {
+            async fn value(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+                ctx.load_one::<MyLoader>(self.id).await
+            }
+        }
+
+        #[Object(internal)]
+        impl Query {
+            async fn items(&self) -> Vec<Item> {
+                vec![Item { id: 1 }, Item { id: 2 }]
+            }
+        }
+
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(extensions::Explain)
+            .data(DataLoader::new(MyLoader, tokio::spawn))
+            .finish();
+
+        let mut resp = schema
+            .execute(Request::new("{ items { value } }").data(extensions::ExplainEnabled))
+            .await
+            .into_result()
+            .unwrap();
+        let explain = resp
+            .extensions
+            .remove("explain")
+            .unwrap()
+            .into_json()
+            .unwrap();
+
+        assert_eq!(explain["dataloaderBatches"], 1);
+        let items = &explain["resolvers"][0]["children"];
+        let loads = (0..2)
+            .map(|idx| {
+                let value = &items[idx]["children"][0];
+                assert_eq!(value["path"], serde_json::json!(["items", idx, "value"]));
+                let load = &value["dataloaderLoads"][0];
+                assert!(load["loader"].as_str().unwrap().ends_with("MyLoader"));
+                assert_eq!(load["keys"], 1);
+                load["newBatch"].as_bool().unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(loads.iter().filter(|new_batch| **new_batch).count(), 1);
+    }
+}
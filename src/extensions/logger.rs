@@ -1,9 +1,11 @@
 use std::{fmt::Write, sync::Arc};
 
 use crate::{
-    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery},
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, RequestId,
+    },
     parser::types::{ExecutableDocument, OperationType, Selection},
-    PathSegment, Response, ServerResult, Variables,
+    ClientInfo, PathSegment, Response, ServerResult, Variables,
 };
 
 /// Logger extension
@@ -18,6 +20,20 @@ impl ExtensionFactory for Logger {
 
 struct LoggerExtension;
 
+fn request_fields(ctx: &ExtensionContext<'_>) -> String {
+    let mut output = match ctx.data_opt::<RequestId>() {
+        Some(request_id) => format!(" request_id={}", request_id.0),
+        None => String::new(),
+    };
+    if let Some(client_info) = ctx.data_opt::<ClientInfo>() {
+        let _ = write!(&mut output, " client={}", client_info.name);
+        if let Some(version) = &client_info.version {
+            let _ = write!(&mut output, " client_version={}", version);
+        }
+    }
+    output
+}
+
 #[async_trait::async_trait]
 impl Extension for LoggerExtension {
     async fn parse_query(
@@ -36,7 +52,7 @@ impl Extension for LoggerExtension {
         if !is_schema {
             log::info!(
                 target: "async-graphql",
-                "[Execute] {}", ctx.stringify_execute_doc(&document, variables)
+                "[Execute]{} {}", request_fields(ctx), ctx.stringify_execute_doc(&document, variables)
             );
         }
         Ok(document)
@@ -69,12 +85,12 @@ impl Extension for LoggerExtension {
 
                     log::info!(
                         target: "async-graphql",
-                        "[Error] path={} message={}", path, err.message,
+                        "[Error]{} path={} message={}", request_fields(ctx), path, err.message,
                     );
                 } else {
                     log::info!(
                         target: "async-graphql",
-                        "[Error] message={}", err.message,
+                        "[Error]{} message={}", request_fields(ctx), err.message,
                     );
                 }
             }
@@ -5,10 +5,21 @@ mod analyzer;
 pub mod apollo_persisted_queries;
 #[cfg(feature = "apollo_tracing")]
 mod apollo_tracing;
+mod audit_log;
+mod cache_storage;
+mod complexity_budget;
+mod deprecation_warnings;
+mod explain;
 #[cfg(feature = "log")]
 mod logger;
 #[cfg(feature = "opentelemetry")]
 mod opentelemetry;
+#[cfg(feature = "opentelemetry")]
+mod opentelemetry_metrics;
+mod request_id;
+mod schema_usage;
+#[cfg(feature = "tracing")]
+mod structured_logger;
 #[cfg(feature = "tracing")]
 mod tracing;
 
@@ -23,15 +34,34 @@ use futures_util::stream::BoxStream;
 pub use self::analyzer::Analyzer;
 #[cfg(feature = "apollo_tracing")]
 pub use self::apollo_tracing::ApolloTracing;
+pub use self::audit_log::{
+    AuditLog, AuditOutcome, AuditRecord, AuditSink, ClientIdentity, StdoutAuditSink,
+};
+pub use self::cache_storage::CacheStorage;
+pub use self::complexity_budget::{
+    BudgetStorage, BudgetUsage, ComplexityBudget, MemoryBudgetStorage,
+};
+pub use self::deprecation_warnings::DeprecationWarnings;
+#[cfg(feature = "dataloader")]
+pub(crate) use self::explain::ExplainLoads;
+pub use self::explain::{Explain, ExplainEnabled};
 #[cfg(feature = "log")]
 pub use self::logger::Logger;
 #[cfg(feature = "opentelemetry")]
 pub use self::opentelemetry::OpenTelemetry;
+#[cfg(feature = "opentelemetry")]
+pub use self::opentelemetry_metrics::OpenTelemetryMetrics;
+pub use self::request_id::{RequestId, RequestIdPropagation};
+pub use self::schema_usage::{SchemaUsage, SchemaUsageReporter};
+#[cfg(feature = "tracing")]
+pub use self::structured_logger::StructuredLogger;
 #[cfg(feature = "tracing")]
-pub use self::tracing::Tracing;
+pub use self::tracing::{Tracing, TracingConfig};
 use crate::{
-    parser::types::ExecutableDocument, Data, DataContext, Error, QueryPathNode, Request, Response,
-    Result, SchemaEnv, ServerError, ServerResult, ValidationResult, Value, Variables,
+    meta::SchemaMeta,
+    parser::types::{ExecutableDocument, OperationDefinition},
+    Data, DataContext, Error, Positioned, QueryPathNode, Request, Response, Result, SchemaEnv,
+    ServerError, ServerResult, ValidationResult, Value, Variables,
 };
 
 /// Context for extension
@@ -71,6 +101,20 @@ impl<'a> ExtensionContext<'a> {
             .unwrap_or_default()
     }
 
+    /// Returns a copy of the variables where the values passed to secret
+    /// arguments or secret input fields are replaced with `"<secret>"`.
+    ///
+    /// Usually used together with [`stringify_execute_doc`](Self::stringify_execute_doc)
+    /// to log or trace the variables of a request.
+    pub fn redact_variables(&self, doc: &ExecutableDocument, variables: &Variables) -> Variables {
+        self.schema_env.registry.redact_variables(variables, doc)
+    }
+
+    /// Returns a read-only view over the metadata of the schema.
+    pub fn schema_meta(&self) -> SchemaMeta<'a> {
+        SchemaMeta::new(&self.schema_env.registry)
+    }
+
     /// Gets the global data defined in the `Context` or `Schema`.
     ///
     /// If both `Schema` and `Query` have the same data type, the data in the
@@ -103,12 +147,50 @@ impl<'a> ExtensionContext<'a> {
     pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
         self.query_data
             .and_then(|query_data| query_data.get(&TypeId::of::<D>()))
-            .or_else(|| self.session_data.get(&TypeId::of::<D>()))
-            .or_else(|| self.schema_env.data.get(&TypeId::of::<D>()))
+            .or_else(|| self.session_data.get_value(&TypeId::of::<D>()))
+            .map(AsRef::as_ref)
+            .or_else(|| self.schema_env.get_data(&TypeId::of::<D>()))
             .and_then(|d| d.downcast_ref::<D>())
     }
 }
 
+/// A value of the request data captured by an extension.
+///
+/// The request data is attached to the extension context after the
+/// `prepare_request` hook, so it is not available yet in the `request` and
+/// `subscribe` hooks, nor when their spans are created. The extensions read it
+/// from the `parse_query` or the `validation` hook and capture it there.
+pub(crate) struct RequestCapture<T>(Arc<std::sync::Mutex<Option<T>>>);
+
+impl<T> Default for RequestCapture<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for RequestCapture<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Any + Clone + Send + Sync> RequestCapture<T> {
+    /// Captures the value of type `T` of the request data, if any.
+    pub(crate) fn capture(&self, ctx: &ExtensionContext<'_>) {
+        *self.0.lock().unwrap() = ctx.data_opt::<T>().cloned();
+    }
+
+    /// Returns the captured value.
+    pub(crate) fn get(&self) -> Option<T> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Takes the captured value.
+    pub(crate) fn take(&self) -> Option<T> {
+        self.0.lock().unwrap().take()
+    }
+}
+
 /// Parameters for `Extension::resolve_field_start`
 pub struct ResolveInfo<'a> {
     /// Current path node, You can go through the entire path.
@@ -369,6 +451,24 @@ pub trait Extension: Sync + Send + 'static {
         next.run(ctx).await
     }
 
+    /// Called after the query has been validated, with the validated document,
+    /// the operation that will be executed and the variables of the request.
+    ///
+    /// Allows to inspect the query without parsing it again, for example to
+    /// capture it or to apply custom static checks. Returning an error rejects
+    /// the request before it is executed.
+    #[allow(unused_variables)]
+    async fn validation_did_finish(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        document: &ExecutableDocument,
+        operation_name: Option<&str>,
+        operation: &Positioned<OperationDefinition>,
+        variables: &Variables,
+    ) -> ServerResult<()> {
+        Ok(())
+    }
+
     /// Called at execute query.
     async fn execute(
         &self,
@@ -396,6 +496,62 @@ pub trait Extension: Sync + Send + 'static {
 pub trait ExtensionFactory: Send + Sync + 'static {
     /// Create an extended instance.
     fn create(&self) -> Arc<dyn Extension>;
+
+    /// Returns `true` if the extension is enabled for the request, which is
+    /// the default.
+    ///
+    /// No instance is created for a request the extension is disabled for, so
+    /// none of its hooks are called.
+    fn enabled(&self, request: &Request) -> bool {
+        let _ = request;
+        true
+    }
+
+    #[doc(hidden)]
+    fn factory_type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+}
+
+/// An extension factory only enabled for the requests matching a predicate,
+/// added with `SchemaBuilder::extension_if`.
+pub(crate) struct ConditionalExtension<E, F> {
+    pub(crate) extension: E,
+    pub(crate) predicate: F,
+}
+
+impl<E, F> ExtensionFactory for ConditionalExtension<E, F>
+where
+    E: ExtensionFactory,
+    F: Fn(&Request) -> bool + Send + Sync + 'static,
+{
+    fn create(&self) -> Arc<dyn Extension> {
+        self.extension.create()
+    }
+
+    fn enabled(&self, request: &Request) -> bool {
+        self.extension.enabled(request) && (self.predicate)(request)
+    }
+
+    fn factory_type_id(&self) -> TypeId {
+        self.extension.factory_type_id()
+    }
+}
+
+/// Creates the extensions enabled for `request`.
+pub(crate) fn create_extensions<'a>(
+    factories: &'a [Box<dyn ExtensionFactory>],
+    request: &'a Request,
+) -> impl Iterator<Item = Arc<dyn Extension>> + 'a {
+    factories
+        .iter()
+        .filter(|factory| {
+            factory.enabled(request)
+                && !request
+                    .disabled_extensions
+                    .contains(&factory.factory_type_id())
+        })
+        .map(|factory| factory.create())
 }
 
 #[derive(Clone)]
@@ -487,6 +643,22 @@ impl Extensions {
         next.run(&self.create_context()).await
     }
 
+    pub async fn validation_did_finish(
+        &self,
+        document: &ExecutableDocument,
+        operation_name: Option<&str>,
+        operation: &Positioned<OperationDefinition>,
+        variables: &Variables,
+    ) -> ServerResult<()> {
+        let ctx = self.create_context();
+        for extension in &self.extensions {
+            extension
+                .validation_did_finish(&ctx, document, operation_name, operation, variables)
+                .await?;
+        }
+        Ok(())
+    }
+
     pub async fn execute(
         &self,
         operation_name: Option<&str>,
@@ -11,8 +11,9 @@ use opentelemetry::{
 use crate::{
     extensions::{
         Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextRequest,
-        NextResolve, NextSubscribe, NextValidation, ResolveInfo,
+        NextResolve, NextSubscribe, NextValidation, RequestId, ResolveInfo,
     },
+    registry::redact_unparsed_query,
     Response, ServerError, ServerResult, ValidationResult, Value,
 };
 
@@ -23,6 +24,7 @@ const KEY_RETURN_TYPE: Key = Key::from_static_str("graphql.returnType");
 const KEY_ERROR: Key = Key::from_static_str("graphql.error");
 const KEY_COMPLEXITY: Key = Key::from_static_str("graphql.complexity");
 const KEY_DEPTH: Key = Key::from_static_str("graphql.depth");
+const KEY_REQUEST_ID: Key = Key::from_static_str("graphql.requestId");
 
 /// OpenTelemetry extension
 #[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
@@ -100,23 +102,34 @@ where
         variables: &Variables,
         next: NextParseQuery<'_>,
     ) -> ServerResult<ExecutableDocument> {
-        let attributes = vec![
-            KEY_SOURCE.string(query.to_string()),
-            KEY_VARIABLES.string(serde_json::to_string(variables).unwrap()),
-        ];
+        if let Some(request_id) = ctx.data_opt::<RequestId>() {
+            OpenTelemetryContext::current()
+                .span()
+                .set_attribute(KEY_REQUEST_ID.string(request_id.0.clone()));
+        }
+
+        // The source and variables are recorded after parsing, so that the secret
+        // arguments can be redacted.
         let span = self
             .tracer
             .span_builder("parse")
             .with_kind(SpanKind::Server)
-            .with_attributes(attributes)
             .start(&*self.tracer);
 
         async move {
             let res = next.run(ctx, query, variables).await;
-            if let Ok(doc) = &res {
-                OpenTelemetryContext::current()
-                    .span()
-                    .set_attribute(KEY_SOURCE.string(ctx.stringify_execute_doc(doc, variables)));
+            let span = OpenTelemetryContext::current();
+            let span = span.span();
+            match &res {
+                Ok(doc) => {
+                    span.set_attribute(
+                        KEY_SOURCE.string(ctx.stringify_execute_doc(doc, variables)),
+                    );
+                    span.set_attribute(KEY_VARIABLES.string(
+                        serde_json::to_string(&ctx.redact_variables(doc, variables)).unwrap(),
+                    ));
+                }
+                Err(_) => span.set_attribute(KEY_SOURCE.string(redact_unparsed_query(query))),
             }
             res
         }
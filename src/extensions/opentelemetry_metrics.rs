@@ -0,0 +1,454 @@
+use std::{collections::HashSet, sync::Arc, time::Instant};
+
+use futures_util::{lock::Mutex, stream::BoxStream, StreamExt};
+use opentelemetry::{
+    metrics::{Counter, Histogram, Meter, Unit, UpDownCounter},
+    Context as OpenTelemetryContext, Key,
+};
+
+use crate::{
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextRequest, NextResolve,
+        NextSubscribe, ResolveInfo,
+    },
+    ClientInfo, Response, ServerResult, Value,
+};
+
+const KEY_OPERATION_NAME: Key = Key::from_static_str("graphql.operationName");
+const KEY_PARENT_TYPE: Key = Key::from_static_str("graphql.parentType");
+const KEY_FIELD_NAME: Key = Key::from_static_str("graphql.fieldName");
+const KEY_CLIENT_NAME: Key = Key::from_static_str("graphql.clientName");
+const KEY_CLIENT_VERSION: Key = Key::from_static_str("graphql.clientVersion");
+
+struct Instruments {
+    requests: Counter<u64>,
+    errors: Counter<u64>,
+    request_duration: Histogram<f64>,
+    field_duration: Histogram<f64>,
+    active_subscriptions: UpDownCounter<i64>,
+}
+
+/// OpenTelemetry metrics extension
+///
+/// Records the following metrics with the given meter, so they are exported
+/// by the meter provider that created it:
+///
+/// - `graphql.requests`: the number of requests.
+/// - `graphql.errors`: the number of errors returned in the responses.
+/// - `graphql.request.duration`: the duration of the requests in seconds.
+/// - `graphql.field.duration`: the resolution time of the fields in seconds.
+/// - `graphql.subscriptions.active`: the number of running subscriptions.
+///
+/// The request metrics have a `graphql.operationName` attribute when the
+/// request specifies an operation name, and a `graphql.clientName` attribute
+/// when the request has a [`ClientInfo`]. To bound the number of attribute
+/// values, the client names which are not allowed by
+/// [`allowed_clients`](OpenTelemetryMetrics::allowed_clients) are recorded as
+/// `other`, and only the allowed clients have a `graphql.clientVersion`
+/// attribute. The field durations have the `graphql.parentType` and
+/// `graphql.fieldName` attributes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::{extensions::OpenTelemetryMetrics, *};
+/// use opentelemetry::global;
+///
+/// #[derive(SimpleObject)]
+/// struct Query {
+///     value: i32,
+/// }
+///
+/// let schema = Schema::build(Query { value: 100 }, EmptyMutation, EmptySubscription)
+///     .extension(OpenTelemetryMetrics::new(global::meter("async-graphql")))
+///     .finish();
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+pub struct OpenTelemetryMetrics {
+    instruments: Arc<Instruments>,
+    allowed_clients: Arc<HashSet<String>>,
+}
+
+impl OpenTelemetryMetrics {
+    /// Use `meter` to create an OpenTelemetry metrics extension.
+    pub fn new(meter: Meter) -> OpenTelemetryMetrics {
+        Self {
+            instruments: Arc::new(Instruments {
+                requests: meter
+                    .u64_counter("graphql.requests")
+                    .with_description("The number of GraphQL requests")
+                    .init(),
+                errors: meter
+                    .u64_counter("graphql.errors")
+                    .with_description("The number of errors returned in the GraphQL responses")
+                    .init(),
+                request_duration: meter
+                    .f64_histogram("graphql.request.duration")
+                    .with_description("The duration of the GraphQL requests")
+                    .with_unit(Unit::new("s"))
+                    .init(),
+                field_duration: meter
+                    .f64_histogram("graphql.field.duration")
+                    .with_description("The resolution time of the GraphQL fields")
+                    .with_unit(Unit::new("s"))
+                    .init(),
+                active_subscriptions: meter
+                    .i64_up_down_counter("graphql.subscriptions.active")
+                    .with_description("The number of running GraphQL subscriptions")
+                    .init(),
+            }),
+            allowed_clients: Default::default(),
+        }
+    }
+
+    /// Record the names and versions of these clients, the other clients are
+    /// recorded as `other`.
+    #[must_use]
+    pub fn allowed_clients<I, T>(self, names: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self {
+            allowed_clients: Arc::new(names.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+}
+
+impl ExtensionFactory for OpenTelemetryMetrics {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(OpenTelemetryMetricsExtension {
+            instruments: self.instruments.clone(),
+            allowed_clients: self.allowed_clients.clone(),
+            operation_name: Default::default(),
+            client_info: Default::default(),
+        })
+    }
+}
+
+struct OpenTelemetryMetricsExtension {
+    instruments: Arc<Instruments>,
+    allowed_clients: Arc<HashSet<String>>,
+    operation_name: Mutex<Option<String>>,
+    client_info: Mutex<Option<ClientInfo>>,
+}
+
+/// Decrements the active subscriptions when the subscription stream is
+/// dropped.
+struct ActiveSubscription(Arc<Instruments>);
+
+impl Drop for ActiveSubscription {
+    fn drop(&mut self) {
+        self.0
+            .active_subscriptions
+            .add(&OpenTelemetryContext::current(), -1, &[]);
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for OpenTelemetryMetricsExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let start_time = Instant::now();
+        let resp = next.run(ctx).await;
+        let duration = start_time.elapsed();
+
+        let cx = OpenTelemetryContext::current();
+        let mut attributes = match self.operation_name.lock().await.take() {
+            Some(operation_name) => vec![KEY_OPERATION_NAME.string(operation_name)],
+            None => Vec::new(),
+        };
+        if let Some(client_info) = self.client_info.lock().await.take() {
+            if self.allowed_clients.contains(&client_info.name) {
+                attributes.push(KEY_CLIENT_NAME.string(client_info.name));
+                if let Some(version) = client_info.version {
+                    attributes.push(KEY_CLIENT_VERSION.string(version));
+                }
+            } else {
+                attributes.push(KEY_CLIENT_NAME.string("other"));
+            }
+        }
+        self.instruments.requests.add(&cx, 1, &attributes);
+        if !resp.errors.is_empty() {
+            self.instruments
+                .errors
+                .add(&cx, resp.errors.len() as u64, &attributes);
+        }
+        self.instruments
+            .request_duration
+            .record(&cx, duration.as_secs_f64(), &attributes);
+        resp
+    }
+
+    fn subscribe<'s>(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        stream: BoxStream<'s, Response>,
+        next: NextSubscribe<'_>,
+    ) -> BoxStream<'s, Response> {
+        self.instruments
+            .active_subscriptions
+            .add(&OpenTelemetryContext::current(), 1, &[]);
+        let active_subscription = ActiveSubscription(self.instruments.clone());
+        Box::pin(next.run(ctx, stream).map(move |resp| {
+            let _ = &active_subscription;
+            resp
+        }))
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        if let Some(operation_name) = operation_name {
+            *self.operation_name.lock().await = Some(operation_name.to_string());
+        }
+        *self.client_info.lock().await = ctx.data_opt::<ClientInfo>().cloned();
+        next.run(ctx, operation_name).await
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if info.is_for_introspection {
+            return next.run(ctx, info).await;
+        }
+
+        let attributes = [
+            KEY_PARENT_TYPE.string(info.parent_type.to_string()),
+            KEY_FIELD_NAME.string(info.name.to_string()),
+        ];
+        let start_time = Instant::now();
+        let res = next.run(ctx, info).await;
+        self.instruments.field_duration.record(
+            &OpenTelemetryContext::current(),
+            start_time.elapsed().as_secs_f64(),
+            &attributes,
+        );
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+
+    use futures_util::stream::Stream;
+    use opentelemetry::{
+        metrics::{
+            InstrumentProvider, Result as MetricsResult, SyncCounter, SyncHistogram,
+            SyncUpDownCounter,
+        },
+        InstrumentationLibrary, KeyValue,
+    };
+
+    use super::*;
+    use crate::*;
+
+    type Records = Arc<StdMutex<Vec<(String, f64, Vec<KeyValue>)>>>;
+
+    #[derive(Default)]
+    struct RecordingProvider(Records);
+
+    struct Recorder {
+        name: String,
+        records: Records,
+    }
+
+    impl Recorder {
+        fn push(&self, value: f64, attributes: &[KeyValue]) {
+            self.records
+                .lock()
+                .unwrap()
+                .push((self.name.clone(), value, attributes.to_vec()));
+        }
+    }
+
+    impl SyncCounter<u64> for Recorder {
+        fn add(&self, _cx: &OpenTelemetryContext, value: u64, attributes: &[KeyValue]) {
+            self.push(value as f64, attributes);
+        }
+    }
+
+    impl SyncHistogram<f64> for Recorder {
+        fn record(&self, _cx: &OpenTelemetryContext, value: f64, attributes: &[KeyValue]) {
+            self.push(value, attributes);
+        }
+    }
+
+    impl SyncUpDownCounter<i64> for Recorder {
+        fn add(&self, _cx: &OpenTelemetryContext, value: i64, attributes: &[KeyValue]) {
+            self.push(value as f64, attributes);
+        }
+    }
+
+    impl RecordingProvider {
+        fn recorder(&self, name: String) -> Arc<Recorder> {
+            Arc::new(Recorder {
+                name,
+                records: self.0.clone(),
+            })
+        }
+    }
+
+    impl InstrumentProvider for RecordingProvider {
+        fn u64_counter(
+            &self,
+            name: String,
+            _description: Option<String>,
+            _unit: Option<Unit>,
+        ) -> MetricsResult<Counter<u64>> {
+            Ok(Counter::new(self.recorder(name)))
+        }
+
+        fn f64_histogram(
+            &self,
+            name: String,
+            _description: Option<String>,
+            _unit: Option<Unit>,
+        ) -> MetricsResult<Histogram<f64>> {
+            Ok(Histogram::new(self.recorder(name)))
+        }
+
+        fn i64_up_down_counter(
+            &self,
+            name: String,
+            _description: Option<String>,
+            _unit: Option<Unit>,
+        ) -> MetricsResult<UpDownCounter<i64>> {
+            Ok(UpDownCounter::new(self.recorder(name)))
+        }
+
+        fn register_callback(
+            &self,
+            _callback: Box<dyn Fn(&OpenTelemetryContext) + Send + Sync>,
+        ) -> MetricsResult<()> {
+            Ok(())
+        }
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        async fn error(&self) -> Result<i32> {
+            Err("not found".into())
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription(internal)]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..2)
+        }
+    }
+
+    #[tokio::test]
+    async fn opentelemetry_metrics() {
+        let records = Records::default();
+        let meter = Meter::new(
+            InstrumentationLibrary::new("async-graphql", None, None),
+            Arc::new(RecordingProvider(records.clone())),
+        );
+        let schema = Schema::build(Query, EmptyMutation, Subscription)
+            .extension(OpenTelemetryMetrics::new(meter).allowed_clients(["web"]))
+            .finish();
+
+        let take_records = || {
+            std::mem::take(&mut *records.lock().unwrap())
+                .into_iter()
+                .map(|(name, value, attributes)| {
+                    let mut attributes = attributes
+                        .into_iter()
+                        .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+                        .collect::<Vec<_>>();
+                    attributes.sort();
+                    (name, value, attributes)
+                })
+                .collect::<Vec<_>>()
+        };
+        let attr = |key: &str, value: &str| (key.to_string(), value.to_string());
+
+        schema
+            .execute(
+                Request::new("query A { value error }").data(ClientInfo::new("web").version("1.0")),
+            )
+            .await;
+        let mut records = take_records();
+        // The fields are resolved concurrently.
+        records.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.2.cmp(&b.2)));
+        let request_attributes = vec![
+            attr("graphql.clientName", "web"),
+            attr("graphql.clientVersion", "1.0"),
+            attr("graphql.operationName", "A"),
+        ];
+        assert_eq!(
+            records
+                .iter()
+                .map(|(name, value, attributes)| {
+                    let value = if name.ends_with("duration") {
+                        0.0
+                    } else {
+                        *value
+                    };
+                    (name.as_str(), value, attributes.clone())
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                ("graphql.errors", 1.0, request_attributes.clone()),
+                (
+                    "graphql.field.duration",
+                    0.0,
+                    vec![
+                        attr("graphql.fieldName", "error"),
+                        attr("graphql.parentType", "Query")
+                    ]
+                ),
+                (
+                    "graphql.field.duration",
+                    0.0,
+                    vec![
+                        attr("graphql.fieldName", "value"),
+                        attr("graphql.parentType", "Query")
+                    ]
+                ),
+                ("graphql.request.duration", 0.0, request_attributes.clone()),
+                ("graphql.requests", 1.0, request_attributes),
+            ]
+        );
+
+        schema
+            .execute(Request::new("{ value }").data(ClientInfo::new("mobile").version("2.0")))
+            .await;
+        let records = take_records();
+        assert!(records.contains(&(
+            "graphql.requests".to_string(),
+            1.0,
+            vec![attr("graphql.clientName", "other")]
+        )));
+        assert!(!records.iter().any(|(name, _, _)| name == "graphql.errors"));
+
+        let stream = schema.execute_stream("subscription { values }");
+        assert_eq!(stream.count().await, 2);
+        let records = take_records();
+        assert_eq!(
+            records
+                .iter()
+                .filter(|(name, _, _)| name == "graphql.subscriptions.active")
+                .map(|(_, value, _)| *value)
+                .collect::<Vec<_>>(),
+            vec![1.0, -1.0]
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use futures_util::{stream::BoxStream, StreamExt};
+
+use crate::{
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextParseQuery, NextRequest, NextSubscribe,
+        RequestCapture,
+    },
+    parser::types::ExecutableDocument,
+    Response, ServerResult, Value, Variables,
+};
+
+/// The ID of a request, used to correlate the responses and errors reported
+/// by the clients with the logs and traces of the server.
+///
+/// Insert it into the data of the request, usually from a header read by the
+/// HTTP layer:
+///
+/// ```
+/// use async_graphql::{extensions::RequestId, Request};
+///
+/// let request = Request::new("{ value }").data(RequestId("3f2a9c".to_string()));
+/// ```
+///
+/// The `Logger`, `Tracing` and `OpenTelemetry` extensions record it with the
+/// requests, and the [`RequestIdPropagation`] extension returns it to the
+/// client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(pub String);
+
+/// Request ID extension
+///
+/// Adds the [`RequestId`] of the request data to the `requestId` field of the
+/// response extensions and of the extensions of every error in the response.
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql::{
+///     extensions::{RequestId, RequestIdPropagation},
+///     *,
+/// };
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> Result<i32> {
+///         Err("not found".into())
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(RequestIdPropagation)
+///     .finish();
+/// let resp = schema
+///     .execute(Request::new("{ value }").data(RequestId("3f2a9c".to_string())))
+///     .await;
+/// assert_eq!(resp.extensions["requestId"], value!("3f2a9c"));
+/// assert_eq!(
+///     resp.errors[0].extensions.as_ref().unwrap().get("requestId"),
+///     Some(&value!("3f2a9c"))
+/// );
+/// # });
+/// ```
+pub struct RequestIdPropagation;
+
+impl ExtensionFactory for RequestIdPropagation {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RequestIdPropagationExtension {
+            request_id: Default::default(),
+        })
+    }
+}
+
+struct RequestIdPropagationExtension {
+    request_id: RequestCapture<RequestId>,
+}
+
+fn attach_request_id(mut resp: Response, request_id: Option<&RequestId>) -> Response {
+    let request_id = match request_id {
+        Some(request_id) => request_id,
+        None => return resp,
+    };
+    for err in &mut resp.errors {
+        err.extensions
+            .get_or_insert_with(Default::default)
+            .set("requestId", request_id.0.clone());
+    }
+    resp.extension("requestId", Value::from(request_id.0.clone()))
+}
+
+#[async_trait::async_trait]
+impl Extension for RequestIdPropagationExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let resp = next.run(ctx).await;
+        attach_request_id(resp, self.request_id.get().as_ref())
+    }
+
+    fn subscribe<'s>(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        stream: BoxStream<'s, Response>,
+        next: NextSubscribe<'_>,
+    ) -> BoxStream<'s, Response> {
+        let request_id = self.request_id.clone();
+        Box::pin(
+            next.run(ctx, stream)
+                .map(move |resp| attach_request_id(resp, request_id.get().as_ref())),
+        )
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        self.request_id.capture(ctx);
+        next.run(ctx, query, variables).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream::Stream;
+
+    use super::*;
+    use crate::*;
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        async fn error(&self) -> Result<i32> {
+            Err("not found".into())
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription(internal)]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..2)
+        }
+    }
+
+    #[tokio::test]
+    async fn request_id() {
+        let schema = Schema::build(Query, EmptyMutation, Subscription)
+            .extension(RequestIdPropagation)
+            .finish();
+
+        let resp = schema.execute("{ value }").await;
+        assert!(resp.extensions.get("requestId").is_none());
+
+        let resp = schema
+            .execute(Request::new("{ value error }").data(RequestId("abc".to_string())))
+            .await;
+        assert_eq!(resp.extensions["requestId"], value!("abc"));
+        assert_eq!(
+            resp.errors[0].extensions.as_ref().unwrap().get("requestId"),
+            Some(&value!("abc"))
+        );
+
+        let resp = schema
+            .execute(Request::new("{ value").data(RequestId("abc".to_string())))
+            .await;
+        assert_eq!(resp.extensions["requestId"], value!("abc"));
+        assert_eq!(
+            resp.errors[0].extensions.as_ref().unwrap().get("requestId"),
+            Some(&value!("abc"))
+        );
+
+        let responses = schema
+            .execute_stream(
+                Request::new("subscription { values }").data(RequestId("abc".to_string())),
+            )
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(responses.len(), 2);
+        assert!(responses
+            .iter()
+            .all(|resp| resp.extensions["requestId"] == value!("abc")));
+    }
+}
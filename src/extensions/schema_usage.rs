@@ -0,0 +1,255 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use crate::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory},
+    parser::types::{ExecutableDocument, OperationDefinition},
+    validation::collect_schema_usage,
+    ClientInfo, Positioned, ServerResult, Variables,
+};
+
+/// The parts of the schema referenced by a request, passed to the callback of
+/// the [`SchemaUsageReporter`] extension.
+///
+/// The parts of the schema are identified by their schema coordinates, such as
+/// `User.name` for a field, `Query.user(id:)` for an argument and
+/// `Role.ADMIN` for an enum value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaUsage {
+    /// The name of the executed operation.
+    pub operation_name: Option<String>,
+    /// The [`ClientInfo`] of the request, if any.
+    pub client_info: Option<ClientInfo>,
+    /// The types, including the types of the arguments and the variables.
+    pub types: BTreeSet<String>,
+    /// The fields of the objects and interfaces, and the fields of the input
+    /// objects.
+    pub fields: BTreeSet<String>,
+    /// The arguments of the fields.
+    pub arguments: BTreeSet<String>,
+    /// The enum values, from the arguments and the variables.
+    pub enum_values: BTreeSet<String>,
+}
+
+type UsageCallback = Arc<dyn Fn(SchemaUsage) + Send + Sync>;
+
+/// Schema usage reporter extension
+///
+/// This extension reports the types, fields, arguments and enum values
+/// referenced by the executed operation of each valid request, with the
+/// fragments resolved, to a callback. The introspection fields are not
+/// reported. Aggregated over time, this tells which parts of the schema can be
+/// deprecated or removed safely, and which clients still use them.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// use async_graphql::{
+///     extensions::{SchemaUsage, SchemaUsageReporter},
+///     *,
+/// };
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self, #[graphql(default)] offset: i32) -> i32 {
+///         10 + offset
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let reports = Arc::new(Mutex::new(Vec::<SchemaUsage>::new()));
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(SchemaUsageReporter::new({
+///         let reports = reports.clone();
+///         move |usage| reports.lock().unwrap().push(usage)
+///     }))
+///     .finish();
+/// schema.execute("{ value(offset: 1) }").await.into_result().unwrap();
+///
+/// let usage = reports.lock().unwrap().pop().unwrap();
+/// assert!(usage.fields.contains("Query.value"));
+/// assert!(usage.arguments.contains("Query.value(offset:)"));
+/// # });
+/// ```
+pub struct SchemaUsageReporter {
+    callback: UsageCallback,
+}
+
+impl SchemaUsageReporter {
+    /// Create a schema usage reporter extension that calls `callback` with the
+    /// usage of each request.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(SchemaUsage) + Send + Sync + 'static,
+    {
+        Self {
+            callback: Arc::new(callback),
+        }
+    }
+}
+
+impl ExtensionFactory for SchemaUsageReporter {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(SchemaUsageReporterExtension {
+            callback: self.callback.clone(),
+        })
+    }
+}
+
+struct SchemaUsageReporterExtension {
+    callback: UsageCallback,
+}
+
+#[async_trait::async_trait]
+impl Extension for SchemaUsageReporterExtension {
+    async fn validation_did_finish(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        document: &ExecutableDocument,
+        operation_name: Option<&str>,
+        operation: &Positioned<OperationDefinition>,
+        variables: &Variables,
+    ) -> ServerResult<()> {
+        let mut usage = SchemaUsage {
+            operation_name: operation_name.map(ToString::to_string),
+            client_info: ctx.data_opt::<ClientInfo>().cloned(),
+            ..SchemaUsage::default()
+        };
+        // the enum values of the secret arguments are not reported
+        let variables = ctx.redact_variables(document, variables);
+        collect_schema_usage(
+            &ctx.schema_env.registry,
+            document,
+            Some(&variables),
+            operation,
+            &mut usage,
+        );
+        (self.callback)(usage);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        extensions::{SchemaUsage, SchemaUsageReporter},
+        *,
+    };
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(internal)]
+    enum Role {
+        Admin,
+        User,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(internal)]
+    struct UserFilter {
+        role: Option<Role>,
+        name: Option<String>,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(internal)]
+    struct User {
+        id: i32,
+        name: String,
+        #[graphql(deprecation = "Use `name`.")]
+        login: String,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn users(&self, filter: Option<UserFilter>, roles: Option<Vec<Role>>) -> Vec<User> {
+            let _ = (filter, roles);
+            Vec::new()
+        }
+
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    fn schema() -> (
+        Schema<Query, EmptyMutation, EmptySubscription>,
+        Arc<Mutex<Vec<SchemaUsage>>>,
+    ) {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(SchemaUsageReporter::new({
+                let reports = reports.clone();
+                move |usage| reports.lock().unwrap().push(usage)
+            }))
+            .finish();
+        (schema, reports)
+    }
+
+    fn set(items: &[&str]) -> std::collections::BTreeSet<String> {
+        items.iter().map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn schema_usage() {
+        let (schema, reports) = schema();
+        let query = r#"
+            query A($filter: UserFilter) {
+                users(filter: $filter, roles: ADMIN) { ...UserFields }
+            }
+
+            query B {
+                value
+            }
+
+            fragment UserFields on User {
+                id
+                ... on User { login }
+            }
+        "#;
+        let request = Request::new(query)
+            .operation_name("A")
+            .variables(Variables::from_json(serde_json::json!({
+                "filter": { "role": "USER" },
+            })))
+            .data(ClientInfo::new("web"));
+        schema.execute(request).await.into_result().unwrap();
+
+        let usage = reports.lock().unwrap().pop().unwrap();
+        assert_eq!(
+            usage,
+            SchemaUsage {
+                operation_name: Some("A".to_string()),
+                client_info: Some(ClientInfo::new("web")),
+                types: set(&["Int", "Query", "Role", "String", "User", "UserFilter"]),
+                fields: set(&["Query.users", "User.id", "User.login", "UserFilter.role"]),
+                arguments: set(&["Query.users(filter:)", "Query.users(roles:)"]),
+                enum_values: set(&["Role.ADMIN", "Role.USER"]),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn schema_usage_skips_introspection_and_invalid_requests() {
+        let (schema, reports) = schema();
+
+        schema
+            .execute("{ value __schema { types { name } } }")
+            .await
+            .into_result()
+            .unwrap();
+        let usage = reports.lock().unwrap().pop().unwrap();
+        assert_eq!(usage.types, set(&["Int", "Query"]));
+        assert_eq!(usage.fields, set(&["Query.value"]));
+
+        let resp = schema.execute("{ unknown }").await;
+        assert!(resp.is_err());
+        assert!(reports.lock().unwrap().is_empty());
+    }
+}
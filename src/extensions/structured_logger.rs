@@ -0,0 +1,206 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use crate::{
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextRequest,
+        NextValidation, RequestId,
+    },
+    parser::types::ExecutableDocument,
+    Response, ServerError, ServerResult, ValidationResult, Variables,
+};
+
+/// Structured logger extension
+///
+/// Emits one `tracing` event per request, with the target
+/// `async_graphql::request` and the following fields, so that the logs can be
+/// ingested and queried by log aggregators:
+///
+/// - `operation_name`: the operation name of the request, if any.
+/// - `request_id`: the [`RequestId`] of the request, if any.
+/// - `query`: the query, where the values of the secret arguments are hidden.
+/// - `variables`: the variables in JSON, where the values of the secret
+///   arguments are replaced with `"<secret>"`.
+/// - `duration_ms`: the duration of the request in milliseconds.
+/// - `errors`: the number of errors in the response.
+/// - `complexity` and `depth`: the complexity and depth of the query, once it
+///   has been validated.
+///
+/// Requests with errors are logged at the `WARN` level, other requests at the
+/// `INFO` level.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::{extensions::StructuredLogger, *};
+///
+/// #[derive(SimpleObject)]
+/// struct Query {
+///     value: i32,
+/// }
+///
+/// let schema = Schema::build(Query { value: 100 }, EmptyMutation, EmptySubscription)
+///     .extension(StructuredLogger)
+///     .finish();
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub struct StructuredLogger;
+
+impl ExtensionFactory for StructuredLogger {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(StructuredLoggerExtension {
+            inner: Default::default(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct RequestLog {
+    operation_name: Option<String>,
+    request_id: Option<String>,
+    query: Option<String>,
+    variables: Option<String>,
+    complexity: Option<usize>,
+    depth: Option<usize>,
+}
+
+struct StructuredLoggerExtension {
+    inner: Mutex<RequestLog>,
+}
+
+#[async_trait::async_trait]
+impl Extension for StructuredLoggerExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let start_time = Instant::now();
+        let resp = next.run(ctx).await;
+        let duration_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+
+        let log = std::mem::take(&mut *self.inner.lock().unwrap());
+        macro_rules! log_request {
+            ($level:expr) => {
+                tracinglib::event!(
+                    target: "async_graphql::request",
+                    $level,
+                    operation_name = log.operation_name.as_deref(),
+                    request_id = log.request_id.as_deref(),
+                    query = log.query.as_deref(),
+                    variables = log.variables.as_deref(),
+                    duration_ms,
+                    errors = resp.errors.len(),
+                    complexity = log.complexity,
+                    depth = log.depth,
+                    "graphql request",
+                )
+            };
+        }
+        if resp.errors.is_empty() {
+            log_request!(tracinglib::Level::INFO);
+        } else {
+            log_request!(tracinglib::Level::WARN);
+        }
+        resp
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        self.inner.lock().unwrap().request_id = ctx.data_opt::<RequestId>().map(|id| id.0.clone());
+
+        let document = next.run(ctx, query, variables).await?;
+        let mut log = self.inner.lock().unwrap();
+        log.query = Some(ctx.stringify_execute_doc(&document, variables));
+        log.variables = serde_json::to_string(&ctx.redact_variables(&document, variables)).ok();
+        Ok(document)
+    }
+
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let res = next.run(ctx).await?;
+        let mut log = self.inner.lock().unwrap();
+        log.complexity = Some(res.complexity);
+        log.depth = Some(res.depth);
+        Ok(res)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        self.inner.lock().unwrap().operation_name = operation_name.map(ToString::to_string);
+        next.run(ctx, operation_name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extensions::tracing::tests::Collector, *};
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn login(&self, username: String, #[graphql(secret)] password: String) -> bool {
+            username == "abc" && password == "123"
+        }
+    }
+
+    #[tokio::test]
+    async fn structured_logger() {
+        let collector = Collector::default();
+        let _guard = tracinglib::subscriber::set_default(collector.clone());
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(StructuredLogger)
+            .finish();
+
+        schema
+            .execute(
+                Request::new(
+                    "query Login($username: String!, $password: String!) { login(username: \
+                     $username, password: $password) }",
+                )
+                .operation_name("Login")
+                .variables(Variables::from_json(serde_json::json!({
+                    "username": "abc",
+                    "password": "123",
+                })))
+                .data(RequestId("req-1".to_string())),
+            )
+            .await
+            .into_result()
+            .unwrap();
+        schema.execute("{ login }").await;
+
+        let events = std::mem::take(&mut *collector.events.lock().unwrap());
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(events[0]["level"], "INFO");
+        assert_eq!(events[0]["operation_name"], "Login");
+        assert_eq!(events[0]["request_id"], "req-1");
+        assert_eq!(events[0]["errors"], "0");
+        assert_eq!(events[0]["complexity"], "1");
+        assert_eq!(events[0]["depth"], "1");
+        assert!(events[0].contains_key("duration_ms"));
+        let variables: serde_json::Value = serde_json::from_str(&events[0]["variables"]).unwrap();
+        assert_eq!(
+            variables,
+            serde_json::json!({ "username": "abc", "password": "<secret>" })
+        );
+
+        assert_eq!(events[1]["level"], "WARN");
+        assert_eq!(events[1]["errors"], "2");
+        assert!(!events[1].contains_key("operation_name"));
+        assert!(!events[1].contains_key("complexity"));
+    }
+}
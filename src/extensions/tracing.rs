@@ -2,19 +2,40 @@ use std::sync::Arc;
 
 use futures_util::{stream::BoxStream, TryFutureExt};
 use tracing_futures::Instrument;
-use tracinglib::{span, Level};
+use tracinglib::{span, Level, Span};
 
 use crate::{
     extensions::{
         Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextRequest,
-        NextResolve, NextSubscribe, NextValidation, ResolveInfo,
+        NextResolve, NextSubscribe, NextValidation, RequestId, ResolveInfo,
     },
     parser::types::ExecutableDocument,
+    registry::{redact_unparsed_query, MetaType, MetaTypeName},
     Response, ServerError, ServerResult, ValidationResult, Value, Variables,
 };
 
+/// Creates a span with a level that is only known at runtime.
+macro_rules! span_with_level {
+    ($level:expr, $($args:tt)*) => {{
+        let level = $level;
+        if level == Level::ERROR {
+            span!(target: "async_graphql::graphql", Level::ERROR, $($args)*)
+        } else if level == Level::WARN {
+            span!(target: "async_graphql::graphql", Level::WARN, $($args)*)
+        } else if level == Level::INFO {
+            span!(target: "async_graphql::graphql", Level::INFO, $($args)*)
+        } else if level == Level::DEBUG {
+            span!(target: "async_graphql::graphql", Level::DEBUG, $($args)*)
+        } else {
+            span!(target: "async_graphql::graphql", Level::TRACE, $($args)*)
+        }
+    }};
+}
+
 /// Tracing extension
 ///
+/// See [`TracingConfig`] to customize the spans.
+///
 /// # References
 ///
 /// <https://crates.io/crates/tracing>
@@ -42,23 +63,175 @@ pub struct Tracing;
 
 impl ExtensionFactory for Tracing {
     fn create(&self) -> Arc<dyn Extension> {
-        Arc::new(TracingExtension::default())
+        Arc::new(TracingExtension {
+            config: TracingConfig::default(),
+        })
+    }
+}
+
+type FieldFilterFn = dyn Fn(&ExtensionContext<'_>, &ResolveInfo<'_>) -> bool + Send + Sync;
+type FieldSpanFn = dyn Fn(&ExtensionContext<'_>, &ResolveInfo<'_>) -> Span + Send + Sync;
+type RequestSpanFn = dyn Fn(&ExtensionContext<'_>) -> Span + Send + Sync;
+
+/// Configurable tracing extension
+///
+/// Works like [`Tracing`], but allows changing the level of the spans,
+/// skipping fields and replacing the request and field spans to use custom
+/// names or attach custom attributes.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate tracinglib as tracing;
+/// use async_graphql::{extensions::TracingConfig, *};
+/// use tracing::{info_span, Level};
+///
+/// struct TenantId(String);
+///
+/// #[derive(SimpleObject)]
+/// struct Query {
+///     value: i32,
+/// }
+///
+/// let schema = Schema::build(Query { value: 100 }, EmptyMutation, EmptySubscription)
+///     .extension(
+///         TracingConfig::default()
+///             .level(Level::DEBUG)
+///             .skip_leaf_fields()
+///             .request_span(|ctx| {
+///                 let tenant_id = ctx.data_opt::<TenantId>().map(|id| id.0.as_str());
+///                 info_span!("graphql request", tenant_id)
+///             }),
+///     )
+///     .finish();
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+#[derive(Clone)]
+pub struct TracingConfig {
+    level: Level,
+    skip_leaf_fields: bool,
+    filter: Option<Arc<FieldFilterFn>>,
+    request_span: Option<Arc<RequestSpanFn>>,
+    field_span: Option<Arc<FieldSpanFn>>,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            level: Level::INFO,
+            skip_leaf_fields: false,
+            filter: None,
+            request_span: None,
+            field_span: None,
+        }
+    }
+}
+
+impl TracingConfig {
+    /// Set the level of the spans created by the extension. (default:
+    /// `INFO`)
+    ///
+    /// Spans returned by [`TracingConfig::request_span`] and
+    /// [`TracingConfig::field_span`] keep their own level.
+    #[must_use]
+    pub fn level(self, level: Level) -> Self {
+        Self { level, ..self }
+    }
+
+    /// Do not create spans for fields returning a scalar or an enum.
+    #[must_use]
+    pub fn skip_leaf_fields(self) -> Self {
+        Self {
+            skip_leaf_fields: true,
+            ..self
+        }
+    }
+
+    /// Only create spans for the fields for which the function returns
+    /// `true`.
+    #[must_use]
+    pub fn filter<F>(self, f: F) -> Self
+    where
+        F: Fn(&ExtensionContext<'_>, &ResolveInfo<'_>) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            filter: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
+    /// Use the span returned by the function for each request instead of the
+    /// default `request` span.
+    #[must_use]
+    pub fn request_span<F>(self, f: F) -> Self
+    where
+        F: Fn(&ExtensionContext<'_>) -> Span + Send + Sync + 'static,
+    {
+        Self {
+            request_span: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
+    /// Use the span returned by the function for each field instead of the
+    /// default `field` span.
+    #[must_use]
+    pub fn field_span<F>(self, f: F) -> Self
+    where
+        F: Fn(&ExtensionContext<'_>, &ResolveInfo<'_>) -> Span + Send + Sync + 'static,
+    {
+        Self {
+            field_span: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
+    fn should_trace(&self, ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) -> bool {
+        if info.is_for_introspection {
+            return false;
+        }
+        if self.skip_leaf_fields
+            && matches!(
+                ctx.schema_env
+                    .registry
+                    .types
+                    .get(MetaTypeName::concrete_typename(info.return_type)),
+                Some(MetaType::Scalar { .. } | MetaType::Enum { .. })
+            )
+        {
+            return false;
+        }
+        match &self.filter {
+            Some(filter) => filter(ctx, info),
+            None => true,
+        }
     }
 }
 
-#[derive(Default)]
-struct TracingExtension;
+impl ExtensionFactory for TracingConfig {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(TracingExtension {
+            config: self.clone(),
+        })
+    }
+}
+
+struct TracingExtension {
+    config: TracingConfig,
+}
 
 #[async_trait::async_trait]
 impl Extension for TracingExtension {
     async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
-        next.run(ctx)
-            .instrument(span!(
-                target: "async_graphql::graphql",
-                Level::INFO,
+        let span = match &self.config.request_span {
+            Some(request_span) => request_span(ctx),
+            None => span_with_level!(
+                self.config.level,
                 "request",
-            ))
-            .await
+                request_id = tracinglib::field::Empty,
+            ),
+        };
+        next.run(ctx).instrument(span).await
     }
 
     fn subscribe<'s>(
@@ -67,11 +240,10 @@ impl Extension for TracingExtension {
         stream: BoxStream<'s, Response>,
         next: NextSubscribe<'_>,
     ) -> BoxStream<'s, Response> {
-        Box::pin(next.run(ctx, stream).instrument(span!(
-            target: "async_graphql::graphql",
-            Level::INFO,
-            "subscribe",
-        )))
+        Box::pin(
+            next.run(ctx, stream)
+                .instrument(span_with_level!(self.config.level, "subscribe")),
+        )
     }
 
     async fn parse_query(
@@ -81,18 +253,32 @@ impl Extension for TracingExtension {
         variables: &Variables,
         next: NextParseQuery<'_>,
     ) -> ServerResult<ExecutableDocument> {
-        let span = span!(
-            target: "async_graphql::graphql",
-            Level::INFO,
+        if let Some(request_id) = ctx.data_opt::<RequestId>() {
+            tracinglib::Span::current().record("request_id", request_id.0.as_str());
+        }
+
+        let span = span_with_level!(
+            self.config.level,
             "parse",
+            source = tracinglib::field::Empty,
+            variables = tracinglib::field::Empty,
         );
         async move {
             let res = next.run(ctx, query, variables).await;
-            if let Ok(doc) = &res {
-                tracinglib::Span::current().record(
-                    "source",
-                    &ctx.stringify_execute_doc(doc, variables).as_str(),
-                );
+            let span = tracinglib::Span::current();
+            match &res {
+                Ok(doc) => {
+                    span.record("source", ctx.stringify_execute_doc(doc, variables).as_str());
+                    span.record(
+                        "variables",
+                        serde_json::to_string(&ctx.redact_variables(doc, variables))
+                            .unwrap()
+                            .as_str(),
+                    );
+                }
+                Err(_) => {
+                    span.record("source", redact_unparsed_query(query).as_str());
+                }
             }
             res
         }
@@ -105,11 +291,7 @@ impl Extension for TracingExtension {
         ctx: &ExtensionContext<'_>,
         next: NextValidation<'_>,
     ) -> Result<ValidationResult, Vec<ServerError>> {
-        let span = span!(
-            target: "async_graphql::graphql",
-            Level::INFO,
-            "validation"
-        );
+        let span = span_with_level!(self.config.level, "validation");
         next.run(ctx).instrument(span).await
     }
 
@@ -119,11 +301,7 @@ impl Extension for TracingExtension {
         operation_name: Option<&str>,
         next: NextExecute<'_>,
     ) -> Response {
-        let span = span!(
-            target: "async_graphql::graphql",
-            Level::INFO,
-            "execute"
-        );
+        let span = span_with_level!(self.config.level, "execute");
         next.run(ctx, operation_name).instrument(span).await
     }
 
@@ -133,15 +311,17 @@ impl Extension for TracingExtension {
         info: ResolveInfo<'_>,
         next: NextResolve<'_>,
     ) -> ServerResult<Option<Value>> {
-        let span = if !info.is_for_introspection {
-            Some(span!(
-                target: "async_graphql::graphql",
-                Level::INFO,
-                "field",
-                path = %info.path_node,
-                parent_type = %info.parent_type,
-                return_type = %info.return_type,
-            ))
+        let span = if self.config.should_trace(ctx, &info) {
+            Some(match &self.config.field_span {
+                Some(field_span) => field_span(ctx, &info),
+                None => span_with_level!(
+                    self.config.level,
+                    "field",
+                    path = %info.path_node,
+                    parent_type = %info.parent_type,
+                    return_type = %info.return_type,
+                ),
+            })
         } else {
             None
         };
@@ -159,3 +339,133 @@ impl Extension for TracingExtension {
         }
     }
 }
+
+#[cfg(test)]
+pub(super) mod tests {
+    use std::{collections::HashMap, fmt::Debug, sync::Mutex};
+
+    use tracinglib::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    };
+
+    use super::*;
+    use crate::*;
+
+    /// Collects the names and levels of the spans, and the fields of the
+    /// events.
+    #[derive(Clone, Default)]
+    pub(in crate::extensions) struct Collector {
+        pub(in crate::extensions) spans: Arc<Mutex<Vec<(String, Level)>>>,
+        pub(in crate::extensions) events: Arc<Mutex<Vec<HashMap<String, String>>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((span.metadata().name().to_string(), *span.metadata().level()));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = HashMap::new();
+            fields.insert("level".to_string(), event.metadata().level().to_string());
+            event.record(&mut FieldVisitor(&mut fields));
+            self.events.lock().unwrap().push(fields);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(internal)]
+    struct MyObj {
+        value: i32,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj { value: 2 }
+        }
+    }
+
+    async fn collect_spans(extension: impl ExtensionFactory) -> Vec<(String, Level)> {
+        let collector = Collector::default();
+        let _guard = tracinglib::subscriber::set_default(collector.clone());
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(extension)
+            .finish();
+        schema
+            .execute("{ value obj { value } }")
+            .await
+            .into_result()
+            .unwrap();
+        let spans = std::mem::take(&mut *collector.spans.lock().unwrap());
+        spans
+    }
+
+    #[tokio::test]
+    async fn tracing_config() {
+        let spans = collect_spans(Tracing).await;
+        assert_eq!(
+            spans
+                .iter()
+                .filter(|(name, level)| name == "field" && *level == Level::INFO)
+                .count(),
+            3
+        );
+
+        let spans = collect_spans(TracingConfig::default().level(Level::DEBUG)).await;
+        assert!(spans.iter().all(|(_, level)| *level == Level::DEBUG));
+
+        let spans = collect_spans(TracingConfig::default().skip_leaf_fields()).await;
+        assert_eq!(spans.iter().filter(|(name, _)| name == "field").count(), 1);
+
+        let spans = collect_spans(
+            TracingConfig::default()
+                .filter(|_, info| info.path_node.field_name() != "obj")
+                .request_span(|_| tracinglib::info_span!("my_request"))
+                .field_span(|_, info| tracinglib::debug_span!("my_field", name = %info.name)),
+        )
+        .await;
+        assert_eq!(
+            spans
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .filter(|name| name.starts_with("my_"))
+                .collect::<Vec<_>>(),
+            vec!["my_request", "my_field", "my_field"]
+        );
+    }
+}
@@ -0,0 +1,431 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use async_graphql_value::Value as InputValue;
+use futures_util::future::BoxFuture;
+use once_cell::sync::OnceCell;
+
+use crate::{
+    context::{QueryEnv, QueryEnvInner, QueryPathSegment},
+    extensions::{CacheStorage, Extensions},
+    parser::types::{Directive, Field, OperationType, Selection, SelectionSet},
+    provider::ProvidedData,
+    Context, Name, Positioned, Value,
+};
+
+/// A cached value of a field, saved in a
+/// [`CacheStorage`](crate::extensions::CacheStorage).
+///
+/// The keys of the values contain the path of the parent object, the type and
+/// the name of the field, and depending on the `key` option of the field, its
+/// arguments, followed by its selection set, for example
+/// `Query.stats(year:2023)/Stats.count{total}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldCacheEntry {
+    /// The resolved value of the field.
+    pub value: Value,
+    /// The time when the value was resolved.
+    pub created_at: SystemTime,
+    /// The time after which the value is no longer used, even while it is
+    /// being revalidated.
+    pub expires_at: SystemTime,
+}
+
+/// Memory-based field cache storage.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::{
+///     atomic::{AtomicI32, Ordering},
+///     Arc,
+/// };
+///
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     #[graphql(cache(ttl = "30s"))]
+///     async fn total(&self, ctx: &Context<'_>) -> i32 {
+///         ctx.data_unchecked::<Arc<AtomicI32>>().fetch_add(1, Ordering::SeqCst)
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .data(Arc::new(AtomicI32::new(1)))
+///     .field_cache_storage(MemoryFieldCacheStorage::new())
+///     .finish();
+///
+/// assert_eq!(schema.execute("{ total }").await.data, value!({ "total": 1 }));
+/// assert_eq!(schema.execute("{ total }").await.data, value!({ "total": 1 }));
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct MemoryFieldCacheStorage(Arc<Mutex<HashMap<String, FieldCacheEntry>>>);
+
+impl MemoryFieldCacheStorage {
+    /// Creates a new memory-based field cache storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheStorage<FieldCacheEntry> for MemoryFieldCacheStorage {
+    async fn get(&self, key: String) -> Option<FieldCacheEntry> {
+        let entries = self.0.lock().unwrap();
+        entries
+            .get(&key)
+            .filter(|entry| entry.expires_at > SystemTime::now())
+            .cloned()
+    }
+
+    async fn set(&self, key: String, value: FieldCacheEntry) {
+        let now = SystemTime::now();
+        let mut entries = self.0.lock().unwrap();
+        entries.retain(|_, entry| entry.expires_at > now);
+        entries.insert(key, value);
+    }
+}
+
+#[async_trait::async_trait]
+trait ErasedFieldCacheStorage: Send + Sync + 'static {
+    async fn get(&self, key: String) -> Option<FieldCacheEntry>;
+
+    async fn set(&self, key: String, entry: FieldCacheEntry);
+}
+
+#[async_trait::async_trait]
+impl<T: CacheStorage<FieldCacheEntry>> ErasedFieldCacheStorage for T {
+    async fn get(&self, key: String) -> Option<FieldCacheEntry> {
+        CacheStorage::get(self, key).await
+    }
+
+    async fn set(&self, key: String, entry: FieldCacheEntry) {
+        CacheStorage::set(self, key, entry).await
+    }
+}
+
+pub(crate) type FieldCacheSpawner = Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>;
+
+/// Executes the operation of a query environment on the schema, to refresh a
+/// cached value.
+pub(crate) type FieldCacheRefresher = Box<dyn Fn(QueryEnv) -> BoxFuture<'static, ()> + Send + Sync>;
+
+pub(crate) struct FieldCache {
+    storage: Box<dyn ErasedFieldCacheStorage>,
+    spawner: Option<FieldCacheSpawner>,
+    /// Set by the schema when it executes its first request, since the
+    /// schema is not created yet when its field cache is.
+    pub(crate) refresher: OnceCell<FieldCacheRefresher>,
+    revalidating: Arc<Mutex<HashSet<String>>>,
+}
+
+impl FieldCache {
+    pub(crate) fn new(storage: impl CacheStorage<FieldCacheEntry>) -> Self {
+        Self {
+            storage: Box::new(storage),
+            spawner: None,
+            refresher: OnceCell::new(),
+            revalidating: Default::default(),
+        }
+    }
+
+    pub(crate) fn with_spawner(self, spawner: Option<FieldCacheSpawner>) -> Self {
+        Self { spawner, ..self }
+    }
+
+    pub(crate) async fn get(&self, key: &str) -> Option<FieldCacheEntry> {
+        self.storage.get(key.to_string()).await
+    }
+
+    pub(crate) async fn set(&self, key: String, entry: FieldCacheEntry) {
+        self.storage.set(key, entry).await
+    }
+
+    /// Marks `key` as being revalidated until the returned guard is dropped,
+    /// or returns `None` if it is already being revalidated.
+    pub(crate) fn start_revalidation(&self, key: &str) -> Option<RevalidationGuard> {
+        if !self.revalidating.lock().unwrap().insert(key.to_string()) {
+            return None;
+        }
+        Some(RevalidationGuard {
+            revalidating: self.revalidating.clone(),
+            key: key.to_string(),
+        })
+    }
+
+    /// Revalidates the value of `key`, the key of the current field of `ctx`,
+    /// in the background, by executing the path of the field again from the
+    /// query root with the spawner of the cache.
+    ///
+    /// Returns the guard if the value can't be revalidated in the background,
+    /// because no spawner is set or the field is not resolved by a query.
+    pub(crate) fn revalidate_in_background(
+        &self,
+        ctx: &Context<'_>,
+        key: String,
+        guard: RevalidationGuard,
+    ) -> Result<(), RevalidationGuard> {
+        let (spawner, refresher) = match (&self.spawner, self.refresher.get()) {
+            (Some(spawner), Some(refresher)) => (spawner, refresher),
+            _ => return Err(guard),
+        };
+        if ctx.query_env.operation.node.ty != OperationType::Query {
+            return Err(guard);
+        }
+
+        let mut path = Vec::new();
+        if let Some(path_node) = ctx.path_node {
+            path_node.for_each(|segment| {
+                if let QueryPathSegment::Name(name) = segment {
+                    path.push(name.to_string());
+                }
+            });
+        }
+        let query_env = &ctx.query_env;
+        let mut operation = Positioned::clone(&query_env.operation);
+        prune_selection_set(&mut operation.node.selection_set.node, &path);
+
+        let env = QueryEnv::new(QueryEnvInner {
+            extensions: Extensions::new(
+                Vec::new(),
+                ctx.schema_env.clone(),
+                query_env.session_data.clone(),
+            ),
+            variables: query_env.variables.clone(),
+            operation_name: query_env.operation_name.clone(),
+            operation: Arc::new(operation),
+            fragments: query_env.fragments.clone(),
+            uploads: Vec::new(),
+            session_data: query_env.session_data.clone(),
+            ctx_data: query_env.ctx_data.clone(),
+            extension_data: query_env.extension_data.clone(),
+            http_headers: Default::default(),
+            introspection_mode: query_env.introspection_mode,
+            errors: Default::default(),
+            max_errors: query_env.max_errors,
+            errors_exceeded: Default::default(),
+            max_response_size: query_env.max_response_size,
+            response_size: Default::default(),
+            response_extensions: Default::default(),
+            live_query_tokens: Default::default(),
+            operation_signature: query_env.operation_signature.clone(),
+            provided_data: ProvidedData::new_map(&ctx.schema_env.providers),
+            execution_stats: None,
+            field_cache_refresh: Some(key),
+        });
+        let fut = refresher(env);
+        spawner(Box::pin(async move {
+            fut.await;
+            drop(guard);
+        }));
+        Ok(())
+    }
+}
+
+pub(crate) struct RevalidationGuard {
+    revalidating: Arc<Mutex<HashSet<String>>>,
+    key: String,
+}
+
+impl Drop for RevalidationGuard {
+    fn drop(&mut self) {
+        self.revalidating.lock().unwrap().remove(&self.key);
+    }
+}
+
+/// Removes the fields whose response keys are not the next one of `path` from
+/// `selection_set`, recursively, so that only the path is executed.
+///
+/// The fragment spreads are kept, since their fragments are shared.
+fn prune_selection_set(selection_set: &mut SelectionSet, path: &[String]) {
+    let (name, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    selection_set
+        .items
+        .retain_mut(|selection| match &mut selection.node {
+            Selection::Field(field) => {
+                let field = &mut field.node;
+                if field.response_key().node != name.as_str() {
+                    return false;
+                }
+                prune_selection_set(&mut field.selection_set.node, rest);
+                true
+            }
+            Selection::FragmentSpread(_) => true,
+            Selection::InlineFragment(inline_fragment) => {
+                let selection_set = &mut inline_fragment.node.selection_set.node;
+                prune_selection_set(selection_set, path);
+                !selection_set.items.is_empty()
+            }
+        });
+}
+
+/// Returns the cache key of the current field of `ctx`, which is a field of
+/// `type_name`.
+///
+/// The key starts with the path of the parent object, the fields from the
+/// root with their arguments and the list indexes, since the value of the
+/// field depends on its parent. The variables are replaced by their values
+/// and the fragment spreads by their selection sets, so that the key only
+/// depends on the result of the field.
+pub(crate) fn field_cache_key(ctx: &Context<'_>, type_name: &str, with_args: bool) -> String {
+    let mut key = String::new();
+    write_parent_path(ctx, &mut key);
+    let field = &ctx.item.node;
+    write!(key, "{}.{}", type_name, field.name.node).unwrap();
+    if with_args {
+        write_arguments(ctx, &mut key, &field.arguments);
+    }
+    write_selection_set(ctx, &mut key, &field.selection_set.node);
+    key
+}
+
+/// Writes the path of the parent object of the current field of `ctx`, such
+/// as `Query.stats(year:2023)/` or `Query.list[2]/`, nothing for a field of
+/// the root.
+fn write_parent_path(ctx: &Context<'_>, output: &mut String) {
+    let mut segments = Vec::new();
+    if let Some(path_node) = ctx.path_node.as_ref().and_then(|node| node.parent) {
+        path_node.for_each(|segment| segments.push(*segment));
+    }
+    if segments.is_empty() {
+        return;
+    }
+
+    let registry = &ctx.schema_env.registry;
+    let root = match ctx.query_env.operation.node.ty {
+        OperationType::Query => registry.query_type.as_str(),
+        OperationType::Mutation => registry.mutation_type.as_deref().unwrap_or("Mutation"),
+        OperationType::Subscription => registry
+            .subscription_type
+            .as_deref()
+            .unwrap_or("Subscription"),
+    };
+    output.push_str(root);
+
+    let mut selection_set = Some(&ctx.query_env.operation.node.selection_set.node);
+    for segment in segments {
+        match segment {
+            QueryPathSegment::Index(idx) => write!(output, "[{}]", idx).unwrap(),
+            QueryPathSegment::Name(name) => {
+                let field =
+                    selection_set.and_then(|selection_set| find_field(ctx, selection_set, name));
+                output.push('.');
+                match field {
+                    Some(field) => {
+                        output.push_str(&field.name.node);
+                        write_arguments(ctx, output, &field.arguments);
+                    }
+                    None => output.push_str(name),
+                }
+                selection_set = field.map(|field| &field.selection_set.node);
+            }
+        }
+    }
+    output.push('/');
+}
+
+/// Finds the field whose response key is `name` in `selection_set`, or in its
+/// fragments.
+fn find_field<'a>(
+    ctx: &Context<'a>,
+    selection_set: &'a SelectionSet,
+    name: &str,
+) -> Option<&'a Field> {
+    selection_set
+        .items
+        .iter()
+        .find_map(|selection| match &selection.node {
+            Selection::Field(field) if field.node.response_key().node == name => Some(&field.node),
+            Selection::Field(_) => None,
+            Selection::FragmentSpread(spread) => {
+                let fragment = ctx
+                    .query_env
+                    .fragments
+                    .get(&spread.node.fragment_name.node)?;
+                find_field(ctx, &fragment.node.selection_set.node, name)
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                find_field(ctx, &inline_fragment.node.selection_set.node, name)
+            }
+        })
+}
+
+fn write_arguments(
+    ctx: &Context<'_>,
+    output: &mut String,
+    arguments: &[(Positioned<Name>, Positioned<InputValue>)],
+) {
+    if arguments.is_empty() {
+        return;
+    }
+    output.push('(');
+    for (idx, (name, value)) in arguments.iter().enumerate() {
+        if idx > 0 {
+            output.push(',');
+        }
+        let value = ctx.resolve_input_value(value.clone()).unwrap_or_default();
+        write!(output, "{}:{}", name.node, value).unwrap();
+    }
+    output.push(')');
+}
+
+fn write_directives(ctx: &Context<'_>, output: &mut String, directives: &[Positioned<Directive>]) {
+    for directive in directives {
+        write!(output, "@{}", directive.node.name.node).unwrap();
+        write_arguments(ctx, output, &directive.node.arguments);
+    }
+}
+
+fn write_selection_set(ctx: &Context<'_>, output: &mut String, selection_set: &SelectionSet) {
+    if selection_set.items.is_empty() {
+        return;
+    }
+    output.push('{');
+    for (idx, selection) in selection_set.items.iter().enumerate() {
+        if idx > 0 {
+            output.push(' ');
+        }
+        match &selection.node {
+            Selection::Field(field) => {
+                if let Some(alias) = &field.node.alias {
+                    write!(output, "{}:", alias.node).unwrap();
+                }
+                output.push_str(&field.node.name.node);
+                write_arguments(ctx, output, &field.node.arguments);
+                write_directives(ctx, output, &field.node.directives);
+                write_selection_set(ctx, output, &field.node.selection_set.node);
+            }
+            Selection::FragmentSpread(spread) => {
+                output.push_str("...");
+                if let Some(fragment) = ctx.query_env.fragments.get(&spread.node.fragment_name.node)
+                {
+                    write!(output, "on {}", fragment.node.type_condition.node.on.node).unwrap();
+                    write_directives(ctx, output, &spread.node.directives);
+                    write_directives(ctx, output, &fragment.node.directives);
+                    write_selection_set(ctx, output, &fragment.node.selection_set.node);
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                output.push_str("...");
+                if let Some(type_condition) = &inline_fragment.node.type_condition {
+                    write!(output, "on {}", type_condition.node.on.node).unwrap();
+                }
+                write_directives(ctx, output, &inline_fragment.node.directives);
+                write_selection_set(ctx, output, &inline_fragment.node.selection_set.node);
+            }
+        }
+    }
+    output.push('}');
+}
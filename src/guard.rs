@@ -1,6 +1,8 @@
 //! Field guards
 
-use crate::{Context, Result};
+use std::sync::Arc;
+
+use crate::{Context, Error, ErrorExtensionValues, Result};
 
 /// Field guard
 ///
@@ -22,6 +24,102 @@ where
     }
 }
 
+/// The reason a guard denied the access to a field.
+///
+/// It is converted into an [`Error`] whose extensions contain the `code` and
+/// the `requiredPermission` of the denial, unless a hook set with
+/// [`SchemaBuilder::on_guard_denial`](crate::SchemaBuilder::on_guard_denial)
+/// renders it.
+///
+/// The message is a template, in which `{code}` and `{permission}` are
+/// replaced by the error code and the required permission.
+///
+/// ```
+/// use async_graphql::*;
+///
+/// struct RoleGuard(&'static str);
+///
+/// #[async_trait::async_trait]
+/// impl Guard for RoleGuard {
+///     async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+///         if ctx.data_opt::<&'static str>() == Some(&self.0) {
+///             return Ok(());
+///         }
+///         Err(GuardDenial::new("The {permission} role is required.")
+///             .code("FORBIDDEN")
+///             .required_permission(self.0)
+///             .into())
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardDenial {
+    /// The template of the error message.
+    pub message: String,
+    /// The error code, such as `FORBIDDEN`.
+    pub code: Option<String>,
+    /// The permission required to access the field.
+    pub required_permission: Option<String>,
+}
+
+impl GuardDenial {
+    /// Create a denial with the template of the error message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            code: None,
+            required_permission: None,
+        }
+    }
+
+    /// Set the error code.
+    #[must_use]
+    pub fn code(self, code: impl Into<String>) -> Self {
+        Self {
+            code: Some(code.into()),
+            ..self
+        }
+    }
+
+    /// Set the permission required to access the field.
+    #[must_use]
+    pub fn required_permission(self, permission: impl Into<String>) -> Self {
+        Self {
+            required_permission: Some(permission.into()),
+            ..self
+        }
+    }
+
+    /// Returns the error message, with the placeholders of the template
+    /// replaced.
+    pub fn format_message(&self) -> String {
+        self.message
+            .replace("{code}", self.code.as_deref().unwrap_or_default())
+            .replace(
+                "{permission}",
+                self.required_permission.as_deref().unwrap_or_default(),
+            )
+    }
+}
+
+impl From<GuardDenial> for Error {
+    fn from(denial: GuardDenial) -> Self {
+        let mut extensions = ErrorExtensionValues::default();
+        if let Some(code) = &denial.code {
+            extensions.set("code", code.as_str());
+        }
+        if let Some(permission) = &denial.required_permission {
+            extensions.set("requiredPermission", permission.as_str());
+        }
+        Error {
+            message: denial.format_message(),
+            extensions: (denial.code.is_some() || denial.required_permission.is_some())
+                .then_some(extensions),
+            source: Some(Arc::new(denial)),
+        }
+    }
+}
+
 /// An extension trait for `Guard`.
 pub trait GuardExt: Guard + Sized {
     /// Perform `and` operator on two rules
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// A builder for constructing an Altair GraphQL Client HTML page.
+///
+/// # Example
+///
+/// ```rust
+/// use async_graphql::http::*;
+///
+/// AltairSource::build()
+///     .endpoint("/")
+///     .subscription_endpoint("/ws")
+///     .initial_query("{ hello }")
+///     .header("Authorization", "Bearer [token]")
+///     .finish();
+/// ```
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AltairSource<'a> {
+    #[serde(rename = "endpointURL")]
+    endpoint: &'a str,
+    #[serde(rename = "subscriptionsEndpoint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subscription_endpoint: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    initial_query: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    initial_variables: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    initial_headers: Option<HashMap<&'a str, &'a str>>,
+    #[serde(skip)]
+    title: Option<&'a str>,
+    #[serde(skip)]
+    assets_url: Option<&'a str>,
+}
+
+impl<'a> AltairSource<'a> {
+    /// Creates a builder for constructing an Altair GraphQL Client HTML page.
+    pub fn build() -> AltairSource<'a> {
+        Default::default()
+    }
+
+    /// Sets the endpoint of the server Altair will connect to.
+    #[must_use]
+    pub fn endpoint(self, endpoint: &'a str) -> AltairSource<'a> {
+        AltairSource { endpoint, ..self }
+    }
+
+    /// Sets the subscription endpoint of the server Altair will connect to.
+    #[must_use]
+    pub fn subscription_endpoint(self, endpoint: &'a str) -> AltairSource<'a> {
+        AltairSource {
+            subscription_endpoint: Some(endpoint),
+            ..self
+        }
+    }
+
+    /// Sets the document initially displayed in the query editor.
+    #[must_use]
+    pub fn initial_query(self, query: &'a str) -> AltairSource<'a> {
+        AltairSource {
+            initial_query: Some(query),
+            ..self
+        }
+    }
+
+    /// Sets the JSON initially displayed in the variables editor.
+    #[must_use]
+    pub fn initial_variables(self, variables: &'a str) -> AltairSource<'a> {
+        AltairSource {
+            initial_variables: Some(variables),
+            ..self
+        }
+    }
+
+    /// Sets a header to be sent with requests Altair will send.
+    #[must_use]
+    pub fn header(mut self, name: &'a str, value: &'a str) -> AltairSource<'a> {
+        self.initial_headers
+            .get_or_insert_with(HashMap::new)
+            .insert(name, value);
+        self
+    }
+
+    /// Sets the html document title.
+    #[must_use]
+    pub fn title(self, title: &'a str) -> AltairSource<'a> {
+        AltairSource {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// Sets the URL of the `build/dist` directory of the `altair-static`
+    /// package the page is loaded from, for example to serve a copy of it for
+    /// the deployments without an internet access. (default:
+    /// `https://cdn.jsdelivr.net/npm/altair-static@5/build/dist`)
+    #[must_use]
+    pub fn assets_url(self, url: &'a str) -> AltairSource<'a> {
+        AltairSource {
+            assets_url: Some(url),
+            ..self
+        }
+    }
+
+    /// Returns an Altair GraphQL Client HTML page.
+    pub fn finish(self) -> String {
+        let title = self.title.unwrap_or("Altair GraphQL Client");
+        let assets_url = self
+            .assets_url
+            .unwrap_or("https://cdn.jsdelivr.net/npm/altair-static@5/build/dist")
+            .trim_end_matches('/');
+        r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <meta name="robots" content="noindex">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>%ALTAIR_TITLE%</title>
+    <base href="%ALTAIR_ASSETS_URL%/">
+    <link rel="icon" type="image/x-icon" href="favicon.ico">
+    <link rel="stylesheet" href="styles.css">
+  </head>
+
+  <body>
+    <app-root>
+      <div class="loading-screen styled">
+        <div class="loading-screen-inner">
+          <div class="loading-screen-logo-container">
+            <img src="assets/img/logo_350.svg" alt="Altair">
+          </div>
+          <div class="loading-screen-loading-indicator">
+            <span class="loading-indicator-dot"></span>
+            <span class="loading-indicator-dot"></span>
+            <span class="loading-indicator-dot"></span>
+          </div>
+        </div>
+      </div>
+    </app-root>
+    <script type="text/javascript" src="runtime.js"></script>
+    <script type="text/javascript" src="polyfills.js"></script>
+    <script type="text/javascript" src="main.js"></script>
+    <script>
+      const options = %ALTAIR_OPTIONS%;
+      options.endpointURL = new URL(options.endpointURL, window.location.origin).toString();
+      if (options.subscriptionsEndpoint) {
+        const url = new URL(options.subscriptionsEndpoint, window.location.origin);
+        url.protocol = url.protocol === 'https:' ? 'wss:' : 'ws:';
+        options.subscriptionsEndpoint = url.toString();
+      }
+      AltairGraphQL.init(options);
+    </script>
+  </body>
+</html>"#
+            .replace("%ALTAIR_TITLE%", title)
+            .replace("%ALTAIR_ASSETS_URL%", assets_url)
+            .replace("%ALTAIR_OPTIONS%", &super::script_json(&self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_altair_source() {
+        let source = AltairSource::build()
+            .endpoint("/graphql")
+            .subscription_endpoint("/ws")
+            .initial_query("{ hello }")
+            .initial_variables(r#"{"name": "</script>"}"#)
+            .header("Authorization", "Bearer [token]")
+            .title("My API")
+            .assets_url("/static/altair/")
+            .finish();
+
+        assert!(source.contains("<title>My API</title>"));
+        assert!(source.contains(r#"<base href="/static/altair/">"#));
+        assert!(source.contains(
+            r#"const options = {"endpointURL":"/graphql","subscriptionsEndpoint":"/ws","initialQuery":"{ hello }","initialVariables":"{\"name\": \"<\/script>\"}","initialHeaders":{"Authorization":"Bearer [token]"}};"#
+        ));
+
+        let source = AltairSource::build().endpoint("/").finish();
+        assert!(source.contains("<title>Altair GraphQL Client</title>"));
+        assert!(source
+            .contains(r#"<base href="https://cdn.jsdelivr.net/npm/altair-static@5/build/dist/">"#));
+        assert!(source.contains(r#"const options = {"endpointURL":"/"};"#));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// A builder for constructing an embedded Apollo Sandbox HTML page.
+///
+/// # Example
+///
+/// ```rust
+/// use async_graphql::http::*;
+///
+/// ApolloSandboxSource::build()
+///     .endpoint("/")
+///     .subscription_endpoint("/ws")
+///     .initial_document("{ hello }")
+///     .header("Authorization", "Bearer [token]")
+///     .finish();
+/// ```
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApolloSandboxSource<'a> {
+    #[serde(rename = "initialEndpoint")]
+    endpoint: &'a str,
+    #[serde(rename = "initialSubscriptionEndpoint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subscription_endpoint: Option<&'a str>,
+    initial_state: InitialState<'a>,
+    #[serde(skip)]
+    title: Option<&'a str>,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InitialState<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    document: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variables: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    headers: Option<HashMap<&'a str, &'a str>>,
+    include_cookies: bool,
+}
+
+impl<'a> ApolloSandboxSource<'a> {
+    /// Creates a builder for constructing an embedded Apollo Sandbox HTML
+    /// page.
+    pub fn build() -> ApolloSandboxSource<'a> {
+        Default::default()
+    }
+
+    /// Sets the endpoint of the server Apollo Sandbox will connect to.
+    #[must_use]
+    pub fn endpoint(self, endpoint: &'a str) -> ApolloSandboxSource<'a> {
+        ApolloSandboxSource { endpoint, ..self }
+    }
+
+    /// Sets the subscription endpoint of the server Apollo Sandbox will
+    /// connect to.
+    #[must_use]
+    pub fn subscription_endpoint(self, endpoint: &'a str) -> ApolloSandboxSource<'a> {
+        ApolloSandboxSource {
+            subscription_endpoint: Some(endpoint),
+            ..self
+        }
+    }
+
+    /// Sets the document initially displayed in the operation editor.
+    #[must_use]
+    pub fn initial_document(mut self, document: &'a str) -> ApolloSandboxSource<'a> {
+        self.initial_state.document = Some(document);
+        self
+    }
+
+    /// Sets the variables initially displayed in the variables editor.
+    #[must_use]
+    pub fn initial_variables(mut self, variables: serde_json::Value) -> ApolloSandboxSource<'a> {
+        self.initial_state.variables = Some(variables);
+        self
+    }
+
+    /// Sets a header initially displayed in the headers editor.
+    #[must_use]
+    pub fn header(mut self, name: &'a str, value: &'a str) -> ApolloSandboxSource<'a> {
+        self.initial_state
+            .headers
+            .get_or_insert_with(HashMap::new)
+            .insert(name, value);
+        self
+    }
+
+    /// Sets whether the cookies are sent with the requests Apollo Sandbox
+    /// will send. (default: `false`)
+    #[must_use]
+    pub fn include_cookies(mut self, include_cookies: bool) -> ApolloSandboxSource<'a> {
+        self.initial_state.include_cookies = include_cookies;
+        self
+    }
+
+    /// Sets the html document title.
+    #[must_use]
+    pub fn title(self, title: &'a str) -> ApolloSandboxSource<'a> {
+        ApolloSandboxSource {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// Returns an embedded Apollo Sandbox HTML page.
+    pub fn finish(self) -> String {
+        let title = self.title.unwrap_or("Apollo Sandbox");
+        r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <meta name="robots" content="noindex">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>%APOLLO_SANDBOX_TITLE%</title>
+    <style>
+      body {
+        height: 100%;
+        margin: 0;
+        width: 100%;
+        overflow: hidden;
+      }
+
+      #embedded-sandbox {
+        height: 100vh;
+      }
+    </style>
+  </head>
+
+  <body>
+    <div id="embedded-sandbox"></div>
+    <script src="https://embeddable-sandbox.cdn.apollographql.com/_latest/embeddable-sandbox.umd.production.min.js"></script>
+    <script>
+      const options = %APOLLO_SANDBOX_OPTIONS%;
+      options.initialEndpoint = new URL(options.initialEndpoint, window.location.origin).toString();
+      if (options.initialSubscriptionEndpoint) {
+        const url = new URL(options.initialSubscriptionEndpoint, window.location.origin);
+        url.protocol = url.protocol === 'https:' ? 'wss:' : 'ws:';
+        options.initialSubscriptionEndpoint = url.toString();
+      }
+      new window.EmbeddedSandbox({ target: '#embedded-sandbox', ...options });
+    </script>
+  </body>
+</html>"#
+            .replace("%APOLLO_SANDBOX_TITLE%", title)
+            .replace("%APOLLO_SANDBOX_OPTIONS%", &super::script_json(&self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_apollo_sandbox_source() {
+        let source = ApolloSandboxSource::build()
+            .endpoint("/graphql")
+            .subscription_endpoint("/ws")
+            .initial_document("{ hello }")
+            .initial_variables(json!({ "name": "</script>" }))
+            .header("Authorization", "Bearer [token]")
+            .include_cookies(true)
+            .title("My API")
+            .finish();
+
+        assert!(source.contains("<title>My API</title>"));
+        assert!(source.contains(
+            r#"const options = {"initialEndpoint":"/graphql","initialSubscriptionEndpoint":"/ws","initialState":{"document":"{ hello }","variables":{"name":"<\/script>"},"headers":{"Authorization":"Bearer [token]"},"includeCookies":true}};"#
+        ));
+
+        let source = ApolloSandboxSource::build().endpoint("/").finish();
+        assert!(source.contains("<title>Apollo Sandbox</title>"));
+        assert!(source.contains(
+            r#"const options = {"initialEndpoint":"/","initialState":{"includeCookies":false}};"#
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use handlebars::Handlebars;
+use indexmap::IndexMap;
 use serde::Serialize;
 
 /// Indicates whether the user agent should send or receive user credentials
@@ -19,6 +20,91 @@ pub enum Credentials {
     Omit,
 }
 
+/// The protocol GraphiQL uses to execute the subscriptions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SubscriptionProtocol {
+    /// The `graphql-ws` protocol over a WebSocket connected to the
+    /// subscription endpoint. This is the default value.
+    #[default]
+    GraphQLWs,
+    /// The `graphql-sse` protocol over Server-Sent Events.
+    ///
+    /// All the operations are sent to the subscription endpoint, or to the
+    /// endpoint if it is not set, with the `graphql-sse` client.
+    Sse,
+}
+
+/// A plugin of a GraphiQL (v2) HTML page, see [`GraphiQLSource::plugin`].
+///
+/// The URLs of the scripts and stylesheets that are not absolute are
+/// resolved against the assets URL of the page, see
+/// [`GraphiQLSource::assets_url`].
+///
+/// # Example
+///
+/// ```rust
+/// use async_graphql::http::*;
+///
+/// GraphiQLSource::build()
+///     .endpoint("/")
+///     .plugin(
+///         GraphiQLPlugin::new("MyPlugin.create")
+///             .script("/static/my-plugin.js")
+///             .props("{ collapsed: true }"),
+///     )
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GraphiQLPlugin<'a> {
+    constructor: &'a str,
+    props: Option<&'a str>,
+    scripts: Vec<&'a str>,
+    stylesheets: Vec<&'a str>,
+}
+
+impl<'a> GraphiQLPlugin<'a> {
+    /// Creates a plugin created by calling the JavaScript function
+    /// `constructor`.
+    pub fn new(constructor: &'a str) -> GraphiQLPlugin<'a> {
+        GraphiQLPlugin {
+            constructor,
+            ..Default::default()
+        }
+    }
+
+    /// The explorer plugin, which builds the queries by selecting the fields
+    /// in a tree of the schema.
+    pub fn explorer() -> GraphiQLPlugin<'a> {
+        GraphiQLPlugin::new("GraphiQLPluginExplorer.explorerPlugin")
+            .script("@graphiql/plugin-explorer/dist/index.umd.js")
+            .stylesheet("@graphiql/plugin-explorer/dist/style.css")
+    }
+
+    /// Sets the JavaScript expression of the argument passed to the
+    /// constructor of the plugin.
+    #[must_use]
+    pub fn props(self, props: &'a str) -> GraphiQLPlugin<'a> {
+        GraphiQLPlugin {
+            props: Some(props),
+            ..self
+        }
+    }
+
+    /// Adds a script to load for the plugin.
+    #[must_use]
+    pub fn script(mut self, url: &'a str) -> GraphiQLPlugin<'a> {
+        self.scripts.push(url);
+        self
+    }
+
+    /// Adds a stylesheet to load for the plugin.
+    #[must_use]
+    pub fn stylesheet(mut self, url: &'a str) -> GraphiQLPlugin<'a> {
+        self.stylesheets.push(url);
+        self
+    }
+}
+
 /// A builder for constructing a GraphiQL (v2) HTML page.
 ///
 /// # Example
@@ -40,6 +126,15 @@ pub struct GraphiQLSource<'a> {
     headers: Option<HashMap<&'a str, &'a str>>,
     title: Option<&'a str>,
     credentials: Credentials,
+    #[serde(skip)]
+    subscription_protocol: SubscriptionProtocol,
+    #[serde(skip)]
+    default_headers: IndexMap<&'a str, &'a str>,
+    #[serde(skip)]
+    plugins: Vec<GraphiQLPlugin<'a>>,
+    nonce: Option<&'a str>,
+    #[serde(skip)]
+    assets_url: Option<&'a str>,
 }
 
 impl<'a> GraphiQLSource<'a> {
@@ -62,6 +157,15 @@ impl<'a> GraphiQLSource<'a> {
         }
     }
 
+    /// Sets the protocol GraphiQL uses to execute the subscriptions.
+    #[must_use]
+    pub fn subscription_protocol(self, protocol: SubscriptionProtocol) -> GraphiQLSource<'a> {
+        GraphiQLSource {
+            subscription_protocol: protocol,
+            ..self
+        }
+    }
+
     /// Sets a header to be sent with requests GraphiQL will send.
     pub fn header(self, name: &'a str, value: &'a str) -> GraphiQLSource<'a> {
         let mut headers = match self.headers {
@@ -75,6 +179,14 @@ impl<'a> GraphiQLSource<'a> {
         }
     }
 
+    /// Sets a header in the initial content of the headers editor, which the
+    /// user can modify.
+    #[must_use]
+    pub fn default_header(mut self, name: &'a str, value: &'a str) -> GraphiQLSource<'a> {
+        self.default_headers.insert(name, value);
+        self
+    }
+
     /// Sets the html document title.
     pub fn title(self, title: &'a str) -> GraphiQLSource<'a> {
         GraphiQLSource {
@@ -91,8 +203,102 @@ impl<'a> GraphiQLSource<'a> {
         }
     }
 
+    /// Adds a plugin to GraphiQL.
+    #[must_use]
+    pub fn plugin(mut self, plugin: GraphiQLPlugin<'a>) -> GraphiQLSource<'a> {
+        self.plugins.push(plugin);
+        self
+    }
+
+    /// Sets the nonce of the scripts and styles of the page, so that they are
+    /// allowed by a `Content-Security-Policy` with the same nonce.
+    #[must_use]
+    pub fn nonce(self, nonce: &'a str) -> GraphiQLSource<'a> {
+        GraphiQLSource {
+            nonce: Some(nonce),
+            ..self
+        }
+    }
+
+    /// Sets the base URL the scripts and stylesheets are loaded from. (default:
+    /// `https://unpkg.com`)
+    ///
+    /// The assets are loaded from the same paths as on unpkg, for example
+    /// `{assets_url}/graphiql/graphiql.min.js`, so a mirror of these paths
+    /// can be served for the deployments without an internet access.
+    #[must_use]
+    pub fn assets_url(self, url: &'a str) -> GraphiQLSource<'a> {
+        GraphiQLSource {
+            assets_url: Some(url),
+            ..self
+        }
+    }
+
+    fn asset_url(&self, path: &str) -> String {
+        if path.starts_with('/') || path.contains("://") {
+            return path.to_string();
+        }
+        let assets_url = self.assets_url.unwrap_or("https://unpkg.com");
+        format!("{}/{}", assets_url.trim_end_matches('/'), path)
+    }
+
     /// Returns a GraphiQL (v2) HTML page.
     pub fn finish(self) -> String {
+        #[derive(Serialize)]
+        struct Plugin<'a> {
+            constructor: &'a str,
+            props: &'a str,
+            scripts: Vec<String>,
+            stylesheets: Vec<String>,
+        }
+
+        #[derive(Serialize)]
+        struct Template<'a> {
+            #[serde(flatten)]
+            source: &'a GraphiQLSource<'a>,
+            react_url: String,
+            react_dom_url: String,
+            graphiql_js_url: String,
+            graphiql_css_url: String,
+            graphql_sse_url: Option<String>,
+            sse_endpoint: &'a str,
+            default_headers: Option<String>,
+            plugins: Vec<Plugin<'a>>,
+        }
+
+        let sse = self.subscription_protocol == SubscriptionProtocol::Sse;
+        let template = Template {
+            source: &self,
+            react_url: self.asset_url("react@17/umd/react.development.js"),
+            react_dom_url: self.asset_url("react-dom@17/umd/react-dom.development.js"),
+            graphiql_js_url: self.asset_url("graphiql/graphiql.min.js"),
+            graphiql_css_url: self.asset_url("graphiql/graphiql.min.css"),
+            graphql_sse_url: sse.then(|| self.asset_url("graphql-sse/umd/graphql-sse.min.js")),
+            sse_endpoint: self.subscription_endpoint.unwrap_or(self.endpoint),
+            default_headers: (!self.default_headers.is_empty()).then(|| {
+                let headers = serde_json::to_string_pretty(&self.default_headers).unwrap();
+                super::script_json(&headers)
+            }),
+            plugins: self
+                .plugins
+                .iter()
+                .map(|plugin| Plugin {
+                    constructor: plugin.constructor,
+                    props: plugin.props.unwrap_or_default(),
+                    scripts: plugin
+                        .scripts
+                        .iter()
+                        .map(|url| self.asset_url(url))
+                        .collect(),
+                    stylesheets: plugin
+                        .stylesheets
+                        .iter()
+                        .map(|url| self.asset_url(url))
+                        .collect(),
+                })
+                .collect(),
+        };
+
         let mut handlebars = Handlebars::new();
         handlebars
             .register_template_string(
@@ -102,7 +308,7 @@ impl<'a> GraphiQLSource<'a> {
             .expect("Failed to register template");
 
         handlebars
-            .render("graphiql_v2_source", &self)
+            .render("graphiql_v2_source", &template)
             .expect("Failed to render template")
     }
 }
@@ -346,6 +552,113 @@ mod tests {
       );
     </script>
   </body>
+</html>"#
+        )
+    }
+
+    #[test]
+    fn test_with_customizations() {
+        let graphiql_source = GraphiQLSource::build()
+            .endpoint("/")
+            .subscription_endpoint("/sse")
+            .subscription_protocol(SubscriptionProtocol::Sse)
+            .default_header("Authorization", "Bearer </script>")
+            .plugin(GraphiQLPlugin::explorer())
+            .nonce("abc123")
+            .assets_url("/static/")
+            .finish();
+
+        assert_eq!(
+            graphiql_source,
+            r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <meta name="robots" content="noindex">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <meta name="referrer" content="origin">
+
+    <title>GraphiQL IDE</title>
+
+    <style nonce="abc123">
+      body {
+        height: 100%;
+        margin: 0;
+        width: 100%;
+        overflow: hidden;
+      }
+
+      #graphiql {
+        height: 100vh;
+      }
+    </style>
+    <script
+      crossorigin
+      src="/static/react@17/umd/react.development.js"
+      nonce="abc123"
+    ></script>
+    <script
+      crossorigin
+      src="/static/react-dom@17/umd/react-dom.development.js"
+      nonce="abc123"
+    ></script>
+    <link rel="icon" href="https://graphql.org/favicon.ico">
+    <link rel="stylesheet" href="/static/graphiql/graphiql.min.css" nonce="abc123" />
+    <link rel="stylesheet" href="/static/@graphiql/plugin-explorer/dist/style.css" nonce="abc123" />
+  </head>
+
+  <body>
+    <div id="graphiql">Loading...</div>
+    <script
+      src="/static/graphiql/graphiql.min.js"
+      type="application/javascript"
+      nonce="abc123"
+    ></script>
+    <script
+      crossorigin
+      src="/static/graphql-sse/umd/graphql-sse.min.js"
+      type="application/javascript"
+      nonce="abc123"
+    ></script>
+    <script
+      crossorigin
+      src="/static/@graphiql/plugin-explorer/dist/index.umd.js"
+      type="application/javascript"
+      nonce="abc123"
+    ></script>
+    <script nonce="abc123">
+      customFetch = (url, opts = {}) => {
+        return fetch(url, {...opts, credentials: 'same-origin'})
+      }
+
+      createUrl = (endpoint, subscription = false) => {
+        const url = new URL(endpoint, window.location.origin);
+        if (subscription) {
+          url.protocol = url.protocol === 'https:' ? 'wss:' : 'ws:';
+        }
+        return url.toString();
+      }
+
+      sseClient = graphqlSse.createClient({
+        url: createUrl('/sse'),
+        credentials: 'same-origin',
+      });
+
+      ReactDOM.render(
+        React.createElement(GraphiQL, {
+          fetcher: (params) => ({
+            subscribe: (sink) => ({ unsubscribe: sseClient.subscribe(params, sink) }),
+          }),
+          defaultHeaders: "{\n  \"Authorization\": \"Bearer <\/script>\"\n}",
+          plugins: [
+            GraphiQLPluginExplorer.explorerPlugin(),
+          ],
+          defaultEditorToolsVisibility: true,
+        }),
+        document.getElementById("graphiql")
+      );
+    </script>
+  </body>
 </html>"#
         )
     }
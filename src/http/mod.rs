@@ -1,28 +1,44 @@
 //! A helper module that supports HTTP
 
+#[cfg(feature = "altair")]
+mod altair_source;
+#[cfg(feature = "apollo_sandbox")]
+mod apollo_sandbox_source;
 #[cfg(feature = "graphiql")]
 mod graphiql_source;
 #[cfg(feature = "graphiql")]
 mod graphiql_v2_source;
 mod multipart;
+mod multipart_subscribe;
 #[cfg(feature = "playground")]
 mod playground_source;
+mod response_encoding;
 mod websocket;
 
 use std::io::ErrorKind;
 
+#[cfg(feature = "altair")]
+pub use altair_source::AltairSource;
+#[cfg(feature = "apollo_sandbox")]
+pub use apollo_sandbox_source::ApolloSandboxSource;
 use futures_util::io::{AsyncRead, AsyncReadExt};
 #[cfg(feature = "graphiql")]
 pub use graphiql_source::graphiql_source;
 #[cfg(feature = "graphiql")]
-pub use graphiql_v2_source::{Credentials, GraphiQLSource};
+pub use graphiql_v2_source::{Credentials, GraphiQLPlugin, GraphiQLSource, SubscriptionProtocol};
 use mime;
 pub use multipart::MultipartOptions;
+pub use multipart_subscribe::{
+    is_accept_multipart_subscription, multipart_subscribe_stream,
+    multipart_subscribe_stream_with_timer, MULTIPART_SUBSCRIPTION_CONTENT_TYPE,
+};
 #[cfg(feature = "playground")]
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
+pub use response_encoding::ResponseEncoding;
 use serde::Deserialize;
 pub use websocket::{
-    ClientMessage, Protocols as WebSocketProtocols, WebSocket, WsMessage, ALL_WEBSOCKET_PROTOCOLS,
+    ClientMessage, ConnectionExpiry, Protocols as WebSocketProtocols, ServerEvent, WebSocket,
+    WebSocketProtocol, WebSocketProtocolSet, WsMessage, ALL_WEBSOCKET_PROTOCOLS,
 };
 
 use crate::{BatchRequest, ParseRequestError, Request};
@@ -164,6 +180,13 @@ pub async fn receive_batch_cbor(body: impl AsyncRead) -> Result<BatchRequest, Pa
         .map_err(|e| ParseRequestError::InvalidRequest(Box::new(e)))
 }
 
+/// Serializes `value` to JSON that can be embedded in a `<script>` element of
+/// the IDE pages.
+#[cfg(any(feature = "graphiql", feature = "altair", feature = "apollo_sandbox"))]
+fn script_json(value: &impl serde::Serialize) -> String {
+    serde_json::to_string(value).unwrap().replace("</", "<\\/")
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
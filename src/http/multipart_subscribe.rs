@@ -0,0 +1,166 @@
+use std::{sync::Arc, time::Duration};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures_util::{
+    future::{self, Either},
+    stream::{BoxStream, Stream, StreamExt},
+};
+
+use crate::{
+    timer::{default_timer, delay},
+    Response, Timer,
+};
+
+/// The content type of the responses of the multipart subscription protocol.
+pub const MULTIPART_SUBSCRIPTION_CONTENT_TYPE: &str =
+    "multipart/mixed;boundary=\"graphql\";subscriptionSpec=\"1.0\"";
+
+const PART_HEADER: &[u8] = b"\r\n--graphql\r\nContent-Type: application/json\r\n\r\n";
+const EOF: &[u8] = b"\r\n--graphql--\r\n";
+
+/// Returns `true` if the `Accept` header of a request indicates that the
+/// client supports the multipart subscription protocol.
+pub fn is_accept_multipart_subscription(accept: &str) -> bool {
+    accept.split(',').any(|media_type| {
+        let mut params = media_type.split(';').map(str::trim);
+        params.next() == Some("multipart/mixed")
+            && params.any(|param| param.replace('"', "") == "subscriptionSpec=1.0")
+    })
+}
+
+/// Create a stream of the body of a response of Apollo's multipart
+/// subscription protocol.
+///
+/// Each response is sent in a `payload` part, and an empty part is sent as a
+/// heartbeat every `heartbeat_interval` while there is no response. The
+/// content type of the HTTP response should be
+/// [`MULTIPART_SUBSCRIPTION_CONTENT_TYPE`].
+///
+/// The heartbeats use [`FuturesTimer`](crate::FuturesTimer) if the
+/// `futures-timer` feature is enabled, and are not sent otherwise.
+///
+/// Reference: <https://www.apollographql.com/docs/router/executing-operations/subscription-multipart-protocol/>
+pub fn multipart_subscribe_stream<'a>(
+    input: impl Stream<Item = Response> + Send + Unpin + 'a,
+    heartbeat_interval: Duration,
+) -> BoxStream<'a, Bytes> {
+    multipart_subscribe_stream_inner(input, heartbeat_interval, default_timer())
+}
+
+/// Create a stream of the body of a response of Apollo's multipart
+/// subscription protocol, whose heartbeats use `timer`.
+///
+/// See [`multipart_subscribe_stream`].
+pub fn multipart_subscribe_stream_with_timer<'a>(
+    input: impl Stream<Item = Response> + Send + Unpin + 'a,
+    heartbeat_interval: Duration,
+    timer: impl Timer,
+) -> BoxStream<'a, Bytes> {
+    multipart_subscribe_stream_inner(input, heartbeat_interval, Some(Arc::new(timer)))
+}
+
+fn multipart_subscribe_stream_inner<'a>(
+    mut input: impl Stream<Item = Response> + Send + Unpin + 'a,
+    heartbeat_interval: Duration,
+    timer: Option<Arc<dyn Timer>>,
+) -> BoxStream<'a, Bytes> {
+    async_stream::stream! {
+        let mut heartbeat_timer = delay(timer.as_deref(), heartbeat_interval);
+        loop {
+            match future::select(input.next(), &mut heartbeat_timer).await {
+                Either::Left((Some(resp), _)) => {
+                    let payload = serde_json::json!({ "payload": resp });
+                    yield create_part(&serde_json::to_vec(&payload).unwrap());
+                    heartbeat_timer = delay(timer.as_deref(), heartbeat_interval);
+                }
+                Either::Left((None, _)) => break,
+                Either::Right(((), _)) => {
+                    yield create_part(b"{}");
+                    heartbeat_timer = delay(timer.as_deref(), heartbeat_interval);
+                }
+            }
+        }
+        yield Bytes::from_static(EOF);
+    }
+    .boxed()
+}
+
+fn create_part(body: &[u8]) -> Bytes {
+    let mut buf = BytesMut::with_capacity(PART_HEADER.len() + body.len());
+    buf.put(PART_HEADER);
+    buf.put(body);
+    buf.freeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+
+    use super::*;
+    use crate::value;
+
+    #[test]
+    fn test_is_accept_multipart_subscription() {
+        assert!(is_accept_multipart_subscription(
+            "multipart/mixed;subscriptionSpec=\"1.0\", application/json"
+        ));
+        assert!(is_accept_multipart_subscription(
+            "application/json, multipart/mixed; subscriptionSpec=1.0"
+        ));
+        assert!(!is_accept_multipart_subscription("multipart/mixed"));
+        assert!(!is_accept_multipart_subscription("application/json"));
+    }
+
+    #[tokio::test]
+    async fn test_multipart_subscribe_stream() {
+        let input = stream::iter(vec![
+            Response::new(value!({ "a": 1 })),
+            Response::new(value!({ "a": 2 })),
+        ]);
+        let body = multipart_subscribe_stream(input, Duration::from_secs(5))
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "\r\n--graphql\r\nContent-Type: application/json\r\n\r\n{\"payload\":{\"data\":{\"a\":1}}}\
+             \r\n--graphql\r\nContent-Type: application/json\r\n\r\n{\"payload\":{\"data\":{\"a\":2}}}\
+             \r\n--graphql--\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multipart_subscribe_heartbeat() {
+        let parts =
+            multipart_subscribe_stream(stream::pending::<Response>(), Duration::from_millis(10))
+                .take(2)
+                .collect::<Vec<_>>()
+                .await;
+        assert_eq!(
+            parts,
+            vec![
+                Bytes::from_static(b"\r\n--graphql\r\nContent-Type: application/json\r\n\r\n{}");
+                2
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multipart_subscribe_heartbeat_with_timer() {
+        let parts = multipart_subscribe_stream_with_timer(
+            stream::pending::<Response>(),
+            Duration::from_millis(10),
+            tokio::time::sleep,
+        )
+        .take(2)
+        .collect::<Vec<_>>()
+        .await;
+        assert_eq!(
+            parts,
+            vec![
+                Bytes::from_static(b"\r\n--graphql\r\nContent-Type: application/json\r\n\r\n{}");
+                2
+            ]
+        );
+    }
+}
@@ -15,6 +15,14 @@ use crate::Value;
 /// ```
 pub fn playground_source(config: GraphQLPlaygroundConfig) -> String {
     let title = config.title.unwrap_or("GraphQL Playground");
+    let assets_url = config
+        .assets_url
+        .unwrap_or("//cdn.jsdelivr.net/npm")
+        .trim_end_matches('/');
+    let nonce = match config.nonce {
+        Some(nonce) => format!(r#" nonce="{}""#, nonce),
+        None => String::new(),
+    };
     r##"
 <!DOCTYPE html>
 
@@ -24,14 +32,14 @@ pub fn playground_source(config: GraphQLPlaygroundConfig) -> String {
   <meta charset=utf-8 />
   <meta name="viewport" content="user-scalable=no, initial-scale=1.0, minimum-scale=1.0, maximum-scale=1.0, minimal-ui">
   <title>%GRAPHQL_PLAYGROUND_TITLE%</title>
-  <link rel="stylesheet" href="//cdn.jsdelivr.net/npm/graphql-playground-react/build/static/css/index.css" />
-  <link rel="shortcut icon" href="//cdn.jsdelivr.net/npm/graphql-playground-react/build/favicon.png" />
-  <script src="//cdn.jsdelivr.net/npm/graphql-playground-react/build/static/js/middleware.js"></script>
-  <link rel="stylesheet" href="https://fonts.googleapis.com/css?family=Open+Sans:300,400,600,700|Source+Code+Pro:400,700" />
+  <link rel="stylesheet" href="%GRAPHQL_PLAYGROUND_ASSETS_URL%/graphql-playground-react/build/static/css/index.css"%GRAPHQL_PLAYGROUND_NONCE% />
+  <link rel="shortcut icon" href="%GRAPHQL_PLAYGROUND_ASSETS_URL%/graphql-playground-react/build/favicon.png" />
+  <script src="%GRAPHQL_PLAYGROUND_ASSETS_URL%/graphql-playground-react/build/static/js/middleware.js"%GRAPHQL_PLAYGROUND_NONCE%></script>
+  <link rel="stylesheet" href="https://fonts.googleapis.com/css?family=Open+Sans:300,400,600,700|Source+Code+Pro:400,700"%GRAPHQL_PLAYGROUND_NONCE% />
 </head>
 
 <body>
-  <style type="text/css">
+  <style type="text/css"%GRAPHQL_PLAYGROUND_NONCE%>
     html {
       font-family: "Open Sans", sans-serif;
       overflow: hidden;
@@ -78,7 +86,7 @@ pub fn playground_source(config: GraphQLPlaygroundConfig) -> String {
     }
   </style>
 
-  <style type="text/css">
+  <style type="text/css"%GRAPHQL_PLAYGROUND_NONCE%>
     .fadeOut {
       -webkit-animation: fadeOut 0.5s ease-out forwards;
       animation: fadeOut 0.5s ease-out forwards;
@@ -539,7 +547,7 @@ pub fn playground_source(config: GraphQLPlaygroundConfig) -> String {
   </div>
 
   <div id="root" />
-  <script type="text/javascript">
+  <script type="text/javascript"%GRAPHQL_PLAYGROUND_NONCE%>
     window.addEventListener('load', function (event) {
 
       const loadingWrapper = document.getElementById('loading-wrapper');
@@ -559,6 +567,8 @@ pub fn playground_source(config: GraphQLPlaygroundConfig) -> String {
             Err(_) => "{}".to_string()
         })
         .replace("%GRAPHQL_PLAYGROUND_TITLE%", title)
+        .replace("%GRAPHQL_PLAYGROUND_ASSETS_URL%", assets_url)
+        .replace("%GRAPHQL_PLAYGROUND_NONCE%", &nonce)
 }
 
 /// Config for GraphQL Playground
@@ -570,6 +580,10 @@ pub struct GraphQLPlaygroundConfig<'a> {
     headers: Option<HashMap<&'a str, &'a str>>,
     settings: Option<HashMap<&'a str, Value>>,
     title: Option<&'a str>,
+    #[serde(skip)]
+    nonce: Option<&'a str>,
+    #[serde(skip)]
+    assets_url: Option<&'a str>,
 }
 
 impl<'a> GraphQLPlaygroundConfig<'a> {
@@ -581,6 +595,8 @@ impl<'a> GraphQLPlaygroundConfig<'a> {
             headers: Default::default(),
             settings: Default::default(),
             title: Default::default(),
+            nonce: Default::default(),
+            assets_url: Default::default(),
         }
     }
 
@@ -611,6 +627,24 @@ impl<'a> GraphQLPlaygroundConfig<'a> {
         self
     }
 
+    /// Set the nonce of the scripts and styles of the page, so that they are
+    /// allowed by a `Content-Security-Policy` with the same nonce.
+    #[must_use]
+    pub fn nonce(mut self, nonce: &'a str) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Set the base URL the scripts and stylesheets are loaded from, for
+    /// example `/static` to serve a copy of the `graphql-playground-react`
+    /// package for the deployments without an internet access. (default:
+    /// `//cdn.jsdelivr.net/npm`)
+    #[must_use]
+    pub fn assets_url(mut self, url: &'a str) -> Self {
+        self.assets_url = Some(url);
+        self
+    }
+
     /// Set Playground setting for per query.
     ///
     /// ```
@@ -661,4 +695,26 @@ mod tests {
         assert!(settings["array"].as_array().is_some());
         assert!(settings["object"].as_object().is_some());
     }
+
+    #[test]
+    fn test_with_nonce_and_assets_url() {
+        let source = playground_source(
+            GraphQLPlaygroundConfig::new("/")
+                .nonce("abc123")
+                .assets_url("/static/"),
+        );
+
+        assert!(source.contains(
+            r#"<script src="/static/graphql-playground-react/build/static/js/middleware.js" nonce="abc123"></script>"#
+        ));
+        assert!(source.contains(r#"<script type="text/javascript" nonce="abc123">"#));
+        assert!(!source.contains("cdn.jsdelivr.net"));
+        assert!(!source.contains("nonce\":"));
+
+        let source = playground_source(GraphQLPlaygroundConfig::new("/"));
+        assert!(source.contains(
+            r#"<script src="//cdn.jsdelivr.net/npm/graphql-playground-react/build/static/js/middleware.js"></script>"#
+        ));
+        assert!(!source.contains("nonce"));
+    }
 }
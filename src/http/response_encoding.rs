@@ -0,0 +1,152 @@
+use std::io::Error;
+
+use serde::Serialize;
+
+/// The encoding of a response body, negotiated with the `Accept` header of the
+/// request.
+///
+/// Binary values are encoded as byte strings with CBOR and MessagePack, while
+/// JSON has no binary type. The encodings depend on the enabled features, so
+/// this enum is not exhaustive.
+#[non_exhaustive]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ResponseEncoding {
+    /// `application/json`
+    #[default]
+    Json,
+    /// `application/cbor`
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    Cbor,
+    /// `application/msgpack`
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+    MsgPack,
+}
+
+impl ResponseEncoding {
+    /// Selects the encoding preferred by the `Accept` header of a request.
+    ///
+    /// The media types are ordered by their `q` parameter, and `Json` is
+    /// returned if none of them is supported.
+    pub fn from_accept(accept: &str) -> Self {
+        let mut encoding = Self::Json;
+        let mut max_quality = 0.0;
+
+        for media_type in accept.split(',') {
+            let mut params = media_type.split(';').map(str::trim);
+            let current = match params.next().and_then(Self::from_media_type) {
+                Some(encoding) => encoding,
+                None => continue,
+            };
+            let quality = params
+                .find_map(|param| param.strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality > max_quality {
+                encoding = current;
+                max_quality = quality;
+            }
+        }
+
+        encoding
+    }
+
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/json" | "application/graphql-response+json" | "*/*" => Some(Self::Json),
+            #[cfg(feature = "cbor")]
+            "application/cbor" => Some(Self::Cbor),
+            #[cfg(feature = "msgpack")]
+            "application/msgpack" | "application/x-msgpack" => Some(Self::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// Returns the content type of the response body.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            #[cfg(feature = "cbor")]
+            Self::Cbor => "application/cbor",
+            #[cfg(feature = "msgpack")]
+            Self::MsgPack => "application/msgpack",
+        }
+    }
+
+    /// Serializes a response, such as a
+    /// [`BatchResponse`](crate::BatchResponse), to the response body.
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Json => serde_json::to_vec(value).map_err(Into::into),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => serde_cbor::to_vec(value)
+                .map_err(|err| Error::new(std::io::ErrorKind::InvalidData, err)),
+            #[cfg(feature = "msgpack")]
+            Self::MsgPack => rmp_serde::to_vec_named(value)
+                .map_err(|err| Error::new(std::io::ErrorKind::InvalidData, err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{value, Response};
+
+    #[test]
+    fn test_from_accept() {
+        assert_eq!(ResponseEncoding::from_accept(""), ResponseEncoding::Json);
+        assert_eq!(
+            ResponseEncoding::from_accept("text/html, application/json"),
+            ResponseEncoding::Json
+        );
+        #[cfg(feature = "cbor")]
+        assert_eq!(
+            ResponseEncoding::from_accept("application/json;q=0.9, application/cbor"),
+            ResponseEncoding::Cbor
+        );
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            ResponseEncoding::from_accept("application/msgpack, application/json;q=0.5"),
+            ResponseEncoding::MsgPack
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        let resp = Response::new(value!({ "a": 1 }));
+        assert_eq!(
+            ResponseEncoding::Json.encode(&resp).unwrap(),
+            br#"{"data":{"a":1}}"#
+        );
+
+        #[cfg(any(feature = "cbor", feature = "msgpack"))]
+        let resp = Response::new(value!({ "a": crate::Value::Binary(vec![1, 2, 3].into()) }));
+        #[cfg(feature = "cbor")]
+        assert_eq!(
+            ResponseEncoding::Cbor.encode(&resp).unwrap(),
+            serde_cbor::to_vec(&serde_cbor::Value::Map(
+                [(
+                    serde_cbor::Value::Text("data".to_string()),
+                    serde_cbor::Value::Map(
+                        [(
+                            serde_cbor::Value::Text("a".to_string()),
+                            serde_cbor::Value::Bytes(vec![1, 2, 3]),
+                        )]
+                        .into_iter()
+                        .collect()
+                    ),
+                )]
+                .into_iter()
+                .collect()
+            ))
+            .unwrap()
+        );
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            ResponseEncoding::MsgPack.encode(&resp).unwrap(),
+            [0x81, 0xa4, b'd', b'a', b't', b'a', 0x81, 0xa1, b'a', 0xc4, 3, 1, 2, 3]
+        );
+    }
+}
@@ -6,6 +6,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::SystemTime,
 };
 
 use futures_util::{
@@ -16,7 +17,10 @@ use futures_util::{
 use pin_project_lite::pin_project;
 use serde::{Deserialize, Serialize};
 
-use crate::{Data, Error, Executor, Request, Response, Result};
+use crate::{
+    timer::{default_timer, delay},
+    Data, Error, Executor, Request, Response, Result, Timer,
+};
 
 /// All known protocols based on WebSocket.
 pub const ALL_WEBSOCKET_PROTOCOLS: [&str; 2] = ["graphql-transport-ws", "graphql-ws"];
@@ -73,18 +77,25 @@ pin_project! {
     pub struct WebSocket<S, E, OnInit> {
         on_connection_init: Option<OnInit>,
         init_fut: Option<BoxFuture<'static, Result<Data>>>,
+        on_connection_refresh: Option<OnConnRefreshFn>,
+        refresh_fut: Option<BoxFuture<'static, Result<Data>>>,
         connection_data: Option<Data>,
         data: Option<Arc<Data>>,
+        expiry: Option<(SystemTime, BoxFuture<'static, ()>)>,
+        timer: Option<Arc<dyn Timer>>,
         executor: E,
         streams: HashMap<String, Pin<Box<dyn Stream<Item = Response> + Send>>>,
         #[pin]
         stream: S,
-        protocol: Protocols,
+        protocol: Arc<dyn WebSocketProtocol>,
     }
 }
 
-type MessageMapStream<S> =
-    futures_util::stream::Map<S, fn(<S as Stream>::Item) -> serde_json::Result<ClientMessage>>;
+type MessageMapStream<S> = futures_util::stream::Map<S, DecodeMessageFn<<S as Stream>::Item>>;
+
+type DecodeMessageFn<T> = Box<dyn FnMut(T) -> serde_json::Result<ClientMessage> + Send + Sync>;
+
+type OnConnRefreshFn = Box<dyn FnMut(serde_json::Value) -> BoxFuture<'static, Result<Data>> + Send>;
 
 type DefaultOnConnInitType = fn(serde_json::Value) -> Ready<Result<Data>>;
 
@@ -98,12 +109,20 @@ where
     S: Stream<Item = serde_json::Result<ClientMessage>>,
 {
     /// Create a new websocket from [`ClientMessage`] stream.
-    pub fn from_message_stream(executor: E, stream: S, protocol: Protocols) -> Self {
+    pub fn from_message_stream(executor: E, stream: S, protocol: impl WebSocketProtocol) -> Self {
+        Self::with_protocol(executor, stream, Arc::new(protocol))
+    }
+
+    fn with_protocol(executor: E, stream: S, protocol: Arc<dyn WebSocketProtocol>) -> Self {
         WebSocket {
             on_connection_init: Some(default_on_connection_init),
             init_fut: None,
+            on_connection_refresh: None,
+            refresh_fut: None,
             connection_data: None,
             data: None,
+            expiry: None,
+            timer: default_timer(),
             executor,
             streams: HashMap::new(),
             stream,
@@ -119,10 +138,17 @@ where
     S::Item: AsRef<[u8]>,
 {
     /// Create a new websocket from bytes stream.
-    pub fn new(executor: E, stream: S, protocol: Protocols) -> Self {
-        let stream = stream
-            .map(ClientMessage::from_bytes as fn(S::Item) -> serde_json::Result<ClientMessage>);
-        WebSocket::from_message_stream(executor, stream, protocol)
+    ///
+    /// The messages are decoded with [`WebSocketProtocol::decode`].
+    pub fn new(executor: E, stream: S, protocol: impl WebSocketProtocol) -> Self {
+        let protocol: Arc<dyn WebSocketProtocol> = Arc::new(protocol);
+        let decode_protocol = protocol.clone();
+        let stream =
+            stream.map(
+                Box::new(move |message: S::Item| decode_protocol.decode(message.as_ref()))
+                    as DecodeMessageFn<S::Item>,
+            );
+        WebSocket::with_protocol(executor, stream, protocol)
     }
 }
 
@@ -143,11 +169,43 @@ where
         self
     }
 
+    /// Specify the timer used to close the connection when its
+    /// [`ConnectionExpiry`] is reached.
+    ///
+    /// By default, it is [`FuturesTimer`](crate::FuturesTimer) if the
+    /// `futures-timer` feature is enabled. Without a timer, the expiry is only
+    /// checked when the connection receives a message.
+    #[must_use]
+    pub fn timer(mut self, timer: impl Timer) -> Self {
+        self.timer = Some(Arc::new(timer));
+        self
+    }
+
     /// Specify a connection initialize callback function.
     ///
     /// This function if present, will be called with the data sent by the
     /// client in the [`GQL_CONNECTION_INIT` message](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md#gql_connection_init).
     /// From that point on the returned data will be accessible to all requests.
+    ///
+    /// The returned data is merged into the data specified by
+    /// [`connection_data`](Self::connection_data), so the values of both are
+    /// accessible to all operations of the connection with
+    /// [`Context::data`](crate::Context::data).
+    ///
+    /// ```
+    /// use async_graphql::{http::*, *};
+    ///
+    /// struct Token(String);
+    ///
+    /// # fn build(executor: impl Executor, stream: futures_util::stream::Empty<String>) {
+    /// WebSocket::new(executor, stream, WebSocketProtocols::GraphQLWS).on_connection_init(
+    ///     |payload| async move {
+    ///         let token = payload["token"].as_str().ok_or("Token is required.")?;
+    ///         Ok(Data::default().data(Token(token.to_string())))
+    ///     },
+    /// );
+    /// # }
+    /// ```
     #[must_use]
     pub fn on_connection_init<F, R>(self, callback: F) -> WebSocket<S, E, F>
     where
@@ -157,14 +215,62 @@ where
         WebSocket {
             on_connection_init: Some(callback),
             init_fut: self.init_fut,
+            on_connection_refresh: self.on_connection_refresh,
+            refresh_fut: self.refresh_fut,
             connection_data: self.connection_data,
             data: self.data,
+            expiry: self.expiry,
+            timer: self.timer,
             executor: self.executor,
             streams: self.streams,
             stream: self.stream,
             protocol: self.protocol,
         }
     }
+
+    /// Specify a connection refresh callback function.
+    ///
+    /// This function will be called with the payload of each
+    /// `connection_refresh` message sent by the client, usually to
+    /// re-validate the credentials of a long-lived connection before they
+    /// expire. If it succeeds, the returned data replaces the data returned by
+    /// the [`on_connection_init`](Self::on_connection_init) callback (or by the
+    /// previous refresh) for the following operations, and a
+    /// `connection_refresh_ack` message is sent to the client. Otherwise the
+    /// active subscriptions are stopped, the following operations are
+    /// rejected and the connection error of the protocol is sent to the
+    /// client.
+    ///
+    /// If no callback is specified, the `connection_refresh` messages close
+    /// the connection.
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// use async_graphql::{http::*, *};
+    ///
+    /// struct Token(String);
+    ///
+    /// # fn build(executor: impl Executor, stream: futures_util::stream::Empty<String>) {
+    /// WebSocket::new(executor, stream, WebSocketProtocols::GraphQLWS).on_connection_refresh(
+    ///     |payload| async move {
+    ///         let token = payload["token"].as_str().ok_or("Token is required.")?;
+    ///         Ok(Data::default()
+    ///             .data(Token(token.to_string()))
+    ///             .data(ConnectionExpiry(SystemTime::now() + Duration::from_secs(3600))))
+    ///     },
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn on_connection_refresh<F, R>(mut self, mut callback: F) -> Self
+    where
+        F: FnMut(serde_json::Value) -> R + Send + 'static,
+        R: Future<Output = Result<Data>> + Send + 'static,
+    {
+        self.on_connection_refresh = Some(Box::new(move |payload| Box::pin(callback(payload))));
+        self
+    }
 }
 
 impl<S, E, OnInit, InitFut> Stream for WebSocket<S, E, OnInit>
@@ -179,7 +285,19 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
-        if this.init_fut.is_none() {
+        if let Some((expiry, delay)) = this.expiry {
+            if delay.poll_unpin(cx).is_ready() || SystemTime::now() >= *expiry {
+                *this.expiry = None;
+                this.streams.clear();
+                *this.data = None;
+                return Poll::Ready(Some(WsMessage::Close(
+                    4403,
+                    "The connection has expired.".to_string(),
+                )));
+            }
+        }
+
+        if this.init_fut.is_none() && this.refresh_fut.is_none() {
             while let Poll::Ready(message) = Pin::new(&mut this.stream).poll_next(cx) {
                 let message = match message {
                     Some(message) => message,
@@ -199,24 +317,10 @@ where
                             }));
                             break;
                         } else {
-                            match this.protocol {
-                                Protocols::SubscriptionsTransportWS => {
-                                    return Poll::Ready(Some(WsMessage::Text(
-                                        serde_json::to_string(&ServerMessage::ConnectionError {
-                                            payload: Error::new(
-                                                "Too many initialisation requests.",
-                                            ),
-                                        })
-                                        .unwrap(),
-                                    )));
-                                }
-                                Protocols::GraphQLWS => {
-                                    return Poll::Ready(Some(WsMessage::Close(
-                                        4429,
-                                        "Too many initialisation requests.".to_string(),
-                                    )));
-                                }
-                            }
+                            return Poll::Ready(Some(
+                                this.protocol
+                                    .encode(ServerEvent::TooManyInitialisationRequests),
+                            ));
                         }
                     }
                     ClientMessage::Start {
@@ -235,12 +339,32 @@ where
                             )));
                         }
                     }
+                    ClientMessage::ConnectionRefresh { payload } => {
+                        if this.data.is_none() {
+                            return Poll::Ready(Some(WsMessage::Close(
+                                1011,
+                                "The handshake is not completed.".to_string(),
+                            )));
+                        }
+                        match this.on_connection_refresh {
+                            Some(on_connection_refresh) => {
+                                *this.refresh_fut =
+                                    Some(on_connection_refresh(payload.unwrap_or_default()));
+                                break;
+                            }
+                            None => {
+                                return Poll::Ready(Some(WsMessage::Close(
+                                    4400,
+                                    "Connection refresh is not supported.".to_string(),
+                                )));
+                            }
+                        }
+                    }
                     ClientMessage::Stop { id } => {
                         if this.streams.remove(&id).is_some() {
-                            return Poll::Ready(Some(WsMessage::Text(
-                                serde_json::to_string(&ServerMessage::Complete { id: &id })
-                                    .unwrap(),
-                            )));
+                            return Poll::Ready(Some(
+                                this.protocol.encode(ServerEvent::Complete { id: &id }),
+                            ));
                         }
                     }
                     // Note: in the revised `graphql-ws` spec, there is no equivalent to the
@@ -249,9 +373,9 @@ where
                     ClientMessage::ConnectionTerminate => return Poll::Ready(None),
                     // Pong must be sent in response from the receiving party as soon as possible.
                     ClientMessage::Ping { .. } => {
-                        return Poll::Ready(Some(WsMessage::Text(
-                            serde_json::to_string(&ServerMessage::Pong { payload: None }).unwrap(),
-                        )));
+                        return Poll::Ready(Some(
+                            this.protocol.encode(ServerEvent::Pong { payload: None }),
+                        ));
                     }
                     ClientMessage::Pong { .. } => {
                         // Do nothing...
@@ -265,24 +389,50 @@ where
                 *this.init_fut = None;
                 return match res {
                     Ok(data) => {
-                        let mut ctx_data = this.connection_data.take().unwrap_or_default();
+                        *this.expiry = expiry_delay(&data, this.timer.as_deref());
+                        let connection_data =
+                            Arc::new(this.connection_data.take().unwrap_or_default());
+                        let mut ctx_data = Data::with_parent(connection_data);
                         ctx_data.merge(data);
                         *this.data = Some(Arc::new(ctx_data));
-                        Poll::Ready(Some(WsMessage::Text(
-                            serde_json::to_string(&ServerMessage::ConnectionAck).unwrap(),
-                        )))
+                        Poll::Ready(Some(this.protocol.encode(ServerEvent::ConnectionAck)))
+                    }
+                    Err(err) => Poll::Ready(Some(
+                        this.protocol
+                            .encode(ServerEvent::ConnectionError(Error::new(err.message))),
+                    )),
+                };
+            }
+        }
+
+        if let Some(refresh_fut) = this.refresh_fut {
+            if let Poll::Ready(res) = refresh_fut.poll_unpin(cx) {
+                *this.refresh_fut = None;
+                return match res {
+                    Ok(data) => {
+                        *this.expiry = expiry_delay(&data, this.timer.as_deref());
+                        // the running subscriptions keep the previous data
+                        let connection_data = this
+                            .data
+                            .as_ref()
+                            .and_then(|data| data.parent().cloned())
+                            .unwrap_or_default();
+                        let mut ctx_data = Data::with_parent(connection_data);
+                        ctx_data.merge(data);
+                        *this.data = Some(Arc::new(ctx_data));
+                        Poll::Ready(Some(
+                            this.protocol.encode(ServerEvent::ConnectionRefreshAck),
+                        ))
+                    }
+                    Err(err) => {
+                        *this.expiry = None;
+                        this.streams.clear();
+                        *this.data = None;
+                        Poll::Ready(Some(
+                            this.protocol
+                                .encode(ServerEvent::ConnectionError(Error::new(err.message))),
+                        ))
                     }
-                    Err(err) => match this.protocol {
-                        Protocols::SubscriptionsTransportWS => Poll::Ready(Some(WsMessage::Text(
-                            serde_json::to_string(&ServerMessage::ConnectionError {
-                                payload: Error::new(err.message),
-                            })
-                            .unwrap(),
-                        ))),
-                        Protocols::GraphQLWS => {
-                            Poll::Ready(Some(WsMessage::Close(1002, err.message)))
-                        }
-                    },
                 };
             }
         }
@@ -290,16 +440,16 @@ where
         for (id, stream) in &mut *this.streams {
             match Pin::new(stream).poll_next(cx) {
                 Poll::Ready(Some(payload)) => {
-                    return Poll::Ready(Some(WsMessage::Text(
-                        serde_json::to_string(&this.protocol.next_message(id, payload)).unwrap(),
-                    )));
+                    return Poll::Ready(Some(
+                        this.protocol.encode(ServerEvent::Next { id, payload }),
+                    ));
                 }
                 Poll::Ready(None) => {
                     let id = id.clone();
                     this.streams.remove(&id);
-                    return Poll::Ready(Some(WsMessage::Text(
-                        serde_json::to_string(&ServerMessage::Complete { id: &id }).unwrap(),
-                    )));
+                    return Poll::Ready(Some(
+                        this.protocol.encode(ServerEvent::Complete { id: &id }),
+                    ));
                 }
                 Poll::Pending => {}
             }
@@ -309,6 +459,29 @@ where
     }
 }
 
+/// The expiry of the credentials of a WebSocket connection.
+///
+/// If the data returned by the
+/// [`on_connection_init`](WebSocket::on_connection_init) or
+/// [`on_connection_refresh`](WebSocket::on_connection_refresh) callback
+/// contains it, the connection is closed at this time unless the client
+/// refreshes it before.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ConnectionExpiry(pub SystemTime);
+
+fn expiry_delay(
+    data: &Data,
+    timer: Option<&dyn Timer>,
+) -> Option<(SystemTime, BoxFuture<'static, ()>)> {
+    data.data_opt::<ConnectionExpiry>().map(|expiry| {
+        let duration = expiry
+            .0
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+        (expiry.0, delay(timer, duration))
+    })
+}
+
 /// Specification of which GraphQL Over WebSockets protocol is being utilized
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Protocols {
@@ -326,13 +499,41 @@ impl Protocols {
             Protocols::GraphQLWS => "graphql-transport-ws",
         }
     }
+}
 
-    #[inline]
-    fn next_message<'s>(&self, id: &'s str, payload: Response) -> ServerMessage<'s> {
-        match self {
-            Protocols::SubscriptionsTransportWS => ServerMessage::Data { id, payload },
-            Protocols::GraphQLWS => ServerMessage::Next { id, payload },
-        }
+impl WebSocketProtocol for Protocols {
+    fn sec_websocket_protocol(&self) -> &str {
+        Protocols::sec_websocket_protocol(self)
+    }
+
+    fn encode(&self, event: ServerEvent<'_>) -> WsMessage {
+        let message = match (self, event) {
+            (_, ServerEvent::ConnectionAck) => ServerMessage::ConnectionAck,
+            (_, ServerEvent::ConnectionRefreshAck) => ServerMessage::ConnectionRefreshAck,
+            (Protocols::SubscriptionsTransportWS, ServerEvent::ConnectionError(err)) => {
+                ServerMessage::ConnectionError { payload: err }
+            }
+            (Protocols::GraphQLWS, ServerEvent::ConnectionError(err)) => {
+                return WsMessage::Close(1002, err.message);
+            }
+            (Protocols::SubscriptionsTransportWS, ServerEvent::TooManyInitialisationRequests) => {
+                ServerMessage::ConnectionError {
+                    payload: Error::new("Too many initialisation requests."),
+                }
+            }
+            (Protocols::GraphQLWS, ServerEvent::TooManyInitialisationRequests) => {
+                return WsMessage::Close(4429, "Too many initialisation requests.".to_string());
+            }
+            (Protocols::SubscriptionsTransportWS, ServerEvent::Next { id, payload }) => {
+                ServerMessage::Data { id, payload }
+            }
+            (Protocols::GraphQLWS, ServerEvent::Next { id, payload }) => {
+                ServerMessage::Next { id, payload }
+            }
+            (_, ServerEvent::Complete { id }) => ServerMessage::Complete { id },
+            (_, ServerEvent::Pong { payload }) => ServerMessage::Pong { payload },
+        };
+        WsMessage::Text(serde_json::to_string(&message).unwrap())
     }
 }
 
@@ -353,6 +554,171 @@ impl std::str::FromStr for Protocols {
     }
 }
 
+/// A GraphQL over WebSocket protocol, which decodes the messages received
+/// from the client and encodes the messages sent to the client.
+///
+/// [`Protocols`] implements the built-in protocols. Other protocols, such as a
+/// legacy in-house protocol, can implement this trait to be executed by
+/// [`WebSocket`], and be registered in a [`WebSocketProtocolSet`] to be
+/// negotiated with the clients.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::http::*;
+///
+/// /// A protocol which sends the results without their envelope.
+/// struct RawResultsProtocol;
+///
+/// impl WebSocketProtocol for RawResultsProtocol {
+///     fn sec_websocket_protocol(&self) -> &str {
+///         "raw-results"
+///     }
+///
+///     fn encode(&self, event: ServerEvent<'_>) -> WsMessage {
+///         match event {
+///             ServerEvent::Next { payload, .. } => {
+///                 WsMessage::Text(serde_json::to_string(&payload).unwrap())
+///             }
+///             event => WebSocketProtocols::GraphQLWS.encode(event),
+///         }
+///     }
+/// }
+///
+/// let protocols = WebSocketProtocolSet::new().register(RawResultsProtocol);
+/// let protocol = protocols.negotiate("raw-results, graphql-ws").unwrap();
+/// assert_eq!(protocol.sec_websocket_protocol(), "raw-results");
+/// ```
+pub trait WebSocketProtocol: Send + Sync + 'static {
+    /// Returns the `Sec-WebSocket-Protocol` header value for the protocol.
+    fn sec_websocket_protocol(&self) -> &str;
+
+    /// Decodes a message received from the client.
+    ///
+    /// The default implementation decodes the JSON messages of the built-in
+    /// protocols.
+    fn decode(&self, message: &[u8]) -> serde_json::Result<ClientMessage> {
+        ClientMessage::from_bytes(message)
+    }
+
+    /// Encodes a message sent to the client.
+    fn encode(&self, event: ServerEvent<'_>) -> WsMessage;
+}
+
+impl<T: WebSocketProtocol + ?Sized> WebSocketProtocol for Arc<T> {
+    fn sec_websocket_protocol(&self) -> &str {
+        T::sec_websocket_protocol(self)
+    }
+
+    fn decode(&self, message: &[u8]) -> serde_json::Result<ClientMessage> {
+        T::decode(self, message)
+    }
+
+    fn encode(&self, event: ServerEvent<'_>) -> WsMessage {
+        T::encode(self, event)
+    }
+}
+
+/// A message sent to the client by a [`WebSocket`], which is encoded by its
+/// [`WebSocketProtocol`].
+///
+/// Events may be added in the future, which a protocol can encode like one of
+/// the built-in protocols.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(clippy::large_enum_variant)] // Response is at fault
+pub enum ServerEvent<'a> {
+    /// The connection initialisation is accepted.
+    ConnectionAck,
+    /// The connection refresh is accepted.
+    ConnectionRefreshAck,
+    /// The connection initialisation or refresh is rejected.
+    ConnectionError(Error),
+    /// The client requested the initialisation of the connection more than
+    /// once.
+    TooManyInitialisationRequests,
+    /// A result of an operation.
+    Next {
+        /// Message ID
+        id: &'a str,
+        /// The result
+        payload: Response,
+    },
+    /// An operation is completed.
+    Complete {
+        /// Message ID
+        id: &'a str,
+    },
+    /// The response to a ping message.
+    Pong {
+        /// Additional details about the pong.
+        payload: Option<serde_json::Value>,
+    },
+}
+
+/// The protocols accepted by a WebSocket endpoint, which negotiates the
+/// protocol of a connection with the `Sec-WebSocket-Protocol` header.
+///
+/// It contains the built-in protocols by default, see
+/// [`WebSocketProtocol`] to register other protocols.
+#[derive(Clone)]
+pub struct WebSocketProtocolSet(Vec<Arc<dyn WebSocketProtocol>>);
+
+impl Default for WebSocketProtocolSet {
+    fn default() -> Self {
+        Self(vec![
+            Arc::new(Protocols::GraphQLWS),
+            Arc::new(Protocols::SubscriptionsTransportWS),
+        ])
+    }
+}
+
+impl WebSocketProtocolSet {
+    /// Create a set of the built-in protocols.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Register a protocol.
+    ///
+    /// It replaces the protocol with the same `Sec-WebSocket-Protocol` header
+    /// value, if any.
+    #[must_use]
+    pub fn register(mut self, protocol: impl WebSocketProtocol) -> Self {
+        self.0.retain(|registered| {
+            !registered
+                .sec_websocket_protocol()
+                .eq_ignore_ascii_case(protocol.sec_websocket_protocol())
+        });
+        self.0.push(Arc::new(protocol));
+        self
+    }
+
+    /// Returns the `Sec-WebSocket-Protocol` header values of the protocols,
+    /// to be passed to the WebSocket upgrade of the HTTP server.
+    pub fn sec_websocket_protocols(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(WebSocketProtocol::sec_websocket_protocol)
+    }
+
+    /// Selects the protocol of a connection from the value of its
+    /// `Sec-WebSocket-Protocol` header, which lists the protocols supported by
+    /// the client in order of preference.
+    ///
+    /// Returns `None` if none of the protocols are supported.
+    pub fn negotiate(&self, sec_websocket_protocol: &str) -> Option<Arc<dyn WebSocketProtocol>> {
+        sec_websocket_protocol.split(',').find_map(|name| {
+            self.0
+                .iter()
+                .find(|protocol| {
+                    protocol
+                        .sec_websocket_protocol()
+                        .eq_ignore_ascii_case(name.trim())
+                })
+                .cloned()
+        })
+    }
+}
+
 /// A websocket message received from the client
 #[derive(Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -372,6 +738,11 @@ pub enum ClientMessage {
         /// to add files uploads.
         payload: Request,
     },
+    /// A refresh of the connection, usually carrying renewed credentials
+    ConnectionRefresh {
+        /// Optional refresh payload from the client
+        payload: Option<serde_json::Value>,
+    },
     /// The end of a Websocket subscription
     #[serde(alias = "complete")]
     Stop {
@@ -414,6 +785,7 @@ enum ServerMessage<'a> {
         payload: Error,
     },
     ConnectionAck,
+    ConnectionRefreshAck,
     /// subscriptions-transport-ws protocol next payload
     Data {
         id: &'a str,
@@ -65,9 +65,11 @@
 //!   extension](extensions/apollo_persisted_queries/struct.
 //!   ApolloPersistedQueries.html).
 //! - `log`: Enable the [logger extension](extensions/struct.Logger.html).
-//! - `tracing`: Enable the [tracing extension](extensions/struct.Tracing.html).
+//! - `tracing`: Enable the [tracing extension](extensions/struct.Tracing.html)
+//!   and the [structured logger extension](extensions/struct.StructuredLogger.html).
 //! - `opentelemetry`: Enable the [OpenTelemetry
-//!   extension](extensions/struct.OpenTelemetry.html).
+//!   extension](extensions/struct.OpenTelemetry.html) and the [OpenTelemetry
+//!   metrics extension](extensions/struct.OpenTelemetryMetrics.html).
 //! - `unblock`: Support [asynchronous reader for
 //!   Upload](types/struct.Upload.html)
 //! - `bson`: Integrate with the [`bson` crate](https://crates.io/crates/bson).
@@ -79,10 +81,14 @@
 //! - `string_number`: Enable the
 //!   [StringNumber](types/struct.StringNumber.html).
 //! - `dataloader`: Support [DataLoader](dataloader/struct.DataLoader.html).
+//! - `dataloader-tokio`, `dataloader-async-std`, `dataloader-wasm`: Support the
+//!   [DataLoader](dataloader/struct.DataLoader.html) runtime of Tokio,
+//!   async-std or WebAssembly.
 //! - `secrecy`: Integrate with the [`secrecy` crate](https://crates.io/crates/secrecy).
 //! - `decimal`: Integrate with the [`rust_decimal` crate](https://crates.io/crates/rust_decimal).
 //! - `bigdecimal`: Integrate with the [`bigdecimal` crate](https://crates.io/crates/bigdecimal).
 //! - `cbor`: Support for [serde_cbor](https://crates.io/crates/serde_cbor).
+//! - `msgpack`: Support MessagePack response bodies with [rmp-serde](https://crates.io/crates/rmp-serde).
 //! - `smol_str`: Integrate with the [`smol_str` crate](https://crates.io/crates/smol_str).
 //! - `hashbrown`: Integrate with the [`hashbrown` crate](https://github.com/rust-lang/hashbrown).
 //! - `time`: Integrate with the [`time` crate](https://github.com/time-rs/time).
@@ -91,6 +97,14 @@
 //! - `fast_chemail`: Integrate with the [`fast_chemail` crate](https://crates.io/crates/fast_chemail).
 //! - `tempfile`: Save the uploaded content in the temporary file.
 //! - `dynamic-schema`: Support dynamic schema.
+//! - `fragment-arguments`: Support fragment arguments, like `...Greeting(name: "Bob")`.
+//! - `futures-timer` (default): Use the timer of the [`futures-timer`
+//!   crate](https://crates.io/crates/futures-timer) by default, see [`Timer`].
+//! - `altair`: Enables the [Altair GraphQL Client](https://github.com/altair-graphql/altair)
+//!   integration.
+//! - `apollo_sandbox`: Enables the [Apollo
+//!   Sandbox](https://www.apollographql.com/docs/graphos/explorer/sandbox) integration.
+//! - `test-utils`: Helpers for testing schemas, see the `test` module.
 //!
 //! ## Integrations
 //!
@@ -182,17 +196,33 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 mod base;
+mod client_info;
 mod custom_directive;
+mod entity_cache;
 mod error;
+mod execution_stats;
 mod executor;
+mod export_directive;
+mod field_cache;
 mod guard;
+mod live_query;
+mod locale;
 mod look_ahead;
 mod model;
+mod mutation_transaction;
+mod object_extension;
+mod operation_signature;
+mod provider;
 mod request;
 mod response;
 mod schema;
+mod schema_diff;
+mod source_preview;
+mod static_query;
 mod subscription;
+mod timer;
 mod validation;
+mod visibility;
 
 pub mod context;
 #[cfg(feature = "dataloader")]
@@ -203,7 +233,11 @@ pub mod dataloader;
 pub mod dynamic;
 pub mod extensions;
 pub mod http;
+pub mod meta;
 pub mod resolver_utils;
+#[cfg(feature = "test-utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+pub mod test;
 pub mod types;
 #[doc(hidden)]
 pub mod validators;
@@ -224,6 +258,7 @@ pub use base::{
     ComplexObject, Description, InputObjectType, InputType, InterfaceType, ObjectType,
     OneofObjectType, OutputType, TypeName, UnionType,
 };
+pub use client_info::ClientInfo;
 #[doc(hidden)]
 pub use context::ContextSelectionSet;
 pub use context::*;
@@ -232,28 +267,46 @@ pub use error::{
     Error, ErrorExtensionValues, ErrorExtensions, InputValueError, InputValueResult,
     ParseRequestError, PathSegment, Result, ResultExt, ServerError, ServerResult,
 };
+pub use execution_stats::ExecutionStats;
 pub use executor::Executor;
 pub use extensions::ResolveFut;
+pub use field_cache::{FieldCacheEntry, MemoryFieldCacheStorage};
 #[doc(hidden)]
 pub use futures_util;
-pub use guard::{Guard, GuardExt};
+pub use guard::{Guard, GuardDenial, GuardExt};
 #[doc(hidden)]
 pub use indexmap;
-pub use look_ahead::Lookahead;
+pub use live_query::{LiveQueryStore, MemoryLiveQueryStore};
+pub use locale::Locale;
+pub use look_ahead::{Lookahead, RelayConnection};
+pub use mutation_transaction::MutationTransaction;
+pub use object_extension::ObjectExtension;
+pub use operation_signature::{operation_signature, OperationSignatureOptions};
 #[doc(no_inline)]
 pub use parser::{Pos, Positioned};
-pub use registry::{CacheControl, SDLExportOptions};
+pub use provider::Provider;
+pub use registry::{CacheControl, RegistryStats, SDLExportAudience, SDLExportOptions};
 pub use request::{BatchRequest, Request};
 #[doc(no_inline)]
 pub use resolver_utils::{ContainerType, EnumType, ScalarType};
-pub use response::{BatchResponse, Response};
-pub use schema::{IntrospectionMode, Schema, SchemaBuilder, SchemaEnv};
+pub use response::{BatchResponse, OperationMetadata, Response};
+pub use schema::{IntrospectionMode, Schema, SchemaBuilder, SchemaEnv, SchemaVariantBuilder};
+pub use schema_diff::{diff_schema, Criticality, SchemaChange, SchemaChangeKind};
 #[doc(hidden)]
 pub use static_assertions;
-pub use subscription::SubscriptionType;
+pub use static_query::StaticQuery;
+#[doc(hidden)]
+pub use subscription::SubscriptionItemResult;
+pub use subscription::{
+    MemoryReplayBuffer, Replay, ReplayBuffer, SubscriptionStreamExt, SubscriptionType,
+};
+#[cfg(feature = "futures-timer")]
+pub use timer::FuturesTimer;
+pub use timer::Timer;
 pub use types::*;
 pub use validation::{ValidationMode, ValidationResult, VisitorContext};
-pub use validators::CustomValidator;
+pub use validators::{AsyncCustomValidator, CustomValidator, ValidateInput};
+pub use visibility::VisibilityProfile;
 
 /// An alias of [async_graphql::Error](struct.Error.html). Present for backward
 /// compatibility reasons.
@@ -263,6 +316,8 @@ pub type FieldError = Error;
 /// compatibility reasons.
 pub type FieldResult<T> = Result<T>;
 
+#[doc = include_str!("docs/graphql_query.md")]
+pub use async_graphql_derive::graphql_query;
 #[doc = include_str!("docs/complex_object.md")]
 pub use async_graphql_derive::ComplexObject;
 #[doc = include_str!("docs/description.md")]
@@ -271,6 +326,8 @@ pub use async_graphql_derive::Description;
 pub use async_graphql_derive::Directive;
 #[doc = include_str!("docs/enum.md")]
 pub use async_graphql_derive::Enum;
+#[doc = include_str!("docs/extend_object.md")]
+pub use async_graphql_derive::ExtendObject;
 #[doc = include_str!("docs/input_object.md")]
 pub use async_graphql_derive::InputObject;
 #[doc = include_str!("docs/interface.md")]
@@ -281,10 +338,14 @@ pub use async_graphql_derive::MergedObject;
 pub use async_graphql_derive::MergedSubscription;
 #[doc = include_str!("docs/newtype.md")]
 pub use async_graphql_derive::NewType;
+#[doc = include_str!("docs/newtype_id.md")]
+pub use async_graphql_derive::NewTypeId;
 #[doc = include_str!("docs/object.md")]
 pub use async_graphql_derive::Object;
 #[doc = include_str!("docs/oneof_object.md")]
 pub use async_graphql_derive::OneofObject;
+#[doc = include_str!("docs/patch.md")]
+pub use async_graphql_derive::Patch;
 #[doc = include_str!("docs/scalar.md")]
 pub use async_graphql_derive::Scalar;
 #[doc = include_str!("docs/simple_object.md")]
@@ -0,0 +1,218 @@
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll},
+};
+
+use futures_util::{stream::BoxStream, task::AtomicWaker, Stream, StreamExt};
+use indexmap::IndexMap;
+
+use crate::{Name, Value};
+
+/// Storage of the invalidation tokens of live queries.
+///
+/// A query marked with the `@live` directive is executed again each time one
+/// of its invalidation tokens fires. The tokens of a query are
+/// `"<query type>.<field>"` for each root field it selects, for example
+/// `"Query.todos"`, and the tokens added by the resolvers with
+/// [`Context::add_live_query_token`](crate::Context::add_live_query_token).
+pub trait LiveQueryStore: Send + Sync + 'static {
+    /// Returns a stream that yields an item each time one of `tokens` is
+    /// invalidated.
+    fn watch(&self, tokens: Vec<String>) -> BoxStream<'static, ()>;
+}
+
+struct Watcher {
+    tokens: HashSet<String>,
+    invalidated: AtomicBool,
+    waker: AtomicWaker,
+}
+
+struct WatcherStream(Arc<Watcher>);
+
+impl Stream for WatcherStream {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.0.invalidated.swap(false, Ordering::AcqRel) {
+            return Poll::Ready(Some(()));
+        }
+        self.0.waker.register(cx.waker());
+        if self.0.invalidated.swap(false, Ordering::AcqRel) {
+            return Poll::Ready(Some(()));
+        }
+        Poll::Pending
+    }
+}
+
+/// Memory-based live query store.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::{
+///     atomic::{AtomicI32, Ordering},
+///     Arc,
+/// };
+///
+/// use async_graphql::*;
+/// use futures_util::stream::StreamExt;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self, ctx: &Context<'_>) -> i32 {
+///         ctx.data_unchecked::<Arc<AtomicI32>>().load(Ordering::SeqCst)
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let value = Arc::new(AtomicI32::new(1));
+/// let store = MemoryLiveQueryStore::new();
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .data(value.clone())
+///     .live_query_store(store.clone())
+///     .finish();
+///
+/// let mut stream = schema.execute_stream("query @live { value }");
+/// assert_eq!(stream.next().await.unwrap().data, value!({ "value": 1 }));
+///
+/// value.store(2, Ordering::SeqCst);
+/// store.invalidate("Query.value");
+/// assert_eq!(
+///     stream.next().await.unwrap().extensions["live"],
+///     value!({
+///         "revision": 1,
+///         "patch": [{ "op": "replace", "path": "/value", "value": 2 }],
+///     })
+/// );
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct MemoryLiveQueryStore(Arc<Mutex<Vec<Weak<Watcher>>>>);
+
+impl MemoryLiveQueryStore {
+    /// Creates a new memory-based live query store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Invalidates `token`, the live queries that depend on it are executed
+    /// again.
+    pub fn invalidate(&self, token: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .retain(|watcher| match watcher.upgrade() {
+                Some(watcher) => {
+                    if watcher.tokens.contains(token) {
+                        watcher.invalidated.store(true, Ordering::Release);
+                        watcher.waker.wake();
+                    }
+                    true
+                }
+                None => false,
+            });
+    }
+}
+
+impl LiveQueryStore for MemoryLiveQueryStore {
+    fn watch(&self, tokens: Vec<String>) -> BoxStream<'static, ()> {
+        let watcher = Arc::new(Watcher {
+            tokens: tokens.into_iter().collect(),
+            invalidated: AtomicBool::new(false),
+            waker: AtomicWaker::new(),
+        });
+        self.0.lock().unwrap().push(Arc::downgrade(&watcher));
+        WatcherStream(watcher).boxed()
+    }
+}
+
+/// Returns the JSON patch (RFC 6902) that transforms `old` into `new`.
+pub(crate) fn diff_value(old: &Value, new: &Value) -> Vec<Value> {
+    let mut patch = Vec::new();
+    diff_value_inner(&mut String::new(), old, new, &mut patch);
+    patch
+}
+
+fn diff_value_inner(path: &mut String, old: &Value, new: &Value, patch: &mut Vec<Value>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (name, old_value) in old {
+                let len = path.len();
+                push_path_segment(path, name);
+                match new.get(name) {
+                    Some(new_value) => diff_value_inner(path, old_value, new_value, patch),
+                    None => patch.push(patch_op("remove", path, None)),
+                }
+                path.truncate(len);
+            }
+            for (name, new_value) in new {
+                if !old.contains_key(name) {
+                    let len = path.len();
+                    push_path_segment(path, name);
+                    patch.push(patch_op("add", path, Some(new_value)));
+                    path.truncate(len);
+                }
+            }
+        }
+        (Value::List(old), Value::List(new)) if old.len() == new.len() => {
+            for (idx, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&idx.to_string());
+                diff_value_inner(path, old_value, new_value, patch);
+                path.truncate(len);
+            }
+        }
+        _ => {
+            if old != new {
+                patch.push(patch_op("replace", path, Some(new)));
+            }
+        }
+    }
+}
+
+fn push_path_segment(path: &mut String, name: &str) {
+    path.push('/');
+    path.push_str(&name.replace('~', "~0").replace('/', "~1"));
+}
+
+fn patch_op(op: &str, path: &str, value: Option<&Value>) -> Value {
+    let mut map = IndexMap::new();
+    map.insert(Name::new("op"), Value::String(op.to_string()));
+    map.insert(Name::new("path"), Value::String(path.to_string()));
+    if let Some(value) = value {
+        map.insert(Name::new("value"), value.clone());
+    }
+    Value::Object(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    #[test]
+    fn test_diff_value() {
+        assert!(diff_value(&value!({ "a": 1 }), &value!({ "a": 1 })).is_empty());
+        assert_eq!(
+            Value::List(diff_value(
+                &value!({ "a": 1, "b": [1, 2], "c/d": true, "e": [1] }),
+                &value!({ "a": 2, "b": [1, 3], "e": [1, 2], "f": null }),
+            )),
+            value!([
+                { "op": "replace", "path": "/a", "value": 2 },
+                { "op": "replace", "path": "/b/1", "value": 3 },
+                { "op": "remove", "path": "/c~1d" },
+                { "op": "replace", "path": "/e", "value": [1, 2] },
+                { "op": "add", "path": "/f", "value": null },
+            ])
+        );
+    }
+}
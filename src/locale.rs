@@ -0,0 +1,89 @@
+use std::{fmt, sync::Arc};
+
+pub(crate) type DescriptionResolver = Arc<dyn Fn(&str, &str) -> String + Send + Sync>;
+
+/// The locale of a request, used to resolve the descriptions of the schema
+/// with the resolver set by
+/// [`SchemaBuilder::description_resolver`](crate::SchemaBuilder::description_resolver).
+///
+/// The locale is selected by adding a `Locale` to the data of the request,
+/// the session or the schema. The descriptions are not resolved if there is
+/// no locale, so a default locale is usually added to the data of the schema.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     /// query.value
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .description_resolver(|key, locale| match (key, locale) {
+///         ("query.value", "fr") => "La valeur".to_string(),
+///         ("query.value", _) => "The value".to_string(),
+///         _ => key.to_string(),
+///     })
+///     .data(Locale::new("en"))
+///     .finish();
+///
+/// let query = r#"{ __type(name: "Query") { fields { description } } }"#;
+/// assert_eq!(
+///     schema.execute(query).await.into_result().unwrap().data,
+///     value!({ "__type": { "fields": [{ "description": "The value" }] } })
+/// );
+/// assert_eq!(
+///     schema
+///         .execute(Request::new(query).data(Locale::new("fr")))
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .data,
+///     value!({ "__type": { "fields": [{ "description": "La valeur" }] } })
+/// );
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Locale(String);
+
+impl Locale {
+    /// Create a locale, such as `en-US`.
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self(locale.into())
+    }
+
+    /// Returns the name of the locale.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The description resolver of a schema with the locale to resolve the
+/// descriptions of an exported SDL for.
+#[derive(Clone)]
+pub(crate) struct LocalizedDescriptions {
+    pub(crate) resolver: DescriptionResolver,
+    pub(crate) locale: String,
+}
+
+impl LocalizedDescriptions {
+    pub(crate) fn resolve(&self, key: &str) -> String {
+        (self.resolver)(key, &self.locale)
+    }
+}
+
+impl fmt::Debug for LocalizedDescriptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalizedDescriptions")
+            .field("locale", &self.locale)
+            .finish()
+    }
+}
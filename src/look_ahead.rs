@@ -2,9 +2,22 @@ use std::collections::HashMap;
 
 use crate::{
     parser::types::{Field, FragmentDefinition, Selection, SelectionSet},
-    Context, Name, Positioned, SelectionField,
+    Context, Name, Positioned, SelectionField, ServerResult,
 };
 
+/// The arguments of the Relay `@connection(key:, filter:)` directive applied
+/// to a field.
+///
+/// See [`SchemaBuilder::enable_relay_connection_directive`](crate::SchemaBuilder::enable_relay_connection_directive).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayConnection {
+    /// The key identifying the connection in the client store.
+    pub key: String,
+    /// The names of the field arguments the connection is identified by, or
+    /// `None` if it is identified by all of them.
+    pub filter: Option<Vec<String>>,
+}
+
 /// A selection performed by a query.
 pub struct Lookahead<'a> {
     fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
@@ -65,6 +78,21 @@ impl<'a> Lookahead<'a> {
             })
             .collect()
     }
+
+    /// Get the arguments of the Relay `@connection` directive applied to the
+    /// fields covered by this `Lookahead`, if any.
+    ///
+    /// For example, calling `.field("friends").relay_connection()` on
+    /// `{ friends(first: 10) @connection(key: "Friends_friends") { edges { node { id } } } }`
+    /// returns the key `Friends_friends`.
+    pub fn relay_connection(&self) -> ServerResult<Option<RelayConnection>> {
+        for field in self.selection_fields() {
+            if let Some(connection) = field.relay_connection()? {
+                return Ok(Some(connection));
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl<'a> From<SelectionField<'a>> for Lookahead<'a> {
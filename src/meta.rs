@@ -0,0 +1,667 @@
+//! A read-only view over the metadata of a schema
+//!
+//! The view can be obtained with [`Schema::meta`](crate::Schema::meta),
+//! [`Context::schema_meta`](crate::Context::schema_meta) or
+//! [`ExtensionContext::schema_meta`](crate::extensions::ExtensionContext::schema_meta),
+//! and can be used to do schema-aware work such as generating custom
+//! documentation or permission matrices.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::{meta::TypeKind, *};
+//!
+//! #[derive(SimpleObject)]
+//! struct Query {
+//!     /// The value
+//!     value: i32,
+//! }
+//!
+//! let schema = Schema::new(Query { value: 10 }, EmptyMutation, EmptySubscription);
+//! let meta = schema.meta();
+//!
+//! let query = meta.query_type();
+//! assert_eq!(query.name(), "Query");
+//! assert_eq!(query.kind(), TypeKind::Object);
+//!
+//! let field = query.field_by_name("value").unwrap();
+//! assert_eq!(field.ty(), "Int!");
+//! assert_eq!(field.description(), Some("The value"));
+//! ```
+
+pub use crate::model::__DirectiveLocation;
+use crate::{
+    registry::{
+        MetaDirective, MetaDirectiveInvocation, MetaEnumValue, MetaField, MetaInputValue, MetaType,
+        MetaTypeName, Registry,
+    },
+    CacheControl, Value,
+};
+
+/// The kind of a type
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TypeKind {
+    /// Scalar
+    Scalar,
+    /// Object
+    Object,
+    /// Interface
+    Interface,
+    /// Union
+    Union,
+    /// Enum
+    Enum,
+    /// Input object
+    InputObject,
+}
+
+/// The metadata of a schema
+#[derive(Copy, Clone)]
+pub struct SchemaMeta<'a> {
+    registry: &'a Registry,
+}
+
+impl<'a> SchemaMeta<'a> {
+    pub(crate) fn new(registry: &'a Registry) -> Self {
+        Self { registry }
+    }
+
+    fn type_meta(&self, ty: &'a MetaType) -> TypeMeta<'a> {
+        TypeMeta {
+            registry: self.registry,
+            ty,
+        }
+    }
+
+    /// Returns the description of the schema
+    pub fn description(&self) -> Option<&'a str> {
+        self.registry.description.as_deref()
+    }
+
+    /// Returns the query root type
+    pub fn query_type(&self) -> TypeMeta<'a> {
+        self.type_meta(&self.registry.types[&self.registry.query_type])
+    }
+
+    /// Returns the mutation root type
+    pub fn mutation_type(&self) -> Option<TypeMeta<'a>> {
+        self.registry
+            .mutation_type
+            .as_ref()
+            .and_then(|name| self.type_by_name(name))
+    }
+
+    /// Returns the subscription root type
+    pub fn subscription_type(&self) -> Option<TypeMeta<'a>> {
+        self.registry
+            .subscription_type
+            .as_ref()
+            .and_then(|name| self.type_by_name(name))
+    }
+
+    /// Returns all types in the schema, sorted by name
+    pub fn types(&self) -> impl Iterator<Item = TypeMeta<'a>> + 'a {
+        let registry = self.registry;
+        registry
+            .types
+            .values()
+            .map(move |ty| TypeMeta { registry, ty })
+    }
+
+    /// Returns the type with the specified name
+    ///
+    /// The name can be a qualified type name such as `[String!]!`, in which
+    /// case the named type is returned.
+    pub fn type_by_name(&self, name: &str) -> Option<TypeMeta<'a>> {
+        self.registry
+            .concrete_type_by_name(name)
+            .map(|ty| self.type_meta(ty))
+    }
+
+    /// Returns all directives in the schema, sorted by name
+    pub fn directives(&self) -> impl Iterator<Item = DirectiveMeta<'a>> + 'a {
+        self.registry
+            .directives
+            .values()
+            .map(|directive| DirectiveMeta { directive })
+    }
+
+    /// Returns the directive with the specified name
+    pub fn directive_by_name(&self, name: &str) -> Option<DirectiveMeta<'a>> {
+        self.registry
+            .directives
+            .get(name)
+            .map(|directive| DirectiveMeta { directive })
+    }
+
+    /// Returns `true` if Apollo Federation is enabled for the schema
+    pub fn is_federation_enabled(&self) -> bool {
+        self.registry.enable_federation
+            || self.registry.types.contains_key("_Service")
+            || self.registry.has_entities()
+    }
+}
+
+/// The metadata of a type
+#[derive(Copy, Clone)]
+pub struct TypeMeta<'a> {
+    registry: &'a Registry,
+    ty: &'a MetaType,
+}
+
+impl<'a> TypeMeta<'a> {
+    /// Returns the name of the type
+    pub fn name(&self) -> &'a str {
+        self.ty.name()
+    }
+
+    /// Returns the kind of the type
+    pub fn kind(&self) -> TypeKind {
+        match self.ty {
+            MetaType::Scalar { .. } => TypeKind::Scalar,
+            MetaType::Object { .. } => TypeKind::Object,
+            MetaType::Interface { .. } => TypeKind::Interface,
+            MetaType::Union { .. } => TypeKind::Union,
+            MetaType::Enum { .. } => TypeKind::Enum,
+            MetaType::InputObject { .. } => TypeKind::InputObject,
+        }
+    }
+
+    /// Returns the description of the type
+    pub fn description(&self) -> Option<&'a str> {
+        match self.ty {
+            MetaType::Scalar { description, .. }
+            | MetaType::Object { description, .. }
+            | MetaType::Interface { description, .. }
+            | MetaType::Union { description, .. }
+            | MetaType::Enum { description, .. }
+            | MetaType::InputObject { description, .. } => description.as_deref(),
+        }
+    }
+
+    /// Returns the Rust type name corresponding to the type, if it is known
+    pub fn rust_typename(&self) -> Option<&'static str> {
+        self.ty.rust_typename()
+    }
+
+    /// Returns `true` if the type is an input type (scalar, enum or input
+    /// object)
+    pub fn is_input(&self) -> bool {
+        self.ty.is_input()
+    }
+
+    /// Returns `true` if the type is a built-in or introspection type
+    pub fn is_system_type(&self) -> bool {
+        crate::registry::is_system_type(self.name())
+    }
+
+    /// Returns `true` if the type is visible only under some conditions
+    pub fn has_visibility_condition(&self) -> bool {
+        self.ty.visible().is_some()
+    }
+
+    /// Returns the fields of an object or interface type
+    ///
+    /// For other kinds of types, the iterator is empty.
+    pub fn fields(&self) -> impl Iterator<Item = FieldMeta<'a>> + 'a {
+        let registry = self.registry;
+        self.ty
+            .fields()
+            .into_iter()
+            .flat_map(|fields| fields.values())
+            .filter(|field| !field.name.starts_with("__"))
+            .map(move |field| FieldMeta { registry, field })
+    }
+
+    /// Returns the field of an object or interface type with the specified
+    /// name
+    pub fn field_by_name(&self, name: &str) -> Option<FieldMeta<'a>> {
+        self.ty.field_by_name(name).map(|field| FieldMeta {
+            registry: self.registry,
+            field,
+        })
+    }
+
+    /// Returns the fields of an input object type
+    ///
+    /// For other kinds of types, the iterator is empty.
+    pub fn input_fields(&self) -> impl Iterator<Item = InputValueMeta<'a>> + 'a {
+        let input_fields = match self.ty {
+            MetaType::InputObject { input_fields, .. } => Some(input_fields),
+            _ => None,
+        };
+        input_fields
+            .into_iter()
+            .flat_map(|input_fields| input_fields.values())
+            .map(|input_value| InputValueMeta { input_value })
+    }
+
+    /// Returns the field of an input object type with the specified name
+    pub fn input_field_by_name(&self, name: &str) -> Option<InputValueMeta<'a>> {
+        match self.ty {
+            MetaType::InputObject { input_fields, .. } => input_fields
+                .get(name)
+                .map(|input_value| InputValueMeta { input_value }),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of an enum type
+    ///
+    /// For other kinds of types, the iterator is empty.
+    pub fn enum_values(&self) -> impl Iterator<Item = EnumValueMeta<'a>> + 'a {
+        let enum_values = match self.ty {
+            MetaType::Enum { enum_values, .. } => Some(enum_values),
+            _ => None,
+        };
+        enum_values
+            .into_iter()
+            .flat_map(|enum_values| enum_values.values())
+            .map(|value| EnumValueMeta { value })
+    }
+
+    /// Returns the names of the object types that implement an interface or
+    /// are members of a union
+    ///
+    /// For other kinds of types, the iterator is empty.
+    pub fn possible_types(&self) -> impl Iterator<Item = &'a str> + 'a {
+        self.ty
+            .possible_types()
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Returns the names of the interfaces implemented by an object or
+    /// interface type
+    pub fn interfaces(&self) -> impl Iterator<Item = &'a str> + 'a {
+        self.registry
+            .implements
+            .get(self.name())
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Returns the specification URL of a scalar type
+    pub fn specified_by_url(&self) -> Option<&'a str> {
+        match self.ty {
+            MetaType::Scalar {
+                specified_by_url, ..
+            } => specified_by_url.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the type is a oneof input object
+    pub fn is_oneof(&self) -> bool {
+        matches!(self.ty, MetaType::InputObject { oneof: true, .. })
+    }
+
+    /// Returns the cache control of an object type
+    pub fn cache_control(&self) -> Option<CacheControl> {
+        match self.ty {
+            MetaType::Object { cache_control, .. } => Some(*cache_control),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the type is a subscription root type
+    pub fn is_subscription(&self) -> bool {
+        matches!(
+            self.ty,
+            MetaType::Object {
+                is_subscription: true,
+                ..
+            }
+        )
+    }
+
+    /// Returns the directives applied to the type
+    pub fn applied_directives(&self) -> impl Iterator<Item = AppliedDirective<'a>> + 'a {
+        let directive_invocations = match self.ty {
+            MetaType::Object {
+                directive_invocations,
+                ..
+            } => Some(directive_invocations),
+            _ => None,
+        };
+        directive_invocations
+            .into_iter()
+            .flatten()
+            .map(|invocation| AppliedDirective { invocation })
+    }
+
+    /// Returns the federation `@key` field sets of an entity type
+    pub fn keys(&self) -> &'a [String] {
+        match self.ty {
+            MetaType::Object { keys, .. } | MetaType::Interface { keys, .. } => {
+                keys.as_deref().unwrap_or_default()
+            }
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if the type is a federation entity
+    pub fn is_entity(&self) -> bool {
+        !self.keys().is_empty()
+    }
+
+    /// Returns `true` if the type extends a type of another federation
+    /// subgraph
+    pub fn is_extends(&self) -> bool {
+        matches!(
+            self.ty,
+            MetaType::Object { extends: true, .. } | MetaType::Interface { extends: true, .. }
+        )
+    }
+
+    /// Returns `true` if the type is marked with the federation `@shareable`
+    /// directive
+    pub fn is_shareable(&self) -> bool {
+        matches!(
+            self.ty,
+            MetaType::Object {
+                shareable: true,
+                ..
+            }
+        )
+    }
+
+    /// Returns `true` if the type is marked with the federation
+    /// `@inaccessible` directive
+    pub fn is_inaccessible(&self) -> bool {
+        match self.ty {
+            MetaType::Scalar { inaccessible, .. }
+            | MetaType::Object { inaccessible, .. }
+            | MetaType::Interface { inaccessible, .. }
+            | MetaType::Union { inaccessible, .. }
+            | MetaType::Enum { inaccessible, .. }
+            | MetaType::InputObject { inaccessible, .. } => *inaccessible,
+        }
+    }
+
+    /// Returns the federation `@tag` names of the type
+    pub fn tags(&self) -> &'a [String] {
+        self.ty.tags()
+    }
+}
+
+/// The metadata of a field of an object or interface type
+#[derive(Copy, Clone)]
+pub struct FieldMeta<'a> {
+    registry: &'a Registry,
+    field: &'a MetaField,
+}
+
+impl<'a> FieldMeta<'a> {
+    /// Returns the name of the field
+    pub fn name(&self) -> &'a str {
+        &self.field.name
+    }
+
+    /// Returns the description of the field
+    pub fn description(&self) -> Option<&'a str> {
+        self.field.description.as_deref()
+    }
+
+    /// Returns the qualified type name of the field, such as `[String!]!`
+    pub fn ty(&self) -> &'a str {
+        &self.field.ty
+    }
+
+    /// Returns the named type of the field
+    pub fn named_type(&self) -> Option<TypeMeta<'a>> {
+        let registry = self.registry;
+        registry
+            .types
+            .get(MetaTypeName::concrete_typename(&self.field.ty))
+            .map(|ty| TypeMeta { registry, ty })
+    }
+
+    /// Returns the arguments of the field
+    pub fn args(&self) -> impl Iterator<Item = InputValueMeta<'a>> + 'a {
+        self.field
+            .args
+            .values()
+            .map(|input_value| InputValueMeta { input_value })
+    }
+
+    /// Returns the argument of the field with the specified name
+    pub fn arg_by_name(&self, name: &str) -> Option<InputValueMeta<'a>> {
+        self.field
+            .args
+            .get(name)
+            .map(|input_value| InputValueMeta { input_value })
+    }
+
+    /// Returns `true` if the field is deprecated
+    pub fn is_deprecated(&self) -> bool {
+        self.field.deprecation.is_deprecated()
+    }
+
+    /// Returns the deprecation reason of the field
+    pub fn deprecation_reason(&self) -> Option<&'a str> {
+        self.field.deprecation.reason()
+    }
+
+    /// Returns the cache control of the field
+    pub fn cache_control(&self) -> CacheControl {
+        self.field.cache_control
+    }
+
+    /// Returns `true` if the field has a custom complexity
+    pub fn has_complexity(&self) -> bool {
+        self.field.compute_complexity.is_some()
+    }
+
+    /// Returns `true` if the field is visible only under some conditions
+    pub fn has_visibility_condition(&self) -> bool {
+        self.field.visible.is_some()
+    }
+
+    /// Returns the directives applied to the field
+    pub fn applied_directives(&self) -> impl Iterator<Item = AppliedDirective<'a>> + 'a {
+        self.field
+            .directive_invocations
+            .iter()
+            .map(|invocation| AppliedDirective { invocation })
+    }
+
+    /// Returns `true` if the field is marked with the federation `@external`
+    /// directive
+    pub fn is_external(&self) -> bool {
+        self.field.external
+    }
+
+    /// Returns the field set of the federation `@requires` directive
+    pub fn requires(&self) -> Option<&'a str> {
+        self.field.requires.as_deref()
+    }
+
+    /// Returns the field set of the federation `@provides` directive
+    pub fn provides(&self) -> Option<&'a str> {
+        self.field.provides.as_deref()
+    }
+
+    /// Returns `true` if the field is marked with the federation `@shareable`
+    /// directive
+    pub fn is_shareable(&self) -> bool {
+        self.field.shareable
+    }
+
+    /// Returns `true` if the field is marked with the federation
+    /// `@inaccessible` directive
+    pub fn is_inaccessible(&self) -> bool {
+        self.field.inaccessible
+    }
+
+    /// Returns the federation `@tag` names of the field
+    pub fn tags(&self) -> &'a [String] {
+        &self.field.tags
+    }
+
+    /// Returns the subgraph name of the federation `@override` directive
+    pub fn override_from(&self) -> Option<&'a str> {
+        self.field.override_from.as_deref()
+    }
+}
+
+/// The metadata of an argument or an input object field
+#[derive(Copy, Clone)]
+pub struct InputValueMeta<'a> {
+    input_value: &'a MetaInputValue,
+}
+
+impl<'a> InputValueMeta<'a> {
+    /// Returns the name of the input value
+    pub fn name(&self) -> &'a str {
+        &self.input_value.name
+    }
+
+    /// Returns the description of the input value
+    pub fn description(&self) -> Option<&'a str> {
+        self.input_value.description.as_deref()
+    }
+
+    /// Returns the qualified type name of the input value, such as
+    /// `[String!]!`
+    pub fn ty(&self) -> &'a str {
+        &self.input_value.ty
+    }
+
+    /// Returns the default value of the input value, in GraphQL syntax
+    pub fn default_value(&self) -> Option<&'a str> {
+        self.input_value.default_value.as_deref()
+    }
+
+    /// Returns `true` if the input value is secret
+    pub fn is_secret(&self) -> bool {
+        self.input_value.is_secret
+    }
+
+    /// Returns `true` if the input value is visible only under some
+    /// conditions
+    pub fn has_visibility_condition(&self) -> bool {
+        self.input_value.visible.is_some()
+    }
+
+    /// Returns `true` if the input value is marked with the federation
+    /// `@inaccessible` directive
+    pub fn is_inaccessible(&self) -> bool {
+        self.input_value.inaccessible
+    }
+
+    /// Returns the federation `@tag` names of the input value
+    pub fn tags(&self) -> &'a [String] {
+        &self.input_value.tags
+    }
+}
+
+/// The metadata of an enum value
+#[derive(Copy, Clone)]
+pub struct EnumValueMeta<'a> {
+    value: &'a MetaEnumValue,
+}
+
+impl<'a> EnumValueMeta<'a> {
+    /// Returns the name of the enum value
+    pub fn name(&self) -> &'a str {
+        &self.value.name
+    }
+
+    /// Returns the description of the enum value
+    pub fn description(&self) -> Option<&'a str> {
+        self.value.description.as_deref()
+    }
+
+    /// Returns `true` if the enum value is deprecated
+    pub fn is_deprecated(&self) -> bool {
+        self.value.deprecation.is_deprecated()
+    }
+
+    /// Returns the deprecation reason of the enum value
+    pub fn deprecation_reason(&self) -> Option<&'a str> {
+        self.value.deprecation.reason()
+    }
+
+    /// Returns `true` if the enum value is visible only under some conditions
+    pub fn has_visibility_condition(&self) -> bool {
+        self.value.visible.is_some()
+    }
+
+    /// Returns `true` if the enum value is marked with the federation
+    /// `@inaccessible` directive
+    pub fn is_inaccessible(&self) -> bool {
+        self.value.inaccessible
+    }
+
+    /// Returns the federation `@tag` names of the enum value
+    pub fn tags(&self) -> &'a [String] {
+        &self.value.tags
+    }
+}
+
+/// The metadata of a directive definition
+#[derive(Copy, Clone)]
+pub struct DirectiveMeta<'a> {
+    directive: &'a MetaDirective,
+}
+
+impl<'a> DirectiveMeta<'a> {
+    /// Returns the name of the directive
+    pub fn name(&self) -> &'a str {
+        &self.directive.name
+    }
+
+    /// Returns the description of the directive
+    pub fn description(&self) -> Option<&'a str> {
+        self.directive.description.as_deref()
+    }
+
+    /// Returns the locations the directive can be applied to
+    pub fn locations(&self) -> &'a [__DirectiveLocation] {
+        &self.directive.locations
+    }
+
+    /// Returns the arguments of the directive
+    pub fn args(&self) -> impl Iterator<Item = InputValueMeta<'a>> + 'a {
+        self.directive
+            .args
+            .values()
+            .map(|input_value| InputValueMeta { input_value })
+    }
+
+    /// Returns `true` if the directive can be applied multiple times to the
+    /// same location
+    pub fn is_repeatable(&self) -> bool {
+        self.directive.is_repeatable
+    }
+
+    /// Returns the specification URL used by the federation
+    /// `@composeDirective` directive
+    pub fn composable(&self) -> Option<&'a str> {
+        self.directive.composable.as_deref()
+    }
+}
+
+/// A directive applied to a type or field
+#[derive(Copy, Clone)]
+pub struct AppliedDirective<'a> {
+    invocation: &'a MetaDirectiveInvocation,
+}
+
+impl<'a> AppliedDirective<'a> {
+    /// Returns the name of the directive
+    pub fn name(&self) -> &'a str {
+        &self.invocation.name
+    }
+
+    /// Returns the arguments passed to the directive
+    pub fn args(&self) -> impl Iterator<Item = (&'a str, &'a Value)> + 'a {
+        self.invocation
+            .args
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+}
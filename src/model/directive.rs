@@ -1,6 +1,6 @@
-use std::collections::HashSet;
+use std::{borrow::Cow, collections::HashSet};
 
-use crate::{model::__InputValue, registry, Enum, Object};
+use crate::{model::__InputValue, registry, Context, Enum, Object};
 
 /// A Directive can be adjacent to many parts of the GraphQL language, a
 /// __DirectiveLocation describes one such possible adjacencies.
@@ -102,8 +102,9 @@ impl<'a> __Directive<'a> {
     }
 
     #[inline]
-    async fn description(&self) -> Option<&str> {
-        self.directive.description.as_deref()
+    async fn description(&self, ctx: &Context<'_>) -> Option<Cow<'a, str>> {
+        ctx.schema_env
+            .localize_description(ctx, self.directive.description.as_deref())
     }
 
     #[inline]
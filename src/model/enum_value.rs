@@ -1,4 +1,6 @@
-use crate::{registry, Object};
+use std::borrow::Cow;
+
+use crate::{registry, Context, Object};
 
 pub struct __EnumValue<'a> {
     pub registry: &'a registry::Registry,
@@ -16,8 +18,9 @@ impl<'a> __EnumValue<'a> {
     }
 
     #[inline]
-    async fn description(&self) -> Option<&str> {
-        self.value.description.as_deref()
+    async fn description(&self, ctx: &Context<'_>) -> Option<Cow<'a, str>> {
+        ctx.schema_env
+            .localize_description(ctx, self.value.description.as_deref())
     }
 
     #[inline]
@@ -29,4 +32,10 @@ impl<'a> __EnumValue<'a> {
     async fn deprecation_reason(&self) -> Option<&str> {
         self.value.deprecation.reason()
     }
+
+    /// The integer value of the enum value, if the enum accepts integers.
+    #[inline]
+    async fn as_int(&self) -> Option<i64> {
+        self.value.int_value
+    }
 }
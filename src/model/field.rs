@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{borrow::Cow, collections::HashSet};
 
 use crate::{
     model::{__InputValue, __Type},
@@ -23,8 +23,9 @@ impl<'a> __Field<'a> {
     }
 
     #[inline]
-    async fn description(&self) -> Option<&str> {
-        self.field.description.as_deref()
+    async fn description(&self, ctx: &Context<'_>) -> Option<Cow<'a, str>> {
+        ctx.schema_env
+            .localize_description(ctx, self.field.description.as_deref())
     }
 
     async fn args(&self, ctx: &Context<'_>) -> Vec<__InputValue<'a>> {
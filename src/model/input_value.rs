@@ -1,6 +1,6 @@
-use std::collections::HashSet;
+use std::{borrow::Cow, collections::HashSet};
 
-use crate::{model::__Type, registry, Object};
+use crate::{model::__Type, registry, Context, Object};
 
 pub struct __InputValue<'a> {
     pub registry: &'a registry::Registry,
@@ -19,8 +19,9 @@ impl<'a> __InputValue<'a> {
     }
 
     #[inline]
-    async fn description(&self) -> Option<&str> {
-        self.input_value.description.as_deref()
+    async fn description(&self, ctx: &Context<'_>) -> Option<Cow<'a, str>> {
+        ctx.schema_env
+            .localize_description(ctx, self.input_value.description.as_deref())
     }
 
     #[graphql(name = "type")]
@@ -1,8 +1,8 @@
-use std::collections::HashSet;
+use std::{borrow::Cow, collections::HashSet};
 
 use crate::{
     model::{__Directive, __Type},
-    registry, Object,
+    registry, Context, Object,
 };
 
 pub struct __Schema<'a> {
@@ -24,6 +24,12 @@ impl<'a> __Schema<'a> {
 /// points for query, mutation, and subscription operations.
 #[Object(internal, name = "__Schema")]
 impl<'a> __Schema<'a> {
+    /// The description of the schema.
+    async fn description(&self, ctx: &Context<'_>) -> Option<Cow<'a, str>> {
+        ctx.schema_env
+            .localize_description(ctx, self.registry.description.as_deref())
+    }
+
     /// A list of all types supported by this server.
     async fn types(&self) -> Vec<__Type<'a>> {
         let mut types: Vec<_> = self
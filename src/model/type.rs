@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{borrow::Cow, collections::HashSet};
 
 use crate::{
     model::{__EnumValue, __Field, __InputValue, __TypeKind},
@@ -99,8 +99,8 @@ impl<'a> __Type<'a> {
     }
 
     #[inline]
-    async fn description(&self) -> Option<&str> {
-        match &self.detail {
+    async fn description(&self, ctx: &Context<'_>) -> Option<Cow<'a, str>> {
+        let description = match &self.detail {
             TypeDetail::Named(ty) => match ty {
                 registry::MetaType::Scalar { description, .. }
                 | registry::MetaType::Object { description, .. }
@@ -111,7 +111,8 @@ impl<'a> __Type<'a> {
             },
             TypeDetail::NonNull(_) => None,
             TypeDetail::List(_) => None,
-        }
+        };
+        ctx.schema_env.localize_description(ctx, description)
     }
 
     async fn fields(
@@ -141,7 +142,10 @@ impl<'a> __Type<'a> {
     }
 
     async fn interfaces(&self) -> Option<Vec<__Type<'a>>> {
-        if let TypeDetail::Named(registry::MetaType::Object { name, .. }) = &self.detail {
+        if let TypeDetail::Named(
+            registry::MetaType::Object { name, .. } | registry::MetaType::Interface { name, .. },
+        ) = &self.detail
+        {
             Some(
                 self.registry
                     .implements
@@ -0,0 +1,175 @@
+use std::{any::Any, sync::Arc};
+
+use crate::{
+    context::{DataScope, ScopedData},
+    resolver_utils::{resolve_container_serial, ContainerType},
+    ContextBase, ContextSelectionSet, Result, ServerResult, Value,
+};
+
+/// Wraps the execution of each mutation operation in a transaction.
+///
+/// The transaction is begun before the first field of the mutation is
+/// executed, and is accessible to the resolvers of the mutation and of their
+/// descendants with `Context::data`. After the fields are executed, it is
+/// committed if no error is recorded, and rolled back otherwise. The queries
+/// and the subscriptions are not executed in a transaction.
+///
+/// Register it with
+/// [`SchemaBuilder::mutation_transaction`](crate::SchemaBuilder::mutation_transaction).
+///
+/// The transaction is dropped without being committed or rolled back if the
+/// request times out.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// use async_graphql::*;
+///
+/// #[derive(Default)]
+/// struct Transaction(Mutex<Vec<String>>);
+///
+/// #[derive(Default, Clone)]
+/// struct Storage(Arc<Mutex<Vec<String>>>);
+///
+/// #[async_trait::async_trait]
+/// impl MutationTransaction for Storage {
+///     type Transaction = Transaction;
+///
+///     async fn begin(&self, _ctx: &ContextSelectionSet<'_>) -> Result<Transaction> {
+///         Ok(Transaction::default())
+///     }
+///
+///     async fn commit(&self, transaction: Transaction) -> Result<()> {
+///         let names = transaction.0.into_inner().unwrap();
+///         self.0.lock().unwrap().extend(names);
+///         Ok(())
+///     }
+///
+///     async fn rollback(&self, _transaction: Transaction) -> Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct Mutation;
+///
+/// #[Object]
+/// impl Mutation {
+///     async fn add(&self, ctx: &Context<'_>, name: String) -> Result<bool> {
+///         if name.is_empty() {
+///             return Err("The name is empty".into());
+///         }
+///         let transaction = ctx.data::<Transaction>()?;
+///         transaction.0.lock().unwrap().push(name);
+///         Ok(true)
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let storage = Storage::default();
+/// let schema = Schema::build(Query, Mutation, EmptySubscription)
+///     .mutation_transaction(storage.clone())
+///     .finish();
+///
+/// schema
+///     .execute(r#"mutation { a: add(name: "a") b: add(name: "b") }"#)
+///     .await;
+/// // The second field fails, so the first one is rolled back.
+/// schema
+///     .execute(r#"mutation { c: add(name: "c") d: add(name: "") }"#)
+///     .await;
+/// assert_eq!(*storage.0.lock().unwrap(), vec!["a", "b"]);
+/// # });
+/// ```
+#[async_trait::async_trait]
+pub trait MutationTransaction: Send + Sync + 'static {
+    /// The transaction handle, inserted into the context data of the
+    /// mutation.
+    type Transaction: Any + Send + Sync;
+
+    /// Begin a transaction, before the first field of a mutation is
+    /// executed.
+    ///
+    /// The mutation is not executed if it fails.
+    async fn begin(&self, ctx: &ContextSelectionSet<'_>) -> Result<Self::Transaction>;
+
+    /// Commit the transaction, after the fields of the mutation are executed
+    /// without errors.
+    ///
+    /// If it fails, the response contains its error and no data.
+    async fn commit(&self, transaction: Self::Transaction) -> Result<()>;
+
+    /// Roll back the transaction, after the fields of the mutation are
+    /// executed with errors.
+    async fn rollback(&self, transaction: Self::Transaction) -> Result<()>;
+}
+
+#[async_trait::async_trait]
+pub(crate) trait ErasedMutationTransaction: Send + Sync + 'static {
+    async fn begin(&self, ctx: &ContextSelectionSet<'_>) -> Result<ScopedData>;
+
+    async fn end(&self, data: ScopedData, commit: bool) -> Result<()>;
+}
+
+#[async_trait::async_trait]
+impl<T: MutationTransaction> ErasedMutationTransaction for T {
+    async fn begin(&self, ctx: &ContextSelectionSet<'_>) -> Result<ScopedData> {
+        let data = ScopedData::default();
+        data.insert(MutationTransaction::begin(self, ctx).await?);
+        Ok(data)
+    }
+
+    async fn end(&self, data: ScopedData, commit: bool) -> Result<()> {
+        let transaction = data
+            .take::<T::Transaction>()
+            .expect("You definitely encountered a bug!");
+        if commit {
+            self.commit(transaction).await
+        } else {
+            self.rollback(transaction).await
+        }
+    }
+}
+
+/// Resolves the fields of a mutation in a transaction.
+pub(crate) async fn resolve_mutation_in_transaction<'a, T: ContainerType + ?Sized>(
+    ctx: &ContextSelectionSet<'a>,
+    root: &'a T,
+    transaction: &dyn ErasedMutationTransaction,
+) -> ServerResult<Value> {
+    let data = Arc::new(
+        transaction
+            .begin(ctx)
+            .await
+            .map_err(|err| err.into_server_error(ctx.item.pos))?,
+    );
+
+    let res = {
+        let ctx = ContextBase {
+            scope: DataScope::root(data.clone()),
+            ..ctx.clone()
+        };
+        resolve_container_serial(&ctx, root).await
+    };
+
+    let commit = res.is_ok() && !ctx.query_env.has_errors();
+    let data = Arc::try_unwrap(data).expect("You definitely encountered a bug!");
+    match transaction.end(data, commit).await {
+        Ok(()) => res,
+        Err(err) if commit => Err(err.into_server_error(ctx.item.pos)),
+        Err(err) => {
+            ctx.add_error(err.into_server_error(ctx.item.pos));
+            res
+        }
+    }
+}
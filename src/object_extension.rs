@@ -0,0 +1,79 @@
+use std::{any::Any, collections::HashMap};
+
+use futures_util::future::BoxFuture;
+
+use crate::{
+    registry::{MetaType, Registry},
+    resolver_utils::ContainerType,
+    Context, OutputType, ServerResult, Value,
+};
+
+/// An object that adds fields to another object, like `extend type` in the
+/// SDL.
+///
+/// It allows a crate to add fields to an object defined in another crate,
+/// usually with [`ExtendObject`](derive.ExtendObject.html). The extension is
+/// registered with
+/// [`SchemaBuilder::extend_object`](crate::SchemaBuilder::extend_object), its
+/// fields are merged into the target object, and it is created from the
+/// target object to resolve them.
+///
+/// Only the objects without generic parameters can be extended.
+pub trait ObjectExtension: ContainerType + 'static {
+    /// The extended object.
+    type Target: OutputType + 'static;
+
+    /// Create the extension of `target`.
+    fn extend(target: &Self::Target) -> Self;
+}
+
+pub(crate) type ObjectExtensionResolver = for<'a> fn(
+    &'a (dyn Any + Send + Sync),
+    &'a Context<'a>,
+) -> BoxFuture<'a, ServerResult<Option<Value>>>;
+
+fn resolve_extension_field<'a, E: ObjectExtension>(
+    target: &'a (dyn Any + Send + Sync),
+    ctx: &'a Context<'a>,
+) -> BoxFuture<'a, ServerResult<Option<Value>>> {
+    Box::pin(async move {
+        match target.downcast_ref::<E::Target>() {
+            Some(target) => E::extend(target).resolve_field(ctx).await,
+            None => Ok(None),
+        }
+    })
+}
+
+/// Merges the fields of `E` into its target object, and adds their resolvers
+/// to `extensions`.
+pub(crate) fn register_object_extension<E: ObjectExtension>(
+    registry: &mut Registry,
+    extensions: &mut HashMap<String, HashMap<String, ObjectExtensionResolver>>,
+) {
+    let target_name = E::Target::type_name().into_owned();
+    E::Target::create_type_info(registry);
+
+    let extension_fields = match registry.create_fake_output_type::<E>() {
+        MetaType::Object { fields, .. } => fields,
+        _ => panic!("Extension \"{}\" is not an object", E::type_name()),
+    };
+    registry.types.remove(E::type_name().as_ref());
+
+    let resolvers = extensions.entry(target_name.clone()).or_default();
+    match registry.types.get_mut(&target_name) {
+        Some(MetaType::Object {
+            fields,
+            is_subscription: false,
+            ..
+        }) => {
+            for (name, field) in extension_fields {
+                if fields.contains_key(&name) {
+                    panic!("Field \"{}.{}\" already exists", target_name, name);
+                }
+                resolvers.insert(name.clone(), resolve_extension_field::<E>);
+                fields.insert(name, field);
+            }
+        }
+        _ => panic!("Type \"{}\" is not an object", target_name),
+    }
+}
@@ -0,0 +1,426 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Debug, Formatter, Write},
+    sync::Arc,
+};
+
+use async_graphql_value::Value;
+use once_cell::sync::OnceCell;
+
+use crate::{
+    parser::types::{
+        Directive, DocumentOperations, ExecutableDocument, FragmentDefinition, OperationDefinition,
+        Selection, SelectionSet, VariableDefinition,
+    },
+    Name, Positioned,
+};
+
+/// Options for [`operation_signature`].
+#[derive(Debug, Clone, Default)]
+pub struct OperationSignatureOptions {
+    sorted_fields: bool,
+}
+
+impl OperationSignatureOptions {
+    /// Create a `OperationSignatureOptions`
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sort the fields and the fragments of the selection sets
+    #[inline]
+    #[must_use]
+    pub fn sorted_fields(self) -> Self {
+        Self {
+            sorted_fields: true,
+        }
+    }
+}
+
+/// Returns the normalized signature of an operation of a query document, or
+/// `None` if the document does not contain the operation.
+///
+/// The signature contains the operation and the fragments it uses, sorted by
+/// name. The literals are replaced with placeholders, the aliases are
+/// removed, the arguments and directives are sorted, and the whitespaces are
+/// reduced, so the operations that only differ in these respects have the
+/// same signature. This is similar to the operation signatures of Apollo,
+/// and is useful to group the operations in logs and metrics, or to build the
+/// manifests of persisted operations.
+///
+/// The signatures of the executed operations are also available in
+/// [`Response::operation`](crate::Response::operation).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{parser::parse_query, *};
+///
+/// let doc = parse_query(r#"query GetUser { u: user(id: "1") { name id } }"#).unwrap();
+/// assert_eq!(
+///     operation_signature(&doc, None, &OperationSignatureOptions::new()).unwrap(),
+///     r#"query GetUser{user(id:""){name id}}"#
+/// );
+/// assert_eq!(
+///     operation_signature(
+///         &doc,
+///         Some("GetUser"),
+///         &OperationSignatureOptions::new().sorted_fields()
+///     )
+///     .unwrap(),
+///     r#"query GetUser{user(id:""){id name}}"#
+/// );
+/// ```
+pub fn operation_signature(
+    document: &ExecutableDocument,
+    operation_name: Option<&str>,
+    options: &OperationSignatureOptions,
+) -> Option<String> {
+    let (operation_name, operation) = match (&document.operations, operation_name) {
+        (DocumentOperations::Single(operation), None) => (None, operation),
+        (DocumentOperations::Single(_), Some(_)) => return None,
+        (DocumentOperations::Multiple(operations), Some(name)) => {
+            (Some(name), operations.get(name)?)
+        }
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            let (name, operation) = operations.iter().next()?;
+            (Some(name.as_str()), operation)
+        }
+        (DocumentOperations::Multiple(_), None) => return None,
+    };
+    Some(signature(
+        operation_name,
+        &operation.node,
+        &document.fragments,
+        options,
+    ))
+}
+
+/// The signature of an executed operation, computed the first time it is read
+/// since most of the responses never use it.
+#[derive(Clone)]
+pub(crate) struct LazySignature(Arc<LazySignatureInner>);
+
+struct LazySignatureInner {
+    source: Option<SignatureSource>,
+    signature: OnceCell<String>,
+}
+
+struct SignatureSource {
+    operation_name: Option<String>,
+    operation: Arc<Positioned<OperationDefinition>>,
+    fragments: Arc<HashMap<Name, Positioned<FragmentDefinition>>>,
+}
+
+impl LazySignature {
+    /// The operation and the fragments must not have their skipped selections
+    /// removed, so the signature is the same for all the variables.
+    pub(crate) fn new(
+        operation_name: Option<String>,
+        operation: Arc<Positioned<OperationDefinition>>,
+        fragments: Arc<HashMap<Name, Positioned<FragmentDefinition>>>,
+    ) -> Self {
+        Self(Arc::new(LazySignatureInner {
+            source: Some(SignatureSource {
+                operation_name,
+                operation,
+                fragments,
+            }),
+            signature: OnceCell::new(),
+        }))
+    }
+
+    pub(crate) fn from_signature(signature: String) -> Self {
+        Self(Arc::new(LazySignatureInner {
+            source: None,
+            signature: OnceCell::with_value(signature),
+        }))
+    }
+
+    pub(crate) fn get(&self) -> &str {
+        self.0.signature.get_or_init(|| match &self.0.source {
+            Some(source) => signature(
+                source.operation_name.as_deref(),
+                &source.operation.node,
+                &source.fragments,
+                &OperationSignatureOptions::new().sorted_fields(),
+            ),
+            None => String::new(),
+        })
+    }
+}
+
+impl Debug for LazySignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.get(), f)
+    }
+}
+
+impl PartialEq for LazySignature {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl Eq for LazySignature {}
+
+pub(crate) fn signature(
+    operation_name: Option<&str>,
+    operation: &OperationDefinition,
+    fragments: &HashMap<Name, Positioned<FragmentDefinition>>,
+    options: &OperationSignatureOptions,
+) -> String {
+    let sorted = options.sorted_fields;
+    let mut used_fragments = BTreeMap::new();
+    collect_fragments(
+        &operation.selection_set.node,
+        fragments,
+        &mut used_fragments,
+    );
+
+    let mut output = String::new();
+    for (name, fragment) in used_fragments {
+        write!(output, "fragment {}", name).unwrap();
+        write_variable_definitions(&mut output, &fragment.variable_definitions);
+        write!(output, " on {}", fragment.type_condition.node.on.node).unwrap();
+        write_directives(&mut output, &fragment.directives);
+        write_selection_set(&mut output, &fragment.selection_set.node, sorted);
+        output.push(' ');
+    }
+
+    output.push_str(&operation.ty.to_string());
+    if let Some(name) = operation_name {
+        write!(output, " {}", name).unwrap();
+    }
+    write_variable_definitions(&mut output, &operation.variable_definitions);
+    write_directives(&mut output, &operation.directives);
+    write_selection_set(&mut output, &operation.selection_set.node, sorted);
+    output
+}
+
+fn collect_fragments<'a>(
+    selection_set: &'a SelectionSet,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    used_fragments: &mut BTreeMap<&'a str, &'a FragmentDefinition>,
+) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                collect_fragments(&field.node.selection_set.node, fragments, used_fragments)
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = spread.node.fragment_name.node.as_str();
+                if used_fragments.contains_key(name) {
+                    continue;
+                }
+                if let Some(fragment) = fragments.get(name) {
+                    used_fragments.insert(name, &fragment.node);
+                    collect_fragments(&fragment.node.selection_set.node, fragments, used_fragments);
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => collect_fragments(
+                &inline_fragment.node.selection_set.node,
+                fragments,
+                used_fragments,
+            ),
+        }
+    }
+}
+
+fn write_variable_definitions(
+    output: &mut String,
+    variable_definitions: &[Positioned<VariableDefinition>],
+) {
+    if variable_definitions.is_empty() {
+        return;
+    }
+    let mut variable_definitions = variable_definitions.iter().collect::<Vec<_>>();
+    variable_definitions.sort_by(|a, b| a.node.name.node.cmp(&b.node.name.node));
+
+    output.push('(');
+    for (idx, variable) in variable_definitions.into_iter().enumerate() {
+        if idx > 0 {
+            output.push(',');
+        }
+        write!(
+            output,
+            "${}:{}",
+            variable.node.name.node, variable.node.var_type.node
+        )
+        .unwrap();
+        if let Some(default_value) = &variable.node.default_value {
+            output.push('=');
+            write_value(output, &default_value.node.clone().into_value());
+        }
+        write_directives(output, &variable.node.directives);
+    }
+    output.push(')');
+}
+
+fn write_arguments(output: &mut String, arguments: &[(Positioned<Name>, Positioned<Value>)]) {
+    if arguments.is_empty() {
+        return;
+    }
+    let mut arguments = arguments.iter().collect::<Vec<_>>();
+    arguments.sort_by(|a, b| a.0.node.cmp(&b.0.node));
+
+    output.push('(');
+    for (idx, (name, value)) in arguments.into_iter().enumerate() {
+        if idx > 0 {
+            output.push(',');
+        }
+        write!(output, "{}:", name.node).unwrap();
+        write_value(output, &value.node);
+    }
+    output.push(')');
+}
+
+fn write_directives(output: &mut String, directives: &[Positioned<Directive>]) {
+    let mut directives = directives.iter().collect::<Vec<_>>();
+    directives.sort_by(|a, b| a.node.name.node.cmp(&b.node.name.node));
+    for directive in directives {
+        write!(output, "@{}", directive.node.name.node).unwrap();
+        write_arguments(output, &directive.node.arguments);
+    }
+}
+
+fn write_selection_set(output: &mut String, selection_set: &SelectionSet, sorted: bool) {
+    if selection_set.items.is_empty() {
+        return;
+    }
+
+    let mut selections = selection_set
+        .items
+        .iter()
+        .map(|selection| {
+            let mut output = String::new();
+            let key = match &selection.node {
+                Selection::Field(field) => {
+                    output.push_str(&field.node.name.node);
+                    write_arguments(&mut output, &field.node.arguments);
+                    if let Some(nullability) = &field.node.nullability {
+                        write!(output, "{}", nullability.node).unwrap();
+                    }
+                    write_directives(&mut output, &field.node.directives);
+                    write_selection_set(&mut output, &field.node.selection_set.node, sorted);
+                    (0, field.node.name.node.to_string())
+                }
+                Selection::FragmentSpread(spread) => {
+                    write!(output, "...{}", spread.node.fragment_name.node).unwrap();
+                    write_arguments(&mut output, &spread.node.arguments);
+                    write_directives(&mut output, &spread.node.directives);
+                    (1, spread.node.fragment_name.node.to_string())
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    output.push_str("...");
+                    let type_condition = inline_fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map(|type_condition| type_condition.node.on.node.to_string())
+                        .unwrap_or_default();
+                    if !type_condition.is_empty() {
+                        write!(output, "on {}", type_condition).unwrap();
+                    }
+                    write_directives(&mut output, &inline_fragment.node.directives);
+                    write_selection_set(
+                        &mut output,
+                        &inline_fragment.node.selection_set.node,
+                        sorted,
+                    );
+                    (2, type_condition)
+                }
+            };
+            (key, output)
+        })
+        .collect::<Vec<_>>();
+    if sorted {
+        selections.sort();
+    }
+
+    output.push('{');
+    for (idx, (_, selection)) in selections.into_iter().enumerate() {
+        if idx > 0 {
+            output.push(' ');
+        }
+        output.push_str(&selection);
+    }
+    output.push('}');
+}
+
+fn write_value(output: &mut String, value: &Value) {
+    match value {
+        Value::Variable(name) => write!(output, "${}", name).unwrap(),
+        Value::Number(_) => output.push('0'),
+        Value::String(_) | Value::Binary(_) => output.push_str("\"\""),
+        Value::List(_) => output.push_str("[]"),
+        Value::Object(_) => output.push_str("{}"),
+        Value::Null | Value::Boolean(_) | Value::Enum(_) => write!(output, "{}", value).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_query;
+
+    fn signature(query: &str) -> String {
+        let doc = parse_query(query).unwrap();
+        operation_signature(
+            &doc,
+            None,
+            &OperationSignatureOptions::new().sorted_fields(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_operation_signature() {
+        assert_eq!(
+            signature(
+                r#"
+                query GetUser($id: ID!, $a: Int = 10) {
+                    u: user(id: $id, filter: { name: "abc" }, limit: 10) {
+                        ...UserFields
+                        name
+                        ... on Admin @include(if: true) { level }
+                    }
+                    version
+                }
+
+                fragment UserFields on User {
+                    age
+                    friends(first: 5) { id }
+                }
+
+                fragment Unused on User { id }
+                "#
+            ),
+            "fragment UserFields on User{age friends(first:0){id}} \
+             query GetUser($a:Int=0,$id:ID!){user(filter:{},id:$id,limit:0){name ...UserFields ...on Admin@include(if:true){level}} version}"
+        );
+
+        assert_eq!(signature("{ b a(x: \"1\") }"), "query{a(x:\"\") b}");
+        assert_eq!(
+            signature("{ b a(x: \"1\") }"),
+            signature("{\n  a(x: \"2\")\n  b\n}")
+        );
+    }
+
+    #[test]
+    fn test_operation_signature_options() {
+        let doc = parse_query("query A { b a(x: 1) } query B { c }").unwrap();
+        let options = OperationSignatureOptions::new();
+        assert_eq!(
+            operation_signature(&doc, Some("A"), &options).as_deref(),
+            Some("query A{b a(x:0)}")
+        );
+        assert_eq!(
+            operation_signature(&doc, Some("B"), &options.clone().sorted_fields()).as_deref(),
+            Some("query B{c}")
+        );
+        assert_eq!(operation_signature(&doc, None, &options), None);
+        assert_eq!(operation_signature(&doc, Some("C"), &options), None);
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
+
+use futures_util::lock::Mutex;
+use once_cell::sync::OnceCell;
+
+use crate::{Context, Result};
+
+/// Provides a data of the context lazily, the first time a resolver asks for
+/// it in a request.
+///
+/// It allows to create the data that are expensive or not always used, such
+/// as a database transaction, only when a field needs them, instead of
+/// creating them for every request before the execution. Register it with
+/// [`SchemaBuilder::provider`](crate::SchemaBuilder::provider), and get the
+/// data with [`Context::provide`].
+///
+/// The data is created once per request, unless it fails, in which case it is
+/// created again the next time a resolver asks for it.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{
+///     atomic::{AtomicI32, Ordering},
+///     Arc,
+/// };
+///
+/// use async_graphql::*;
+///
+/// struct Transaction(i32);
+///
+/// struct TransactionProvider(Arc<AtomicI32>);
+///
+/// #[async_trait::async_trait]
+/// impl Provider for TransactionProvider {
+///     type Output = Transaction;
+///
+///     async fn provide(&self, _ctx: &Context<'_>) -> Result<Transaction> {
+///         Ok(Transaction(self.0.fetch_add(1, Ordering::SeqCst)))
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+///
+///     async fn transaction(&self, ctx: &Context<'_>) -> Result<i32> {
+///         Ok(ctx.provide::<Transaction>().await?.0)
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let counter = Arc::new(AtomicI32::new(1));
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .provider(TransactionProvider(counter.clone()))
+///     .finish();
+///
+/// // The transaction is not created if no field needs it.
+/// schema.execute("{ value }").await;
+/// assert_eq!(counter.load(Ordering::SeqCst), 1);
+///
+/// // The transaction is created once per request.
+/// let res = schema.execute("{ a: transaction b: transaction }").await;
+/// assert_eq!(res.data, value!({ "a": 1, "b": 1 }));
+/// # });
+/// ```
+#[async_trait::async_trait]
+pub trait Provider: Send + Sync + 'static {
+    /// The provided data.
+    type Output: Any + Send + Sync;
+
+    /// Create the data for the request of `ctx`, which is the context of the
+    /// first field that asks for it.
+    async fn provide(&self, ctx: &Context<'_>) -> Result<Self::Output>;
+}
+
+#[async_trait::async_trait]
+pub(crate) trait ErasedProvider: Send + Sync + 'static {
+    async fn provide(&self, ctx: &Context<'_>) -> Result<Box<dyn Any + Send + Sync>>;
+}
+
+#[async_trait::async_trait]
+impl<P: Provider> ErasedProvider for P {
+    async fn provide(&self, ctx: &Context<'_>) -> Result<Box<dyn Any + Send + Sync>> {
+        Ok(Box::new(Provider::provide(self, ctx).await?))
+    }
+}
+
+pub(crate) type Providers = HashMap<TypeId, Arc<dyn ErasedProvider>>;
+
+/// The data created by a provider during a request.
+pub(crate) struct ProvidedData {
+    provider: Arc<dyn ErasedProvider>,
+    value: OnceCell<Box<dyn Any + Send + Sync>>,
+    lock: Mutex<()>,
+}
+
+impl ProvidedData {
+    pub(crate) fn new_map(providers: &Providers) -> HashMap<TypeId, ProvidedData> {
+        providers
+            .iter()
+            .map(|(type_id, provider)| {
+                (
+                    *type_id,
+                    ProvidedData {
+                        provider: provider.clone(),
+                        value: OnceCell::new(),
+                        lock: Mutex::new(()),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the data, creating it if it is not created yet.
+    pub(crate) async fn get_or_provide(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<&(dyn Any + Send + Sync)> {
+        if let Some(value) = self.value.get() {
+            return Ok(value.as_ref());
+        }
+
+        // Only one resolver creates the data, the others wait for it.
+        let _guard = self.lock.lock().await;
+        if let Some(value) = self.value.get() {
+            return Ok(value.as_ref());
+        }
+        let value = self.provider.provide(ctx).await?;
+        Ok(self.value.get_or_init(|| value).as_ref())
+    }
+}
@@ -127,7 +127,8 @@ impl CacheControl {
 
 impl CacheControl {
     #[must_use]
-    pub(crate) fn merge(self, other: &CacheControl) -> CacheControl {
+    #[doc(hidden)]
+    pub fn merge(self, other: &CacheControl) -> CacheControl {
         CacheControl {
             public: self.public && other.public,
             max_age: match (self.max_age, other.max_age) {
@@ -0,0 +1,300 @@
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use crate::registry::{IsVisibleFn, MetaInputValue, MetaType, MetaTypeName, Registry};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl Registry {
+    /// Returns the JSON Schema of the input object `type_name`, `None` if it is
+    /// not an input object or it is not exported.
+    ///
+    /// Like the SDL, only the types, fields and enum values for which
+    /// `is_visible` returns `true` are exported, and the inaccessible ones are
+    /// never exported.
+    pub(crate) fn export_input_json_schema(
+        &self,
+        type_name: &str,
+        is_visible: IsVisibleFn<'_>,
+    ) -> Option<Value> {
+        let mut exporter = JsonSchemaExporter::new(self, is_visible);
+        if !exporter.is_type_exported(type_name) {
+            return None;
+        }
+        let schema = match self.types.get(type_name)? {
+            MetaType::InputObject {
+                description,
+                input_fields,
+                oneof,
+                ..
+            } => exporter.object_schema(description.as_deref(), input_fields, *oneof),
+            _ => return None,
+        };
+        Some(exporter.finish(type_name, schema))
+    }
+
+    /// Returns the JSON Schema of the arguments of the field `field_name` of
+    /// the object or interface `type_name`, `None` if the field does not exist
+    /// or it is not exported.
+    pub(crate) fn export_arguments_json_schema(
+        &self,
+        type_name: &str,
+        field_name: &str,
+        is_visible: IsVisibleFn<'_>,
+    ) -> Option<Value> {
+        let mut exporter = JsonSchemaExporter::new(self, is_visible);
+        let field = self.types.get(type_name)?.field_by_name(field_name)?;
+        if !exporter.is_type_exported(type_name)
+            || !is_visible(&field.visible)
+            || field.inaccessible
+        {
+            return None;
+        }
+        let schema = exporter.object_schema(field.description.as_deref(), &field.args, false);
+        Some(exporter.finish(&format!("{}.{}", type_name, field_name), schema))
+    }
+}
+
+struct JsonSchemaExporter<'a> {
+    registry: &'a Registry,
+    is_visible: IsVisibleFn<'a>,
+    defs: Map<String, Value>,
+}
+
+impl<'a> JsonSchemaExporter<'a> {
+    fn new(registry: &'a Registry, is_visible: IsVisibleFn<'a>) -> Self {
+        Self {
+            registry,
+            is_visible,
+            defs: Map::new(),
+        }
+    }
+
+    fn is_type_exported(&self, name: &str) -> bool {
+        match self.registry.types.get(name) {
+            Some(
+                MetaType::Scalar {
+                    visible,
+                    inaccessible,
+                    ..
+                }
+                | MetaType::Object {
+                    visible,
+                    inaccessible,
+                    ..
+                }
+                | MetaType::Interface {
+                    visible,
+                    inaccessible,
+                    ..
+                }
+                | MetaType::Union {
+                    visible,
+                    inaccessible,
+                    ..
+                }
+                | MetaType::Enum {
+                    visible,
+                    inaccessible,
+                    ..
+                }
+                | MetaType::InputObject {
+                    visible,
+                    inaccessible,
+                    ..
+                },
+            ) => (self.is_visible)(visible) && !*inaccessible,
+            None => true,
+        }
+    }
+
+    fn is_input_value_exported(&self, input_value: &MetaInputValue) -> bool {
+        (self.is_visible)(&input_value.visible)
+            && !input_value.inaccessible
+            && self.is_type_exported(MetaTypeName::concrete_typename(&input_value.ty))
+    }
+
+    fn finish(self, title: &str, schema: Map<String, Value>) -> Value {
+        let mut document = Map::new();
+        document.insert("$schema".to_string(), JSON_SCHEMA_DIALECT.into());
+        document.insert("title".to_string(), title.into());
+        document.extend(schema);
+        if !self.defs.is_empty() {
+            document.insert("$defs".to_string(), Value::Object(self.defs));
+        }
+        Value::Object(document)
+    }
+
+    fn object_schema(
+        &mut self,
+        description: Option<&str>,
+        fields: &IndexMap<String, MetaInputValue>,
+        oneof: bool,
+    ) -> Map<String, Value> {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+
+        for field in fields.values() {
+            if !self.is_input_value_exported(field) {
+                continue;
+            }
+
+            // the fields of a oneof input object are nullable, but the field
+            // that is set must not be null
+            let mut schema = if oneof {
+                self.non_null_schema(&field.ty)
+            } else {
+                self.type_schema(&field.ty)
+            };
+            if let Some(description) = &field.description {
+                schema.insert("description".to_string(), description.as_str().into());
+            }
+            if let Some(default_value) = &field.default_value {
+                if let Some(default_value) = self.default_value(&field.ty, default_value) {
+                    schema.insert("default".to_string(), default_value);
+                }
+            }
+            if field.is_secret {
+                schema.insert("writeOnly".to_string(), true.into());
+            }
+            if !oneof
+                && MetaTypeName::create(&field.ty).is_non_null()
+                && field.default_value.is_none()
+            {
+                required.push(Value::from(field.name.as_str()));
+            }
+            properties.insert(field.name.clone(), Value::Object(schema));
+        }
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), "object".into());
+        if let Some(description) = description {
+            schema.insert("description".to_string(), description.into());
+        }
+        schema.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::Array(required));
+        }
+        if oneof {
+            schema.insert("minProperties".to_string(), 1.into());
+            schema.insert("maxProperties".to_string(), 1.into());
+        }
+        schema.insert("additionalProperties".to_string(), false.into());
+        schema
+    }
+
+    fn type_schema(&mut self, ty: &str) -> Map<String, Value> {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.non_null_schema(ty),
+            _ => nullable(self.non_null_schema(ty)),
+        }
+    }
+
+    fn non_null_schema(&mut self, ty: &str) -> Map<String, Value> {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.non_null_schema(ty),
+            MetaTypeName::List(ty) => to_map(json!({
+                "type": "array",
+                "items": self.type_schema(ty),
+            })),
+            MetaTypeName::Named(name) => self.named_schema(name),
+        }
+    }
+
+    fn named_schema(&mut self, name: &str) -> Map<String, Value> {
+        match name {
+            "Int" => {
+                return to_map(json!({
+                    "type": "integer",
+                    "minimum": i32::MIN,
+                    "maximum": i32::MAX,
+                }))
+            }
+            "Float" => return to_map(json!({ "type": "number" })),
+            "String" => return to_map(json!({ "type": "string" })),
+            "Boolean" => return to_map(json!({ "type": "boolean" })),
+            "ID" => return to_map(json!({ "type": ["string", "integer"] })),
+            _ => {}
+        }
+
+        if !self.defs.contains_key(name) {
+            // inserted before the schema is created, so that the recursive
+            // input objects refer to it instead of creating it again
+            self.defs.insert(name.to_string(), Value::Null);
+            let schema = match self.registry.types.get(name) {
+                Some(MetaType::InputObject {
+                    description,
+                    input_fields,
+                    oneof,
+                    ..
+                }) => self.object_schema(description.as_deref(), input_fields, *oneof),
+                Some(MetaType::Enum {
+                    description,
+                    enum_values,
+                    ..
+                }) => {
+                    let enum_values = enum_values
+                        .values()
+                        .filter(|value| (self.is_visible)(&value.visible) && !value.inaccessible)
+                        .map(|value| value.name.as_str())
+                        .collect::<Vec<_>>();
+                    let mut schema = to_map(json!({
+                        "type": "string",
+                        "enum": enum_values,
+                    }));
+                    if let Some(description) = description {
+                        schema.insert("description".to_string(), description.as_str().into());
+                    }
+                    schema
+                }
+                // the custom scalars accept any value
+                Some(MetaType::Scalar {
+                    description: Some(description),
+                    ..
+                }) => to_map(json!({ "description": description })),
+                _ => Map::new(),
+            };
+            self.defs.insert(name.to_string(), Value::Object(schema));
+        }
+
+        to_map(json!({ "$ref": format!("#/$defs/{}", name) }))
+    }
+
+    /// Converts a default value from GraphQL to JSON, `None` if it is not
+    /// representable as JSON, such as an input object literal.
+    fn default_value(&self, ty: &str, default_value: &str) -> Option<Value> {
+        match serde_json::from_str(default_value) {
+            Ok(value) => Some(value),
+            Err(_) => match self.registry.types.get(MetaTypeName::concrete_typename(ty)) {
+                Some(MetaType::Enum { enum_values, .. })
+                    if enum_values.contains_key(default_value) =>
+                {
+                    Some(default_value.into())
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+fn nullable(mut schema: Map<String, Value>) -> Map<String, Value> {
+    match schema.get_mut("type") {
+        Some(Value::String(ty)) => {
+            let ty = std::mem::take(ty);
+            schema.insert("type".to_string(), json!([ty, "null"]));
+            schema
+        }
+        Some(Value::Array(types)) => {
+            types.push("null".into());
+            schema
+        }
+        _ => to_map(json!({ "anyOf": [schema, { "type": "null" }] })),
+    }
+}
+
+fn to_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
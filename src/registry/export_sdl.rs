@@ -1,6 +1,20 @@
-use std::{collections::HashMap, fmt::Write};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    hash::Hasher,
+};
 
-use crate::registry::{Deprecation, MetaField, MetaInputValue, MetaType, Registry};
+use fnv::FnvHasher;
+
+use crate::{
+    locale::{DescriptionResolver, LocalizedDescriptions},
+    registry::{
+        is_system_type, is_visible_outside_request, Deprecation, MetaEnumValue, MetaField,
+        MetaInputValue, MetaType, MetaTypeName, MetaVisible, Registry,
+    },
+};
+
+pub(crate) type IsVisibleFn<'a> = &'a dyn Fn(&Option<MetaVisible>) -> bool;
 
 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
 const FEDERATION_SCALARS: &[&str] = &["Any"];
@@ -11,10 +25,14 @@ pub struct SDLExportOptions {
     sorted_fields: bool,
     sorted_arguments: bool,
     sorted_enum_values: bool,
+    sorted_union_members: bool,
+    sorted_implements: bool,
     federation: bool,
     prefer_single_line_descriptions: bool,
     include_specified_by: bool,
     compose_directive: bool,
+    strip_descriptions: bool,
+    inline_extends: bool,
 }
 
 impl SDLExportOptions {
@@ -54,6 +72,26 @@ impl SDLExportOptions {
         }
     }
 
+    /// Export sorted union member types
+    #[inline]
+    #[must_use]
+    pub fn sorted_union_members(self) -> Self {
+        Self {
+            sorted_union_members: true,
+            ..self
+        }
+    }
+
+    /// Export sorted implemented interfaces
+    #[inline]
+    #[must_use]
+    pub fn sorted_implements(self) -> Self {
+        Self {
+            sorted_implements: true,
+            ..self
+        }
+    }
+
     /// Export as Federation SDL(Schema Definition Language)
     #[inline]
     #[must_use]
@@ -89,10 +127,186 @@ impl SDLExportOptions {
             ..self
         }
     }
+
+    /// Do not export descriptions
+    #[inline]
+    #[must_use]
+    pub fn strip_descriptions(self) -> Self {
+        Self {
+            strip_descriptions: true,
+            ..self
+        }
+    }
+
+    /// Export the types marked with `extends` as type definitions instead of
+    /// `extend` blocks if federation is enabled
+    #[inline]
+    #[must_use]
+    pub fn inline_extends(self) -> Self {
+        Self {
+            inline_extends: true,
+            ..self
+        }
+    }
+}
+
+/// The audience of an exported SDL, which selects the exported elements and
+/// the locale of their descriptions
+///
+/// Used with [`Schema::sdl_for_audience`](crate::Schema::sdl_for_audience)
+/// to publish a trimmed public SDL and a full internal SDL from the same
+/// schema.
+#[derive(Debug, Clone, Default)]
+pub struct SDLExportAudience {
+    include_tags: Vec<String>,
+    exclude_tags: Vec<String>,
+    exclude_prefixes: Vec<String>,
+    contract: bool,
+    visibility_profile: Option<String>,
+    locale: Option<String>,
+}
+
+impl SDLExportAudience {
+    /// Create a `SDLExportAudience` which sees all elements
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Only export the types and fields tagged with the specified tag
+    ///
+    /// Can be called multiple times, an element is exported if it has any of
+    /// the included tags. All fields of an included type are exported, and
+    /// the root operation types are always exported.
+    #[must_use]
+    pub fn include_tag(mut self, tag: impl Into<String>) -> Self {
+        self.include_tags.push(tag.into());
+        self
+    }
+
+    /// Do not export the types, fields, arguments, input fields and enum
+    /// values tagged with the specified tag
+    #[must_use]
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.exclude_tags.push(tag.into());
+        self
+    }
+
+    /// Do not export the types, fields, arguments, input fields and enum
+    /// values whose names start with the specified prefix
+    #[must_use]
+    pub fn exclude_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.exclude_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Export a contract schema filtered by the `@tag` values, like the
+    /// contracts of Apollo GraphOS
+    ///
+    /// The elements are included and excluded as with
+    /// [`SDLExportAudience::include_tag`] and [`SDLExportAudience::exclude_tag`],
+    /// and the references left dangling by the removed elements are cleaned
+    /// up:
+    ///
+    /// - The objects, interfaces and input objects without fields, the unions
+    ///   without members and the enums without values are removed, as are
+    ///   the mutation and subscription root types without fields.
+    /// - The fields and arguments whose types are removed are removed.
+    /// - The fields with a removed required argument, and the input objects
+    ///   with a removed required field are removed.
+    #[must_use]
+    pub fn contract<I, E>(mut self, include_tags: I, exclude_tags: E) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        E: IntoIterator,
+        E::Item: Into<String>,
+    {
+        self.include_tags
+            .extend(include_tags.into_iter().map(Into::into));
+        self.exclude_tags
+            .extend(exclude_tags.into_iter().map(Into::into));
+        self.contract = true;
+        self
+    }
+
+    /// Only export the types, fields, arguments, input fields and enum values
+    /// which are visible to the specified visibility profile
+    ///
+    /// The elements hidden with `visible = false` are not exported either.
+    /// The visibility functions need the context of a request, so they are
+    /// not evaluated and the elements they check are exported.
+    #[must_use]
+    pub fn visibility_profile(self, profile: impl Into<String>) -> Self {
+        Self {
+            visibility_profile: Some(profile.into()),
+            ..self
+        }
+    }
+
+    /// Resolve the descriptions for the specified locale with the resolver
+    /// set by
+    /// [`SchemaBuilder::description_resolver`](crate::SchemaBuilder::description_resolver).
+    #[must_use]
+    pub fn locale(self, locale: impl Into<String>) -> Self {
+        Self {
+            locale: Some(locale.into()),
+            ..self
+        }
+    }
+
+    fn has_filters(&self) -> bool {
+        !self.include_tags.is_empty()
+            || !self.exclude_tags.is_empty()
+            || !self.exclude_prefixes.is_empty()
+            || self.contract
+    }
 }
 
 impl Registry {
+    /// Returns a stable fingerprint of the schema.
+    ///
+    /// The fingerprint is computed from the SDL with all fields, arguments,
+    /// enum values, union members and implemented interfaces sorted, so it
+    /// does not depend on the order in which they are declared.
+    pub(crate) fn fingerprint(&self) -> String {
+        let sdl = self.export_sdl(
+            SDLExportOptions::new()
+                .sorted_fields()
+                .sorted_arguments()
+                .sorted_enum_items()
+                .sorted_union_members()
+                .sorted_implements()
+                .include_specified_by(),
+        );
+        let mut hasher = FnvHasher::default();
+        hasher.write(sdl.as_bytes());
+        format!("{:016x}", hasher.finish())
+    }
+
     pub(crate) fn export_sdl(&self, options: SDLExportOptions) -> String {
+        self.export_sdl_for_audience(options, &SDLExportAudience::new(), None)
+    }
+
+    /// Same as [`Registry::export_sdl`], but only exports the elements seen by
+    /// `audience`, with the descriptions resolved by `description_resolver`
+    /// for its locale.
+    pub(crate) fn export_sdl_for_audience(
+        &self,
+        options: SDLExportOptions,
+        audience: &SDLExportAudience,
+        description_resolver: Option<&DescriptionResolver>,
+    ) -> String {
+        let localized_descriptions =
+            audience
+                .locale
+                .clone()
+                .zip(description_resolver)
+                .map(|(locale, resolver)| LocalizedDescriptions {
+                    resolver: resolver.clone(),
+                    locale,
+                });
+        let filter = ExportFilter::new(self, options, audience, localized_descriptions);
         let mut sdl = String::new();
 
         let has_oneof = self
@@ -105,7 +319,7 @@ impl Registry {
         }
 
         for ty in self.types.values() {
-            if ty.name().starts_with("__") {
+            if ty.name().starts_with("__") || !filter.is_type_exported(ty.name()) {
                 continue;
             }
 
@@ -116,7 +330,7 @@ impl Registry {
                 }
             }
 
-            self.export_type(ty, &mut sdl, &options);
+            self.export_type(ty, &mut sdl, &filter);
             writeln!(sdl).ok();
         }
 
@@ -158,12 +372,23 @@ impl Registry {
                 writeln!(sdl, "{}", directive.sdl()).ok();
             });
         } else {
+            if let Some(description) = &self.description {
+                export_description(&mut sdl, &filter, true, description);
+            }
             writeln!(sdl, "schema {{").ok();
             writeln!(sdl, "\tquery: {}", self.query_type).ok();
-            if let Some(mutation_type) = self.mutation_type.as_deref() {
+            if let Some(mutation_type) = self
+                .mutation_type
+                .as_deref()
+                .filter(|ty| filter.is_type_exported(ty))
+            {
                 writeln!(sdl, "\tmutation: {}", mutation_type).ok();
             }
-            if let Some(subscription_type) = self.subscription_type.as_deref() {
+            if let Some(subscription_type) = self
+                .subscription_type
+                .as_deref()
+                .filter(|ty| filter.is_type_exported(ty))
+            {
                 writeln!(sdl, "\tsubscription: {}", subscription_type).ok();
             }
             writeln!(sdl, "}}").ok();
@@ -172,12 +397,13 @@ impl Registry {
         sdl
     }
 
-    fn export_fields<'a, I: Iterator<Item = &'a MetaField>>(
-        sdl: &mut String,
-        it: I,
-        options: &SDLExportOptions,
-    ) {
-        let mut fields = it.collect::<Vec<_>>();
+    fn export_fields(sdl: &mut String, ty: &MetaType, filter: &ExportFilter<'_>) {
+        let options = filter.options;
+        let mut fields = ty
+            .fields()
+            .into_iter()
+            .flat_map(|fields| fields.values())
+            .collect::<Vec<_>>();
 
         if options.sorted_fields {
             fields.sort_by_key(|field| &field.name);
@@ -186,18 +412,23 @@ impl Registry {
         for field in fields {
             if field.name.starts_with("__")
                 || (options.federation && matches!(&*field.name, "_service" | "_entities"))
+                || !filter.is_field_exported(ty, field)
             {
                 continue;
             }
 
             if let Some(description) = &field.description {
-                export_description(sdl, options, false, description);
+                export_description(sdl, filter, false, description);
             }
 
-            if !field.args.is_empty() {
+            let mut args = field
+                .args
+                .values()
+                .filter(|arg| filter.is_input_value_exported(arg))
+                .collect::<Vec<_>>();
+            if !args.is_empty() {
                 write!(sdl, "\t{}(", field.name).ok();
 
-                let mut args = field.args.values().collect::<Vec<_>>();
                 if options.sorted_arguments {
                     args.sort_by_key(|value| &value.name);
                 }
@@ -256,7 +487,8 @@ impl Registry {
         }
     }
 
-    fn export_type(&self, ty: &MetaType, sdl: &mut String, options: &SDLExportOptions) {
+    fn export_type(&self, ty: &MetaType, sdl: &mut String, filter: &ExportFilter<'_>) {
+        let options = filter.options;
         match ty {
             MetaType::Scalar {
                 name,
@@ -272,7 +504,7 @@ impl Registry {
                 }
                 if export_scalar {
                     if let Some(description) = description {
-                        export_description(sdl, options, true, description);
+                        export_description(sdl, filter, true, description);
                     }
                     write!(sdl, "scalar {}", name).ok();
 
@@ -323,6 +555,7 @@ impl Registry {
                         if field.name.starts_with("__")
                             || (options.federation
                                 && matches!(&*field.name, "_service" | "_entities"))
+                            || !filter.is_field_exported(ty, field)
                         {
                             continue;
                         }
@@ -335,15 +568,15 @@ impl Registry {
                 }
 
                 if let Some(description) = description {
-                    export_description(sdl, options, true, description);
+                    export_description(sdl, filter, true, description);
                 }
 
-                if options.federation && *extends {
+                if options.federation && *extends && !options.inline_extends {
                     write!(sdl, "extend ").ok();
                 }
 
                 write!(sdl, "type {}", name).ok();
-                self.write_implements(sdl, name);
+                self.write_implements(sdl, name, filter);
 
                 if options.federation {
                     if let Some(keys) = keys {
@@ -369,12 +602,11 @@ impl Registry {
                 }
 
                 writeln!(sdl, " {{").ok();
-                Self::export_fields(sdl, fields.values(), options);
+                Self::export_fields(sdl, ty, filter);
                 writeln!(sdl, "}}").ok();
             }
             MetaType::Interface {
                 name,
-                fields,
                 extends,
                 keys,
                 description,
@@ -383,10 +615,10 @@ impl Registry {
                 ..
             } => {
                 if let Some(description) = description {
-                    export_description(sdl, options, true, description);
+                    export_description(sdl, filter, true, description);
                 }
 
-                if options.federation && *extends {
+                if options.federation && *extends && !options.inline_extends {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "interface {}", name).ok();
@@ -405,10 +637,10 @@ impl Registry {
                         write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                     }
                 }
-                self.write_implements(sdl, name);
+                self.write_implements(sdl, name, filter);
 
                 writeln!(sdl, " {{").ok();
-                Self::export_fields(sdl, fields.values(), options);
+                Self::export_fields(sdl, ty, filter);
                 writeln!(sdl, "}}").ok();
             }
             MetaType::Enum {
@@ -420,7 +652,7 @@ impl Registry {
                 ..
             } => {
                 if let Some(description) = description {
-                    export_description(sdl, options, true, description);
+                    export_description(sdl, filter, true, description);
                 }
 
                 write!(sdl, "enum {}", name).ok();
@@ -434,14 +666,17 @@ impl Registry {
                 }
                 writeln!(sdl, " {{").ok();
 
-                let mut values = enum_values.values().collect::<Vec<_>>();
+                let mut values = enum_values
+                    .values()
+                    .filter(|value| filter.is_enum_value_exported(value))
+                    .collect::<Vec<_>>();
                 if options.sorted_enum_values {
                     values.sort_by_key(|value| &value.name);
                 }
 
                 for value in values {
                     if let Some(description) = &value.description {
-                        export_description(sdl, options, false, description);
+                        export_description(sdl, filter, false, description);
                     }
                     write!(sdl, "\t{}", value.name).ok();
                     write_deprecated(sdl, &value.deprecation);
@@ -470,7 +705,7 @@ impl Registry {
                 ..
             } => {
                 if let Some(description) = description {
-                    export_description(sdl, options, true, description);
+                    export_description(sdl, filter, true, description);
                 }
 
                 write!(sdl, "input {}", name).ok();
@@ -488,14 +723,17 @@ impl Registry {
                 }
                 writeln!(sdl, " {{").ok();
 
-                let mut fields = input_fields.values().collect::<Vec<_>>();
+                let mut fields = input_fields
+                    .values()
+                    .filter(|field| filter.is_input_value_exported(field))
+                    .collect::<Vec<_>>();
                 if options.sorted_fields {
                     fields.sort_by_key(|value| &value.name);
                 }
 
                 for field in fields {
                     if let Some(description) = &field.description {
-                        export_description(sdl, options, false, description);
+                        export_description(sdl, filter, false, description);
                     }
                     write!(sdl, "\t{}", export_input_value(&field)).ok();
                     if options.federation {
@@ -520,7 +758,7 @@ impl Registry {
                 ..
             } => {
                 if let Some(description) = description {
-                    export_description(sdl, options, true, description);
+                    export_description(sdl, filter, true, description);
                 }
 
                 write!(sdl, "union {}", name).ok();
@@ -534,7 +772,15 @@ impl Registry {
                 }
                 write!(sdl, " =").ok();
 
-                for (idx, ty) in possible_types.iter().enumerate() {
+                let mut possible_types = possible_types
+                    .iter()
+                    .filter(|ty| filter.is_type_exported(ty))
+                    .collect::<Vec<_>>();
+                if options.sorted_union_members {
+                    possible_types.sort();
+                }
+
+                for (idx, ty) in possible_types.into_iter().enumerate() {
                     if idx == 0 {
                         write!(sdl, " {}", ty).ok();
                     } else {
@@ -546,30 +792,297 @@ impl Registry {
         }
     }
 
-    fn write_implements(&self, sdl: &mut String, name: &str) {
+    fn write_implements(&self, sdl: &mut String, name: &str, filter: &ExportFilter<'_>) {
         if let Some(implements) = self.implements.get(name) {
+            let mut implements = implements
+                .iter()
+                .map(AsRef::as_ref)
+                .filter(|ty| filter.is_type_exported(ty))
+                .collect::<Vec<&str>>();
             if !implements.is_empty() {
-                write!(
-                    sdl,
-                    " implements {}",
-                    implements
-                        .iter()
-                        .map(AsRef::as_ref)
-                        .collect::<Vec<&str>>()
-                        .join(" & ")
-                )
-                .ok();
+                if filter.options.sorted_implements {
+                    implements.sort_unstable();
+                }
+                write!(sdl, " implements {}", implements.join(" & ")).ok();
             }
         }
     }
 }
 
+struct ExportFilter<'a> {
+    registry: &'a Registry,
+    options: SDLExportOptions,
+    audience: &'a SDLExportAudience,
+    localized_descriptions: Option<LocalizedDescriptions>,
+    exported_types: Option<HashSet<&'a str>>,
+    removed_types: HashSet<&'a str>,
+}
+
+impl<'a> ExportFilter<'a> {
+    fn new(
+        registry: &'a Registry,
+        options: SDLExportOptions,
+        audience: &'a SDLExportAudience,
+        localized_descriptions: Option<LocalizedDescriptions>,
+    ) -> Self {
+        let mut filter = ExportFilter {
+            registry,
+            options,
+            audience,
+            localized_descriptions,
+            exported_types: None,
+            removed_types: HashSet::new(),
+        };
+        if audience.visibility_profile.is_some() || audience.has_filters() {
+            filter.exported_types = Some(filter.find_exported_types());
+        }
+        if audience.contract {
+            // removing a type can leave other types empty, so repeat until
+            // no more types are removed
+            loop {
+                let empty_types = filter
+                    .exported_types
+                    .iter()
+                    .flatten()
+                    .filter(|name| filter.is_type_empty(&filter.registry.types[**name]))
+                    .copied()
+                    .collect::<Vec<_>>();
+                if empty_types.is_empty() {
+                    break;
+                }
+                filter.removed_types.extend(empty_types);
+                filter.exported_types = Some(filter.find_exported_types());
+            }
+        }
+        filter
+    }
+
+    fn is_visible(&self, visible: &Option<MetaVisible>) -> bool {
+        match &self.audience.visibility_profile {
+            Some(profile) => is_visible_outside_request(visible, Some(profile)),
+            None => true,
+        }
+    }
+
+    fn is_excluded(&self, name: &str, tags: &[String]) -> bool {
+        tags.iter()
+            .any(|tag| self.audience.exclude_tags.contains(tag))
+            || self
+                .audience
+                .exclude_prefixes
+                .iter()
+                .any(|prefix| name.starts_with(prefix.as_str()))
+    }
+
+    fn is_included(&self, tags: &[String]) -> bool {
+        self.audience.include_tags.is_empty()
+            || tags
+                .iter()
+                .any(|tag| self.audience.include_tags.contains(tag))
+    }
+
+    fn is_root_type(&self, name: &str) -> bool {
+        name == self.registry.query_type
+            || self.registry.mutation_type.as_deref() == Some(name)
+            || self.registry.subscription_type.as_deref() == Some(name)
+    }
+
+    /// Returns `true` if the type passes the filters, regardless of whether it
+    /// is referenced by the other exported types.
+    fn is_type_allowed(&self, ty: &MetaType) -> bool {
+        if is_system_type(ty.name()) {
+            return true;
+        }
+        if !self.is_visible(ty.visible()) {
+            return false;
+        }
+        if self.removed_types.contains(ty.name()) {
+            return false;
+        }
+        if self.is_root_type(ty.name()) {
+            return true;
+        }
+        !self.is_excluded(ty.name(), ty.tags()) && self.is_included(ty.tags())
+    }
+
+    /// Returns `true` if the type has no exported fields, members or values,
+    /// or has a required input field that is not exported, in which case it
+    /// is removed from a contract schema.
+    fn is_type_empty(&self, ty: &MetaType) -> bool {
+        if is_system_type(ty.name()) || ty.name() == self.registry.query_type {
+            return false;
+        }
+        match ty {
+            MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => !fields
+                .values()
+                .any(|field| !field.name.starts_with("__") && self.is_field_exported(ty, field)),
+            MetaType::Union { possible_types, .. } => {
+                !possible_types.iter().any(|ty| self.is_type_ref_allowed(ty))
+            }
+            MetaType::Enum { enum_values, .. } => !enum_values
+                .values()
+                .any(|value| self.is_enum_value_exported(value)),
+            MetaType::InputObject { input_fields, .. } => {
+                input_fields
+                    .values()
+                    .all(|field| !self.is_input_value_exported(field))
+                    || input_fields
+                        .values()
+                        .any(|field| is_required(field) && !self.is_input_value_exported(field))
+            }
+            MetaType::Scalar { .. } => false,
+        }
+    }
+
+    fn is_type_ref_allowed(&self, ty: &str) -> bool {
+        match self.registry.types.get(MetaTypeName::concrete_typename(ty)) {
+            Some(ty) => self.is_type_allowed(ty),
+            None => true,
+        }
+    }
+
+    fn is_type_exported(&self, name: &str) -> bool {
+        match &self.exported_types {
+            Some(exported_types) => exported_types.contains(name),
+            None => true,
+        }
+    }
+
+    fn is_field_exported(&self, parent: &MetaType, field: &MetaField) -> bool {
+        self.is_visible(&field.visible)
+            && !self.is_excluded(&field.name, &field.tags)
+            && (self.is_included(&field.tags) || self.is_included(parent.tags()))
+            && self.is_type_ref_allowed(&field.ty)
+            && (!self.audience.contract
+                || field
+                    .args
+                    .values()
+                    .all(|arg| !is_required(arg) || self.is_input_value_exported(arg)))
+    }
+
+    fn is_input_value_exported(&self, input_value: &MetaInputValue) -> bool {
+        self.is_visible(&input_value.visible)
+            && !self.is_excluded(&input_value.name, &input_value.tags)
+            && self.is_type_ref_allowed(&input_value.ty)
+    }
+
+    fn is_enum_value_exported(&self, enum_value: &MetaEnumValue) -> bool {
+        self.is_visible(&enum_value.visible)
+            && !self.is_excluded(&enum_value.name, &enum_value.tags)
+    }
+
+    /// Returns the names of the types that pass the filters and are
+    /// referenced by the root operation types, the entities or the
+    /// directives.
+    fn find_exported_types(&self) -> HashSet<&'a str> {
+        let registry = self.registry;
+        let mut exported_types = HashSet::new();
+        let mut pending = Vec::new();
+
+        for directive in registry.directives.values() {
+            if self.is_visible(&directive.visible) {
+                pending.extend(
+                    directive
+                        .args
+                        .values()
+                        .filter(|arg| self.is_input_value_exported(arg))
+                        .map(|arg| MetaTypeName::concrete_typename(&arg.ty)),
+                );
+            }
+        }
+        pending.extend(
+            Some(registry.query_type.as_str())
+                .into_iter()
+                .chain(registry.mutation_type.as_deref())
+                .chain(registry.subscription_type.as_deref()),
+        );
+        pending.extend(
+            registry
+                .types
+                .values()
+                .filter(|ty| {
+                    matches!(
+                        ty,
+                        MetaType::Object { keys: Some(keys), .. }
+                            | MetaType::Interface { keys: Some(keys), .. } if !keys.is_empty()
+                    )
+                })
+                .map(MetaType::name),
+        );
+
+        while let Some(type_name) = pending.pop() {
+            if exported_types.contains(type_name) {
+                continue;
+            }
+            let ty = match registry.types.get(type_name) {
+                Some(ty) if self.is_type_allowed(ty) => ty,
+                _ => continue,
+            };
+            exported_types.insert(ty.name());
+
+            for field in ty.fields().into_iter().flat_map(|fields| fields.values()) {
+                if self.is_field_exported(ty, field) {
+                    pending.push(MetaTypeName::concrete_typename(&field.ty));
+                    pending.extend(
+                        field
+                            .args
+                            .values()
+                            .filter(|arg| self.is_input_value_exported(arg))
+                            .map(|arg| MetaTypeName::concrete_typename(&arg.ty)),
+                    );
+                }
+            }
+            match ty {
+                MetaType::Object { name, .. } => {
+                    pending.extend(
+                        registry
+                            .implements
+                            .get(name)
+                            .into_iter()
+                            .flatten()
+                            .map(String::as_str),
+                    );
+                }
+                MetaType::Interface { possible_types, .. }
+                | MetaType::Union { possible_types, .. } => {
+                    pending.extend(possible_types.iter().map(String::as_str));
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    pending.extend(
+                        input_fields
+                            .values()
+                            .filter(|field| self.is_input_value_exported(field))
+                            .map(|field| MetaTypeName::concrete_typename(&field.ty)),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        exported_types
+    }
+}
+
 fn export_description(
     sdl: &mut String,
-    options: &SDLExportOptions,
+    filter: &ExportFilter<'_>,
     top_level: bool,
     description: &str,
 ) {
+    let options = filter.options;
+    if options.strip_descriptions {
+        return;
+    }
+
+    let localized;
+    let description = match &filter.localized_descriptions {
+        Some(localized_descriptions) => {
+            localized = localized_descriptions.resolve(description);
+            localized.as_str()
+        }
+        None => description,
+    };
+
     if options.prefer_single_line_descriptions && !description.contains('\n') {
         let tab = if top_level { "" } else { "\t" };
         let description = description.replace('"', r#"\""#);
@@ -582,6 +1095,10 @@ fn export_description(
     }
 }
 
+fn is_required(input_value: &MetaInputValue) -> bool {
+    input_value.ty.ends_with('!') && input_value.default_value.is_none()
+}
+
 fn export_input_value(input_value: &MetaInputValue) -> String {
     if let Some(default_value) = &input_value.default_value {
         format!(
@@ -669,4 +1186,18 @@ directive @custom_type_directive on FIELD_DEFINITION
         let dsl = registry.export_sdl(SDLExportOptions::new().federation().compose_directive());
         assert_eq!(dsl, expected)
     }
+
+    #[test]
+    fn test_repeatable_directive_sdl() {
+        let directive = MetaDirective {
+            name: "custom".to_string(),
+            description: None,
+            locations: vec![__DirectiveLocation::OBJECT],
+            args: Default::default(),
+            is_repeatable: true,
+            visible: None,
+            composable: None,
+        };
+        assert_eq!(directive.sdl(), "directive @custom repeatable on OBJECT");
+    }
 }
@@ -0,0 +1,139 @@
+use crate::{
+    parser::{
+        parse_query,
+        types::{DocumentOperations, Selection, SelectionSet},
+    },
+    registry::{MetaType, MetaTypeName, Registry},
+};
+
+impl Registry {
+    /// Checks that the fields referenced by the `@key`, `@requires` and
+    /// `@provides` field sets exist on their types, and returns a message
+    /// describing the first invalid field set otherwise.
+    ///
+    /// An invalid field set produces a federation SDL that fails the
+    /// composition of the supergraph.
+    pub(crate) fn check_federation_field_sets(&self) -> Result<(), String> {
+        for ty in self.types.values() {
+            let (type_name, fields, keys) = match ty {
+                MetaType::Object {
+                    name, fields, keys, ..
+                }
+                | MetaType::Interface {
+                    name, fields, keys, ..
+                } => (name, fields, keys),
+                _ => continue,
+            };
+
+            for key in keys.iter().flatten() {
+                self.check_field_set(type_name, key).map_err(|err| {
+                    format!(
+                        "Invalid @key(fields: \"{}\") on type \"{}\": {}",
+                        key, type_name, err
+                    )
+                })?;
+            }
+
+            for field in fields.values() {
+                if let Some(requires) = &field.requires {
+                    self.check_field_set(type_name, requires).map_err(|err| {
+                        format!(
+                            "Invalid @requires(fields: \"{}\") on field \"{}.{}\": {}",
+                            requires, type_name, field.name, err
+                        )
+                    })?;
+                }
+                if let Some(provides) = &field.provides {
+                    let field_type = MetaTypeName::concrete_typename(&field.ty);
+                    self.check_field_set(field_type, provides).map_err(|err| {
+                        format!(
+                            "Invalid @provides(fields: \"{}\") on field \"{}.{}\": {}",
+                            provides, type_name, field.name, err
+                        )
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a field set such as `id organization { id }` against the type
+    /// `type_name`.
+    fn check_field_set(&self, type_name: &str, fields: &str) -> Result<(), String> {
+        let doc = parse_query(format!("{{ {} }}", fields))
+            .map_err(|_| "the field set cannot be parsed.".to_string())?;
+        match &doc.operations {
+            DocumentOperations::Single(operation) => {
+                self.check_selection_set(type_name, &operation.node.selection_set.node)
+            }
+            DocumentOperations::Multiple(_) => Err("the field set cannot be parsed.".to_string()),
+        }
+    }
+
+    fn check_selection_set(
+        &self,
+        type_name: &str,
+        selection_set: &SelectionSet,
+    ) -> Result<(), String> {
+        let ty = self
+            .types
+            .get(type_name)
+            .ok_or_else(|| format!("type \"{}\" does not exist.", type_name))?;
+
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let name = field.node.name.node.as_str();
+                    if name == "__typename" {
+                        continue;
+                    }
+                    let meta_field = ty.field_by_name(name).ok_or_else(|| {
+                        format!(
+                            "field \"{}\" does not exist on type \"{}\".",
+                            name, type_name
+                        )
+                    })?;
+                    let field_type = MetaTypeName::concrete_typename(&meta_field.ty);
+                    let is_composite = self
+                        .types
+                        .get(field_type)
+                        .map_or(false, MetaType::is_composite);
+                    let selection_set = &field.node.selection_set.node;
+                    match (is_composite, selection_set.items.is_empty()) {
+                        (true, true) => {
+                            return Err(format!(
+                                "field \"{}.{}\" of type \"{}\" must have a selection of subfields.",
+                                type_name, name, field_type
+                            ));
+                        }
+                        (false, false) => {
+                            return Err(format!(
+                                "field \"{}.{}\" of type \"{}\" must not have a selection of subfields.",
+                                type_name, name, field_type
+                            ));
+                        }
+                        (true, false) => self.check_selection_set(field_type, selection_set)?,
+                        (false, true) => {}
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let fragment_type = match &fragment.node.type_condition {
+                        Some(type_condition) => type_condition.node.on.node.as_str(),
+                        None => type_name,
+                    };
+                    if fragment_type != type_name && !ty.is_possible_type(fragment_type) {
+                        return Err(format!(
+                            "type \"{}\" is not a possible type of \"{}\".",
+                            fragment_type, type_name
+                        ));
+                    }
+                    self.check_selection_set(fragment_type, &fragment.node.selection_set.node)?;
+                }
+                Selection::FragmentSpread(_) => {
+                    return Err("fragment spreads are not allowed in a field set.".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    registry::{
+        MetaDirective, MetaEnumValue, MetaField, MetaInputValue, MetaType, MetaTypeName, Registry,
+    },
+    InputType,
+};
+
+impl Registry {
+    /// Returns the introspection result of the `__schema` field, as it would
+    /// be returned by executing the standard introspection query.
+    ///
+    /// All types and fields are considered visible.
+    pub(crate) fn introspection(&self) -> Value {
+        let visible_types = self.find_visible_types_by(&|_| true);
+        let exporter = IntrospectionExporter {
+            registry: self,
+            visible_types: &visible_types,
+        };
+        json!({ "__schema": exporter.schema() })
+    }
+}
+
+struct IntrospectionExporter<'a> {
+    registry: &'a Registry,
+    visible_types: &'a HashSet<&'a str>,
+}
+
+impl<'a> IntrospectionExporter<'a> {
+    fn schema(&self) -> Value {
+        let root_type = |name: Option<&String>| match name {
+            Some(name) if self.visible_types.contains(name.as_str()) => json!({ "name": name }),
+            _ => Value::Null,
+        };
+
+        let mut types = self
+            .registry
+            .types
+            .values()
+            .filter(|ty| self.visible_types.contains(ty.name()))
+            .collect::<Vec<_>>();
+        types.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let mut directives = self.registry.directives.values().collect::<Vec<_>>();
+        directives.sort_by(|a, b| a.name.cmp(&b.name));
+
+        json!({
+            "description": self.registry.description,
+            "queryType": { "name": self.registry.query_type },
+            "mutationType": root_type(self.registry.mutation_type.as_ref()),
+            "subscriptionType": root_type(self.registry.subscription_type.as_ref()),
+            "types": types.into_iter().map(|ty| self.full_type(ty)).collect::<Vec<_>>(),
+            "directives": directives
+                .into_iter()
+                .map(|directive| self.directive(directive))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    fn full_type(&self, ty: &MetaType) -> Value {
+        let (kind, description) = match ty {
+            MetaType::Scalar { description, .. } => ("SCALAR", description),
+            MetaType::Object { description, .. } => ("OBJECT", description),
+            MetaType::Interface { description, .. } => ("INTERFACE", description),
+            MetaType::Union { description, .. } => ("UNION", description),
+            MetaType::Enum { description, .. } => ("ENUM", description),
+            MetaType::InputObject { description, .. } => ("INPUT_OBJECT", description),
+        };
+
+        let mut value = Map::new();
+        value.insert("kind".to_string(), kind.into());
+        value.insert("name".to_string(), ty.name().into());
+        value.insert("description".to_string(), description.clone().into());
+        value.insert(
+            "specifiedByURL".to_string(),
+            match ty {
+                MetaType::Scalar {
+                    specified_by_url, ..
+                } => specified_by_url.clone().into(),
+                _ => Value::Null,
+            },
+        );
+        value.insert(
+            "fields".to_string(),
+            match ty.fields() {
+                Some(fields) => fields
+                    .values()
+                    .filter(|field| !field.name.starts_with("__"))
+                    .map(|field| self.field(field))
+                    .collect(),
+                None => Value::Null,
+            },
+        );
+        value.insert(
+            "inputFields".to_string(),
+            match ty {
+                MetaType::InputObject { input_fields, .. } => input_fields
+                    .values()
+                    .map(|input_value| self.input_value(input_value))
+                    .collect(),
+                _ => Value::Null,
+            },
+        );
+        value.insert(
+            "interfaces".to_string(),
+            match ty {
+                MetaType::Object { name, .. } | MetaType::Interface { name, .. } => self
+                    .registry
+                    .implements
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .filter(|ty| self.visible_types.contains(ty.as_str()))
+                    .map(|ty| self.type_ref(ty))
+                    .collect(),
+                _ => Value::Null,
+            },
+        );
+        value.insert(
+            "enumValues".to_string(),
+            match ty {
+                MetaType::Enum { enum_values, .. } => enum_values
+                    .values()
+                    .map(|value| self.enum_value(value))
+                    .collect(),
+                _ => Value::Null,
+            },
+        );
+        value.insert(
+            "possibleTypes".to_string(),
+            match ty.possible_types() {
+                Some(possible_types) => possible_types
+                    .iter()
+                    .filter(|ty| self.visible_types.contains(ty.as_str()))
+                    .map(|ty| self.type_ref(ty))
+                    .collect(),
+                None => Value::Null,
+            },
+        );
+        value.insert(
+            "isOneOf".to_string(),
+            match ty {
+                MetaType::InputObject { oneof, .. } => (*oneof).into(),
+                _ => Value::Null,
+            },
+        );
+        Value::Object(value)
+    }
+
+    fn field(&self, field: &MetaField) -> Value {
+        json!({
+            "name": field.name,
+            "description": field.description,
+            "args": field
+                .args
+                .values()
+                .map(|arg| self.input_value(arg))
+                .collect::<Vec<_>>(),
+            "type": self.type_ref(&field.ty),
+            "isDeprecated": field.deprecation.is_deprecated(),
+            "deprecationReason": field.deprecation.reason(),
+        })
+    }
+
+    fn input_value(&self, input_value: &MetaInputValue) -> Value {
+        json!({
+            "name": input_value.name,
+            "description": input_value.description,
+            "type": self.type_ref(&input_value.ty),
+            "defaultValue": input_value.default_value,
+        })
+    }
+
+    fn enum_value(&self, value: &MetaEnumValue) -> Value {
+        json!({
+            "name": value.name,
+            "description": value.description,
+            "isDeprecated": value.deprecation.is_deprecated(),
+            "deprecationReason": value.deprecation.reason(),
+        })
+    }
+
+    fn directive(&self, directive: &MetaDirective) -> Value {
+        json!({
+            "name": directive.name,
+            "description": directive.description,
+            "locations": directive
+                .locations
+                .iter()
+                .map(|location| location.to_value().to_string())
+                .collect::<Vec<_>>(),
+            "args": directive
+                .args
+                .values()
+                .map(|arg| self.input_value(arg))
+                .collect::<Vec<_>>(),
+            "isRepeatable": directive.is_repeatable,
+        })
+    }
+
+    fn type_ref(&self, type_name: &str) -> Value {
+        match MetaTypeName::create(type_name) {
+            MetaTypeName::NonNull(ty) => json!({
+                "kind": "NON_NULL",
+                "name": null,
+                "ofType": self.type_ref(ty),
+            }),
+            MetaTypeName::List(ty) => json!({
+                "kind": "LIST",
+                "name": null,
+                "ofType": self.type_ref(ty),
+            }),
+            MetaTypeName::Named(ty) => {
+                let kind = match self.registry.types.get(ty) {
+                    Some(MetaType::Scalar { .. }) | None => "SCALAR",
+                    Some(MetaType::Object { .. }) => "OBJECT",
+                    Some(MetaType::Interface { .. }) => "INTERFACE",
+                    Some(MetaType::Union { .. }) => "UNION",
+                    Some(MetaType::Enum { .. }) => "ENUM",
+                    Some(MetaType::InputObject { .. }) => "INPUT_OBJECT",
+                };
+                json!({
+                    "kind": kind,
+                    "name": ty,
+                    "ofType": null,
+                })
+            }
+        }
+    }
+}
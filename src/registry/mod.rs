@@ -1,5 +1,9 @@
 mod cache_control;
+mod export_json_schema;
 mod export_sdl;
+mod federation;
+mod introspection;
+mod stats;
 mod stringify_exec_doc;
 
 use std::{
@@ -9,8 +13,12 @@ use std::{
 };
 
 pub use cache_control::CacheControl;
-pub use export_sdl::SDLExportOptions;
+pub(crate) use export_sdl::IsVisibleFn;
+pub use export_sdl::{SDLExportAudience, SDLExportOptions};
 use indexmap::{map::IndexMap, set::IndexSet};
+pub use stats::RegistryStats;
+#[cfg(any(feature = "tracing", feature = "opentelemetry"))]
+pub(crate) use stringify_exec_doc::redact_unparsed_query;
 
 pub use crate::model::{__DirectiveLocation, location_traits};
 use crate::{
@@ -146,7 +154,7 @@ pub struct MetaInputValue {
     pub default_value: Option<String>,
     /// A function that uses to check if the input value should be exported to
     /// schemas
-    pub visible: Option<MetaVisibleFn>,
+    pub visible: Option<MetaVisible>,
     /// Indicate that an input object is not accessible from a supergraph when
     /// using Apollo Federation
     pub inaccessible: bool,
@@ -217,7 +225,7 @@ pub struct MetaField {
     pub provides: Option<String>,
     /// A function that uses to check if the field should be exported to
     /// schemas
-    pub visible: Option<MetaVisibleFn>,
+    pub visible: Option<MetaVisible>,
     /// Indicate that an object type's field is allowed to be resolved by
     /// multiple subgraphs
     pub shareable: bool,
@@ -237,18 +245,57 @@ pub struct MetaField {
 }
 
 #[derive(Clone)]
+#[non_exhaustive]
 pub struct MetaEnumValue {
     pub name: String,
     pub description: Option<String>,
     pub deprecation: Deprecation,
-    pub visible: Option<MetaVisibleFn>,
+    pub visible: Option<MetaVisible>,
     pub inaccessible: bool,
     pub tags: Vec<String>,
+    /// The integer value of the enum value, for the enums with integer values
+    pub int_value: Option<i64>,
 }
 
-type MetaVisibleFn = fn(&Context<'_>) -> bool;
+impl MetaEnumValue {
+    /// Create an enum value, which is visible and not deprecated.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            deprecation: Deprecation::NoDeprecated,
+            visible: None,
+            inaccessible: false,
+            tags: Vec::new(),
+            int_value: None,
+        }
+    }
+}
+
+/// The condition that a type, a field, an argument or an enum value is
+/// visible.
+#[derive(Debug, Copy, Clone)]
+pub enum MetaVisible {
+    /// Never visible
+    Hidden,
+    /// Visible if the function returns `true`
+    Fn(fn(&Context<'_>) -> bool),
+    /// Visible if the active visibility profile is one of these profiles
+    Profiles(&'static [&'static str]),
+}
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+impl MetaVisible {
+    /// Returns the visibility profiles, `None` if it is not checked by
+    /// profiles.
+    pub fn profiles(&self) -> Option<&'static [&'static str]> {
+        match self {
+            MetaVisible::Hidden | MetaVisible::Fn(_) => None,
+            MetaVisible::Profiles(profiles) => Some(profiles),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum MetaTypeId {
     Scalar,
     Object,
@@ -360,7 +407,7 @@ pub enum MetaType {
         is_valid: Option<ScalarValidatorFn>,
         /// A function that uses to check if the scalar should be exported to
         /// schemas
-        visible: Option<MetaVisibleFn>,
+        visible: Option<MetaVisible>,
         /// Indicate that a scalar is not accessible from a supergraph when
         /// using Apollo Federation
         ///
@@ -409,7 +456,7 @@ pub enum MetaType {
         keys: Option<Vec<String>>,
         /// A function that uses to check if the object should be exported to
         /// schemas
-        visible: Option<MetaVisibleFn>,
+        visible: Option<MetaVisible>,
         /// Indicate that an object is not accessible from a supergraph when
         /// using Apollo Federation
         ///
@@ -455,7 +502,7 @@ pub enum MetaType {
         keys: Option<Vec<String>>,
         /// A function that uses to check if the interface should be exported to
         /// schemas
-        visible: Option<MetaVisibleFn>,
+        visible: Option<MetaVisible>,
         /// Indicate that an interface is not accessible from a supergraph when
         /// using Apollo Federation
         ///
@@ -481,7 +528,7 @@ pub enum MetaType {
         possible_types: IndexSet<String>,
         /// A function that uses to check if the union should be exported to
         /// schemas
-        visible: Option<MetaVisibleFn>,
+        visible: Option<MetaVisible>,
         /// Indicate that an union is not accessible from a supergraph when
         /// using Apollo Federation
         ///
@@ -507,7 +554,7 @@ pub enum MetaType {
         enum_values: IndexMap<String, MetaEnumValue>,
         /// A function that uses to check if the enum should be exported to
         /// schemas
-        visible: Option<MetaVisibleFn>,
+        visible: Option<MetaVisible>,
         /// Indicate that an enum is not accessible from a supergraph when
         /// using Apollo Federation
         ///
@@ -533,7 +580,7 @@ pub enum MetaType {
         input_fields: IndexMap<String, MetaInputValue>,
         /// A function that uses to check if the input object should be exported
         /// to schemas
-        visible: Option<MetaVisibleFn>,
+        visible: Option<MetaVisible>,
         /// Indicate that a input object is not accessible from a supergraph
         /// when using Apollo Federation
         ///
@@ -582,15 +629,31 @@ impl MetaType {
 
     #[inline]
     pub fn is_visible(&self, ctx: &Context<'_>) -> bool {
-        let visible = match self {
+        is_visible(ctx, self.visible())
+    }
+
+    #[inline]
+    pub(crate) fn visible(&self) -> &Option<MetaVisible> {
+        match self {
             MetaType::Scalar { visible, .. } => visible,
             MetaType::Object { visible, .. } => visible,
             MetaType::Interface { visible, .. } => visible,
             MetaType::Union { visible, .. } => visible,
             MetaType::Enum { visible, .. } => visible,
             MetaType::InputObject { visible, .. } => visible,
-        };
-        is_visible(ctx, visible)
+        }
+    }
+
+    #[inline]
+    pub(crate) fn tags(&self) -> &[String] {
+        match self {
+            MetaType::Scalar { tags, .. }
+            | MetaType::Object { tags, .. }
+            | MetaType::Interface { tags, .. }
+            | MetaType::Union { tags, .. }
+            | MetaType::Enum { tags, .. }
+            | MetaType::InputObject { tags, .. } => tags,
+        }
     }
 
     #[inline]
@@ -686,7 +749,7 @@ pub struct MetaDirective {
     pub locations: Vec<__DirectiveLocation>,
     pub args: IndexMap<String, MetaInputValue>,
     pub is_repeatable: bool,
-    pub visible: Option<MetaVisibleFn>,
+    pub visible: Option<MetaVisible>,
     pub composable: Option<String>,
 }
 
@@ -702,6 +765,9 @@ impl MetaDirective {
                 .join(", ");
             write!(sdl, "({})", args).ok();
         }
+        if self.is_repeatable {
+            write!(sdl, " repeatable").ok();
+        }
         let locations = self
             .locations
             .iter()
@@ -716,6 +782,7 @@ impl MetaDirective {
 /// A type registry for build schemas
 #[derive(Default)]
 pub struct Registry {
+    pub description: Option<String>,
     pub types: BTreeMap<String, MetaType>,
     pub directives: BTreeMap<String, MetaDirective>,
     pub implements: HashMap<String, IndexSet<String>>,
@@ -727,6 +794,13 @@ pub struct Registry {
     pub federation_subscription: bool,
     pub ignore_name_conflicts: HashSet<String>,
     pub enable_suggestions: bool,
+    pub enable_experimental_ccn: bool,
+    pub merged_fields: HashMap<String, HashMap<String, usize>>,
+    pub visibility_profiles: Vec<String>,
+    /// The GraphQL name and the qualified name of the registered types, by
+    /// Rust type name and kind, since a Rust type can be registered both as an
+    /// input object and as an object.
+    pub(crate) registered_names: HashMap<(&'static str, MetaTypeId), (String, String)>,
 }
 
 impl Registry {
@@ -798,8 +872,13 @@ impl Registry {
         T: InputType + ?Sized,
         F: FnMut(&mut Registry) -> MetaType,
     {
-        self.create_type(&mut f, &T::type_name(), std::any::type_name::<T>(), type_id);
-        T::qualified_type_name()
+        let rust_typename = std::any::type_name::<T>();
+        if let Some(qualified_name) = self.registered_name(rust_typename, type_id) {
+            return qualified_name;
+        }
+        let name = T::type_name();
+        self.create_type(&mut f, &name, rust_typename, type_id);
+        self.add_registered_name(rust_typename, type_id, &name, T::qualified_type_name())
     }
 
     pub fn create_output_type<T, F>(&mut self, type_id: MetaTypeId, mut f: F) -> String
@@ -807,8 +886,13 @@ impl Registry {
         T: OutputType + ?Sized,
         F: FnMut(&mut Registry) -> MetaType,
     {
-        self.create_type(&mut f, &T::type_name(), std::any::type_name::<T>(), type_id);
-        T::qualified_type_name()
+        let rust_typename = std::any::type_name::<T>();
+        if let Some(qualified_name) = self.registered_name(rust_typename, type_id) {
+            return qualified_name;
+        }
+        let name = T::type_name();
+        self.create_type(&mut f, &name, rust_typename, type_id);
+        self.add_registered_name(rust_typename, type_id, &name, T::qualified_type_name())
     }
 
     pub fn create_subscription_type<T, F>(&mut self, mut f: F) -> String
@@ -816,13 +900,43 @@ impl Registry {
         T: SubscriptionType + ?Sized,
         F: FnMut(&mut Registry) -> MetaType,
     {
-        self.create_type(
-            &mut f,
-            &T::type_name(),
-            std::any::type_name::<T>(),
-            MetaTypeId::Object,
+        let rust_typename = std::any::type_name::<T>();
+        let type_id = MetaTypeId::Object;
+        if let Some(qualified_name) = self.registered_name(rust_typename, type_id) {
+            return qualified_name;
+        }
+        let name = T::type_name();
+        self.create_type(&mut f, &name, rust_typename, type_id);
+        self.add_registered_name(rust_typename, type_id, &name, T::qualified_type_name())
+    }
+
+    /// Returns the qualified name of a registered Rust type.
+    ///
+    /// The names of the generic types are built from the names of their
+    /// parameters each time they are requested, so the types referenced many
+    /// times, such as the connections, are only named once. The registration
+    /// itself stays serial: `create_type_info` takes `&mut Registry`, and a
+    /// type depends on the types it references for the recursive types and
+    /// the name conflict checks.
+    fn registered_name(&self, rust_typename: &'static str, type_id: MetaTypeId) -> Option<String> {
+        self.registered_names
+            .get(&(rust_typename, type_id))
+            .filter(|(name, _)| self.types.contains_key(name))
+            .map(|(_, qualified_name)| qualified_name.clone())
+    }
+
+    fn add_registered_name(
+        &mut self,
+        rust_typename: &'static str,
+        type_id: MetaTypeId,
+        name: &str,
+        qualified_name: String,
+    ) -> String {
+        self.registered_names.insert(
+            (rust_typename, type_id),
+            (name.to_string(), qualified_name.clone()),
         );
-        T::qualified_type_name()
+        qualified_name
     }
 
     fn create_type<F: FnMut(&mut Registry) -> MetaType>(
@@ -1336,41 +1450,50 @@ impl Registry {
     }
 
     pub fn find_visible_types(&self, ctx: &Context<'_>) -> HashSet<&str> {
+        self.find_visible_types_by(&|visible| is_visible(ctx, visible))
+    }
+
+    /// Same as [`Registry::find_visible_types`], but uses `is_visible` to check
+    /// the visibility of an element instead of calling its visible function.
+    pub(crate) fn find_visible_types_by(
+        &self,
+        is_visible: &dyn Fn(&Option<MetaVisible>) -> bool,
+    ) -> HashSet<&str> {
         let mut visible_types = HashSet::new();
 
         fn traverse_field<'a>(
-            ctx: &Context<'_>,
+            is_visible: &dyn Fn(&Option<MetaVisible>) -> bool,
             types: &'a BTreeMap<String, MetaType>,
             visible_types: &mut HashSet<&'a str>,
             field: &'a MetaField,
         ) {
-            if !is_visible(ctx, &field.visible) {
+            if !is_visible(&field.visible) {
                 return;
             }
 
             traverse_type(
-                ctx,
+                is_visible,
                 types,
                 visible_types,
                 MetaTypeName::concrete_typename(&field.ty),
             );
             for arg in field.args.values() {
-                traverse_input_value(ctx, types, visible_types, arg);
+                traverse_input_value(is_visible, types, visible_types, arg);
             }
         }
 
         fn traverse_input_value<'a>(
-            ctx: &Context<'_>,
+            is_visible: &dyn Fn(&Option<MetaVisible>) -> bool,
             types: &'a BTreeMap<String, MetaType>,
             visible_types: &mut HashSet<&'a str>,
             input_value: &'a MetaInputValue,
         ) {
-            if !is_visible(ctx, &input_value.visible) {
+            if !is_visible(&input_value.visible) {
                 return;
             }
 
             traverse_type(
-                ctx,
+                is_visible,
                 types,
                 visible_types,
                 MetaTypeName::concrete_typename(&input_value.ty),
@@ -1378,7 +1501,7 @@ impl Registry {
         }
 
         fn traverse_type<'a>(
-            ctx: &Context<'_>,
+            is_visible: &dyn Fn(&Option<MetaVisible>) -> bool,
             types: &'a BTreeMap<String, MetaType>,
             visible_types: &mut HashSet<&'a str>,
             type_name: &'a str,
@@ -1388,7 +1511,7 @@ impl Registry {
             }
 
             if let Some(ty) = types.get(type_name) {
-                if !ty.is_visible(ctx) {
+                if !is_visible(ty.visible()) {
                     return;
                 }
 
@@ -1396,7 +1519,7 @@ impl Registry {
                 match ty {
                     MetaType::Object { fields, .. } => {
                         for field in fields.values() {
-                            traverse_field(ctx, types, visible_types, field);
+                            traverse_field(is_visible, types, visible_types, field);
                         }
                     }
                     MetaType::Interface {
@@ -1405,20 +1528,20 @@ impl Registry {
                         ..
                     } => {
                         for field in fields.values() {
-                            traverse_field(ctx, types, visible_types, field);
+                            traverse_field(is_visible, types, visible_types, field);
                         }
                         for type_name in possible_types.iter() {
-                            traverse_type(ctx, types, visible_types, type_name);
+                            traverse_type(is_visible, types, visible_types, type_name);
                         }
                     }
                     MetaType::Union { possible_types, .. } => {
                         for type_name in possible_types.iter() {
-                            traverse_type(ctx, types, visible_types, type_name);
+                            traverse_type(is_visible, types, visible_types, type_name);
                         }
                     }
                     MetaType::InputObject { input_fields, .. } => {
                         for field in input_fields.values() {
-                            traverse_input_value(ctx, types, visible_types, field);
+                            traverse_input_value(is_visible, types, visible_types, field);
                         }
                     }
                     _ => {}
@@ -1427,9 +1550,9 @@ impl Registry {
         }
 
         for directive in self.directives.values() {
-            if is_visible(ctx, &directive.visible) {
+            if is_visible(&directive.visible) {
                 for arg in directive.args.values() {
-                    traverse_input_value(ctx, &self.types, &mut visible_types, arg);
+                    traverse_input_value(is_visible, &self.types, &mut visible_types, arg);
                 }
             }
         }
@@ -1439,7 +1562,7 @@ impl Registry {
             .chain(self.mutation_type.iter())
             .chain(self.subscription_type.iter())
         {
-            traverse_type(ctx, &self.types, &mut visible_types, type_name);
+            traverse_type(is_visible, &self.types, &mut visible_types, type_name);
         }
 
         for ty in self.types.values().filter(|ty| match ty {
@@ -1451,15 +1574,15 @@ impl Registry {
             } => !keys.is_empty(),
             _ => false,
         }) {
-            traverse_type(ctx, &self.types, &mut visible_types, ty.name());
+            traverse_type(is_visible, &self.types, &mut visible_types, ty.name());
         }
 
         for ty in self.types.values() {
             if let MetaType::Interface { possible_types, .. } = ty {
-                if ty.is_visible(ctx) && !visible_types.contains(ty.name()) {
+                if is_visible(ty.visible()) && !visible_types.contains(ty.name()) {
                     for type_name in possible_types.iter() {
                         if visible_types.contains(type_name.as_str()) {
-                            traverse_type(ctx, &self.types, &mut visible_types, ty.name());
+                            traverse_type(is_visible, &self.types, &mut visible_types, ty.name());
                             break;
                         }
                     }
@@ -1479,16 +1602,155 @@ impl Registry {
             })
             .collect()
     }
+
+    /// Returns the first visibility profile that is used by an element but not
+    /// declared in [`Registry::visibility_profiles`].
+    pub(crate) fn find_undeclared_visibility_profile(&self) -> Option<&'static str> {
+        let input_values = |args: &IndexMap<String, MetaInputValue>| {
+            args.values().map(|arg| arg.visible).collect::<Vec<_>>()
+        };
+
+        let mut visibles = Vec::new();
+        for ty in self.types.values() {
+            visibles.push(*ty.visible());
+            match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    for field in fields.values() {
+                        visibles.push(field.visible);
+                        visibles.extend(input_values(&field.args));
+                    }
+                }
+                MetaType::Enum { enum_values, .. } => {
+                    visibles.extend(enum_values.values().map(|value| value.visible));
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    visibles.extend(input_values(input_fields));
+                }
+                _ => {}
+            }
+        }
+        for directive in self.directives.values() {
+            visibles.push(directive.visible);
+            visibles.extend(input_values(&directive.args));
+        }
+
+        visibles
+            .into_iter()
+            .flatten()
+            .filter_map(|visible| visible.profiles())
+            .flatten()
+            .find(|profile| !self.visibility_profiles.iter().any(|name| name == *profile))
+            .copied()
+    }
+
+    /// Checks that the interfaces implemented by the types agree with the
+    /// possible types of the interfaces, and that the possible types of the
+    /// interfaces and unions are registered, and returns a message describing
+    /// the first inconsistency otherwise.
+    pub(crate) fn check_possible_types(&self) -> Result<(), String> {
+        for (type_name, interfaces) in &self.implements {
+            for interface in interfaces {
+                match self.types.get(interface) {
+                    Some(MetaType::Interface { possible_types, .. }) => {
+                        if !possible_types.contains(type_name) {
+                            return Err(format!(
+                                "Type \"{}\" implements the interface \"{}\", but it is not a possible type of \"{}\".",
+                                type_name, interface, interface
+                            ));
+                        }
+                    }
+                    Some(_) => {
+                        return Err(format!(
+                            "Type \"{}\" implements \"{}\", which is not an interface.",
+                            type_name, interface
+                        ));
+                    }
+                    None => {
+                        return Err(format!(
+                            "Type \"{}\" implements the interface \"{}\", which is not registered.",
+                            type_name, interface
+                        ));
+                    }
+                }
+            }
+        }
+
+        for ty in self.types.values() {
+            match ty {
+                MetaType::Interface {
+                    name,
+                    possible_types,
+                    ..
+                } => {
+                    for type_name in possible_types {
+                        if !self.types.contains_key(type_name) {
+                            return Err(format!(
+                                "Type \"{}\" is a possible type of the interface \"{}\", but it is not registered.",
+                                type_name, name
+                            ));
+                        }
+                        if !self
+                            .implements
+                            .get(type_name)
+                            .map_or(false, |interfaces| interfaces.contains(name))
+                        {
+                            return Err(format!(
+                                "Type \"{}\" is a possible type of the interface \"{}\", but it does not implement \"{}\".",
+                                type_name, name, name
+                            ));
+                        }
+                    }
+                }
+                MetaType::Union {
+                    name,
+                    possible_types,
+                    ..
+                } => {
+                    for type_name in possible_types {
+                        if !self.types.contains_key(type_name) {
+                            return Err(format!(
+                                "Type \"{}\" is a member of the union \"{}\", but it is not registered.",
+                                type_name, name
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
 }
 
-pub(crate) fn is_visible(ctx: &Context<'_>, visible: &Option<MetaVisibleFn>) -> bool {
+pub(crate) fn is_visible(ctx: &Context<'_>, visible: &Option<MetaVisible>) -> bool {
     match visible {
-        Some(f) => f(ctx),
+        Some(MetaVisible::Hidden) => false,
+        Some(MetaVisible::Fn(f)) => f(ctx),
+        _ => is_visible_to_profile(visible, ctx.visibility_profile()),
+    }
+}
+
+/// Checks the visibility of an element outside of a request, with the
+/// visibility profile `profile`. The visibility functions need the context of
+/// a request, so the elements whose visibility is checked by a function are
+/// considered visible.
+pub(crate) fn is_visible_outside_request(
+    visible: &Option<MetaVisible>,
+    profile: Option<&str>,
+) -> bool {
+    !matches!(visible, Some(MetaVisible::Hidden)) && is_visible_to_profile(visible, profile)
+}
+
+/// Checks the visibility profiles of an element, the elements whose
+/// visibility is not checked by profiles are considered visible.
+pub(crate) fn is_visible_to_profile(visible: &Option<MetaVisible>, profile: Option<&str>) -> bool {
+    match visible.and_then(|visible| visible.profiles()) {
+        Some(profiles) => profile.map_or(false, |profile| profiles.contains(&profile)),
         None => true,
     }
 }
 
-fn is_system_type(name: &str) -> bool {
+pub(crate) fn is_system_type(name: &str) -> bool {
     if name.starts_with("__") {
         return true;
     }
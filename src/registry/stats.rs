@@ -0,0 +1,436 @@
+use std::mem::{size_of, size_of_val};
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::registry::{
+    MetaDirective, MetaDirectiveInvocation, MetaEnumValue, MetaField, MetaInputValue, MetaType,
+    Registry,
+};
+
+/// The size of a schema registry, see
+/// [`Schema::registry_stats`](crate::Schema::registry_stats).
+///
+/// The number of bytes is an estimate of the memory used by the registry, it
+/// counts the capacity of its strings and collections but not the allocator
+/// overhead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegistryStats {
+    /// The number of types.
+    pub types: usize,
+    /// The number of scalars.
+    pub scalars: usize,
+    /// The number of objects.
+    pub objects: usize,
+    /// The number of interfaces.
+    pub interfaces: usize,
+    /// The number of unions.
+    pub unions: usize,
+    /// The number of enums.
+    pub enums: usize,
+    /// The number of input objects.
+    pub input_objects: usize,
+    /// The number of fields of the objects and interfaces.
+    pub fields: usize,
+    /// The number of arguments of the fields and directives.
+    pub arguments: usize,
+    /// The number of fields of the input objects.
+    pub input_fields: usize,
+    /// The number of enum values.
+    pub enum_values: usize,
+    /// The number of directives.
+    pub directives: usize,
+    /// The estimated number of bytes used by the registry.
+    pub bytes: usize,
+    /// The number of bytes of the type references of the fields, arguments
+    /// and input fields, such as `[String!]!`, which are included in `bytes`.
+    ///
+    /// Each reference is a separate string, this is the part of the registry
+    /// that interning the type names would share.
+    pub type_reference_bytes: usize,
+}
+
+impl Registry {
+    /// Returns the number of types, fields and other elements of the registry,
+    /// and its estimated memory usage.
+    ///
+    /// The names and the types of the registry are public `String`s and
+    /// `MetaType`s built when the schema is built, they are neither interned
+    /// nor built lazily.
+    pub fn stats(&self) -> RegistryStats {
+        let mut stats = RegistryStats {
+            types: self.types.len(),
+            directives: self.directives.len(),
+            bytes: size_of::<Registry>()
+                + option_string_bytes(&self.description)
+                + self.query_type.capacity()
+                + option_string_bytes(&self.mutation_type)
+                + option_string_bytes(&self.subscription_type),
+            ..Default::default()
+        };
+
+        for (name, ty) in &self.types {
+            stats.bytes += BTREE_ENTRY_OVERHEAD
+                + size_of::<String>()
+                + size_of::<MetaType>()
+                + name.capacity()
+                + type_bytes(&mut stats, ty);
+        }
+
+        for (name, directive) in &self.directives {
+            stats.arguments += directive.args.len();
+            stats.bytes += BTREE_ENTRY_OVERHEAD
+                + size_of::<String>()
+                + size_of::<MetaDirective>()
+                + name.capacity()
+                + directive.name.capacity()
+                + option_string_bytes(&directive.description)
+                + directive.locations.capacity() * size_of::<crate::model::__DirectiveLocation>()
+                + option_string_bytes(&directive.composable)
+                + input_values_bytes(&mut stats, &directive.args);
+        }
+
+        for (name, interfaces) in &self.implements {
+            stats.bytes += size_of::<(String, IndexSet<String>)>()
+                + name.capacity()
+                + string_set_bytes(interfaces);
+        }
+
+        for (name, fields) in &self.merged_fields {
+            stats.bytes += size_of::<(String, std::collections::HashMap<String, usize>)>()
+                + name.capacity()
+                + fields
+                    .keys()
+                    .map(|name| size_of::<(String, usize)>() + name.capacity())
+                    .sum::<usize>();
+        }
+
+        stats.bytes += self
+            .visibility_profiles
+            .iter()
+            .map(|profile| size_of::<String>() + profile.capacity())
+            .sum::<usize>();
+
+        stats
+    }
+
+    /// Releases the unused capacity of the strings and collections of the
+    /// registry.
+    pub fn shrink_to_fit(&mut self) {
+        for ty in self.types.values_mut() {
+            match ty {
+                MetaType::Scalar {
+                    name,
+                    description,
+                    tags,
+                    specified_by_url,
+                    ..
+                } => {
+                    name.shrink_to_fit();
+                    shrink_option_string(description);
+                    shrink_strings(tags);
+                    shrink_option_string(specified_by_url);
+                }
+                MetaType::Object {
+                    name,
+                    description,
+                    fields,
+                    keys,
+                    tags,
+                    directive_invocations,
+                    ..
+                } => {
+                    name.shrink_to_fit();
+                    shrink_option_string(description);
+                    shrink_fields(fields);
+                    if let Some(keys) = keys {
+                        shrink_strings(keys);
+                    }
+                    shrink_strings(tags);
+                    directive_invocations.shrink_to_fit();
+                }
+                MetaType::Interface {
+                    name,
+                    description,
+                    fields,
+                    possible_types,
+                    keys,
+                    tags,
+                    ..
+                } => {
+                    name.shrink_to_fit();
+                    shrink_option_string(description);
+                    shrink_fields(fields);
+                    possible_types.shrink_to_fit();
+                    if let Some(keys) = keys {
+                        shrink_strings(keys);
+                    }
+                    shrink_strings(tags);
+                }
+                MetaType::Union {
+                    name,
+                    description,
+                    possible_types,
+                    tags,
+                    ..
+                } => {
+                    name.shrink_to_fit();
+                    shrink_option_string(description);
+                    possible_types.shrink_to_fit();
+                    shrink_strings(tags);
+                }
+                MetaType::Enum {
+                    name,
+                    description,
+                    enum_values,
+                    tags,
+                    ..
+                } => {
+                    name.shrink_to_fit();
+                    shrink_option_string(description);
+                    enum_values.shrink_to_fit();
+                    for value in enum_values.values_mut() {
+                        value.name.shrink_to_fit();
+                        shrink_option_string(&mut value.description);
+                        shrink_strings(&mut value.tags);
+                    }
+                    shrink_strings(tags);
+                }
+                MetaType::InputObject {
+                    name,
+                    description,
+                    input_fields,
+                    ..
+                } => {
+                    name.shrink_to_fit();
+                    shrink_option_string(description);
+                    shrink_input_values(input_fields);
+                }
+            }
+        }
+
+        for directive in self.directives.values_mut() {
+            shrink_input_values(&mut directive.args);
+            directive.locations.shrink_to_fit();
+        }
+        for interfaces in self.implements.values_mut() {
+            interfaces.shrink_to_fit();
+        }
+        self.implements.shrink_to_fit();
+        self.merged_fields.shrink_to_fit();
+    }
+}
+
+/// The approximate size of the node pointers and padding of an entry of a
+/// `BTreeMap`.
+const BTREE_ENTRY_OVERHEAD: usize = size_of::<usize>();
+
+fn option_string_bytes(s: &Option<String>) -> usize {
+    s.as_ref().map(String::capacity).unwrap_or_default()
+}
+
+fn strings_bytes(strings: &[String]) -> usize {
+    size_of_val(strings) + strings.iter().map(String::capacity).sum::<usize>()
+}
+
+fn index_map_bytes<K, V>(map: &IndexMap<K, V>) -> usize {
+    // an entry stores the hash, the key and the value, and the table stores
+    // its index
+    map.capacity() * (size_of::<usize>() * 2 + size_of::<K>() + size_of::<V>())
+}
+
+fn string_set_bytes(set: &IndexSet<String>) -> usize {
+    set.capacity() * (size_of::<usize>() * 2 + size_of::<String>())
+        + set.iter().map(String::capacity).sum::<usize>()
+}
+
+fn type_bytes(stats: &mut RegistryStats, ty: &MetaType) -> usize {
+    match ty {
+        MetaType::Scalar {
+            name,
+            description,
+            tags,
+            specified_by_url,
+            ..
+        } => {
+            stats.scalars += 1;
+            name.capacity()
+                + option_string_bytes(description)
+                + strings_bytes(tags)
+                + option_string_bytes(specified_by_url)
+        }
+        MetaType::Object {
+            name,
+            description,
+            fields,
+            keys,
+            tags,
+            directive_invocations,
+            ..
+        } => {
+            stats.objects += 1;
+            name.capacity()
+                + option_string_bytes(description)
+                + fields_bytes(stats, fields)
+                + keys.as_deref().map(strings_bytes).unwrap_or_default()
+                + strings_bytes(tags)
+                + directive_invocations_bytes(directive_invocations)
+        }
+        MetaType::Interface {
+            name,
+            description,
+            fields,
+            possible_types,
+            keys,
+            tags,
+            ..
+        } => {
+            stats.interfaces += 1;
+            name.capacity()
+                + option_string_bytes(description)
+                + fields_bytes(stats, fields)
+                + string_set_bytes(possible_types)
+                + keys.as_deref().map(strings_bytes).unwrap_or_default()
+                + strings_bytes(tags)
+        }
+        MetaType::Union {
+            name,
+            description,
+            possible_types,
+            tags,
+            ..
+        } => {
+            stats.unions += 1;
+            name.capacity()
+                + option_string_bytes(description)
+                + string_set_bytes(possible_types)
+                + strings_bytes(tags)
+        }
+        MetaType::Enum {
+            name,
+            description,
+            enum_values,
+            tags,
+            ..
+        } => {
+            stats.enums += 1;
+            stats.enum_values += enum_values.len();
+            name.capacity()
+                + option_string_bytes(description)
+                + index_map_bytes(enum_values)
+                + enum_values
+                    .iter()
+                    .map(|(key, value)| key.capacity() + enum_value_bytes(value))
+                    .sum::<usize>()
+                + strings_bytes(tags)
+        }
+        MetaType::InputObject {
+            name,
+            description,
+            input_fields,
+            ..
+        } => {
+            stats.input_objects += 1;
+            stats.input_fields += input_fields.len();
+            name.capacity()
+                + option_string_bytes(description)
+                + input_values_bytes(stats, input_fields)
+        }
+    }
+}
+
+fn fields_bytes(stats: &mut RegistryStats, fields: &IndexMap<String, MetaField>) -> usize {
+    stats.fields += fields.len();
+    index_map_bytes(fields)
+        + fields
+            .iter()
+            .map(|(key, field)| {
+                stats.arguments += field.args.len();
+                stats.type_reference_bytes += field.ty.capacity();
+                key.capacity()
+                    + field.name.capacity()
+                    + option_string_bytes(&field.description)
+                    + input_values_bytes(stats, &field.args)
+                    + field.ty.capacity()
+                    + field.deprecation.reason().map(str::len).unwrap_or_default()
+                    + option_string_bytes(&field.requires)
+                    + option_string_bytes(&field.provides)
+                    + strings_bytes(&field.tags)
+                    + option_string_bytes(&field.override_from)
+                    + directive_invocations_bytes(&field.directive_invocations)
+            })
+            .sum::<usize>()
+}
+
+fn input_values_bytes(
+    stats: &mut RegistryStats,
+    values: &IndexMap<String, MetaInputValue>,
+) -> usize {
+    index_map_bytes(values)
+        + values
+            .iter()
+            .map(|(key, value)| {
+                stats.type_reference_bytes += value.ty.capacity();
+                key.capacity()
+                    + value.name.capacity()
+                    + option_string_bytes(&value.description)
+                    + value.ty.capacity()
+                    + option_string_bytes(&value.default_value)
+                    + strings_bytes(&value.tags)
+            })
+            .sum::<usize>()
+}
+
+fn enum_value_bytes(value: &MetaEnumValue) -> usize {
+    value.name.capacity()
+        + option_string_bytes(&value.description)
+        + value.deprecation.reason().map(str::len).unwrap_or_default()
+        + strings_bytes(&value.tags)
+}
+
+fn directive_invocations_bytes(invocations: &[MetaDirectiveInvocation]) -> usize {
+    size_of_val(invocations)
+        + invocations
+            .iter()
+            .map(|invocation| {
+                invocation.name.capacity()
+                    + index_map_bytes(&invocation.args)
+                    + invocation.args.keys().map(String::capacity).sum::<usize>()
+            })
+            .sum::<usize>()
+}
+
+fn shrink_option_string(s: &mut Option<String>) {
+    if let Some(s) = s {
+        s.shrink_to_fit();
+    }
+}
+
+fn shrink_strings(strings: &mut Vec<String>) {
+    strings.shrink_to_fit();
+    for s in strings {
+        s.shrink_to_fit();
+    }
+}
+
+fn shrink_fields(fields: &mut IndexMap<String, MetaField>) {
+    fields.shrink_to_fit();
+    for field in fields.values_mut() {
+        field.name.shrink_to_fit();
+        shrink_option_string(&mut field.description);
+        shrink_input_values(&mut field.args);
+        field.ty.shrink_to_fit();
+        shrink_strings(&mut field.tags);
+        field.directive_invocations.shrink_to_fit();
+    }
+}
+
+fn shrink_input_values(values: &mut IndexMap<String, MetaInputValue>) {
+    values.shrink_to_fit();
+    for value in values.values_mut() {
+        value.name.shrink_to_fit();
+        shrink_option_string(&mut value.description);
+        value.ty.shrink_to_fit();
+        shrink_option_string(&mut value.default_value);
+        shrink_strings(&mut value.tags);
+    }
+}
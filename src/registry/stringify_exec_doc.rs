@@ -1,6 +1,9 @@
-use std::fmt::{Error, Result as FmtResult, Write};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Error, Result as FmtResult, Write},
+};
 
-use async_graphql_value::ConstValue;
+use async_graphql_value::{ConstValue, Name, Value};
 
 use crate::{
     parser::types::{
@@ -10,6 +13,75 @@ use crate::{
     Variables,
 };
 
+const SECRET: &str = "<secret>";
+
+/// The values passed to the secret arguments and input fields of a document.
+#[derive(Default)]
+struct SecretValues<'a> {
+    /// The variables.
+    variables: HashSet<&'a Name>,
+    /// Whether some values are written in the document.
+    literals: bool,
+}
+
+/// Returns a query that cannot be parsed with its strings replaced with
+/// `"<secret>"`, since the values of the secret arguments cannot be found
+/// without the document.
+#[cfg(any(feature = "tracing", feature = "opentelemetry"))]
+pub(crate) fn redact_unparsed_query(query: &str) -> String {
+    let mut output = String::with_capacity(query.len());
+    let mut rest = query;
+    while let Some(start) = rest.find(['"', '#']) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = if rest.starts_with('#') {
+            // a comment ends with the line
+            let end = rest.find(['\n', '\r']).unwrap_or(rest.len());
+            output.push_str(&rest[..end]);
+            end
+        } else {
+            write!(output, "\"{}\"", SECRET).unwrap();
+            if let Some(content) = rest.strip_prefix("\"\"\"") {
+                block_string_len(content) + 3
+            } else {
+                string_len(&rest[1..]) + 1
+            }
+        };
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Returns the length of the content and the closing quotes of a block string.
+#[cfg(any(feature = "tracing", feature = "opentelemetry"))]
+fn block_string_len(content: &str) -> usize {
+    let mut offset = 0;
+    while let Some(end) = content[offset..].find("\"\"\"") {
+        let end = offset + end;
+        if !content[..end].ends_with('\\') {
+            return end + 3;
+        }
+        offset = end + 3;
+    }
+    content.len()
+}
+
+/// Returns the length of the content and the closing quote of a string, which
+/// is unterminated at the end of the line.
+#[cfg(any(feature = "tracing", feature = "opentelemetry"))]
+fn string_len(content: &str) -> usize {
+    let mut escaped = false;
+    for (idx, c) in content.char_indices() {
+        match c {
+            '"' if !escaped => return idx + 1,
+            '\n' | '\r' => return idx,
+            _ => escaped = c == '\\' && !escaped,
+        }
+    }
+    content.len()
+}
+
 impl Registry {
     pub(crate) fn stringify_exec_doc(
         &self,
@@ -79,6 +151,209 @@ impl Registry {
         Ok(output)
     }
 
+    /// Returns a copy of `variables` where the values that are passed to
+    /// secret arguments or secret input fields are replaced with `"<secret>"`.
+    pub(crate) fn redact_variables(
+        &self,
+        variables: &Variables,
+        doc: &ExecutableDocument,
+    ) -> Variables {
+        let secret_values = self.secret_values(doc);
+        let mut variable_types = HashMap::new();
+        for (_, operation_definition) in doc.operations.iter() {
+            for variable_definition in &operation_definition.node.variable_definitions {
+                variable_types.insert(
+                    &variable_definition.node.name.node,
+                    variable_definition.node.var_type.node.to_string(),
+                );
+            }
+        }
+
+        let mut variables = variables.clone();
+        for (name, value) in variables.iter_mut() {
+            if secret_values.variables.contains(name) {
+                *value = ConstValue::String(SECRET.to_string());
+            } else if let Some(ty) = variable_types.get(name) {
+                self.redact_value(ty, value);
+            }
+        }
+        variables
+    }
+
+    /// Returns `true` if a value is written in `doc` for a secret argument or
+    /// a secret input field, instead of being passed in a variable.
+    #[cfg(feature = "apollo_persisted_queries")]
+    pub(crate) fn has_secret_literals(&self, doc: &ExecutableDocument) -> bool {
+        self.secret_values(doc).literals
+    }
+
+    fn secret_values<'a>(&self, doc: &'a ExecutableDocument) -> SecretValues<'a> {
+        let mut secret_values = SecretValues::default();
+        let mut visited_fragments = HashSet::new();
+        for (_, operation_definition) in doc.operations.iter() {
+            let root_type = match operation_definition.node.ty {
+                OperationType::Query => self.types.get(&self.query_type),
+                OperationType::Mutation => self
+                    .mutation_type
+                    .as_ref()
+                    .and_then(|name| self.types.get(name)),
+                OperationType::Subscription => self
+                    .subscription_type
+                    .as_ref()
+                    .and_then(|name| self.types.get(name)),
+            };
+            self.collect_secret_values(
+                doc,
+                &operation_definition.node.selection_set.node,
+                root_type,
+                &mut secret_values,
+                &mut visited_fragments,
+            );
+        }
+        secret_values
+    }
+
+    fn redact_value(&self, ty: &str, value: &mut ConstValue) {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.redact_value(ty, value),
+            MetaTypeName::List(ty) => match value {
+                ConstValue::List(items) => {
+                    for item in items {
+                        self.redact_value(ty, item);
+                    }
+                }
+                _ => self.redact_value(ty, value),
+            },
+            MetaTypeName::Named(name) => {
+                if let (Some(MetaType::InputObject { input_fields, .. }), ConstValue::Object(obj)) =
+                    (self.types.get(name), value)
+                {
+                    for (key, value) in obj.iter_mut() {
+                        match input_fields.get(key.as_str()) {
+                            Some(field) if field.is_secret => {
+                                *value = ConstValue::String(SECRET.to_string());
+                            }
+                            Some(field) => self.redact_value(&field.ty, value),
+                            None => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn collect_secret_values<'a>(
+        &self,
+        doc: &'a ExecutableDocument,
+        selection_set: &'a SelectionSet,
+        parent_type: Option<&MetaType>,
+        secret_values: &mut SecretValues<'a>,
+        visited_fragments: &mut HashSet<&'a Name>,
+    ) {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let meta_field = parent_type
+                        .and_then(|parent_type| parent_type.field_by_name(&field.node.name.node));
+                    for (name, value) in &field.node.arguments {
+                        if let Some(arg) = meta_field.and_then(|field| field.args.get(&*name.node))
+                        {
+                            self.collect_secret_values_in_value(arg, &value.node, secret_values);
+                        }
+                    }
+                    let ty = meta_field.and_then(|field| {
+                        self.types.get(MetaTypeName::concrete_typename(&field.ty))
+                    });
+                    self.collect_secret_values(
+                        doc,
+                        &field.node.selection_set.node,
+                        ty,
+                        secret_values,
+                        visited_fragments,
+                    );
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    let name = &fragment_spread.node.fragment_name.node;
+                    if !visited_fragments.insert(name) {
+                        continue;
+                    }
+                    if let Some(fragment) = doc.fragments.get(name) {
+                        self.collect_secret_values(
+                            doc,
+                            &fragment.node.selection_set.node,
+                            self.types
+                                .get(fragment.node.type_condition.node.on.node.as_str()),
+                            secret_values,
+                            visited_fragments,
+                        );
+                    }
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    let ty = match &inline_fragment.node.type_condition {
+                        Some(name) => self.types.get(name.node.on.node.as_str()),
+                        None => parent_type,
+                    };
+                    self.collect_secret_values(
+                        doc,
+                        &inline_fragment.node.selection_set.node,
+                        ty,
+                        secret_values,
+                        visited_fragments,
+                    );
+                }
+            }
+        }
+    }
+
+    fn collect_secret_values_in_value<'a>(
+        &self,
+        meta_input_value: &MetaInputValue,
+        value: &'a Value,
+        secret_values: &mut SecretValues<'a>,
+    ) {
+        fn collect_all<'a>(value: &'a Value, secret_values: &mut SecretValues<'a>) {
+            match value {
+                Value::Variable(name) => {
+                    secret_values.variables.insert(name);
+                }
+                Value::List(items) => items
+                    .iter()
+                    .for_each(|item| collect_all(item, secret_values)),
+                Value::Object(obj) => obj
+                    .values()
+                    .for_each(|item| collect_all(item, secret_values)),
+                Value::Null => {}
+                _ => secret_values.literals = true,
+            }
+        }
+
+        if meta_input_value.is_secret {
+            collect_all(value, secret_values);
+            return;
+        }
+
+        match value {
+            Value::List(items) => {
+                for item in items {
+                    self.collect_secret_values_in_value(meta_input_value, item, secret_values);
+                }
+            }
+            Value::Object(obj) => {
+                if let Some(MetaType::InputObject { input_fields, .. }) = self
+                    .types
+                    .get(MetaTypeName::concrete_typename(&meta_input_value.ty))
+                {
+                    for (key, value) in obj {
+                        if let Some(field) = input_fields.get(key.as_str()) {
+                            self.collect_secret_values_in_value(field, value, secret_values);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn stringify_fragment_definition(
         &self,
         output: &mut String,
@@ -109,7 +384,7 @@ impl Registry {
         value: &ConstValue,
     ) -> FmtResult {
         if meta_input_value.map(|v| v.is_secret).unwrap_or_default() {
-            output.push_str("\"<secret>\"");
+            write!(output, "\"{}\"", SECRET)?;
             return Ok(());
         }
 
@@ -178,6 +453,9 @@ impl Registry {
                         }
                         output.push(')');
                     }
+                    if let Some(nullability) = &field.node.nullability {
+                        write!(output, "{}", nullability.node)?;
+                    }
                     if !field.node.selection_set.node.items.is_empty() {
                         output.push(' ');
                         let parent_type = parent_type
@@ -223,6 +501,21 @@ mod tests {
     use super::*;
     use crate::{parser::parse_query, *};
 
+    #[cfg(any(feature = "tracing", feature = "opentelemetry"))]
+    #[test]
+    fn test_redact_unparsed_query() {
+        assert_eq!(
+            redact_unparsed_query(
+                r#"{ login(password: "a\"b", token: """c\"""d""") # "e"
+                  value(a: 1, b: "f
+                }"#
+            ),
+            r#"{ login(password: "<secret>", token: "<secret>") # "e"
+                  value(a: 1, b: "<secret>"
+                }"#
+        );
+    }
+
     #[test]
     fn test_stringify() {
         let registry = Registry::default();
@@ -313,4 +606,61 @@ mod tests {
             r#"query { value(a: 10, b: "<secret>", c: {v1: 1, v2: "<secret>", v3: {v4: 4, v5: "<secret>"}}) }"#
         );
     }
+
+    #[test]
+    fn test_redact_variables() {
+        #[derive(InputObject)]
+        #[graphql(internal)]
+        struct MyInput {
+            v1: i32,
+            #[graphql(secret)]
+            v2: String,
+        }
+
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct MyObj {
+            value: i32,
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        #[allow(unreachable_code, unused_variables)]
+        impl Query {
+            async fn login(&self, username: String, #[graphql(secret)] password: String) -> MyObj {
+                todo!()
+            }
+
+            async fn inputs(&self, input: Vec<MyInput>) -> i32 {
+                todo!()
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let registry = schema.registry();
+        let doc = parse_query(
+            r#"
+            query ($username: String!, $password: String!, $input: [MyInput!]!) {
+                ... on Query { login(username: $username, password: $password) { ...F } }
+                inputs(input: $input)
+            }
+            fragment F on MyObj { value }
+        "#,
+        )
+        .unwrap();
+        let variables = Variables::from_value(value!({
+            "username": "abc",
+            "password": "123456",
+            "input": [{ "v1": 1, "v2": "a" }, { "v1": 2, "v2": "b" }],
+        }));
+        assert_eq!(
+            registry.redact_variables(&variables, &doc).into_value(),
+            value!({
+                "username": "abc",
+                "password": "<secret>",
+                "input": [{ "v1": 1, "v2": "<secret>" }, { "v1": 2, "v2": "<secret>" }],
+            })
+        );
+    }
 }
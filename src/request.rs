@@ -1,12 +1,14 @@
 use std::{
-    any::Any,
-    collections::HashMap,
+    any::{Any, TypeId},
+    collections::{HashMap, HashSet},
     fmt::{self, Debug, Formatter},
+    time::Duration,
 };
 
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
+    extensions::ExtensionFactory,
     parser::{parse_query, types::ExecutableDocument},
     schema::IntrospectionMode,
     Data, ParseRequestError, ServerError, UploadValue, Value, Variables,
@@ -54,6 +56,26 @@ pub struct Request {
     /// [IntrospectionMode::Enabled]).
     #[serde(skip)]
     pub introspection_mode: IntrospectionMode,
+
+    /// Overrides the maximum depth of the schema for this request.
+    #[serde(skip)]
+    pub depth_limit: Option<usize>,
+
+    /// Overrides the maximum complexity of the schema for this request.
+    #[serde(skip)]
+    pub complexity_limit: Option<usize>,
+
+    /// Overrides the maximum approximate size of the response of the schema
+    /// for this request.
+    #[serde(skip)]
+    pub response_size_limit: Option<usize>,
+
+    /// The maximum duration of the execution of this request.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+
+    #[serde(skip)]
+    pub(crate) disabled_extensions: HashSet<TypeId>,
 }
 
 impl Request {
@@ -68,6 +90,11 @@ impl Request {
             extensions: Default::default(),
             parsed_query: None,
             introspection_mode: IntrospectionMode::Enabled,
+            depth_limit: None,
+            complexity_limit: None,
+            response_size_limit: None,
+            timeout: None,
+            disabled_extensions: HashSet::new(),
         }
     }
 
@@ -107,6 +134,55 @@ impl Request {
         self
     }
 
+    /// Set the maximum depth of this request, overriding the limit of the
+    /// schema.
+    ///
+    /// This allows trusted callers to run deeper queries than the schema
+    /// allows by default, or to further restrict untrusted ones.
+    #[must_use]
+    pub fn depth_limit(mut self, depth: usize) -> Self {
+        self.depth_limit = Some(depth);
+        self
+    }
+
+    /// Set the maximum complexity of this request, overriding the limit of
+    /// the schema.
+    #[must_use]
+    pub fn complexity_limit(mut self, complexity: usize) -> Self {
+        self.complexity_limit = Some(complexity);
+        self
+    }
+
+    /// Set the maximum approximate size, in bytes, of the response of this
+    /// request, overriding the limit of the schema. Subscriptions are not
+    /// limited.
+    #[must_use]
+    pub fn response_size_limit(mut self, size: usize) -> Self {
+        self.response_size_limit = Some(size);
+        self
+    }
+
+    /// Set the maximum duration of the execution of this request.
+    ///
+    /// If the execution takes longer, it is cancelled and the response
+    /// contains a `Request timed out.` error. This has no effect on
+    /// subscriptions.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Disable the extension created by the factory `E` for this request.
+    ///
+    /// This allows debugging extensions, such as `ApolloTracing`, to only run
+    /// for the requests of authorized clients.
+    #[must_use]
+    pub fn disable_extension<E: ExtensionFactory>(mut self) -> Self {
+        self.disabled_extensions.insert(TypeId::of::<E>());
+        self
+    }
+
     #[inline]
     /// Performs parsing of query ahead of execution.
     ///
@@ -259,6 +335,51 @@ impl BatchRequest {
         }
         self
     }
+
+    /// Set the maximum depth of each request.
+    #[must_use]
+    pub fn depth_limit(mut self, depth: usize) -> Self {
+        for request in self.iter_mut() {
+            request.depth_limit = Some(depth);
+        }
+        self
+    }
+
+    /// Set the maximum complexity of each request.
+    #[must_use]
+    pub fn complexity_limit(mut self, complexity: usize) -> Self {
+        for request in self.iter_mut() {
+            request.complexity_limit = Some(complexity);
+        }
+        self
+    }
+
+    /// Set the maximum approximate size of the response of each request.
+    #[must_use]
+    pub fn response_size_limit(mut self, size: usize) -> Self {
+        for request in self.iter_mut() {
+            request.response_size_limit = Some(size);
+        }
+        self
+    }
+
+    /// Set the maximum duration of the execution of each request.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        for request in self.iter_mut() {
+            request.timeout = Some(timeout);
+        }
+        self
+    }
+
+    /// Disable the extension created by the factory `E` for each request.
+    #[must_use]
+    pub fn disable_extension<E: ExtensionFactory>(mut self) -> Self {
+        for request in self.iter_mut() {
+            request.disabled_extensions.insert(TypeId::of::<E>());
+        }
+        self
+    }
 }
 
 fn deserialize_non_empty_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
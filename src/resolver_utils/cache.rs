@@ -0,0 +1,93 @@
+use std::{future::Future, time::Duration, time::SystemTime};
+
+use crate::{
+    field_cache::{field_cache_key, FieldCacheEntry},
+    Context, ServerResult, Value,
+};
+
+/// The cache policy of a field resolver.
+///
+/// It is used by `#[graphql(cache(ttl = "30s", swr = "5m"))]`.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldCachePolicy {
+    /// How long the cached value is fresh.
+    pub ttl: Duration,
+    /// How long the cached value can still be used after it is stale, while
+    /// it is being revalidated.
+    pub swr: Duration,
+    /// Whether the arguments of the field are part of the cache key.
+    pub key_args: bool,
+}
+
+/// Resolves a field of `type_name` with a cache.
+///
+/// The resolved value is cached in the storage set with
+/// [`SchemaBuilder::field_cache_storage`](crate::SchemaBuilder::field_cache_storage),
+/// and the field is resolved without cache if no storage is set. A fresh
+/// value is returned from the cache. A stale value is also returned until
+/// the end of the `swr` window, while the first request that finds it
+/// refreshes it in the background with the spawner set with
+/// [`SchemaBuilder::field_cache_spawner`](crate::SchemaBuilder::field_cache_spawner).
+/// Without a spawner, or in a mutation or a subscription, that request
+/// refreshes it before responding instead. The values resolved with errors
+/// are not cached.
+pub async fn resolve_with_cache(
+    ctx: &Context<'_>,
+    type_name: &str,
+    policy: FieldCachePolicy,
+    fut: impl Future<Output = ServerResult<Option<Value>>>,
+) -> ServerResult<Option<Value>> {
+    let cache = match &ctx.schema_env.field_cache {
+        Some(cache) => cache,
+        None => return fut.await,
+    };
+
+    let key = field_cache_key(ctx, type_name, policy.key_args);
+    let mut _guard = None;
+    // the value is not read when this query is the one refreshing it
+    let entry = if ctx.query_env.field_cache_refresh.as_deref() == Some(key.as_str()) {
+        None
+    } else {
+        cache.get(&key).await
+    };
+    if let Some(entry) = entry {
+        let age = entry.created_at.elapsed().unwrap_or_default();
+        if age < policy.ttl {
+            add_cache_hit(ctx);
+            return Ok(Some(entry.value));
+        }
+        if age < policy.ttl + policy.swr {
+            if let Some(guard) = cache.start_revalidation(&key) {
+                match cache.revalidate_in_background(ctx, key.clone(), guard) {
+                    Ok(()) => {}
+                    Err(guard) => _guard = Some(guard),
+                }
+            }
+            if _guard.is_none() {
+                add_cache_hit(ctx);
+                return Ok(Some(entry.value));
+            }
+        }
+    }
+
+    let errors = ctx.query_env.errors.lock().unwrap().len();
+    let res = fut.await;
+    if let Ok(Some(value)) = &res {
+        if ctx.query_env.errors.lock().unwrap().len() == errors {
+            let created_at = SystemTime::now();
+            let entry = FieldCacheEntry {
+                value: value.clone(),
+                created_at,
+                expires_at: created_at + policy.ttl + policy.swr,
+            };
+            cache.set(key, entry).await;
+        }
+    }
+    res
+}
+
+fn add_cache_hit(ctx: &Context<'_>) {
+    if let Some(stats) = &ctx.query_env.execution_stats {
+        stats.add_cache_hit();
+    }
+}
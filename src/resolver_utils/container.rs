@@ -1,11 +1,13 @@
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{any::Any, future::Future, pin::Pin, sync::Arc};
 
 use futures_util::FutureExt;
 use indexmap::IndexMap;
 
 use crate::{
-    extensions::ResolveInfo, parser::types::Selection, Context, ContextBase, ContextSelectionSet,
-    Error, Name, OutputType, ServerError, ServerResult, Value,
+    extensions::ResolveInfo,
+    parser::types::{Field, NullabilityDesignator, Selection},
+    Context, ContextBase, ContextSelectionSet, CustomDirective, Error, Name, OutputType,
+    Positioned, ServerError, ServerResult, Value,
 };
 
 /// Represents a GraphQL container object.
@@ -48,6 +50,13 @@ pub trait ContainerType: OutputType {
     async fn find_entity(&self, _: &Context<'_>, _params: &Value) -> ServerResult<Option<Value>> {
         Ok(None)
     }
+
+    /// Returns the container as `Any`, so that it can be passed to the
+    /// [`ObjectExtension`](crate::ObjectExtension)s of its type.
+    #[doc(hidden)]
+    fn as_any(&self) -> Option<&(dyn Any + Send + Sync)> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
@@ -59,6 +68,10 @@ impl<T: ContainerType + ?Sized> ContainerType for &T {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(*self, ctx, params).await
     }
+
+    fn as_any(&self) -> Option<&(dyn Any + Send + Sync)> {
+        T::as_any(*self)
+    }
 }
 
 #[async_trait::async_trait]
@@ -70,6 +83,10 @@ impl<T: ContainerType + ?Sized> ContainerType for Arc<T> {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(self, ctx, params).await
     }
+
+    fn as_any(&self) -> Option<&(dyn Any + Send + Sync)> {
+        T::as_any(self)
+    }
 }
 
 #[async_trait::async_trait]
@@ -81,6 +98,10 @@ impl<T: ContainerType + ?Sized> ContainerType for Box<T> {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(self, ctx, params).await
     }
+
+    fn as_any(&self) -> Option<&(dyn Any + Send + Sync)> {
+        T::as_any(self)
+    }
 }
 
 #[async_trait::async_trait]
@@ -98,6 +119,10 @@ impl<T: ContainerType, E: Into<Error> + Send + Sync + Clone> ContainerType for R
             Err(err) => Err(ctx.set_error_path(err.clone().into().into_server_error(ctx.item.pos))),
         }
     }
+
+    fn as_any(&self) -> Option<&(dyn Any + Send + Sync)> {
+        self.as_ref().ok().and_then(T::as_any)
+    }
 }
 
 /// Resolve an container by executing each of the fields concurrently.
@@ -168,10 +193,110 @@ async fn resolve_container_inner<'a, T: ContainerType + ?Sized>(
         results
     };
 
-    Ok(create_value_object(res))
+    Ok(create_value_object(res.into_iter().flatten().collect()))
+}
+
+async fn resolve_field<T: ContainerType + ?Sized>(
+    root: &T,
+    ctx: &Context<'_>,
+) -> ServerResult<Option<Value>> {
+    match &ctx.query_env.execution_stats {
+        Some(stats) => stats.resolve_field(resolve_field_inner(root, ctx)).await,
+        None => resolve_field_inner(root, ctx).await,
+    }
 }
 
-type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<(Name, Value)>> + 'a + Send>>;
+async fn resolve_field_inner<T: ContainerType + ?Sized>(
+    root: &T,
+    ctx: &Context<'_>,
+) -> ServerResult<Option<Value>> {
+    #[cfg(feature = "dynamic-schema")]
+    let has_dynamic_fields = !ctx.schema_env.dynamic_fields.is_empty();
+    #[cfg(not(feature = "dynamic-schema"))]
+    let has_dynamic_fields = false;
+
+    // The type name is computed for each field, and allocated for the generic
+    // types, so it is skipped unless some fields are added to the types.
+    if !has_dynamic_fields && ctx.schema_env.object_extensions.is_empty() {
+        return root.resolve_field(ctx).await;
+    }
+    let type_name = T::type_name();
+    let field_name = ctx.item.node.name.node.as_str();
+
+    #[cfg(feature = "dynamic-schema")]
+    if let Some(field) = ctx
+        .schema_env
+        .dynamic_fields
+        .get(type_name.as_ref())
+        .and_then(|fields| fields.get(field_name))
+    {
+        return crate::dynamic::resolve_dynamic_field(field, root.as_any(), ctx).await;
+    }
+
+    if let (Some(resolver), Some(target)) = (
+        ctx.schema_env
+            .object_extensions
+            .get(type_name.as_ref())
+            .and_then(|fields| fields.get(field_name)),
+        root.as_any(),
+    ) {
+        return resolver(target, ctx).await;
+    }
+
+    root.resolve_field(ctx).await
+}
+
+/// Calls [`CustomDirective::transform_arguments`] for the directives of a
+/// field, and returns a copy of the field with the transformed arguments if
+/// they have changed.
+async fn transform_arguments(
+    ctx_field: &Context<'_>,
+    directives: &[Box<dyn CustomDirective>],
+) -> ServerResult<Option<Positioned<Field>>> {
+    if directives.is_empty() {
+        return Ok(None);
+    }
+
+    let field = ctx_field.item;
+    let mut arguments = IndexMap::new();
+    for (name, value) in &field.node.arguments {
+        arguments.insert(
+            name.node.clone(),
+            ctx_field.resolve_input_value(value.clone())?,
+        );
+    }
+    let original_arguments = arguments.clone();
+    for directive in directives {
+        directive
+            .transform_arguments(ctx_field, &mut arguments)
+            .await?;
+    }
+    if arguments == original_arguments {
+        return Ok(None);
+    }
+
+    let mut transformed_field = field.clone();
+    transformed_field.node.arguments = arguments
+        .into_iter()
+        .map(|(name, value)| {
+            let pos = field
+                .node
+                .arguments
+                .iter()
+                .find(|(arg_name, _)| arg_name.node == name)
+                .map(|(_, value)| value.pos)
+                .unwrap_or(field.pos);
+            (
+                Positioned::new(name, pos),
+                Positioned::new(value.into_value(), pos),
+            )
+        })
+        .collect();
+    Ok(Some(transformed_field))
+}
+
+type BoxFieldFuture<'a> =
+    Pin<Box<dyn Future<Output = ServerResult<Option<(Name, Value)>>> + 'a + Send>>;
 
 /// A set of fields on an container that are being selected.
 pub struct Fields<'a>(Vec<BoxFieldFuture<'a>>);
@@ -194,7 +319,7 @@ impl<'a> Fields<'a> {
                         let typename = root.introspection_type_name().into_owned();
 
                         self.0.push(Box::pin(async move {
-                            Ok((field_name, Value::String(typename)))
+                            Ok(Some((field_name, Value::String(typename))))
                         }));
                         continue;
                     }
@@ -206,98 +331,177 @@ impl<'a> Fields<'a> {
                             let field_name = ctx_field.item.node.response_key().node.clone();
                             let extensions = &ctx.query_env.extensions;
 
+                            if ctx.query_env.is_errors_exceeded() {
+                                return Err(ctx_field.set_error_path(
+                                    ctx.query_env.errors_exceeded_error(field.pos),
+                                ));
+                            }
+                            if ctx.query_env.is_response_size_exceeded() {
+                                return Ok(Some((field_name, Value::Null)));
+                            }
+
                             if extensions.is_empty() && field.node.directives.is_empty() {
-                                Ok((
+                                return Ok(Some((
                                     field_name,
-                                    root.resolve_field(&ctx_field).await?.unwrap_or_default(),
-                                ))
-                            } else {
-                                let type_name = T::type_name();
-                                let resolve_info = ResolveInfo {
-                                    path_node: ctx_field.path_node.as_ref().unwrap(),
-                                    parent_type: &type_name,
-                                    return_type: match ctx_field
-                                        .schema_env
-                                        .registry
-                                        .types
-                                        .get(type_name.as_ref())
-                                        .and_then(|ty| {
-                                            ty.field_by_name(field.node.name.node.as_str())
-                                        })
-                                        .map(|field| &field.ty)
-                                    {
-                                        Some(ty) => &ty,
-                                        None => {
-                                            return Err(ServerError::new(
-                                                format!(
-                                                    r#"Cannot query field "{}" on type "{}"."#,
-                                                    field_name, type_name
-                                                ),
-                                                Some(ctx_field.item.pos),
-                                            ));
-                                        }
-                                    },
-                                    name: field.node.name.node.as_str(),
-                                    alias: field
-                                        .node
-                                        .alias
-                                        .as_ref()
-                                        .map(|alias| alias.node.as_str()),
-                                    is_for_introspection: ctx_field.is_for_introspection,
-                                };
-
-                                let resolve_fut = root.resolve_field(&ctx_field);
-
-                                if field.node.directives.is_empty() {
-                                    futures_util::pin_mut!(resolve_fut);
-                                    Ok((
-                                        field_name,
-                                        extensions
-                                            .resolve(resolve_info, &mut resolve_fut)
-                                            .await?
-                                            .unwrap_or_default(),
-                                    ))
-                                } else {
-                                    let mut resolve_fut = resolve_fut.boxed();
-
-                                    for directive in &field.node.directives {
-                                        if let Some(directive_factory) = ctx
-                                            .schema_env
-                                            .custom_directives
-                                            .get(directive.node.name.node.as_str())
-                                        {
-                                            let ctx_directive = ContextBase {
-                                                path_node: ctx_field.path_node,
-                                                is_for_introspection: false,
-                                                item: directive,
-                                                schema_env: ctx_field.schema_env,
-                                                query_env: ctx_field.query_env,
-                                            };
-                                            let directive_instance = directive_factory
-                                                .create(&ctx_directive, &directive.node)?;
-                                            resolve_fut = Box::pin({
-                                                let ctx_field = ctx_field.clone();
-                                                async move {
-                                                    directive_instance
-                                                        .resolve_field(&ctx_field, &mut resolve_fut)
-                                                        .await
-                                                }
-                                            });
-                                        }
-                                    }
+                                    resolve_field(root, &ctx_field).await?.unwrap_or_default(),
+                                )));
+                            }
 
-                                    Ok((
-                                        field_name,
-                                        extensions
-                                            .resolve(resolve_info, &mut resolve_fut)
-                                            .await?
-                                            .unwrap_or_default(),
-                                    ))
+                            let mut directives = Vec::new();
+                            for directive in &field.node.directives {
+                                if let Some(directive_factory) = ctx
+                                    .schema_env
+                                    .custom_directives
+                                    .get(directive.node.name.node.as_str())
+                                {
+                                    let ctx_directive = ContextBase {
+                                        path_node: ctx_field.path_node,
+                                        is_for_introspection: false,
+                                        item: directive,
+                                        schema_env: ctx_field.schema_env,
+                                        query_env: ctx_field.query_env,
+                                        scope: ctx_field.scope.clone(),
+                                    };
+                                    directives.push(
+                                        directive_factory
+                                            .create(&ctx_directive, &directive.node)?,
+                                    );
                                 }
                             }
+
+                            for directive in &directives {
+                                if directive.skip_field(&ctx_field).await? {
+                                    return Ok(None);
+                                }
+                            }
+
+                            let transformed_field =
+                                transform_arguments(&ctx_field, &directives).await?;
+                            let ctx_field = match &transformed_field {
+                                Some(field) => ctx.with_field(field),
+                                None => ctx_field,
+                            };
+
+                            let type_name = T::type_name();
+                            let resolve_info = ResolveInfo {
+                                path_node: ctx_field.path_node.as_ref().unwrap(),
+                                parent_type: &type_name,
+                                return_type: match ctx_field
+                                    .schema_env
+                                    .registry
+                                    .types
+                                    .get(type_name.as_ref())
+                                    .and_then(|ty| ty.field_by_name(field.node.name.node.as_str()))
+                                    .map(|field| &field.ty)
+                                {
+                                    Some(ty) => &ty,
+                                    None => {
+                                        return Err(ServerError::new(
+                                            format!(
+                                                r#"Cannot query field "{}" on type "{}"."#,
+                                                field_name, type_name
+                                            ),
+                                            Some(ctx_field.item.pos),
+                                        ));
+                                    }
+                                },
+                                name: field.node.name.node.as_str(),
+                                alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
+                                is_for_introspection: ctx_field.is_for_introspection,
+                            };
+
+                            let mut resolve_fut = resolve_field(root, &ctx_field).boxed();
+                            for directive in &directives {
+                                let ctx_field = &ctx_field;
+                                resolve_fut = Box::pin(async move {
+                                    directive.resolve_field(ctx_field, &mut resolve_fut).await
+                                });
+                            }
+
+                            Ok(Some((
+                                field_name,
+                                extensions
+                                    .resolve(resolve_info, &mut resolve_fut)
+                                    .await?
+                                    .unwrap_or_default(),
+                            )))
                         }
                     });
 
+                    let resolve_fut: BoxFieldFuture<'a> = if ctx.is_for_introspection
+                        && !ctx.schema_env.introspection_hooks.is_empty()
+                    {
+                        Box::pin({
+                            let ctx = ctx.clone();
+                            async move {
+                                let mut res = resolve_fut.await?;
+                                if let Some((_, value)) = &mut res {
+                                    ctx.schema_env
+                                        .apply_introspection_hooks(&ctx.with_field(field), value);
+                                }
+                                Ok(res)
+                            }
+                        })
+                    } else {
+                        resolve_fut
+                    };
+
+                    let resolve_fut: BoxFieldFuture<'a> = match ctx.query_env.max_response_size {
+                        Some(_) => Box::pin({
+                            let query_env = ctx.query_env;
+                            async move {
+                                let res = resolve_fut.await?;
+                                if let Some((_, value)) = &res {
+                                    query_env.add_response_size(&field.node, value);
+                                }
+                                Ok(res)
+                            }
+                        }),
+                        None => resolve_fut,
+                    };
+
+                    let resolve_fut: BoxFieldFuture<'a> = match field
+                        .node
+                        .nullability
+                        .as_ref()
+                        .map(|nullability| nullability.node)
+                    {
+                        Some(NullabilityDesignator::Optional) => Box::pin({
+                            let ctx = ctx.clone();
+                            async move {
+                                match resolve_fut.await {
+                                    Ok(res) => Ok(res),
+                                    Err(err) => {
+                                        ctx.add_error(err);
+                                        Ok(Some((
+                                            field.node.response_key().node.clone(),
+                                            Value::Null,
+                                        )))
+                                    }
+                                }
+                            }
+                        }),
+                        Some(NullabilityDesignator::Required) => Box::pin({
+                            let ctx = ctx.clone();
+                            async move {
+                                let res = resolve_fut.await?;
+                                if let Some((field_name, Value::Null)) = &res {
+                                    return Err(ctx.with_field(field).set_error_path(
+                                            ServerError::new(
+                                                format!(
+                                                    r#"Cannot return null for the required field "{}"."#,
+                                                    field_name
+                                                ),
+                                                Some(field.pos),
+                                            ),
+                                        ));
+                                }
+                                Ok(res)
+                            }
+                        }),
+                        None => resolve_fut,
+                    };
+
                     self.0.push(resolve_fut);
                 }
                 selection => {
@@ -0,0 +1,20 @@
+/// Converts the value of a field into the value of a field derived from it
+/// with `#[graphql(derived(into = "..."))]`.
+#[doc(hidden)]
+#[cfg_attr(
+    diagnostic_namespace,
+    diagnostic::on_unimplemented(
+        message = "the derived field can't be converted from `{Self}` into `{T}`",
+        note = "did you mean to add `#[graphql(owned)]` to the field, or `owned` to `derived(...)`, to convert the owned value?"
+    )
+)]
+pub trait IntoDerived<T> {
+    fn into_derived(self) -> T;
+}
+
+#[cfg_attr(diagnostic_do_not_recommend, diagnostic::do_not_recommend)]
+impl<S: Into<T>, T> IntoDerived<T> for S {
+    fn into_derived(self) -> T {
+        self.into()
+    }
+}
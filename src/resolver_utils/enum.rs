@@ -1,3 +1,5 @@
+use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
 use crate::{InputType, InputValueError, InputValueResult, Name, Value};
 
 /// A variant of an enum.
@@ -12,15 +14,49 @@ pub struct EnumItem<T> {
 pub trait EnumType: Sized + Eq + Send + Copy + Sized + 'static {
     /// Get a list of possible variants of the enum and their values.
     fn items() -> &'static [EnumItem<Self>];
+
+    /// Get the variant that the unknown external strings are mapped to, if
+    /// any.
+    ///
+    /// The unknown GraphQL values are still rejected.
+    fn fallback() -> Option<Self> {
+        None
+    }
+
+    /// Get a list of possible variants of the enum and their external
+    /// strings, which are used to exchange the enum with external systems.
+    ///
+    /// The external strings are the names of the variants by default.
+    fn external_items() -> &'static [EnumItem<Self>] {
+        Self::items()
+    }
+
+    /// Get the integer value of the variant, if the enum has integer values.
+    fn as_int(self) -> Option<i64> {
+        None
+    }
+
+    /// Get the variant whose integer value is `value`, if any.
+    fn from_int(_value: i64) -> Option<Self> {
+        None
+    }
 }
 
 /// Parse a value as an enum value.
 ///
+/// The integers are parsed with [`EnumType::from_int`].
+///
 /// This can be used to implement `InputType::parse`.
 pub fn parse_enum<T: EnumType + InputType>(value: Value) -> InputValueResult<T> {
     let value = match &value {
         Value::Enum(s) => s,
         Value::String(s) => s.as_str(),
+        Value::Number(n) => {
+            return n
+                .as_i64()
+                .and_then(T::from_int)
+                .ok_or_else(|| InputValueError::expected_type(value))
+        }
         _ => return Err(InputValueError::expected_type(value)),
     };
 
@@ -44,3 +80,46 @@ pub fn enum_value<T: EnumType>(value: T) -> Value {
     let item = T::items().iter().find(|item| item.value == value).unwrap();
     Value::Enum(Name::new(item.name))
 }
+
+/// Convert the enum value into its external string.
+pub fn enum_external_str<T: EnumType>(value: T) -> &'static str {
+    T::external_items()
+        .iter()
+        .find(|item| item.value == value)
+        .unwrap()
+        .name
+}
+
+/// Parse an external string as an enum value.
+///
+/// The unknown strings are parsed as the fallback value, if any.
+pub fn parse_enum_external<T: EnumType>(value: &str) -> Option<T> {
+    T::external_items()
+        .iter()
+        .find(|item| item.name == value)
+        .map(|item| item.value)
+        .or_else(T::fallback)
+}
+
+/// Serialize and deserialize an enum as its external string, for use with
+/// `#[serde(with = "async_graphql::resolver_utils::external_enum")]`.
+pub mod external_enum {
+    use super::*;
+
+    /// Serialize an enum value as its external string.
+    pub fn serialize<T: EnumType, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(enum_external_str(*value))
+    }
+
+    /// Deserialize an enum value from its external string.
+    pub fn deserialize<'de, T: EnumType, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_enum_external(&value)
+            .ok_or_else(|| D::Error::custom(format!(r#"unknown enum value "{}""#, value)))
+    }
+}
@@ -1,3 +1,7 @@
+use std::sync::atomic::AtomicUsize;
+
+use futures_util::{Stream, StreamExt, TryStreamExt};
+
 use crate::{
     extensions::ResolveInfo, parser::types::Field, ContextSelectionSet, OutputType, Positioned,
     ServerResult, Value,
@@ -10,53 +14,82 @@ pub async fn resolve_list<'a, T: OutputType + 'a>(
     iter: impl IntoIterator<Item = T>,
     len: Option<usize>,
 ) -> ServerResult<Value> {
-    let extensions = &ctx.query_env.extensions;
-    if !extensions.is_empty() {
-        let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
-        for (idx, item) in iter.into_iter().enumerate() {
-            futures.push({
-                let ctx = ctx.clone();
-                async move {
-                    let ctx_idx = ctx.with_index(idx);
-                    let extensions = &ctx.query_env.extensions;
+    let list_size = AtomicUsize::new(0);
+    let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
+    for (idx, item) in iter.into_iter().enumerate() {
+        futures.push(resolve_list_item(ctx, field, &list_size, idx, item));
+    }
+    Ok(Value::List(
+        futures_util::future::try_join_all(futures).await?,
+    ))
+}
+
+/// Resolve a list from a stream, executing up to `buffer_size` of the items
+/// concurrently as soon as the stream produces them.
+///
+/// Only the resolved values are kept, so the items of the stream are never
+/// buffered all at once.
+pub async fn resolve_list_stream<'a, T: OutputType + 'a>(
+    ctx: &ContextSelectionSet<'a>,
+    field: &Positioned<Field>,
+    stream: impl Stream<Item = T>,
+    buffer_size: usize,
+) -> ServerResult<Value> {
+    let list_size = AtomicUsize::new(0);
+    Ok(Value::List(
+        stream
+            .enumerate()
+            .map(|(idx, item)| resolve_list_item(ctx, field, &list_size, idx, item))
+            .buffered(buffer_size.max(1))
+            .try_collect()
+            .await?,
+    ))
+}
 
-                    let resolve_info = ResolveInfo {
-                        path_node: ctx_idx.path_node.as_ref().unwrap(),
-                        parent_type: &Vec::<T>::type_name(),
-                        return_type: &T::qualified_type_name(),
-                        name: field.node.name.node.as_str(),
-                        alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
-                        is_for_introspection: ctx_idx.is_for_introspection,
-                    };
-                    let resolve_fut = async {
-                        OutputType::resolve(&item, &ctx_idx, field)
-                            .await
-                            .map(Option::Some)
-                            .map_err(|err| ctx_idx.set_error_path(err))
-                    };
-                    futures_util::pin_mut!(resolve_fut);
-                    extensions
-                        .resolve(resolve_info, &mut resolve_fut)
-                        .await
-                        .map(|value| value.expect("You definitely encountered a bug!"))
-                }
-            });
-        }
-        Ok(Value::List(
-            futures_util::future::try_join_all(futures).await?,
-        ))
-    } else {
-        let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
-        for (idx, item) in iter.into_iter().enumerate() {
-            let ctx_idx = ctx.with_index(idx);
-            futures.push(async move {
-                OutputType::resolve(&item, &ctx_idx, field)
-                    .await
-                    .map_err(|err| ctx_idx.set_error_path(err))
-            });
-        }
-        Ok(Value::List(
-            futures_util::future::try_join_all(futures).await?,
-        ))
+async fn resolve_list_item<'a, T: OutputType + 'a>(
+    ctx: &ContextSelectionSet<'a>,
+    field: &Positioned<Field>,
+    list_size: &AtomicUsize,
+    idx: usize,
+    item: T,
+) -> ServerResult<Value> {
+    let ctx_idx = ctx.with_index(idx);
+    let value = resolve_item(&ctx_idx, field, item).await?;
+    ctx.query_env
+        .check_list_item_size(list_size, field, &value)
+        .map_err(|err| ctx_idx.set_error_path(err))?;
+    Ok(value)
+}
+
+async fn resolve_item<'a, T: OutputType + 'a>(
+    ctx_idx: &ContextSelectionSet<'a>,
+    field: &Positioned<Field>,
+    item: T,
+) -> ServerResult<Value> {
+    let extensions = &ctx_idx.query_env.extensions;
+    if extensions.is_empty() {
+        return OutputType::resolve(&item, ctx_idx, field)
+            .await
+            .map_err(|err| ctx_idx.set_error_path(err));
     }
+
+    let resolve_info = ResolveInfo {
+        path_node: ctx_idx.path_node.as_ref().unwrap(),
+        parent_type: &Vec::<T>::type_name(),
+        return_type: &T::qualified_type_name(),
+        name: field.node.name.node.as_str(),
+        alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
+        is_for_introspection: ctx_idx.is_for_introspection,
+    };
+    let resolve_fut = async {
+        OutputType::resolve(&item, ctx_idx, field)
+            .await
+            .map(Option::Some)
+            .map_err(|err| ctx_idx.set_error_path(err))
+    };
+    futures_util::pin_mut!(resolve_fut);
+    extensions
+        .resolve(resolve_info, &mut resolve_fut)
+        .await
+        .map(|value| value.expect("You definitely encountered a bug!"))
 }
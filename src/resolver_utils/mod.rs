@@ -1,12 +1,20 @@
 //! Utilities for implementing
 //! [`OutputType::resolve`](trait.OutputType.html#tymethod.resolve).
 
+mod cache;
 mod container;
+mod derived;
 mod r#enum;
 mod list;
+mod retry;
 mod scalar;
+mod timeout;
 
+pub use cache::*;
 pub use container::*;
+pub use derived::*;
 pub use list::*;
 pub use r#enum::*;
+pub use retry::*;
 pub use scalar::*;
+pub use timeout::*;
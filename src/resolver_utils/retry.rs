@@ -0,0 +1,108 @@
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use crate::{Context, ServerError, ServerResult};
+
+/// The retry policy of a field resolver.
+///
+/// It is used by `#[graphql(retry(times = 3, backoff = "100ms"))]`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// The maximum number of retries after the first attempt.
+    pub times: usize,
+    /// The delay before the first retry, which doubles after each retry.
+    pub backoff: Duration,
+    /// Randomize each delay between half of it and all of it.
+    pub jitter: bool,
+    /// Only retry the errors that this function returns `true` for, all errors
+    /// are retried if it is `None`.
+    pub retry_if: Option<fn(&ServerError) -> bool>,
+}
+
+impl RetryPolicy {
+    fn delay(&self, retry: usize) -> Duration {
+        let delay = self
+            .backoff
+            .saturating_mul(1u32.checked_shl(retry as u32).unwrap_or(u32::MAX));
+        if !self.jitter {
+            return delay;
+        }
+        let half = delay / 2;
+        let random = RandomState::new().build_hasher().finish();
+        half + Duration::from_nanos(random % (half.as_nanos() as u64).saturating_add(1))
+    }
+}
+
+/// Resolves a field with retries.
+///
+/// The future returned by `f` is retried if it fails, until it succeeds, the
+/// number of retries reaches [`RetryPolicy::times`], or the error is rejected
+/// by [`RetryPolicy::retry_if`], and the last error is returned.
+///
+/// The retries are delayed with the [`Timer`](crate::Timer) of the schema, and
+/// are not delayed if there is none.
+///
+/// Only use it for idempotent resolvers.
+pub async fn resolve_with_retry<T, F, Fut>(
+    ctx: &Context<'_>,
+    policy: RetryPolicy,
+    mut f: F,
+) -> ServerResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ServerResult<T>>,
+{
+    let mut retry = 0;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err)
+                if retry < policy.times
+                    && policy
+                        .retry_if
+                        .map(|retry_if| retry_if(&err))
+                        .unwrap_or(true) =>
+            {
+                let delay = policy.delay(retry);
+                if !delay.is_zero() {
+                    if let Some(timer) = &ctx.schema_env.timer {
+                        timer.delay(delay).await;
+                    }
+                }
+                retry += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy {
+            times: 3,
+            backoff: Duration::from_millis(100),
+            jitter: false,
+            retry_if: None,
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+
+        let policy = RetryPolicy {
+            jitter: true,
+            ..policy
+        };
+        for retry in 0..3 {
+            let delay = policy.delay(retry);
+            assert!(delay >= Duration::from_millis(50 << retry));
+            assert!(delay <= Duration::from_millis(100 << retry));
+        }
+    }
+}
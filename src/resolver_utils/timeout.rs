@@ -0,0 +1,39 @@
+use std::{future::Future, time::Duration};
+
+use futures_util::future::{self, Either};
+
+use crate::{Context, OutputType, ServerError, ServerResult};
+
+/// Resolves a field with a timeout.
+///
+/// If the future does not complete in time, an error is added at the path of
+/// the field and `None` is returned so that the field is resolved to `null`,
+/// or the error is returned if the type of the field is non-null. The timeout
+/// is not applied if the schema has no [`Timer`](crate::Timer).
+///
+/// It is used by `#[graphql(timeout = "2s")]`.
+pub async fn resolve_with_timeout<T: OutputType>(
+    ctx: &Context<'_>,
+    timeout: Duration,
+    fut: impl Future<Output = ServerResult<T>>,
+) -> ServerResult<Option<T>> {
+    let timer = match ctx.schema_env.timer.as_deref() {
+        Some(timer) => timer,
+        None => return fut.await.map(Some),
+    };
+    futures_util::pin_mut!(fut);
+    match future::select(fut, timer.delay(timeout)).await {
+        Either::Left((res, _)) => res.map(Some),
+        Either::Right(_) => {
+            let err = ctx.set_error_path(ServerError::new(
+                format!(r#"Field "{}" timed out."#, ctx.item.node.name.node),
+                Some(ctx.item.pos),
+            ));
+            if T::qualified_type_name().ends_with('!') {
+                return Err(err);
+            }
+            ctx.add_error(err);
+            Ok(None)
+        }
+    }
+}
@@ -6,11 +6,14 @@ use http::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{CacheControl, Result, ServerError, Value};
+use crate::{
+    operation_signature::LazySignature, parser::types::OperationType, CacheControl, ExecutionStats,
+    Result, ServerError, Value,
+};
 
 /// Query response
 #[non_exhaustive]
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Response {
     /// Data of query result
     #[serde(default)]
@@ -31,6 +34,78 @@ pub struct Response {
     /// HTTP headers
     #[serde(skip)]
     pub http_headers: HeaderMap,
+
+    /// Metadata of the executed operation, `None` if the request failed
+    /// before an operation was selected
+    #[serde(skip)]
+    pub operation: Option<OperationMetadata>,
+
+    /// Statistics of the execution, `None` unless enabled with
+    /// [`SchemaBuilder::enable_execution_stats`](crate::SchemaBuilder::enable_execution_stats)
+    #[serde(skip)]
+    pub execution_stats: Option<ExecutionStats>,
+}
+
+/// Metadata of the operation executed for a response.
+///
+/// It allows the HTTP integrations and the middlewares to collect
+/// per-operation metrics or route the requests without parsing the request
+/// again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationMetadata {
+    /// The name of the operation, `None` for an anonymous operation.
+    pub name: Option<String>,
+
+    /// The type of the operation.
+    pub ty: OperationType,
+
+    signature: LazySignature,
+}
+
+impl OperationMetadata {
+    /// Create the metadata of an operation.
+    pub fn new(name: Option<String>, ty: OperationType, signature: impl Into<String>) -> Self {
+        Self {
+            name,
+            ty,
+            signature: LazySignature::from_signature(signature.into()),
+        }
+    }
+
+    pub(crate) fn with_lazy_signature(
+        name: Option<String>,
+        ty: OperationType,
+        signature: LazySignature,
+    ) -> Self {
+        Self {
+            name,
+            ty,
+            signature,
+        }
+    }
+
+    /// The normalized signature of the operation.
+    ///
+    /// The signature contains the operation and the fragments it uses, with
+    /// the literals hidden, the aliases removed, the selections sorted and the
+    /// whitespaces reduced, so it is the same for the executions of an
+    /// operation with different arguments. It is computed the first time it
+    /// is read.
+    pub fn signature(&self) -> &str {
+        self.signature.get()
+    }
+}
+
+// The operation metadata is not compared, so the responses that are only
+// different in how they were produced are equal.
+impl PartialEq for Response {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+            && self.extensions == other.extensions
+            && self.cache_control == other.cache_control
+            && self.errors == other.errors
+            && self.http_headers == other.http_headers
+    }
 }
 
 impl Response {
@@ -68,6 +143,15 @@ impl Response {
         }
     }
 
+    /// Set the metadata of the executed operation.
+    #[must_use]
+    pub fn operation(self, operation: OperationMetadata) -> Self {
+        Self {
+            operation: Some(operation),
+            ..self
+        }
+    }
+
     /// Set the cache control of the response.
     #[must_use]
     pub fn cache_control(self, cache_control: CacheControl) -> Self {
@@ -1,30 +1,60 @@
 use std::{
-    any::Any,
+    any::{Any, TypeId},
+    borrow::Cow,
     collections::{HashMap, HashSet},
+    future::Future,
     ops::Deref,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 use async_graphql_parser::types::ExecutableDocument;
-use futures_util::stream::{self, BoxStream, FuturesOrdered, Stream, StreamExt};
+use futures_util::{
+    future::{BoxFuture, Either},
+    stream::{self, BoxStream, FuturesOrdered, Stream, StreamExt},
+};
 
 use crate::{
     context::{Data, QueryEnvInner},
     custom_directive::CustomDirectiveFactory,
-    extensions::{ExtensionFactory, Extensions},
+    execution_stats::ExecutionStatsCollector,
+    export_directive::{ExportDirectiveFactory, ExportedValues},
+    extensions::{
+        create_extensions, CacheStorage, ConditionalExtension, ExtensionFactory, Extensions,
+    },
+    field_cache::{FieldCache, FieldCacheEntry, FieldCacheSpawner},
+    live_query::diff_value,
+    locale::{DescriptionResolver, Locale},
+    meta::SchemaMeta,
+    mutation_transaction::{resolve_mutation_in_transaction, ErasedMutationTransaction},
+    object_extension::{register_object_extension, ObjectExtension, ObjectExtensionResolver},
+    operation_signature::LazySignature,
     parser::{
         parse_query,
-        types::{Directive, DocumentOperations, OperationType, Selection, SelectionSet},
+        types::{
+            Directive, DocumentOperations, OperationDefinition, OperationType, Selection,
+            SelectionSet,
+        },
         Positioned,
     },
-    registry::{Registry, SDLExportOptions},
+    provider::{ProvidedData, Provider, Providers},
+    registry::{
+        __DirectiveLocation, is_visible_outside_request, MetaDirective, MetaInputValue, MetaType,
+        Registry, RegistryStats, SDLExportAudience, SDLExportOptions,
+    },
     resolver_utils::{resolve_container, resolve_container_serial},
+    source_preview::SourcePreview,
     subscription::collect_subscription_streams,
+    timer::{default_timer, Timer},
     types::QueryRoot,
-    validation::{check_rules, ValidationMode},
-    BatchRequest, BatchResponse, CacheControl, ContextBase, EmptyMutation, EmptySubscription,
-    Executor, InputType, ObjectType, OutputType, QueryEnv, Request, Response, ServerError,
-    ServerResult, SubscriptionType, Variables,
+    validation::{check_rules, ValidationMode, ValidationResult},
+    value,
+    visibility::{active_visibility_profile, check_visibility_profile},
+    BatchRequest, BatchResponse, Context, ContextBase, EmptyMutation, EmptySubscription, Error,
+    Executor, GuardDenial, InputType, LiveQueryStore, MutationTransaction, Name, ObjectType,
+    OutputType, QueryEnv, Request, Response, ServerError, ServerResult, SubscriptionType, Value,
+    Variables,
 };
 
 /// Introspection mode
@@ -39,6 +69,27 @@ pub enum IntrospectionMode {
     Disabled,
 }
 
+pub(crate) type RegistryHook = Box<dyn FnOnce(&mut Registry)>;
+
+pub(crate) type VariablesHook =
+    Box<dyn Fn(&mut Variables, &OperationDefinition) -> Result<(), Error> + Send + Sync>;
+
+pub(crate) type EntityResolver = Box<
+    dyn for<'a> Fn(&'a Context<'_>, &'a Value) -> BoxFuture<'a, ServerResult<Option<Value>>>
+        + Send
+        + Sync,
+>;
+
+pub(crate) type UnknownEntityHook = Arc<
+    dyn for<'a> Fn(&'a Context<'_>, &'a Value) -> BoxFuture<'a, Result<Option<Value>, Error>>
+        + Send
+        + Sync,
+>;
+
+pub(crate) type IntrospectionHook = Box<dyn Fn(&Context<'_>, &mut Value) + Send + Sync>;
+
+pub(crate) type GuardDenialHook = Arc<dyn Fn(&Context<'_>, &GuardDenial) -> Error + Send + Sync>;
+
 /// Schema builder
 pub struct SchemaBuilder<Query, Mutation, Subscription> {
     validation_mode: ValidationMode,
@@ -49,9 +100,34 @@ pub struct SchemaBuilder<Query, Mutation, Subscription> {
     data: Data,
     complexity: Option<usize>,
     depth: Option<usize>,
+    skip_introspection_limits: bool,
     recursive_depth: usize,
+    input_depth: Option<usize>,
+    input_nodes: Option<usize>,
+    max_errors: Option<usize>,
+    max_response_size: Option<usize>,
+    disable_anonymous_operations: bool,
+    unique_batch_operation_names: bool,
+    export_directive: bool,
     extensions: Vec<Box<dyn ExtensionFactory>>,
     custom_directives: HashMap<String, Box<dyn CustomDirectiveFactory>>,
+    registry_hooks: Vec<RegistryHook>,
+    variables_hooks: Vec<VariablesHook>,
+    entity_resolvers: Vec<EntityResolver>,
+    unknown_entity_hook: Option<UnknownEntityHook>,
+    guard_denial_hook: Option<GuardDenialHook>,
+    introspection_hooks: Vec<IntrospectionHook>,
+    description_resolver: Option<DescriptionResolver>,
+    timer: Option<Arc<dyn Timer>>,
+    live_query_store: Option<Arc<dyn LiveQueryStore>>,
+    field_cache: Option<FieldCache>,
+    field_cache_spawner: Option<FieldCacheSpawner>,
+    object_extensions: HashMap<String, HashMap<String, ObjectExtensionResolver>>,
+    providers: Providers,
+    mutation_transaction: Option<Arc<dyn ErasedMutationTransaction>>,
+    execution_stats: bool,
+    #[cfg(feature = "dynamic-schema")]
+    dynamic_fields: Vec<(String, crate::dynamic::Field)>,
 }
 
 impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription> {
@@ -75,6 +151,80 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Register an object whose `#[graphql(entity)]` resolvers are used to
+    /// resolve federation entity references, in addition to the ones declared
+    /// on the query root.
+    ///
+    /// This allows each module to own the lookup of its entities instead of
+    /// declaring all of them in the query root. The object can be any object
+    /// type, including the entity type itself. Resolvers on the query root
+    /// take precedence, then registered objects are tried in order.
+    ///
+    /// ```
+    /// use async_graphql::*;
+    ///
+    /// #[derive(SimpleObject)]
+    /// struct User {
+    ///     id: ID,
+    /// }
+    ///
+    /// struct UserEntities;
+    ///
+    /// #[Object]
+    /// impl UserEntities {
+    ///     #[graphql(entity)]
+    ///     async fn find_user_by_id(&self, id: ID) -> User {
+    ///         User { id }
+    ///     }
+    /// }
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .entity_resolvers(UserEntities)
+    ///     .finish();
+    /// let query = r#"{
+    ///     _entities(representations: [{__typename: "User", id: "1"}]) {
+    ///         ... on User { id }
+    ///     }
+    /// }"#;
+    /// assert_eq!(
+    ///     schema.execute(query).await.into_result().unwrap().data,
+    ///     value!({ "_entities": [{ "id": "1" }] })
+    /// );
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn entity_resolvers<T: ObjectType + 'static>(mut self, resolvers: T) -> Self {
+        let type_name = T::type_name();
+        let exists = self.registry.types.contains_key(&*type_name);
+        T::create_type_info(&mut self.registry);
+        if !exists
+            && matches!(
+                self.registry.types.get(&*type_name),
+                Some(MetaType::Object { keys: None, .. })
+            )
+        {
+            // The object only provides entity resolvers, it is not part of the schema.
+            self.registry.types.remove(&*type_name);
+        }
+
+        let resolvers = Arc::new(resolvers);
+        self.entity_resolvers.push(Box::new(move |ctx, params| {
+            let resolvers = resolvers.clone();
+            Box::pin(async move { resolvers.find_entity(ctx, params).await })
+        }));
+        self
+    }
+
     /// Disable introspection queries.
     #[must_use]
     pub fn disable_introspection(mut self) -> Self {
@@ -105,6 +255,15 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Do not apply the depth and complexity limits to operations that only
+    /// select introspection fields (`__schema`, `__type` and `__typename`),
+    /// such as the queries sent by GraphiQL.
+    #[must_use]
+    pub fn skip_introspection_limits(mut self) -> Self {
+        self.skip_introspection_limits = true;
+        self
+    }
+
     /// Set the maximum recursive depth a query can have. (default: 32)
     ///
     /// If the value is too large, stack overflow may occur, usually `32` is
@@ -115,6 +274,84 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Set the maximum nesting depth of the input values in the variables of a
+    /// request. By default, there is no limit.
+    ///
+    /// Each list or input object counts as one level, so `{ a: [1] }` has a
+    /// depth of `2`. This protects recursive input objects, such as filter
+    /// trees, from overflowing the stack during coercion.
+    #[must_use]
+    pub fn limit_input_depth(mut self, depth: usize) -> Self {
+        self.input_depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum total number of input values in the variables of a
+    /// request. By default, there is no limit.
+    ///
+    /// Every scalar, list and input object counts as one node.
+    #[must_use]
+    pub fn limit_input_nodes(mut self, nodes: usize) -> Self {
+        self.input_nodes = Some(nodes);
+        self
+    }
+
+    /// Set the maximum number of errors of a request. By default, there is
+    /// no limit.
+    ///
+    /// The validation is aborted once the limit is exceeded, and the execution
+    /// stops resolving the fields, so that the documents that trigger a lot of
+    /// errors do not consume a lot of resources. The fields that are no longer
+    /// resolved fail like the fields returning an error, and only the first
+    /// errors are kept, followed by an error reporting that the limit is
+    /// exceeded.
+    #[must_use]
+    pub fn limit_errors(mut self, errors: usize) -> Self {
+        self.max_errors = Some(errors);
+        self
+    }
+
+    /// Set the maximum approximate size, in bytes, of the response of a
+    /// request. By default, there is no limit.
+    ///
+    /// The size of the values is accounted as the fields and the items of the
+    /// lists are resolved, and the execution is aborted with an error once the
+    /// limit is exceeded, so a query that accidentally selects a huge amount
+    /// of data does not materialize it all in memory. The size is approximated
+    /// as the length of the JSON serialization of the response.
+    ///
+    /// Subscriptions are not limited, since their events are resolved with
+    /// the same environment and their sizes would accumulate.
+    #[must_use]
+    pub fn limit_response_size(mut self, size: usize) -> Self {
+        self.max_response_size = Some(size);
+        self
+    }
+
+    /// Reject the operations that have no name, such as `{ value }` or
+    /// `query { value }`.
+    ///
+    /// Named operations make the logs and metrics of the server easier to
+    /// follow, so they are often required by the policies of the APIs.
+    #[must_use]
+    pub fn disable_anonymous_operations(mut self) -> Self {
+        self.disable_anonymous_operations = true;
+        self
+    }
+
+    /// Require that the requests of a batch have operation names that are
+    /// unique in the batch.
+    ///
+    /// The operation name of a request is the one it selects, or the name of
+    /// the only operation of its document. The requests whose operation is
+    /// anonymous or whose name is used by another request of the batch are
+    /// not executed, and an error is returned for them instead.
+    #[must_use]
+    pub fn require_unique_batch_operation_names(mut self) -> Self {
+        self.unique_batch_operation_names = true;
+        self
+    }
+
     /// Add an extension to the schema.
     ///
     /// # Examples
@@ -141,6 +378,51 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Add an extension to the schema, which is only enabled for the requests
+    /// matching `predicate`.
+    ///
+    /// This allows debugging extensions to only run for authorized requests,
+    /// for example when the integration stored a debug header in the data of
+    /// the request. An extension can also be disabled by a request with
+    /// [`Request::disable_extension`](crate::Request::disable_extension).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         100
+    ///     }
+    /// }
+    ///
+    /// struct DebugHeader;
+    ///
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .extension_if(extensions::Analyzer, |request| {
+    ///         request
+    ///             .data
+    ///             .contains_key(&std::any::TypeId::of::<DebugHeader>())
+    ///     })
+    ///     .finish();
+    /// ```
+    #[must_use]
+    pub fn extension_if(
+        mut self,
+        extension: impl ExtensionFactory,
+        predicate: impl Fn(&Request) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.extensions.push(Box::new(ConditionalExtension {
+            extension,
+            predicate,
+        }));
+        self
+    }
+
     /// Add a global data that can be accessed in the `Schema`. You access it
     /// with `Context::data`.
     #[must_use]
@@ -149,6 +431,53 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Add a provider that creates a data of the context lazily, the first
+    /// time a resolver asks for it with `Context::provide`, see
+    /// [`Provider`](crate::Provider).
+    #[must_use]
+    pub fn provider<P: Provider>(mut self, provider: P) -> Self {
+        self.providers
+            .insert(TypeId::of::<P::Output>(), Arc::new(provider));
+        self
+    }
+
+    /// Execute each mutation operation in a transaction, see
+    /// [`MutationTransaction`](crate::MutationTransaction).
+    #[must_use]
+    pub fn mutation_transaction(mut self, transaction: impl MutationTransaction) -> Self {
+        self.mutation_transaction = Some(Arc::new(transaction));
+        self
+    }
+
+    /// Collect the statistics of the execution of each request, and attach
+    /// them to [`Response::execution_stats`](crate::Response::execution_stats).
+    ///
+    /// ```
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .enable_execution_stats()
+    ///     .finish();
+    /// let resp = schema.execute("{ a: value b: value }").await;
+    /// assert_eq!(resp.execution_stats.unwrap().resolver_count, 2);
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn enable_execution_stats(mut self) -> Self {
+        self.execution_stats = true;
+        self
+    }
+
     /// Set the validation mode, default is `ValidationMode::Strict`.
     #[must_use]
     pub fn validation_mode(mut self, validation_mode: ValidationMode) -> Self {
@@ -220,63 +549,1092 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
-    /// Consumes this builder and returns a schema.
-    pub fn finish(mut self) -> Schema<Query, Mutation, Subscription> {
-        // federation
-        if self.registry.enable_federation || self.registry.has_entities() {
-            self.registry.create_federation_types();
-        }
+    /// Declare the visibility profiles, the first profile is active for the
+    /// requests that do not select a
+    /// [`VisibilityProfile`](crate::VisibilityProfile).
+    ///
+    /// The types and fields tagged with `#[graphql(visible("profile"))]` are
+    /// only visible to the specified profiles, and [`SchemaBuilder::finish`]
+    /// panics if they use a profile that is not declared.
+    #[must_use]
+    pub fn visibility_profiles<I, T>(mut self, profiles: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.registry.visibility_profiles = profiles.into_iter().map(Into::into).collect();
+        self
+    }
 
-        Schema(Arc::new(SchemaInner {
-            validation_mode: self.validation_mode,
-            query: self.query,
-            mutation: self.mutation,
-            subscription: self.subscription,
-            complexity: self.complexity,
-            depth: self.depth,
-            recursive_depth: self.recursive_depth,
-            extensions: self.extensions,
-            env: SchemaEnv(Arc::new(SchemaEnvInner {
-                registry: self.registry,
-                data: self.data,
-                custom_directives: self.custom_directives,
-            })),
-        }))
+    /// Enable the experimental client-controlled nullability operators.
+    ///
+    /// A field selected with `!` is treated as non-null, so a null value or an
+    /// error is propagated to the parent field, and a field selected with `?`
+    /// is treated as nullable, so an error is caught and the field is set to
+    /// null. Without this, queries using these operators fail the validation.
+    ///
+    /// Reference: <https://github.com/graphql/graphql-wg/blob/main/rfcs/ClientControlledNullability.md>
+    #[must_use]
+    pub fn enable_experimental_ccn(mut self) -> Self {
+        self.registry.enable_experimental_ccn = true;
+        self
     }
-}
 
-#[doc(hidden)]
-pub struct SchemaEnvInner {
-    pub registry: Registry,
-    pub data: Data,
-    pub custom_directives: HashMap<String, Box<dyn CustomDirectiveFactory>>,
-}
+    /// Declare the Relay `@connection(key: String!, filter: [String]) on FIELD`
+    /// client directive.
+    ///
+    /// Without this, queries written for the Relay client store fail the
+    /// validation because the directive is unknown. The directive has no effect
+    /// on the execution, but its arguments can be read with
+    /// [`Lookahead::relay_connection`](crate::Lookahead::relay_connection), for
+    /// example to cache the connection pages by key.
+    ///
+    /// ```
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn friends(&self, ctx: &Context<'_>) -> Result<Vec<i32>> {
+    ///         let connection = ctx.look_ahead().relay_connection()?;
+    ///         assert_eq!(connection.unwrap().key, "Friends_friends");
+    ///         Ok(vec![1, 2, 3])
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .enable_relay_connection_directive()
+    ///     .finish();
+    /// let resp = schema
+    ///     .execute(r#"{ friends @connection(key: "Friends_friends") }"#)
+    ///     .await;
+    /// assert_eq!(resp.data, value!({ "friends": [1, 2, 3] }));
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn enable_relay_connection_directive(mut self) -> Self {
+        let input_value = |name: &str, description: &str, ty: &str| MetaInputValue {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            ty: ty.to_string(),
+            default_value: None,
+            visible: None,
+            inaccessible: false,
+            tags: Default::default(),
+            is_secret: false,
+        };
+        self.registry.add_directive(MetaDirective {
+            name: "connection".into(),
+            description: Some(
+                "Identifies a connection field in the Relay client store.".to_string(),
+            ),
+            locations: vec![__DirectiveLocation::FIELD],
+            args: [
+                input_value("key", "The key of the connection in the store.", "String!"),
+                input_value(
+                    "filter",
+                    "The arguments the connection is identified by.",
+                    "[String]",
+                ),
+            ]
+            .into_iter()
+            .map(|arg| (arg.name.clone(), arg))
+            .collect(),
+            is_repeatable: false,
+            visible: None,
+            composable: None,
+        });
+        self
+    }
+
+    /// Enable the `@export(as: "name")` directive for the batch requests.
+    ///
+    /// The value of a field marked with `@export` is passed as the variable
+    /// `name` to the following requests of the batch, so that an operation
+    /// can use the result of a previous one without another round-trip. The
+    /// value of a field inside of a list is exported as the list of the
+    /// values. The exported variables replace the variables of the requests
+    /// with the same name.
+    ///
+    /// The requests of a batch are executed sequentially when the directive
+    /// is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn user_id(&self) -> i32 {
+    ///         10
+    ///     }
+    ///
+    ///     async fn user_name(&self, id: i32) -> String {
+    ///         format!("user{}", id)
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .enable_export_directive()
+    ///     .finish();
+    /// let resp = schema
+    ///     .execute_batch(BatchRequest::Batch(vec![
+    ///         Request::new(r#"{ userId @export(as: "id") }"#),
+    ///         Request::new("query($id: Int!) { userName(id: $id) }"),
+    ///     ]))
+    ///     .await;
+    /// if let BatchResponse::Batch(responses) = resp {
+    ///     assert_eq!(responses[1].data, value!({ "userName": "user10" }));
+    /// }
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn enable_export_directive(mut self) -> Self {
+        self.export_directive = true;
+        self.directive(ExportDirectiveFactory)
+    }
+
+    /// Enable the experimental `@live` directive with the specified live
+    /// query store.
+    ///
+    /// A query marked with `@live` and executed with
+    /// [`Schema::execute_stream`], for example over a WebSocket, is executed
+    /// again each time one of its invalidation tokens fires in the store. The
+    /// first response contains the whole data, and the following responses
+    /// only contain a JSON patch (RFC 6902) to apply to the previous data, in
+    /// the `live` extension: `{ "revision": 1, "patch": [...] }`.
+    ///
+    /// See [`MemoryLiveQueryStore`](crate::MemoryLiveQueryStore) for an
+    /// example.
+    #[must_use]
+    pub fn live_query_store(mut self, store: impl LiveQueryStore) -> Self {
+        self.live_query_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Set the storage of the fields cached with
+    /// `#[graphql(cache(ttl = "30s"))]`.
+    ///
+    /// The fields are resolved without cache if no storage is set. See
+    /// [`MemoryFieldCacheStorage`](crate::MemoryFieldCacheStorage) for an
+    /// example.
+    #[must_use]
+    pub fn field_cache_storage(mut self, storage: impl CacheStorage<FieldCacheEntry>) -> Self {
+        self.field_cache = Some(FieldCache::new(storage));
+        self
+    }
+
+    /// Set the spawner, such as `tokio::spawn`, used to revalidate the stale
+    /// values of the cached fields in the background.
+    ///
+    /// Without a spawner, the request that finds a stale value refreshes it
+    /// before responding.
+    #[must_use]
+    pub fn field_cache_spawner<S, R>(mut self, spawner: S) -> Self
+    where
+        S: Fn(BoxFuture<'static, ()>) -> R + Send + Sync + 'static,
+    {
+        self.field_cache_spawner = Some(Arc::new(move |fut| {
+            spawner(fut);
+        }));
+        self
+    }
+
+    /// Set the description of the schema.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.registry.description = Some(description.into());
+        self
+    }
+
+    /// Add a hook that can modify the registry before the schema is
+    /// finished.
+    ///
+    /// Hooks are called in the order they are added, after all types have
+    /// been registered. They can be used to inject descriptions from an
+    /// external source or to strip internal fields.
+    ///
+    /// NOTE: The resolvers are not changed, so renaming a type or field that
+    /// is still resolved by a Rust type will break the execution.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         100
+    ///     }
+    /// }
+    ///
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .on_registry(|registry| {
+    ///         if let Some(registry::MetaType::Object { description, .. }) =
+    ///             registry.types.get_mut("Query")
+    ///         {
+    ///             *description = Some("The query root".to_string());
+    ///         }
+    ///     })
+    ///     .finish();
+    /// assert_eq!(
+    ///     schema.meta().query_type().description(),
+    ///     Some("The query root")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn on_registry(mut self, f: impl FnOnce(&mut Registry) + 'static) -> Self {
+        self.registry_hooks.push(Box::new(f));
+        self
+    }
+
+    /// Add a hook that can rewrite or reject the variables of a request.
+    ///
+    /// Hooks are called in the order they are added, after the operation to
+    /// execute has been selected and validated, and before it is executed.
+    /// Returning an error rejects the request. The rewritten variables are not
+    /// validated again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn items(&self, limit: i32) -> i32 {
+    ///         limit
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .on_variables(|variables, _operation| {
+    ///         if let Some(Value::Number(limit)) = variables.get_mut(&Name::new("limit")) {
+    ///             if limit.as_i64().unwrap_or_default() > 10 {
+    ///                 *limit = 10.into();
+    ///             }
+    ///         }
+    ///         Ok(())
+    ///     })
+    ///     .finish();
+    /// let request = Request::new("query($limit: Int!) { items(limit: $limit) }")
+    ///     .variables(Variables::from_json(serde_json::json!({ "limit": 100 })));
+    /// assert_eq!(
+    ///     schema.execute(request).await.into_result().unwrap().data,
+    ///     value!({ "items": 10 })
+    /// );
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn on_variables(
+        mut self,
+        f: impl Fn(&mut Variables, &OperationDefinition) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        self.variables_hooks.push(Box::new(f));
+        self
+    }
+
+    /// Add a hook that can post-process the result of the `__schema` and
+    /// `__type` introspection fields of a request.
+    ///
+    /// The hook is called for each field inside of the introspection result,
+    /// after its subfields, with the context of the field and its resolved
+    /// value, once the invisible types and fields have been removed. The name
+    /// of the field is `ctx.field().name()`, whatever its alias in the
+    /// request, which allows rewriting the descriptions for localization or
+    /// stripping the deprecation reasons for external consumers. Hooks are
+    /// called in the order they are added.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     /// The value
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .on_introspection(|ctx, value| {
+    ///         if let (Value::String(description), "description") = (value, ctx.field().name()) {
+    ///             *description = description.replace("The value", "La valeur");
+    ///         }
+    ///     })
+    ///     .finish();
+    /// let query = r#"{ __type(name: "Query") { fields { text: description } } }"#;
+    /// assert_eq!(
+    ///     schema.execute(query).await.into_result().unwrap().data,
+    ///     value!({ "__type": { "fields": [{ "text": "La valeur" }] } })
+    /// );
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn on_introspection(
+        mut self,
+        f: impl Fn(&Context<'_>, &mut Value) + Send + Sync + 'static,
+    ) -> Self {
+        self.introspection_hooks.push(Box::new(f));
+        self
+    }
+
+    /// Set the resolver of the descriptions, which are used as message keys
+    /// resolved for the locale of the request.
+    ///
+    /// The resolver is called with the description and the name of the
+    /// [`Locale`] of the request, the session or the schema, for the
+    /// descriptions returned by the introspection and exported to the SDL
+    /// with [`SDLExportAudience::locale`]. The descriptions are not resolved if
+    /// there is no locale. The descriptions which are not message keys, such
+    /// as the ones of the built-in types, should be returned unchanged.
+    ///
+    /// See [`Locale`] for an example.
+    #[must_use]
+    pub fn description_resolver(
+        mut self,
+        f: impl Fn(&str, &str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.description_resolver = Some(Arc::new(f));
+        self
+    }
+
+    /// Set the timer used for the timeouts of the requests and the fields, and
+    /// the delays of the retries.
+    ///
+    /// By default, it is [`FuturesTimer`](crate::FuturesTimer) if the
+    /// `futures-timer` feature is enabled. Without a timer, the timeouts are
+    /// not applied and the retries are not delayed.
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .timer(tokio::time::sleep)
+    ///     .finish();
+    /// ```
+    #[must_use]
+    pub fn timer(mut self, timer: impl Timer) -> Self {
+        self.timer = Some(Arc::new(timer));
+        self
+    }
+
+    /// Set a hook called when no entity resolver resolves a representation
+    /// of the `_entities` field, such as when its `__typename` is unknown.
+    ///
+    /// It allows to log the stale representations sent by the router during
+    /// a migration, or to deny or resolve them, possibly asynchronously:
+    ///
+    /// - `Ok(Some(representation))` resolves the returned representation
+    ///   instead.
+    /// - `Ok(None)` returns a null entity without error.
+    /// - `Err(err)` returns a null entity with the error.
+    ///
+    /// Without a hook, the entity is null with an `Entity not found.` error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// #[derive(SimpleObject)]
+    /// struct User {
+    ///     id: ID,
+    /// }
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     #[graphql(entity)]
+    ///     async fn find_user_by_id(&self, id: ID) -> User {
+    ///         User { id }
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .on_unknown_entity(|_ctx, representation| {
+    ///         Box::pin(async move {
+    ///             // `Account` was renamed to `User`.
+    ///             let mut representation = match representation {
+    ///                 Value::Object(representation) => representation.clone(),
+    ///                 _ => return Ok(None),
+    ///             };
+    ///             if representation.get("__typename") == Some(&value!("Account")) {
+    ///                 representation.insert(Name::new("__typename"), value!("User"));
+    ///                 return Ok(Some(Value::Object(representation)));
+    ///             }
+    ///             Err("Unknown entity".into())
+    ///         })
+    ///     })
+    ///     .finish();
+    /// let query = r#"{
+    ///     _entities(representations: [{__typename: "Account", id: "1"}]) {
+    ///         ... on User { id }
+    ///     }
+    /// }"#;
+    /// assert_eq!(
+    ///     schema.execute(query).await.into_result().unwrap().data,
+    ///     value!({ "_entities": [{ "id": "1" }] })
+    /// );
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn on_unknown_entity<F>(mut self, f: F) -> Self
+    where
+        F: for<'a> Fn(&'a Context<'_>, &'a Value) -> BoxFuture<'a, Result<Option<Value>, Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.unknown_entity_hook = Some(Arc::new(f));
+        self
+    }
+
+    /// Set a hook rendering the [`GuardDenial`]s returned by the guards into
+    /// the errors of the fields, instead of the default rendering which puts
+    /// the `code` and the `requiredPermission` of the denial in the extensions
+    /// of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     #[graphql(guard = "|_: &Context<'_>| -> Result<()> {
+    ///         Err(GuardDenial::new(\"denied\").required_permission(\"admin\").into())
+    ///     }")]
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .on_guard_denial(|_ctx, denial| {
+    ///         Error::new(format!(
+    ///             "Missing permission: {}",
+    ///             denial.required_permission.as_deref().unwrap_or_default()
+    ///         ))
+    ///     })
+    ///     .finish();
+    /// let errors = schema.execute("{ value }").await.into_result().unwrap_err();
+    /// assert_eq!(errors[0].message, "Missing permission: admin");
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn on_guard_denial(
+        mut self,
+        f: impl Fn(&Context<'_>, &GuardDenial) -> Error + Send + Sync + 'static,
+    ) -> Self {
+        self.guard_denial_hook = Some(Arc::new(f));
+        self
+    }
+
+    /// Add the fields of an [`ObjectExtension`] to its target object, like
+    /// `extend type` in the SDL.
+    ///
+    /// See [`ExtendObject`](derive.ExtendObject.html) for an example.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target is not an object, or if a field of the extension
+    /// already exists in the target.
+    #[must_use]
+    pub fn extend_object<E: ObjectExtension>(mut self) -> Self {
+        register_object_extension::<E>(&mut self.registry, &mut self.object_extensions);
+        self
+    }
+
+    /// Add a field to a code-first object type with the
+    /// [`dynamic::Field`](crate::dynamic::Field) builder.
+    ///
+    /// This allows extending the schema at startup, for example from plugins.
+    /// Dynamic fields can only return scalars and enums, and the
+    /// `parent_value` of their resolver context is always `FieldValue::NULL`.
+    ///
+    /// # Panics
+    ///
+    /// Calling [`finish`](Self::finish) panics if `type_name` is not an
+    /// object, if the object already has a field with the same name, or if
+    /// the field does not return a scalar or an enum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::{dynamic, *};
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         100
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .dynamic_field(
+    ///         "Query",
+    ///         dynamic::Field::new(
+    ///             "pluginVersion",
+    ///             dynamic::TypeRef::named_nn(dynamic::TypeRef::STRING),
+    ///             |_| dynamic::FieldFuture::new(async { Ok(Some(Value::from("1.0"))) }),
+    ///         ),
+    ///     )
+    ///     .finish();
+    /// assert_eq!(
+    ///     schema.execute("{ value pluginVersion }").await.into_result().unwrap().data,
+    ///     value!({ "value": 100, "pluginVersion": "1.0" })
+    /// );
+    /// # });
+    /// ```
+    #[cfg(feature = "dynamic-schema")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dynamic-schema")))]
+    #[must_use]
+    pub fn dynamic_field(
+        mut self,
+        type_name: impl Into<String>,
+        field: crate::dynamic::Field,
+    ) -> Self {
+        self.dynamic_fields.push((type_name.into(), field));
+        self
+    }
+
+    /// Consumes this builder and returns a schema, or an error if a field
+    /// added with [`SchemaBuilder::dynamic_field`] is invalid: if its type is
+    /// not an object of the schema, if it conflicts with another field, or if
+    /// it does not return a scalar or an enum of the schema.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`SchemaBuilder::finish`] for the other errors.
+    #[cfg(feature = "dynamic-schema")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dynamic-schema")))]
+    pub fn try_finish(
+        self,
+    ) -> Result<Schema<Query, Mutation, Subscription>, crate::dynamic::SchemaError> {
+        check_dynamic_fields(&self.registry, &self.dynamic_fields)?;
+        Ok(self.finish())
+    }
+
+    /// Consumes this builder and returns a schema.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a visibility profile is used but not declared, if the
+    /// interfaces implemented by a type don't list it as a possible type (or
+    /// vice versa), if a possible type of an interface or union is not
+    /// registered, or if federation is enabled or an entity is defined, and a
+    /// `@key`, `@requires` or `@provides` field set references a field that
+    /// does not exist. Also panics if a dynamic field is invalid, see
+    /// [`SchemaBuilder::try_finish`] to get an error instead.
+    pub fn finish(mut self) -> Schema<Query, Mutation, Subscription> {
+        // federation
+        let is_federation = self.registry.enable_federation || self.registry.has_entities();
+        if is_federation {
+            self.registry.create_federation_types();
+        }
+
+        #[cfg(feature = "dynamic-schema")]
+        let dynamic_fields = match check_dynamic_fields(&self.registry, &self.dynamic_fields) {
+            Ok(()) => register_dynamic_fields(&mut self.registry, self.dynamic_fields),
+            Err(err) => panic!("{}", err),
+        };
+
+        if self.live_query_store.is_some() {
+            self.registry.add_directive(MetaDirective {
+                name: "live".into(),
+                description: Some(
+                    "Directs the executor to send the updates of the query result when its data changes."
+                        .to_string(),
+                ),
+                locations: vec![__DirectiveLocation::QUERY],
+                args: Default::default(),
+                is_repeatable: false,
+                visible: None,
+                composable: None,
+            });
+        }
+
+        for hook in self.registry_hooks {
+            hook(&mut self.registry);
+        }
+
+        if let Some(profile) = self.registry.find_undeclared_visibility_profile() {
+            panic!("Visibility profile \"{}\" is not declared", profile);
+        }
+        if let Err(err) = self.registry.check_possible_types() {
+            panic!("{}", err);
+        }
+        if is_federation {
+            if let Err(err) = self.registry.check_federation_field_sets() {
+                panic!("{}", err);
+            }
+        }
+        self.registry.shrink_to_fit();
+
+        Schema(Arc::new(SchemaInner {
+            validation_mode: self.validation_mode,
+            query: Arc::new(self.query),
+            mutation: Arc::new(self.mutation),
+            subscription: Arc::new(self.subscription),
+            complexity: self.complexity,
+            depth: self.depth,
+            skip_introspection_limits: self.skip_introspection_limits,
+            recursive_depth: self.recursive_depth,
+            input_depth: self.input_depth,
+            input_nodes: self.input_nodes,
+            max_errors: self.max_errors,
+            max_response_size: self.max_response_size,
+            disable_anonymous_operations: self.disable_anonymous_operations,
+            unique_batch_operation_names: self.unique_batch_operation_names,
+            export_directive: self.export_directive,
+            variables_hooks: Arc::new(self.variables_hooks),
+            extensions: Arc::new(self.extensions),
+            live_query_store: self.live_query_store,
+            mutation_transaction: self.mutation_transaction,
+            field_cache_spawner: self.field_cache_spawner.clone(),
+            env: SchemaEnv(Arc::new(SchemaEnvInner {
+                registry: Arc::new(self.registry),
+                data: self.data,
+                custom_directives: Arc::new(self.custom_directives),
+                entity_resolvers: Arc::new(self.entity_resolvers),
+                unknown_entity_hook: self.unknown_entity_hook,
+                guard_denial_hook: self.guard_denial_hook,
+                introspection_hooks: Arc::new(self.introspection_hooks),
+                description_resolver: self.description_resolver,
+                timer: self.timer,
+                field_cache: self
+                    .field_cache
+                    .map(|cache| cache.with_spawner(self.field_cache_spawner.clone())),
+                object_extensions: Arc::new(self.object_extensions),
+                providers: Arc::new(self.providers),
+                execution_stats: self.execution_stats,
+                #[cfg(feature = "dynamic-schema")]
+                dynamic_fields: Arc::new(dynamic_fields),
+                base: None,
+            })),
+        }))
+    }
+}
+
+/// Schema variant builder, see [`Schema::variant`].
+pub struct SchemaVariantBuilder<Query, Mutation, Subscription> {
+    schema: Schema<Query, Mutation, Subscription>,
+    validation_mode: ValidationMode,
+    data: Data,
+    complexity: Option<usize>,
+    depth: Option<usize>,
+    recursive_depth: usize,
+    input_depth: Option<usize>,
+    input_nodes: Option<usize>,
+    max_errors: Option<usize>,
+    max_response_size: Option<usize>,
+    field_cache: Option<FieldCache>,
+}
+
+impl<Query, Mutation, Subscription> SchemaVariantBuilder<Query, Mutation, Subscription> {
+    /// Add a global data that can be accessed in the variant, it takes
+    /// precedence over the data of the original schema.
+    ///
+    /// Add a [`VisibilityProfile`](crate::VisibilityProfile) to select the
+    /// elements of the schema that are visible in the variant.
+    #[must_use]
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.data.insert(data);
+        self
+    }
+
+    /// Set the maximum complexity a query can have.
+    #[must_use]
+    pub fn limit_complexity(mut self, complexity: usize) -> Self {
+        self.complexity = Some(complexity);
+        self
+    }
+
+    /// Set the maximum depth a query can have.
+    #[must_use]
+    pub fn limit_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum recursive depth a query can have.
+    #[must_use]
+    pub fn limit_recursive_depth(mut self, depth: usize) -> Self {
+        self.recursive_depth = depth;
+        self
+    }
+
+    /// Set the maximum depth of the input values of a request.
+    #[must_use]
+    pub fn limit_input_depth(mut self, depth: usize) -> Self {
+        self.input_depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum number of nodes of the input values of a request.
+    #[must_use]
+    pub fn limit_input_nodes(mut self, nodes: usize) -> Self {
+        self.input_nodes = Some(nodes);
+        self
+    }
+
+    /// Set the maximum number of errors of a request.
+    #[must_use]
+    pub fn limit_errors(mut self, errors: usize) -> Self {
+        self.max_errors = Some(errors);
+        self
+    }
+
+    /// Set the maximum approximate size of the response of a request.
+    /// Subscriptions are not limited.
+    #[must_use]
+    pub fn limit_response_size(mut self, size: usize) -> Self {
+        self.max_response_size = Some(size);
+        self
+    }
+
+    /// Set the validation mode.
+    #[must_use]
+    pub fn validation_mode(mut self, validation_mode: ValidationMode) -> Self {
+        self.validation_mode = validation_mode;
+        self
+    }
+
+    /// Set the storage of the fields cached with `#[graphql(cache(...))]`.
+    ///
+    /// The storage of the original schema is not shared with its variants,
+    /// because the cached values can depend on their data, so the fields are
+    /// not cached in a variant without a storage.
+    #[must_use]
+    pub fn field_cache_storage(mut self, storage: impl CacheStorage<FieldCacheEntry>) -> Self {
+        self.field_cache = Some(FieldCache::new(storage));
+        self
+    }
+
+    /// Consumes this builder and returns the variant.
+    pub fn finish(self) -> Schema<Query, Mutation, Subscription> {
+        let inner = &self.schema.0;
+        let env = &inner.env;
+        Schema(Arc::new(SchemaInner {
+            validation_mode: self.validation_mode,
+            query: inner.query.clone(),
+            mutation: inner.mutation.clone(),
+            subscription: inner.subscription.clone(),
+            complexity: self.complexity,
+            depth: self.depth,
+            skip_introspection_limits: inner.skip_introspection_limits,
+            recursive_depth: self.recursive_depth,
+            input_depth: self.input_depth,
+            input_nodes: self.input_nodes,
+            max_errors: self.max_errors,
+            max_response_size: self.max_response_size,
+            disable_anonymous_operations: inner.disable_anonymous_operations,
+            unique_batch_operation_names: inner.unique_batch_operation_names,
+            export_directive: inner.export_directive,
+            variables_hooks: inner.variables_hooks.clone(),
+            extensions: inner.extensions.clone(),
+            live_query_store: inner.live_query_store.clone(),
+            mutation_transaction: inner.mutation_transaction.clone(),
+            field_cache_spawner: inner.field_cache_spawner.clone(),
+            env: SchemaEnv(Arc::new(SchemaEnvInner {
+                registry: env.registry.clone(),
+                data: self.data,
+                custom_directives: env.custom_directives.clone(),
+                entity_resolvers: env.entity_resolvers.clone(),
+                unknown_entity_hook: env.unknown_entity_hook.clone(),
+                guard_denial_hook: env.guard_denial_hook.clone(),
+                introspection_hooks: env.introspection_hooks.clone(),
+                description_resolver: env.description_resolver.clone(),
+                timer: env.timer.clone(),
+                field_cache: self
+                    .field_cache
+                    .map(|cache| cache.with_spawner(inner.field_cache_spawner.clone())),
+                object_extensions: env.object_extensions.clone(),
+                providers: env.providers.clone(),
+                execution_stats: env.execution_stats,
+                #[cfg(feature = "dynamic-schema")]
+                dynamic_fields: env.dynamic_fields.clone(),
+                base: Some(env.clone()),
+            })),
+        }))
+    }
+}
+
+#[cfg(feature = "dynamic-schema")]
+fn check_dynamic_fields(
+    registry: &Registry,
+    fields: &[(String, crate::dynamic::Field)],
+) -> Result<(), crate::dynamic::SchemaError> {
+    let mut names = HashSet::new();
+
+    for (type_name, field) in fields {
+        if !matches!(
+            registry.types.get(field.ty.type_name()),
+            Some(MetaType::Scalar { .. } | MetaType::Enum { .. })
+        ) {
+            return Err(format!(
+                "Dynamic field \"{}.{}\" must return a scalar or an enum",
+                type_name, field.name
+            )
+            .into());
+        }
+
+        match registry.types.get(type_name) {
+            Some(MetaType::Object {
+                fields,
+                is_subscription: false,
+                ..
+            }) => {
+                if fields.contains_key(&field.name) || !names.insert((type_name, &field.name)) {
+                    return Err(
+                        format!("Field \"{}.{}\" already exists", type_name, field.name).into(),
+                    );
+                }
+            }
+            _ => return Err(format!("Type \"{}\" is not an object", type_name).into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds the dynamic fields to their types, which are checked by
+/// [`check_dynamic_fields`].
+#[cfg(feature = "dynamic-schema")]
+fn register_dynamic_fields(
+    registry: &mut Registry,
+    fields: Vec<(String, crate::dynamic::Field)>,
+) -> HashMap<String, HashMap<String, crate::dynamic::Field>> {
+    let mut dynamic_fields: HashMap<String, HashMap<String, crate::dynamic::Field>> =
+        HashMap::new();
+
+    for (type_name, field) in fields {
+        if let Some(MetaType::Object { fields, .. }) = registry.types.get_mut(&type_name) {
+            fields.insert(field.name.clone(), field.to_meta_field());
+        }
+
+        dynamic_fields
+            .entry(type_name)
+            .or_default()
+            .insert(field.name.clone(), field);
+    }
+
+    dynamic_fields
+}
+
+#[doc(hidden)]
+pub struct SchemaEnvInner {
+    pub registry: Arc<Registry>,
+    pub data: Data,
+    pub custom_directives: Arc<HashMap<String, Box<dyn CustomDirectiveFactory>>>,
+    pub(crate) entity_resolvers: Arc<Vec<EntityResolver>>,
+    pub(crate) unknown_entity_hook: Option<UnknownEntityHook>,
+    pub(crate) guard_denial_hook: Option<GuardDenialHook>,
+    pub(crate) introspection_hooks: Arc<Vec<IntrospectionHook>>,
+    pub(crate) description_resolver: Option<DescriptionResolver>,
+    pub(crate) timer: Option<Arc<dyn Timer>>,
+    pub(crate) field_cache: Option<FieldCache>,
+    pub(crate) object_extensions: Arc<HashMap<String, HashMap<String, ObjectExtensionResolver>>>,
+    pub(crate) providers: Arc<Providers>,
+    pub(crate) execution_stats: bool,
+    #[cfg(feature = "dynamic-schema")]
+    pub(crate) dynamic_fields: Arc<HashMap<String, HashMap<String, crate::dynamic::Field>>>,
+    /// The environment of the schema this variant was created from, see
+    /// [`Schema::variant`].
+    pub(crate) base: Option<SchemaEnv>,
+}
+
+impl SchemaEnvInner {
+    /// Resolves a description returned by the introspection for the locale of
+    /// `ctx`.
+    pub(crate) fn localize_description<'a>(
+        &self,
+        ctx: &Context<'_>,
+        description: Option<&'a str>,
+    ) -> Option<Cow<'a, str>> {
+        let description = description?;
+        match (&self.description_resolver, ctx.data_opt::<Locale>()) {
+            (Some(resolver), Some(locale)) => {
+                Some(Cow::Owned(resolver(description, locale.as_str())))
+            }
+            _ => Some(Cow::Borrowed(description)),
+        }
+    }
+
+    /// Calls the introspection hooks with the value of the field of `ctx`,
+    /// inside of the result of `__schema` or `__type`.
+    pub(crate) fn apply_introspection_hooks(&self, ctx: &Context<'_>, value: &mut Value) {
+        for hook in self.introspection_hooks.iter() {
+            hook(ctx, value);
+        }
+    }
+
+    /// Gets the data of the schema, or of the schemas it was created from if
+    /// it is a variant.
+    pub(crate) fn get_data(&self, type_id: &TypeId) -> Option<&(dyn Any + Send + Sync)> {
+        match self.data.get(type_id) {
+            Some(data) => Some(data.as_ref()),
+            None => self.base.as_ref()?.get_data(type_id),
+        }
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct SchemaEnv(pub(crate) Arc<SchemaEnvInner>);
+
+impl Deref for SchemaEnv {
+    type Target = SchemaEnvInner;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl SchemaEnv {
+    pub(crate) fn export_sdl_for_audience(
+        &self,
+        options: SDLExportOptions,
+        audience: &SDLExportAudience,
+    ) -> String {
+        self.registry
+            .export_sdl_for_audience(options, audience, self.description_resolver.as_ref())
+    }
+
+    pub(crate) fn input_json_schema(&self, type_name: &str) -> Option<serde_json::Value> {
+        // the visibility is evaluated like for a request without visibility profile
+        let profile = self
+            .registry
+            .visibility_profiles
+            .first()
+            .map(String::as_str);
+        self.registry
+            .export_input_json_schema(type_name, &|visible| {
+                is_visible_outside_request(visible, profile)
+            })
+    }
 
-#[doc(hidden)]
-#[derive(Clone)]
-pub struct SchemaEnv(pub(crate) Arc<SchemaEnvInner>);
+    pub(crate) fn arguments_json_schema(
+        &self,
+        type_name: &str,
+        field_name: &str,
+    ) -> Option<serde_json::Value> {
+        let profile = self
+            .registry
+            .visibility_profiles
+            .first()
+            .map(String::as_str);
+        self.registry
+            .export_arguments_json_schema(type_name, field_name, &|visible| {
+                is_visible_outside_request(visible, profile)
+            })
+    }
 
-impl Deref for SchemaEnv {
-    type Target = SchemaEnvInner;
+    pub(crate) fn write_sdl_to_out_dir(
+        &self,
+        file_name: &Path,
+        options: SDLExportOptions,
+    ) -> std::io::Result<PathBuf> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "the `OUT_DIR` environment variable is not set, the SDL must be written by a build script",
+            )
+        })?;
+        let path = Path::new(&out_dir).join(file_name);
+        self.write_sdl(&path, options)?;
+        Ok(path)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    pub(crate) fn write_sdl(&self, path: &Path, options: SDLExportOptions) -> std::io::Result<()> {
+        let sdl = self.registry.export_sdl(options);
+
+        // an unchanged file is not rewritten, to not rebuild the crates including it
+        if std::fs::read_to_string(path).ok().as_deref() != Some(sdl.as_str()) {
+            std::fs::write(path, sdl)?;
+        }
+        Ok(())
     }
 }
 
 #[doc(hidden)]
 pub struct SchemaInner<Query, Mutation, Subscription> {
     pub(crate) validation_mode: ValidationMode,
-    pub(crate) query: QueryRoot<Query>,
-    pub(crate) mutation: Mutation,
-    pub(crate) subscription: Subscription,
+    pub(crate) query: Arc<QueryRoot<Query>>,
+    pub(crate) mutation: Arc<Mutation>,
+    pub(crate) subscription: Arc<Subscription>,
     pub(crate) complexity: Option<usize>,
     pub(crate) depth: Option<usize>,
+    pub(crate) skip_introspection_limits: bool,
     pub(crate) recursive_depth: usize,
-    pub(crate) extensions: Vec<Box<dyn ExtensionFactory>>,
+    pub(crate) input_depth: Option<usize>,
+    pub(crate) input_nodes: Option<usize>,
+    pub(crate) max_errors: Option<usize>,
+    pub(crate) max_response_size: Option<usize>,
+    pub(crate) disable_anonymous_operations: bool,
+    pub(crate) unique_batch_operation_names: bool,
+    pub(crate) export_directive: bool,
+    pub(crate) variables_hooks: Arc<Vec<VariablesHook>>,
+    pub(crate) extensions: Arc<Vec<Box<dyn ExtensionFactory>>>,
+    pub(crate) live_query_store: Option<Arc<dyn LiveQueryStore>>,
+    pub(crate) mutation_transaction: Option<Arc<dyn ErasedMutationTransaction>>,
+    pub(crate) field_cache_spawner: Option<FieldCacheSpawner>,
     pub(crate) env: SchemaEnv,
 }
 
+impl<Query, Mutation, Subscription> SchemaInner<Query, Mutation, Subscription> {
+    fn prepare_request_config(&self) -> PrepareRequestConfig<'_> {
+        PrepareRequestConfig {
+            validation_mode: self.validation_mode,
+            recursive_depth: self.recursive_depth,
+            complexity: self.complexity,
+            depth: self.depth,
+            skip_introspection_limits: self.skip_introspection_limits,
+            input_depth: self.input_depth,
+            input_nodes: self.input_nodes,
+            max_errors: self.max_errors,
+            max_response_size: self.max_response_size,
+            disable_anonymous_operations: self.disable_anonymous_operations,
+            variables_hooks: &self.variables_hooks,
+        }
+    }
+}
+
 /// GraphQL schema.
 ///
 /// Cloning a schema is cheap, so it can be easily shared.
@@ -350,14 +1708,40 @@ where
             data: Default::default(),
             complexity: None,
             depth: None,
+            skip_introspection_limits: false,
             recursive_depth: 32,
+            input_depth: None,
+            input_nodes: None,
+            max_errors: None,
+            max_response_size: None,
+            disable_anonymous_operations: false,
+            unique_batch_operation_names: false,
+            export_directive: false,
             extensions: Default::default(),
             custom_directives: Default::default(),
+            registry_hooks: Default::default(),
+            variables_hooks: Default::default(),
+            entity_resolvers: Default::default(),
+            unknown_entity_hook: None,
+            guard_denial_hook: None,
+            introspection_hooks: Default::default(),
+            description_resolver: None,
+            timer: default_timer(),
+            live_query_store: None,
+            field_cache: None,
+            field_cache_spawner: None,
+            object_extensions: Default::default(),
+            providers: Default::default(),
+            mutation_transaction: None,
+            execution_stats: false,
+            #[cfg(feature = "dynamic-schema")]
+            dynamic_fields: Default::default(),
         }
     }
 
     pub(crate) fn create_registry(ignore_name_conflicts: HashSet<String>) -> Registry {
         let mut registry = Registry {
+            description: None,
             types: Default::default(),
             directives: Default::default(),
             implements: Default::default(),
@@ -377,6 +1761,10 @@ where
             federation_subscription: false,
             ignore_name_conflicts,
             enable_suggestions: true,
+            enable_experimental_ccn: false,
+            merged_fields: Default::default(),
+            visibility_profiles: Default::default(),
+            registered_names: Default::default(),
         };
         registry.add_system_types();
 
@@ -417,6 +1805,164 @@ where
         self.0.env.registry.export_sdl(options)
     }
 
+    /// Returns SDL(Schema Definition Language) of this schema with options,
+    /// with only the elements seen by `audience`.
+    pub fn sdl_for_audience(
+        &self,
+        options: SDLExportOptions,
+        audience: &SDLExportAudience,
+    ) -> String {
+        self.0.env.export_sdl_for_audience(options, audience)
+    }
+
+    /// Writes the SDL(Schema Definition Language) of this schema with options
+    /// to the file `file_name` in the `OUT_DIR` of the build script, and
+    /// returns the path of the file.
+    ///
+    /// This is used to generate a snapshot of the schema at build time for
+    /// downstream code generators, such as TypeScript clients, without
+    /// starting the server. The schema must be defined in a crate which is a
+    /// build dependency of the crate whose `build.rs` calls this method. The
+    /// file is not rewritten if the SDL has not changed.
+    ///
+    /// Returns an error if the `OUT_DIR` environment variable is not set, or
+    /// if the file cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// // build.rs
+    /// use async_graphql::SDLExportOptions;
+    ///
+    /// fn main() {
+    ///     let schema = my_schema::build_schema();
+    ///     schema
+    ///         .write_sdl_to_out_dir("schema.graphql", SDLExportOptions::new())
+    ///         .expect("failed to write the schema");
+    /// }
+    /// ```
+    pub fn write_sdl_to_out_dir(
+        &self,
+        file_name: impl AsRef<Path>,
+        options: SDLExportOptions,
+    ) -> std::io::Result<PathBuf> {
+        self.0.env.write_sdl_to_out_dir(file_name.as_ref(), options)
+    }
+
+    /// Writes the SDL(Schema Definition Language) of this schema with options
+    /// to the file `path`.
+    ///
+    /// Like [`Schema::write_sdl_to_out_dir`], the file is not rewritten if the
+    /// SDL has not changed.
+    pub fn write_sdl(
+        &self,
+        path: impl AsRef<Path>,
+        options: SDLExportOptions,
+    ) -> std::io::Result<()> {
+        self.0.env.write_sdl(path.as_ref(), options)
+    }
+
+    /// Returns a read-only view over the metadata of this schema.
+    pub fn meta(&self) -> SchemaMeta<'_> {
+        SchemaMeta::new(&self.0.env.registry)
+    }
+
+    /// Returns the result of the standard introspection query of this schema,
+    /// without executing it.
+    ///
+    /// The returned value contains the `__schema` field, and can be written to
+    /// a `schema.json` file used by code generators. Like
+    /// [`sdl`](Self::sdl), all types and fields are included regardless of
+    /// their visibility.
+    pub fn introspection(&self) -> serde_json::Value {
+        self.0.env.registry.introspection()
+    }
+
+    /// Returns the JSON Schema of the input object `type_name`, or `None` if
+    /// it is not an input object of this schema.
+    ///
+    /// The JSON Schema can be used by non-GraphQL consumers, such as form
+    /// builders or configuration validators, to validate the payloads sent to
+    /// the operations. The enums, input objects and custom scalars it refers to
+    /// are defined in its `$defs`, the custom scalars accepting any value.
+    ///
+    /// The types, fields and enum values hidden with `visible = false` or from
+    /// the default visibility profile, and the inaccessible ones, are not
+    /// exported. The visibility functions need the context of a request, so
+    /// they are not evaluated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// #[derive(InputObject)]
+    /// struct CreateUserInput {
+    ///     name: String,
+    ///     age: Option<i32>,
+    /// }
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// struct Mutation;
+    ///
+    /// #[Object]
+    /// impl Mutation {
+    ///     async fn create_user(&self, input: CreateUserInput) -> bool {
+    ///         true
+    ///     }
+    /// }
+    ///
+    /// let schema = Schema::new(Query, Mutation, EmptySubscription);
+    /// let json_schema = schema.input_json_schema("CreateUserInput").unwrap();
+    /// assert_eq!(json_schema["required"], serde_json::json!(["name"]));
+    /// assert_eq!(
+    ///     json_schema["properties"]["age"]["type"],
+    ///     serde_json::json!(["integer", "null"])
+    /// );
+    /// ```
+    pub fn input_json_schema(&self, type_name: &str) -> Option<serde_json::Value> {
+        self.0.env.input_json_schema(type_name)
+    }
+
+    /// Returns the JSON Schema of the arguments of the field `field_name` of
+    /// the object or interface `type_name`, such as a field of the mutation
+    /// root, or `None` if the field does not exist.
+    ///
+    /// See [`input_json_schema`](Self::input_json_schema).
+    pub fn arguments_json_schema(
+        &self,
+        type_name: &str,
+        field_name: &str,
+    ) -> Option<serde_json::Value> {
+        self.0.env.arguments_json_schema(type_name, field_name)
+    }
+
+    /// Returns a stable fingerprint of this schema.
+    ///
+    /// The fingerprint is a hash of the normalized SDL, it does not depend on
+    /// the declaration order of fields, arguments, enum values, union members
+    /// or implemented interfaces. It can be used as a cache key, or as a
+    /// schema identifier when reporting to a schema registry.
+    pub fn fingerprint(&self) -> String {
+        self.0.env.registry.fingerprint()
+    }
+
+    /// Returns the number of types, fields and other elements of this schema,
+    /// and the estimated memory usage of its registry.
+    ///
+    /// The registry is shared by the [variants](Self::variant) of the schema.
+    pub fn registry_stats(&self) -> RegistryStats {
+        self.0.env.registry.stats()
+    }
+
     /// Get all names in this schema
     ///
     /// Maybe you want to serialize a custom binary protocol. In order to
@@ -427,15 +1973,103 @@ where
         self.0.env.registry.names()
     }
 
-    fn create_extensions(&self, session_data: Arc<Data>) -> Extensions {
+    /// Create a builder for a variant of this schema.
+    ///
+    /// The variant shares the types, the resolvers and the extensions of this
+    /// schema, and has its own data, limits and validation mode, which
+    /// default to those of this schema. Its data takes precedence over the
+    /// data of this schema, so a multi-tenant server can create a variant per
+    /// tenant, with its own
+    /// [`VisibilityProfile`](crate::VisibilityProfile) and settings, without
+    /// building the registry again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct TenantName(String);
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn tenant(&self, ctx: &Context<'_>) -> String {
+    ///         ctx.data_unchecked::<TenantName>().0.clone()
+    ///     }
+    ///
+    ///     #[graphql(visible("internal"))]
+    ///     async fn secret(&self) -> i32 {
+    ///         42
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    ///     .visibility_profiles(["public", "internal"])
+    ///     .data(TenantName("default".to_string()))
+    ///     .finish();
+    /// let acme = schema
+    ///     .variant()
+    ///     .data(TenantName("acme".to_string()))
+    ///     .data(VisibilityProfile::new("internal"))
+    ///     .limit_depth(5)
+    ///     .finish();
+    ///
+    /// assert_eq!(
+    ///     acme.execute("{ tenant secret }").await.into_result().unwrap().data,
+    ///     value!({ "tenant": "acme", "secret": 42 })
+    /// );
+    /// assert!(schema.execute("{ tenant secret }").await.is_err());
+    /// # });
+    /// ```
+    pub fn variant(&self) -> SchemaVariantBuilder<Query, Mutation, Subscription> {
+        let inner = &self.0;
+        SchemaVariantBuilder {
+            schema: self.clone(),
+            validation_mode: inner.validation_mode,
+            data: Default::default(),
+            complexity: inner.complexity,
+            depth: inner.depth,
+            recursive_depth: inner.recursive_depth,
+            input_depth: inner.input_depth,
+            input_nodes: inner.input_nodes,
+            max_errors: inner.max_errors,
+            max_response_size: inner.max_response_size,
+            field_cache: None,
+        }
+    }
+
+    fn create_extensions(&self, session_data: Arc<Data>, request: &Request) -> Extensions {
         Extensions::new(
-            self.0.extensions.iter().map(|f| f.create()),
+            create_extensions(&self.0.extensions, request),
             self.0.env.clone(),
             session_data,
         )
     }
 
+    /// Executes a query only to refresh the value of a cached field, see
+    /// [`QueryEnvInner::field_cache_refresh`].
+    async fn refresh_field_cache(&self, env: QueryEnv) {
+        let ctx = env.create_context(&self.0.env, None, &env.operation.node.selection_set);
+        let _ = resolve_container(&ctx, &*self.0.query).await;
+    }
+
     async fn execute_once(&self, env: QueryEnv) -> Response {
+        if let Some(cache) = &self.0.env.field_cache {
+            cache.refresher.get_or_init(|| {
+                let schema = Arc::downgrade(&self.0);
+                Box::new(move |env| {
+                    let schema = schema.upgrade().map(Schema);
+                    Box::pin(async move {
+                        if let Some(schema) = schema {
+                            schema.refresh_field_cache(env).await;
+                        }
+                    })
+                })
+            });
+        }
+
         // execute
         let ctx = ContextBase {
             path_node: None,
@@ -443,17 +2077,22 @@ where
             item: &env.operation.node.selection_set,
             schema_env: &self.0.env,
             query_env: &env,
+            scope: Default::default(),
         };
 
+        let start_time = Instant::now();
         let res = match &env.operation.node.ty {
-            OperationType::Query => resolve_container(&ctx, &self.0.query).await,
+            OperationType::Query => resolve_container(&ctx, &*self.0.query).await,
             OperationType::Mutation => {
                 if self.0.env.registry.introspection_mode == IntrospectionMode::IntrospectionOnly
                     || env.introspection_mode == IntrospectionMode::IntrospectionOnly
                 {
                     resolve_container_serial(&ctx, &EmptyMutation).await
+                } else if let Some(transaction) = &self.0.mutation_transaction {
+                    resolve_mutation_in_transaction(&ctx, &*self.0.mutation, transaction.as_ref())
+                        .await
                 } else {
-                    resolve_container_serial(&ctx, &self.0.mutation).await
+                    resolve_container_serial(&ctx, &*self.0.mutation).await
                 }
             }
             OperationType::Subscription => Err(ServerError::new(
@@ -462,21 +2101,33 @@ where
             )),
         };
 
+        let res = match env.response_size_error() {
+            Some(err) => Err(err),
+            None => res,
+        };
         let mut resp = match res {
             Ok(value) => Response::new(value),
             Err(err) => Response::from_errors(vec![err]),
         }
-        .http_headers(std::mem::take(&mut *env.http_headers.lock().unwrap()));
-
-        resp.errors
-            .extend(std::mem::take(&mut *env.errors.lock().unwrap()));
+        .http_headers(std::mem::take(&mut *env.http_headers.lock().unwrap()))
+        .operation(env.operation_metadata());
+
+        resp.execution_stats = env
+            .execution_stats
+            .as_ref()
+            .map(|stats| stats.finish(start_time.elapsed()));
+        env.take_errors(&mut resp.errors);
+        resp.extensions.extend(std::mem::take(
+            &mut *env.response_extensions.lock().unwrap(),
+        ));
         resp
     }
 
     /// Execute a GraphQL query.
     pub async fn execute(&self, request: impl Into<Request>) -> Response {
         let request = request.into();
-        let extensions = self.create_extensions(Default::default());
+        let timeout = request.timeout;
+        let extensions = self.create_extensions(Default::default(), &request);
         let request_fut = {
             let extensions = extensions.clone();
             async move {
@@ -484,19 +2135,20 @@ where
                     extensions,
                     request,
                     Default::default(),
-                    &self.0.env.registry,
-                    self.0.validation_mode,
-                    self.0.recursive_depth,
-                    self.0.complexity,
-                    self.0.depth,
+                    &self.0.env,
+                    self.0.prepare_request_config(),
                 )
                 .await
                 {
-                    Ok((env, cache_control)) => {
+                    Ok((env, validation_result)) => {
                         let fut = async {
-                            self.execute_once(env.clone())
-                                .await
-                                .cache_control(cache_control)
+                            with_timeout(
+                                self.execute_once(env.clone()),
+                                timeout,
+                                self.0.env.timer.as_deref(),
+                            )
+                            .await
+                            .cache_control(validation_result.cache_control)
                         };
                         futures_util::pin_mut!(fut);
                         env.extensions
@@ -511,18 +2163,105 @@ where
         extensions.request(&mut request_fut).await
     }
 
+    /// Validate a GraphQL request without executing it.
+    ///
+    /// The request is parsed, validated and checked against the limits of the
+    /// schema exactly as [`Schema::execute`] would do, but no resolvers are
+    /// called. On success, the depth, complexity and cache control of the
+    /// operation are returned.
+    ///
+    /// ```
+    /// use async_graphql::*;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         unreachable!()
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    /// let result = schema.validate("{ value }").await.unwrap();
+    /// assert_eq!(result.depth, 1);
+    /// assert_eq!(result.complexity, 1);
+    /// assert!(schema.validate("{ unknown }").await.is_err());
+    /// # });
+    /// ```
+    pub async fn validate(
+        &self,
+        request: impl Into<Request>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let request = request.into();
+        let extensions = self.create_extensions(Default::default(), &request);
+        prepare_request(
+            extensions,
+            request,
+            Default::default(),
+            &self.0.env,
+            self.0.prepare_request_config(),
+        )
+        .await
+        .map(|(_, validation_result)| validation_result)
+    }
+
     /// Execute a GraphQL batch query.
     pub async fn execute_batch(&self, batch_request: BatchRequest) -> BatchResponse {
         match batch_request {
             BatchRequest::Single(request) => BatchResponse::Single(self.execute(request).await),
-            BatchRequest::Batch(requests) => BatchResponse::Batch(
-                FuturesOrdered::from_iter(
-                    requests.into_iter().map(|request| self.execute(request)),
+            BatchRequest::Batch(mut requests) => {
+                let errors = if self.0.unique_batch_operation_names {
+                    check_batch_operation_names(&mut requests)
+                } else {
+                    vec![None; requests.len()]
+                };
+                if self.0.export_directive {
+                    return BatchResponse::Batch(
+                        self.execute_batch_with_exports(requests, errors).await,
+                    );
+                }
+                BatchResponse::Batch(
+                    FuturesOrdered::from_iter(requests.into_iter().zip(errors).map(
+                        |(request, error)| async move {
+                            match error {
+                                Some(error) => Response::from_errors(vec![error]),
+                                None => self.execute(request).await,
+                            }
+                        },
+                    ))
+                    .collect()
+                    .await,
                 )
-                .collect()
-                .await,
-            ),
+            }
+        }
+    }
+
+    async fn execute_batch_with_exports(
+        &self,
+        requests: Vec<Request>,
+        errors: Vec<Option<ServerError>>,
+    ) -> Vec<Response> {
+        let mut exported = Variables::default();
+        let mut responses = Vec::with_capacity(requests.len());
+        for (mut request, error) in requests.into_iter().zip(errors) {
+            if let Some(error) = error {
+                responses.push(Response::from_errors(vec![error]));
+                continue;
+            }
+
+            request.variables.extend(
+                exported
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+            let exports = ExportedValues::default();
+            request.data.insert(exports.clone());
+            responses.push(self.execute(request).await);
+            exports.export_to(&mut exported);
         }
+        responses
     }
 
     /// Execute a GraphQL subscription with session data.
@@ -533,15 +2272,14 @@ where
     ) -> impl Stream<Item = Response> + Send + Unpin {
         let schema = self.clone();
         let request = request.into();
-        let extensions = self.create_extensions(session_data.clone());
+        let extensions = self.create_extensions(session_data.clone(), &request);
 
         let stream = futures_util::stream::StreamExt::boxed({
             let extensions = extensions.clone();
             let env = self.0.env.clone();
             async_stream::stream! {
-                let (env, cache_control) = match prepare_request(
-                        extensions, request, session_data, &env.registry,
-                        schema.0.validation_mode, schema.0.recursive_depth, schema.0.complexity, schema.0.depth
+                let (env, validation_result) = match prepare_request(
+                        extensions, request, session_data, &env, schema.0.prepare_request_config(),
                 ).await {
                     Ok(res) => res,
                     Err(errors) => {
@@ -550,8 +2288,52 @@ where
                     }
                 };
 
+                let is_live = env.operation.node.ty == OperationType::Query
+                    && env.operation.node.directives.iter().any(|directive| directive.node.name.node == "live");
+                if is_live {
+                    if let Some(store) = &schema.0.live_query_store {
+                        let root_tokens = live_query_root_tokens(&schema.0.env, &env);
+                        let mut resolver_tokens = HashSet::new();
+                        let mut previous: Option<Value> = None;
+                        let mut revision = 0;
+                        loop {
+                            // the tokens are watched before the execution to not miss an
+                            // invalidation during it, but the tokens added by the resolvers are
+                            // only known after it, so the ones of the previous execution are
+                            // watched before it and the new ones after it
+                            let watched = root_tokens.union(&resolver_tokens).cloned().collect::<HashSet<_>>();
+                            let mut invalidations = store.watch(watched.iter().cloned().collect());
+                            let mut resp = schema.execute_once(env.clone()).await.cache_control(validation_result.cache_control);
+                            resolver_tokens = std::mem::take(&mut *env.live_query_tokens.lock().unwrap());
+                            let new_tokens = resolver_tokens.difference(&watched).cloned().collect::<Vec<_>>();
+                            if !new_tokens.is_empty() {
+                                invalidations = futures_util::stream::select(invalidations, store.watch(new_tokens)).boxed();
+                            }
+
+                            match previous.replace(resp.data.clone()) {
+                                None => yield resp.extension("live", value!({ "revision": 0 })),
+                                Some(previous) => {
+                                    let patch = diff_value(&previous, &resp.data);
+                                    if !patch.is_empty() || !resp.errors.is_empty() {
+                                        revision += 1;
+                                        resp.data = Value::Null;
+                                        yield resp.extension("live", value!({
+                                            "revision": revision,
+                                            "patch": Value::List(patch),
+                                        }));
+                                    }
+                                }
+                            }
+
+                            if invalidations.next().await.is_none() {
+                                return;
+                            }
+                        }
+                    }
+                }
+
                 if env.operation.node.ty != OperationType::Subscription {
-                    yield schema.execute_once(env).await.cache_control(cache_control);
+                    yield schema.execute_once(env).await.cache_control(validation_result.cache_control);
                     return;
                 }
 
@@ -568,15 +2350,16 @@ where
                 {
                     collect_subscription_streams(&ctx, &EmptySubscription, &mut streams)
                 } else {
-                    collect_subscription_streams(&ctx, &schema.0.subscription, &mut streams)
+                    collect_subscription_streams(&ctx, &*schema.0.subscription, &mut streams)
                 };
+                let operation = env.operation_metadata();
                 if let Err(err) = collect_result {
-                    yield Response::from_errors(vec![err]);
+                    yield Response::from_errors(vec![err]).operation(operation.clone());
                 }
 
                 let mut stream = stream::select_all(streams);
                 while let Some(resp) = stream.next().await {
-                    yield resp;
+                    yield resp.operation(operation.clone());
                 }
             }
         });
@@ -603,6 +2386,10 @@ where
         Schema::execute(self, request).await
     }
 
+    async fn execute_batch(&self, batch_request: BatchRequest) -> BatchResponse {
+        Schema::execute_batch(self, batch_request).await
+    }
+
     fn execute_stream(
         &self,
         request: Request,
@@ -613,6 +2400,82 @@ where
     }
 }
 
+/// Returns the invalidation tokens of the root fields of a live query, which
+/// are known before its execution.
+fn live_query_root_tokens(schema_env: &SchemaEnv, env: &QueryEnv) -> HashSet<String> {
+    fn collect_root_fields(
+        env: &QueryEnv,
+        selection_set: &SelectionSet,
+        query_type: &str,
+        tokens: &mut HashSet<String>,
+    ) {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    if !field.node.name.node.starts_with("__") {
+                        tokens.insert(format!("{}.{}", query_type, field.node.name.node));
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    if let Some(fragment) =
+                        env.fragments.get(&fragment_spread.node.fragment_name.node)
+                    {
+                        collect_root_fields(
+                            env,
+                            &fragment.node.selection_set.node,
+                            query_type,
+                            tokens,
+                        );
+                    }
+                }
+                Selection::InlineFragment(inline_fragment) => collect_root_fields(
+                    env,
+                    &inline_fragment.node.selection_set.node,
+                    query_type,
+                    tokens,
+                ),
+            }
+        }
+    }
+
+    let mut tokens = HashSet::new();
+    collect_root_fields(
+        env,
+        &env.operation.node.selection_set.node,
+        &schema_env.registry.query_type,
+        &mut tokens,
+    );
+    tokens
+}
+
+/// Returns `true` if all operations of the document only select introspection
+/// fields.
+fn is_introspection_document(doc: &ExecutableDocument) -> bool {
+    fn is_introspection_selection_set(
+        doc: &ExecutableDocument,
+        selection_set: &SelectionSet,
+    ) -> bool {
+        selection_set.items.iter().all(|selection| match &selection.node {
+            Selection::Field(field) => matches!(
+                field.node.name.node.as_str(),
+                "__schema" | "__type" | "__typename"
+            ),
+            Selection::FragmentSpread(fragment_spread) => matches!(
+                doc.fragments.get(&fragment_spread.node.fragment_name.node),
+                Some(fragment) if is_introspection_selection_set(doc, &fragment.node.selection_set.node)
+            ),
+            Selection::InlineFragment(inline_fragment) => {
+                is_introspection_selection_set(doc, &inline_fragment.node.selection_set.node)
+            }
+        })
+    }
+
+    doc.operations.iter().all(|(_, operation)| {
+        operation.node.ty == OperationType::Query
+            && is_introspection_selection_set(doc, &operation.node.selection_set.node)
+    })
+}
+
 fn check_recursive_depth(doc: &ExecutableDocument, max_depth: usize) -> ServerResult<()> {
     fn check_selection_set(
         doc: &ExecutableDocument,
@@ -675,6 +2538,79 @@ fn check_recursive_depth(doc: &ExecutableDocument, max_depth: usize) -> ServerRe
     Ok(())
 }
 
+fn check_input_limits(
+    variables: &Variables,
+    max_depth: Option<usize>,
+    max_nodes: Option<usize>,
+) -> ServerResult<()> {
+    fn check_value(
+        name: &Name,
+        value: &Value,
+        depth: usize,
+        max_depth: Option<usize>,
+        nodes: &mut usize,
+        max_nodes: Option<usize>,
+    ) -> ServerResult<()> {
+        *nodes += 1;
+        if let Some(max_nodes) = max_nodes {
+            if *nodes > max_nodes {
+                return Err(ServerError::new(
+                    format!(
+                        "The variables cannot contain more than `{}` input values",
+                        max_nodes
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        let items: Box<dyn Iterator<Item = &Value>> = match value {
+            Value::List(items) => Box::new(items.iter()),
+            Value::Object(obj) => Box::new(obj.values()),
+            _ => return Ok(()),
+        };
+        if let Some(max_depth) = max_depth {
+            if depth + 1 > max_depth {
+                return Err(ServerError::new(
+                    format!(
+                        "The input value of variable \"${}\" cannot be nested deeper than `{}`",
+                        name, max_depth
+                    ),
+                    None,
+                ));
+            }
+        }
+        for item in items {
+            check_value(name, item, depth + 1, max_depth, nodes, max_nodes)?;
+        }
+        Ok(())
+    }
+
+    if max_depth.is_none() && max_nodes.is_none() {
+        return Ok(());
+    }
+
+    let mut nodes = 0;
+    for (name, value) in variables.iter() {
+        check_value(name, value, 0, max_depth, &mut nodes, max_nodes)?;
+    }
+    Ok(())
+}
+
+fn has_conditional_selections(selection_set: &SelectionSet) -> bool {
+    selection_set.items.iter().any(|selection| {
+        selection.node.directives().iter().any(|directive| {
+            directive.node.name.node == "skip" || directive.node.name.node == "include"
+        }) || match &selection.node {
+            Selection::Field(field) => has_conditional_selections(&field.node.selection_set.node),
+            Selection::FragmentSpread(_) => false,
+            Selection::InlineFragment(inline_fragment) => {
+                has_conditional_selections(&inline_fragment.node.selection_set.node)
+            }
+        }
+    })
+}
+
 fn remove_skipped_selection(selection_set: &mut SelectionSet, variables: &Variables) {
     fn is_skipped(directives: &[Positioned<Directive>], variables: &Variables) -> bool {
         for directive in directives {
@@ -723,29 +2659,118 @@ fn remove_skipped_selection(selection_set: &mut SelectionSet, variables: &Variab
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+pub(crate) async fn with_timeout(
+    fut: impl Future<Output = Response>,
+    timeout: Option<Duration>,
+    timer: Option<&dyn Timer>,
+) -> Response {
+    match (timeout, timer) {
+        (Some(timeout), Some(timer)) => {
+            futures_util::pin_mut!(fut);
+            match futures_util::future::select(fut, timer.delay(timeout)).await {
+                Either::Left((resp, _)) => resp,
+                Either::Right(_) => {
+                    Response::from_errors(vec![ServerError::new("Request timed out.", None)])
+                }
+            }
+        }
+        _ => fut.await,
+    }
+}
+
+/// Checks that the requests of a batch have unique operation names, and
+/// returns the error of each request that does not.
+pub(crate) fn check_batch_operation_names(requests: &mut [Request]) -> Vec<Option<ServerError>> {
+    let names = requests
+        .iter_mut()
+        .map(|request| match &request.operation_name {
+            Some(operation_name) => Ok(Some(operation_name.clone())),
+            None => request
+                .parsed_query()
+                .map(|document| match &document.operations {
+                    DocumentOperations::Multiple(operations) if operations.len() == 1 => {
+                        operations.keys().next().map(ToString::to_string)
+                    }
+                    _ => None,
+                }),
+        })
+        .collect::<Vec<_>>();
+
+    let mut counts = HashMap::new();
+    for name in names.iter().flatten().flatten() {
+        *counts.entry(name.as_str()).or_insert(0usize) += 1;
+    }
+
+    names
+        .iter()
+        .map(|name| match name {
+            Ok(Some(name)) if counts[name.as_str()] > 1 => Some(ServerError::new(
+                format!(
+                    r#"Operation name "{}" is used by more than one request of the batch."#,
+                    name
+                ),
+                None,
+            )),
+            Ok(Some(_)) => None,
+            Ok(None) => Some(ServerError::new(
+                "Operation name required in batch request.",
+                None,
+            )),
+            // the syntax errors are reported by the execution of the request
+            Err(_) => None,
+        })
+        .collect()
+}
+
+/// The settings of a schema used to prepare its requests.
+pub(crate) struct PrepareRequestConfig<'a> {
+    pub(crate) validation_mode: ValidationMode,
+    pub(crate) recursive_depth: usize,
+    pub(crate) complexity: Option<usize>,
+    pub(crate) depth: Option<usize>,
+    pub(crate) skip_introspection_limits: bool,
+    pub(crate) input_depth: Option<usize>,
+    pub(crate) input_nodes: Option<usize>,
+    pub(crate) max_errors: Option<usize>,
+    pub(crate) max_response_size: Option<usize>,
+    pub(crate) disable_anonymous_operations: bool,
+    pub(crate) variables_hooks: &'a [VariablesHook],
+}
+
 pub(crate) async fn prepare_request(
     mut extensions: Extensions,
     request: Request,
     session_data: Arc<Data>,
-    registry: &Registry,
-    validation_mode: ValidationMode,
-    recursive_depth: usize,
-    complexity: Option<usize>,
-    depth: Option<usize>,
-) -> Result<(QueryEnv, CacheControl), Vec<ServerError>> {
+    schema_env: &SchemaEnv,
+    config: PrepareRequestConfig<'_>,
+) -> Result<(QueryEnv, ValidationResult), Vec<ServerError>> {
+    let PrepareRequestConfig {
+        validation_mode,
+        recursive_depth,
+        complexity,
+        depth,
+        skip_introspection_limits,
+        input_depth,
+        input_nodes,
+        max_errors,
+        max_response_size,
+        disable_anonymous_operations,
+        variables_hooks,
+    } = config;
     let mut request = request;
     let query_data = Arc::new(std::mem::take(&mut request.data));
     extensions.attach_query_data(query_data.clone());
 
     let mut request = extensions.prepare_request(request).await?;
+    #[cfg_attr(not(feature = "fragment-arguments"), allow(unused_mut))]
     let mut document = {
         let query = &request.query;
         let parsed_doc = request.parsed_query.take();
         let fut_parse = async move {
             let doc = match parsed_doc {
                 Some(parsed_doc) => parsed_doc,
-                None => parse_query(query)?,
+                None => parse_query(query)
+                    .map_err(|err| SourcePreview::new(query).add_to(err.into()))?,
             };
             check_recursive_depth(&doc, recursive_depth)?;
             Ok(doc)
@@ -756,52 +2781,73 @@ pub(crate) async fn prepare_request(
             .await?
     };
 
+    check_input_limits(&request.variables, input_depth, input_nodes).map_err(|err| vec![err])?;
+
+    #[cfg(feature = "fragment-arguments")]
+    crate::validation::expand_fragment_arguments(&mut document).map_err(|err| vec![err])?;
+
+    let visibility_profile =
+        active_visibility_profile(schema_env, &[&request.data, &query_data, &session_data]);
+    check_visibility_profile(&schema_env.registry, visibility_profile).map_err(|err| vec![err])?;
+
     // check rules
     let validation_result = {
         let validation_fut = async {
             check_rules(
-                registry,
+                &schema_env.registry,
                 &document,
                 Some(&request.variables),
                 validation_mode,
+                visibility_profile,
+                max_errors,
             )
+            .map_err(|errors| {
+                let source_preview = SourcePreview::new(&request.query);
+                errors
+                    .into_iter()
+                    .map(|err| source_preview.add_to(err))
+                    .collect::<Vec<_>>()
+            })
         };
         futures_util::pin_mut!(validation_fut);
         extensions.validation(&mut validation_fut).await?
     };
 
     // check limit
-    if let Some(limit_complexity) = complexity {
-        if validation_result.complexity > limit_complexity {
-            return Err(vec![ServerError::new("Query is too complex.", None)]);
+    if !skip_introspection_limits || !is_introspection_document(&document) {
+        let complexity = request.complexity_limit.or(complexity);
+        let depth = request.depth_limit.or(depth);
+
+        if let Some(limit_complexity) = complexity {
+            if validation_result.complexity > limit_complexity {
+                return Err(vec![ServerError::new("Query is too complex.", None)]);
+            }
         }
-    }
 
-    if let Some(limit_depth) = depth {
-        if validation_result.depth > limit_depth {
-            return Err(vec![ServerError::new("Query is nested too deep.", None)]);
+        if let Some(limit_depth) = depth {
+            if validation_result.depth > limit_depth {
+                return Err(vec![ServerError::new("Query is nested too deep.", None)]);
+            }
         }
     }
 
-    let operation = if let Some(operation_name) = &request.operation_name {
-        match document.operations {
-            DocumentOperations::Single(_) => None,
-            DocumentOperations::Multiple(mut operations) => operations
-                .remove(operation_name.as_str())
-                .map(|operation| (Some(operation_name.clone()), operation)),
-        }
-        .ok_or_else(|| {
-            ServerError::new(
+    let operation_name = if let Some(operation_name) = &request.operation_name {
+        match &document.operations {
+            DocumentOperations::Multiple(operations)
+                if operations.contains_key(operation_name.as_str()) =>
+            {
+                Ok(Some(operation_name.clone()))
+            }
+            _ => Err(ServerError::new(
                 format!(r#"Unknown operation named "{}""#, operation_name),
                 None,
-            )
-        })
+            )),
+        }
     } else {
-        match document.operations {
-            DocumentOperations::Single(operation) => Ok((None, operation)),
+        match &document.operations {
+            DocumentOperations::Single(_) => Ok(None),
             DocumentOperations::Multiple(map) if map.len() == 1 => {
-                let (operation_name, operation) = map.into_iter().next().unwrap();
-                Ok((Some(operation_name.to_string()), operation))
+                Ok(map.keys().next().map(ToString::to_string))
             }
             DocumentOperations::Multiple(_) => Err(ServerError::new(
                 "Operation name required in request.",
@@ -809,21 +2855,76 @@ pub(crate) async fn prepare_request(
             )),
         }
     };
+    let operation_name = operation_name.map_err(|err| vec![err])?;
+
+    let operation = match &document.operations {
+        DocumentOperations::Single(operation) => operation,
+        DocumentOperations::Multiple(operations) => {
+            &operations[operation_name.as_deref().unwrap_or_default()]
+        }
+    };
+    if disable_anonymous_operations && operation_name.is_none() {
+        return Err(vec![ServerError::new(
+            "Anonymous operations are not allowed, the operation must have a name.",
+            Some(operation.pos),
+        )]);
+    }
+    extensions
+        .validation_did_finish(
+            &document,
+            operation_name.as_deref(),
+            operation,
+            &request.variables,
+        )
+        .await
+        .map_err(|err| vec![err])?;
+
+    let operation = match document.operations {
+        DocumentOperations::Single(operation) => operation,
+        DocumentOperations::Multiple(mut operations) => operations
+            .remove(operation_name.as_deref().unwrap_or_default())
+            .unwrap(),
+    };
 
-    let (operation_name, mut operation) = operation.map_err(|err| vec![err])?;
+    for hook in variables_hooks {
+        hook(&mut request.variables, &operation.node)
+            .map_err(|err| vec![err.into_server_error(operation.pos)])?;
+    }
 
-    // remove skipped fields
-    for fragment in document.fragments.values_mut() {
-        remove_skipped_selection(&mut fragment.node.selection_set.node, &request.variables);
+    let mut operation = Arc::new(operation);
+    let mut fragments = Arc::new(document.fragments);
+    let operation_signature =
+        LazySignature::new(operation_name.clone(), operation.clone(), fragments.clone());
+
+    // remove skipped fields, the operation and the fragments are only copied
+    // for the signature if they have conditional selections
+    if has_conditional_selections(&operation.node.selection_set.node)
+        || fragments
+            .values()
+            .any(|fragment| has_conditional_selections(&fragment.node.selection_set.node))
+    {
+        for fragment in Arc::make_mut(&mut fragments).values_mut() {
+            remove_skipped_selection(&mut fragment.node.selection_set.node, &request.variables);
+        }
+        remove_skipped_selection(
+            &mut Arc::make_mut(&mut operation).node.selection_set.node,
+            &request.variables,
+        );
     }
-    remove_skipped_selection(&mut operation.node.selection_set.node, &request.variables);
+
+    // Subscriptions resolve each event with the same environment, so the size
+    // of their responses would accumulate.
+    let max_response_size = match operation.node.ty {
+        OperationType::Subscription => None,
+        _ => request.response_size_limit.or(max_response_size),
+    };
 
     let env = QueryEnvInner {
         extensions,
         variables: request.variables,
         operation_name,
         operation,
-        fragments: document.fragments,
+        fragments,
         uploads: request.uploads,
         session_data,
         ctx_data: query_data,
@@ -831,6 +2932,18 @@ pub(crate) async fn prepare_request(
         http_headers: Default::default(),
         introspection_mode: request.introspection_mode,
         errors: Default::default(),
+        max_errors,
+        errors_exceeded: Default::default(),
+        max_response_size,
+        response_size: Default::default(),
+        response_extensions: Default::default(),
+        live_query_tokens: Default::default(),
+        operation_signature,
+        provided_data: ProvidedData::new_map(&schema_env.providers),
+        execution_stats: schema_env
+            .execution_stats
+            .then(ExecutionStatsCollector::default),
+        field_cache_refresh: None,
     };
-    Ok((QueryEnv::new(env), validation_result.cache_control))
+    Ok((QueryEnv::new(env), validation_result))
 }
@@ -0,0 +1,914 @@
+use std::fmt::{self, Display, Formatter};
+
+use indexmap::IndexMap;
+
+use crate::parser::{
+    self, parse_schema,
+    types::{
+        BaseType, ConstDirective, DirectiveDefinition, FieldDefinition, InputValueDefinition,
+        ServiceDocument, Type, TypeKind, TypeSystemDefinition,
+    },
+    Positioned,
+};
+
+/// The criticality of a schema change
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Criticality {
+    /// The change is backwards compatible with all existing clients
+    Safe,
+    /// The change is backwards compatible, but may change the behavior of
+    /// existing clients (e.g. a new enum value that a client does not handle)
+    Dangerous,
+    /// The change breaks existing clients
+    Breaking,
+}
+
+/// The kind of a schema change
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum SchemaChangeKind {
+    /// A type was added
+    TypeAdded,
+    /// A type was removed
+    TypeRemoved,
+    /// A type changed its kind, e.g. from object to interface
+    TypeKindChanged,
+    /// The description of a type, field, argument or value changed
+    DescriptionChanged,
+    /// A field was added to an object or interface
+    FieldAdded,
+    /// A field was removed from an object or interface
+    FieldRemoved,
+    /// The type of a field changed
+    FieldTypeChanged,
+    /// A field was marked as deprecated
+    FieldDeprecated,
+    /// An argument was added to a field
+    ArgumentAdded,
+    /// An argument was removed from a field
+    ArgumentRemoved,
+    /// The type of an argument changed
+    ArgumentTypeChanged,
+    /// The default value of an argument changed
+    ArgumentDefaultValueChanged,
+    /// A field was added to an input object
+    InputFieldAdded,
+    /// A field was removed from an input object
+    InputFieldRemoved,
+    /// The type of an input object field changed
+    InputFieldTypeChanged,
+    /// The default value of an input object field changed
+    InputFieldDefaultValueChanged,
+    /// A value was added to an enum
+    EnumValueAdded,
+    /// A value was removed from an enum
+    EnumValueRemoved,
+    /// An enum value was marked as deprecated
+    EnumValueDeprecated,
+    /// A member type was added to a union
+    UnionMemberAdded,
+    /// A member type was removed from a union
+    UnionMemberRemoved,
+    /// An object or interface implements a new interface
+    InterfaceImplementationAdded,
+    /// An object or interface no longer implements an interface
+    InterfaceImplementationRemoved,
+    /// A directive was added
+    DirectiveAdded,
+    /// A directive was removed
+    DirectiveRemoved,
+    /// A location was added to a directive
+    DirectiveLocationAdded,
+    /// A location was removed from a directive
+    DirectiveLocationRemoved,
+    /// An argument was added to a directive
+    DirectiveArgumentAdded,
+    /// An argument was removed from a directive
+    DirectiveArgumentRemoved,
+    /// A directive became repeatable or is no longer repeatable
+    DirectiveRepeatableChanged,
+    /// A root operation type changed or was removed
+    RootTypeChanged,
+}
+
+/// A change between two versions of a schema
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SchemaChange {
+    /// The kind of the change
+    pub kind: SchemaChangeKind,
+    /// The criticality of the change
+    pub criticality: Criticality,
+    /// The path of the changed schema element, e.g. `Query.user.id`
+    pub path: String,
+    /// A human-readable description of the change
+    pub message: String,
+}
+
+impl SchemaChange {
+    /// Returns `true` if the change breaks existing clients
+    #[inline]
+    pub fn is_breaking(&self) -> bool {
+        self.criticality == Criticality::Breaking
+    }
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let criticality = match self.criticality {
+            Criticality::Safe => "safe",
+            Criticality::Dangerous => "dangerous",
+            Criticality::Breaking => "breaking",
+        };
+        write!(f, "[{}] {}", criticality, self.message)
+    }
+}
+
+/// Compares two schemas written in SDL(Schema Definition Language) and
+/// returns the list of changes from `old_sdl` to `new_sdl`.
+///
+/// Type extensions are merged into the extended type before comparing, so the
+/// output of [`Schema::sdl`](crate::Schema::sdl) can be compared directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// let changes = diff_schema(
+///     "type Query { a: Int b: String }",
+///     "type Query { a: Int! c(id: ID!): String }",
+/// )
+/// .unwrap();
+/// assert!(changes.iter().any(|change| change.is_breaking()));
+/// ```
+pub fn diff_schema(old_sdl: &str, new_sdl: &str) -> parser::Result<Vec<SchemaChange>> {
+    let old_doc = parse_schema(old_sdl)?;
+    let new_doc = parse_schema(new_sdl)?;
+    let old = SchemaModel::new(&old_doc);
+    let new = SchemaModel::new(&new_doc);
+    let mut changes = Vec::new();
+    diff_roots(&old, &new, &mut changes);
+    diff_types(&old, &new, &mut changes);
+    diff_directives(&old, &new, &mut changes);
+    Ok(changes)
+}
+
+#[derive(Default)]
+struct TypeModel<'a> {
+    kind: &'static str,
+    description: Option<&'a str>,
+    implements: Vec<&'a str>,
+    fields: IndexMap<&'a str, &'a FieldDefinition>,
+    input_fields: IndexMap<&'a str, &'a InputValueDefinition>,
+    enum_values: IndexMap<&'a str, &'a [Positioned<ConstDirective>]>,
+    members: Vec<&'a str>,
+}
+
+#[derive(Default)]
+struct SchemaModel<'a> {
+    query: Option<&'a str>,
+    mutation: Option<&'a str>,
+    subscription: Option<&'a str>,
+    types: IndexMap<&'a str, TypeModel<'a>>,
+    directives: IndexMap<&'a str, &'a DirectiveDefinition>,
+}
+
+impl<'a> SchemaModel<'a> {
+    fn new(doc: &'a ServiceDocument) -> Self {
+        let mut model = SchemaModel::default();
+
+        for definition in &doc.definitions {
+            match definition {
+                TypeSystemDefinition::Schema(schema) => {
+                    let schema = &schema.node;
+                    if let Some(query) = &schema.query {
+                        model.query = Some(query.node.as_str());
+                    }
+                    if let Some(mutation) = &schema.mutation {
+                        model.mutation = Some(mutation.node.as_str());
+                    }
+                    if let Some(subscription) = &schema.subscription {
+                        model.subscription = Some(subscription.node.as_str());
+                    }
+                }
+                TypeSystemDefinition::Type(ty) => {
+                    let ty = &ty.node;
+                    let model = model.types.entry(ty.name.node.as_str()).or_default();
+                    if let Some(description) = &ty.description {
+                        model.description = Some(description.node.as_str());
+                    }
+                    match &ty.kind {
+                        TypeKind::Scalar => model.kind = "scalar",
+                        TypeKind::Object(object) => {
+                            model.kind = "object";
+                            model
+                                .implements
+                                .extend(object.implements.iter().map(|name| name.node.as_str()));
+                            model.fields.extend(
+                                object
+                                    .fields
+                                    .iter()
+                                    .map(|field| (field.node.name.node.as_str(), &field.node)),
+                            );
+                        }
+                        TypeKind::Interface(interface) => {
+                            model.kind = "interface";
+                            model
+                                .implements
+                                .extend(interface.implements.iter().map(|name| name.node.as_str()));
+                            model.fields.extend(
+                                interface
+                                    .fields
+                                    .iter()
+                                    .map(|field| (field.node.name.node.as_str(), &field.node)),
+                            );
+                        }
+                        TypeKind::Union(union) => {
+                            model.kind = "union";
+                            model
+                                .members
+                                .extend(union.members.iter().map(|name| name.node.as_str()));
+                        }
+                        TypeKind::Enum(enum_type) => {
+                            model.kind = "enum";
+                            model
+                                .enum_values
+                                .extend(enum_type.values.iter().map(|value| {
+                                    (
+                                        value.node.value.node.as_str(),
+                                        value.node.directives.as_slice(),
+                                    )
+                                }));
+                        }
+                        TypeKind::InputObject(input_object) => {
+                            model.kind = "input object";
+                            model.input_fields.extend(
+                                input_object
+                                    .fields
+                                    .iter()
+                                    .map(|field| (field.node.name.node.as_str(), &field.node)),
+                            );
+                        }
+                    }
+                }
+                TypeSystemDefinition::Directive(directive) => {
+                    model
+                        .directives
+                        .insert(directive.node.name.node.as_str(), &directive.node);
+                }
+            }
+        }
+
+        // Without a schema definition, the root types use their default names.
+        if model.query.is_none() && model.types.contains_key("Query") {
+            model.query = Some("Query");
+        }
+        if model.mutation.is_none() && model.types.contains_key("Mutation") {
+            model.mutation = Some("Mutation");
+        }
+        if model.subscription.is_none() && model.types.contains_key("Subscription") {
+            model.subscription = Some("Subscription");
+        }
+
+        model
+    }
+}
+
+fn push(
+    changes: &mut Vec<SchemaChange>,
+    kind: SchemaChangeKind,
+    criticality: Criticality,
+    path: impl Into<String>,
+    message: String,
+) {
+    changes.push(SchemaChange {
+        kind,
+        criticality,
+        path: path.into(),
+        message,
+    });
+}
+
+fn is_deprecated(directives: &[Positioned<ConstDirective>]) -> bool {
+    directives
+        .iter()
+        .any(|directive| directive.node.name.node == "deprecated")
+}
+
+fn is_required(input_value: &InputValueDefinition) -> bool {
+    !input_value.ty.node.nullable && input_value.default_value.is_none()
+}
+
+/// Returns `true` if a field of type `old` can be changed to `new` without
+/// breaking clients that read it.
+fn is_safe_output_type_change(old: &Type, new: &Type) -> bool {
+    if old.nullable && !new.nullable {
+        let new = Type {
+            base: new.base.clone(),
+            nullable: true,
+        };
+        return is_safe_output_type_change(old, &new);
+    }
+    if old.nullable != new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old), BaseType::Named(new)) => old == new,
+        (BaseType::List(old), BaseType::List(new)) => is_safe_output_type_change(old, new),
+        _ => false,
+    }
+}
+
+/// Returns `true` if an argument or input field of type `old` can be changed
+/// to `new` without breaking clients that provide it.
+fn is_safe_input_type_change(old: &Type, new: &Type) -> bool {
+    if !old.nullable && new.nullable {
+        let new = Type {
+            base: new.base.clone(),
+            nullable: false,
+        };
+        return is_safe_input_type_change(old, &new);
+    }
+    if old.nullable != new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old), BaseType::Named(new)) => old == new,
+        (BaseType::List(old), BaseType::List(new)) => is_safe_input_type_change(old, new),
+        _ => false,
+    }
+}
+
+fn diff_roots(old: &SchemaModel<'_>, new: &SchemaModel<'_>, changes: &mut Vec<SchemaChange>) {
+    for (operation, old_root, new_root) in [
+        ("query", old.query, new.query),
+        ("mutation", old.mutation, new.mutation),
+        ("subscription", old.subscription, new.subscription),
+    ] {
+        match (old_root, new_root) {
+            (Some(old_root), Some(new_root)) if old_root != new_root => push(
+                changes,
+                SchemaChangeKind::RootTypeChanged,
+                Criticality::Breaking,
+                operation,
+                format!(
+                    "The {} root type changed from `{}` to `{}`",
+                    operation, old_root, new_root
+                ),
+            ),
+            (Some(old_root), None) => push(
+                changes,
+                SchemaChangeKind::RootTypeChanged,
+                Criticality::Breaking,
+                operation,
+                format!("The {} root type `{}` was removed", operation, old_root),
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn diff_description(
+    path: &str,
+    old: Option<&str>,
+    new: Option<&str>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    if old != new {
+        push(
+            changes,
+            SchemaChangeKind::DescriptionChanged,
+            Criticality::Safe,
+            path,
+            format!("The description of `{}` changed", path),
+        );
+    }
+}
+
+fn diff_types(old: &SchemaModel<'_>, new: &SchemaModel<'_>, changes: &mut Vec<SchemaChange>) {
+    for (name, old_ty) in &old.types {
+        let new_ty = match new.types.get(name) {
+            Some(new_ty) => new_ty,
+            None => {
+                push(
+                    changes,
+                    SchemaChangeKind::TypeRemoved,
+                    Criticality::Breaking,
+                    *name,
+                    format!("Type `{}` was removed", name),
+                );
+                continue;
+            }
+        };
+
+        if old_ty.kind != new_ty.kind {
+            push(
+                changes,
+                SchemaChangeKind::TypeKindChanged,
+                Criticality::Breaking,
+                *name,
+                format!("`{}` changed from {} to {}", name, old_ty.kind, new_ty.kind),
+            );
+            continue;
+        }
+
+        diff_description(name, old_ty.description, new_ty.description, changes);
+        diff_implements(name, old_ty, new_ty, changes);
+        diff_fields(name, old_ty, new_ty, changes);
+        diff_input_fields(name, old_ty, new_ty, changes);
+        diff_enum_values(name, old_ty, new_ty, changes);
+        diff_union_members(name, old_ty, new_ty, changes);
+    }
+
+    for name in new.types.keys() {
+        if !old.types.contains_key(name) {
+            push(
+                changes,
+                SchemaChangeKind::TypeAdded,
+                Criticality::Safe,
+                *name,
+                format!("Type `{}` was added", name),
+            );
+        }
+    }
+}
+
+fn diff_implements(
+    type_name: &str,
+    old: &TypeModel<'_>,
+    new: &TypeModel<'_>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for interface in &old.implements {
+        if !new.implements.contains(interface) {
+            push(
+                changes,
+                SchemaChangeKind::InterfaceImplementationRemoved,
+                Criticality::Breaking,
+                type_name,
+                format!("`{}` no longer implements `{}`", type_name, interface),
+            );
+        }
+    }
+    for interface in &new.implements {
+        if !old.implements.contains(interface) {
+            push(
+                changes,
+                SchemaChangeKind::InterfaceImplementationAdded,
+                Criticality::Dangerous,
+                type_name,
+                format!("`{}` now implements `{}`", type_name, interface),
+            );
+        }
+    }
+}
+
+fn diff_fields(
+    type_name: &str,
+    old: &TypeModel<'_>,
+    new: &TypeModel<'_>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for (name, old_field) in &old.fields {
+        let path = format!("{}.{}", type_name, name);
+        let new_field = match new.fields.get(name) {
+            Some(new_field) => new_field,
+            None => {
+                push(
+                    changes,
+                    SchemaChangeKind::FieldRemoved,
+                    Criticality::Breaking,
+                    &path,
+                    format!("Field `{}` was removed", path),
+                );
+                continue;
+            }
+        };
+
+        let (old_ty, new_ty) = (&old_field.ty.node, &new_field.ty.node);
+        if old_ty != new_ty {
+            push(
+                changes,
+                SchemaChangeKind::FieldTypeChanged,
+                if is_safe_output_type_change(old_ty, new_ty) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                },
+                &path,
+                format!(
+                    "Field `{}` changed type from `{}` to `{}`",
+                    path, old_ty, new_ty
+                ),
+            );
+        }
+
+        if !is_deprecated(&old_field.directives) && is_deprecated(&new_field.directives) {
+            push(
+                changes,
+                SchemaChangeKind::FieldDeprecated,
+                Criticality::Safe,
+                &path,
+                format!("Field `{}` was deprecated", path),
+            );
+        }
+
+        diff_description(
+            &path,
+            old_field.description.as_ref().map(|s| s.node.as_str()),
+            new_field.description.as_ref().map(|s| s.node.as_str()),
+            changes,
+        );
+        diff_arguments(&path, &old_field.arguments, &new_field.arguments, changes);
+    }
+
+    for name in new.fields.keys() {
+        if !old.fields.contains_key(name) {
+            let path = format!("{}.{}", type_name, name);
+            push(
+                changes,
+                SchemaChangeKind::FieldAdded,
+                Criticality::Safe,
+                &path,
+                format!("Field `{}` was added", path),
+            );
+        }
+    }
+}
+
+fn diff_arguments(
+    field_path: &str,
+    old: &[Positioned<InputValueDefinition>],
+    new: &[Positioned<InputValueDefinition>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_arg in old {
+        let old_arg = &old_arg.node;
+        let path = format!("{}.{}", field_path, old_arg.name.node);
+        let new_arg = match new
+            .iter()
+            .find(|arg| arg.node.name.node == old_arg.name.node)
+        {
+            Some(new_arg) => &new_arg.node,
+            None => {
+                push(
+                    changes,
+                    SchemaChangeKind::ArgumentRemoved,
+                    Criticality::Breaking,
+                    &path,
+                    format!("Argument `{}` was removed", path),
+                );
+                continue;
+            }
+        };
+
+        let (old_ty, new_ty) = (&old_arg.ty.node, &new_arg.ty.node);
+        if old_ty != new_ty {
+            push(
+                changes,
+                SchemaChangeKind::ArgumentTypeChanged,
+                if is_safe_input_type_change(old_ty, new_ty) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                },
+                &path,
+                format!(
+                    "Argument `{}` changed type from `{}` to `{}`",
+                    path, old_ty, new_ty
+                ),
+            );
+        }
+
+        let old_default = old_arg.default_value.as_ref().map(|value| &value.node);
+        let new_default = new_arg.default_value.as_ref().map(|value| &value.node);
+        if old_default.is_some() && old_default != new_default {
+            push(
+                changes,
+                SchemaChangeKind::ArgumentDefaultValueChanged,
+                Criticality::Dangerous,
+                &path,
+                format!("The default value of argument `{}` changed", path),
+            );
+        }
+
+        diff_description(
+            &path,
+            old_arg.description.as_ref().map(|s| s.node.as_str()),
+            new_arg.description.as_ref().map(|s| s.node.as_str()),
+            changes,
+        );
+    }
+
+    for new_arg in new {
+        let new_arg = &new_arg.node;
+        if old
+            .iter()
+            .all(|arg| arg.node.name.node != new_arg.name.node)
+        {
+            let path = format!("{}.{}", field_path, new_arg.name.node);
+            if is_required(new_arg) {
+                push(
+                    changes,
+                    SchemaChangeKind::ArgumentAdded,
+                    Criticality::Breaking,
+                    &path,
+                    format!("Required argument `{}` was added", path),
+                );
+            } else {
+                push(
+                    changes,
+                    SchemaChangeKind::ArgumentAdded,
+                    Criticality::Dangerous,
+                    &path,
+                    format!("Optional argument `{}` was added", path),
+                );
+            }
+        }
+    }
+}
+
+fn diff_input_fields(
+    type_name: &str,
+    old: &TypeModel<'_>,
+    new: &TypeModel<'_>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for (name, old_field) in &old.input_fields {
+        let path = format!("{}.{}", type_name, name);
+        let new_field = match new.input_fields.get(name) {
+            Some(new_field) => new_field,
+            None => {
+                push(
+                    changes,
+                    SchemaChangeKind::InputFieldRemoved,
+                    Criticality::Breaking,
+                    &path,
+                    format!("Input field `{}` was removed", path),
+                );
+                continue;
+            }
+        };
+
+        let (old_ty, new_ty) = (&old_field.ty.node, &new_field.ty.node);
+        if old_ty != new_ty {
+            push(
+                changes,
+                SchemaChangeKind::InputFieldTypeChanged,
+                if is_safe_input_type_change(old_ty, new_ty) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                },
+                &path,
+                format!(
+                    "Input field `{}` changed type from `{}` to `{}`",
+                    path, old_ty, new_ty
+                ),
+            );
+        }
+
+        let old_default = old_field.default_value.as_ref().map(|value| &value.node);
+        let new_default = new_field.default_value.as_ref().map(|value| &value.node);
+        if old_default.is_some() && old_default != new_default {
+            push(
+                changes,
+                SchemaChangeKind::InputFieldDefaultValueChanged,
+                Criticality::Dangerous,
+                &path,
+                format!("The default value of input field `{}` changed", path),
+            );
+        }
+
+        diff_description(
+            &path,
+            old_field.description.as_ref().map(|s| s.node.as_str()),
+            new_field.description.as_ref().map(|s| s.node.as_str()),
+            changes,
+        );
+    }
+
+    for (name, new_field) in &new.input_fields {
+        if !old.input_fields.contains_key(name) {
+            let path = format!("{}.{}", type_name, name);
+            if is_required(new_field) {
+                push(
+                    changes,
+                    SchemaChangeKind::InputFieldAdded,
+                    Criticality::Breaking,
+                    &path,
+                    format!("Required input field `{}` was added", path),
+                );
+            } else {
+                push(
+                    changes,
+                    SchemaChangeKind::InputFieldAdded,
+                    Criticality::Dangerous,
+                    &path,
+                    format!("Optional input field `{}` was added", path),
+                );
+            }
+        }
+    }
+}
+
+fn diff_enum_values(
+    type_name: &str,
+    old: &TypeModel<'_>,
+    new: &TypeModel<'_>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for (name, old_directives) in &old.enum_values {
+        let path = format!("{}.{}", type_name, name);
+        match new.enum_values.get(name) {
+            Some(new_directives) => {
+                if !is_deprecated(old_directives) && is_deprecated(new_directives) {
+                    push(
+                        changes,
+                        SchemaChangeKind::EnumValueDeprecated,
+                        Criticality::Safe,
+                        &path,
+                        format!("Enum value `{}` was deprecated", path),
+                    );
+                }
+            }
+            None => push(
+                changes,
+                SchemaChangeKind::EnumValueRemoved,
+                Criticality::Breaking,
+                &path,
+                format!("Enum value `{}` was removed", path),
+            ),
+        }
+    }
+
+    for name in new.enum_values.keys() {
+        if !old.enum_values.contains_key(name) {
+            let path = format!("{}.{}", type_name, name);
+            push(
+                changes,
+                SchemaChangeKind::EnumValueAdded,
+                Criticality::Dangerous,
+                &path,
+                format!("Enum value `{}` was added", path),
+            );
+        }
+    }
+}
+
+fn diff_union_members(
+    type_name: &str,
+    old: &TypeModel<'_>,
+    new: &TypeModel<'_>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for member in &old.members {
+        if !new.members.contains(member) {
+            push(
+                changes,
+                SchemaChangeKind::UnionMemberRemoved,
+                Criticality::Breaking,
+                type_name,
+                format!("`{}` was removed from union `{}`", member, type_name),
+            );
+        }
+    }
+    for member in &new.members {
+        if !old.members.contains(member) {
+            push(
+                changes,
+                SchemaChangeKind::UnionMemberAdded,
+                Criticality::Dangerous,
+                type_name,
+                format!("`{}` was added to union `{}`", member, type_name),
+            );
+        }
+    }
+}
+
+fn diff_directives(old: &SchemaModel<'_>, new: &SchemaModel<'_>, changes: &mut Vec<SchemaChange>) {
+    for (name, old_directive) in &old.directives {
+        let path = format!("@{}", name);
+        let new_directive = match new.directives.get(name) {
+            Some(new_directive) => new_directive,
+            None => {
+                push(
+                    changes,
+                    SchemaChangeKind::DirectiveRemoved,
+                    Criticality::Breaking,
+                    &path,
+                    format!("Directive `{}` was removed", path),
+                );
+                continue;
+            }
+        };
+
+        if old_directive.is_repeatable != new_directive.is_repeatable {
+            let (criticality, message) = if new_directive.is_repeatable {
+                (
+                    Criticality::Safe,
+                    format!("Directive `{}` became repeatable", path),
+                )
+            } else {
+                (
+                    Criticality::Breaking,
+                    format!("Directive `{}` is no longer repeatable", path),
+                )
+            };
+            push(
+                changes,
+                SchemaChangeKind::DirectiveRepeatableChanged,
+                criticality,
+                &path,
+                message,
+            );
+        }
+
+        for location in &old_directive.locations {
+            if new_directive
+                .locations
+                .iter()
+                .all(|new_location| new_location.node != location.node)
+            {
+                push(
+                    changes,
+                    SchemaChangeKind::DirectiveLocationRemoved,
+                    Criticality::Breaking,
+                    &path,
+                    format!(
+                        "Location `{:?}` was removed from directive `{}`",
+                        location.node, path
+                    ),
+                );
+            }
+        }
+        for location in &new_directive.locations {
+            if old_directive
+                .locations
+                .iter()
+                .all(|old_location| old_location.node != location.node)
+            {
+                push(
+                    changes,
+                    SchemaChangeKind::DirectiveLocationAdded,
+                    Criticality::Safe,
+                    &path,
+                    format!(
+                        "Location `{:?}` was added to directive `{}`",
+                        location.node, path
+                    ),
+                );
+            }
+        }
+
+        for old_arg in &old_directive.arguments {
+            if new_directive
+                .arguments
+                .iter()
+                .all(|arg| arg.node.name.node != old_arg.node.name.node)
+            {
+                let arg_path = format!("{}.{}", path, old_arg.node.name.node);
+                push(
+                    changes,
+                    SchemaChangeKind::DirectiveArgumentRemoved,
+                    Criticality::Breaking,
+                    &arg_path,
+                    format!("Directive argument `{}` was removed", arg_path),
+                );
+            }
+        }
+        for new_arg in &new_directive.arguments {
+            if old_directive
+                .arguments
+                .iter()
+                .all(|arg| arg.node.name.node != new_arg.node.name.node)
+            {
+                let arg_path = format!("{}.{}", path, new_arg.node.name.node);
+                push(
+                    changes,
+                    SchemaChangeKind::DirectiveArgumentAdded,
+                    if is_required(&new_arg.node) {
+                        Criticality::Breaking
+                    } else {
+                        Criticality::Safe
+                    },
+                    &arg_path,
+                    format!("Directive argument `{}` was added", arg_path),
+                );
+            }
+        }
+    }
+
+    for name in new.directives.keys() {
+        if !old.directives.contains_key(name) {
+            let path = format!("@{}", name);
+            push(
+                changes,
+                SchemaChangeKind::DirectiveAdded,
+                Criticality::Safe,
+                &path,
+                format!("Directive `{}` was added", path),
+            );
+        }
+    }
+}
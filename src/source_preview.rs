@@ -0,0 +1,279 @@
+use std::fmt::Write;
+
+use crate::{value, Pos, ServerError};
+
+/// The literals longer than this, in characters, are reported with their end
+/// position and a preview of the source.
+const LARGE_LITERAL_LEN: usize = 80;
+
+/// The maximum number of lines of a source preview.
+const MAX_PREVIEW_LINES: usize = 6;
+
+/// The characters of a query with their positions, computed once to add the
+/// source preview to all the errors of the query.
+pub(crate) struct SourcePreview<'a> {
+    source: &'a str,
+    chars: Vec<(char, Pos)>,
+    line_starts: Vec<usize>,
+    strings: Vec<(usize, usize)>,
+}
+
+impl<'a> SourcePreview<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        let chars = source_chars(source);
+        let line_starts = std::iter::once(0)
+            .chain(
+                chars
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (ch, _))| *ch == '\n')
+                    .map(|(idx, _)| idx + 1),
+            )
+            .collect();
+        let strings = string_spans(&chars);
+        Self {
+            source,
+            chars,
+            line_starts,
+            strings,
+        }
+    }
+
+    /// Adds the end position and a preview of the source to a parse or
+    /// validation error located in a block string, a multi-line literal or a
+    /// large literal, in the `endLocation` and `sourcePreview` extensions.
+    pub(crate) fn add_to(&self, mut err: ServerError) -> ServerError {
+        let chars = &self.chars;
+        let start = match err.locations.first() {
+            Some(start) => *start,
+            None => return err,
+        };
+        let start_idx = match self.index_of(start) {
+            Some(idx) => idx,
+            None => return err,
+        };
+        let (start_idx, end_idx) = match literal_at(chars, start_idx)
+            .or_else(|| argument_value(chars, start_idx))
+            .or_else(|| self.enclosing_string(start_idx))
+        {
+            Some(span) => span,
+            None => return err,
+        };
+        let (literal_start, end) = (chars[start_idx].1, chars[end_idx].1);
+        let is_block_string =
+            chars[start_idx..].iter().take(3).all(|(ch, _)| *ch == '"') && end_idx >= start_idx + 5;
+        if !is_block_string
+            && literal_start.line == end.line
+            && end_idx - start_idx < LARGE_LITERAL_LEN
+        {
+            return err;
+        }
+
+        let preview = source_preview(self.source, start, end);
+        let extensions = err.extensions.get_or_insert_with(Default::default);
+        extensions.set(
+            "endLocation",
+            value!({ "line": end.line, "column": end.column }),
+        );
+        extensions.set("sourcePreview", preview);
+        err
+    }
+
+    /// Returns the index of the character at `pos`.
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        let line_start = *self.line_starts.get(pos.line.checked_sub(1)?)?;
+        self.chars[line_start..]
+            .iter()
+            .take_while(|(_, char_pos)| char_pos.line == pos.line)
+            .position(|(_, char_pos)| *char_pos == pos)
+            .map(|n| line_start + n)
+    }
+
+    /// Returns the string or block string containing the character at `idx`.
+    fn enclosing_string(&self, idx: usize) -> Option<(usize, usize)> {
+        let n = self.strings.partition_point(|(start, _)| *start < idx);
+        self.strings[..n]
+            .last()
+            .copied()
+            .filter(|(_, end)| *end >= idx)
+    }
+}
+
+/// Returns the characters of the source with their positions, counted like
+/// the parser does.
+fn source_chars(source: &str) -> Vec<(char, Pos)> {
+    let mut pos = Pos { line: 1, column: 1 };
+    source
+        .chars()
+        .map(|ch| {
+            let item = (ch, pos);
+            match ch {
+                '\r' => pos.column = 1,
+                '\n' => {
+                    pos.line += 1;
+                    pos.column = 1;
+                }
+                _ => pos.column += 1,
+            }
+            item
+        })
+        .collect()
+}
+
+/// Returns the first and last characters of the strings and block strings of
+/// the source, in order.
+fn string_spans(chars: &[(char, Pos)]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i].0 {
+            '"' => {
+                // an unterminated string extends to the end of the source
+                let end = string_end(chars, i).unwrap_or(chars.len() - 1);
+                spans.push((i, end));
+                i = end;
+            }
+            '#' => i = line_end(chars, i),
+            _ => {}
+        }
+        i += 1;
+    }
+    spans
+}
+
+/// Returns the index of the last character of the string, the block string,
+/// the list or the object starting at `idx`.
+fn literal_at(chars: &[(char, Pos)], idx: usize) -> Option<(usize, usize)> {
+    match chars[idx].0 {
+        '"' => string_end(chars, idx).map(|end| (idx, end)),
+        '[' | '{' => {
+            let mut depth = 0usize;
+            let mut i = idx;
+            while i < chars.len() {
+                match chars[i].0 {
+                    '[' | '{' => depth += 1,
+                    ']' | '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some((idx, i));
+                        }
+                    }
+                    '"' => i = string_end(chars, i)?,
+                    '#' => i = line_end(chars, i),
+                    _ => {}
+                }
+                i += 1;
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Returns the value of the argument or the input field whose name starts at
+/// `idx`, such as `text: "..."`, since the validation errors are located at the
+/// names.
+fn argument_value(chars: &[(char, Pos)], idx: usize) -> Option<(usize, usize)> {
+    let is_name = |ch: char| ch.is_ascii_alphanumeric() || ch == '_';
+    let mut i = idx;
+    while i < chars.len() && is_name(chars[i].0) {
+        i += 1;
+    }
+    if i == idx {
+        return None;
+    }
+    let skip_ignored = |mut i: usize| {
+        while i < chars.len() && (chars[i].0.is_whitespace() || chars[i].0 == ',') {
+            i += 1;
+        }
+        i
+    };
+    i = skip_ignored(i);
+    if chars.get(i)?.0 != ':' {
+        return None;
+    }
+    i = skip_ignored(i + 1);
+    if i == chars.len() {
+        return None;
+    }
+    literal_at(chars, i)
+}
+
+/// Returns the index of the closing quote of the string or block string
+/// starting at `idx`.
+fn string_end(chars: &[(char, Pos)], idx: usize) -> Option<usize> {
+    let is_quote = |i: usize| chars.get(i).map(|(ch, _)| *ch) == Some('"');
+    if is_quote(idx + 1) && is_quote(idx + 2) {
+        let mut i = idx + 3;
+        while i < chars.len() {
+            match chars[i].0 {
+                '\\' if is_quote(i + 1) && is_quote(i + 2) && is_quote(i + 3) => i += 3,
+                '"' if is_quote(i + 1) && is_quote(i + 2) => return Some(i + 2),
+                _ => {}
+            }
+            i += 1;
+        }
+        return None;
+    }
+
+    let mut i = idx + 1;
+    while i < chars.len() {
+        match chars[i].0 {
+            '\\' => i += 1,
+            '"' => return Some(i),
+            '\n' | '\r' => return None,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn line_end(chars: &[(char, Pos)], idx: usize) -> usize {
+    chars[idx..]
+        .iter()
+        .position(|(ch, _)| matches!(ch, '\n' | '\r'))
+        .map_or(chars.len(), |n| idx + n)
+}
+
+/// Returns the lines of the source from `start` to `end`, with a caret under
+/// both positions.
+fn source_preview(source: &str, start: Pos, end: Pos) -> String {
+    let lines = source.lines().collect::<Vec<_>>();
+    let gutter = end.line.to_string().len();
+    let caret = |preview: &mut String, from: usize, to: usize| {
+        let _ = writeln!(
+            preview,
+            "{:gutter$} | {}{}",
+            "",
+            " ".repeat(from - 1),
+            "^".repeat(to + 1 - from),
+            gutter = gutter
+        );
+    };
+
+    let mut preview = String::new();
+    for line in start.line..=end.line {
+        let elided = end.line - start.line + 1 > MAX_PREVIEW_LINES
+            && line > start.line + MAX_PREVIEW_LINES / 2 - 1
+            && line < end.line - (MAX_PREVIEW_LINES / 2 - 1);
+        if elided {
+            if line == start.line + MAX_PREVIEW_LINES / 2 {
+                let _ = writeln!(preview, "{:gutter$} | ...", "", gutter = gutter);
+            }
+            continue;
+        }
+
+        let text = lines.get(line - 1).copied().unwrap_or_default();
+        let _ = writeln!(preview, "{:gutter$} | {}", line, text, gutter = gutter);
+        if start.line == end.line {
+            caret(&mut preview, start.column, end.column);
+        } else if line == start.line {
+            caret(&mut preview, start.column, start.column);
+        } else if line == end.line {
+            caret(&mut preview, end.column, end.column);
+        }
+    }
+    preview.pop();
+    preview
+}
@@ -0,0 +1,52 @@
+use std::{fmt, marker::PhantomData};
+
+use crate::Request;
+
+/// A query document which is checked against the SDL of the schema `S` at
+/// compile time, created by the [`graphql_query!`](crate::graphql_query)
+/// macro.
+pub struct StaticQuery<S> {
+    query: &'static str,
+    _mark: PhantomData<fn() -> S>,
+}
+
+impl<S> StaticQuery<S> {
+    #[doc(hidden)]
+    pub const fn new(query: &'static str) -> Self {
+        Self {
+            query,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Returns the query document.
+    pub const fn as_str(&self) -> &'static str {
+        self.query
+    }
+}
+
+impl<S> Clone for StaticQuery<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for StaticQuery<S> {}
+
+impl<S> fmt::Debug for StaticQuery<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StaticQuery").field(&self.query).finish()
+    }
+}
+
+impl<S> fmt::Display for StaticQuery<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.query)
+    }
+}
+
+impl<S> From<StaticQuery<S>> for Request {
+    fn from(query: StaticQuery<S>) -> Self {
+        Request::new(query.query)
+    }
+}
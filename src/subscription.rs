@@ -1,10 +1,19 @@
-use std::{borrow::Cow, pin::Pin};
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context as TaskContext, Poll},
+};
 
-use futures_util::stream::{Stream, StreamExt};
+use futures_util::{
+    future::{self, Ready},
+    stream::{self, Chain, Once, Stream, StreamExt},
+};
 
 use crate::{
-    parser::types::Selection, registry, registry::Registry, Context, ContextSelectionSet,
-    PathSegment, Response, ServerError, ServerResult,
+    parser::types::Selection, registry, registry::Registry, Context, ContextSelectionSet, Error,
+    OutputType, PathSegment, Response, ServerError, ServerResult,
 };
 
 /// A GraphQL subscription object
@@ -33,6 +42,25 @@ pub trait SubscriptionType: Send + Sync {
     ) -> Option<Pin<Box<dyn Stream<Item = Response> + Send + 'a>>>;
 }
 
+/// An item of a fallible subscription stream.
+///
+/// Unlike the `OutputType` implementation of `Result`, the error type doesn't
+/// need to implement `Clone`.
+#[doc(hidden)]
+pub trait SubscriptionItemResult: Send {
+    type Item: OutputType;
+
+    fn into_result(self) -> Result<Self::Item, Error>;
+}
+
+impl<T: OutputType, E: Into<Error> + Send> SubscriptionItemResult for Result<T, E> {
+    type Item = T;
+
+    fn into_result(self) -> Result<Self::Item, Error> {
+        self.map_err(Into::into)
+    }
+}
+
 pub(crate) type BoxFieldStream<'a> = Pin<Box<dyn Stream<Item = Response> + 'a + Send>>;
 
 pub(crate) fn collect_subscription_streams<'a, T: SubscriptionType + 'static>(
@@ -80,3 +108,176 @@ impl<T: SubscriptionType> SubscriptionType for &T {
         T::create_field_stream(*self, ctx)
     }
 }
+
+/// A buffer that records the events of a subscription, so they can be replayed
+/// to late subscribers with [`SubscriptionStreamExt::with_replay`].
+pub trait ReplayBuffer<T>: Send + Sync {
+    /// Records an event.
+    fn push(&self, event: T);
+
+    /// Returns the recorded events, from the oldest to the newest.
+    fn events(&self) -> Vec<T>;
+}
+
+/// Memory-based replay buffer that keeps the last `capacity` events.
+pub struct MemoryReplayBuffer<T> {
+    capacity: usize,
+    events: Arc<Mutex<VecDeque<T>>>,
+}
+
+impl<T> Clone for MemoryReplayBuffer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            events: self.events.clone(),
+        }
+    }
+}
+
+impl<T> MemoryReplayBuffer<T> {
+    /// Creates a new replay buffer that keeps at most `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            events: Default::default(),
+        }
+    }
+}
+
+impl<T: Clone + Send> ReplayBuffer<T> for MemoryReplayBuffer<T> {
+    fn push(&self, event: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut events = self.events.lock().unwrap();
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    fn events(&self) -> Vec<T> {
+        self.events.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// An extension trait for the streams returned by subscription resolvers.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use futures_util::stream::{self, Stream, StreamExt};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct Subscription {
+///     messages: MemoryReplayBuffer<String>,
+/// }
+///
+/// #[Subscription]
+/// impl Subscription {
+///     async fn online_users(&self) -> impl Stream<Item = i32> {
+///         // the current number of online users, then the updates
+///         stream::iter(vec![4, 5]).with_initial_value(3)
+///     }
+///
+///     async fn messages(&self) -> impl Stream<Item = String> {
+///         stream::iter(vec!["c".to_string()]).with_replay(&self.messages)
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let messages = MemoryReplayBuffer::new(2);
+/// for message in ["a", "b"] {
+///     messages.push(message.to_string());
+/// }
+/// let schema = Schema::new(Query, EmptyMutation, Subscription { messages });
+///
+/// let stream = schema.execute_stream("subscription { onlineUsers }");
+/// let values = stream.map(|resp| resp.data).collect::<Vec<_>>().await;
+/// assert_eq!(
+///     values,
+///     vec![
+///         value!({ "onlineUsers": 3 }),
+///         value!({ "onlineUsers": 4 }),
+///         value!({ "onlineUsers": 5 }),
+///     ]
+/// );
+///
+/// let stream = schema.execute_stream("subscription { messages }");
+/// let values = stream.map(|resp| resp.data).collect::<Vec<_>>().await;
+/// assert_eq!(
+///     values,
+///     vec![
+///         value!({ "messages": "a" }),
+///         value!({ "messages": "b" }),
+///         value!({ "messages": "c" }),
+///     ]
+/// );
+/// # });
+/// ```
+pub trait SubscriptionStreamExt: Stream + Sized {
+    /// Emits `value` before the items of the stream, for example the current
+    /// state before the updates.
+    fn with_initial_value(self, value: Self::Item) -> Chain<Once<Ready<Self::Item>>, Self> {
+        stream::once(future::ready(value)).chain(self)
+    }
+
+    /// Emits the events recorded in `buffer` before the items of the stream.
+    ///
+    /// The stream is polled once before the recorded events are read, so the
+    /// streams that subscribe to their source when they are first polled do
+    /// not miss the events recorded in between. An event recorded after the
+    /// subscription but before the events are read is emitted twice.
+    fn with_replay<B: ReplayBuffer<Self::Item> + Clone>(self, buffer: &B) -> Replay<Self, B> {
+        Replay {
+            stream: self,
+            buffer: Some(buffer.clone()),
+            events: Vec::new().into_iter(),
+            first: None,
+        }
+    }
+}
+
+impl<T: Stream> SubscriptionStreamExt for T {}
+
+pin_project_lite::pin_project! {
+    /// Stream for the [`SubscriptionStreamExt::with_replay`] method.
+    pub struct Replay<S: Stream, B> {
+        #[pin]
+        stream: S,
+        buffer: Option<B>,
+        events: std::vec::IntoIter<S::Item>,
+        first: Option<Option<S::Item>>,
+    }
+}
+
+impl<S: Stream, B: ReplayBuffer<S::Item>> Stream for Replay<S, B> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if let Some(buffer) = this.buffer.take() {
+            if let Poll::Ready(item) = this.stream.as_mut().poll_next(cx) {
+                *this.first = Some(item);
+            }
+            *this.events = buffer.events().into_iter();
+        }
+
+        if let Some(event) = this.events.next() {
+            return Poll::Ready(Some(event));
+        }
+        if let Some(item) = this.first.take() {
+            return Poll::Ready(item);
+        }
+        this.stream.poll_next(cx)
+    }
+}
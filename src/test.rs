@@ -0,0 +1,286 @@
+//! Helpers for testing schemas.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::{test::TestRequest, *};
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn add(&self, a: i32, b: i32) -> i32 {
+//!         a + b
+//!     }
+//!
+//!     async fn fail(&self) -> Result<i32> {
+//!         Err(Error::new("failed").extend_with(|_, e| e.set("code", "FAILED")))
+//!     }
+//! }
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+//! let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+//!
+//! let resp = TestRequest::new(&schema)
+//!     .query("query ($a: Int!) { add(a: $a, b: 2) }")
+//!     .variables(serde_json::json!({ "a": 1 }))
+//!     .execute()
+//!     .await;
+//! assert_data_eq!(resp, { "add": 3 });
+//!
+//! let resp = TestRequest::new(&schema).query("{ fail }").execute().await;
+//! assert_error_code!(resp, "FAILED");
+//! # });
+//! ```
+
+use std::{any::Any, sync::Arc, time::Duration};
+
+use futures_util::{
+    future::{self, Either},
+    stream::{BoxStream, StreamExt},
+};
+use http::header::{HeaderMap, HeaderValue, IntoHeaderName};
+
+use crate::{
+    timer::{default_timer, delay},
+    Executor, Request, Response, ServerError, Timer, Value, Variables,
+};
+
+/// A fluent builder of the requests to execute in tests.
+///
+/// The headers added with [`TestRequest::header`] are available in the
+/// resolvers as a [`HeaderMap`] in the request data.
+pub struct TestRequest<'a, E> {
+    executor: &'a E,
+    request: Request,
+    headers: HeaderMap,
+}
+
+impl<'a, E: Executor> TestRequest<'a, E> {
+    /// Create a request that is executed with `executor`, usually a
+    /// [`Schema`](crate::Schema).
+    pub fn new(executor: &'a E) -> Self {
+        Self {
+            executor,
+            request: Request::new(""),
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Set the query source of the request.
+    #[must_use]
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.request.query = query.into();
+        self
+    }
+
+    /// Specify the operation name of the request.
+    #[must_use]
+    pub fn operation_name(self, name: impl Into<String>) -> Self {
+        Self {
+            request: self.request.operation_name(name),
+            ..self
+        }
+    }
+
+    /// Specify the variables of the request as a JSON value.
+    #[must_use]
+    pub fn variables(self, variables: serde_json::Value) -> Self {
+        Self {
+            request: self.request.variables(Variables::from_json(variables)),
+            ..self
+        }
+    }
+
+    /// Append an HTTP header to the request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a valid header value.
+    #[must_use]
+    pub fn header<V>(mut self, key: impl IntoHeaderName, value: V) -> Self
+    where
+        V: TryInto<HeaderValue>,
+        V::Error: std::fmt::Debug,
+    {
+        self.headers
+            .append(key, value.try_into().expect("invalid header value"));
+        self
+    }
+
+    /// Insert some data for the request.
+    #[must_use]
+    pub fn data<D: Any + Send + Sync>(self, data: D) -> Self {
+        Self {
+            request: self.request.data(data),
+            ..self
+        }
+    }
+
+    /// Returns the built request.
+    pub fn into_request(self) -> Request {
+        let mut request = self.request;
+        if !self.headers.is_empty() {
+            request = request.data(self.headers);
+        }
+        request
+    }
+
+    /// Execute the request.
+    pub async fn execute(self) -> Response {
+        let executor = self.executor;
+        executor.execute(self.into_request()).await
+    }
+
+    /// Execute the request as a subscription.
+    pub fn execute_stream(self) -> TestSubscription {
+        let executor = self.executor;
+        TestSubscription::new(executor.execute_stream(self.into_request(), None))
+    }
+}
+
+/// A subscription stream in tests.
+///
+/// Waiting for a response panics if the stream ends or if no response is
+/// received before the timeout, which is 5 seconds by default. The timeout
+/// uses [`FuturesTimer`](crate::FuturesTimer) by default.
+pub struct TestSubscription {
+    stream: BoxStream<'static, Response>,
+    timeout: Duration,
+    timer: Option<Arc<dyn Timer>>,
+}
+
+impl TestSubscription {
+    /// Create a `TestSubscription` from a stream of responses.
+    pub fn new(stream: BoxStream<'static, Response>) -> Self {
+        Self {
+            stream,
+            timeout: Duration::from_secs(5),
+            timer: default_timer(),
+        }
+    }
+
+    /// Set the timeout of waiting for a response.
+    #[must_use]
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Set the timer of the timeout.
+    #[must_use]
+    pub fn timer(self, timer: impl Timer) -> Self {
+        Self {
+            timer: Some(Arc::new(timer)),
+            ..self
+        }
+    }
+
+    /// Wait for the next response.
+    pub async fn next_response(&mut self) -> Response {
+        match future::select(
+            self.stream.next(),
+            delay(self.timer.as_deref(), self.timeout),
+        )
+        .await
+        {
+            Either::Left((Some(resp), _)) => resp,
+            Either::Left((None, _)) => panic!("the subscription stream has ended"),
+            Either::Right(_) => panic!("timed out waiting for a subscription response"),
+        }
+    }
+
+    /// Wait for the next response, and returns its data.
+    ///
+    /// Panics if the response contains errors.
+    pub async fn next_data(&mut self) -> Value {
+        let resp = self.next_response().await;
+        assert_no_errors(&resp.errors);
+        resp.data
+    }
+
+    /// Asserts that the stream ends before the timeout.
+    pub async fn assert_ended(&mut self) {
+        match future::select(
+            self.stream.next(),
+            delay(self.timer.as_deref(), self.timeout),
+        )
+        .await
+        {
+            Either::Left((Some(resp), _)) => panic!(
+                "expected the subscription stream to end, but got a response:\n{}",
+                pretty(&resp)
+            ),
+            Either::Left((None, _)) => {}
+            Either::Right(_) => panic!("timed out waiting for the subscription stream to end"),
+        }
+    }
+}
+
+/// Asserts that the response has no errors and its data is equal to the
+/// expected value.
+///
+/// This function is used by the [`assert_data_eq!`](crate::assert_data_eq)
+/// macro.
+#[track_caller]
+pub fn assert_data_eq(response: &Response, expected: Value) {
+    assert_no_errors(&response.errors);
+    if response.data != expected {
+        panic!(
+            "assertion failed: `(left == right)`\n  left: {}\n right: {}",
+            pretty(&response.data),
+            pretty(&expected)
+        );
+    }
+}
+
+/// Asserts that the response contains an error with the `code` extension.
+///
+/// This function is used by the
+/// [`assert_error_code!`](crate::assert_error_code) macro.
+#[track_caller]
+pub fn assert_error_code(response: &Response, code: &str) {
+    let found = response.errors.iter().any(|err| {
+        err.extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get("code"))
+            == Some(&Value::String(code.to_string()))
+    });
+    if !found {
+        panic!(
+            "expected an error with the code `{}`, found errors: {}",
+            code,
+            pretty(&response.errors)
+        );
+    }
+}
+
+#[track_caller]
+fn assert_no_errors(errors: &[ServerError]) {
+    if !errors.is_empty() {
+        panic!("unexpected errors: {}", pretty(&errors));
+    }
+}
+
+fn pretty(value: &impl serde::Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+/// Asserts that the response has no errors and its data is equal to the
+/// expected value, which is written like the [`value!`](crate::value) macro.
+///
+/// The values are printed as pretty JSON on failure.
+#[macro_export]
+macro_rules! assert_data_eq {
+    ($response:expr, $($expected:tt)+) => {
+        $crate::test::assert_data_eq(&$response, $crate::value!($($expected)+))
+    };
+}
+
+/// Asserts that the response contains an error with the given `code`
+/// extension.
+#[macro_export]
+macro_rules! assert_error_code {
+    ($response:expr, $code:expr) => {
+        $crate::test::assert_error_code(&$response, $code)
+    };
+}
@@ -0,0 +1,64 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
+use futures_util::future::BoxFuture;
+
+/// A timer, which is used for the timeouts of the requests and the fields,
+/// the delays of the retries, the expiry of the WebSocket connections and the
+/// heartbeats of the multipart subscriptions.
+///
+/// A function returning a future, such as `tokio::time::sleep`, is also a
+/// timer.
+///
+/// By default, the timer of the `futures-timer` crate is used if the
+/// `futures-timer` feature is enabled. It runs in a separate thread, so on
+/// WebAssembly, enable the `wasm-bindgen` feature of `futures-timer` or
+/// replace it with [`SchemaBuilder::timer`](crate::SchemaBuilder::timer).
+pub trait Timer: Send + Sync + 'static {
+    /// Returns a future that completes after `duration`.
+    fn delay(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+impl<F, R> Timer for F
+where
+    F: Fn(Duration) -> R + Send + Sync + 'static,
+    R: Future<Output = ()> + Send + 'static,
+{
+    fn delay(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(self(duration))
+    }
+}
+
+/// The timer of the `futures-timer` crate.
+#[cfg(feature = "futures-timer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-timer")))]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct FuturesTimer;
+
+#[cfg(feature = "futures-timer")]
+impl Timer for FuturesTimer {
+    fn delay(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(futures_timer::Delay::new(duration))
+    }
+}
+
+/// Returns the timer used when none is specified, which is `None` if the
+/// `futures-timer` feature is disabled.
+pub(crate) fn default_timer() -> Option<Arc<dyn Timer>> {
+    #[cfg(feature = "futures-timer")]
+    {
+        Some(Arc::new(FuturesTimer))
+    }
+    #[cfg(not(feature = "futures-timer"))]
+    {
+        None
+    }
+}
+
+/// Returns a future that completes after `duration` with `timer`, or never
+/// if there is no timer.
+pub(crate) fn delay(timer: Option<&dyn Timer>, duration: Duration) -> BoxFuture<'static, ()> {
+    match timer {
+        Some(timer) => timer.delay(duration),
+        None => Box::pin(futures_util::future::pending()),
+    }
+}
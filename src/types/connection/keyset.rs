@@ -0,0 +1,197 @@
+use serde::Serialize;
+
+use super::{
+    Connection, ConnectionNameType, CursorType, Edge, EdgeNameType, EmptyFields,
+    NodesFieldSwitcherSealed, OpaqueCursor,
+};
+use crate::{to_value, OutputType, Value};
+
+/// Keyset pagination over the sort keys of a SQL query, such as
+/// `(created_at, id)`.
+///
+/// The cursors are the values of the sort keys of the rows, encoded with
+/// [`OpaqueCursor`], so a page is selected with a `WHERE (created_at, id) >
+/// (?, ?)` predicate instead of an offset. The sort keys must be sorted in
+/// ascending order and identify a row, typically by ending with the primary
+/// key.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{connection::*, *};
+///
+/// let after = Some(OpaqueCursor((10, 2)));
+/// let keyset = Keyset::new(["created_at", "id"], after, None, Some(20), None);
+/// let sql = format!(
+///     "SELECT created_at, id, name FROM users WHERE {} ORDER BY {} LIMIT {}",
+///     keyset.where_clause().unwrap(),
+///     keyset.order_by(),
+///     keyset.limit().unwrap(),
+/// );
+/// assert_eq!(
+///     sql,
+///     "SELECT created_at, id, name FROM users WHERE (created_at, id) > (?, ?) ORDER BY created_at ASC, id ASC LIMIT 21"
+/// );
+/// // Bind the parameters, and build the connection from the fetched rows.
+/// assert_eq!(keyset.params(), vec![value!(10), value!(2)]);
+/// let rows = vec![(11, 1, "a".to_string())];
+/// let connection: Connection<_, String> =
+///     keyset.into_connection(rows, |(created_at, id, _)| (*created_at, *id), |(_, _, name)| name);
+/// assert!(connection.has_previous_page);
+/// assert!(!connection.has_next_page);
+/// ```
+pub struct Keyset<K> {
+    columns: Vec<String>,
+    placeholder: fn(usize) -> String,
+    after: Option<K>,
+    before: Option<K>,
+    first: Option<usize>,
+    last: Option<usize>,
+}
+
+impl<K> Keyset<K> {
+    /// Create a keyset pagination over the specified columns, with the
+    /// arguments parsed by [`query`](super::query).
+    pub fn new(
+        columns: impl IntoIterator<Item = impl Into<String>>,
+        after: Option<OpaqueCursor<K>>,
+        before: Option<OpaqueCursor<K>>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Self {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            placeholder: |_| "?".to_string(),
+            after: after.map(|cursor| cursor.0),
+            before: before.map(|cursor| cursor.0),
+            first,
+            last,
+        }
+    }
+
+    /// Set the function returning the placeholder of the parameter at the
+    /// specified 1-based index, such as `|n| format!("${}", n)` for
+    /// PostgreSQL. Defaults to `?`.
+    #[must_use]
+    pub fn placeholder(self, placeholder: fn(usize) -> String) -> Self {
+        Self {
+            placeholder,
+            ..self
+        }
+    }
+
+    /// Returns the predicate selecting the rows between the `after` and
+    /// `before` cursors, or `None` if there are no cursors.
+    ///
+    /// The parameters of the predicate are returned by [`Keyset::params`].
+    pub fn where_clause(&self) -> Option<String> {
+        let columns = format!("({})", self.columns.join(", "));
+        let mut index = 0;
+        let mut values = || {
+            let placeholders = (0..self.columns.len())
+                .map(|_| {
+                    index += 1;
+                    (self.placeholder)(index)
+                })
+                .collect::<Vec<_>>();
+            format!("({})", placeholders.join(", "))
+        };
+        let mut predicates = Vec::new();
+        if self.after.is_some() {
+            predicates.push(format!("{} > {}", columns, values()));
+        }
+        if self.before.is_some() {
+            predicates.push(format!("{} < {}", columns, values()));
+        }
+        (!predicates.is_empty()).then(|| predicates.join(" AND "))
+    }
+
+    /// Returns the values to bind to the parameters of
+    /// [`Keyset::where_clause`], one for each placeholder, in order.
+    ///
+    /// The sort keys of a cursor are split into the values of the columns,
+    /// the items of a tuple for example.
+    pub fn params(&self) -> Vec<Value>
+    where
+        K: Serialize,
+    {
+        self.after
+            .iter()
+            .chain(&self.before)
+            .flat_map(|key| match to_value(key).unwrap_or_default() {
+                Value::List(values) if self.columns.len() > 1 => values,
+                value => vec![value],
+            })
+            .collect()
+    }
+
+    /// Returns the `ORDER BY` clause of the query.
+    ///
+    /// The rows are sorted in descending order when the `last` parameter is
+    /// used, to fetch the end of the range.
+    pub fn order_by(&self) -> String {
+        let direction = if self.last.is_some() { "DESC" } else { "ASC" };
+        self.columns
+            .iter()
+            .map(|column| format!("{} {}", column, direction))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Returns the `LIMIT` of the query, which is one more than the page size
+    /// to know if there are more rows, or `None` to fetch all the rows.
+    pub fn limit(&self) -> Option<usize> {
+        self.first.or(self.last).map(|page_size| page_size + 1)
+    }
+
+    /// Returns the predicate of [`Keyset::where_clause`] as a closure, to
+    /// paginate rows that are not fetched with SQL.
+    pub fn filter(&self) -> impl Fn(&K) -> bool + '_
+    where
+        K: PartialOrd,
+    {
+        move |key| {
+            self.after.as_ref().map_or(true, |after| key > after)
+                && self.before.as_ref().map_or(true, |before| key < before)
+        }
+    }
+
+    /// Builds the connection from the rows fetched with
+    /// [`Keyset::where_clause`], [`Keyset::order_by`] and [`Keyset::limit`].
+    ///
+    /// `key` returns the sort keys of a row, used as the cursor of its edge,
+    /// and `node` converts a row to the node of its edge.
+    pub fn into_connection<Row, Node, Name, EdgeName, NodesField>(
+        self,
+        mut rows: Vec<Row>,
+        key: impl Fn(&Row) -> K,
+        node: impl Fn(Row) -> Node,
+    ) -> Connection<OpaqueCursor<K>, Node, EmptyFields, EmptyFields, Name, EdgeName, NodesField>
+    where
+        OpaqueCursor<K>: CursorType + Send + Sync,
+        Node: OutputType,
+        Name: ConnectionNameType,
+        EdgeName: EdgeNameType,
+        NodesField: NodesFieldSwitcherSealed,
+    {
+        let page_size = self.first.or(self.last);
+        let has_more = page_size.map_or(false, |page_size| rows.len() > page_size);
+        if let Some(page_size) = page_size {
+            rows.truncate(page_size);
+        }
+
+        let (has_previous_page, has_next_page) = if self.last.is_some() {
+            rows.reverse();
+            (has_more, self.before.is_some())
+        } else {
+            (self.after.is_some(), has_more)
+        };
+
+        let mut connection = Connection::new(has_previous_page, has_next_page);
+        connection.edges.extend(
+            rows.into_iter()
+                .map(|row| Edge::new(OpaqueCursor(key(&row)), node(row))),
+        );
+        connection
+    }
+}
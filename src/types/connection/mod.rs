@@ -3,6 +3,7 @@
 mod connection_type;
 mod cursor;
 mod edge;
+mod keyset;
 mod page_info;
 
 use std::{fmt::Display, future::Future};
@@ -10,6 +11,7 @@ use std::{fmt::Display, future::Future};
 pub use connection_type::Connection;
 pub use cursor::{CursorType, OpaqueCursor};
 pub use edge::Edge;
+pub use keyset::Keyset;
 pub use page_info::PageInfo;
 
 use crate::{Error, ObjectType, OutputType, Result, SimpleObject};
@@ -258,7 +260,7 @@ where
 /// # Examples
 ///
 /// ```rust
-/// 
+///
 /// use async_graphql::*;
 /// use async_graphql::types::connection::*;
 ///
@@ -352,25 +354,91 @@ where
     R: Future<Output = Result<T, E>>,
     E: Into<Error>,
 {
-    if first.is_some() && last.is_some() {
-        return Err("The \"first\" and \"last\" parameters cannot exist at the same time".into());
+    query_with_limits(after, before, first, last, PaginationLimits::default(), f).await
+}
+
+/// The limits of the pagination arguments checked by [`query_with_limits`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PaginationLimits {
+    max_page_size: Option<usize>,
+}
+
+impl PaginationLimits {
+    /// Create limits that only check that `first` and `last` are not both
+    /// set and are non-negative.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    let first = match first {
-        Some(first) if first < 0 => {
-            return Err("The \"first\" parameter must be a non-negative number".into());
+    /// Set the maximum value of the `first` and `last` parameters.
+    #[must_use]
+    pub fn max_page_size(self, max_page_size: usize) -> Self {
+        Self {
+            max_page_size: Some(max_page_size),
         }
-        Some(first) => Some(first as usize),
-        None => None,
-    };
+    }
+}
 
-    let last = match last {
-        Some(last) if last < 0 => {
-            return Err("The \"last\" parameter must be a non-negative number".into());
-        }
-        Some(last) => Some(last as usize),
-        None => None,
-    };
+/// Same as [`query_with`], but also checks the pagination arguments against
+/// the specified limits.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{connection::*, *};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn numbers(
+///         &self,
+///         after: Option<String>,
+///         before: Option<String>,
+///         first: Option<i32>,
+///         last: Option<i32>,
+///     ) -> Result<Connection<usize, i32>> {
+///         let limits = PaginationLimits::new().max_page_size(100);
+///         query_with_limits(after, before, first, last, limits, |after: Option<usize>, before, first, last| async move {
+///             let start = after.map(|after| after + 1).unwrap_or(0);
+///             let end = start + first.unwrap_or(100);
+///             let mut connection = Connection::new(start > 0, true);
+///             connection.edges.extend((start..end).map(|n| Edge::new(n, n as i32)));
+///             Ok::<_, Error>(connection)
+///         }).await
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let resp = schema.execute("{ numbers(first: 1000) { edges { node } } }").await;
+/// assert_eq!(
+///     resp.errors[0].message,
+///     r#"The "first" parameter must not be greater than 100"#
+/// );
+/// # });
+/// ```
+pub async fn query_with_limits<Cursor, T, F, R, E>(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    limits: PaginationLimits,
+    f: F,
+) -> Result<T>
+where
+    Cursor: CursorType + Send + Sync,
+    <Cursor as CursorType>::Error: Display + Send + Sync + 'static,
+    F: FnOnce(Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>) -> R,
+    R: Future<Output = Result<T, E>>,
+    E: Into<Error>,
+{
+    if first.is_some() && last.is_some() {
+        return Err("The \"first\" and \"last\" parameters cannot exist at the same time".into());
+    }
+
+    let first = check_page_size("first", first, &limits)?;
+    let last = check_page_size("last", last, &limits)?;
 
     let before = match before {
         Some(before) => Some(Cursor::decode_cursor(&before)?),
@@ -384,3 +452,25 @@ where
 
     f(after, before, first, last).await.map_err(Into::into)
 }
+
+fn check_page_size(
+    name: &str,
+    page_size: Option<i32>,
+    limits: &PaginationLimits,
+) -> Result<Option<usize>> {
+    let page_size = match page_size {
+        Some(page_size) if page_size < 0 => {
+            return Err(format!("The \"{}\" parameter must be a non-negative number", name).into());
+        }
+        Some(page_size) => page_size as usize,
+        None => return Ok(None),
+    };
+    match limits.max_page_size {
+        Some(max_page_size) if page_size > max_page_size => Err(format!(
+            "The \"{}\" parameter must not be greater than {}",
+            name, max_page_size
+        )
+        .into()),
+        _ => Ok(Some(page_size)),
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::{
+    parser::types::Field,
+    registry,
+    resolver_utils::{enum_value, EnumType},
+    ContextSelectionSet, InputType, InputValueError, InputValueResult, OutputType, PathSegment,
+    Positioned, ServerResult, Value,
+};
+
+/// A set of flags of the enum `T`, such as the flags of a protobuf or FFI
+/// enum.
+///
+/// The set is a bit mask of the integer values of the variants, which must be
+/// distinct powers of two, with `#[graphql(int_values)]` on the enum. It is a
+/// list of the enum values in GraphQL.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+/// #[graphql(int_values)]
+/// enum Permission {
+///     Read = 1,
+///     Write = 2,
+///     Delete = 4,
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn permissions(&self, mask: u64) -> EnumFlags<Permission> {
+///         EnumFlags::from_bits(mask)
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let res = schema.execute("{ permissions(mask: 5) }").await.into_result().unwrap().data;
+/// assert_eq!(res, value!({ "permissions": ["READ", "DELETE"] }));
+/// # });
+/// ```
+pub struct EnumFlags<T> {
+    bits: u64,
+    _marker: PhantomData<T>,
+}
+
+impl<T: EnumType> EnumFlags<T> {
+    /// Create an empty set of flags.
+    pub fn empty() -> Self {
+        Self::from_bits(0)
+    }
+
+    /// Create a set of flags from a bit mask.
+    pub fn from_bits(bits: u64) -> Self {
+        Self {
+            bits,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the bit mask of the flags.
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// Returns `true` if the set contains no flags.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns `true` if the set contains `flag`.
+    pub fn contains(&self, flag: T) -> bool {
+        let bit = flag_bit(flag);
+        bit != 0 && self.bits & bit == bit
+    }
+
+    /// Adds `flag` to the set.
+    pub fn insert(&mut self, flag: T) {
+        self.bits |= flag_bit(flag);
+    }
+
+    /// Removes `flag` from the set.
+    pub fn remove(&mut self, flag: T) {
+        self.bits &= !flag_bit(flag);
+    }
+
+    /// Returns an iterator over the flags of the set, in the order of the
+    /// variants.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        T::items()
+            .iter()
+            .map(|item| item.value)
+            .filter(move |flag| self.contains(*flag))
+    }
+}
+
+fn flag_bit<T: EnumType>(flag: T) -> u64 {
+    flag.as_int().unwrap_or_default() as u64
+}
+
+impl<T> Clone for EnumFlags<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for EnumFlags<T> {}
+
+impl<T> PartialEq for EnumFlags<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<T> Eq for EnumFlags<T> {}
+
+impl<T> Hash for EnumFlags<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<T> Debug for EnumFlags<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EnumFlags")
+            .field(&format_args!("{:#b}", self.bits))
+            .finish()
+    }
+}
+
+impl<T: EnumType> Default for EnumFlags<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T: EnumType> FromIterator<T> for EnumFlags<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut flags = Self::empty();
+        for flag in iter {
+            flags.insert(flag);
+        }
+        flags
+    }
+}
+
+impl<T: EnumType + InputType> InputType for EnumFlags<T> {
+    type RawValueType = Self;
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("[{}]", T::qualified_type_name()))
+    }
+
+    fn qualified_type_name() -> String {
+        format!("[{}]!", T::qualified_type_name())
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        T::create_type_info(registry);
+        Self::qualified_type_name()
+    }
+
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        match value.unwrap_or_default() {
+            Value::List(values) => values
+                .into_iter()
+                .enumerate()
+                .map(|(idx, value)| {
+                    T::parse(Some(value))
+                        .map_err(|err| err.with_path_segment(PathSegment::Index(idx)))
+                })
+                .collect::<Result<_, _>>()
+                .map_err(InputValueError::propagate),
+            value => Ok(Self::from_iter([
+                T::parse(Some(value)).map_err(InputValueError::propagate)?
+            ])),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(enum_value).collect())
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: EnumType + OutputType> OutputType for EnumFlags<T> {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("[{}]", T::qualified_type_name()))
+    }
+
+    fn qualified_type_name() -> String {
+        format!("[{}]!", T::qualified_type_name())
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        T::create_type_info(registry);
+        Self::qualified_type_name()
+    }
+
+    async fn resolve(
+        &self,
+        _ctx: &ContextSelectionSet<'_>,
+        _field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        Ok(Value::List(self.iter().map(enum_value).collect()))
+    }
+}
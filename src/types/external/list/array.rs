@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use crate::{
     parser::types::Field, registry, resolver_utils::resolve_list, ContextSelectionSet, InputType,
-    InputValueError, InputValueResult, OutputType, Positioned, ServerResult, Value,
+    InputValueError, InputValueResult, OutputType, PathSegment, Positioned, ServerResult, Value,
 };
 
 impl<T: InputType, const N: usize> InputType for [T; N] {
@@ -25,7 +25,11 @@ impl<T: InputType, const N: usize> InputType for [T; N] {
         if let Some(Value::List(values)) = value {
             let items: Vec<T> = values
                 .into_iter()
-                .map(|value| InputType::parse(Some(value)))
+                .enumerate()
+                .map(|(idx, value)| {
+                    InputType::parse(Some(value))
+                        .map_err(|err| err.with_path_segment(PathSegment::Index(idx)))
+                })
                 .collect::<Result<_, _>>()
                 .map_err(InputValueError::propagate)?;
             let len = items.len();
@@ -4,7 +4,8 @@ use hashbrown::HashSet;
 
 use crate::{
     parser::types::Field, registry, resolver_utils::resolve_list, ContextSelectionSet, InputType,
-    InputValueError, InputValueResult, OutputType, Positioned, Result, ServerResult, Value,
+    InputValueError, InputValueResult, OutputType, PathSegment, Positioned, Result, ServerResult,
+    Value,
 };
 
 impl<T: InputType + Hash + Eq> InputType for HashSet<T> {
@@ -26,7 +27,11 @@ impl<T: InputType + Hash + Eq> InputType for HashSet<T> {
         match value.unwrap_or_default() {
             Value::List(values) => values
                 .into_iter()
-                .map(|value| InputType::parse(Some(value)))
+                .enumerate()
+                .map(|(idx, value)| {
+                    InputType::parse(Some(value))
+                        .map_err(|err| err.with_path_segment(PathSegment::Index(idx)))
+                })
                 .collect::<Result<_, _>>()
                 .map_err(InputValueError::propagate),
             value => Ok({
@@ -2,7 +2,7 @@ use std::{borrow::Cow, collections::LinkedList};
 
 use crate::{
     parser::types::Field, registry, resolver_utils::resolve_list, ContextSelectionSet, InputType,
-    InputValueError, InputValueResult, OutputType, Positioned, ServerResult, Value,
+    InputValueError, InputValueResult, OutputType, PathSegment, Positioned, ServerResult, Value,
 };
 
 impl<T: InputType> InputType for LinkedList<T> {
@@ -25,7 +25,11 @@ impl<T: InputType> InputType for LinkedList<T> {
         match value.unwrap_or_default() {
             Value::List(values) => values
                 .into_iter()
-                .map(|value| InputType::parse(Some(value)))
+                .enumerate()
+                .map(|(idx, value)| {
+                    InputType::parse(Some(value))
+                        .map_err(|err| err.with_path_segment(PathSegment::Index(idx)))
+                })
                 .collect::<Result<_, _>>()
                 .map_err(InputValueError::propagate),
             value => Ok({
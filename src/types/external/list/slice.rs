@@ -2,7 +2,7 @@ use std::{borrow::Cow, sync::Arc};
 
 use crate::{
     parser::types::Field, registry, resolver_utils::resolve_list, ContextSelectionSet, InputType,
-    InputValueError, InputValueResult, OutputType, Positioned, ServerResult, Value,
+    InputValueError, InputValueResult, OutputType, PathSegment, Positioned, ServerResult, Value,
 };
 
 #[async_trait::async_trait]
@@ -82,7 +82,11 @@ macro_rules! impl_input_slice_for_smart_ptr {
                 match value.unwrap_or_default() {
                     Value::List(values) => values
                         .into_iter()
-                        .map(|value| InputType::parse(Some(value)))
+                        .enumerate()
+                        .map(|(idx, value)| {
+                            InputType::parse(Some(value))
+                                .map_err(|err| err.with_path_segment(PathSegment::Index(idx)))
+                        })
                         .collect::<Result<_, _>>()
                         .map_err(InputValueError::propagate),
                     value => {
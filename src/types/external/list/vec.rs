@@ -2,7 +2,8 @@ use std::borrow::Cow;
 
 use crate::{
     parser::types::Field, registry, resolver_utils::resolve_list, ContextSelectionSet, InputType,
-    InputValueError, InputValueResult, OutputType, Positioned, Result, ServerResult, Value,
+    InputValueError, InputValueResult, OutputType, PathSegment, Positioned, Result, ServerResult,
+    Value,
 };
 
 impl<T: InputType> InputType for Vec<T> {
@@ -25,7 +26,11 @@ impl<T: InputType> InputType for Vec<T> {
         match value.unwrap_or_default() {
             Value::List(values) => values
                 .into_iter()
-                .map(|value| InputType::parse(Some(value)))
+                .enumerate()
+                .map(|(idx, value)| {
+                    InputType::parse(Some(value))
+                        .map_err(|err| err.with_path_segment(PathSegment::Index(idx)))
+                })
                 .collect::<Result<_, _>>()
                 .map_err(InputValueError::propagate),
             value => Ok(vec![
@@ -0,0 +1,100 @@
+use std::{borrow::Cow, sync::Mutex};
+
+use futures_util::stream::{BoxStream, Stream, StreamExt};
+
+use crate::{
+    parser::types::Field, registry, resolver_utils::resolve_list_stream, ContextSelectionSet,
+    OutputType, Positioned, ServerError, ServerResult, Value,
+};
+
+/// A list whose items are produced by a stream, such as the rows of a
+/// database cursor.
+///
+/// The items are resolved as soon as the stream produces them, so they don't
+/// have to be buffered into a `Vec` first. Up to
+/// [`ListStream::buffer_size`] items, 16 by default, are resolved
+/// concurrently, and the stream is not polled while they are pending. The
+/// stream is consumed when the field is resolved, so it can only be resolved
+/// once.
+///
+/// The `@stream` directive is not supported, the list is delivered in the
+/// response when the stream is exhausted.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use futures_util::stream;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn values(&self) -> ListStream<i32> {
+///         ListStream::new(stream::iter(vec![1, 2, 3]))
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let res = schema.execute("{ values }").await.into_result().unwrap().data;
+/// assert_eq!(res, value!({ "values": [1, 2, 3] }));
+/// # });
+/// ```
+pub struct ListStream<T> {
+    stream: Mutex<Option<BoxStream<'static, T>>>,
+    buffer_size: usize,
+}
+
+impl<T> ListStream<T> {
+    /// Create a list from a stream.
+    pub fn new(stream: impl Stream<Item = T> + Send + 'static) -> Self {
+        Self {
+            stream: Mutex::new(Some(stream.boxed())),
+            buffer_size: 16,
+        }
+    }
+
+    /// Set the maximum number of items resolved concurrently, default is
+    /// `16`.
+    ///
+    /// A size of `0` is treated as `1`.
+    #[must_use]
+    pub fn buffer_size(self, buffer_size: usize) -> Self {
+        Self {
+            buffer_size,
+            ..self
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: OutputType + 'static> OutputType for ListStream<T> {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("[{}]", T::qualified_type_name()))
+    }
+
+    fn qualified_type_name() -> String {
+        format!("[{}]!", T::qualified_type_name())
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        T::create_type_info(registry);
+        Self::qualified_type_name()
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        let stream = self.stream.lock().unwrap().take();
+        match stream {
+            Some(stream) => resolve_list_stream(ctx, field, stream, self.buffer_size).await,
+            None => Err(ServerError::new(
+                "The stream of the list has already been consumed.",
+                Some(field.pos),
+            )),
+        }
+    }
+}
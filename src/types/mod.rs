@@ -5,8 +5,10 @@ pub mod connection;
 mod any;
 mod empty_mutation;
 mod empty_subscription;
+mod enum_flags;
 mod id;
 mod json;
+mod list_stream;
 mod maybe_undefined;
 mod merged_object;
 mod query_root;
@@ -19,8 +21,10 @@ mod external;
 pub use any::Any;
 pub use empty_mutation::EmptyMutation;
 pub use empty_subscription::EmptySubscription;
+pub use enum_flags::EnumFlags;
 pub use id::ID;
 pub use json::Json;
+pub use list_stream::ListStream;
 pub use maybe_undefined::MaybeUndefined;
 pub use merged_object::{MergedObject, MergedObjectTail};
 pub(crate) use query_root::QueryRoot;
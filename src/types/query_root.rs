@@ -1,13 +1,14 @@
 use std::borrow::Cow;
 
 use crate::{
+    entity_cache::add_entity_cache_tags,
     model::{__Schema, __Type},
     parser::types::Field,
     registry::{self, SDLExportOptions},
     resolver_utils::{resolve_container, ContainerType},
     schema::IntrospectionMode,
-    Any, Context, ContextSelectionSet, ObjectType, OutputType, Positioned, ServerError,
-    ServerResult, SimpleObject, Value,
+    Any, Context, ContextSelectionSet, ErrorExtensionValues, ObjectType, OutputType, Positioned,
+    ServerError, ServerResult, SimpleObject, Value,
 };
 
 /// Federation service
@@ -71,14 +72,27 @@ impl<T: ObjectType> ContainerType for QueryRoot<T> {
         if ctx.schema_env.registry.enable_federation || ctx.schema_env.registry.has_entities() {
             if ctx.item.node.name.node == "_entities" {
                 let (_, representations) = ctx.param_value::<Vec<Any>>("representations", None)?;
-                let res = futures_util::future::try_join_all(representations.iter().map(
-                    |item| async move {
-                        self.inner.find_entity(ctx, &item.0).await?.ok_or_else(|| {
-                            ServerError::new("Entity not found.", Some(ctx.item.pos))
-                        })
+                add_entity_cache_tags(ctx, representations.iter().map(|item| &item.0));
+                // A representation that cannot be resolved is null with an error, so
+                // that the other entities of the batch are still returned.
+                let res = futures_util::future::join_all(representations.iter().enumerate().map(
+                    |(idx, item)| async move {
+                        let ctx_item = ctx.with_index(idx);
+                        match self.resolve_entity(&ctx_item, &item.0).await {
+                            Ok(value) => value,
+                            Err(err) => {
+                                let err = if err.path.is_empty() {
+                                    ctx_item.set_error_path(err)
+                                } else {
+                                    err
+                                };
+                                ctx.add_error(err);
+                                Value::Null
+                            }
+                        }
                     },
                 ))
-                .await?;
+                .await;
                 return Ok(Some(Value::List(res)));
             } else if ctx.item.node.name.node == "_service" {
                 let mut ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
@@ -101,6 +115,63 @@ impl<T: ObjectType> ContainerType for QueryRoot<T> {
 
         self.inner.resolve_field(ctx).await
     }
+
+    fn as_any(&self) -> Option<&(dyn std::any::Any + Send + Sync)> {
+        self.inner.as_any()
+    }
+}
+
+impl<T: ObjectType> QueryRoot<T> {
+    /// Resolves the entity of a representation of the `_entities` field.
+    async fn resolve_entity(
+        &self,
+        ctx: &Context<'_>,
+        representation: &Value,
+    ) -> ServerResult<Value> {
+        if let Some(value) = self.find_registered_entity(ctx, representation).await? {
+            return Ok(value);
+        }
+
+        let hook = match &ctx.schema_env.unknown_entity_hook {
+            Some(hook) => hook,
+            None => return Err(entity_not_found(ctx, representation)),
+        };
+        match hook(ctx, representation).await {
+            Ok(Some(representation)) => {
+                match self.find_registered_entity(ctx, &representation).await? {
+                    Some(value) => Ok(value),
+                    None => Err(entity_not_found(ctx, &representation)),
+                }
+            }
+            Ok(None) => Ok(Value::Null),
+            Err(err) => Err(ctx.set_error_path(err.into_server_error(ctx.item.pos))),
+        }
+    }
+
+    async fn find_registered_entity(
+        &self,
+        ctx: &Context<'_>,
+        representation: &Value,
+    ) -> ServerResult<Option<Value>> {
+        if let Some(value) = self.inner.find_entity(ctx, representation).await? {
+            return Ok(Some(value));
+        }
+        for resolver in ctx.schema_env.entity_resolvers.iter() {
+            if let Some(value) = resolver(ctx, representation).await? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn entity_not_found(ctx: &Context<'_>, representation: &Value) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("representation", representation.clone());
+    ServerError {
+        extensions: Some(extensions),
+        ..ctx.set_error_path(ServerError::new("Entity not found.", Some(ctx.item.pos)))
+    }
 }
 
 #[async_trait::async_trait]
@@ -0,0 +1,264 @@
+//! Expansion of fragment arguments.
+//!
+//! Every spread of a fragment that defines variables is replaced by a spread
+//! of a copy of the fragment, where the variables of the fragment are
+//! substituted with the arguments of the spread or their default values. The
+//! resulting document no longer uses fragment arguments, so it can be
+//! validated and executed as usual.
+
+use std::collections::{HashMap, HashSet};
+
+use async_graphql_value::Value;
+
+use crate::{
+    parser::types::{
+        DocumentOperations, ExecutableDocument, FragmentDefinition, Selection, SelectionSet,
+    },
+    Name, Positioned, ServerError,
+};
+
+pub(crate) fn expand_fragment_arguments(doc: &mut ExecutableDocument) -> Result<(), ServerError> {
+    if doc
+        .fragments
+        .values()
+        .all(|fragment| fragment.node.variable_definitions.is_empty())
+        && !doc
+            .operations
+            .iter()
+            .any(|(_, operation)| has_fragment_arguments(&operation.node.selection_set.node))
+        && !doc
+            .fragments
+            .values()
+            .any(|fragment| has_fragment_arguments(&fragment.node.selection_set.node))
+    {
+        return Ok(());
+    }
+
+    let mut expander = Expander {
+        fragments: doc.fragments.clone(),
+        expanded: HashMap::new(),
+        new_fragments: HashMap::new(),
+        referenced: HashSet::new(),
+        stack: Vec::new(),
+    };
+
+    match &mut doc.operations {
+        DocumentOperations::Single(operation) => {
+            expander.expand_selection_set(&mut operation.node.selection_set.node)?
+        }
+        DocumentOperations::Multiple(operations) => {
+            for operation in operations.values_mut() {
+                expander.expand_selection_set(&mut operation.node.selection_set.node)?;
+            }
+        }
+    }
+    for fragment in doc.fragments.values_mut() {
+        if fragment.node.variable_definitions.is_empty() {
+            expander.expand_selection_set(&mut fragment.node.selection_set.node)?;
+        }
+    }
+
+    let Expander {
+        new_fragments,
+        referenced,
+        ..
+    } = expander;
+    doc.fragments.retain(|name, fragment| {
+        fragment.node.variable_definitions.is_empty() || referenced.contains(name)
+    });
+    doc.fragments.extend(new_fragments);
+    Ok(())
+}
+
+fn has_fragment_arguments(selection_set: &SelectionSet) -> bool {
+    selection_set
+        .items
+        .iter()
+        .any(|selection| match &selection.node {
+            Selection::Field(field) => has_fragment_arguments(&field.node.selection_set.node),
+            Selection::FragmentSpread(spread) => !spread.node.arguments.is_empty(),
+            Selection::InlineFragment(inline_fragment) => {
+                has_fragment_arguments(&inline_fragment.node.selection_set.node)
+            }
+        })
+}
+
+struct Expander {
+    fragments: HashMap<Name, Positioned<FragmentDefinition>>,
+    /// The names of the expanded fragments, by fragment name and arguments.
+    expanded: HashMap<String, Name>,
+    new_fragments: HashMap<Name, Positioned<FragmentDefinition>>,
+    /// The fragments with variables that are still spread without being
+    /// expanded, because they spread themselves.
+    referenced: HashSet<Name>,
+    stack: Vec<Name>,
+}
+
+impl Expander {
+    fn expand_selection_set(
+        &mut self,
+        selection_set: &mut SelectionSet,
+    ) -> Result<(), ServerError> {
+        for selection in &mut selection_set.items {
+            match &mut selection.node {
+                Selection::Field(field) => {
+                    self.expand_selection_set(&mut field.node.selection_set.node)?
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    self.expand_selection_set(&mut inline_fragment.node.selection_set.node)?
+                }
+                Selection::FragmentSpread(spread) => {
+                    let fragment_name = spread.node.fragment_name.node.clone();
+                    let fragment = match self.fragments.get(&fragment_name) {
+                        Some(fragment) => fragment,
+                        None => continue,
+                    };
+
+                    let mut arguments = std::mem::take(&mut spread.node.arguments);
+                    if let Some((name, _)) = arguments.iter().find(|(name, _)| {
+                        !fragment
+                            .node
+                            .variable_definitions
+                            .iter()
+                            .any(|variable| variable.node.name.node == name.node)
+                    }) {
+                        return Err(ServerError::new(
+                            format!(
+                                "Unknown argument \"{}\" on fragment \"{}\"",
+                                name.node, fragment_name
+                            ),
+                            Some(name.pos),
+                        ));
+                    }
+                    if fragment.node.variable_definitions.is_empty() {
+                        continue;
+                    }
+
+                    let mut values = HashMap::new();
+                    for variable in &fragment.node.variable_definitions {
+                        let name = &variable.node.name.node;
+                        let value = match arguments.iter().position(|(arg, _)| &arg.node == name) {
+                            Some(idx) => arguments.swap_remove(idx).1.node,
+                            None => match &variable.node.default_value {
+                                Some(value) => value.node.clone().into_value(),
+                                None if variable.node.var_type.node.nullable => Value::Null,
+                                None => {
+                                    return Err(ServerError::new(
+                                        format!(
+                                            "Fragment \"{}\" argument \"{}\" of type \"{}\" is \
+                                             required, but it was not provided",
+                                            fragment_name, name, variable.node.var_type.node
+                                        ),
+                                        Some(spread.pos),
+                                    ))
+                                }
+                            },
+                        };
+                        values.insert(name.clone(), value);
+                    }
+
+                    let mut key = fragment_name.to_string();
+                    let mut sorted_values = values.iter().collect::<Vec<_>>();
+                    sorted_values.sort_by_key(|(name, _)| *name);
+                    for (name, value) in sorted_values {
+                        key.push_str(&format!(" {}: {}", name, value));
+                    }
+
+                    if let Some(name) = self.expanded.get(&key) {
+                        spread.node.fragment_name.node = name.clone();
+                        continue;
+                    }
+                    if self.stack.contains(&fragment_name) {
+                        self.referenced.insert(fragment_name);
+                        continue;
+                    }
+
+                    let mut expanded_fragment = fragment.clone();
+                    expanded_fragment.node.variable_definitions.clear();
+                    substitute_selection_set(
+                        &mut expanded_fragment.node.selection_set.node,
+                        &values,
+                    );
+                    for directive in &mut expanded_fragment.node.directives {
+                        substitute_arguments(&mut directive.node.arguments, &values);
+                    }
+
+                    let mut idx = self.expanded.len() + 1;
+                    let expanded_name = loop {
+                        let name = Name::new(format!("{}__{}", fragment_name, idx));
+                        if !self.fragments.contains_key(&name) {
+                            break name;
+                        }
+                        idx += 1;
+                    };
+                    self.expanded.insert(key, expanded_name.clone());
+
+                    self.stack.push(fragment_name);
+                    self.expand_selection_set(&mut expanded_fragment.node.selection_set.node)?;
+                    self.stack.pop();
+
+                    self.new_fragments
+                        .insert(expanded_name.clone(), expanded_fragment);
+                    spread.node.fragment_name.node = expanded_name;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn substitute_selection_set(selection_set: &mut SelectionSet, values: &HashMap<Name, Value>) {
+    for selection in &mut selection_set.items {
+        match &mut selection.node {
+            Selection::Field(field) => {
+                substitute_arguments(&mut field.node.arguments, values);
+                for directive in &mut field.node.directives {
+                    substitute_arguments(&mut directive.node.arguments, values);
+                }
+                substitute_selection_set(&mut field.node.selection_set.node, values);
+            }
+            Selection::FragmentSpread(spread) => {
+                substitute_arguments(&mut spread.node.arguments, values);
+                for directive in &mut spread.node.directives {
+                    substitute_arguments(&mut directive.node.arguments, values);
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                for directive in &mut inline_fragment.node.directives {
+                    substitute_arguments(&mut directive.node.arguments, values);
+                }
+                substitute_selection_set(&mut inline_fragment.node.selection_set.node, values);
+            }
+        }
+    }
+}
+
+fn substitute_arguments(
+    arguments: &mut [(Positioned<Name>, Positioned<Value>)],
+    values: &HashMap<Name, Value>,
+) {
+    for (_, value) in arguments {
+        substitute_value(&mut value.node, values);
+    }
+}
+
+fn substitute_value(value: &mut Value, values: &HashMap<Name, Value>) {
+    match value {
+        Value::Variable(name) => {
+            if let Some(new_value) = values.get(name) {
+                *value = new_value.clone();
+            }
+        }
+        Value::List(items) => {
+            for item in items {
+                substitute_value(item, values);
+            }
+        }
+        Value::Object(fields) => {
+            for value in fields.values_mut() {
+                substitute_value(value, values);
+            }
+        }
+        _ => {}
+    }
+}
@@ -2,17 +2,24 @@
 #[macro_use]
 mod test_harness;
 
+#[cfg(feature = "fragment-arguments")]
+mod fragment_arguments;
 mod rules;
 mod suggestion;
 mod utils;
 mod visitor;
 mod visitors;
 
+#[cfg(feature = "fragment-arguments")]
+pub(crate) use fragment_arguments::expand_fragment_arguments;
 pub use visitor::VisitorContext;
 use visitor::{visit, VisitorNil};
 
 use crate::{
-    parser::types::ExecutableDocument, registry::Registry, CacheControl, ServerError, Variables,
+    extensions::SchemaUsage,
+    parser::types::{ExecutableDocument, OperationDefinition},
+    registry::Registry,
+    CacheControl, Positioned, ServerError, Variables,
 };
 
 /// Validation results.
@@ -44,8 +51,12 @@ pub fn check_rules(
     doc: &ExecutableDocument,
     variables: Option<&Variables>,
     mode: ValidationMode,
+    visibility_profile: Option<&str>,
+    max_errors: Option<usize>,
 ) -> Result<ValidationResult, Vec<ServerError>> {
-    let mut ctx = VisitorContext::new(registry, doc, variables);
+    let mut ctx = VisitorContext::new(registry, doc, variables)
+        .visibility_profile(visibility_profile)
+        .max_errors(max_errors);
     let mut cache_control = CacheControl::default();
     let mut complexity = 0;
     let mut depth = 0;
@@ -75,6 +86,7 @@ pub fn check_rules(
                 .with(rules::DirectivesUnique::default())
                 .with(rules::OverlappingFieldsCanBeMerged)
                 .with(rules::UploadFile)
+                .with(rules::ClientControlledNullability)
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
                 })
@@ -86,6 +98,7 @@ pub fn check_rules(
             let mut visitor = VisitorNil
                 .with(rules::NoFragmentCycles::default())
                 .with(rules::UploadFile)
+                .with(rules::ClientControlledNullability)
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
                 })
@@ -105,3 +118,17 @@ pub fn check_rules(
         depth,
     })
 }
+
+/// Collects the types, fields, arguments and enum values referenced by
+/// `operation`, which is an operation of `doc`, into `usage`.
+pub(crate) fn collect_schema_usage(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    variables: Option<&Variables>,
+    operation: &Positioned<OperationDefinition>,
+    usage: &mut SchemaUsage,
+) {
+    let mut ctx = VisitorContext::new(registry, doc, variables);
+    let mut visitor = visitors::SchemaUsageCollect::new(operation, usage);
+    visit(&mut visitor, &mut ctx, doc);
+}
@@ -62,6 +62,7 @@ impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
             if let Some(reason) = value.and_then(|value| {
                 is_valid_input_value(
                     ctx.registry,
+                    ctx.visibility_profile,
                     &arg.ty,
                     &value,
                     QueryPathNode {
@@ -0,0 +1,72 @@
+use crate::{
+    parser::types::Field,
+    validation::visitor::{Visitor, VisitorContext},
+    Positioned,
+};
+
+#[derive(Default)]
+pub struct ClientControlledNullability;
+
+impl<'a> Visitor<'a> for ClientControlledNullability {
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        if ctx.registry.enable_experimental_ccn {
+            return;
+        }
+        if let Some(nullability) = &field.node.nullability {
+            ctx.report_error(
+                vec![nullability.pos],
+                format!(
+                    "The nullability designator \"{}\" on field \"{}\" is experimental and is \
+                     not enabled in this schema",
+                    nullability.node,
+                    field.node.response_key().node
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub fn factory() -> ClientControlledNullability {
+        ClientControlledNullability
+    }
+
+    #[test]
+    fn no_nullability_designators() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          {
+            dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn required_designator() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          {
+            dog { name! }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn optional_designator() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          {
+            dog? { name }
+          }
+        "#,
+        );
+    }
+}
@@ -36,6 +36,7 @@ impl<'a> Visitor<'a> for DefaultValuesOfCorrectType {
                 ));
             } else if let Some(reason) = is_valid_input_value(
                 ctx.registry,
+                ctx.visibility_profile,
                 &variable_definition.node.var_type.to_string(),
                 &value.node,
                 QueryPathNode {
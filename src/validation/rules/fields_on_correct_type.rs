@@ -22,10 +22,10 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
                 }
             }
 
-            if parent_type
+            if !parent_type
                 .fields()
                 .and_then(|fields| fields.get(field.node.name.node.as_str()))
-                .is_none()
+                .map_or(false, |field| ctx.is_visible(&field.visible))
                 && !field
                     .node
                     .directives
@@ -44,9 +44,10 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
                                 parent_type
                                     .fields()
                                     .iter()
-                                    .map(|fields| fields.keys())
+                                    .map(|fields| fields.values())
                                     .flatten()
-                                    .map(String::as_str),
+                                    .filter(|field| ctx.is_visible(&field.visible))
+                                    .map(|field| field.name.as_str()),
                                 &field.node.name.node,
                             )
                             .unwrap_or_default()
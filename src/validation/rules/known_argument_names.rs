@@ -25,13 +25,15 @@ pub struct KnownArgumentNames<'a> {
 }
 
 impl<'a> KnownArgumentNames<'a> {
-    fn get_suggestion(&self, name: &str) -> String {
+    fn get_suggestion(&self, ctx: &VisitorContext<'a>, name: &str) -> String {
         make_suggestion(
             " Did you mean",
             self.current_args
                 .iter()
-                .map(|(args, _)| args.iter().map(|arg| arg.0.as_str()))
-                .flatten(),
+                .map(|(args, _)| args.values())
+                .flatten()
+                .filter(|arg| ctx.is_visible(&arg.visible))
+                .map(|arg| arg.name.as_str()),
             name,
         )
         .unwrap_or_default()
@@ -66,7 +68,10 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
         _value: &'a Positioned<Value>,
     ) {
         if let Some((args, arg_type)) = &self.current_args {
-            if !args.contains_key(name.node.as_str()) {
+            if !args
+                .get(name.node.as_str())
+                .map_or(false, |arg| ctx.is_visible(&arg.visible))
+            {
                 match arg_type {
                     ArgsType::Field {
                         field_name,
@@ -80,7 +85,7 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
                                 field_name,
                                 type_name,
                                 if ctx.registry.enable_suggestions {
-                                    self.get_suggestion(name.node.as_str())
+                                    self.get_suggestion(ctx, name.node.as_str())
                                 } else {
                                     String::new()
                                 }
@@ -94,7 +99,7 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
                                 "Unknown argument \"{}\" on directive \"{}\".{}",
                                 name,
                                 directive_name,
-                                self.get_suggestion(name.node.as_str())
+                                self.get_suggestion(ctx, name.node.as_str())
                             ),
                         );
                     }
@@ -48,7 +48,12 @@ impl<'a> Visitor<'a> for KnownTypeNames {
 }
 
 fn validate_type(ctx: &mut VisitorContext<'_>, type_name: &str, pos: Pos) {
-    if ctx.registry.types.get(type_name).is_none() {
+    if !ctx
+        .registry
+        .types
+        .get(type_name)
+        .map_or(false, |ty| ctx.is_visible(ty.visible()))
+    {
         ctx.report_error(vec![pos], format!(r#"Unknown type "{}""#, type_name));
     }
 }
@@ -1,4 +1,5 @@
 mod arguments_of_correct_type;
+mod client_controlled_nullability;
 mod default_values_of_correct_type;
 mod directives_unique;
 mod fields_on_correct_type;
@@ -22,6 +23,7 @@ mod variables_are_input_types;
 mod variables_in_allowed_position;
 
 pub use arguments_of_correct_type::ArgumentsOfCorrectType;
+pub use client_controlled_nullability::ClientControlledNullability;
 pub use default_values_of_correct_type::DefaultValuesOfCorrectType;
 pub use directives_unique::DirectivesUnique;
 pub use fields_on_correct_type::FieldsOnCorrectType;
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use async_graphql_value::{ConstValue, Value};
+use indexmap::IndexMap;
 
 use crate::{context::QueryPathNode, registry, QueryPathSegment};
 
@@ -35,8 +36,19 @@ fn referenced_variables_to_vec<'a>(value: &'a Value, vars: &mut Vec<&'a str>) {
     }
 }
 
+fn is_visible_enum_value(
+    enum_values: &IndexMap<String, registry::MetaEnumValue>,
+    name: &str,
+    visibility_profile: Option<&str>,
+) -> bool {
+    enum_values.get(name).map_or(false, |value| {
+        registry::is_visible_to_profile(&value.visible, visibility_profile)
+    })
+}
+
 pub fn is_valid_input_value(
     registry: &registry::Registry,
+    visibility_profile: Option<&str>,
     type_name: &str,
     value: &ConstValue,
     path_node: QueryPathNode,
@@ -47,12 +59,13 @@ pub fn is_valid_input_value(
                 &path_node,
                 format!("expected type \"{}\"", type_name),
             )),
-            _ => is_valid_input_value(registry, type_name, value, path_node),
+            _ => is_valid_input_value(registry, visibility_profile, type_name, value, path_node),
         },
         registry::MetaTypeName::List(type_name) => match value {
             ConstValue::List(elems) => elems.iter().enumerate().find_map(|(idx, elem)| {
                 is_valid_input_value(
                     registry,
+                    visibility_profile,
                     type_name,
                     elem,
                     QueryPathNode {
@@ -62,7 +75,7 @@ pub fn is_valid_input_value(
                 )
             }),
             ConstValue::Null => None,
-            _ => is_valid_input_value(registry, type_name, value, path_node),
+            _ => is_valid_input_value(registry, visibility_profile, type_name, value, path_node),
         },
         registry::MetaTypeName::Named(type_name) => {
             if let ConstValue::Null = value {
@@ -94,7 +107,7 @@ pub fn is_valid_input_value(
                     ..
                 } => match value {
                     ConstValue::Enum(name) => {
-                        if !enum_values.contains_key(name.as_str()) {
+                        if !is_visible_enum_value(enum_values, name.as_str(), visibility_profile) {
                             Some(valid_error(
                                 &path_node,
                                 format!(
@@ -107,7 +120,7 @@ pub fn is_valid_input_value(
                         }
                     }
                     ConstValue::String(name) => {
-                        if !enum_values.contains_key(name.as_str()) {
+                        if !is_visible_enum_value(enum_values, name.as_str(), visibility_profile) {
                             Some(valid_error(
                                 &path_node,
                                 format!(
@@ -119,6 +132,31 @@ pub fn is_valid_input_value(
                             None
                         }
                     }
+                    // the integer values are accepted by the enums with integer values
+                    ConstValue::Number(n)
+                        if enum_values.values().any(|value| value.int_value.is_some()) =>
+                    {
+                        match n.as_i64() {
+                            Some(n)
+                                if enum_values.values().any(|value| {
+                                    value.int_value == Some(n)
+                                        && registry::is_visible_to_profile(
+                                            &value.visible,
+                                            visibility_profile,
+                                        )
+                                }) =>
+                            {
+                                None
+                            }
+                            _ => Some(valid_error(
+                                &path_node,
+                                format!(
+                                    "enumeration type \"{}\" does not contain the integer value {}",
+                                    enum_name, n
+                                ),
+                            )),
+                        }
+                    }
                     _ => Some(valid_error(
                         &path_node,
                         format!("expected type \"{}\"", type_name),
@@ -152,11 +190,15 @@ pub fn is_valid_input_value(
                         let mut input_names =
                             values.keys().map(AsRef::as_ref).collect::<HashSet<_>>();
 
-                        for field in input_fields.values() {
+                        // the hidden fields are unknown
+                        for field in input_fields.values().filter(|field| {
+                            registry::is_visible_to_profile(&field.visible, visibility_profile)
+                        }) {
                             input_names.remove(&*field.name);
                             if let Some(value) = values.get(&*field.name) {
                                 if let Some(reason) = is_valid_input_value(
                                     registry,
+                                    visibility_profile,
                                     &field.ty,
                                     value,
                                     QueryPathNode {
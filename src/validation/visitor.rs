@@ -3,7 +3,7 @@ use std::{
     fmt::{self, Display, Formatter},
 };
 
-use async_graphql_value::Value;
+use async_graphql_value::{ConstValue, Value};
 
 use crate::{
     parser::types::{
@@ -15,14 +15,23 @@ use crate::{
     InputType, Name, Pos, Positioned, ServerError, ServerResult, Variables,
 };
 
-#[doc(hidden)]
+/// The context of the validation of a query document.
+///
+/// It is passed to the functions that compute the complexity of the fields,
+/// [`MetaField::compute_complexity`](crate::registry::MetaField::compute_complexity),
+/// which can read the arguments of the fields with the variables replaced by
+/// their values.
 pub struct VisitorContext<'a> {
     pub(crate) registry: &'a registry::Registry,
     pub(crate) variables: Option<&'a Variables>,
+    variable_definitions: &'a [Positioned<VariableDefinition>],
     pub(crate) errors: Vec<RuleError>,
     type_stack: Vec<Option<&'a registry::MetaType>>,
     input_type: Vec<Option<MetaTypeName<'a>>>,
     fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    pub(crate) visibility_profile: Option<&'a str>,
+    max_errors: Option<usize>,
+    aborted: bool,
 }
 
 impl<'a> VisitorContext<'a> {
@@ -38,15 +47,59 @@ impl<'a> VisitorContext<'a> {
             type_stack: Default::default(),
             input_type: Default::default(),
             fragments: &doc.fragments,
+            visibility_profile: None,
+            variable_definitions: &[],
+            max_errors: None,
+            aborted: false,
         }
     }
 
+    pub(crate) fn max_errors(self, max_errors: Option<usize>) -> Self {
+        Self { max_errors, ..self }
+    }
+
+    /// Returns whether the validation is aborted because there are too many
+    /// errors.
+    pub(crate) fn is_aborted(&self) -> bool {
+        self.aborted
+    }
+
+    pub(crate) fn visibility_profile(self, visibility_profile: Option<&'a str>) -> Self {
+        Self {
+            visibility_profile,
+            ..self
+        }
+    }
+
+    /// Returns whether an element is visible to the active visibility
+    /// profile.
+    pub(crate) fn is_visible(&self, visible: &Option<registry::MetaVisible>) -> bool {
+        registry::is_visible_to_profile(visible, self.visibility_profile)
+    }
+
     pub(crate) fn report_error<T: Into<String>>(&mut self, locations: Vec<Pos>, msg: T) {
-        self.errors.push(RuleError::new(locations, msg));
+        self.push_error(RuleError::new(locations, msg));
     }
 
     pub(crate) fn append_errors(&mut self, errors: Vec<RuleError>) {
-        self.errors.extend(errors);
+        for error in errors {
+            self.push_error(error);
+        }
+    }
+
+    fn push_error(&mut self, error: RuleError) {
+        if self.aborted {
+            return;
+        }
+        if matches!(self.max_errors, Some(max_errors) if self.errors.len() >= max_errors) {
+            self.errors.push(RuleError::new(
+                Vec::new(),
+                "Too many validation errors, error limit reached. Validation aborted.",
+            ));
+            self.aborted = true;
+            return;
+        }
+        self.errors.push(error);
     }
 
     pub(crate) fn with_type<F: FnMut(&mut VisitorContext<'a>)>(
@@ -92,6 +145,21 @@ impl<'a> VisitorContext<'a> {
         self.fragments.get(name)
     }
 
+    /// Returns the value of a variable of the current operation, or its
+    /// default value if it is not provided.
+    pub fn variable_value(&self, name: &str) -> Option<ConstValue> {
+        variable_value(self.variables, self.variable_definitions, name)
+    }
+
+    /// Returns the value of an argument of a field of the current operation,
+    /// with the variables replaced by their values.
+    ///
+    /// Returns `None` if the argument is not specified, or if it is a variable
+    /// that is not provided and has no default value.
+    pub fn argument_value(&self, field: &Field, name: &str) -> ServerResult<Option<ConstValue>> {
+        argument_value(self.variables, self.variable_definitions, field, name)
+    }
+
     #[doc(hidden)]
     pub fn param_value<T: InputType>(
         &self,
@@ -100,7 +168,7 @@ impl<'a> VisitorContext<'a> {
         name: &str,
         default: Option<fn() -> T>,
     ) -> ServerResult<T> {
-        let value = field.get_argument(name).cloned();
+        let value = argument_value(self.variables, variable_definitions, field, name)?;
 
         if value.is_none() {
             if let Some(default) = default {
@@ -108,41 +176,64 @@ impl<'a> VisitorContext<'a> {
             }
         }
 
-        let (pos, value) = match value {
-            Some(value) => {
-                let pos = value.pos;
-                (
-                    pos,
-                    Some(value.node.into_const_with(|name| {
-                        variable_definitions
-                            .iter()
-                            .find(|def| def.node.name.node == name)
-                            .and_then(|def| {
-                                if let Some(variables) = self.variables {
-                                    variables
-                                        .get(&def.node.name.node)
-                                        .or_else(|| def.node.default_value())
-                                } else {
-                                    None
-                                }
-                            })
-                            .cloned()
-                            .ok_or_else(|| {
-                                ServerError::new(
-                                    format!("Variable {} is not defined.", name),
-                                    Some(pos),
-                                )
-                            })
-                    })?),
-                )
-            }
-            None => (Pos::default(), None),
-        };
-
-        T::parse(value).map_err(|e| e.into_server_error(pos))
+        let pos = field
+            .get_argument(name)
+            .map(|value| value.pos)
+            .unwrap_or_default();
+        T::parse(value).map_err(|e| e.in_argument(name).into_server_error(pos))
     }
 }
 
+fn variable_value(
+    variables: Option<&Variables>,
+    variable_definitions: &[Positioned<VariableDefinition>],
+    name: &str,
+) -> Option<ConstValue> {
+    let def = variable_definitions
+        .iter()
+        .find(|def| def.node.name.node == name)?;
+    variables
+        .and_then(|variables| variables.get(&def.node.name.node))
+        .or_else(|| def.node.default_value.as_ref().map(|value| &value.node))
+        .cloned()
+}
+
+fn argument_value(
+    variables: Option<&Variables>,
+    variable_definitions: &[Positioned<VariableDefinition>],
+    field: &Field,
+    name: &str,
+) -> ServerResult<Option<ConstValue>> {
+    let value = match field.get_argument(name) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let pos = value.pos;
+
+    // An argument whose variable is not provided is treated as not specified.
+    if let Value::Variable(name) = &value.node {
+        return Ok(variable_value(variables, variable_definitions, name));
+    }
+
+    value
+        .node
+        .clone()
+        .into_const_with(|name| {
+            variable_value(variables, variable_definitions, &name)
+                .or_else(|| {
+                    variable_definitions
+                        .iter()
+                        .find(|def| def.node.name.node == name)
+                        .filter(|def| def.node.var_type.node.nullable)
+                        .map(|_| ConstValue::Null)
+                })
+                .ok_or_else(|| {
+                    ServerError::new(format!("Variable {} is not defined.", name), Some(pos))
+                })
+        })
+        .map(Some)
+}
+
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub(crate) enum VisitMode {
     Normal,
@@ -529,6 +620,9 @@ pub(crate) fn visit<'a, V: Visitor<'a>>(
     v.enter_document(ctx, doc);
 
     for (name, fragment) in &doc.fragments {
+        if ctx.is_aborted() {
+            break;
+        }
         ctx.with_type(
             ctx.registry
                 .types
@@ -538,6 +632,9 @@ pub(crate) fn visit<'a, V: Visitor<'a>>(
     }
 
     for (name, operation) in doc.operations.iter() {
+        if ctx.is_aborted() {
+            break;
+        }
         visit_operation_definition(v, ctx, name, operation);
     }
 
@@ -550,6 +647,7 @@ fn visit_operation_definition<'a, V: Visitor<'a>>(
     name: Option<&'a Name>,
     operation: &'a Positioned<OperationDefinition>,
 ) {
+    ctx.variable_definitions = &operation.node.variable_definitions;
     v.enter_operation_definition(ctx, name, operation);
     let root_name = match &operation.node.ty {
         OperationType::Query => Some(&*ctx.registry.query_type),
@@ -580,6 +678,9 @@ fn visit_selection_set<'a, V: Visitor<'a>>(
     if !selection_set.node.items.is_empty() {
         v.enter_selection_set(ctx, selection_set);
         for selection in &selection_set.node.items {
+            if ctx.is_aborted() {
+                break;
+            }
             visit_selection(v, ctx, selection);
         }
         v.exit_selection_set(ctx, selection_set);
@@ -86,7 +86,7 @@ mod tests {
     use crate::{
         parser::parse_query,
         validation::{visit, VisitorContext},
-        EmptyMutation, Object, Schema, Subscription,
+        value, EmptyMutation, Object, Schema, Subscription, Variables,
     };
 
     struct Query;
@@ -166,10 +166,18 @@ mod tests {
     }
 
     fn check_complex(query: &str, expect_complex: usize) {
+        check_complex_with_variables(query, None, expect_complex);
+    }
+
+    fn check_complex_with_variables(
+        query: &str,
+        variables: Option<&Variables>,
+        expect_complex: usize,
+    ) {
         let registry =
             Schema::<Query, EmptyMutation, Subscription>::create_registry(Default::default());
         let doc = parse_query(query).unwrap();
-        let mut ctx = VisitorContext::new(&registry, &doc, None);
+        let mut ctx = VisitorContext::new(&registry, &doc, variables);
         let mut complex = 0;
         let mut complex_calculate = ComplexityCalculate::new(&mut complex);
         visit(&mut complex_calculate, &mut ctx, &doc);
@@ -413,4 +421,31 @@ mod tests {
             10,
         );
     }
+
+    #[test]
+    fn complex_object_with_variables() {
+        let query = r#"
+            query($count: Int, $nested: Int = 2) {
+                objs(count: $count) { a b }
+                obj { c { a } }
+            }"#;
+        check_complex_with_variables(
+            query,
+            Some(&Variables::from_value(value!({ "count": 3 }))),
+            9,
+        );
+        // The default value of the argument is used if the variable is not
+        // provided.
+        check_complex_with_variables(query, Some(&Variables::default()), 13);
+        check_complex(query, 13);
+
+        check_complex_with_variables(
+            r#"
+            query($count: Int = 4) {
+                ... on Query { objs(count: $count) { a } }
+            }"#,
+            Some(&Variables::default()),
+            4,
+        );
+    }
 }
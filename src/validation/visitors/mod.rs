@@ -1,7 +1,9 @@
 mod cache_control;
 mod complexity;
 mod depth;
+mod schema_usage;
 
 pub use cache_control::CacheControlCalculate;
 pub use complexity::ComplexityCalculate;
 pub use depth::DepthCalculate;
+pub use schema_usage::SchemaUsageCollect;
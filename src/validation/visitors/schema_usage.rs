@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+
+use async_graphql_parser::{
+    types::{Field, FragmentSpread, InlineFragment, OperationDefinition, VariableDefinition},
+    Pos,
+};
+use async_graphql_value::{ConstValue, Name, Value};
+
+use crate::{
+    extensions::SchemaUsage,
+    registry::{MetaType, MetaTypeName, Registry},
+    validation::visitor::{VisitMode, Visitor, VisitorContext},
+    Positioned,
+};
+
+pub struct SchemaUsageCollect<'ctx, 'a> {
+    usage: &'a mut SchemaUsage,
+    operation: &'ctx Positioned<OperationDefinition>,
+    active: bool,
+    introspection_depth: usize,
+    // the values of the secret arguments and input fields, whose enum values
+    // are not collected
+    secret_values: HashSet<*const Value>,
+}
+
+impl<'ctx, 'a> SchemaUsageCollect<'ctx, 'a> {
+    pub fn new(
+        operation: &'ctx Positioned<OperationDefinition>,
+        usage: &'a mut SchemaUsage,
+    ) -> Self {
+        Self {
+            usage,
+            operation,
+            active: false,
+            introspection_depth: 0,
+            secret_values: HashSet::new(),
+        }
+    }
+
+    fn is_collecting(&self) -> bool {
+        self.active && self.introspection_depth == 0
+    }
+
+    fn add_const_value(&mut self, registry: &Registry, ty: &str, value: Option<&ConstValue>) {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.add_const_value(registry, ty, value),
+            MetaTypeName::List(ty) => match value {
+                Some(ConstValue::List(values)) => {
+                    for value in values {
+                        self.add_const_value(registry, ty, Some(value));
+                    }
+                }
+                // a single value is coerced to a list
+                value => self.add_const_value(registry, ty, value),
+            },
+            MetaTypeName::Named(name) => {
+                self.usage.types.insert(name.to_string());
+                match (registry.types.get(name), value) {
+                    (Some(MetaType::Enum { enum_values, .. }), Some(ConstValue::Enum(value)))
+                        if enum_values.contains_key(value.as_str()) =>
+                    {
+                        self.usage.enum_values.insert(format!("{}.{}", name, value));
+                    }
+                    // the enum values of the variables are strings in JSON
+                    (Some(MetaType::Enum { enum_values, .. }), Some(ConstValue::String(value)))
+                        if enum_values.contains_key(value.as_str()) =>
+                    {
+                        self.usage.enum_values.insert(format!("{}.{}", name, value));
+                    }
+                    (
+                        Some(MetaType::InputObject { input_fields, .. }),
+                        Some(ConstValue::Object(values)),
+                    ) => {
+                        for (field_name, value) in values {
+                            if let Some(field) = input_fields.get(field_name.as_str()) {
+                                self.usage.fields.insert(format!("{}.{}", name, field_name));
+                                self.add_const_value(registry, &field.ty, Some(value));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for SchemaUsageCollect<'ctx, 'a> {
+    fn mode(&self) -> VisitMode {
+        VisitMode::Inline
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _name: Option<&'ctx Name>,
+        operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        // only the executed operation is collected
+        self.active = std::ptr::eq(operation_definition, self.operation);
+    }
+
+    fn exit_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _name: Option<&'ctx Name>,
+        _operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        self.active = false;
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        variable_definition: &'ctx Positioned<VariableDefinition>,
+    ) {
+        if self.is_collecting() {
+            let ty = variable_definition.node.var_type.node.to_string();
+            let value = ctx.variable_value(&variable_definition.node.name.node);
+            self.add_const_value(ctx.registry, &ty, value.as_ref());
+        }
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'ctx>, field: &'ctx Positioned<Field>) {
+        if field.node.name.node.starts_with("__") {
+            self.introspection_depth += 1;
+        }
+        if !self.is_collecting() {
+            return;
+        }
+
+        let parent_type = match ctx.parent_type() {
+            Some(parent_type) => parent_type,
+            None => return,
+        };
+        if let Some(schema_field) = parent_type.field_by_name(&field.node.name.node) {
+            let coordinate = format!("{}.{}", parent_type.name(), field.node.name.node);
+            for (name, value) in &field.node.arguments {
+                if let Some(arg) = schema_field.args.get(name.node.as_str()) {
+                    self.usage
+                        .arguments
+                        .insert(format!("{}({}:)", coordinate, name.node));
+                    if arg.is_secret {
+                        self.secret_values.insert(&value.node as *const Value);
+                    }
+                }
+            }
+            self.usage.types.insert(parent_type.name().to_string());
+            self.usage
+                .types
+                .insert(MetaTypeName::concrete_typename(&schema_field.ty).to_string());
+            self.usage.fields.insert(coordinate);
+        }
+    }
+
+    fn exit_field(&mut self, _ctx: &mut VisitorContext<'ctx>, field: &'ctx Positioned<Field>) {
+        if field.node.name.node.starts_with("__") {
+            self.introspection_depth -= 1;
+        }
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        fragment_spread: &'ctx Positioned<FragmentSpread>,
+    ) {
+        if self.is_collecting() {
+            if let Some(fragment) = ctx.fragment(&fragment_spread.node.fragment_name.node) {
+                let type_name = &fragment.node.type_condition.node.on.node;
+                if ctx.registry.types.contains_key(type_name.as_str()) {
+                    self.usage.types.insert(type_name.to_string());
+                }
+            }
+        }
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        _inline_fragment: &'ctx Positioned<InlineFragment>,
+    ) {
+        if self.is_collecting() {
+            if let Some(ty) = ctx.current_type() {
+                self.usage.types.insert(ty.name().to_string());
+            }
+        }
+    }
+
+    fn enter_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        _pos: Pos,
+        expected_type: &Option<MetaTypeName<'ctx>>,
+        value: &'ctx Value,
+    ) {
+        if !self.is_collecting() {
+            return;
+        }
+
+        let type_name = match expected_type {
+            Some(MetaTypeName::Named(type_name)) => type_name,
+            Some(MetaTypeName::List(type_name) | MetaTypeName::NonNull(type_name)) => {
+                MetaTypeName::concrete_typename(type_name)
+            }
+            None => return,
+        };
+        let is_secret = self.secret_values.contains(&(value as *const Value));
+        if is_secret {
+            match value {
+                Value::List(items) => self
+                    .secret_values
+                    .extend(items.iter().map(|item| item as *const Value)),
+                Value::Object(values) => self
+                    .secret_values
+                    .extend(values.values().map(|item| item as *const Value)),
+                _ => {}
+            }
+        }
+
+        self.usage.types.insert(type_name.to_string());
+        match (ctx.registry.types.get(type_name), value) {
+            (Some(MetaType::Enum { enum_values, .. }), Value::Enum(value))
+                if !is_secret && enum_values.contains_key(value.as_str()) =>
+            {
+                self.usage
+                    .enum_values
+                    .insert(format!("{}.{}", type_name, value));
+            }
+            (Some(MetaType::InputObject { input_fields, .. }), Value::Object(values)) => {
+                for (field_name, value) in values {
+                    if let Some(field) = input_fields.get(field_name.as_str()) {
+                        self.usage
+                            .fields
+                            .insert(format!("{}.{}", type_name, field_name));
+                        if field.is_secret {
+                            self.secret_values.insert(value as *const Value);
+                        }
+                    }
+                }
+            }
+            // the values of the variables are collected from their definitions
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use chrono::format::{parse, ParseErrorKind, Parsed, StrftimeItems};
+
+use crate::{InputType, InputValueError};
+
+pub fn date_format<T: AsRef<str> + InputType>(
+    value: &T,
+    format: &'static str,
+) -> Result<(), InputValueError<T>> {
+    let mut parsed = Parsed::new();
+    let is_valid = parse(&mut parsed, value.as_ref(), StrftimeItems::new(format)).is_ok()
+        && match parsed.to_naive_date() {
+            Ok(_) => true,
+            // The format does not contain a complete date
+            Err(err) => err.kind() == ParseErrorKind::NotEnough,
+        };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format_args!("value doesn't match expected date format '{}'", format).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_format() {
+        assert!(date_format(&"2022-01-31".to_string(), "%Y-%m-%d").is_ok());
+        assert!(date_format(&"2022-01-31 12:30".to_string(), "%Y-%m-%d %H:%M").is_ok());
+
+        assert!(date_format(&"12:30".to_string(), "%H:%M").is_ok());
+
+        assert!(date_format(&"2022-13-31".to_string(), "%Y-%m-%d").is_err());
+        assert!(date_format(&"2022-02-30".to_string(), "%Y-%m-%d").is_err());
+        assert!(date_format(&"2022/01/31".to_string(), "%Y-%m-%d").is_err());
+        assert!(date_format(&"2022-01-31 12:30".to_string(), "%Y-%m-%d").is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt::Display;
+
+use num_traits::AsPrimitive;
+
+use crate::{InputType, InputValueError};
+
+pub fn exclusive_maximum<T, N>(value: &T, n: N) -> Result<(), InputValueError<T>>
+where
+    T: AsPrimitive<N> + InputType,
+    N: PartialOrd + Display + Copy + 'static,
+{
+    if value.as_() < n {
+        Ok(())
+    } else {
+        Err(format!("the value is {}, must be less than {}", value.as_(), n).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclusive_maximum() {
+        assert!(exclusive_maximum(&99, 100).is_ok());
+        assert!(exclusive_maximum(&100, 100).is_err());
+        assert!(exclusive_maximum(&101, 100).is_err());
+    }
+}
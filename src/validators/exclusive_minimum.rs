@@ -0,0 +1,29 @@
+use std::fmt::Display;
+
+use num_traits::AsPrimitive;
+
+use crate::{InputType, InputValueError};
+
+pub fn exclusive_minimum<T, N>(value: &T, n: N) -> Result<(), InputValueError<T>>
+where
+    T: AsPrimitive<N> + InputType,
+    N: PartialOrd + Display + Copy + 'static,
+{
+    if value.as_() > n {
+        Ok(())
+    } else {
+        Err(format!("the value is {}, must be greater than {}", value.as_(), n).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclusive_minimum() {
+        assert!(exclusive_minimum(&99, 100).is_err());
+        assert!(exclusive_minimum(&100, 100).is_err());
+        assert!(exclusive_minimum(&101, 100).is_ok());
+    }
+}
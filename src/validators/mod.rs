@@ -1,7 +1,11 @@
 mod chars_max_length;
 mod chars_min_length;
+#[cfg(feature = "chrono")]
+mod date_format;
 #[cfg(feature = "email-validator")]
 mod email;
+mod exclusive_maximum;
+mod exclusive_minimum;
 mod ip;
 mod max_items;
 mod max_length;
@@ -12,13 +16,20 @@ mod min_length;
 mod min_password_strength;
 mod minimum;
 mod multiple_of;
+mod not_blank;
+mod one_of;
 mod regex;
 mod url;
+mod uuid;
 
 pub use chars_max_length::chars_max_length;
 pub use chars_min_length::chars_min_length;
+#[cfg(feature = "chrono")]
+pub use date_format::date_format;
 #[cfg(feature = "email-validator")]
 pub use email::email;
+pub use exclusive_maximum::exclusive_maximum;
+pub use exclusive_minimum::exclusive_minimum;
 pub use ip::ip;
 pub use max_items::max_items;
 pub use max_length::max_length;
@@ -29,9 +40,11 @@ pub use min_length::min_length;
 pub use min_password_strength::min_password_strength;
 pub use minimum::minimum;
 pub use multiple_of::multiple_of;
+pub use not_blank::not_blank;
+pub use one_of::one_of;
 
-pub use self::{regex::regex, url::url};
-use crate::{InputType, InputValueError};
+pub use self::{regex::regex, url::url, uuid::uuid};
+use crate::{Context, InputType, InputValueError};
 
 /// Represents a custom input value validator.
 pub trait CustomValidator<T: InputType> {
@@ -39,6 +52,83 @@ pub trait CustomValidator<T: InputType> {
     fn check(&self, value: &T) -> Result<(), InputValueError<T>>;
 }
 
+/// Represents a custom input value validator that can run asynchronously and
+/// access the [`Context`].
+///
+/// It is used via `#[graphql(validator(async_custom = "..."))]` on the
+/// arguments of objects, complex objects and subscriptions.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashSet;
+///
+/// use async_graphql::*;
+///
+/// struct UsernameNotTaken;
+///
+/// #[async_trait::async_trait]
+/// impl AsyncCustomValidator<String> for UsernameNotTaken {
+///     async fn check(&self, ctx: &Context<'_>, value: &String) -> Result<(), InputValueError<String>> {
+///         let taken = ctx.data_unchecked::<HashSet<String>>();
+///         if taken.contains(value) {
+///             return Err(InputValueError::custom("username is already taken"));
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// struct Mutation;
+///
+/// #[Object]
+/// impl Mutation {
+///     async fn register(
+///         &self,
+///         #[graphql(validator(async_custom = "UsernameNotTaken"))] username: String,
+///     ) -> String {
+///         username
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait AsyncCustomValidator<T: InputType> {
+    /// Check the value is valid.
+    async fn check(&self, ctx: &Context<'_>, value: &T) -> Result<(), InputValueError<T>>;
+}
+
+/// Represents a validator of an input object that checks the relations
+/// between its fields.
+///
+/// It is called after all fields of the input object are parsed and
+/// validated, and is enabled with `#[graphql(validator(validate_input))]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(InputObject)]
+/// #[graphql(validator(validate_input))]
+/// struct DateRange {
+///     start_date: i32,
+///     end_date: i32,
+/// }
+///
+/// impl ValidateInput for DateRange {
+///     fn validate(&self) -> Result<(), InputValueError<Self>> {
+///         if self.end_date <= self.start_date {
+///             return Err(InputValueError::custom("endDate must be after startDate")
+///                 .with_fields(["startDate", "endDate"]));
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait ValidateInput: InputType {
+    /// Check the input object is valid.
+    fn validate(&self) -> Result<(), InputValueError<Self>>;
+}
+
 impl<T, F, E> CustomValidator<T> for F
 where
     T: InputType,
@@ -0,0 +1,23 @@
+use crate::{InputType, InputValueError};
+
+pub fn not_blank<T: AsRef<str> + InputType>(value: &T) -> Result<(), InputValueError<T>> {
+    if !value.as_ref().trim().is_empty() {
+        Ok(())
+    } else {
+        Err("the string must not be blank".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_blank() {
+        assert!(not_blank(&"abc".to_string()).is_ok());
+        assert!(not_blank(&" a ".to_string()).is_ok());
+
+        assert!(not_blank(&"".to_string()).is_err());
+        assert!(not_blank(&"  \t\n".to_string()).is_err());
+    }
+}
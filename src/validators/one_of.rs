@@ -0,0 +1,34 @@
+use crate::{InputType, InputValueError};
+
+pub fn one_of<T: AsRef<str> + InputType>(
+    value: &T,
+    values: &'static [&'static str],
+) -> Result<(), InputValueError<T>> {
+    if values.contains(&value.as_ref()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "the value is \"{}\", must be one of {}",
+            value.as_ref(),
+            values
+                .iter()
+                .map(|value| format!("\"{}\"", value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_of() {
+        assert!(one_of(&"draft".to_string(), &["draft", "published"]).is_ok());
+        assert!(one_of(&"published".to_string(), &["draft", "published"]).is_ok());
+        assert!(one_of(&"Draft".to_string(), &["draft", "published"]).is_err());
+        assert!(one_of(&"".to_string(), &["draft", "published"]).is_err());
+    }
+}
@@ -1,12 +1,36 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use once_cell::sync::Lazy;
 use regex::Regex;
 
 use crate::{InputType, InputValueError};
 
+/// The compiled regular expressions by pattern, which are compiled once and
+/// then shared by all requests.
+static REGEXES: Lazy<RwLock<HashMap<&'static str, Regex>>> = Lazy::new(Default::default);
+
+fn compiled_regex(pattern: &'static str) -> Regex {
+    if let Some(regex) = REGEXES.read().unwrap().get(pattern) {
+        return regex.clone();
+    }
+    let regex = Regex::new(pattern)
+        .unwrap_or_else(|err| panic!("invalid regular expression '{}': {}", pattern, err));
+    REGEXES
+        .write()
+        .unwrap()
+        .entry(pattern)
+        .or_insert(regex)
+        .clone()
+}
+
+/// # Panics
+///
+/// Panics if `regex` is not a valid regular expression.
 pub fn regex<T: AsRef<str> + InputType>(
     value: &T,
     regex: &'static str,
 ) -> Result<(), InputValueError<T>> {
-    if let Ok(true) = Regex::new(regex).map(|re| re.is_match(value.as_ref())) {
+    if compiled_regex(regex).is_match(value.as_ref()) {
         Ok(())
     } else {
         Err(format_args!("value doesn't match expected format '{}'", regex).into())
@@ -22,4 +46,10 @@ mod tests {
         assert!(regex(&"123".to_string(), "^[0-9]+$").is_ok());
         assert!(regex(&"12a3".to_string(), "^[0-9]+$").is_err());
     }
+
+    #[test]
+    #[should_panic(expected = "invalid regular expression '[0-9'")]
+    fn test_invalid_regex() {
+        let _ = regex(&"123".to_string(), "[0-9");
+    }
 }
@@ -0,0 +1,31 @@
+use crate::{InputType, InputValueError};
+
+pub fn uuid<T: AsRef<str> + InputType>(value: &T) -> Result<(), InputValueError<T>> {
+    let value = value.as_ref();
+    let is_valid = value.len() == 36
+        && value.char_indices().all(|(idx, c)| match idx {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    if is_valid {
+        Ok(())
+    } else {
+        Err("invalid uuid".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid() {
+        assert!(uuid(&"67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()).is_ok());
+        assert!(uuid(&"67E55044-10B1-426F-9247-BB680E5FE0C8".to_string()).is_ok());
+
+        assert!(uuid(&"67e5504410b1426f9247bb680e5fe0c8".to_string()).is_err());
+        assert!(uuid(&"67e55044-10b1-426f-9247-bb680e5fe0cg".to_string()).is_err());
+        assert!(uuid(&"67e55044-10b1-426f-9247-bb680e5fe0c".to_string()).is_err());
+        assert!(uuid(&"67e55044-10b1-426f-9247_bb680e5fe0c8".to_string()).is_err());
+    }
+}
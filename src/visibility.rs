@@ -0,0 +1,109 @@
+use std::any::TypeId;
+
+use crate::{registry::Registry, schema::SchemaEnvInner, Data, ServerError};
+
+/// The visibility profile of a request.
+///
+/// The visibility profiles are declared with
+/// [`SchemaBuilder::visibility_profiles`](crate::SchemaBuilder::visibility_profiles),
+/// and the types, fields, arguments and enum values are tagged with the
+/// profiles that can see them with `#[graphql(visible("partner",
+/// "internal"))]`. The active profile is selected by adding a
+/// `VisibilityProfile` to the data of the request, the session or the schema,
+/// and the first declared profile is active if none is added.
+///
+/// The elements that are not visible to the active profile are hidden from the
+/// introspection, are not exported to the SDL with
+/// [`SDLExportAudience::visibility_profile`](crate::SDLExportAudience::visibility_profile),
+/// and are reported as unknown by the validation.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn name(&self) -> &str {
+///         "abc"
+///     }
+///
+///     #[graphql(visible("partner", "internal"))]
+///     async fn email(&self) -> &str {
+///         "abc@example.com"
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .visibility_profiles(["public", "partner", "internal"])
+///     .finish();
+///
+/// assert!(schema.execute("{ email }").await.is_err());
+///
+/// let request = Request::new("{ email }").data(VisibilityProfile::new("partner"));
+/// assert_eq!(
+///     schema.execute(request).await.into_result().unwrap().data,
+///     value!({ "email": "abc@example.com" })
+/// );
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VisibilityProfile(String);
+
+impl VisibilityProfile {
+    /// Create a visibility profile.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Returns the name of the visibility profile.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Returns the active visibility profile, which is the first
+/// `VisibilityProfile` found in `data`, in the data of the schema or the first
+/// declared profile.
+pub(crate) fn active_visibility_profile<'a>(
+    schema_env: &'a SchemaEnvInner,
+    data: &[&'a Data],
+) -> Option<&'a str> {
+    data.iter()
+        .find_map(|data| data.get_value(&TypeId::of::<VisibilityProfile>()))
+        .map(AsRef::as_ref)
+        .or_else(|| schema_env.get_data(&TypeId::of::<VisibilityProfile>()))
+        .and_then(|profile| profile.downcast_ref::<VisibilityProfile>())
+        .map(VisibilityProfile::name)
+        .or_else(|| {
+            schema_env
+                .registry
+                .visibility_profiles
+                .first()
+                .map(String::as_str)
+        })
+}
+
+/// Checks that the active visibility profile is declared.
+pub(crate) fn check_visibility_profile(
+    registry: &Registry,
+    profile: Option<&str>,
+) -> Result<(), ServerError> {
+    match profile {
+        Some(profile)
+            if !registry
+                .visibility_profiles
+                .iter()
+                .any(|name| name == profile) =>
+        {
+            Err(ServerError::new(
+                format!(r#"Unknown visibility profile "{}""#, profile),
+                None,
+            ))
+        }
+        _ => Ok(()),
+    }
+}
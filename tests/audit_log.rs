@@ -0,0 +1,170 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_graphql::{
+    extensions::{AuditLog, AuditOutcome, AuditRecord, AuditSink, ClientIdentity},
+    parser::types::OperationType,
+    *,
+};
+use futures_util::stream::{Stream, StreamExt};
+
+#[derive(Clone, Default)]
+struct MemorySink(Arc<Mutex<Vec<Vec<AuditRecord>>>>);
+
+impl MemorySink {
+    /// Returns the sizes of the written batches, after the spawned writes.
+    async fn batch_sizes(&self) -> Vec<usize> {
+        tokio::task::yield_now().await;
+        self.0.lock().unwrap().iter().map(Vec::len).collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditSink for MemorySink {
+    async fn write(&self, records: Vec<AuditRecord>) {
+        self.0.lock().unwrap().push(records);
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self, secret: String) -> i32 {
+        secret.len() as i32
+    }
+
+    async fn fail(&self) -> Result<i32> {
+        Err("fail".into())
+    }
+}
+
+#[tokio::test]
+pub async fn test_audit_log_outcomes() {
+    let sink = MemorySink::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(AuditLog::new(sink.clone(), tokio::spawn))
+        .finish();
+
+    schema
+        .execute(
+            Request::new("query Q($secret: String!) { value(secret: $secret) }")
+                .variables(Variables::from_value(value!({ "secret": "hunter2" })))
+                .data(ClientIdentity("billing".to_string())),
+        )
+        .await;
+    schema.execute("{ fail }").await;
+    schema.execute("{ unknown }").await;
+
+    assert_eq!(sink.batch_sizes().await, vec![1, 1, 1]);
+    let batches = sink.0.lock().unwrap();
+    let records = batches.iter().flatten().collect::<Vec<_>>();
+
+    assert_eq!(records[0].outcome, AuditOutcome::Success);
+    assert_eq!(
+        records[0].document.as_deref(),
+        Some("query Q($secret:String!){value(secret:$secret)}")
+    );
+    assert_eq!(records[0].operation_type, Some(OperationType::Query));
+    assert_eq!(records[0].variables, value!({ "secret": "string" }));
+    assert_eq!(records[0].client.as_deref(), Some("billing"));
+    assert_eq!(records[0].errors, 0);
+
+    assert_eq!(records[1].outcome, AuditOutcome::Error);
+    assert_eq!(records[1].errors, 1);
+    assert_eq!(records[1].client, None);
+
+    assert_eq!(records[2].outcome, AuditOutcome::Rejected);
+    assert_eq!(records[2].errors, 1);
+}
+
+#[tokio::test]
+pub async fn test_audit_log_batches() {
+    let sink = MemorySink::default();
+    let audit_log = AuditLog::new(sink.clone(), tokio::spawn).batch_size(2);
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(audit_log.clone())
+        .finish();
+
+    for _ in 0..3 {
+        schema.execute(r#"{ value(secret: "abc") }"#).await;
+    }
+    assert_eq!(sink.batch_sizes().await, vec![2]);
+
+    audit_log.flush().await;
+    assert_eq!(sink.batch_sizes().await, vec![2, 1]);
+    assert_eq!(
+        sink.0.lock().unwrap()[0][0].document.as_deref(),
+        Some("query{value(secret:\"\")}")
+    );
+}
+
+#[tokio::test]
+pub async fn test_audit_log_flush_interval() {
+    let sink = MemorySink::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(
+            AuditLog::new(sink.clone(), tokio::spawn)
+                .batch_size(10)
+                .flush_interval(Duration::from_millis(50)),
+        )
+        .finish();
+
+    schema.execute(r#"{ value(secret: "abc") }"#).await;
+    schema.execute(r#"{ value(secret: "abc") }"#).await;
+    assert_eq!(sink.batch_sizes().await, Vec::<usize>::new());
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(sink.batch_sizes().await, vec![2]);
+
+    // the pending records are written when the extension is dropped
+    schema.execute(r#"{ value(secret: "abc") }"#).await;
+    drop(schema);
+    assert_eq!(sink.batch_sizes().await, vec![2, 1]);
+}
+
+#[tokio::test]
+pub async fn test_audit_log_subscription() {
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = Result<i32>> {
+            futures_util::stream::iter(vec![Ok(1), Err("fail".into())])
+        }
+    }
+
+    let sink = MemorySink::default();
+    let schema = Schema::build(Query, EmptyMutation, Subscription)
+        .extension(AuditLog::new(sink.clone(), tokio::spawn))
+        .finish();
+
+    let responses = schema
+        .execute_stream(
+            Request::new("subscription S { values }").data(ClientIdentity("billing".to_string())),
+        )
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(responses.len(), 2);
+    schema
+        .execute_stream("subscription { unknown }")
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(sink.batch_sizes().await, vec![1, 1]);
+    let batches = sink.0.lock().unwrap();
+    assert_eq!(batches[0][0].outcome, AuditOutcome::Error);
+    assert_eq!(batches[0][0].errors, 1);
+    assert_eq!(
+        batches[0][0].document.as_deref(),
+        Some("subscription S{values}")
+    );
+    assert_eq!(
+        batches[0][0].operation_type,
+        Some(OperationType::Subscription)
+    );
+    assert_eq!(batches[0][0].client.as_deref(), Some("billing"));
+    assert_eq!(batches[1][0].outcome, AuditOutcome::Rejected);
+}
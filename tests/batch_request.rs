@@ -31,3 +31,74 @@ pub async fn test_batch_request() {
         ])
     );
 }
+
+#[tokio::test]
+pub async fn test_batch_request_export() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: i32,
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn me(&self) -> User {
+            User {
+                id: 1,
+                name: "a".to_string(),
+            }
+        }
+
+        async fn friends(&self) -> Vec<User> {
+            (2..5)
+                .map(|id| User {
+                    id,
+                    name: format!("user{}", id),
+                })
+                .collect()
+        }
+
+        async fn posts(&self, user_id: i32) -> Vec<String> {
+            vec![format!("post of {}", user_id)]
+        }
+
+        async fn count(&self, ids: Vec<i32>) -> usize {
+            ids.len()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_export_directive()
+        .finish();
+    let batch: BatchRequest = vec![
+        Request::new(
+            r#"query Me { me { ...UserId name } }
+            fragment UserId on User { id @export(as: "userId") }"#,
+        ),
+        Request::new(r#"{ friends { id @export(as: "friendIds") } }"#),
+        Request::new("query Posts($userId: Int!) { posts(userId: $userId) }"),
+        Request::new("query Count($friendIds: [Int!]!) { count(ids: $friendIds) }")
+            // the exported variables replace the variables of the request
+            .variables(Variables::from_json(serde_json::json!({ "friendIds": [] }))),
+    ]
+    .into();
+    let resp = schema.execute_batch(batch).await;
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!([
+            {"data": { "me": { "id": 1, "name": "a" } }},
+            {"data": { "friends": [{ "id": 2 }, { "id": 3 }, { "id": 4 }] }},
+            {"data": { "posts": ["post of 1"] }},
+            {"data": { "count": 3 }},
+        ])
+    );
+
+    // the directive is unknown if it is not enabled
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let resp = schema
+        .execute(r#"{ me { id @export(as: "userId") } }"#)
+        .await;
+    assert_eq!(resp.errors[0].message, r#"Unknown directive "export""#);
+}
@@ -0,0 +1,122 @@
+use async_graphql::*;
+
+struct MyObj;
+
+#[Object]
+impl MyObj {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn none(&self) -> Option<i32> {
+        None
+    }
+
+    async fn error(&self) -> Result<i32> {
+        Err("failed".into())
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn obj(&self) -> MyObj {
+        MyObj
+    }
+
+    async fn optional_obj(&self) -> Option<MyObj> {
+        Some(MyObj)
+    }
+}
+
+#[tokio::test]
+pub async fn test_ccn_disabled() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ obj { value! } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"The nullability designator "!" on field "value" is experimental and is not enabled in this schema"#.to_owned(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 14
+            }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+}
+
+#[tokio::test]
+pub async fn test_ccn_optional() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_experimental_ccn()
+        .finish();
+
+    let resp = schema.execute("{ obj { value error? } }").await;
+    assert_eq!(resp.data, value!({ "obj": { "value": 10, "error": null } }));
+    assert_eq!(
+        resp.errors,
+        vec![ServerError {
+            message: "failed".to_owned(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 15
+            }],
+            path: vec![
+                PathSegment::Field("obj".to_owned()),
+                PathSegment::Field("error".to_owned())
+            ],
+            extensions: None,
+        }]
+    );
+
+    let resp = schema.execute("{ obj? { value error } }").await;
+    assert_eq!(resp.data, value!({ "obj": null }));
+    assert_eq!(resp.errors.len(), 1);
+}
+
+#[tokio::test]
+pub async fn test_ccn_required() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_experimental_ccn()
+        .finish();
+
+    let resp = schema
+        .execute("{ obj { value } optionalObj { value none! } }")
+        .await;
+    assert_eq!(
+        resp.data,
+        value!({ "obj": { "value": 10 }, "optionalObj": null })
+    );
+    assert_eq!(
+        resp.errors,
+        vec![ServerError {
+            message: r#"Cannot return null for the required field "none"."#.to_owned(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 37
+            }],
+            path: vec![
+                PathSegment::Field("optionalObj".to_owned()),
+                PathSegment::Field("none".to_owned())
+            ],
+            extensions: None,
+        }]
+    );
+
+    let resp = schema.execute("{ obj { none! } }").await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors.len(), 1);
+
+    let resp = schema.execute("{ obj { value! none } }").await;
+    assert_eq!(resp.data, value!({ "obj": { "value": 10, "none": null } }));
+    assert!(resp.errors.is_empty());
+}
@@ -225,3 +225,191 @@ pub async fn test_connection_nodes_disabled() {
         "Unknown field \"nodes\" on type \"IntConnection\"."
     );
 }
+
+#[tokio::test]
+pub async fn test_connection_pagination_limits() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn numbers(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> Result<Connection<usize, i32>> {
+            connection::query_with_limits(
+                after,
+                before,
+                first,
+                last,
+                PaginationLimits::new().max_page_size(10),
+                |_after: Option<usize>, _before, first, last| async move {
+                    let len = first.or(last).unwrap_or(10);
+                    let mut connection = Connection::new(false, false);
+                    connection
+                        .edges
+                        .extend((0..len).map(|n| Edge::new(n, n as i32)));
+                    Ok::<_, Error>(connection)
+                },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let r = schema.execute("{ numbers(first: 10) { nodes } }").await;
+    assert_eq!(
+        r.into_result().unwrap().data,
+        value!({ "numbers": { "nodes": [0, 1, 2, 3, 4, 5, 6, 7, 8, 9] } })
+    );
+
+    for (query, message) in [
+        (
+            "{ numbers(first: 11) { nodes } }",
+            "The \"first\" parameter must not be greater than 10",
+        ),
+        (
+            "{ numbers(last: 100) { nodes } }",
+            "The \"last\" parameter must not be greater than 10",
+        ),
+        (
+            "{ numbers(last: -1) { nodes } }",
+            "The \"last\" parameter must be a non-negative number",
+        ),
+        (
+            "{ numbers(first: 1, last: 1) { nodes } }",
+            "The \"first\" and \"last\" parameters cannot exist at the same time",
+        ),
+    ] {
+        let r = schema.execute(query).await;
+        assert_eq!(r.errors[0].message, message);
+    }
+}
+
+#[tokio::test]
+pub async fn test_connection_keyset() {
+    #[derive(SimpleObject)]
+    struct User {
+        created_at: i32,
+        id: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn users(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> Result<Connection<OpaqueCursor<(i32, i32)>, User>> {
+            connection::query(
+                after,
+                before,
+                first,
+                last,
+                |after, before, first, last| async move {
+                    let rows = [(1, 1), (1, 2), (2, 1), (3, 1), (3, 2)];
+                    let keyset = Keyset::new(["created_at", "id"], after, before, first, last);
+                    let mut rows = rows.into_iter().filter(keyset.filter()).collect::<Vec<_>>();
+                    if keyset.order_by().ends_with("DESC") {
+                        rows.reverse();
+                    }
+                    rows.truncate(keyset.limit().unwrap_or(rows.len()));
+                    Ok::<_, Error>(keyset.into_connection(
+                        rows,
+                        |row| *row,
+                        |(created_at, id)| User { created_at, id },
+                    ))
+                },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = |args: &str| {
+        format!(
+            "{{ users({}) {{ nodes {{ createdAt id }} pageInfo {{ hasPreviousPage hasNextPage startCursor endCursor }} }} }}",
+            args
+        )
+    };
+
+    let data = schema
+        .execute(query("first: 2"))
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    assert_eq!(
+        data["users"]["nodes"],
+        serde_json::json!([{ "createdAt": 1, "id": 1 }, { "createdAt": 1, "id": 2 }])
+    );
+    assert_eq!(data["users"]["pageInfo"]["hasPreviousPage"], false);
+    assert_eq!(data["users"]["pageInfo"]["hasNextPage"], true);
+
+    let end_cursor = data["users"]["pageInfo"]["endCursor"].as_str().unwrap();
+    let data = schema
+        .execute(query(&format!("first: 2, after: \"{}\"", end_cursor)))
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    assert_eq!(
+        data["users"]["nodes"],
+        serde_json::json!([{ "createdAt": 2, "id": 1 }, { "createdAt": 3, "id": 1 }])
+    );
+    assert_eq!(data["users"]["pageInfo"]["hasPreviousPage"], true);
+    assert_eq!(data["users"]["pageInfo"]["hasNextPage"], true);
+
+    let start_cursor = data["users"]["pageInfo"]["startCursor"].as_str().unwrap();
+    let data = schema
+        .execute(query(&format!("last: 1, before: \"{}\"", start_cursor)))
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    assert_eq!(
+        data["users"]["nodes"],
+        serde_json::json!([{ "createdAt": 1, "id": 2 }])
+    );
+    assert_eq!(data["users"]["pageInfo"]["hasPreviousPage"], true);
+    assert_eq!(data["users"]["pageInfo"]["hasNextPage"], true);
+}
+
+#[test]
+pub fn test_connection_keyset_sql() {
+    let keyset = Keyset::new(
+        ["created_at", "id"],
+        Some(OpaqueCursor((1, 2))),
+        Some(OpaqueCursor((5, 1))),
+        None,
+        Some(10),
+    )
+    .placeholder(|n| format!("${}", n));
+    assert_eq!(
+        keyset.where_clause().as_deref(),
+        Some("(created_at, id) > ($1, $2) AND (created_at, id) < ($3, $4)")
+    );
+    assert_eq!(
+        keyset.params(),
+        vec![value!(1), value!(2), value!(5), value!(1)]
+    );
+    assert_eq!(keyset.order_by(), "created_at DESC, id DESC");
+    assert_eq!(keyset.limit(), Some(11));
+
+    let keyset = Keyset::<(i32, i32)>::new(["created_at", "id"], None, None, None, None);
+    assert_eq!(keyset.where_clause(), None);
+    assert_eq!(keyset.limit(), None);
+}
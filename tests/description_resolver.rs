@@ -0,0 +1,89 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_description_resolver() {
+    /// query.description
+    #[derive(SimpleObject)]
+    struct Query {
+        /// query.value
+        value: i32,
+    }
+
+    fn resolve(key: &str, locale: &str) -> String {
+        match (key, locale) {
+            ("query.description", "fr") => "La requête".to_string(),
+            ("query.description", _) => "The query".to_string(),
+            ("query.value", "fr") => "La valeur".to_string(),
+            ("query.value", _) => "The value".to_string(),
+            _ => key.to_string(),
+        }
+    }
+
+    let query = r#"{
+        __type(name: "Query") { description fields { description } }
+    }"#;
+
+    // The descriptions are not resolved without a locale.
+    let schema = Schema::build(Query { value: 10 }, EmptyMutation, EmptySubscription)
+        .description_resolver(resolve)
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "__type": {
+                "description": "query.description",
+                "fields": [{ "description": "query.value" }],
+            }
+        })
+    );
+
+    let schema = Schema::build(Query { value: 10 }, EmptyMutation, EmptySubscription)
+        .description_resolver(resolve)
+        .data(Locale::new("en"))
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "__type": {
+                "description": "The query",
+                "fields": [{ "description": "The value" }],
+            }
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Locale::new("fr")))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": {
+                "description": "La requête",
+                "fields": [{ "description": "La valeur" }],
+            }
+        })
+    );
+
+    assert_eq!(
+        schema.sdl_for_audience(
+            SDLExportOptions::new().prefer_single_line_descriptions(),
+            &SDLExportAudience::new().locale("fr"),
+        ),
+        r#"
+
+
+
+"La requête"
+type Query {
+	"La valeur"
+	value: Int!
+}
+
+
+schema {
+	query: Query
+}
+"#
+    );
+}
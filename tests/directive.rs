@@ -127,3 +127,83 @@ pub async fn test_custom_directive() {
         value!({ "value": "&abc*" })
     );
 }
+
+#[tokio::test]
+pub async fn test_custom_directive_hooks() {
+    struct Mask {
+        role: String,
+    }
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Mask {
+        async fn skip_field(&self, ctx: &Context<'_>) -> ServerResult<bool> {
+            Ok(ctx.data_opt::<String>() != Some(&self.role))
+        }
+    }
+
+    #[Directive(location = "Field")]
+    fn mask(role: String) -> impl CustomDirective {
+        Mask { role }
+    }
+
+    struct Translate {
+        locale: String,
+    }
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Translate {
+        async fn transform_arguments(
+            &self,
+            _ctx: &Context<'_>,
+            arguments: &mut indexmap::IndexMap<Name, Value>,
+        ) -> ServerResult<()> {
+            arguments.insert(Name::new("locale"), Value::String(self.locale.clone()));
+            Ok(())
+        }
+    }
+
+    #[Directive(location = "Field")]
+    fn translate(locale: String) -> impl CustomDirective {
+        Translate { locale }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        pub async fn greeting(&self, #[graphql(default = "en")] locale: String) -> &'static str {
+            match locale.as_str() {
+                "fr" => "Bonjour",
+                _ => "Hello",
+            }
+        }
+
+        pub async fn secret(&self) -> i32 {
+            42
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .directive(mask)
+        .directive(translate)
+        .finish();
+    let query = r#"{
+        greeting
+        translated: greeting @translate(locale: "fr")
+        secret @mask(role: "admin")
+    }"#;
+
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({ "greeting": "Hello", "translated": "Bonjour" })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data("admin".to_string()))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "greeting": "Hello", "translated": "Bonjour", "secret": 42 })
+    );
+}
@@ -92,3 +92,240 @@ pub async fn test_remote_enum() {
     let _: remote::RemoteEnum = LocalEnum::A.into();
     let _: LocalEnum = remote::RemoteEnum::A.into();
 }
+
+#[tokio::test]
+pub async fn test_enum_fallback() {
+    use async_graphql::resolver_utils::parse_enum_external;
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+    enum Status {
+        Active,
+        Suspended,
+        #[graphql(fallback)]
+        Unknown,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn status(&self, status: Status) -> Status {
+            status
+        }
+    }
+
+    // the unknown GraphQL values are rejected
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ status(status: ARCHIVED) }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "status", enumeration type "Status" does not contain the value "ARCHIVED""#
+    );
+    assert!(schema
+        .execute(
+            Request::new("query($status: Status!) { status(status: $status) }").variables(
+                Variables::from_json(serde_json::json!({ "status": "ARCHIVED" }))
+            )
+        )
+        .await
+        .is_err());
+    assert!(Status::parse(Some(Value::Enum(Name::new("ARCHIVED")))).is_err());
+
+    // the unknown external strings are mapped to the fallback value
+    assert_eq!(
+        parse_enum_external::<Status>("ARCHIVED"),
+        Some(Status::Unknown)
+    );
+    assert_eq!(
+        parse_enum_external::<Status>("ACTIVE"),
+        Some(Status::Active)
+    );
+}
+
+#[tokio::test]
+pub async fn test_enum_external_strings() {
+    use async_graphql::resolver_utils::{enum_external_str, parse_enum_external};
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+    enum Role {
+        #[graphql(external = "role_admin")]
+        Admin,
+        #[graphql(external = "role_member")]
+        User,
+        Guest,
+        #[graphql(fallback, external = "role_unknown")]
+        Other,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct Row {
+        #[serde(with = "async_graphql::resolver_utils::external_enum")]
+        role: Role,
+    }
+
+    assert_eq!(enum_external_str(Role::Admin), "role_admin");
+    assert_eq!(enum_external_str(Role::Guest), "GUEST");
+    assert_eq!(parse_enum_external::<Role>("role_member"), Some(Role::User));
+    assert_eq!(parse_enum_external::<Role>("role_owner"), Some(Role::Other));
+
+    assert_eq!(
+        serde_json::to_value(Row { role: Role::User }).unwrap(),
+        serde_json::json!({ "role": "role_member" })
+    );
+    assert_eq!(
+        serde_json::from_value::<Row>(serde_json::json!({ "role": "role_owner" })).unwrap(),
+        Row { role: Role::Other }
+    );
+
+    // the GraphQL names are unchanged
+    assert_eq!(Role::Admin.to_value(), Value::Enum(Name::new("ADMIN")));
+}
+
+#[tokio::test]
+pub async fn test_enum_int_values() {
+    use async_graphql::resolver_utils::EnumType;
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+    #[graphql(int_values)]
+    enum Priority {
+        Low = 10,
+        Normal = 20,
+        High = 30,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn priority(&self, priority: Priority) -> Priority {
+            priority
+        }
+    }
+
+    assert_eq!(Priority::Normal.as_int(), Some(20));
+    assert_eq!(Priority::from_int(30), Some(Priority::High));
+    assert_eq!(Priority::from_int(40), None);
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ a: priority(priority: LOW) b: priority(priority: 30) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": "LOW", "b": "HIGH" })
+    );
+    assert_eq!(
+        schema
+            .execute(
+                Request::new("query($p: Priority!) { priority(priority: $p) }")
+                    .variables(Variables::from_json(serde_json::json!({ "p": 20 })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "priority": "NORMAL" })
+    );
+    assert_eq!(
+        schema
+            .execute("{ priority(priority: 40) }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "priority", enumeration type "Priority" does not contain the integer value 40"#
+    );
+
+    // the integer values are exposed to the clients
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "Priority") { enumValues { name asInt } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": {
+                "enumValues": [
+                    { "name": "LOW", "asInt": 10 },
+                    { "name": "NORMAL", "asInt": 20 },
+                    { "name": "HIGH", "asInt": 30 },
+                ]
+            }
+        })
+    );
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        Red,
+    }
+
+    struct ColorQuery;
+
+    #[Object]
+    impl ColorQuery {
+        async fn color(&self, color: Color) -> Color {
+            color
+        }
+    }
+
+    // the enums without integer values don't accept integers
+    let schema = Schema::new(ColorQuery, EmptyMutation, EmptySubscription);
+    assert!(schema.execute("{ color(color: 0) }").await.is_err());
+}
+
+#[tokio::test]
+pub async fn test_enum_flags() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+    #[graphql(int_values)]
+    enum Permission {
+        Read = 1,
+        Write = 2,
+        Delete = 4,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn permissions(&self, permissions: EnumFlags<Permission>) -> EnumFlags<Permission> {
+            permissions
+        }
+
+        async fn mask(&self, permissions: EnumFlags<Permission>) -> u64 {
+            permissions.bits()
+        }
+    }
+
+    let mut flags = EnumFlags::from_iter([Permission::Read, Permission::Delete]);
+    assert_eq!(flags.bits(), 5);
+    assert!(flags.contains(Permission::Delete));
+    assert!(!flags.contains(Permission::Write));
+    flags.remove(Permission::Read);
+    flags.insert(Permission::Write);
+    assert_eq!(
+        flags.iter().collect::<Vec<_>>(),
+        vec![Permission::Write, Permission::Delete]
+    );
+    assert!(EnumFlags::<Permission>::empty().is_empty());
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema
+        .sdl()
+        .contains("permissions(permissions: [Permission!]!): [Permission!]!"));
+    assert_eq!(
+        schema
+            .execute("{ permissions(permissions: [DELETE, READ]) mask(permissions: [WRITE, 4]) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "permissions": ["READ", "DELETE"], "mask": 6 })
+    );
+}
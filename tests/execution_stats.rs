@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct Item {
+    value: i32,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn slow(&self) -> i32 {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        10
+    }
+
+    async fn items(&self) -> Vec<Item> {
+        vec![Item { value: 1 }, Item { value: 2 }]
+    }
+
+    #[graphql(cache(ttl = "10s"))]
+    async fn cached(&self) -> i32 {
+        10
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .field_cache_storage(MemoryFieldCacheStorage::new())
+        .enable_execution_stats()
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_execution_stats_disabled() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.execute("{ value }").await.execution_stats.is_none());
+}
+
+#[tokio::test]
+pub async fn test_execution_stats_resolvers() {
+    let schema = schema();
+    let stats = schema
+        .execute("{ value __typename items { value } }")
+        .await
+        .execution_stats
+        .unwrap();
+    assert_eq!(stats.resolver_count, 4);
+    assert_eq!(stats.cache_hits, 0);
+    assert_eq!(stats.dataloader_batches, 0);
+}
+
+#[tokio::test]
+pub async fn test_execution_stats_concurrency() {
+    let schema = schema();
+    let stats = schema
+        .execute("{ a: slow b: slow c: slow }")
+        .await
+        .execution_stats
+        .unwrap();
+    assert_eq!(stats.resolver_count, 3);
+    assert_eq!(stats.max_concurrency, 3);
+    assert!(stats.total_await_time >= Duration::from_millis(150));
+    assert!(stats.wall_time >= Duration::from_millis(50));
+    assert!(stats.wall_time < stats.total_await_time);
+}
+
+#[tokio::test]
+pub async fn test_execution_stats_cache_hits() {
+    let schema = schema();
+    let stats = schema.execute("{ cached }").await.execution_stats.unwrap();
+    assert_eq!(stats.cache_hits, 0);
+
+    let stats = schema.execute("{ cached }").await.execution_stats.unwrap();
+    assert_eq!(stats.cache_hits, 1);
+}
+
+#[cfg(feature = "dataloader")]
+#[tokio::test]
+pub async fn test_execution_stats_dataloader_batches() {
+    use std::collections::HashMap;
+
+    use async_graphql::dataloader::*;
+
+    #[Loader]
+    async fn load_names(ids: &[i32]) -> HashMap<i32, String> {
+        ids.iter().map(|id| (*id, id.to_string())).collect()
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn name(&self, ctx: &Context<'_>, id: i32) -> Result<Option<String>> {
+            ctx.load_one::<NamesLoader>(id).await
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(NamesLoader.data_loader(tokio::spawn))
+        .enable_execution_stats()
+        .finish();
+    let stats = schema
+        .execute("{ a: name(id: 1) b: name(id: 2) c: name(id: 3) }")
+        .await
+        .execution_stats
+        .unwrap();
+    assert_eq!(stats.resolver_count, 3);
+    assert_eq!(stats.dataloader_batches, 1);
+}
@@ -0,0 +1,216 @@
+use async_graphql::*;
+use serde_json::json;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Role {
+    Admin,
+    User,
+}
+
+/// A tree of tags
+#[derive(InputObject)]
+struct TagInput {
+    name: String,
+    children: Option<Vec<TagInput>>,
+}
+
+#[derive(OneofObject)]
+enum UserBy {
+    Id(ID),
+    Email(String),
+}
+
+/// The input to create a user
+#[derive(InputObject)]
+struct CreateUserInput {
+    /// The name of the user
+    name: String,
+    age: Option<i32>,
+    #[graphql(default = 1.5)]
+    score: f64,
+    #[graphql(default_with = "Role::User")]
+    role: Role,
+    tags: Vec<Option<TagInput>>,
+    #[graphql(secret)]
+    password: String,
+    referrer: Option<UserBy>,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// Create a user
+    async fn create_user(&self, input: CreateUserInput, #[graphql(default)] dry_run: bool) -> bool {
+        let _ = (input, dry_run);
+        true
+    }
+}
+
+#[test]
+pub fn test_input_json_schema() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    assert_eq!(
+        schema.input_json_schema("CreateUserInput").unwrap(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "CreateUserInput",
+            "type": "object",
+            "description": "The input to create a user",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "The name of the user",
+                },
+                "age": {
+                    "type": ["integer", "null"],
+                    "minimum": i32::MIN,
+                    "maximum": i32::MAX,
+                },
+                "score": { "type": "number", "default": 1.5 },
+                "role": { "$ref": "#/$defs/Role", "default": "USER" },
+                "tags": {
+                    "type": "array",
+                    "items": {
+                        "anyOf": [{ "$ref": "#/$defs/TagInput" }, { "type": "null" }],
+                    },
+                },
+                "password": { "type": "string", "writeOnly": true },
+                "referrer": {
+                    "anyOf": [{ "$ref": "#/$defs/UserBy" }, { "type": "null" }],
+                },
+            },
+            "required": ["name", "tags", "password"],
+            "additionalProperties": false,
+            "$defs": {
+                "Role": { "type": "string", "enum": ["ADMIN", "USER"] },
+                "TagInput": {
+                    "type": "object",
+                    "description": "A tree of tags",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "children": {
+                            "type": ["array", "null"],
+                            "items": { "$ref": "#/$defs/TagInput" },
+                        },
+                    },
+                    "required": ["name"],
+                    "additionalProperties": false,
+                },
+                "UserBy": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": ["string", "integer"] },
+                        "email": { "type": "string" },
+                    },
+                    "minProperties": 1,
+                    "maxProperties": 1,
+                    "additionalProperties": false,
+                },
+            },
+        })
+    );
+
+    assert!(schema.input_json_schema("Role").is_none());
+    assert!(schema.input_json_schema("Unknown").is_none());
+}
+
+#[test]
+pub fn test_arguments_json_schema() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let json_schema = schema
+        .arguments_json_schema("Mutation", "createUser")
+        .unwrap();
+    assert_eq!(json_schema["title"], json!("Mutation.createUser"));
+    assert_eq!(json_schema["description"], json!("Create a user"));
+    assert_eq!(
+        json_schema["properties"],
+        json!({
+            "input": { "$ref": "#/$defs/CreateUserInput" },
+            "dryRun": { "type": "boolean", "default": false },
+        })
+    );
+    assert_eq!(json_schema["required"], json!(["input"]));
+    assert_eq!(
+        json_schema["$defs"]["CreateUserInput"]["required"],
+        json!(["name", "tags", "password"])
+    );
+    assert!(json_schema["$defs"]["TagInput"].is_object());
+
+    assert!(schema
+        .arguments_json_schema("Mutation", "unknown")
+        .is_none());
+}
+
+#[test]
+pub fn test_json_schema_visibility() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Plan {
+        Free,
+        #[graphql(visible = false)]
+        Internal,
+        #[graphql(inaccessible)]
+        Legacy,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(visible = false)]
+    struct AuditInput {
+        reason: String,
+    }
+
+    #[derive(InputObject)]
+    struct UpdateInput {
+        plan: Plan,
+        #[graphql(visible = false)]
+        internal_note: Option<String>,
+        #[graphql(inaccessible)]
+        legacy_id: Option<i32>,
+        #[graphql(visible("internal"))]
+        admin_flag: Option<bool>,
+        audit: Option<AuditInput>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn update(&self, input: UpdateInput) -> bool {
+            let _ = input;
+            true
+        }
+
+        #[graphql(visible = false)]
+        async fn audit(&self, input: AuditInput) -> bool {
+            let _ = input;
+            true
+        }
+    }
+
+    // the fields are exported for the first visibility profile
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .visibility_profiles(["public", "internal"])
+        .finish();
+    let json_schema = schema.input_json_schema("UpdateInput").unwrap();
+    assert_eq!(
+        json_schema["properties"],
+        json!({ "plan": { "$ref": "#/$defs/Plan" } })
+    );
+    assert_eq!(
+        json_schema["$defs"],
+        json!({ "Plan": { "type": "string", "enum": ["FREE"] } })
+    );
+
+    assert!(schema.input_json_schema("AuditInput").is_none());
+    assert!(schema.arguments_json_schema("Query", "audit").is_none());
+    assert!(schema.arguments_json_schema("Query", "update").is_some());
+}
@@ -0,0 +1,319 @@
+use async_graphql::*;
+
+/// A product
+#[derive(SimpleObject)]
+#[graphql(tag = "public")]
+struct Product {
+    id: ID,
+    /// The name of the product
+    name: String,
+    #[graphql(tag = "internal")]
+    cost: i32,
+}
+
+#[derive(SimpleObject)]
+struct Stats {
+    count: i32,
+}
+
+#[derive(SimpleObject)]
+#[graphql(visible("internal"))]
+struct AdminInfo {
+    secret: String,
+}
+
+struct Query;
+
+#[Object]
+#[allow(unreachable_code)]
+impl Query {
+    /// Find a product
+    #[graphql(tag = "public")]
+    async fn product(&self, id: ID) -> Product {
+        Product {
+            id,
+            name: "Book".to_string(),
+            cost: 10,
+        }
+    }
+
+    async fn stats(&self) -> Stats {
+        todo!()
+    }
+
+    #[graphql(visible = false)]
+    async fn internal_version(&self) -> i32 {
+        todo!()
+    }
+
+    async fn admin(&self) -> AdminInfo {
+        todo!()
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .visibility_profiles(["public", "internal"])
+        .finish()
+}
+
+#[test]
+fn test_sdl_strip_descriptions() {
+    let sdl = schema().sdl_with_options(SDLExportOptions::new().strip_descriptions());
+    assert!(schema().sdl().contains("A product"));
+    assert!(!sdl.contains("A product"));
+    assert!(!sdl.contains("Find a product"));
+    assert!(!sdl.contains("\"\"\""));
+}
+
+#[test]
+fn test_sdl_include_tag() {
+    let sdl = schema().sdl_for_audience(
+        SDLExportOptions::new().strip_descriptions(),
+        &SDLExportAudience::new().include_tag("public"),
+    );
+    assert_eq!(
+        sdl,
+        r#"
+
+
+type Product {
+	id: ID!
+	name: String!
+	cost: Int!
+}
+
+type Query {
+	product(id: ID!): Product!
+}
+
+
+schema {
+	query: Query
+}
+"#
+    );
+}
+
+#[test]
+fn test_sdl_exclude_tag_and_prefix() {
+    let sdl = schema().sdl_for_audience(
+        SDLExportOptions::new().strip_descriptions(),
+        &SDLExportAudience::new()
+            .exclude_tag("internal")
+            .exclude_prefix("internal")
+            .exclude_prefix("Stats"),
+    );
+    assert_eq!(
+        sdl,
+        r#"type AdminInfo {
+	secret: String!
+}
+
+
+
+
+type Product {
+	id: ID!
+	name: String!
+}
+
+type Query {
+	product(id: ID!): Product!
+	admin: AdminInfo!
+}
+
+
+schema {
+	query: Query
+}
+"#
+    );
+}
+
+#[test]
+fn test_sdl_visibility_profile() {
+    let schema = schema();
+
+    let sdl = schema.sdl_for_audience(
+        SDLExportOptions::new(),
+        &SDLExportAudience::new().visibility_profile("public"),
+    );
+    assert!(!sdl.contains("AdminInfo"));
+    assert!(!sdl.contains("admin"));
+    assert!(!sdl.contains("internalVersion"));
+
+    let sdl = schema.sdl_for_audience(
+        SDLExportOptions::new(),
+        &SDLExportAudience::new().visibility_profile("internal"),
+    );
+    assert!(sdl.contains("type AdminInfo"));
+    assert!(sdl.contains("admin: AdminInfo!"));
+    assert!(!sdl.contains("internalVersion"));
+
+    // Without visibility profile, all types are exported
+    assert!(schema.sdl().contains("type AdminInfo"));
+    assert!(schema.sdl().contains("internalVersion"));
+}
+
+#[test]
+fn test_sdl_inline_extends() {
+    #[derive(SimpleObject)]
+    #[graphql(extends)]
+    struct User {
+        #[graphql(external)]
+        id: ID,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User { id }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().federation())
+        .contains("extend type User @key(fields: \"id\") {"));
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().federation().inline_extends());
+    assert!(sdl.contains("\ntype User @key(fields: \"id\") {"));
+    assert!(!sdl.contains("extend type"));
+}
+
+#[test]
+fn test_sdl_contract() {
+    #[derive(SimpleObject)]
+    #[graphql(tag = "partner")]
+    struct Order {
+        id: ID,
+        #[graphql(tag = "internal")]
+        margin: i32,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(tag = "partner")]
+    struct Warehouse {
+        #[graphql(tag = "internal")]
+        location: String,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(tag = "partner")]
+    enum Region {
+        #[graphql(tag = "internal")]
+        Internal,
+    }
+
+    #[derive(Union)]
+    #[graphql(tag = "partner")]
+    enum SearchResult {
+        Warehouse(Warehouse),
+    }
+
+    #[derive(InputObject)]
+    #[graphql(tag = "partner")]
+    struct OrderFilter {
+        status: Option<String>,
+        #[graphql(tag = "internal")]
+        shard: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    #[allow(unreachable_code)]
+    impl Query {
+        #[graphql(tag = "partner")]
+        async fn orders(&self, _filter: Option<OrderFilter>) -> Vec<Order> {
+            todo!()
+        }
+
+        #[graphql(tag = "partner")]
+        async fn order(&self, _id: ID, _filter: OrderFilter) -> Order {
+            todo!()
+        }
+
+        #[graphql(tag = "partner")]
+        async fn warehouse(&self) -> Warehouse {
+            todo!()
+        }
+
+        #[graphql(tag = "partner")]
+        async fn region(&self) -> Region {
+            todo!()
+        }
+
+        #[graphql(tag = "partner")]
+        async fn search(&self) -> Vec<SearchResult> {
+            todo!()
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    #[allow(unreachable_code)]
+    impl Mutation {
+        #[graphql(tag = "internal")]
+        async fn cancel_order(&self, _id: ID) -> bool {
+            todo!()
+        }
+    }
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let sdl = schema.sdl_for_audience(
+        SDLExportOptions::new(),
+        &SDLExportAudience::new().contract(["partner"], ["internal"]),
+    );
+    assert_eq!(
+        sdl,
+        r#"
+
+type Order {
+	id: ID!
+}
+
+type Query {
+	orders: [Order!]!
+}
+
+schema {
+	query: Query
+}
+"#
+    );
+}
+
+#[test]
+fn test_write_sdl() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let out_dir = std::env::temp_dir().join(format!("async-graphql-sdl-{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let path = out_dir.join("schema.graphql");
+
+    schema.write_sdl(&path, SDLExportOptions::new()).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), schema.sdl());
+
+    let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+    schema.write_sdl(&path, SDLExportOptions::new()).unwrap();
+    assert_eq!(
+        std::fs::metadata(&path).unwrap().modified().unwrap(),
+        modified
+    );
+
+    std::fs::remove_dir_all(&out_dir).unwrap();
+}
@@ -0,0 +1,167 @@
+use async_graphql::*;
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+struct Product {
+    id: i32,
+}
+
+#[ComplexObject]
+impl Product {
+    async fn double_id(&self) -> i32 {
+        self.id * 2
+    }
+}
+
+#[derive(SimpleObject)]
+struct Review {
+    body: String,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn product(&self) -> Product {
+        Product { id: 1 }
+    }
+
+    async fn products(&self) -> Vec<Product> {
+        vec![Product { id: 1 }, Product { id: 2 }]
+    }
+}
+
+#[derive(ExtendObject)]
+struct ProductReviews(Product);
+
+#[Object]
+impl ProductReviews {
+    async fn reviews(&self, #[graphql(default = 1)] limit: usize) -> Vec<Review> {
+        (0..limit)
+            .map(|idx| Review {
+                body: format!("review {} of {}", idx, self.0.id),
+            })
+            .collect()
+    }
+}
+
+struct ProductPrice {
+    product_id: i32,
+}
+
+impl ObjectExtension for ProductPrice {
+    type Target = Product;
+
+    fn extend(target: &Product) -> Self {
+        Self {
+            product_id: target.id,
+        }
+    }
+}
+
+#[Object]
+impl ProductPrice {
+    async fn price(&self) -> f64 {
+        self.product_id as f64 * 1.5
+    }
+}
+
+struct QueryVersion;
+
+impl ObjectExtension for QueryVersion {
+    type Target = Query;
+
+    fn extend(_target: &Query) -> Self {
+        QueryVersion
+    }
+}
+
+#[Object]
+impl QueryVersion {
+    async fn version(&self) -> &str {
+        "1.0"
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extend_object::<ProductReviews>()
+        .extend_object::<ProductPrice>()
+        .extend_object::<QueryVersion>()
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_extend_object() {
+    let schema = schema();
+    let query = r#"{
+        version
+        product { id doubleId price reviews(limit: 2) { body } }
+        products { id reviews { body } }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "version": "1.0",
+            "product": {
+                "id": 1,
+                "doubleId": 2,
+                "price": 1.5,
+                "reviews": [{ "body": "review 0 of 1" }, { "body": "review 1 of 1" }],
+            },
+            "products": [
+                { "id": 1, "reviews": [{ "body": "review 0 of 1" }] },
+                { "id": 2, "reviews": [{ "body": "review 0 of 2" }] },
+            ],
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_extend_object_introspection() {
+    let schema = schema();
+    let sdl = schema.sdl();
+    assert!(sdl.contains("reviews(limit: Int! = 1): [Review!]!"));
+    assert!(sdl.contains("price: Float!"));
+    assert!(sdl.contains("version: String!"));
+    assert!(!sdl.contains("ProductReviews"));
+    assert!(!sdl.contains("ProductPrice"));
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "Product") { fields { name } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": {
+                "fields": [
+                    { "name": "id" },
+                    { "name": "doubleId" },
+                    { "name": "reviews" },
+                    { "name": "price" },
+                ]
+            }
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ product { reviews(limit: \"a\") { body } } }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "limit", expected type "Int""#
+    );
+}
+
+#[test]
+#[should_panic(expected = r#"Field "Product.reviews" already exists"#)]
+pub fn test_extend_object_conflict() {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extend_object::<ProductReviews>()
+        .extend_object::<ProductReviews>()
+        .finish();
+}
@@ -6,7 +6,7 @@ use async_graphql::{
         NextPrepareRequest, NextRequest, NextResolve, NextSubscribe, NextValidation, ResolveInfo,
     },
     futures_util::stream::BoxStream,
-    parser::types::ExecutableDocument,
+    parser::types::{ExecutableDocument, OperationDefinition, OperationType},
     *,
 };
 use async_graphql_value::ConstValue;
@@ -329,3 +329,179 @@ pub async fn test_extension_call_order() {
         );
     }
 }
+
+#[tokio::test]
+pub async fn test_validation_did_finish() {
+    struct MyExtensionImpl {
+        operations: Arc<Mutex<Vec<(Option<String>, usize)>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for MyExtensionImpl {
+        async fn validation_did_finish(
+            &self,
+            _ctx: &ExtensionContext<'_>,
+            document: &ExecutableDocument,
+            operation_name: Option<&str>,
+            operation: &Positioned<OperationDefinition>,
+            _variables: &Variables,
+        ) -> ServerResult<()> {
+            self.operations.lock().await.push((
+                operation_name.map(ToString::to_string),
+                document.operations.iter().count(),
+            ));
+            if operation.node.ty == OperationType::Query && operation_name.is_none() {
+                return Err(ServerError::new(
+                    "Anonymous queries are not allowed.",
+                    Some(operation.pos),
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    struct MyExtension {
+        operations: Arc<Mutex<Vec<(Option<String>, usize)>>>,
+    }
+
+    impl ExtensionFactory for MyExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(MyExtensionImpl {
+                operations: self.operations.clone(),
+            })
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let operations: Arc<Mutex<Vec<(Option<String>, usize)>>> = Default::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(MyExtension {
+            operations: operations.clone(),
+        })
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute(Request::new("query A { value } query B { value }").operation_name("B"))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10 })
+    );
+    assert_eq!(
+        schema.execute("{ value }").await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Anonymous queries are not allowed.".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 1 }],
+            path: Default::default(),
+            extensions: None,
+        }]
+    );
+    // Queries that fail the validation are rejected before the hook is called.
+    assert!(schema
+        .execute("{ value(a: 1) }")
+        .await
+        .into_result()
+        .is_err());
+    assert_eq!(
+        &*operations.lock().await,
+        &[(Some("B".to_string()), 2), (None, 1)]
+    );
+}
+
+#[tokio::test]
+pub async fn test_extension_enabled_per_request() {
+    struct MarkerExtensionImpl(&'static str);
+
+    #[async_trait::async_trait]
+    impl Extension for MarkerExtensionImpl {
+        async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+            let mut resp = next.run(ctx).await;
+            resp.extensions
+                .insert(self.0.to_string(), ConstValue::Boolean(true));
+            resp
+        }
+    }
+
+    struct First;
+
+    impl ExtensionFactory for First {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(MarkerExtensionImpl("first"))
+        }
+    }
+
+    struct Second;
+
+    impl ExtensionFactory for Second {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(MarkerExtensionImpl("second"))
+        }
+    }
+
+    struct DebugHeader;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(First)
+        .extension_if(Second, |request| {
+            request
+                .data
+                .contains_key(&std::any::TypeId::of::<DebugHeader>())
+        })
+        .finish();
+
+    let extensions = |resp: Response| resp.extensions.into_keys().collect::<Vec<_>>();
+
+    assert_eq!(extensions(schema.execute("{ value }").await), vec!["first"]);
+    assert_eq!(
+        extensions(
+            schema
+                .execute(Request::new("{ value }").data(DebugHeader))
+                .await
+        ),
+        vec!["first", "second"]
+    );
+    assert_eq!(
+        extensions(
+            schema
+                .execute(
+                    Request::new("{ value }")
+                        .data(DebugHeader)
+                        .disable_extension::<First>()
+                )
+                .await
+        ),
+        vec!["second"]
+    );
+    assert_eq!(
+        extensions(
+            schema
+                .execute(
+                    Request::new("{ value }")
+                        .data(DebugHeader)
+                        .disable_extension::<Second>()
+                )
+                .await
+        ),
+        vec!["first"]
+    );
+}
@@ -30,10 +30,20 @@ pub async fn test_nested_key() {
     struct Query;
 
     #[derive(SimpleObject)]
-    struct MyObj {
+    struct MyObjInputA {
         a: i32,
         b: i32,
-        c: i32,
+        c: MyObjInputB,
+    }
+
+    #[derive(SimpleObject)]
+    struct MyObjInputB {
+        v: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct MyObj {
+        input: MyObjInputA,
     }
 
     #[Object]
@@ -41,9 +51,11 @@ pub async fn test_nested_key() {
         #[graphql(entity)]
         async fn find_obj(&self, input: MyInputA) -> MyObj {
             MyObj {
-                a: input.a,
-                b: input.b,
-                c: input.c.v,
+                input: MyObjInputA {
+                    a: input.a,
+                    b: input.b,
+                    c: MyObjInputB { v: input.c.v },
+                },
             }
         }
     }
@@ -53,7 +65,7 @@ pub async fn test_nested_key() {
             _entities(representations: [{__typename: "MyObj", input: {a: 1, b: 2, c: { v: 3 }}}]) {
                 __typename
                 ... on MyObj {
-                    a b c
+                    input { a b c { v } }
                 }
             }
         }"#;
@@ -61,7 +73,7 @@ pub async fn test_nested_key() {
         schema.execute(query).await.into_result().unwrap().data,
         value!({
             "_entities": [
-                {"__typename": "MyObj", "a": 1, "b": 2, "c": 3},
+                {"__typename": "MyObj", "input": {"a": 1, "b": 2, "c": {"v": 3}}},
             ]
         })
     );
@@ -248,16 +260,105 @@ pub async fn test_find_entity_with_context() {
                 line: 2,
                 column: 13
             }],
-            path: vec![PathSegment::Field("_entities".to_owned())],
+            path: vec![
+                PathSegment::Field("_entities".to_owned()),
+                PathSegment::Index(0)
+            ],
             extensions: None,
         }]
     );
 }
 
+#[tokio::test]
+pub async fn test_entity_resolvers() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+    }
+
+    #[derive(SimpleObject)]
+    struct Product {
+        upc: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User { id }
+        }
+    }
+
+    struct ProductEntities {
+        prefix: String,
+    }
+
+    #[Object]
+    impl ProductEntities {
+        #[graphql(entity)]
+        async fn find_product_by_upc(&self, upc: String) -> Product {
+            Product {
+                upc: format!("{}{}", self.prefix, upc),
+            }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .entity_resolvers(ProductEntities {
+            prefix: "p-".to_string(),
+        })
+        .finish();
+    let query = r#"{
+            _entities(representations: [
+                {__typename: "User", id: "1"},
+                {__typename: "Product", upc: "2"}
+            ]) {
+                __typename
+                ... on User { id }
+                ... on Product { upc }
+            }
+        }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "_entities": [
+                {"__typename": "User", "id": "1"},
+                {"__typename": "Product", "upc": "p-2"},
+            ]
+        })
+    );
+
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().federation());
+    assert!(sdl.contains("type Product @key(fields: \"upc\")"));
+    assert!(!sdl.contains("ProductEntities"));
+
+    let query = r#"{
+            __type(name: "_Entity") { possibleTypes { name } }
+        }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "__type": {
+                "possibleTypes": [
+                    {"name": "Product"},
+                    {"name": "User"},
+                ]
+            }
+        })
+    );
+}
+
 #[tokio::test]
 pub async fn test_entity_union() {
     #[derive(SimpleObject)]
     struct MyObj {
+        id: i32,
         a: i32,
     }
 
@@ -291,6 +392,7 @@ pub async fn test_entity_union() {
 pub async fn test_entity_shareable() {
     #[derive(SimpleObject)]
     struct MyObjFieldShareable {
+        id: i32,
         #[graphql(shareable)]
         field_shareable_a: i32,
     }
@@ -298,6 +400,7 @@ pub async fn test_entity_shareable() {
     #[derive(SimpleObject)]
     #[graphql(shareable)]
     struct MyObjShareable {
+        id: i32,
         a: i32,
     }
 
@@ -326,6 +429,7 @@ pub async fn test_entity_shareable() {
 pub async fn test_field_override_directive() {
     #[derive(SimpleObject)]
     struct MyObjFieldOverride {
+        id: i32,
         #[graphql(override_from = "AnotherSubgraph")]
         field_override_a: i32,
     }
@@ -363,6 +467,7 @@ pub async fn test_entity_inaccessible() {
 
     #[derive(SimpleObject)]
     struct MyObjFieldInaccessible {
+        id: i32,
         #[graphql(inaccessible)]
         obj_field_inaccessible_a: i32,
     }
@@ -370,6 +475,7 @@ pub async fn test_entity_inaccessible() {
     #[derive(SimpleObject)]
     #[graphql(inaccessible)]
     struct MyObjInaccessible {
+        id: i32,
         a: i32,
     }
 
@@ -641,6 +747,7 @@ pub async fn test_entity_tag() {
 
     #[derive(SimpleObject)]
     struct MyObjFieldTagged {
+        id: i32,
         #[graphql(tag = "tagged_field")]
         obj_field_tagged_a: i32,
     }
@@ -648,6 +755,7 @@ pub async fn test_entity_tag() {
     #[derive(SimpleObject)]
     #[graphql(tag = "tagged_simple_object")]
     struct MyObjTagged {
+        id: i32,
         a: i32,
     }
 
@@ -826,3 +934,267 @@ pub async fn test_entity_tag() {
         panic!("schema was not up-to-date. rerun")
     }
 }
+
+#[tokio::test]
+pub async fn test_entities_partial_results() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> Result<User> {
+            if id.as_str() == "0" {
+                return Err("invalid id".into());
+            }
+            Ok(User { id })
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+            _entities(representations: [
+                {__typename: "User", id: "1"},
+                {__typename: "User", id: "0"},
+                {__typename: "Product", upc: "1"},
+            ]) {
+                ... on User { id }
+            }
+        }"#;
+    let resp = schema.execute(query).await;
+    assert_eq!(
+        resp.data,
+        value!({ "_entities": [{ "id": "1" }, null, null] })
+    );
+
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set(
+        "representation",
+        value!({ "__typename": "Product", "upc": "1" }),
+    );
+    assert_eq!(
+        resp.errors,
+        vec![
+            ServerError {
+                message: "invalid id".to_string(),
+                source: None,
+                locations: vec![Pos {
+                    line: 2,
+                    column: 13
+                }],
+                path: vec![
+                    PathSegment::Field("_entities".to_string()),
+                    PathSegment::Index(1)
+                ],
+                extensions: None,
+            },
+            ServerError {
+                message: "Entity not found.".to_string(),
+                source: None,
+                locations: vec![Pos {
+                    line: 2,
+                    column: 13
+                }],
+                path: vec![
+                    PathSegment::Field("_entities".to_string()),
+                    PathSegment::Index(2)
+                ],
+                extensions: Some(extensions),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_unknown_entity_hook() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User { id }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .on_unknown_entity(|_ctx, representation| {
+            Box::pin(async move {
+                let mut representation = match representation {
+                    Value::Object(representation) => representation.clone(),
+                    _ => return Ok(None),
+                };
+                match representation.get("__typename") {
+                    Some(Value::String(type_name)) if type_name == "Account" => {
+                        representation.insert(Name::new("__typename"), value!("User"));
+                        Ok(Some(Value::Object(representation)))
+                    }
+                    Some(Value::String(type_name)) if type_name == "Stale" => Ok(None),
+                    _ => Err("Unknown entity".into()),
+                }
+            })
+        })
+        .finish();
+    let query = r#"{
+            _entities(representations: [
+                {__typename: "Account", id: "1"},
+                {__typename: "User", id: "2"},
+                {__typename: "Stale", id: "3"},
+                {__typename: "Product", upc: "4"},
+            ]) {
+                ... on User { id }
+            }
+        }"#;
+    let resp = schema.execute(query).await;
+    assert_eq!(
+        resp.data,
+        value!({ "_entities": [{ "id": "1" }, { "id": "2" }, null, null] })
+    );
+    assert_eq!(
+        resp.errors,
+        vec![ServerError {
+            message: "Unknown entity".to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 2,
+                column: 13
+            }],
+            path: vec![
+                PathSegment::Field("_entities".to_string()),
+                PathSegment::Index(3)
+            ],
+            extensions: None,
+        }]
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = r#"Invalid @requires(fields: "weight") on field "Product.shippingEstimate": field "weight" does not exist on type "Product"."#
+)]
+pub fn test_invalid_requires() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct Product {
+        upc: String,
+    }
+
+    #[ComplexObject]
+    impl Product {
+        #[graphql(requires = "weight")]
+        async fn shipping_estimate(&self) -> i32 {
+            0
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_product(&self, upc: String) -> Product {
+            Product { upc }
+        }
+    }
+
+    let _ = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation()
+        .finish();
+}
+
+#[test]
+#[should_panic(
+    expected = r#"Invalid @provides(fields: "reviews") on field "Query.topProduct": field "Product.reviews" of type "Review" must have a selection of subfields."#
+)]
+pub fn test_invalid_provides() {
+    #[derive(SimpleObject)]
+    struct Review {
+        body: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Product {
+        upc: String,
+        #[graphql(external)]
+        reviews: Vec<Review>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(provides = "reviews")]
+        async fn top_product(&self) -> Product {
+            todo!()
+        }
+
+        #[graphql(entity)]
+        async fn find_product(&self, upc: String) -> Product {
+            Product {
+                upc,
+                reviews: Vec::new(),
+            }
+        }
+    }
+
+    let _ = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation()
+        .finish();
+}
+
+#[test]
+#[should_panic(
+    expected = r#"Invalid @key(fields: "sku") on type "Product": field "sku" does not exist on type "Product"."#
+)]
+pub fn test_invalid_key() {
+    #[derive(SimpleObject)]
+    struct Product {
+        upc: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_product(&self, sku: String) -> Product {
+            Product { upc: sku }
+        }
+    }
+
+    let _ = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation()
+        .finish();
+}
+
+#[test]
+#[should_panic(
+    expected = r#"Invalid @key(fields: "sku") on type "Product": field "sku" does not exist on type "Product"."#
+)]
+pub fn test_invalid_key_without_enable_federation() {
+    #[derive(SimpleObject)]
+    struct Product {
+        upc: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_product(&self, sku: String) -> Product {
+            Product { upc: sku }
+        }
+    }
+
+    let _ = Schema::build(Query, EmptyMutation, EmptySubscription).finish();
+}
@@ -0,0 +1,101 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+#[graphql(cache_control(max_age = 60))]
+struct User {
+    id: ID,
+    name: String,
+    #[graphql(cache_control(max_age = 10))]
+    status: String,
+}
+
+#[derive(SimpleObject)]
+struct Product {
+    upc: String,
+    sku: String,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    #[graphql(entity)]
+    async fn find_user_by_id(&self, id: ID) -> User {
+        User {
+            name: format!("user{}", id.as_str()),
+            status: "online".to_string(),
+            id,
+        }
+    }
+
+    #[graphql(entity)]
+    async fn find_product(&self, upc: String, sku: String) -> Product {
+        Product { upc, sku }
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation()
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_entity_cache_tags() {
+    let query = r#"{
+        _entities(representations: [
+            {__typename: "User", id: "1"},
+            {__typename: "Product", upc: "a", sku: "b"},
+            {__typename: "User", id: "2"},
+        ]) {
+            ... on User { name }
+            ... on Product { upc }
+        }
+    }"#;
+    let resp = schema().execute(query).await;
+    assert!(resp.is_ok());
+    assert_eq!(
+        resp.extensions["apolloEntityCacheTags"],
+        value!([[r#"User:{"id":"1"}"#], [], [r#"User:{"id":"2"}"#]])
+    );
+    assert_eq!(resp.cache_control.value().as_deref(), Some("max-age=60"));
+
+    let query = r#"{
+        _entities(representations: [{__typename: "User", id: "1"}]) {
+            ... on User { name status }
+        }
+    }"#;
+    let resp = schema().execute(query).await;
+    assert_eq!(
+        resp.extensions["apolloEntityCacheTags"],
+        value!([[r#"User:{"id":"1"}"#]])
+    );
+    assert_eq!(resp.cache_control.value().as_deref(), Some("max-age=10"));
+}
+
+#[tokio::test]
+pub async fn test_entity_cache_tags_key_fields() {
+    let query = r#"{
+        _entities(representations: [{__typename: "User", id: "1", name: "a"}]) {
+            ...UserFields
+        }
+    }
+    fragment UserFields on User { id }"#;
+    let resp = schema().execute(query).await;
+    assert_eq!(
+        resp.extensions["apolloEntityCacheTags"],
+        value!([[r#"User:{"id":"1"}"#]])
+    );
+}
+
+#[tokio::test]
+pub async fn test_entity_cache_tags_not_cacheable() {
+    let query = r#"{
+        _entities(representations: [{__typename: "Product", upc: "a", sku: "b"}]) {
+            ... on Product { upc }
+        }
+    }"#;
+    let resp = schema().execute(query).await;
+    assert!(resp.is_ok());
+    assert!(!resp.extensions.contains_key("apolloEntityCacheTags"));
+}
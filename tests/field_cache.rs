@@ -0,0 +1,224 @@
+use std::{
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_graphql::*;
+
+#[derive(Default)]
+struct Counters {
+    total: AtomicI32,
+    by_year: AtomicI32,
+    unkeyed: AtomicI32,
+    failing: AtomicI32,
+    slow: AtomicI32,
+    obj: AtomicI32,
+}
+
+fn counters<'a>(ctx: &Context<'a>) -> &'a Counters {
+    ctx.data_unchecked::<Arc<Counters>>()
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+struct Stats {
+    year: i32,
+}
+
+#[ComplexObject]
+impl Stats {
+    #[graphql(cache(ttl = "10s"))]
+    async fn count(&self, ctx: &Context<'_>) -> i32 {
+        counters(ctx).obj.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    #[graphql(cache(ttl = "10s"))]
+    async fn total(&self, ctx: &Context<'_>) -> i32 {
+        counters(ctx).total.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    #[graphql(cache(ttl = "10s"))]
+    async fn by_year(&self, ctx: &Context<'_>, year: i32) -> Stats {
+        counters(ctx).by_year.fetch_add(1, Ordering::SeqCst);
+        Stats { year }
+    }
+
+    async fn years(&self) -> Vec<Stats> {
+        vec![Stats { year: 2022 }, Stats { year: 2023 }]
+    }
+
+    #[graphql(cache(ttl = "10s", key = "none"))]
+    async fn unkeyed(&self, ctx: &Context<'_>, year: i32) -> i32 {
+        counters(ctx).unkeyed.fetch_add(1, Ordering::SeqCst);
+        year
+    }
+
+    #[graphql(cache(ttl = "10s"))]
+    async fn failing(&self, ctx: &Context<'_>) -> Result<i32> {
+        counters(ctx).failing.fetch_add(1, Ordering::SeqCst);
+        Err("failed".into())
+    }
+
+    #[graphql(cache(ttl = "50ms", swr = "10s"))]
+    async fn slow(&self, ctx: &Context<'_>) -> i32 {
+        let value = counters(ctx).slow.fetch_add(1, Ordering::SeqCst) + 1;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        value
+    }
+}
+
+fn schema(counters: Arc<Counters>) -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(counters)
+        .field_cache_storage(MemoryFieldCacheStorage::new())
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_field_cache() {
+    let counters = Arc::new(Counters::default());
+    let schema = schema(counters.clone());
+
+    for _ in 0..2 {
+        assert_eq!(
+            schema.execute("{ total }").await.data,
+            value!({ "total": 1 })
+        );
+    }
+
+    let query = "query($year: Int!) { byYear(year: $year) { year } }";
+    for year in [2022, 2023, 2022] {
+        let request =
+            Request::new(query).variables(Variables::from_value(value!({ "year": year })));
+        assert_eq!(
+            schema.execute(request).await.data,
+            value!({ "byYear": { "year": year } })
+        );
+    }
+    assert_eq!(counters.by_year.load(Ordering::SeqCst), 2);
+
+    // A different selection set is cached separately.
+    assert_eq!(
+        schema
+            .execute("{ byYear(year: 2022) { year count } }")
+            .await
+            .data,
+        value!({ "byYear": { "year": 2022, "count": 1 } })
+    );
+    assert_eq!(counters.by_year.load(Ordering::SeqCst), 3);
+
+    // The cached fields of nested objects are cached for each parent.
+    for _ in 0..2 {
+        assert_eq!(
+            schema
+                .execute("{ byYear(year: 2023) { count } }")
+                .await
+                .data,
+            value!({ "byYear": { "count": 2 } })
+        );
+    }
+    assert_eq!(counters.obj.load(Ordering::SeqCst), 2);
+
+    for year in [2022, 2023] {
+        assert_eq!(
+            schema
+                .execute(format!("{{ unkeyed(year: {}) }}", year))
+                .await
+                .data,
+            value!({ "unkeyed": 2022 })
+        );
+    }
+    assert_eq!(counters.unkeyed.load(Ordering::SeqCst), 1);
+
+    for _ in 0..2 {
+        assert!(schema.execute("{ failing }").await.is_err());
+    }
+    assert_eq!(counters.failing.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+pub async fn test_field_cache_in_list() {
+    let counters = Arc::new(Counters::default());
+    let schema = schema(counters.clone());
+
+    for _ in 0..2 {
+        assert_eq!(
+            schema.execute("{ years { year count } }").await.data,
+            value!({
+                "years": [
+                    { "year": 2022, "count": 1 },
+                    { "year": 2023, "count": 2 },
+                ]
+            })
+        );
+    }
+    assert_eq!(counters.obj.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+pub async fn test_field_cache_stale_while_revalidate() {
+    let counters = Arc::new(Counters::default());
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(counters.clone())
+        .field_cache_storage(MemoryFieldCacheStorage::new())
+        .field_cache_spawner(tokio::spawn)
+        .finish();
+
+    assert_eq!(schema.execute("{ slow }").await.data, value!({ "slow": 1 }));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // The stale value is returned while it is refreshed in the background.
+    for _ in 0..2 {
+        assert_eq!(schema.execute("{ slow }").await.data, value!({ "slow": 1 }));
+    }
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    assert_eq!(schema.execute("{ slow }").await.data, value!({ "slow": 2 }));
+    assert_eq!(counters.slow.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+pub async fn test_field_cache_stale_while_revalidate_without_spawner() {
+    let counters = Arc::new(Counters::default());
+    let schema = schema(counters.clone());
+
+    assert_eq!(schema.execute("{ slow }").await.data, value!({ "slow": 1 }));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // The first request refreshes the stale value, the other one receives it.
+    let (a, b) = futures_util::future::join(schema.execute("{ slow }"), async {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        schema.execute("{ slow }").await
+    })
+    .await;
+    assert_eq!(a.data, value!({ "slow": 2 }));
+    assert_eq!(b.data, value!({ "slow": 1 }));
+
+    assert_eq!(schema.execute("{ slow }").await.data, value!({ "slow": 2 }));
+    assert_eq!(counters.slow.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+pub async fn test_field_cache_without_storage() {
+    let counters = Arc::new(Counters::default());
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(counters.clone())
+        .finish();
+
+    assert_eq!(
+        schema.execute("{ total }").await.data,
+        value!({ "total": 1 })
+    );
+    assert_eq!(
+        schema.execute("{ total }").await.data,
+        value!({ "total": 2 })
+    );
+}
@@ -0,0 +1,101 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use async_graphql::*;
+
+fn is_retryable(err: &ServerError) -> bool {
+    err.message == "unavailable"
+}
+
+#[tokio::test]
+pub async fn test_field_retry() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct MyObj {
+        a: i32,
+    }
+
+    #[ComplexObject]
+    impl MyObj {
+        #[graphql(retry(times = 2))]
+        async fn b(&self, ctx: &Context<'_>) -> Result<i32> {
+            let attempts = &ctx.data_unchecked::<Arc<Attempts>>().obj;
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                return Err("unavailable".into());
+            }
+            Ok(self.a)
+        }
+    }
+
+    #[derive(Default)]
+    struct Attempts {
+        value: AtomicUsize,
+        obj: AtomicUsize,
+        exhausted: AtomicUsize,
+        rejected: AtomicUsize,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(retry(times = 3, backoff = "10ms", jitter))]
+        async fn value(&self, ctx: &Context<'_>, n: i32) -> Result<i32> {
+            let attempts = &ctx.data_unchecked::<Arc<Attempts>>().value;
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                return Err("unavailable".into());
+            }
+            Ok(n)
+        }
+
+        #[graphql(retry(times = 2, backoff = "1ms"))]
+        async fn exhausted(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+            ctx.data_unchecked::<Arc<Attempts>>()
+                .exhausted
+                .fetch_add(1, Ordering::SeqCst);
+            Err("unavailable".into())
+        }
+
+        #[graphql(retry(times = 3, retry_if = "is_retryable"))]
+        async fn rejected(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+            ctx.data_unchecked::<Arc<Attempts>>()
+                .rejected
+                .fetch_add(1, Ordering::SeqCst);
+            Err("forbidden".into())
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj { a: 30 }
+        }
+    }
+
+    let attempts = Arc::new(Attempts::default());
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(attempts.clone())
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ value(n: 10) obj { b } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10, "obj": { "b": 30 } })
+    );
+
+    let errors = schema.execute("{ exhausted }").await.errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "unavailable");
+
+    let errors = schema.execute("{ rejected }").await.errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "forbidden");
+
+    assert_eq!(attempts.value.load(Ordering::SeqCst), 3);
+    assert_eq!(attempts.obj.load(Ordering::SeqCst), 3);
+    assert_eq!(attempts.exhausted.load(Ordering::SeqCst), 3);
+    assert_eq!(attempts.rejected.load(Ordering::SeqCst), 1);
+}
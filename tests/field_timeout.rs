@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_field_timeout() {
+    #[derive(SimpleObject)]
+    #[graphql(complex)]
+    struct MyObj {
+        a: i32,
+    }
+
+    #[ComplexObject]
+    impl MyObj {
+        #[graphql(timeout = "100ms")]
+        async fn slow(&self) -> Option<i32> {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Some(self.a)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(timeout = "1s")]
+        async fn fast(&self) -> i32 {
+            10
+        }
+
+        #[graphql(timeout = "50ms")]
+        async fn slow(&self) -> Option<i32> {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Some(20)
+        }
+
+        async fn obj(&self) -> MyObj {
+            MyObj { a: 30 }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let resp = schema.execute("{ fast slow obj { a slow } }").await;
+    assert_eq!(
+        resp.data,
+        value!({
+            "fast": 10,
+            "slow": null,
+            "obj": { "a": 30, "slow": null },
+        })
+    );
+    assert_eq!(
+        resp.errors,
+        vec![
+            ServerError {
+                message: r#"Field "slow" timed out."#.to_string(),
+                source: None,
+                locations: vec![Pos { line: 1, column: 8 }],
+                path: vec![PathSegment::Field("slow".to_string())],
+                extensions: None,
+            },
+            ServerError {
+                message: r#"Field "slow" timed out."#.to_string(),
+                source: None,
+                locations: vec![Pos {
+                    line: 1,
+                    column: 21
+                }],
+                path: vec![
+                    PathSegment::Field("obj".to_string()),
+                    PathSegment::Field("slow".to_string())
+                ],
+                extensions: None,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_non_null_field_timeout() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(timeout = "50ms")]
+        async fn slow(&self) -> i32 {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let resp = schema.execute("{ slow }").await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors[0].message, r#"Field "slow" timed out."#);
+    assert_eq!(
+        resp.errors[0].path,
+        vec![PathSegment::Field("slow".to_string())]
+    );
+}
+
+#[tokio::test]
+pub async fn test_field_timeout_with_timer() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(timeout = "1h")]
+        async fn slow(&self) -> Option<i32> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Some(10)
+        }
+    }
+
+    // the timer elapses immediately, whatever the timeout
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .timer(|_| async {})
+        .finish();
+    let resp = schema.execute("{ slow }").await;
+    assert_eq!(resp.data, value!({ "slow": null }));
+    assert_eq!(resp.errors[0].message, r#"Field "slow" timed out."#);
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .timer(tokio::time::sleep)
+        .finish();
+    let resp = schema.execute("{ slow }").await;
+    assert_eq!(resp.data, value!({ "slow": 10 }));
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "fragment-arguments")]
+
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn greet(&self, name: String, #[graphql(default = 1)] times: i32) -> String {
+        format!("Hello, {}!", name).repeat(times as usize)
+    }
+
+    async fn obj(&self) -> Query {
+        Query
+    }
+}
+
+#[tokio::test]
+pub async fn test_fragment_arguments() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"
+        query($name: String!) {
+            a: obj { ...Greeting }
+            b: obj { ...Greeting(name: "Bob") }
+            c: obj { ...Greeting(name: $name, times: 2) }
+            d: obj { ...Nested(name: "Eve") }
+        }
+
+        fragment Greeting($name: String = "Alice", $times: Int = 1) on Query {
+            greet(name: $name, times: $times)
+        }
+
+        fragment Nested($name: String!) on Query {
+            obj { ...Greeting(name: $name) }
+        }
+    "#;
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(Variables::from_value(value!({
+                "name": "Carol",
+            }))))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "a": { "greet": "Hello, Alice!" },
+            "b": { "greet": "Hello, Bob!" },
+            "c": { "greet": "Hello, Carol!Hello, Carol!" },
+            "d": { "obj": { "greet": "Hello, Eve!" } },
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_fragment_arguments_errors() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"
+        { ...Greeting(name: "Bob", count: 2) }
+
+        fragment Greeting($name: String!) on Query {
+            greet(name: $name)
+        }
+    "#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: r#"Unknown argument "count" on fragment "Greeting""#.to_owned(),
+            source: None,
+            locations: vec![Pos {
+                line: 2,
+                column: 36
+            }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    let query = r#"
+        { ...Greeting }
+
+        fragment Greeting($name: String!) on Query {
+            greet(name: $name)
+        }
+    "#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: r#"Fragment "Greeting" argument "name" of type "String!" is required, but it was not provided"#.to_owned(),
+            source: None,
+            locations: vec![Pos {
+                line: 2,
+                column: 11
+            }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+}
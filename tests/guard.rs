@@ -626,3 +626,96 @@ pub async fn test_guard_with_fn() {
         }]
     );
 }
+
+#[tokio::test]
+pub async fn test_guard_denial() {
+    struct PermissionGuard(&'static str);
+
+    #[async_trait::async_trait]
+    impl Guard for PermissionGuard {
+        async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+            if ctx.data_opt::<Role>() == Some(&Role::Admin) {
+                return Ok(());
+            }
+            Err(
+                GuardDenial::new("The \"{permission}\" permission is required ({code}).")
+                    .code("FORBIDDEN")
+                    .required_permission(self.0)
+                    .into(),
+            )
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(guard = "PermissionGuard(\"users:read\")")]
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        #[graphql(guard = "RoleGuard::new(Role::Admin)")]
+        async fn other(&self) -> i32 {
+            20
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let errors = schema.execute("{ value }").await.into_result().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        "The \"users:read\" permission is required (FORBIDDEN)."
+    );
+    let extensions = errors[0].extensions.as_ref().unwrap();
+    assert_eq!(extensions.get("code"), Some(&value!("FORBIDDEN")));
+    assert_eq!(
+        extensions.get("requiredPermission"),
+        Some(&value!("users:read"))
+    );
+    assert_eq!(
+        errors[0]
+            .source::<GuardDenial>()
+            .unwrap()
+            .required_permission,
+        Some("users:read".to_string())
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .on_guard_denial(|_, denial| {
+            let mut err = Error::new(format!(
+                "Missing permission {}",
+                denial.required_permission.as_deref().unwrap_or_default()
+            ));
+            err = err.extend_with(|_, e| e.set("reason", "PERMISSION"));
+            err
+        })
+        .finish();
+    assert_eq!(
+        schema.execute("{ value }").await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Missing permission users:read".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("value".to_owned())],
+            extensions: Some({
+                let mut extensions = ErrorExtensionValues::default();
+                extensions.set("reason", "PERMISSION");
+                extensions
+            }),
+        },]
+    );
+
+    // The errors which are not denials are not rendered by the hook.
+    assert_eq!(
+        schema.execute("{ other }").await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Forbidden".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("other".to_owned())],
+            extensions: None,
+        }]
+    );
+}
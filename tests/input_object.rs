@@ -750,6 +750,99 @@ pub async fn test_input_object_validator() {
     );
 }
 
+#[tokio::test]
+pub async fn test_input_object_validate_input() {
+    #[derive(InputObject)]
+    #[graphql(validator(validate_input))]
+    struct DateRange {
+        start_date: i32,
+        end_date: i32,
+    }
+
+    impl ValidateInput for DateRange {
+        fn validate(&self) -> Result<(), InputValueError<Self>> {
+            if self.end_date <= self.start_date {
+                return Err(InputValueError::custom("endDate must be after startDate")
+                    .with_fields(["startDate", "endDate"]));
+            }
+            Ok(())
+        }
+    }
+
+    struct MaxDays(i32);
+
+    #[derive(InputObject)]
+    #[graphql(validator(custom = "MaxDays(10)"))]
+    struct ShortDateRange {
+        start_date: i32,
+        end_date: i32,
+    }
+
+    impl CustomValidator<ShortDateRange> for MaxDays {
+        fn check(&self, value: &ShortDateRange) -> Result<(), InputValueError<ShortDateRange>> {
+            if value.end_date - value.start_date > self.0 {
+                return Err(InputValueError::custom("date range is too long"));
+            }
+            Ok(())
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn days(&self, range: DateRange) -> i32 {
+            range.end_date - range.start_date
+        }
+
+        async fn short_days(&self, range: ShortDateRange) -> i32 {
+            range.end_date - range.start_date
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ days(range: { startDate: 1, endDate: 5 }) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "days": 4 })
+    );
+
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("fields", value!(["startDate", "endDate"]));
+    assert_eq!(
+        schema
+            .execute("{ days(range: { startDate: 5, endDate: 1 }) }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Failed to parse "DateRange": endDate must be after startDate"#.to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 15
+            }],
+            path: vec![PathSegment::Field("days".to_string())],
+            extensions: Some(extensions)
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ shortDays(range: { startDate: 1, endDate: 20 }) }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Failed to parse "ShortDateRange": date range is too long"#
+    );
+}
+
 #[tokio::test]
 pub async fn test_custom_validator_with_extensions_input() {
     struct MyValidator {
@@ -803,6 +896,7 @@ pub async fn test_custom_validator_with_extensions_input() {
 
     let mut error_extensions = ErrorExtensionValues::default();
     error_extensions.set("code", 99);
+    error_extensions.set("inputPath", value!(["n", "v"]));
     assert_eq!(
         schema
             .execute("{ value(n: {v: 11}) }")
@@ -810,7 +904,7 @@ pub async fn test_custom_validator_with_extensions_input() {
             .into_result()
             .unwrap_err(),
         vec![ServerError {
-            message: r#"Failed to parse "Int": expect 100, actual 11 (occurred while parsing "ValueInput")"#.to_string(),
+            message: r#"Invalid value for argument "n.v", Failed to parse "Int": expect 100, actual 11 (occurred while parsing "ValueInput")"#.to_string(),
             source: None,
             locations: vec![Pos {
                 line: 1,
@@ -82,3 +82,90 @@ pub async fn test_input_box_slice() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_input_value_error_path() {
+    struct Even(i64);
+
+    #[Scalar]
+    impl ScalarType for Even {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match value {
+                Value::Number(n) if n.as_i64().map_or(false, |n| n % 2 == 0) => {
+                    Ok(Even(n.as_i64().unwrap()))
+                }
+                _ => Err(InputValueError::custom("expected an even number")),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::Number(self.0.into())
+        }
+    }
+
+    #[derive(InputObject)]
+    struct Range {
+        start: Even,
+    }
+
+    #[derive(InputObject)]
+    struct Filter {
+        range: Range,
+    }
+
+    #[derive(InputObject)]
+    struct Input {
+        filters: Vec<Filter>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn count(&self, input: Input) -> usize {
+            input.filters.len()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let expected = ServerError {
+        message: r#"Invalid value for argument "input.filters[1].range.start", Failed to parse "Even": expected an even number (occurred while parsing "Range") (occurred while parsing "Filter") (occurred while parsing "[Filter!]") (occurred while parsing "Input")"#.to_owned(),
+        source: None,
+        locations: vec![Pos {
+            line: 1,
+            column: 16,
+        }],
+        path: vec![PathSegment::Field("count".to_owned())],
+        extensions: Some({
+            let mut extensions = ErrorExtensionValues::default();
+            extensions.set("inputPath", value!(["input", "filters", 1, "range", "start"]));
+            extensions
+        }),
+    };
+
+    let query =
+        r#"{ count(input: { filters: [{ range: { start: 2 } }, { range: { start: 3 } }] }) }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        vec![expected.clone()],
+    );
+
+    let query = r#"query($input: Input!) { count(input: $input) }"#;
+    let variables = Variables::from_value(value!({
+        "input": { "filters": [{ "range": { "start": 2 } }, { "range": { "start": 3 } }] },
+    }));
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(variables))
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            locations: vec![Pos {
+                line: 1,
+                column: 38,
+            }],
+            ..expected
+        }],
+    );
+}
@@ -473,3 +473,62 @@ pub async fn test_issue_330() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_remote_interface() {
+    mod remote {
+        pub struct User {
+            pub id: i32,
+        }
+
+        pub enum Node {
+            User(User),
+        }
+    }
+
+    #[derive(SimpleObject)]
+    struct User {
+        id: i32,
+    }
+
+    impl From<remote::User> for User {
+        fn from(user: remote::User) -> Self {
+            User { id: user.id }
+        }
+    }
+
+    impl From<User> for remote::User {
+        fn from(user: User) -> Self {
+            remote::User { id: user.id }
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(remote = "remote::Node", field(name = "id", ty = "&i32"))]
+    enum Node {
+        User(User),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self) -> Node {
+            remote::Node::User(remote::User { id: 7 }).into()
+        }
+    }
+
+    let node: remote::Node = Node::User(User { id: 1 }).into();
+    assert!(matches!(node, remote::Node::User(remote::User { id: 1 })));
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ node { id } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "node": { "id": 7 } })
+    );
+}
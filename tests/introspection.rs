@@ -1513,3 +1513,112 @@ pub async fn test_introspection_default() {
     let res = schema.execute(query).await.into_result().unwrap().data;
     assert_eq!(res, res_json);
 }
+
+#[tokio::test]
+pub async fn test_introspection_without_executing() {
+    let schema = Schema::new(Query, Mutation, Subscription);
+    let query = r#"
+    query IntrospectionQuery {
+      __schema {
+        description
+        queryType { name }
+        mutationType { name }
+        subscriptionType { name }
+        types { ...FullType }
+        directives {
+          name
+          description
+          locations
+          args { ...InputValue }
+          isRepeatable
+        }
+      }
+    }
+
+    fragment FullType on __Type {
+      kind
+      name
+      description
+      specifiedByURL
+      fields(includeDeprecated: true) {
+        name
+        description
+        args { ...InputValue }
+        type { ...TypeRef }
+        isDeprecated
+        deprecationReason
+      }
+      inputFields { ...InputValue }
+      interfaces { ...TypeRef }
+      enumValues(includeDeprecated: true) {
+        name
+        description
+        isDeprecated
+        deprecationReason
+      }
+      possibleTypes { ...TypeRef }
+      isOneOf
+    }
+
+    fragment InputValue on __InputValue {
+      name
+      description
+      type { ...TypeRef }
+      defaultValue
+    }
+
+    fragment TypeRef on __Type {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+    "#;
+
+    let mut expected = schema
+        .execute(query)
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    let mut actual = schema.introspection();
+
+    // The query above stops at a fixed depth, so strip the leaf `ofType: null`
+    // from both results before comparing them.
+    fn strip_null_of_type(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                if map.get("ofType") == Some(&serde_json::Value::Null) {
+                    map.remove("ofType");
+                }
+                map.values_mut().for_each(strip_null_of_type);
+            }
+            serde_json::Value::Array(values) => values.iter_mut().for_each(strip_null_of_type),
+            _ => {}
+        }
+    }
+    strip_null_of_type(&mut expected);
+    strip_null_of_type(&mut actual);
+
+    assert_eq!(actual, expected);
+}
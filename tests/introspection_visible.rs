@@ -665,3 +665,70 @@ pub async fn root() {
         value!({ "__schema": { "queryType": { "name": "Query" } } })
     );
 }
+
+#[tokio::test]
+pub async fn test_introspection_hook() {
+    struct External;
+
+    struct Query;
+
+    #[Object]
+    #[allow(unreachable_code)]
+    impl Query {
+        #[graphql(deprecation = "Use `b`")]
+        async fn a(&self) -> i32 {
+            todo!()
+        }
+
+        async fn b(&self) -> i32 {
+            todo!()
+        }
+
+        #[graphql(visible = false)]
+        async fn c(&self) -> i32 {
+            todo!()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .on_introspection(|ctx, value| {
+            if ctx.field().name() == "deprecationReason" && ctx.data_opt::<External>().is_some() {
+                *value = Value::Null;
+            }
+        })
+        .finish();
+    let query = r#"{
+        __type(name: "Query") {
+            fields(includeDeprecated: true) { name reason: deprecationReason }
+        }
+        __schema {
+            queryType { fields(includeDeprecated: true) { name reason: deprecationReason } }
+        }
+    }"#;
+    let fields = |reason: Value| {
+        value!([
+            { "name": "a", "reason": reason },
+            { "name": "b", "reason": null },
+        ])
+    };
+
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "__type": { "fields": fields(value!("Use `b`")) },
+            "__schema": { "queryType": { "fields": fields(value!("Use `b`")) } },
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(External))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": { "fields": fields(Value::Null) },
+            "__schema": { "queryType": { "fields": fields(Value::Null) } },
+        })
+    );
+}
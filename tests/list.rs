@@ -182,3 +182,117 @@ pub async fn test_array_type() {
         }],
     );
 }
+
+#[tokio::test]
+pub async fn test_list_stream() {
+    use futures_util::{stream, StreamExt};
+
+    #[derive(SimpleObject)]
+    struct Row {
+        id: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn rows(&self, count: i32) -> ListStream<Row> {
+            ListStream::new(stream::iter(0..count).map(|id| Row { id }))
+        }
+
+        async fn checked(&self) -> ListStream<Result<i32>> {
+            ListStream::new(stream::iter(vec![Ok(1), Err("bad row".into()), Ok(3)]))
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ rows(count: 3) { id } empty: rows(count: 0) { id } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "rows": [{ "id": 0 }, { "id": 1 }, { "id": 2 }],
+            "empty": [],
+        })
+    );
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("rows(count: Int!): [Row!]!"));
+    assert!(sdl.contains("checked: [Int!]!"));
+
+    let err = schema
+        .execute("{ checked }")
+        .await
+        .into_result()
+        .unwrap_err()
+        .remove(0);
+    assert_eq!(err.message, "bad row");
+    assert_eq!(
+        err.path,
+        vec![
+            PathSegment::Field("checked".to_owned()),
+            PathSegment::Index(1)
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_list_stream_buffer_size() {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures_util::{stream, StreamExt};
+
+    #[derive(Default)]
+    struct Concurrency {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    struct Row(i32, Arc<Concurrency>);
+
+    #[Object]
+    impl Row {
+        async fn id(&self) -> i32 {
+            let current = self.1.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.1.max.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.1.current.fetch_sub(1, Ordering::SeqCst);
+            self.0
+        }
+    }
+
+    struct Query(Arc<Concurrency>);
+
+    #[Object]
+    impl Query {
+        async fn rows(&self) -> ListStream<Row> {
+            let concurrency = self.0.clone();
+            ListStream::new(stream::iter(0..6).map(move |id| Row(id, concurrency.clone())))
+                .buffer_size(2)
+        }
+    }
+
+    let concurrency = Arc::new(Concurrency::default());
+    let schema = Schema::new(Query(concurrency.clone()), EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ rows { id } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "rows": [{ "id": 0 }, { "id": 1 }, { "id": 2 }, { "id": 3 }, { "id": 4 }, { "id": 5 }],
+        })
+    );
+    assert_eq!(concurrency.max.load(Ordering::SeqCst), 2);
+}
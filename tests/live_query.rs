@@ -0,0 +1,153 @@
+use std::sync::{Arc, Mutex};
+
+use async_graphql::*;
+use futures_util::stream::StreamExt;
+
+#[tokio::test]
+pub async fn test_live_query() {
+    #[derive(SimpleObject, Clone)]
+    struct Todo {
+        id: i32,
+        text: String,
+    }
+
+    type Todos = Arc<Mutex<Vec<Todo>>>;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn todos(&self, ctx: &Context<'_>) -> Vec<Todo> {
+            ctx.add_live_query_token("todos");
+            ctx.data_unchecked::<Todos>().lock().unwrap().clone()
+        }
+
+        async fn version(&self) -> i32 {
+            1
+        }
+    }
+
+    let todos: Todos = Arc::new(Mutex::new(vec![Todo {
+        id: 1,
+        text: "a".to_string(),
+    }]));
+    let store = MemoryLiveQueryStore::new();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(todos.clone())
+        .live_query_store(store.clone())
+        .finish();
+
+    let mut stream = schema.execute_stream("query @live { todos { id text } version }");
+    let resp = stream.next().await.unwrap();
+    assert_eq!(
+        resp.data,
+        value!({ "todos": [{ "id": 1, "text": "a" }], "version": 1 })
+    );
+    assert_eq!(resp.extensions["live"], value!({ "revision": 0 }));
+
+    // the data has not changed, so nothing is sent
+    store.invalidate("Query.version");
+
+    todos.lock().unwrap()[0].text = "b".to_string();
+    store.invalidate("todos");
+    let resp = stream.next().await.unwrap();
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(
+        resp.extensions["live"],
+        value!({
+            "revision": 1,
+            "patch": [{ "op": "replace", "path": "/todos/0/text", "value": "b" }],
+        })
+    );
+
+    todos.lock().unwrap().push(Todo {
+        id: 2,
+        text: "c".to_string(),
+    });
+    store.invalidate("Query.todos");
+    assert_eq!(
+        stream.next().await.unwrap().extensions["live"],
+        value!({
+            "revision": 2,
+            "patch": [{
+                "op": "replace",
+                "path": "/todos",
+                "value": [{ "id": 1, "text": "b" }, { "id": 2, "text": "c" }],
+            }],
+        })
+    );
+
+    // without a stream, the query is executed once
+    assert_eq!(
+        schema
+            .execute("query @live { version }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "version": 1 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_live_query_invalidated_during_execution() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>) -> i32 {
+            let (value, store) = ctx.data_unchecked::<(Arc<Mutex<i32>>, MemoryLiveQueryStore)>();
+            let mut value = value.lock().unwrap();
+            *value += 1;
+            // the value changes again while the first result is being computed
+            if *value == 1 {
+                store.invalidate("Query.value");
+            }
+            *value
+        }
+    }
+
+    let store = MemoryLiveQueryStore::new();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data((Arc::new(Mutex::new(0)), store.clone()))
+        .live_query_store(store)
+        .finish();
+
+    let mut stream = schema.execute_stream("query @live { value }");
+    assert_eq!(stream.next().await.unwrap().data, value!({ "value": 1 }));
+    assert_eq!(
+        stream.next().await.unwrap().extensions["live"],
+        value!({
+            "revision": 1,
+            "patch": [{ "op": "replace", "path": "/value", "value": 2 }],
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_live_query_without_store() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("query @live { value }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Unknown directive "live""#.to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 7 }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "dataloader")]
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_graphql::{dataloader::*, *};
+
+#[tokio::test]
+pub async fn test_loader_macro() {
+    type Calls = Arc<Mutex<Vec<Vec<i32>>>>;
+
+    #[Loader]
+    async fn load_names(calls: &Calls, ids: &[i32]) -> Result<HashMap<i32, String>> {
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        calls.lock().unwrap().push(ids.clone());
+        if ids.contains(&0) {
+            return Err("invalid id".into());
+        }
+        Ok(ids.into_iter().map(|id| (id, id.to_string())).collect())
+    }
+
+    #[Loader(name = "DoubleLoader")]
+    async fn double(keys: &[i32]) -> HashMap<i32, i32> {
+        keys.iter().map(|key| (*key, key * 2)).collect()
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn name(&self, ctx: &Context<'_>, id: i32) -> Result<Option<String>> {
+            ctx.load_one::<NamesLoader>(id).await
+        }
+
+        async fn doubles(&self, ctx: &Context<'_>, values: Vec<i32>) -> Result<Vec<i32>> {
+            let map = ctx.load_many::<DoubleLoader>(values.clone()).await?;
+            Ok(values.iter().map(|value| map[value]).collect())
+        }
+    }
+
+    let calls = Calls::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(NamesLoader(calls.clone()).data_loader(tokio::spawn))
+        .data(DoubleLoader.data_loader(tokio::spawn))
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ a: name(id: 1) b: name(id: 2) doubles(values: [1, 2]) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": "1", "b": "2", "doubles": [2, 4] })
+    );
+    assert_eq!(*calls.lock().unwrap(), vec![vec![1, 2]]);
+
+    assert_eq!(
+        schema
+            .execute("{ name(id: 0) }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "invalid id"
+    );
+}
+
+#[tokio::test]
+pub async fn test_loader_macro_without_data_loader() {
+    #[Loader]
+    async fn load_values(keys: &[i32]) -> HashMap<i32, i32> {
+        keys.iter().map(|key| (*key, *key)).collect()
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+            ctx.load_one::<ValuesLoader>(1).await
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let err = schema.execute("{ value }").await.into_result().unwrap_err();
+    assert!(err[0].message.starts_with("Data `"));
+}
@@ -55,3 +55,93 @@ pub async fn test_maybe_undefined_type() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_patch() {
+    use std::sync::Mutex;
+
+    #[derive(SimpleObject, Patch, Clone)]
+    #[patch(name = "UpdateUserInput")]
+    struct User {
+        #[patch(skip)]
+        id: i32,
+        /// The name of the user
+        name: String,
+        nickname: Option<String>,
+        #[patch(name = "years")]
+        age: Option<i32>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Mutation(Mutex<User>);
+
+    #[Object]
+    impl Mutation {
+        async fn update_user(&self, input: UserPatch) -> User {
+            let mut user = self.0.lock().unwrap();
+            input.apply_to(&mut user);
+            user.clone()
+        }
+    }
+
+    let schema = Schema::new(
+        Query,
+        Mutation(Mutex::new(User {
+            id: 1,
+            name: "a".to_string(),
+            nickname: Some("b".to_string()),
+            age: Some(30),
+        })),
+        EmptySubscription,
+    );
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains(
+        r#"input UpdateUserInput {
+	"""
+	The name of the user
+	"""
+	name: String
+	nickname: String
+	years: Int
+}"#
+    ));
+
+    let query = "{ id name nickname age }";
+    assert_eq!(
+        schema
+            .execute(format!(
+                r#"mutation {{ updateUser(input: {{ name: "c" }}) {} }}"#,
+                query
+            ))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "updateUser": { "id": 1, "name": "c", "nickname": "b", "age": 30 },
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(format!(
+                r#"mutation {{ updateUser(input: {{ name: null, nickname: null, years: 31 }}) {} }}"#,
+                query
+            ))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "updateUser": { "id": 1, "name": "c", "nickname": null, "age": 31 },
+        })
+    );
+}
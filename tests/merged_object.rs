@@ -72,6 +72,175 @@ pub async fn test_merged_object_derive() {
     );
 }
 
+#[tokio::test]
+pub async fn test_merged_object_dispatch() {
+    struct Part1;
+
+    #[Object]
+    impl Part1 {
+        async fn a(&self) -> i32 {
+            1
+        }
+
+        async fn shared(&self) -> i32 {
+            1
+        }
+    }
+
+    struct Part2;
+
+    #[Object]
+    impl Part2 {
+        async fn b(&self) -> Result<i32> {
+            Err("b failed".into())
+        }
+
+        async fn shared(&self) -> i32 {
+            2
+        }
+    }
+
+    #[derive(MergedObject)]
+    struct Inner(Part1, Part2);
+
+    #[derive(MergedObject)]
+    struct Query(Inner, Object3);
+
+    let schema = Schema::new(
+        Query(Inner(Part1, Part2), Object3 { c: 3 }),
+        EmptyMutation,
+        EmptySubscription,
+    );
+    assert_eq!(
+        schema
+            .execute("{ a shared c }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "a": 1,
+            "shared": 2,
+            "c": 3,
+        })
+    );
+
+    let resp = schema.execute("{ a b }").await;
+    assert_eq!(
+        resp.errors,
+        vec![ServerError {
+            message: "b failed".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 5 }],
+            path: vec![PathSegment::Field("b".to_string())],
+            extensions: None,
+        }]
+    );
+}
+
+#[tokio::test]
+pub async fn test_merged_object_namespace() {
+    struct Billing;
+
+    #[Object]
+    impl Billing {
+        async fn invoices(&self) -> Vec<i32> {
+            vec![1, 2]
+        }
+    }
+
+    struct UserQuery;
+
+    #[Object]
+    impl UserQuery {
+        async fn count(&self) -> i32 {
+            3
+        }
+    }
+
+    #[derive(MergedObject)]
+    struct Query(
+        #[graphql(namespace)] Billing,
+        /// User operations
+        #[graphql(namespace = "users")]
+        UserQuery,
+        Object1,
+    );
+
+    let schema = Schema::new(
+        Query(Billing, UserQuery, Object1 { a: 10 }),
+        EmptyMutation,
+        EmptySubscription,
+    );
+    assert_eq!(
+        schema
+            .execute("{ a billing { invoices } users { count } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "a": 10,
+            "billing": { "invoices": [1, 2] },
+            "users": { "count": 3 },
+        })
+    );
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("billing: Billing!"));
+    assert!(sdl.contains("\"\"\"\n\tUser operations\n\t\"\"\"\n\tusers: UserQuery!"));
+    assert!(schema.execute("{ invoices }").await.is_err());
+}
+
+#[tokio::test]
+pub async fn test_merged_object_namespace_mutation() {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    type Log = Arc<Mutex<Vec<i32>>>;
+
+    struct Counter;
+
+    #[Object]
+    impl Counter {
+        async fn add(&self, ctx: &Context<'_>, value: i32, delay: u64) -> i32 {
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            ctx.data_unchecked::<Log>().lock().unwrap().push(value);
+            value
+        }
+    }
+
+    #[derive(MergedObject)]
+    struct Query(Object1);
+
+    #[derive(MergedObject)]
+    struct Mutation(#[graphql(namespace)] Counter);
+
+    let log = Log::default();
+    let schema = Schema::build(
+        Query(Object1 { a: 10 }),
+        Mutation(Counter),
+        EmptySubscription,
+    )
+    .data(log.clone())
+    .finish();
+    assert_eq!(
+        schema
+            .execute(
+                "mutation { counter { a: add(value: 1, delay: 50) b: add(value: 2, delay: 0) } }"
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "counter": { "a": 1, "b": 2 } })
+    );
+    // the mutations of the namespace are executed serially
+    assert_eq!(*log.lock().unwrap(), vec![1, 2]);
+}
+
 #[tokio::test]
 pub async fn test_merged_object_default() {
     mod a {
@@ -185,6 +354,89 @@ pub async fn test_merged_subscription() {
     }
 }
 
+#[tokio::test]
+pub async fn test_generic_merged_object() {
+    trait Store: Send + Sync + 'static {
+        type Item: OutputType + Clone;
+
+        fn get(&self) -> Self::Item;
+    }
+
+    struct MemoryStore;
+
+    impl Store for MemoryStore {
+        type Item = i32;
+
+        fn get(&self) -> Self::Item {
+            10
+        }
+    }
+
+    struct ValueQuery<S> {
+        store: S,
+    }
+
+    #[Object]
+    impl<S: Store> ValueQuery<S> {
+        async fn value(&self) -> S::Item {
+            self.store.get()
+        }
+    }
+
+    struct ListQuery<T> {
+        items: Vec<T>,
+    }
+
+    #[Object]
+    impl<T: OutputType + Clone> ListQuery<T> {
+        async fn items(&self) -> Vec<T> {
+            self.items.clone()
+        }
+    }
+
+    #[derive(MergedObject)]
+    struct Query<S: Store>(ValueQuery<S>, ListQuery<S::Item>);
+
+    #[derive(MergedObject)]
+    struct Mutation<S>(ValueQuery<S>)
+    where
+        S: Store;
+
+    let schema = Schema::new(
+        Query(
+            ValueQuery { store: MemoryStore },
+            ListQuery {
+                items: vec![1, 2, 3],
+            },
+        ),
+        Mutation(ValueQuery { store: MemoryStore }),
+        EmptySubscription,
+    );
+    assert_eq!(
+        schema
+            .execute("{ value items }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "value": 10,
+            "items": [1, 2, 3],
+        })
+    );
+    assert_eq!(
+        schema
+            .execute("mutation { value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "value": 10,
+        })
+    );
+}
+
 #[tokio::test]
 pub async fn test_generic_merged_subscription() {
     struct Subscription1<T> {
@@ -0,0 +1,173 @@
+use std::sync::{Arc, Mutex};
+
+use async_graphql::*;
+
+#[derive(Default)]
+struct Transaction(Mutex<Vec<String>>);
+
+#[derive(Default, Clone)]
+struct Storage {
+    names: Arc<Mutex<Vec<String>>>,
+    events: Arc<Mutex<Vec<&'static str>>>,
+    fail_begin: bool,
+    fail_commit: bool,
+}
+
+#[async_trait::async_trait]
+impl MutationTransaction for Storage {
+    type Transaction = Transaction;
+
+    async fn begin(&self, _ctx: &ContextSelectionSet<'_>) -> Result<Transaction> {
+        self.events.lock().unwrap().push("begin");
+        if self.fail_begin {
+            return Err("cannot begin".into());
+        }
+        Ok(Transaction::default())
+    }
+
+    async fn commit(&self, transaction: Transaction) -> Result<()> {
+        self.events.lock().unwrap().push("commit");
+        if self.fail_commit {
+            return Err("cannot commit".into());
+        }
+        let names = transaction.0.into_inner().unwrap();
+        self.names.lock().unwrap().extend(names);
+        Ok(())
+    }
+
+    async fn rollback(&self, _transaction: Transaction) -> Result<()> {
+        self.events.lock().unwrap().push("rollback");
+        Ok(())
+    }
+}
+
+struct Added;
+
+#[Object]
+impl Added {
+    async fn count(&self, ctx: &Context<'_>) -> Result<usize> {
+        Ok(ctx.data::<Transaction>()?.0.lock().unwrap().len())
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn in_transaction(&self, ctx: &Context<'_>) -> bool {
+        ctx.data_opt::<Transaction>().is_some()
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn add(&self, ctx: &Context<'_>, name: String) -> Result<Added> {
+        if name.is_empty() {
+            return Err("The name is empty".into());
+        }
+        ctx.data::<Transaction>()?.0.lock().unwrap().push(name);
+        Ok(Added)
+    }
+
+    async fn fail(&self, ctx: &Context<'_>, name: String) -> Option<Result<bool>> {
+        ctx.data_unchecked::<Transaction>()
+            .0
+            .lock()
+            .unwrap()
+            .push(name);
+        Some(Err("failed".into()))
+    }
+}
+
+fn schema(storage: Storage) -> Schema<Query, Mutation, EmptySubscription> {
+    Schema::build(Query, Mutation, EmptySubscription)
+        .mutation_transaction(storage)
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_mutation_transaction_commit() {
+    let storage = Storage::default();
+    let schema = schema(storage.clone());
+
+    let query = r#"mutation { a: add(name: "a") { count } b: add(name: "b") { count } }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({ "a": { "count": 1 }, "b": { "count": 2 } })
+    );
+    assert_eq!(*storage.names.lock().unwrap(), vec!["a", "b"]);
+    assert_eq!(*storage.events.lock().unwrap(), vec!["begin", "commit"]);
+}
+
+#[tokio::test]
+pub async fn test_mutation_transaction_rollback() {
+    let storage = Storage::default();
+    let schema = schema(storage.clone());
+
+    let query = r#"mutation { a: add(name: "a") { count } fail(name: "b") }"#;
+    let resp = schema.execute(query).await;
+    assert_eq!(resp.data, value!({ "a": { "count": 1 }, "fail": null }));
+    assert_eq!(resp.errors.len(), 1);
+
+    let query = r#"mutation { a: add(name: "a") { count } b: add(name: "") { count } }"#;
+    let resp = schema.execute(query).await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors.len(), 1);
+
+    assert!(storage.names.lock().unwrap().is_empty());
+    assert_eq!(
+        *storage.events.lock().unwrap(),
+        vec!["begin", "rollback", "begin", "rollback"]
+    );
+}
+
+#[tokio::test]
+pub async fn test_mutation_transaction_begin_error() {
+    let storage = Storage {
+        fail_begin: true,
+        ..Default::default()
+    };
+    let schema = schema(storage.clone());
+
+    let resp = schema
+        .execute(r#"mutation { add(name: "a") { count } }"#)
+        .await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors[0].message, "cannot begin");
+    assert_eq!(*storage.events.lock().unwrap(), vec!["begin"]);
+}
+
+#[tokio::test]
+pub async fn test_mutation_transaction_commit_error() {
+    let storage = Storage {
+        fail_commit: true,
+        ..Default::default()
+    };
+    let schema = schema(storage.clone());
+
+    let resp = schema
+        .execute(r#"mutation { add(name: "a") { count } }"#)
+        .await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors[0].message, "cannot commit");
+    assert!(storage.names.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+pub async fn test_mutation_transaction_not_used_by_queries() {
+    let storage = Storage::default();
+    let schema = schema(storage.clone());
+
+    assert_eq!(
+        schema
+            .execute("{ inTransaction }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "inTransaction": false })
+    );
+    assert!(storage.events.lock().unwrap().is_empty());
+}
@@ -0,0 +1,71 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_newtype_id() {
+    #[derive(NewTypeId, Copy, Clone, Debug, PartialEq)]
+    struct PostId(u64);
+
+    #[derive(NewTypeId, Copy, Clone, Debug, PartialEq)]
+    #[graphql(prefix = "User")]
+    struct UserId(u64);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self, id: UserId) -> UserId {
+            assert_eq!(id, UserId(1));
+            id
+        }
+
+        async fn post(&self, id: PostId) -> PostId {
+            assert_eq!(id, PostId(2));
+            id
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ user(id: "User_1") post(id: 2) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": "User_1", "post": "2" })
+    );
+
+    let query = r#"query ($id: ID!) { user(id: $id) }"#;
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(query).variables(Variables::from_value(value!({ "id": "User_1" })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": "User_1" })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ user(id: "1") }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Failed to parse "ID": the ID must start with "User_""#
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ post(id: "abc") }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Failed to parse "ID": invalid digit found in string"#
+    );
+
+    assert!(schema.sdl().contains("user(id: ID!): ID!"));
+}
@@ -262,7 +262,7 @@ async fn test_oneof_object_validation() {
         .unwrap_err()
         .into_server_error(Default::default())
         .message,
-        r#"Failed to parse "Int": the value is 20, must be less than or equal to 10 (occurred while parsing "MyOneofObj")"#
+        r#"Invalid value for argument "a", Failed to parse "Int": the value is 20, must be less than or equal to 10 (occurred while parsing "MyOneofObj")"#
     );
 }
 
@@ -345,3 +345,46 @@ async fn test_issue_923() {
     let query = r#"{ query(filter: {all: ["c", "d"]}) }"#;
     schema.execute(query).await.into_result().unwrap();
 }
+
+#[tokio::test]
+async fn test_oneof_object_process_with() {
+    mod processor {
+        pub fn email(input: &mut String) {
+            *input = input.trim().to_lowercase();
+        }
+    }
+
+    #[derive(OneofObject)]
+    enum UserBy {
+        #[graphql(process_with = "processor::email", validator(email))]
+        Email(String),
+        Id(i64),
+    }
+
+    pub struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self, by: UserBy) -> String {
+            match by {
+                UserBy::Email(email) => email,
+                UserBy::Id(id) => id.to_string(),
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    // the value is processed before it is validated
+    let query = r#"{ user(by: {email: "  A@A.com "}) }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({ "user": "a@a.com" })
+    );
+
+    let query = r#"{ user(by: {id: 100}) }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({ "user": "100" })
+    );
+}
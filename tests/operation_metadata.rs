@@ -0,0 +1,86 @@
+use async_graphql::{parser::types::OperationType, *};
+use futures_util::stream::{Stream, StreamExt};
+
+#[tokio::test]
+pub async fn test_operation_metadata() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn add(&self, a: i32, b: i32) -> i32 {
+            a + b
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(vec![1, 2])
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+
+    let resp = schema
+        .execute("query Add { x: add(a: 1, b: 2) } query Other { add(a: 0, b: 0) }")
+        .await;
+    assert!(resp.operation.is_none());
+
+    let resp = schema
+        .execute(
+            Request::new("query Add { x: add(a: 1, b: 2) } query Other { add(a: 0, b: 0) }")
+                .operation_name("Add"),
+        )
+        .await;
+    assert_eq!(
+        resp.operation,
+        Some(OperationMetadata::new(
+            Some("Add".to_string()),
+            OperationType::Query,
+            "query Add{add(a:0,b:0)}",
+        ))
+    );
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({ "data": { "x": 3 } })
+    );
+
+    let resp = schema.execute("{ add(b: 3, a: 4) }").await;
+    assert_eq!(
+        resp.operation,
+        Some(OperationMetadata::new(
+            None,
+            OperationType::Query,
+            "query{add(a:0,b:0)}",
+        ))
+    );
+
+    for skip in [true, false] {
+        let resp = schema
+            .execute(
+                Request::new("query Q($skip: Boolean!) { add(a: 1, b: 2) @skip(if: $skip) }")
+                    .variables(Variables::from_value(value!({ "skip": skip }))),
+            )
+            .await;
+        assert_eq!(
+            resp.operation.unwrap().signature(),
+            "query Q($skip:Boolean!){add(a:0,b:0)@skip(if:$skip)}"
+        );
+        let expected = if skip {
+            value!({})
+        } else {
+            value!({ "add": 3 })
+        };
+        assert_eq!(resp.data, expected);
+    }
+
+    let mut stream = schema.execute_stream("subscription S { values }");
+    for _ in 0..2 {
+        let operation = stream.next().await.unwrap().operation.unwrap();
+        assert_eq!(operation.name.as_deref(), Some("S"));
+        assert_eq!(operation.ty, OperationType::Subscription);
+        assert_eq!(operation.signature(), "subscription S{values}");
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::{
+    atomic::{AtomicI32, Ordering},
+    Arc,
+};
+
+use async_graphql::*;
+
+struct Transaction {
+    id: i32,
+    user: String,
+}
+
+struct User(String);
+
+#[derive(Default, Clone)]
+struct TransactionProvider {
+    created: Arc<AtomicI32>,
+    failures: Arc<AtomicI32>,
+}
+
+#[async_trait::async_trait]
+impl Provider for TransactionProvider {
+    type Output = Transaction;
+
+    async fn provide(&self, ctx: &Context<'_>) -> Result<Transaction> {
+        if self.failures.load(Ordering::SeqCst) > 0 {
+            self.failures.fetch_sub(1, Ordering::SeqCst);
+            return Err("cannot open a transaction".into());
+        }
+        Ok(Transaction {
+            id: self.created.fetch_add(1, Ordering::SeqCst) + 1,
+            user: ctx.data_unchecked::<User>().0.clone(),
+        })
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn transaction(&self, ctx: &Context<'_>) -> Result<i32> {
+        Ok(ctx.provide::<Transaction>().await?.id)
+    }
+
+    async fn user(&self, ctx: &Context<'_>) -> Result<String> {
+        Ok(ctx.provide::<Transaction>().await?.user.clone())
+    }
+
+    async fn missing(&self, ctx: &Context<'_>) -> Result<i32> {
+        Ok(*ctx.provide::<i32>().await?)
+    }
+}
+
+fn schema(provider: TransactionProvider) -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .provider(provider)
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_provider_is_lazy() {
+    let provider = TransactionProvider::default();
+    let schema = schema(provider.clone());
+
+    let request = Request::new("{ value }").data(User("alice".to_string()));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "value": 10 })
+    );
+    assert_eq!(provider.created.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+pub async fn test_provider_once_per_request() {
+    let provider = TransactionProvider::default();
+    let schema = schema(provider.clone());
+
+    let request =
+        Request::new("{ a: transaction b: transaction user }").data(User("alice".to_string()));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "a": 1, "b": 1, "user": "alice" })
+    );
+
+    let request = Request::new("{ transaction user }").data(User("bob".to_string()));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "transaction": 2, "user": "bob" })
+    );
+    assert_eq!(provider.created.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+pub async fn test_provider_error() {
+    let provider = TransactionProvider::default();
+    provider.failures.store(1, Ordering::SeqCst);
+    let schema = schema(provider.clone());
+
+    let request = Request::new("{ transaction }").data(User("alice".to_string()));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "cannot open a transaction".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("transaction".to_string())],
+            extensions: None,
+        }]
+    );
+
+    // The failed data is provided again by the next request.
+    let request = Request::new("{ transaction }").data(User("alice".to_string()));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "transaction": 1 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_provider_missing() {
+    let schema = schema(TransactionProvider::default());
+    assert_eq!(
+        schema
+            .execute("{ missing }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Provider of `i32` does not exist."
+    );
+}
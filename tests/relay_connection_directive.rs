@@ -0,0 +1,67 @@
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn friends(&self, ctx: &Context<'_>, first: Option<i32>) -> Result<Vec<i32>> {
+        let connection = ctx.look_ahead().relay_connection()?;
+        let len = first.unwrap_or(2);
+        Ok(match connection {
+            Some(connection) => vec![connection.key.len() as i32; len as usize],
+            None => vec![0; len as usize],
+        })
+    }
+
+    async fn connection(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        Ok(ctx
+            .look_ahead()
+            .relay_connection()?
+            .map(|connection| format!("{}:{:?}", connection.key, connection.filter)))
+    }
+}
+
+#[tokio::test]
+pub async fn test_relay_connection_directive_unknown() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let resp = schema
+        .execute(r#"{ friends @connection(key: "Friends_friends") }"#)
+        .await;
+    assert_eq!(
+        resp.errors
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec![r#"Unknown directive "connection""#.to_string()]
+    );
+}
+
+#[tokio::test]
+pub async fn test_relay_connection_directive() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_relay_connection_directive()
+        .finish();
+    let resp = schema
+        .execute(
+            Request::new(
+                r#"query($key: String!) {
+                friends(first: 1) @connection(key: $key)
+                connection @connection(key: "A", filter: ["first"])
+                noConnection: connection
+            }"#,
+            )
+            .variables(Variables::from_value(value!({ "key": "abc" }))),
+        )
+        .await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({
+            "friends": [3],
+            "connection": r#"A:Some(["first"])"#,
+            "noConnection": null,
+        })
+    );
+
+    let resp = schema.execute(r#"{ friends @connection }"#).await;
+    assert!(resp.is_err());
+}
@@ -1,5 +1,6 @@
 use ::http::HeaderValue;
 use async_graphql::*;
+use futures_util::stream::{Stream, StreamExt};
 
 #[tokio::test]
 pub async fn test_schema_default() {
@@ -49,3 +50,1085 @@ pub async fn test_http_headers() {
         Some(&HeaderValue::from_static("1"))
     );
 }
+
+#[tokio::test]
+pub async fn test_response_extensions() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn a(&self, ctx: &Context<'_>) -> i32 {
+            ctx.insert_response_extension("a", 1);
+            1
+        }
+
+        async fn b(&self, ctx: &Context<'_>) -> FieldResult<i32> {
+            ctx.insert_response_extension("b", value!({ "x": 2 }));
+            Err("error".into())
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn value(&self) -> impl Stream<Item = ValueWithExtension> {
+            futures_util::stream::iter(0..2).map(ValueWithExtension)
+        }
+    }
+
+    struct ValueWithExtension(i32);
+
+    #[Object]
+    impl ValueWithExtension {
+        async fn n(&self, ctx: &Context<'_>) -> i32 {
+            ctx.insert_response_extension("n", self.0);
+            self.0
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let resp = schema.execute("{ a b }").await;
+    assert_eq!(resp.extensions.get("a"), Some(&value!(1)));
+    assert_eq!(resp.extensions.get("b"), Some(&value!({ "x": 2 })));
+
+    let resp = schema.execute("{ a }").await;
+    assert!(resp.extensions.get("b").is_none());
+
+    let extensions = schema
+        .execute_stream("subscription { value { n } }")
+        .map(|resp| resp.extensions.get("n").cloned())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(extensions, vec![Some(value!(0)), Some(value!(1))]);
+}
+
+#[tokio::test]
+pub async fn test_schema_fingerprint() {
+    mod v1 {
+        use async_graphql::*;
+
+        #[derive(SimpleObject)]
+        pub struct A {
+            pub a: i32,
+        }
+
+        #[derive(SimpleObject)]
+        pub struct B {
+            pub b: i32,
+        }
+
+        #[derive(Union)]
+        pub enum AB {
+            A(A),
+            B(B),
+        }
+
+        pub struct Query;
+
+        #[Object]
+        impl Query {
+            async fn x(&self, a: i32, b: i32) -> i32 {
+                a + b
+            }
+
+            async fn y(&self) -> AB {
+                AB::A(A { a: 1 })
+            }
+        }
+    }
+
+    mod v2 {
+        use async_graphql::*;
+
+        #[derive(SimpleObject)]
+        pub struct A {
+            pub a: i32,
+        }
+
+        #[derive(SimpleObject)]
+        pub struct B {
+            pub b: i32,
+        }
+
+        #[derive(Union)]
+        pub enum AB {
+            B(B),
+            A(A),
+        }
+
+        pub struct Query;
+
+        #[Object]
+        impl Query {
+            async fn y(&self) -> AB {
+                AB::A(A { a: 1 })
+            }
+
+            async fn x(&self, b: i32, a: i32) -> i32 {
+                a + b
+            }
+        }
+    }
+
+    let schema1 = Schema::new(v1::Query, EmptyMutation, EmptySubscription);
+    let schema2 = Schema::new(v2::Query, EmptyMutation, EmptySubscription);
+    assert_ne!(schema1.sdl(), schema2.sdl());
+    assert_eq!(schema1.fingerprint(), schema2.fingerprint());
+    assert_eq!(schema1.fingerprint().len(), 16);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn x(&self, a: i32) -> i32 {
+            a
+        }
+    }
+
+    let schema3 = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_ne!(schema1.fingerprint(), schema3.fingerprint());
+}
+
+#[tokio::test]
+pub async fn test_schema_on_registry() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        async fn internal(&self) -> i32 {
+            20
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .on_registry(|registry| {
+            if let Some(registry::MetaType::Object {
+                description,
+                fields,
+                ..
+            }) = registry.types.get_mut("Query")
+            {
+                *description = Some("The query root".to_string());
+                fields.remove("internal");
+            }
+        })
+        .finish();
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("\"\"\"\nThe query root\n\"\"\"\ntype Query {\n\tvalue: Int!\n}"));
+    assert!(!sdl.contains("internal"));
+    assert_eq!(
+        schema
+            .execute("{ value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10 })
+    );
+    assert!(schema.execute("{ internal }").await.is_err());
+}
+
+mod possible_types {
+    use async_graphql::*;
+
+    #[derive(SimpleObject)]
+    pub struct Dog {
+        pub name: String,
+    }
+
+    #[derive(SimpleObject)]
+    pub struct Cat {
+        pub name: String,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "name", ty = "&String"))]
+    pub enum Named {
+        Dog(Dog),
+    }
+
+    #[derive(Union)]
+    pub enum Pet {
+        Dog(Dog),
+        Cat(Cat),
+    }
+
+    pub struct Query;
+
+    #[Object]
+    impl Query {
+        async fn named(&self) -> Named {
+            Dog {
+                name: "dog".to_string(),
+            }
+            .into()
+        }
+
+        async fn pet(&self) -> Pet {
+            Cat {
+                name: "cat".to_string(),
+            }
+            .into()
+        }
+    }
+
+    pub fn build(f: impl FnOnce(&mut registry::Registry) + 'static) {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .on_registry(f)
+            .finish();
+    }
+}
+
+#[test]
+pub fn test_schema_possible_types() {
+    possible_types::build(|_| {});
+}
+
+#[test]
+#[should_panic(
+    expected = r#"Type "Cat" implements the interface "Named", but it is not a possible type of "Named"."#
+)]
+pub fn test_schema_implements_not_possible_type() {
+    possible_types::build(|registry| registry.add_implements("Cat", "Named"));
+}
+
+#[test]
+#[should_panic(
+    expected = r#"Type "Dog" is a possible type of the interface "Named", but it does not implement "Named"."#
+)]
+pub fn test_schema_possible_type_not_implemented() {
+    possible_types::build(|registry| {
+        registry.implements.remove("Dog");
+    });
+}
+
+#[test]
+#[should_panic(
+    expected = r#"Type "Cat" is a member of the union "Pet", but it is not registered."#
+)]
+pub fn test_schema_union_member_not_registered() {
+    possible_types::build(|registry| {
+        registry.types.remove("Cat");
+    });
+}
+
+#[tokio::test]
+pub async fn test_schema_description() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .description("The schema description")
+        .finish();
+
+    assert!(schema
+        .sdl()
+        .contains("\"\"\"\nThe schema description\n\"\"\"\nschema {\n"));
+    assert_eq!(schema.meta().description(), Some("The schema description"));
+    assert_eq!(
+        schema
+            .execute("{ __schema { description } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "__schema": { "description": "The schema description" } })
+    );
+    assert_eq!(
+        schema.introspection()["__schema"]["description"],
+        serde_json::json!("The schema description")
+    );
+}
+
+#[cfg(feature = "dynamic-schema")]
+#[tokio::test]
+pub async fn test_request_limits() {
+    #[derive(SimpleObject)]
+    struct Obj {
+        a: i32,
+        b: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> Obj {
+            Obj { a: 1, b: 2 }
+        }
+
+        async fn slow(&self) -> i32 {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            1
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(1)
+        .limit_complexity(2)
+        .finish();
+
+    let query = "{ obj { a b } }";
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err()[0].message,
+        "Query is too complex."
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).complexity_limit(3))
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Query is nested too deep."
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).complexity_limit(3).depth_limit(2))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "obj": { "a": 1, "b": 2 } })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new("{ obj { a } }").complexity_limit(1))
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Query is too complex."
+    );
+
+    assert_eq!(
+        schema
+            .execute(Request::new("{ slow }").timeout(std::time::Duration::from_millis(10)))
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError::new("Request timed out.", None)]
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new("{ slow }").timeout(std::time::Duration::from_secs(10)))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "slow": 1 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_skip_introspection_limits() {
+    #[derive(SimpleObject)]
+    struct Obj {
+        a: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct Query {
+        obj: Obj,
+    }
+
+    let introspection = "{ __schema { types { fields { type { name } } } } }";
+    let query = Query { obj: Obj { a: 1 } };
+    let schema = Schema::build(query, EmptyMutation, EmptySubscription)
+        .limit_depth(1)
+        .limit_complexity(3)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(introspection)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Query is too complex."
+    );
+
+    let query = Query { obj: Obj { a: 1 } };
+    let schema = Schema::build(query, EmptyMutation, EmptySubscription)
+        .limit_depth(1)
+        .limit_complexity(3)
+        .skip_introspection_limits()
+        .finish();
+    assert!(schema.execute(introspection).await.is_ok());
+    assert!(schema
+        .execute(
+            "query A { ...F } fragment F on Query { __type(name: \"Obj\") { fields { name } } }"
+        )
+        .await
+        .is_ok());
+    assert_eq!(
+        schema
+            .execute("{ __schema { types { name } } obj { a } }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Query is too complex."
+    );
+}
+
+#[tokio::test]
+pub async fn test_validate() {
+    #[derive(SimpleObject)]
+    struct Obj {
+        a: i32,
+        b: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    #[allow(unreachable_code)]
+    impl Query {
+        #[graphql(cache_control(max_age = 60))]
+        async fn obj(&self) -> Obj {
+            unreachable!()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(3)
+        .finish();
+
+    let result = schema.validate("{ obj { a b } }").await.unwrap();
+    assert_eq!(result.depth, 2);
+    assert_eq!(result.complexity, 3);
+    assert_eq!(result.cache_control.max_age, 60);
+
+    assert_eq!(
+        schema.validate("{ obj { a b c } }").await.unwrap_err()[0].message,
+        r#"Unknown field "c" on type "Obj"."#
+    );
+    assert_eq!(
+        schema
+            .validate("{ obj { a b } a: obj { a } }")
+            .await
+            .unwrap_err()[0]
+            .message,
+        "Query is too complex."
+    );
+    assert_eq!(
+        schema
+            .validate(Request::new("query A { obj { a } }").operation_name("B"))
+            .await
+            .unwrap_err()[0]
+            .message,
+        r#"Unknown operation named "B""#
+    );
+}
+
+#[tokio::test]
+pub async fn test_limit_errors() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn items(&self) -> Vec<Option<Item>> {
+            (0..10).map(|_| Some(Item)).collect()
+        }
+
+        async fn strict(&self) -> Vec<Item> {
+            (0..10).map(|_| Item).collect()
+        }
+    }
+
+    struct Item;
+
+    #[Object]
+    impl Item {
+        async fn value(&self) -> Result<i32> {
+            Err("failed".into())
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_errors(3)
+        .finish();
+
+    let errors = schema
+        .execute("{ a b c d e f }")
+        .await
+        .into_result()
+        .unwrap_err();
+    assert_eq!(errors.len(), 4);
+    assert_eq!(errors[0].message, r#"Unknown field "a" on type "Query"."#);
+    assert_eq!(
+        errors[3].message,
+        "Too many validation errors, error limit reached. Validation aborted."
+    );
+
+    let resp = schema.execute("{ items { value } }").await;
+    assert_eq!(resp.errors.len(), 4);
+    assert!(resp.errors[..3].iter().all(|err| err.message == "failed"));
+    assert_eq!(
+        resp.errors[3].message,
+        "Too many errors, error limit reached. Execution aborted."
+    );
+
+    // the errors of the non-null fields propagate to the root
+    let resp = schema.execute("{ items { value } strict { value } }").await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(resp.errors.len(), 4);
+    assert_eq!(
+        resp.errors[3].message,
+        "Too many errors, error limit reached. Execution aborted."
+    );
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(schema.execute("{ items { value } }").await.errors.len(), 10);
+}
+
+#[tokio::test]
+pub async fn test_limit_response_size() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static RESOLVED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn items(&self, count: usize) -> Vec<Item> {
+            (0..count).map(|_| Item).collect()
+        }
+
+        async fn names(&self, count: usize) -> Vec<Name> {
+            (0..count).map(|_| Name).collect()
+        }
+    }
+
+    struct Item;
+
+    struct Name;
+
+    #[Scalar]
+    impl ScalarType for Name {
+        fn parse(_value: Value) -> InputValueResult<Self> {
+            Ok(Name)
+        }
+
+        fn to_value(&self) -> Value {
+            RESOLVED.fetch_add(1, Ordering::Relaxed);
+            Value::String("x".repeat(100))
+        }
+    }
+
+    #[Object]
+    impl Item {
+        async fn name(&self) -> String {
+            RESOLVED.fetch_add(1, Ordering::Relaxed);
+            "x".repeat(100)
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_response_size(1000)
+        .finish();
+
+    assert!(schema.execute("{ items(count: 5) { name } }").await.is_ok());
+
+    RESOLVED.store(0, Ordering::Relaxed);
+    let resp = schema.execute("{ items(count: 100) { name } }").await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(
+        resp.errors
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec!["Response too large, the size limit of 1000 bytes is exceeded. Execution aborted."]
+    );
+    assert!(RESOLVED.load(Ordering::Relaxed) < 100);
+
+    assert!(schema
+        .execute(Request::new("{ items(count: 100) { name } }").response_size_limit(20000))
+        .await
+        .is_ok());
+
+    RESOLVED.store(0, Ordering::Relaxed);
+    let resp = schema.execute("{ names(count: 100) }").await;
+    assert_eq!(resp.data, Value::Null);
+    assert_eq!(
+        resp.errors
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec!["Response too large, the size limit of 1000 bytes is exceeded. Execution aborted."]
+    );
+    assert!(RESOLVED.load(Ordering::Relaxed) < 100);
+}
+
+#[tokio::test]
+pub async fn test_disable_anonymous_operations() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .disable_anonymous_operations()
+        .finish();
+
+    for query in ["{ value }", "query { value }"] {
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap_err(),
+            vec![ServerError {
+                message: "Anonymous operations are not allowed, the operation must have a name."
+                    .to_string(),
+                source: None,
+                locations: vec![Pos { line: 1, column: 1 }],
+                path: Vec::new(),
+                extensions: None,
+            }]
+        );
+    }
+
+    assert_eq!(
+        schema
+            .execute("query GetValue { value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_require_unique_batch_operation_names() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .require_unique_batch_operation_names()
+        .finish();
+
+    let errors = |resp: &Response| {
+        resp.errors
+            .iter()
+            .map(|err| err.message.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let resp = schema
+        .execute_batch(BatchRequest::Batch(vec![
+            Request::new("query A { value }"),
+            Request::new("query B { value } query C { value }").operation_name("B"),
+            Request::new("query A { value }"),
+            Request::new("{ value }"),
+            Request::new("query C { value"),
+        ]))
+        .await;
+    let resps = match resp {
+        BatchResponse::Batch(resps) => resps,
+        BatchResponse::Single(_) => panic!("expected a batch response"),
+    };
+    assert_eq!(
+        resps[..4].iter().map(errors).collect::<Vec<_>>(),
+        vec![
+            vec![r#"Operation name "A" is used by more than one request of the batch."#],
+            vec![],
+            vec![r#"Operation name "A" is used by more than one request of the batch."#],
+            vec!["Operation name required in batch request."],
+        ]
+    );
+    assert_eq!(resps[1].data, value!({ "value": 10 }));
+    // The syntax errors are reported by the execution.
+    assert!(errors(&resps[4])[0].starts_with(" --> 1:16"));
+
+    // A single request is not a batch.
+    assert!(schema
+        .execute_batch(BatchRequest::Single(Request::new("{ value }")))
+        .await
+        .is_ok());
+}
+
+#[test]
+pub fn test_registry_stats() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(InputObject)]
+    struct Filter {
+        color: Color,
+        limit: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn colors(&self, filter: Filter) -> Vec<Color> {
+            vec![filter.color]
+        }
+
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct SmallQuery;
+
+    #[Object]
+    impl SmallQuery {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let stats = schema.registry_stats();
+    let small = Schema::new(SmallQuery, EmptyMutation, EmptySubscription).registry_stats();
+
+    assert_eq!(stats.types, small.types + 2);
+    assert_eq!(stats.objects, small.objects);
+    assert_eq!(stats.enums, small.enums + 1);
+    assert_eq!(stats.input_objects, small.input_objects + 1);
+    assert_eq!(stats.fields, small.fields + 1);
+    assert_eq!(stats.arguments, small.arguments + 1);
+    assert_eq!(stats.input_fields, small.input_fields + 2);
+    assert_eq!(stats.enum_values, small.enum_values + 2);
+    assert!(stats.bytes > small.bytes);
+    assert!(stats.type_reference_bytes > small.type_reference_bytes);
+    assert!(stats.type_reference_bytes < stats.bytes);
+
+    // The variants share the registry of the schema.
+    assert_eq!(schema.variant().finish().registry_stats(), stats);
+}
+
+#[tokio::test]
+pub async fn test_schema_variants() {
+    struct Tenant(&'static str);
+    struct Region(&'static str);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn tenant(&self, ctx: &Context<'_>) -> &str {
+            ctx.data_unchecked::<Tenant>().0
+        }
+
+        async fn region(&self, ctx: &Context<'_>) -> &str {
+            ctx.data_unchecked::<Region>().0
+        }
+
+        async fn obj(&self) -> Query {
+            Query
+        }
+
+        #[graphql(visible("internal"))]
+        async fn secret(&self) -> i32 {
+            42
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .visibility_profiles(["public", "internal"])
+        .data(Tenant("default"))
+        .data(Region("eu"))
+        .limit_depth(3)
+        .finish();
+    let acme = schema
+        .variant()
+        .data(Tenant("acme"))
+        .data(VisibilityProfile::new("internal"))
+        .finish();
+    let small = acme.variant().limit_depth(1).finish();
+
+    assert_eq!(
+        acme.execute("{ tenant region secret }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "tenant": "acme", "region": "eu", "secret": 42 })
+    );
+    assert_eq!(
+        schema
+            .execute("{ tenant region }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "tenant": "default", "region": "eu" })
+    );
+    assert!(schema.execute("{ secret }").await.is_err());
+
+    // The data and the limits are inherited from the original schema.
+    assert!(acme
+        .execute("{ obj { obj { obj { tenant } } } }")
+        .await
+        .is_err());
+    assert_eq!(
+        small
+            .execute("{ tenant secret }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "tenant": "acme", "secret": 42 })
+    );
+    assert!(small.execute("{ obj { tenant } }").await.is_err());
+    assert!(schema.execute("{ obj { tenant } }").await.is_ok());
+
+    assert_eq!(acme.sdl(), schema.sdl());
+}
+
+#[cfg(feature = "dynamic-schema")]
+#[tokio::test]
+pub async fn test_dynamic_fields() {
+    use async_graphql::dynamic;
+
+    #[derive(SimpleObject)]
+    struct User {
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self) -> User {
+            User {
+                name: "a".to_string(),
+            }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .dynamic_field(
+            "Query",
+            dynamic::Field::new(
+                "add",
+                dynamic::TypeRef::named_nn(dynamic::TypeRef::INT),
+                |ctx| {
+                    dynamic::FieldFuture::new(async move {
+                        let a = ctx.args.try_get("a")?.i64()?;
+                        let b = ctx.args.try_get("b")?.i64()?;
+                        Ok(Some(Value::from(a + b)))
+                    })
+                },
+            )
+            .argument(dynamic::InputValue::new(
+                "a",
+                dynamic::TypeRef::named_nn(dynamic::TypeRef::INT),
+            ))
+            .argument(dynamic::InputValue::new(
+                "b",
+                dynamic::TypeRef::named_nn(dynamic::TypeRef::INT),
+            )),
+        )
+        .dynamic_field(
+            "User",
+            dynamic::Field::new(
+                "tags",
+                dynamic::TypeRef::named_nn_list_nn(dynamic::TypeRef::STRING),
+                |_| {
+                    dynamic::FieldFuture::new(async move {
+                        Ok(Some(dynamic::FieldValue::list([
+                            dynamic::FieldValue::value("x"),
+                            dynamic::FieldValue::value("y"),
+                        ])))
+                    })
+                },
+            ),
+        )
+        .finish();
+
+    assert!(schema.sdl().contains("add(a: Int!, b: Int!): Int!"));
+    assert!(schema.sdl().contains("tags: [String!]!"));
+
+    assert_eq!(
+        schema
+            .execute("{ add(a: 1, b: 2) user { name tags } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "add": 3,
+            "user": { "name": "a", "tags": ["x", "y"] },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ add(a: 1) }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Field \"add\" argument \"b\" of type \"Query\" is required but not provided"
+    );
+}
+
+#[cfg(feature = "dynamic-schema")]
+#[tokio::test]
+pub async fn test_dynamic_fields_parent_value() {
+    use async_graphql::dynamic;
+
+    #[derive(SimpleObject)]
+    struct User {
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self) -> User {
+            User {
+                name: "abc".to_string(),
+            }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .dynamic_field(
+            "User",
+            dynamic::Field::new(
+                "score",
+                dynamic::TypeRef::named_nn(dynamic::TypeRef::FLOAT),
+                |ctx| {
+                    dynamic::FieldFuture::new(async move {
+                        let user = ctx.parent_value.try_downcast_ref::<User>()?;
+                        let scale = ctx.args.try_get("scale")?.f64()?;
+                        Ok(Some(Value::from(user.name.len() as f64 * scale)))
+                    })
+                },
+            )
+            .argument(dynamic::InputValue::new(
+                "scale",
+                dynamic::TypeRef::named_nn(dynamic::TypeRef::FLOAT),
+            )),
+        )
+        .finish();
+
+    assert!(schema.sdl().contains("score(scale: Float!): Float!"));
+    assert_eq!(
+        schema
+            .execute("{ user { name score(scale: 1.5) } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": { "name": "abc", "score": 4.5 } })
+    );
+}
+
+#[cfg(feature = "dynamic-schema")]
+#[test]
+pub fn test_dynamic_field_errors() {
+    use async_graphql::dynamic;
+
+    #[derive(SimpleObject)]
+    struct User {
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self) -> User {
+            User {
+                name: "a".to_string(),
+            }
+        }
+    }
+
+    let field = |name: &str, ty: &str| {
+        dynamic::Field::new(name, dynamic::TypeRef::named(ty), |_| {
+            dynamic::FieldFuture::new(async move { Ok(None::<dynamic::FieldValue>) })
+        })
+    };
+
+    let err = |type_name: &str, field: dynamic::Field| {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .dynamic_field(type_name, field)
+            .try_finish()
+            .err()
+            .map(|err| err.0)
+    };
+
+    assert_eq!(
+        err("User", field("friend", "User")),
+        Some("Dynamic field \"User.friend\" must return a scalar or an enum".to_string())
+    );
+    assert_eq!(
+        err("Unknown", field("value", "Int")),
+        Some("Type \"Unknown\" is not an object".to_string())
+    );
+    assert_eq!(
+        err("User", field("name", "String")),
+        Some("Field \"User.name\" already exists".to_string())
+    );
+    assert_eq!(err("User", field("age", "Int")), None);
+
+    assert_eq!(
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .dynamic_field("User", field("age", "Int"))
+            .dynamic_field("User", field("age", "Int"))
+            .try_finish()
+            .err()
+            .map(|err| err.0),
+        Some("Field \"User.age\" already exists".to_string())
+    );
+}
+
+#[cfg(feature = "dynamic-schema")]
+#[test]
+#[should_panic(expected = "Field \"Query.value\" already exists")]
+pub fn test_dynamic_field_conflict() {
+    use async_graphql::dynamic;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let _ = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .dynamic_field(
+            "Query",
+            dynamic::Field::new(
+                "value",
+                dynamic::TypeRef::named(dynamic::TypeRef::INT),
+                |_| dynamic::FieldFuture::new(async move { Ok(None::<dynamic::FieldValue>) }),
+            ),
+        )
+        .finish();
+}
@@ -0,0 +1,200 @@
+use async_graphql::*;
+
+fn find<'a>(changes: &'a [SchemaChange], path: &str) -> Vec<&'a SchemaChange> {
+    changes
+        .iter()
+        .filter(|change| change.path == path)
+        .collect()
+}
+
+#[test]
+fn test_diff_fields() {
+    let changes = diff_schema(
+        r#"
+        type Query {
+            a: Int
+            b: String!
+            c(id: ID!): String
+            d: [Int]
+        }
+        "#,
+        r#"
+        type Query {
+            a: Int!
+            b: String
+            c(id: ID, name: String!): String
+            d: [Int] @deprecated
+            e: Int
+        }
+        "#,
+    )
+    .unwrap();
+
+    let a = find(&changes, "Query.a");
+    assert_eq!(a.len(), 1);
+    assert_eq!(a[0].kind, SchemaChangeKind::FieldTypeChanged);
+    assert_eq!(a[0].criticality, Criticality::Safe);
+
+    let b = find(&changes, "Query.b");
+    assert_eq!(b[0].kind, SchemaChangeKind::FieldTypeChanged);
+    assert_eq!(b[0].criticality, Criticality::Breaking);
+
+    let id = find(&changes, "Query.c.id");
+    assert_eq!(id[0].kind, SchemaChangeKind::ArgumentTypeChanged);
+    assert_eq!(id[0].criticality, Criticality::Safe);
+
+    let name = find(&changes, "Query.c.name");
+    assert_eq!(name[0].kind, SchemaChangeKind::ArgumentAdded);
+    assert_eq!(name[0].criticality, Criticality::Breaking);
+
+    let d = find(&changes, "Query.d");
+    assert_eq!(d[0].kind, SchemaChangeKind::FieldDeprecated);
+    assert_eq!(d[0].criticality, Criticality::Safe);
+
+    let e = find(&changes, "Query.e");
+    assert_eq!(e[0].kind, SchemaChangeKind::FieldAdded);
+    assert_eq!(e[0].criticality, Criticality::Safe);
+}
+
+#[test]
+fn test_diff_types() {
+    let changes = diff_schema(
+        r#"
+        type Query { a: A u: U e: E }
+        type A { value: Int }
+        type B { value: Int }
+        union U = A | B
+        enum E { X Y }
+        input I { a: Int }
+        scalar S
+        "#,
+        r#"
+        type Query { a: A u: U e: E }
+        type A { value: Int }
+        type B { value: Int }
+        type C { value: Int }
+        union U = A | C
+        enum E { X Z }
+        input I { a: Int b: Int! c: Int }
+        interface S { value: Int }
+        "#,
+    )
+    .unwrap();
+
+    let actual = changes
+        .iter()
+        .map(|change| (change.kind, change.criticality, change.path.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        actual,
+        vec![
+            (
+                SchemaChangeKind::UnionMemberRemoved,
+                Criticality::Breaking,
+                "U"
+            ),
+            (
+                SchemaChangeKind::UnionMemberAdded,
+                Criticality::Dangerous,
+                "U"
+            ),
+            (
+                SchemaChangeKind::EnumValueRemoved,
+                Criticality::Breaking,
+                "E.Y"
+            ),
+            (
+                SchemaChangeKind::EnumValueAdded,
+                Criticality::Dangerous,
+                "E.Z"
+            ),
+            (
+                SchemaChangeKind::InputFieldAdded,
+                Criticality::Breaking,
+                "I.b"
+            ),
+            (
+                SchemaChangeKind::InputFieldAdded,
+                Criticality::Dangerous,
+                "I.c"
+            ),
+            (
+                SchemaChangeKind::TypeKindChanged,
+                Criticality::Breaking,
+                "S"
+            ),
+            (SchemaChangeKind::TypeAdded, Criticality::Safe, "C"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_diff_exported_sdl() {
+    #[derive(SimpleObject)]
+    struct Query {
+        a: i32,
+        b: i32,
+    }
+
+    let old_sdl = Schema::new(Query { a: 1, b: 2 }, EmptyMutation, EmptySubscription).sdl();
+
+    #[derive(SimpleObject)]
+    #[graphql(name = "Query")]
+    struct NewQuery {
+        a: i32,
+    }
+
+    let new_sdl = Schema::new(NewQuery { a: 1 }, EmptyMutation, EmptySubscription).sdl();
+
+    assert!(diff_schema(&old_sdl, &old_sdl).unwrap().is_empty());
+
+    let changes = diff_schema(&old_sdl, &new_sdl).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, SchemaChangeKind::FieldRemoved);
+    assert_eq!(changes[0].path, "Query.b");
+    assert!(changes[0].is_breaking());
+    assert_eq!(
+        changes[0].to_string(),
+        "[breaking] Field `Query.b` was removed"
+    );
+}
+
+#[test]
+fn test_diff_directive_repeatable() {
+    let old_sdl = r#"
+    directive @a on FIELD_DEFINITION
+    directive @b repeatable on FIELD_DEFINITION
+
+    type Query {
+        value: Int
+    }
+    "#;
+    let new_sdl = r#"
+    directive @a repeatable on FIELD_DEFINITION
+    directive @b on FIELD_DEFINITION
+
+    type Query {
+        value: Int
+    }
+    "#;
+
+    let changes = diff_schema(old_sdl, new_sdl).unwrap();
+    assert_eq!(
+        changes
+            .iter()
+            .map(|change| (change.kind, change.criticality, change.path.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                SchemaChangeKind::DirectiveRepeatableChanged,
+                Criticality::Safe,
+                "@a"
+            ),
+            (
+                SchemaChangeKind::DirectiveRepeatableChanged,
+                Criticality::Breaking,
+                "@b"
+            ),
+        ]
+    );
+}
@@ -0,0 +1,117 @@
+use async_graphql::{meta::TypeKind, *};
+
+#[tokio::test]
+pub async fn test_schema_meta() {
+    /// A user
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        #[graphql(deprecation = "use `displayName`")]
+        name: String,
+        #[graphql(tag = "internal")]
+        email: String,
+    }
+
+    #[derive(InputObject)]
+    struct UserFilter {
+        #[graphql(default = 10)]
+        limit: i32,
+        #[graphql(secret)]
+        token: String,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Role {
+        Admin,
+        Guest,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn users(&self, _filter: UserFilter) -> Vec<User> {
+            Vec::new()
+        }
+
+        async fn role(&self) -> Role {
+            Role::Admin
+        }
+
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User {
+                id,
+                name: String::new(),
+                email: String::new(),
+            }
+        }
+
+        async fn field_count(&self, ctx: &Context<'_>) -> usize {
+            ctx.schema_meta().query_type().fields().count()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let meta = schema.meta();
+    assert!(meta.mutation_type().is_none());
+    assert!(meta.subscription_type().is_none());
+    assert!(meta.is_federation_enabled());
+
+    let query = meta.query_type();
+    assert_eq!(query.kind(), TypeKind::Object);
+    assert_eq!(
+        query.fields().map(|field| field.name()).collect::<Vec<_>>(),
+        vec!["users", "role", "fieldCount", "_service", "_entities"]
+    );
+
+    let users = query.field_by_name("users").unwrap();
+    assert_eq!(users.ty(), "[User!]!");
+    assert_eq!(users.named_type().unwrap().name(), "User");
+    let filter = users.arg_by_name("filter").unwrap();
+    assert_eq!(filter.ty(), "UserFilter!");
+
+    let user = meta.type_by_name("[User!]!").unwrap();
+    assert_eq!(user.description(), Some("A user"));
+    assert!(user.is_entity());
+    assert_eq!(user.keys(), &["id".to_string()]);
+    let name = user.field_by_name("name").unwrap();
+    assert!(name.is_deprecated());
+    assert_eq!(name.deprecation_reason(), Some("use `displayName`"));
+    assert_eq!(
+        user.field_by_name("email").unwrap().tags(),
+        &["internal".to_string()]
+    );
+
+    let user_filter = meta.type_by_name("UserFilter").unwrap();
+    assert_eq!(user_filter.kind(), TypeKind::InputObject);
+    let limit = user_filter.input_field_by_name("limit").unwrap();
+    assert_eq!(limit.default_value(), Some("10"));
+    assert!(user_filter
+        .input_field_by_name("token")
+        .unwrap()
+        .is_secret());
+
+    let role = meta.type_by_name("Role").unwrap();
+    assert_eq!(
+        role.enum_values()
+            .map(|value| value.name())
+            .collect::<Vec<_>>(),
+        vec!["ADMIN", "GUEST"]
+    );
+
+    let entity = meta.type_by_name("_Entity").unwrap();
+    assert_eq!(entity.possible_types().collect::<Vec<_>>(), vec!["User"]);
+
+    let skip = meta.directive_by_name("skip").unwrap();
+    assert!(!skip.is_repeatable());
+    assert_eq!(
+        skip.args().map(|arg| arg.name()).collect::<Vec<_>>(),
+        vec!["if"]
+    );
+
+    assert_eq!(
+        schema.execute("{ fieldCount }").await.data,
+        value!({ "fieldCount": 5 })
+    );
+}
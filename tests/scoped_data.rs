@@ -0,0 +1,149 @@
+use async_graphql::*;
+
+struct Replica(&'static str);
+
+fn replica_by_name(name: &str) -> Replica {
+    match name {
+        "a" => Replica("a"),
+        _ => Replica("b"),
+    }
+}
+
+struct Node {
+    children: usize,
+}
+
+#[Object]
+impl Node {
+    async fn replica(&self, ctx: &Context<'_>) -> &'static str {
+        ctx.data_unchecked::<Replica>().0
+    }
+
+    async fn primary(&self, ctx: &Context<'_>) -> Option<&'static str> {
+        ctx.schema_data_opt::<Replica>().map(|replica| replica.0)
+    }
+
+    async fn child(&self) -> Node {
+        Node { children: 0 }
+    }
+
+    async fn children(&self) -> Vec<Node> {
+        (0..self.children).map(|_| Node { children: 0 }).collect()
+    }
+
+    async fn switch(&self, ctx: &Context<'_>, replica: String) -> Node {
+        ctx.scoped_data().insert(replica_by_name(&replica));
+        Node { children: 2 }
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn node(&self) -> Node {
+        Node { children: 0 }
+    }
+
+    async fn replica(&self, ctx: &Context<'_>, replica: String) -> Node {
+        ctx.scoped_data().insert(replica_by_name(&replica));
+        Node { children: 2 }
+    }
+
+    async fn request(&self, ctx: &Context<'_>) -> Option<String> {
+        ctx.request_data_opt::<String>().cloned()
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(Replica("primary"))
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_scoped_data_overrides_descendants() {
+    let query = r#"{
+        node { replica }
+        replica(replica: "a") {
+            replica
+            primary
+            child { child { replica } }
+            children { replica }
+            ... on Node { fragment: replica }
+        }
+    }"#;
+    assert_eq!(
+        schema().execute(query).await.into_result().unwrap().data,
+        value!({
+            "node": { "replica": "primary" },
+            "replica": {
+                "replica": "a",
+                "primary": "primary",
+                "child": { "child": { "replica": "a" } },
+                "children": [{ "replica": "a" }, { "replica": "a" }],
+                "fragment": "a",
+            },
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_scoped_data_nearest_scope() {
+    let query = r#"{
+        replica(replica: "a") {
+            switch(replica: "b") {
+                replica
+                children { replica }
+            }
+            replica
+        }
+    }"#;
+    assert_eq!(
+        schema().execute(query).await.into_result().unwrap().data,
+        value!({
+            "replica": {
+                "switch": {
+                    "replica": "b",
+                    "children": [{ "replica": "b" }, { "replica": "b" }],
+                },
+                "replica": "a",
+            },
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_scoped_data_not_visible_to_the_field() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>) -> &'static str {
+            ctx.scoped_data().insert(Replica("a"));
+            ctx.data_unchecked::<Replica>().0
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(Replica("primary"))
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ value }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": "primary" })
+    );
+}
+
+#[tokio::test]
+pub async fn test_request_data() {
+    let request = Request::new("{ request }").data("request".to_string());
+    assert_eq!(
+        schema().execute(request).await.into_result().unwrap().data,
+        value!({ "request": "request" })
+    );
+}
@@ -0,0 +1,285 @@
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_secret_argument_error_is_redacted() {
+    #[derive(InputObject)]
+    struct Credentials {
+        username: String,
+        #[graphql(secret, validator(min_length = 3, one_of("letmein")))]
+        password: String,
+    }
+
+    #[derive(OneofObject)]
+    enum Token {
+        #[graphql(secret, validator(custom = "check_token"))]
+        Bearer(String),
+    }
+
+    struct Pin(String);
+
+    #[Scalar]
+    impl ScalarType for Pin {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match value {
+                Value::String(s) if s.chars().all(|c| c.is_ascii_digit()) => Ok(Pin(s)),
+                _ => Err(InputValueError::custom(format!("{} is not a pin", value))),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    fn check_token(value: &String) -> Result<(), String> {
+        if value.starts_with("tk_") {
+            Ok(())
+        } else {
+            Err(format!("{} is not a valid token", value))
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn login(
+            &self,
+            #[graphql(secret, validator(one_of("sesame")))] password: String,
+        ) -> bool {
+            password == "sesame"
+        }
+
+        async fn pin(&self, #[graphql(secret)] pin: Pin) -> bool {
+            !pin.0.is_empty()
+        }
+
+        async fn credentials(&self, credentials: Credentials) -> String {
+            credentials.username
+        }
+
+        async fn token(&self, token: Token) -> bool {
+            matches!(token, Token::Bearer(_))
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let error = |query: &'static str| {
+        let schema = &schema;
+        async move {
+            schema
+                .execute(query)
+                .await
+                .into_result()
+                .unwrap_err()
+                .remove(0)
+                .message
+        }
+    };
+
+    assert_eq!(
+        error(r#"{ login(password: "hunter2") }"#).await,
+        r#"Failed to parse "String": the value is "<secret>", must be one of "sesame""#
+    );
+
+    assert_eq!(
+        error(r#"{ pin(pin: "12ab") }"#).await,
+        r#"Failed to parse "Pin": "<secret>" is not a pin"#
+    );
+
+    assert_eq!(
+        error(r#"{ credentials(credentials: { username: "abc", password: "1234" }) }"#).await,
+        r#"Invalid value for argument "credentials.password", Failed to parse "String": the value is "<secret>", must be one of "letmein" (occurred while parsing "Credentials")"#
+    );
+
+    assert_eq!(
+        error(r#"{ token(token: { bearer: "xyz" }) }"#).await,
+        r#"Invalid value for argument "token.bearer", Failed to parse "String": <secret> is not a valid token (occurred while parsing "Token")"#
+    );
+}
+
+#[tokio::test]
+pub async fn test_secret_arguments_in_extension_context() {
+    use std::sync::{Arc, Mutex};
+
+    use async_graphql::{
+        extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery},
+        parser::types::ExecutableDocument,
+    };
+
+    #[derive(Default, Clone)]
+    struct Captured(Arc<Mutex<Vec<String>>>);
+
+    impl ExtensionFactory for Captured {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(self.clone())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for Captured {
+        async fn parse_query(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            query: &str,
+            variables: &Variables,
+            next: NextParseQuery<'_>,
+        ) -> ServerResult<ExecutableDocument> {
+            let doc = next.run(ctx, query, variables).await?;
+            let mut captured = self.0.lock().unwrap();
+            captured.push(ctx.stringify_execute_doc(&doc, variables));
+            captured.push(ctx.redact_variables(&doc, variables).to_string());
+            Ok(doc)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn login(&self, username: String, #[graphql(secret)] password: String) -> bool {
+            !username.is_empty() && !password.is_empty()
+        }
+    }
+
+    let captured = Captured::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(captured.clone())
+        .finish();
+    let resp = schema
+        .execute(
+            Request::new(
+                "query Login($u: String!, $p: String!) { login(username: $u, password: $p) }",
+            )
+            .variables(Variables::from_value(
+                value!({ "u": "abc", "p": "hunter2" }),
+            )),
+        )
+        .await;
+    assert_eq!(resp.data, value!({ "login": true }));
+    assert_eq!(
+        *captured.0.lock().unwrap(),
+        vec![
+            r#"query Login($u: String!, $p: String!) { login(username: "abc", password: "<secret>") }"#
+                .to_string(),
+            r#"{p: "<secret>", u: "abc"}"#.to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_secret_enum_values_are_not_reported() {
+    use std::sync::{Arc, Mutex};
+
+    use async_graphql::extensions::{SchemaUsage, SchemaUsageReporter};
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Role {
+        Admin,
+        User,
+    }
+
+    #[derive(InputObject)]
+    struct Grant {
+        #[graphql(secret)]
+        role: Role,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn check(&self, #[graphql(secret)] role: Role, other: Option<Role>) -> bool {
+            role == Role::Admin && other.is_some()
+        }
+
+        async fn grant(&self, grant: Grant) -> bool {
+            grant.role == Role::Admin
+        }
+    }
+
+    let reports = Arc::new(Mutex::new(Vec::<SchemaUsage>::new()));
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(SchemaUsageReporter::new({
+            let reports = reports.clone();
+            move |usage| reports.lock().unwrap().push(usage)
+        }))
+        .finish();
+
+    schema
+        .execute("{ check(role: ADMIN, other: USER) grant(grant: { role: ADMIN }) }")
+        .await
+        .into_result()
+        .unwrap();
+    schema
+        .execute(
+            Request::new("query($role: Role!) { check(role: $role) }")
+                .variables(Variables::from_value(value!({ "role": "ADMIN" }))),
+        )
+        .await
+        .into_result()
+        .unwrap();
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(
+        reports[0].enum_values.iter().collect::<Vec<_>>(),
+        vec!["Role.USER"]
+    );
+    assert!(reports[0].fields.contains("Grant.role"));
+    assert!(reports[1].enum_values.is_empty());
+}
+
+#[cfg(feature = "apollo_persisted_queries")]
+#[tokio::test]
+pub async fn test_secret_literals_are_not_persisted() {
+    use async_graphql::extensions::apollo_persisted_queries::{
+        ApolloPersistedQueries, LruCacheStorage,
+    };
+    use sha2::{Digest, Sha256};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn login(&self, username: String, #[graphql(secret)] password: String) -> bool {
+            !username.is_empty() && !password.is_empty()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(ApolloPersistedQueries::new(LruCacheStorage::new(256)))
+        .finish();
+    let persisted_request = |query: &str, sha256_hash: String| {
+        let mut request = Request::new(query);
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            value!({ "version": 1, "sha256Hash": sha256_hash }),
+        );
+        request
+    };
+
+    for (query, persisted) in [
+        (r#"{ login(username: "abc", password: "hunter2") }"#, false),
+        (
+            "query($p: String!) { login(username: \"abc\", password: $p) }",
+            true,
+        ),
+    ] {
+        let sha256_hash = format!("{:x}", Sha256::digest(query.as_bytes()));
+        schema
+            .execute(
+                persisted_request(query, sha256_hash.clone())
+                    .variables(Variables::from_value(value!({ "p": "hunter2" }))),
+            )
+            .await
+            .into_result()
+            .unwrap();
+        let resp = schema
+            .execute(
+                persisted_request("", sha256_hash)
+                    .variables(Variables::from_value(value!({ "p": "hunter2" }))),
+            )
+            .await;
+        assert_eq!(resp.is_ok(), persisted, "{}", query);
+    }
+}
@@ -0,0 +1,155 @@
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self, text: i32) -> i32 {
+        text
+    }
+}
+
+fn extensions(resp: &Response) -> Option<serde_json::Value> {
+    resp.errors[0]
+        .extensions
+        .as_ref()
+        .map(|extensions| serde_json::to_value(extensions).unwrap())
+}
+
+#[tokio::test]
+pub async fn test_source_preview_block_string() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+  value(text: """
+    line 1
+    line 2
+  """)
+}"#;
+    let resp = schema.execute(query).await;
+    assert_eq!(
+        resp.errors[0].message,
+        r#"Invalid value for argument "text", expected type "Int""#
+    );
+    assert_eq!(resp.errors[0].locations, vec![Pos { line: 2, column: 9 }]);
+    assert_eq!(
+        extensions(&resp),
+        Some(serde_json::json!({
+            "endLocation": { "line": 5, "column": 5 },
+            "sourcePreview": ([
+                r#"2 |   value(text: """"#,
+                "  |         ^",
+                "3 |     line 1",
+                "4 |     line 2",
+                r#"5 |   """)"#,
+                "  |     ^",
+            ]
+            .join("\n")),
+        }))
+    );
+}
+
+#[tokio::test]
+pub async fn test_source_preview_unterminated_block_string() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let mut query = "{\n  value(text: \"\"\"\n".to_string();
+    for idx in 0..10 {
+        query.push_str(&format!("    line {}\n", idx));
+    }
+    query.push_str("  )\n}");
+
+    let resp = schema.execute(query).await;
+    assert_eq!(
+        resp.errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 17
+        }]
+    );
+    assert_eq!(
+        extensions(&resp),
+        Some(serde_json::json!({
+            "endLocation": { "line": 14, "column": 1 },
+            "sourcePreview": ([
+                r#" 2 |   value(text: """"#,
+                "   |                 ^",
+                " 3 |     line 0",
+                " 4 |     line 1",
+                "   | ...",
+                "12 |     line 9",
+                "13 |   )",
+                "14 | }",
+                "   | ^",
+            ]
+            .join("\n")),
+        }))
+    );
+}
+
+#[tokio::test]
+pub async fn test_source_preview_large_literal() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let text = "a".repeat(100);
+    let resp = schema
+        .execute(format!(r#"{{ value(text: "{}") }}"#, text))
+        .await;
+    assert_eq!(resp.errors[0].locations, vec![Pos { line: 1, column: 9 }]);
+    assert_eq!(
+        extensions(&resp),
+        Some(serde_json::json!({
+            "endLocation": { "line": 1, "column": 116 },
+            "sourcePreview": format!(
+                "1 | {{ value(text: \"{}\") }}\n  |         {}",
+                text,
+                "^".repeat(108)
+            ),
+        }))
+    );
+
+    // the errors in small literals are not changed
+    let resp = schema.execute(r#"{ value(text: "abc") }"#).await;
+    assert_eq!(extensions(&resp), None);
+}
+
+#[tokio::test]
+pub async fn test_source_preview_multiple_errors() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let text = "a".repeat(100);
+    let resp = schema
+        .execute(format!(
+            "{{\n  a: value(text: \"{}\")\n  b: value(text: \"{}\")\n}}",
+            text, text
+        ))
+        .await;
+    let end_locations = resp
+        .errors
+        .iter()
+        .map(|err| {
+            (
+                err.locations.clone(),
+                err.extensions
+                    .as_ref()
+                    .and_then(|extensions| extensions.get("endLocation"))
+                    .cloned(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        end_locations,
+        vec![
+            (
+                vec![Pos {
+                    line: 2,
+                    column: 12
+                }],
+                Some(value!({ "line": 2, "column": 119 }))
+            ),
+            (
+                vec![Pos {
+                    line: 3,
+                    column: 12
+                }],
+                Some(value!({ "line": 3, "column": 119 }))
+            ),
+        ]
+    );
+}
@@ -0,0 +1,68 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct MyObj {
+    a: i32,
+    b: String,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn obj(&self, a: i32) -> MyObj {
+        MyObj {
+            a,
+            b: "abc".to_string(),
+        }
+    }
+}
+
+type MySchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+#[test]
+pub fn test_static_query_sdl() {
+    let schema_sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+
+    // the queries are checked against this snapshot at compile time
+    let path = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("tests/schemas/static_query.schema.graphql");
+    let expected_schema = std::fs::read_to_string(&path).unwrap();
+    if schema_sdl != expected_schema {
+        std::fs::write(path, schema_sdl).unwrap();
+        panic!("schema was not up-to-date. rerun")
+    }
+}
+
+#[tokio::test]
+pub async fn test_static_query() {
+    const OBJ_QUERY: StaticQuery<MySchema> = graphql_query!(
+        MySchema,
+        r#"
+        query Obj($a: Int!) {
+            obj(a: $a) { a b __typename }
+            ... on Query { obj2: obj(a: 20) { ...ObjFields } }
+        }
+
+        fragment ObjFields on MyObj { a }
+        "#,
+        sdl = "tests/schemas/static_query.schema.graphql",
+    );
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(OBJ_QUERY.as_str())
+                    .variables(Variables::from_value(value!({ "a": 10 })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "obj": { "a": 10, "b": "abc", "__typename": "MyObj" },
+            "obj2": { "a": 20 },
+        })
+    );
+}
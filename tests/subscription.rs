@@ -420,3 +420,137 @@ pub async fn test_subscription_fieldresult() {
 
     assert!(stream.next().await.is_none());
 }
+
+#[tokio::test]
+pub async fn test_subscription_typed_error() {
+    #[derive(Debug)]
+    struct MyError(i32);
+
+    impl From<MyError> for Error {
+        fn from(err: MyError) -> Self {
+            Error::new(format!("MyError {}", err.0))
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = Result<i32, MyError>> {
+            futures_util::stream::iter(vec![Ok(1), Err(MyError(2)), Ok(3)])
+        }
+
+        #[graphql(terminate_on_error)]
+        async fn values_terminate(&self) -> impl Stream<Item = Result<i32, MyError>> {
+            futures_util::stream::iter(vec![Ok(1), Err(MyError(2)), Ok(3)])
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+
+    let mut stream = schema.execute_stream("subscription { values }");
+    assert_eq!(
+        stream.next().await.unwrap(),
+        Response::new(value!({ "values": 1 }))
+    );
+    assert_eq!(
+        stream.next().await.unwrap().errors,
+        vec![ServerError {
+            message: "MyError 2".to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 16
+            }],
+            path: vec![PathSegment::Field("values".to_owned())],
+            extensions: None,
+        }]
+    );
+    assert_eq!(
+        stream.next().await.unwrap(),
+        Response::new(value!({ "values": 3 }))
+    );
+    assert!(stream.next().await.is_none());
+
+    let mut stream = schema.execute_stream("subscription { valuesTerminate }");
+    assert_eq!(
+        stream.next().await.unwrap(),
+        Response::new(value!({ "valuesTerminate": 1 }))
+    );
+    assert_eq!(
+        stream.next().await.unwrap().errors,
+        vec![ServerError {
+            message: "MyError 2".to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 16
+            }],
+            path: vec![PathSegment::Field("valuesTerminate".to_owned())],
+            extensions: None,
+        }]
+    );
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+pub async fn test_subscription_initial_value_and_replay() {
+    struct Subscription {
+        events: MemoryReplayBuffer<i32>,
+    }
+
+    #[Subscription]
+    impl Subscription {
+        async fn events(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(10..12)
+                .with_replay(&self.events)
+                .with_initial_value(0)
+        }
+    }
+
+    let events = MemoryReplayBuffer::new(3);
+    for i in 1..=5 {
+        events.push(i);
+    }
+    let schema = Schema::new(Query, EmptyMutation, Subscription { events });
+
+    let values = schema
+        .execute_stream("subscription { events }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        values,
+        vec![0, 3, 4, 5, 10, 11]
+            .into_iter()
+            .map(|i| value!({ "events": i }))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+pub async fn test_subscription_replay_subscribes_first() {
+    let events = MemoryReplayBuffer::new(3);
+    for i in 1..=5 {
+        events.push(i);
+    }
+
+    // a stream subscribing when it is first polled, while an event is recorded
+    let stream = futures_util::stream::poll_fn({
+        let events = events.clone();
+        let mut subscribed = false;
+        move |_| {
+            if !subscribed {
+                subscribed = true;
+                events.push(6);
+                return std::task::Poll::Ready(Some(7));
+            }
+            std::task::Poll::Ready(None)
+        }
+    });
+
+    assert_eq!(
+        stream.with_replay(&events).collect::<Vec<_>>().await,
+        vec![4, 5, 6, 7]
+    );
+}
@@ -0,0 +1,96 @@
+use async_graphql::{http::WebSocketProtocols, *};
+use futures_channel::mpsc;
+use futures_util::stream::{Stream, StreamExt};
+
+struct ConnectionId(u64);
+
+struct Token(String);
+
+#[tokio::test]
+pub async fn test_connection_data() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+            let value = format!(
+                "{}:{}",
+                ctx.data_unchecked::<ConnectionId>().0,
+                ctx.data_unchecked::<Token>().0
+            );
+            futures_util::stream::once(async move { value })
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .connection_data(Data::default().data(ConnectionId(1)))
+        .on_connection_init(|payload| async move {
+            let token = payload["token"].as_str().ok_or("Token is required.")?;
+            Ok(Data::default().data(Token(token.to_string())))
+        });
+
+    tx.unbounded_send(
+        serde_json::json!({
+            "type": "connection_init",
+            "payload": { "token": "123456" },
+        })
+        .to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({ "type": "connection_ack" }),
+    );
+
+    for id in ["1", "2"] {
+        tx.unbounded_send(
+            serde_json::json!({
+                "type": "subscribe",
+                "id": id,
+                "payload": { "query": "subscription { values }" },
+            })
+            .to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "next",
+                "id": id,
+                "payload": { "data": { "values": "1:123456" } },
+            }),
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({ "type": "complete", "id": id }),
+        );
+    }
+}
+
+#[test]
+pub fn test_data_accessors() {
+    let mut data = Data::default()
+        .data(ConnectionId(1))
+        .data(Token("123456".to_string()));
+    assert_eq!(data.data_opt::<ConnectionId>().unwrap().0, 1);
+    assert_eq!(data.data_opt::<Token>().unwrap().0, "123456");
+    assert!(data.data_opt::<String>().is_none());
+
+    data.merge(Data::default().data(ConnectionId(2)));
+    assert_eq!(data.data_opt::<ConnectionId>().unwrap().0, 2);
+    assert_eq!(data.data_opt::<Token>().unwrap().0, "123456");
+}
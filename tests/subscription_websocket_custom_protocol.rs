@@ -0,0 +1,129 @@
+use async_graphql::{
+    http::{
+        ClientMessage, ServerEvent, WebSocketProtocol, WebSocketProtocolSet, WebSocketProtocols,
+        WsMessage,
+    },
+    *,
+};
+use futures_util::stream::{Stream, StreamExt};
+
+/// A legacy protocol, which names the messages with `op` and sends the
+/// results as `result` messages.
+struct LegacyProtocol;
+
+impl WebSocketProtocol for LegacyProtocol {
+    fn sec_websocket_protocol(&self) -> &str {
+        "legacy-graphql"
+    }
+
+    fn decode(&self, message: &[u8]) -> serde_json::Result<ClientMessage> {
+        let mut message: serde_json::Value = serde_json::from_slice(message)?;
+        if let Some(object) = message.as_object_mut() {
+            if let Some(op) = object.remove("op") {
+                object.insert("type".to_string(), op);
+            }
+        }
+        serde_json::from_value(message)
+    }
+
+    fn encode(&self, event: ServerEvent<'_>) -> WsMessage {
+        let message = match event {
+            ServerEvent::ConnectionAck => serde_json::json!({ "op": "ready" }),
+            ServerEvent::Next { id, payload } => {
+                serde_json::json!({ "op": "result", "ref": id, "result": payload })
+            }
+            ServerEvent::Complete { id } => serde_json::json!({ "op": "done", "ref": id }),
+            ServerEvent::ConnectionError(err) => {
+                return WsMessage::Close(4403, err.message);
+            }
+            event => return WebSocketProtocols::GraphQLWS.encode(event),
+        };
+        WsMessage::Text(message.to_string())
+    }
+}
+
+#[tokio::test]
+pub async fn test_custom_protocol() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..3)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let protocols = WebSocketProtocolSet::new().register(LegacyProtocol);
+    assert_eq!(
+        protocols.sec_websocket_protocols().collect::<Vec<_>>(),
+        vec!["graphql-transport-ws", "graphql-ws", "legacy-graphql"]
+    );
+    let protocol = protocols.negotiate("Legacy-GraphQL, graphql-ws").unwrap();
+    assert_eq!(protocol.sec_websocket_protocol(), "legacy-graphql");
+
+    let messages = vec![
+        serde_json::json!({ "op": "connection_init" }).to_string(),
+        serde_json::json!({
+            "op": "start",
+            "id": "1",
+            "payload": { "query": "subscription { values }" },
+        })
+        .to_string(),
+    ];
+    let input = futures_util::stream::iter(messages).chain(futures_util::stream::pending());
+    let mut stream = http::WebSocket::new(schema, input, protocol);
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({ "op": "ready" }),
+    );
+
+    for i in 0..3 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "op": "result",
+                "ref": "1",
+                "result": { "data": { "values": i } },
+            }),
+        );
+    }
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({ "op": "done", "ref": "1" }),
+    );
+}
+
+#[test]
+pub fn test_negotiate_protocol() {
+    let protocols = WebSocketProtocolSet::new();
+    assert_eq!(
+        protocols
+            .negotiate("graphql-ws, graphql-transport-ws")
+            .unwrap()
+            .sec_websocket_protocol(),
+        "graphql-ws"
+    );
+    assert_eq!(
+        protocols
+            .negotiate("unknown,graphql-transport-ws")
+            .unwrap()
+            .sec_websocket_protocol(),
+        "graphql-transport-ws"
+    );
+    assert!(protocols.negotiate("unknown").is_none());
+}
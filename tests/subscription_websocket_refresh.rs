@@ -0,0 +1,264 @@
+use std::time::{Duration, SystemTime};
+
+use async_graphql::{
+    http::{ConnectionExpiry, WebSocketProtocols, WsMessage},
+    *,
+};
+use futures_channel::mpsc;
+use futures_util::stream::{Stream, StreamExt};
+
+struct Token(String);
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    async fn values(&self) -> impl Stream<Item = i32> {
+        futures_util::stream::iter(0..3)
+    }
+
+    async fn token(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+        futures_util::stream::once(futures_util::future::ready(
+            ctx.data_unchecked::<Token>().0.clone(),
+        ))
+    }
+
+    async fn origin(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+        futures_util::stream::once(futures_util::future::ready(
+            ctx.data_unchecked::<&'static str>().to_string(),
+        ))
+    }
+}
+
+fn next_message(message: WsMessage) -> serde_json::Value {
+    serde_json::from_str(&message.unwrap_text()).unwrap()
+}
+
+#[tokio::test]
+pub async fn test_connection_refresh() {
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .on_connection_refresh(|payload| async move {
+            if payload["token"] == "valid" {
+                Ok(Data::default())
+            } else {
+                Err("The token has expired.".into())
+            }
+        });
+
+    let send = |message: serde_json::Value| tx.unbounded_send(message.to_string()).unwrap();
+
+    send(serde_json::json!({ "type": "connection_init" }));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({ "type": "connection_ack" }),
+    );
+
+    send(serde_json::json!({
+        "type": "connection_refresh",
+        "payload": { "token": "valid" },
+    }));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({ "type": "connection_refresh_ack" }),
+    );
+
+    send(serde_json::json!({
+        "type": "subscribe",
+        "id": "1",
+        "payload": { "query": "subscription { values }" },
+    }));
+    for i in 0..3 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "next",
+                "id": "1",
+                "payload": { "data": { "values": i } },
+            }),
+        );
+    }
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({ "type": "complete", "id": "1" }),
+    );
+
+    send(serde_json::json!({
+        "type": "connection_refresh",
+        "payload": { "token": "expired" },
+    }));
+    assert_eq!(
+        stream.next().await.unwrap().unwrap_close(),
+        (1002, "The token has expired.".to_string())
+    );
+
+    send(serde_json::json!({
+        "type": "subscribe",
+        "id": "2",
+        "payload": { "query": "subscription { values }" },
+    }));
+    assert_eq!(
+        stream.next().await.unwrap().unwrap_close(),
+        (1011, "The handshake is not completed.".to_string())
+    );
+}
+
+#[tokio::test]
+pub async fn test_connection_refresh_not_supported() {
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS);
+
+    tx.unbounded_send(serde_json::json!({ "type": "connection_init" }).to_string())
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({ "type": "connection_ack" }),
+    );
+
+    tx.unbounded_send(serde_json::json!({ "type": "connection_refresh" }).to_string())
+        .unwrap();
+    assert_eq!(
+        stream.next().await.unwrap().unwrap_close(),
+        (4400, "Connection refresh is not supported.".to_string())
+    );
+}
+
+#[tokio::test]
+pub async fn test_connection_refresh_data() {
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .connection_data(Data::default().data("http"))
+        .on_connection_init(|payload| async move {
+            Ok(Data::default().data(Token(payload["token"].as_str().unwrap().to_string())))
+        })
+        .on_connection_refresh(|payload| async move {
+            Ok(Data::default().data(Token(payload["token"].as_str().unwrap().to_string())))
+        });
+
+    let send = |message: serde_json::Value| tx.unbounded_send(message.to_string()).unwrap();
+    let subscribe = |id: &str, query: &str| {
+        send(serde_json::json!({
+            "type": "subscribe",
+            "id": id,
+            "payload": { "query": query },
+        }))
+    };
+
+    send(serde_json::json!({
+        "type": "connection_init",
+        "payload": { "token": "first" },
+    }));
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({ "type": "connection_ack" }),
+    );
+
+    subscribe("1", "subscription { token }");
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({
+            "type": "next",
+            "id": "1",
+            "payload": { "data": { "token": "first" } },
+        }),
+    );
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({ "type": "complete", "id": "1" }),
+    );
+
+    send(serde_json::json!({
+        "type": "connection_refresh",
+        "payload": { "token": "second" },
+    }));
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({ "type": "connection_refresh_ack" }),
+    );
+
+    subscribe("2", "subscription { token }");
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({
+            "type": "next",
+            "id": "2",
+            "payload": { "data": { "token": "second" } },
+        }),
+    );
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({ "type": "complete", "id": "2" }),
+    );
+
+    // the connection data is kept after a refresh
+    subscribe("3", "subscription { origin }");
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({
+            "type": "next",
+            "id": "3",
+            "payload": { "data": { "origin": "http" } },
+        }),
+    );
+}
+
+#[tokio::test]
+pub async fn test_connection_expiry() {
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .on_connection_init(|_| async move {
+            Ok(Data::default().data(ConnectionExpiry(
+                SystemTime::now() + Duration::from_millis(100),
+            )))
+        })
+        .on_connection_refresh(|_| async move {
+            Ok(Data::default().data(ConnectionExpiry(
+                SystemTime::now() + Duration::from_millis(300),
+            )))
+        });
+
+    let send = |message: serde_json::Value| tx.unbounded_send(message.to_string()).unwrap();
+
+    send(serde_json::json!({ "type": "connection_init" }));
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({ "type": "connection_ack" }),
+    );
+
+    send(serde_json::json!({ "type": "connection_refresh" }));
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({ "type": "connection_refresh_ack" }),
+    );
+
+    // the refresh postpones the expiry
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    send(serde_json::json!({ "type": "ping" }));
+    assert_eq!(
+        next_message(stream.next().await.unwrap()),
+        serde_json::json!({ "type": "pong" }),
+    );
+
+    assert_eq!(
+        stream.next().await.unwrap().unwrap_close(),
+        (4403, "The connection has expired.".to_string())
+    );
+}
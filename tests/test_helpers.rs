@@ -0,0 +1,109 @@
+#![cfg(feature = "test-utils")]
+
+use std::time::Duration;
+
+use ::http::HeaderMap;
+use async_graphql::{test::TestRequest, *};
+use futures_util::stream::{self, Stream};
+
+#[tokio::test]
+pub async fn test_test_request() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn token<'a>(&self, ctx: &'a Context<'_>) -> Option<&'a str> {
+            ctx.data_opt::<HeaderMap>()
+                .and_then(|headers| headers.get("token"))
+                .and_then(|value| value.to_str().ok())
+        }
+
+        async fn value(&self, ctx: &Context<'_>) -> i32 {
+            *ctx.data_unchecked::<i32>()
+        }
+
+        async fn add(&self, a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        async fn forbidden(&self) -> Result<i32> {
+            Err(Error::new("forbidden").extend_with(|_, e| e.set("code", "FORBIDDEN")))
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let resp = TestRequest::new(&schema)
+        .query("query A { add(a: 1, b: 1) } query B($a: Int!) { add(a: $a, b: 2) token value }")
+        .operation_name("B")
+        .variables(serde_json::json!({ "a": 10 }))
+        .header("token", "abc")
+        .data(100i32)
+        .execute()
+        .await;
+    assert_data_eq!(resp, { "add": 12, "token": "abc", "value": 100 });
+
+    let resp = TestRequest::new(&schema)
+        .query("{ forbidden }")
+        .execute()
+        .await;
+    assert_error_code!(resp, "FORBIDDEN");
+}
+
+#[tokio::test]
+#[should_panic(expected = "unexpected errors")]
+pub async fn test_assert_data_eq_with_errors() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> Result<i32> {
+            Err("error".into())
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let resp = TestRequest::new(&schema).query("{ value }").execute().await;
+    assert_data_eq!(resp, { "value": null });
+}
+
+#[tokio::test]
+pub async fn test_test_subscription() {
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            stream::iter(vec![1, 2])
+        }
+
+        async fn pending(&self) -> impl Stream<Item = i32> {
+            stream::pending()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+
+    let mut stream = TestRequest::new(&schema)
+        .query("subscription { values }")
+        .execute_stream();
+    assert_eq!(stream.next_data().await, value!({ "values": 1 }));
+    assert_eq!(stream.next_data().await, value!({ "values": 2 }));
+    stream.assert_ended().await;
+
+    let mut stream = TestRequest::new(&schema)
+        .query("subscription { pending }")
+        .execute_stream()
+        .timeout(Duration::from_millis(10));
+    let res = tokio::spawn(async move { stream.next_response().await }).await;
+    assert!(res.unwrap_err().is_panic());
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+}
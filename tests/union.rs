@@ -483,3 +483,143 @@ pub async fn test_union_with_oneof_object() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_remote_union() {
+    mod remote {
+        pub struct Circle {
+            pub radius: f32,
+        }
+
+        pub struct Square {
+            pub width: f32,
+        }
+
+        pub enum Shape {
+            Circle(Circle),
+            Square(Square),
+        }
+    }
+
+    #[derive(SimpleObject)]
+    struct Circle {
+        radius: f32,
+    }
+
+    impl From<remote::Circle> for Circle {
+        fn from(circle: remote::Circle) -> Self {
+            Circle {
+                radius: circle.radius,
+            }
+        }
+    }
+
+    impl From<Circle> for remote::Circle {
+        fn from(circle: Circle) -> Self {
+            remote::Circle {
+                radius: circle.radius,
+            }
+        }
+    }
+
+    #[derive(SimpleObject)]
+    struct Square {
+        width: f32,
+    }
+
+    impl From<remote::Square> for Square {
+        fn from(square: remote::Square) -> Self {
+            Square {
+                width: square.width,
+            }
+        }
+    }
+
+    impl From<Square> for remote::Square {
+        fn from(square: Square) -> Self {
+            remote::Square {
+                width: square.width,
+            }
+        }
+    }
+
+    #[derive(Union)]
+    #[graphql(remote = "remote::Shape")]
+    enum Shape {
+        Circle(Circle),
+        Square(Square),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn shape(&self) -> Shape {
+            remote::Shape::Square(remote::Square { width: 2.0 }).into()
+        }
+    }
+
+    let shape: remote::Shape = Shape::Circle(Circle { radius: 1.0 }).into();
+    assert!(matches!(shape, remote::Shape::Circle(remote::Circle { radius }) if radius == 1.0));
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ shape { ... on Square { width } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "shape": { "width": 2.0 } })
+    );
+}
+
+#[tokio::test]
+pub async fn test_generic_remote_union() {
+    mod remote {
+        pub enum Either<L, R> {
+            Left(L),
+            Right(R),
+        }
+    }
+
+    #[derive(SimpleObject)]
+    struct Circle {
+        radius: f32,
+    }
+
+    #[derive(SimpleObject)]
+    struct Square {
+        width: f32,
+    }
+
+    #[derive(Union)]
+    #[graphql(remote = "remote::Either<Circle, Square>")]
+    enum Shape {
+        Left(Circle),
+        Right(Square),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn shape(&self) -> Shape {
+            remote::Either::<Circle, Square>::Right(Square { width: 2.0 }).into()
+        }
+    }
+
+    let shape: remote::Either<Circle, Square> = Shape::Left(Circle { radius: 1.0 }).into();
+    assert!(matches!(shape, remote::Either::Left(Circle { radius }) if radius == 1.0));
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ shape { ... on Square { width } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "shape": { "width": 2.0 } })
+    );
+}
@@ -73,7 +73,180 @@ pub async fn test_all_validator() {
         async fn list_email(&self, #[graphql(validator(list, email))] n: Vec<String>) -> i32 {
             todo!()
         }
+
+        async fn exclusive_maximum(
+            &self,
+            #[graphql(validator(exclusive_maximum = 10))] n: i32,
+        ) -> i32 {
+            todo!()
+        }
+
+        async fn exclusive_minimum(
+            &self,
+            #[graphql(validator(exclusive_minimum = 10))] n: i32,
+        ) -> i32 {
+            todo!()
+        }
+
+        async fn uuid(&self, #[graphql(validator(uuid))] n: String) -> i32 {
+            todo!()
+        }
+
+        async fn not_blank(&self, #[graphql(validator(not_blank))] n: String) -> i32 {
+            todo!()
+        }
+
+        async fn one_of(&self, #[graphql(validator(one_of("a", "b")))] n: String) -> i32 {
+            todo!()
+        }
+
+        #[cfg(feature = "chrono")]
+        async fn date_format(
+            &self,
+            #[graphql(validator(date_format = "%Y-%m-%d"))] n: String,
+        ) -> i32 {
+            todo!()
+        }
+    }
+}
+
+#[tokio::test]
+pub async fn test_builtin_validators() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn range(
+            &self,
+            #[graphql(validator(exclusive_minimum = 0.0, exclusive_maximum = 1.0))] n: f64,
+        ) -> f64 {
+            n
+        }
+
+        async fn status(
+            &self,
+            #[graphql(validator(one_of("draft", "published")))] status: String,
+        ) -> String {
+            status
+        }
+
+        async fn name(&self, #[graphql(validator(not_blank))] name: String) -> String {
+            name
+        }
+
+        async fn id(&self, #[graphql(validator(uuid))] id: String) -> String {
+            id
+        }
+
+        async fn code(
+            &self,
+            #[graphql(validator(list, regex = "^[A-Z]{3}$"))] codes: Vec<String>,
+        ) -> usize {
+            codes.len()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let errors = |query: &'static str| {
+        let schema = &schema;
+        async move {
+            schema
+                .execute(query)
+                .await
+                .into_result()
+                .unwrap_err()
+                .into_iter()
+                .map(|err| err.message)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    range(n: 0.5)
+                    status(status: "draft")
+                    name(name: " a ")
+                    id(id: "67e55044-10b1-426f-9247-bb680e5fe0c8")
+                    code(codes: ["ABC", "XYZ"])
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "range": 0.5,
+            "status": "draft",
+            "name": " a ",
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "code": 2,
+        })
+    );
+
+    assert_eq!(
+        errors("{ range(n: 1) }").await,
+        vec![r#"Failed to parse "Float": the value is 1, must be less than 1"#]
+    );
+    assert_eq!(
+        errors("{ range(n: 0) }").await,
+        vec![r#"Failed to parse "Float": the value is 0, must be greater than 0"#]
+    );
+    assert_eq!(
+        errors(r#"{ status(status: "deleted") }"#).await,
+        vec![
+            r#"Failed to parse "String": the value is "deleted", must be one of "draft", "published""#
+        ]
+    );
+    assert_eq!(
+        errors(r#"{ name(name: "  ") }"#).await,
+        vec![r#"Failed to parse "String": the string must not be blank"#]
+    );
+    assert_eq!(
+        errors(r#"{ id(id: "abc") }"#).await,
+        vec![r#"Failed to parse "String": invalid uuid"#]
+    );
+    assert_eq!(
+        errors(r#"{ code(codes: ["ABC", "abc"]) }"#).await,
+        vec![r#"Failed to parse "String": value doesn't match expected format '^[A-Z]{3}$'"#]
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+pub async fn test_date_format_validator() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn date(
+            &self,
+            #[graphql(validator(date_format = "%Y-%m-%d"))] date: String,
+        ) -> String {
+            date
+        }
     }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ date(date: "2022-01-31") }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "date": "2022-01-31" })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ date(date: "2022-02-30") }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Failed to parse "String": value doesn't match expected date format '%Y-%m-%d'"#
+    );
 }
 
 #[tokio::test]
@@ -166,7 +339,7 @@ pub async fn test_validator_on_input_object_field() {
             .into_result()
             .unwrap_err(),
         vec![ServerError {
-            message: r#"Failed to parse "Int": the value is 11, must be less than or equal to 10 (occurred while parsing "MyInput")"#
+            message: r#"Invalid value for argument "input.a", Failed to parse "Int": the value is 11, must be less than or equal to 10 (occurred while parsing "MyInput")"#
                 .to_string(),
             source: None,
             locations: vec![Pos {
@@ -174,7 +347,11 @@ pub async fn test_validator_on_input_object_field() {
                 column: 16
             }],
             path: vec![PathSegment::Field("value".to_string())],
-            extensions: None
+            extensions: Some({
+                let mut extensions = ErrorExtensionValues::default();
+                extensions.set("inputPath", value!(["input", "a"]));
+                extensions
+            })
         }]
     );
 
@@ -185,7 +362,7 @@ pub async fn test_validator_on_input_object_field() {
             .into_result()
             .unwrap_err(),
         vec![ServerError {
-            message: r#"Failed to parse "Int": the value is 20, must be less than or equal to 10 (occurred while parsing "MyInput")"#
+            message: r#"Invalid value for argument "input.b", Failed to parse "Int": the value is 20, must be less than or equal to 10 (occurred while parsing "MyInput")"#
                 .to_string(),
             source: None,
             locations: vec![Pos {
@@ -193,7 +370,11 @@ pub async fn test_validator_on_input_object_field() {
                 column: 16
             }],
             path: vec![PathSegment::Field("value".to_string())],
-            extensions: None
+            extensions: Some({
+                let mut extensions = ErrorExtensionValues::default();
+                extensions.set("inputPath", value!(["input", "b"]));
+                extensions
+            })
         }]
     );
 }
@@ -421,7 +602,7 @@ pub async fn test_custom_validator() {
             .unwrap_err(),
         vec![ServerError {
             message:
-                r#"Failed to parse "Int": expect 100, actual 11 (occurred while parsing "MyInput")"#
+                r#"Invalid value for argument "input.n", Failed to parse "Int": expect 100, actual 11 (occurred while parsing "MyInput")"#
                     .to_string(),
             source: None,
             locations: vec![Pos {
@@ -429,7 +610,11 @@ pub async fn test_custom_validator() {
                 column: 16
             }],
             path: vec![PathSegment::Field("input".to_string())],
-            extensions: None
+            extensions: Some({
+                let mut extensions = ErrorExtensionValues::default();
+                extensions.set("inputPath", value!(["input", "n"]));
+                extensions
+            })
         }]
     );
 
@@ -563,6 +748,114 @@ pub async fn test_custom_validator_with_fn() {
     );
 }
 
+#[tokio::test]
+pub async fn test_async_custom_validator() {
+    struct TakenUsernames(Vec<String>);
+
+    struct UsernameNotTaken;
+
+    #[async_trait::async_trait]
+    impl AsyncCustomValidator<String> for UsernameNotTaken {
+        async fn check(
+            &self,
+            ctx: &Context<'_>,
+            value: &String,
+        ) -> Result<(), InputValueError<String>> {
+            if ctx.data_unchecked::<TakenUsernames>().0.contains(value) {
+                Err(InputValueError::custom(format!(
+                    "username \"{}\" is already taken",
+                    value
+                )))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn register(
+            &self,
+            #[graphql(validator(async_custom = "UsernameNotTaken", max_length = 10))]
+            username: String,
+        ) -> String {
+            username
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(TakenUsernames(vec!["admin".to_string()]))
+        .finish();
+    assert_eq!(
+        schema
+            .execute(r#"{ register(username: "alice") }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "register": "alice" })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ register(username: "admin") }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Failed to parse "String": username "admin" is already taken"#.to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 22
+            }],
+            path: vec![PathSegment::Field("register".to_string())],
+            extensions: None
+        }]
+    );
+}
+
+#[tokio::test]
+pub async fn test_custom_validator_not_sync() {
+    struct NotSyncValidator(std::cell::Cell<i32>);
+
+    impl CustomValidator<i32> for NotSyncValidator {
+        fn check(&self, value: &i32) -> Result<(), InputValueError<i32>> {
+            if *value == self.0.get() {
+                Ok(())
+            } else {
+                Err(InputValueError::custom("invalid value"))
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(
+            &self,
+            #[graphql(validator(custom = "NotSyncValidator(std::cell::Cell::new(1))"))] n: i32,
+        ) -> i32 {
+            n
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ value(n: 1) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 1 })
+    );
+    assert!(schema.execute("{ value(n: 2) }").await.is_err());
+}
+
 #[tokio::test]
 pub async fn test_custom_validator_with_extensions() {
     struct MyValidator {
@@ -674,6 +967,131 @@ pub async fn test_list_validator() {
     );
 }
 
+#[tokio::test]
+pub async fn test_each_validator() {
+    #[derive(InputObject)]
+    struct Post {
+        #[graphql(validator(max_items = 3, each(min_length = 1, max_length = 5)))]
+        tags: Vec<String>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn tags(
+            &self,
+            #[graphql(validator(max_items = 3, each(min_length = 1, max_length = 5)))] tags: Vec<
+                String,
+            >,
+        ) -> usize {
+            tags.len()
+        }
+
+        async fn matrix(
+            &self,
+            #[graphql(validator(each(max_items = 2, each(maximum = 10))))] matrix: Vec<Vec<i32>>,
+        ) -> i32 {
+            matrix.into_iter().flatten().sum()
+        }
+
+        async fn post(&self, post: Post) -> usize {
+            post.tags.len()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ tags(tags: ["a", "bc", "def"]) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "tags": 3 })
+    );
+
+    let fields = |path: &str| {
+        let mut extensions = ErrorExtensionValues::default();
+        extensions.set("fields", value!([path]));
+        Some(extensions)
+    };
+
+    assert_eq!(
+        schema
+            .execute(r#"{ tags(tags: ["a", "", "def"]) }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Failed to parse "String": the string length is 0, must be greater than or equal to 1"#
+                .to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 14
+            }],
+            path: vec![PathSegment::Field("tags".to_string())],
+            extensions: fields("tags.1")
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ tags(tags: ["a", "b", "c", "d"]) }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Failed to parse "[String!]": the value length is 4, must be less than or equal to 3"#
+                .to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 14
+            }],
+            path: vec![PathSegment::Field("tags".to_string())],
+            extensions: None
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ matrix(matrix: [[1, 2], [3, 11]]) }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Failed to parse "Int": the value is 11, must be less than or equal to 10"#
+                .to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 18
+            }],
+            path: vec![PathSegment::Field("matrix".to_string())],
+            extensions: fields("matrix.1.1")
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ post(post: { tags: ["a", "toolong"] }) }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .extensions,
+        {
+            let mut extensions = fields("tags.1");
+            extensions
+                .as_mut()
+                .unwrap()
+                .set("inputPath", value!(["post", "tags"]));
+            extensions
+        }
+    );
+}
+
 #[tokio::test]
 pub async fn test_validate_wrapper_types() {
     #[derive(NewType)]
@@ -356,3 +356,141 @@ pub async fn test_variables_invalid_type_with_value() {
         Some("Unknown type \"invalid\"")
     );
 }
+
+#[tokio::test]
+pub async fn test_variables_input_limits() {
+    #[derive(InputObject)]
+    struct Filter {
+        name: Option<String>,
+        and: Option<Vec<Filter>>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn count(&self, filter: Filter) -> usize {
+            fn count(filter: &Filter) -> usize {
+                1 + filter.and.iter().flatten().map(count).sum::<usize>()
+            }
+            count(&filter)
+        }
+    }
+
+    fn nested(depth: usize) -> Value {
+        let mut value = value!({ "name": "a" });
+        for _ in 0..depth {
+            value = value!({ "and": [value] });
+        }
+        value
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_input_depth(6)
+        .limit_input_nodes(10)
+        .finish();
+    let query = "query($filter: Filter!) { count(filter: $filter) }";
+
+    // `{ and: [{ and: [{ name: "a" }] }] }` has a depth of 5 and 6 nodes
+    let resp = schema
+        .execute(
+            Request::new(query).variables(Variables::from_value(value!({ "filter": nested(2) }))),
+        )
+        .await;
+    assert_eq!(resp.data, value!({ "count": 3 }));
+
+    let resp = schema
+        .execute(
+            Request::new(query).variables(Variables::from_value(value!({ "filter": nested(3) }))),
+        )
+        .await;
+    assert_eq!(
+        resp.errors.first().map(|err| err.message.as_str()),
+        Some("The input value of variable \"$filter\" cannot be nested deeper than `6`")
+    );
+
+    let resp = schema
+        .execute(Request::new(query).variables(Variables::from_value(value!({
+            "filter": { "and": [
+                { "name": "a" }, { "name": "b" }, { "name": "c" }, { "name": "d" }, { "name": "e" },
+            ] },
+        }))))
+        .await;
+    assert_eq!(
+        resp.errors.first().map(|err| err.message.as_str()),
+        Some("The variables cannot contain more than `10` input values")
+    );
+
+    // No limits by default
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let resp = schema
+        .execute(
+            Request::new(query).variables(Variables::from_value(value!({ "filter": nested(10) }))),
+        )
+        .await;
+    assert_eq!(resp.data, value!({ "count": 11 }));
+}
+
+#[tokio::test]
+pub async fn test_variables_hook() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn items(&self, limit: i32, tenant: Option<String>) -> String {
+            format!("{}:{}", tenant.unwrap_or_default(), limit)
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .on_variables(|variables, _operation| {
+            if let Some(Value::Number(limit)) = variables.get_mut(&Name::new("limit")) {
+                if limit.as_i64().unwrap_or_default() > 10 {
+                    *limit = 10.into();
+                }
+            }
+            Ok(())
+        })
+        .on_variables(|variables, operation| {
+            if variables.contains_key(&Name::new("tenant")) {
+                return Err("The tenant cannot be overridden".into());
+            }
+            if operation
+                .variable_definitions
+                .iter()
+                .any(|def| def.node.name.node == "tenant")
+            {
+                variables.insert(Name::new("tenant"), Value::from("acme"));
+            }
+            Ok(())
+        })
+        .finish();
+    let query = "query($limit: Int!, $tenant: String) { items(limit: $limit, tenant: $tenant) }";
+
+    let resp = schema
+        .execute(Request::new(query).variables(Variables::from_value(value!({ "limit": 100 }))))
+        .await;
+    assert_eq!(resp.data, value!({ "items": "acme:10" }));
+
+    let resp = schema
+        .execute(Request::new(query).variables(Variables::from_value(value!({ "limit": 5 }))))
+        .await;
+    assert_eq!(resp.data, value!({ "items": "acme:5" }));
+
+    let resp = schema
+        .execute(Request::new(query).variables(Variables::from_value(value!({
+            "limit": 5,
+            "tenant": "other",
+        }))))
+        .await;
+    assert_eq!(
+        resp.errors,
+        vec![ServerError {
+            message: "The tenant cannot be overridden".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 1 }],
+            path: vec![],
+            extensions: None,
+        }]
+    );
+}
@@ -0,0 +1,266 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+#[graphql(visible("internal"))]
+struct Audit {
+    actor: String,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Status {
+    Active,
+    #[graphql(visible("partner", "internal"))]
+    Suspended,
+}
+
+#[derive(SimpleObject)]
+struct User {
+    name: String,
+    #[graphql(visible("partner", "internal"))]
+    email: String,
+    status: Status,
+}
+
+#[derive(InputObject)]
+struct UserFilter {
+    name: Option<String>,
+    #[graphql(visible("internal"))]
+    deleted: Option<bool>,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn user(&self, #[graphql(visible("internal"))] include_deleted: Option<bool>) -> User {
+        let _ = include_deleted;
+        User {
+            name: "abc".to_string(),
+            email: "abc@example.com".to_string(),
+            status: Status::Active,
+        }
+    }
+
+    async fn audit(&self) -> Option<Audit> {
+        None
+    }
+
+    async fn count(&self, status: Option<Status>, filter: Option<UserFilter>) -> i32 {
+        let _ = (status, filter);
+        1
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .visibility_profiles(["public", "partner", "internal"])
+        .finish()
+}
+
+fn request(query: &str, profile: &str) -> Request {
+    Request::new(query).data(VisibilityProfile::new(profile))
+}
+
+#[tokio::test]
+pub async fn test_visibility_profiles_introspection() {
+    let schema = schema();
+    let query = r#"{
+        user: __type(name: "User") { fields { name } }
+        status: __type(name: "Status") { enumValues { name } }
+        audit: __type(name: "Audit") { name }
+    }"#;
+
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "user": { "fields": [{ "name": "name" }, { "name": "status" }] },
+            "status": { "enumValues": [{ "name": "ACTIVE" }] },
+            "audit": null,
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(request(query, "partner"))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "user": { "fields": [{ "name": "name" }, { "name": "email" }, { "name": "status" }] },
+            "status": { "enumValues": [{ "name": "ACTIVE" }, { "name": "SUSPENDED" }] },
+            "audit": null,
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(request(query, "internal"))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "user": { "fields": [{ "name": "name" }, { "name": "email" }, { "name": "status" }] },
+            "status": { "enumValues": [{ "name": "ACTIVE" }, { "name": "SUSPENDED" }] },
+            "audit": { "name": "Audit" },
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_visibility_profiles_validation() {
+    let schema = schema();
+
+    assert_eq!(
+        schema
+            .execute("{ user { name email } }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Unknown field "email" on type "User"."#
+    );
+    assert_eq!(
+        schema
+            .execute(request("{ user { name email } }", "partner"))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": { "name": "abc", "email": "abc@example.com" } })
+    );
+
+    assert_eq!(
+        schema
+            .execute(request(
+                "{ user(includeDeleted: true) { name } }",
+                "partner"
+            ))
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Unknown argument "includeDeleted" on field "user" of type "Query"."#
+    );
+    assert!(schema
+        .execute(request(
+            "{ user(includeDeleted: true) { name } }",
+            "internal"
+        ))
+        .await
+        .is_ok());
+
+    assert!(schema
+        .execute(request("{ audit { ... on Audit { actor } } }", "partner"))
+        .await
+        .errors
+        .iter()
+        .any(|err| err.message == r#"Unknown type "Audit""#));
+    assert!(schema
+        .execute(request("{ audit { ... on Audit { actor } } }", "internal"))
+        .await
+        .is_ok());
+
+    assert_eq!(
+        schema
+            .execute(request("{ user { name } }", "admin"))
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Unknown visibility profile "admin""#
+    );
+}
+
+#[tokio::test]
+pub async fn test_visibility_profiles_validation_of_input_values() {
+    let schema = schema();
+
+    assert_eq!(
+        schema
+            .execute("{ count(status: SUSPENDED) }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "status", enumeration type "Status" does not contain the value "SUSPENDED""#
+    );
+    assert!(schema
+        .execute(request("{ count(status: SUSPENDED) }", "partner"))
+        .await
+        .is_ok());
+
+    assert_eq!(
+        schema
+            .execute(request("{ count(filter: { deleted: true }) }", "partner"))
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "filter", unknown field "deleted" of type "UserFilter""#
+    );
+    assert!(schema
+        .execute(request("{ count(filter: { deleted: true }) }", "internal"))
+        .await
+        .is_ok());
+
+    assert_eq!(
+        schema
+            .execute(
+                Request::new("query($status: Status) { count(status: $status) }").variables(
+                    Variables::from_json(serde_json::json!({ "status": "SUSPENDED" }))
+                )
+            )
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "status", enumeration type "Status" does not contain the value "SUSPENDED""#
+    );
+}
+
+#[tokio::test]
+pub async fn test_visibility_profiles_from_schema_data() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .visibility_profiles(["public", "partner", "internal"])
+        .data(VisibilityProfile::new("partner"))
+        .finish();
+
+    assert!(schema.execute("{ user { email } }").await.is_ok());
+    assert!(schema
+        .execute(request("{ user { email } }", "public"))
+        .await
+        .is_err());
+}
+
+#[test]
+pub fn test_visibility_profiles_sdl() {
+    let schema = schema();
+
+    let sdl = schema.sdl_for_audience(
+        SDLExportOptions::new(),
+        &SDLExportAudience::new().visibility_profile("public"),
+    );
+    assert!(!sdl.contains("email"));
+    assert!(!sdl.contains("SUSPENDED"));
+    assert!(!sdl.contains("Audit"));
+
+    let sdl = schema.sdl_for_audience(
+        SDLExportOptions::new(),
+        &SDLExportAudience::new().visibility_profile("partner"),
+    );
+    assert!(sdl.contains("email"));
+    assert!(sdl.contains("SUSPENDED"));
+    assert!(!sdl.contains("Audit"));
+
+    assert!(schema.sdl().contains("Audit"));
+}
+
+#[test]
+#[should_panic(expected = r#"Visibility profile "internal" is not declared"#)]
+pub fn test_undeclared_visibility_profile() {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .visibility_profiles(["public", "partner"])
+        .finish();
+}